@@ -0,0 +1,160 @@
+//! Anonymizer service module
+//!
+//! This service replaces sensitive job attributes (owner, run-as account,
+//! command line, node id) with stable tokens such as `OWNER_a1b2`, so
+//! analysis output can be shared outside the organization. Only the
+//! attribute values change: job names, conditions and resources are kept,
+//! so dependency structure and complexity scores are preserved.
+
+use std::collections::{BTreeMap, HashMap};
+use crate::domain::entities::{Folder, Job};
+use crate::domain::hash::fnv1a;
+
+/// Hex digits of the value hash used in a token; more are used on a collision
+const TOKEN_HASH_DIGITS: usize = 4;
+
+/// Service replacing sensitive attribute values with stable tokens
+///
+/// Within one Anonymizer the same value of the same attribute always gets
+/// the same token, and different values never share one.
+pub struct Anonymizer {
+    /// Token of each (prefix, original value)
+    tokens: HashMap<(&'static str, String), String>,
+    /// Original value of each token, for de-anonymization
+    originals: BTreeMap<String, String>,
+}
+
+impl Anonymizer {
+    /// Creates a new Anonymizer with no tokens assigned
+    pub fn new() -> Self {
+        Self {
+            tokens: HashMap::new(),
+            originals: BTreeMap::new(),
+        }
+    }
+
+    /// Anonymizes the jobs of folders and sub-folders in place
+    ///
+    /// Replaces the job `owner`, `run_as`, `cmdline` and `node_id` values,
+    /// the hosts of detected file transfers and the folder `owner`. Owners
+    /// of jobs and folders share tokens, as do node ids and transfer hosts.
+    ///
+    /// # Arguments
+    ///
+    /// * `folders` - Folders to anonymize
+    pub fn anonymize_folders(&mut self, folders: &mut [Folder]) {
+        for folder in folders {
+            self.replace(&mut folder.owner, "OWNER");
+            for job in &mut folder.jobs {
+                self.anonymize_job(job);
+            }
+            self.anonymize_folders(&mut folder.sub_folders);
+        }
+    }
+
+    /// Anonymizes the sensitive attributes of one job in place
+    ///
+    /// # Arguments
+    ///
+    /// * `job` - Job to anonymize
+    pub fn anonymize_job(&mut self, job: &mut Job) {
+        self.replace(&mut job.owner, "OWNER");
+        self.replace(&mut job.run_as, "RUN_AS");
+        self.replace(&mut job.cmdline, "CMDLINE");
+        self.replace(&mut job.node_id, "NODE");
+        for transfer in &mut job.file_transfers {
+            self.replace(&mut transfer.host, "NODE");
+        }
+    }
+
+    /// Gets the original value of every token assigned so far
+    ///
+    /// # Returns
+    ///
+    /// Original values keyed by token, sorted by token
+    pub fn mapping(&self) -> &BTreeMap<String, String> {
+        &self.originals
+    }
+
+    fn replace(&mut self, value: &mut Option<String>, prefix: &'static str) {
+        if let Some(original) = value.take() {
+            *value = Some(self.token(prefix, original));
+        }
+    }
+
+    /// Gets the token of a value, assigning one from its hash if needed
+    fn token(&mut self, prefix: &'static str, original: String) -> String {
+        let key = (prefix, original);
+        if let Some(token) = self.tokens.get(&key) {
+            return token.clone();
+        }
+
+        let hash = format!("{:016x}", fnv1a(&key.1));
+        let token = (TOKEN_HASH_DIGITS..=hash.len())
+            .map(|digits| format!("{}_{}", prefix, &hash[..digits]))
+            .chain((2..).map(|n| format!("{}_{}_{}", prefix, hash, n)))
+            .find(|token| !self.originals.contains_key(token))
+            .unwrap_or_default();
+        self.originals.insert(token.clone(), key.1.clone());
+        self.tokens.insert(key, token.clone());
+        token
+    }
+}
+
+impl Default for Anonymizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{Condition, FolderType};
+
+    #[test]
+    fn test_same_values_get_same_tokens_and_structure_is_kept() {
+        let mut folder = Folder::new("ETL".to_string(), FolderType::Simple);
+        folder.owner = Some("batchadm".to_string());
+        for name in ["EXTRACT", "LOAD"] {
+            let mut job = Job::new(name.to_string(), "ETL".to_string());
+            job.owner = Some("batchadm".to_string());
+            job.run_as = Some("svc_etl".to_string());
+            job.cmdline = Some(format!("/opt/etl/{}.sh --password=hunter2", name));
+            job.node_id = Some("db01.corp.internal".to_string());
+            job.in_conditions.push(Condition::new_in("SRC-READY".to_string()));
+            folder.add_job(job);
+        }
+
+        let mut anonymizer = Anonymizer::new();
+        anonymizer.anonymize_folders(std::slice::from_mut(&mut folder));
+
+        let (extract, load) = (&folder.jobs[0], &folder.jobs[1]);
+        let owner = extract.owner.clone().unwrap();
+        assert!(owner.starts_with("OWNER_") && owner.len() == "OWNER_".len() + TOKEN_HASH_DIGITS);
+        assert_eq!(load.owner.as_deref(), Some(owner.as_str()));
+        assert_eq!(folder.owner.as_deref(), Some(owner.as_str()));
+        assert_eq!(extract.node_id, load.node_id);
+        assert_ne!(extract.cmdline, load.cmdline);
+        assert!(!extract.cmdline.as_deref().unwrap().contains("hunter2"));
+        assert_eq!(extract.job_name, "EXTRACT");
+        assert_eq!(extract.in_conditions[0].name, "SRC-READY");
+
+        let mapping = anonymizer.mapping();
+        assert_eq!(mapping.len(), 5);
+        assert_eq!(mapping[&owner], "batchadm");
+    }
+
+    #[test]
+    fn test_tokens_are_deterministic_and_never_shared() {
+        let mut first = Anonymizer::new();
+        let mut second = Anonymizer::new();
+        assert_eq!(first.token("OWNER", "alice".to_string()), second.token("OWNER", "alice".to_string()));
+
+        let mut anonymizer = Anonymizer::new();
+        let tokens: std::collections::HashSet<_> = (0..2000)
+            .map(|i| anonymizer.token("NODE", format!("host{}", i)))
+            .collect();
+        assert_eq!(tokens.len(), 2000);
+    }
+}
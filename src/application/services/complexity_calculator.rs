@@ -3,30 +3,168 @@
 //! This service provides functionality to calculate complexity scores,
 //! migration difficulty, and migration priority for jobs.
 
-use crate::domain::entities::Job;
-use crate::domain::value_objects::{ComplexityScore, MigrationDifficulty, MigrationPriority};
+use std::collections::BTreeMap;
+use crate::domain::entities::{DoAction, Job};
+use crate::domain::value_objects::{
+    ComplexityScore, ComplexityWeights, MigrationDifficulty, MigrationPriority, MigrationWeight,
+    MANUAL_OVERRIDE_FACTOR, MIGRATION_WEIGHT_KEY,
+};
+
+pub use crate::domain::value_objects::complexity_weights::{
+    DEFAULT_MULTI_AGENT_WEIGHT, DEFAULT_RERUN_WEIGHT, DEFAULT_SCHEDULING_ENVIRONMENT_WEIGHT,
+};
 
 /// Service for calculating job complexity metrics
 ///
 /// The ComplexityCalculator analyzes various aspects of a job to determine
 /// its complexity score, migration difficulty, and migration priority.
-pub struct ComplexityCalculator;
+pub struct ComplexityCalculator {
+    /// Whether out-conditions (downstream jobs gated) count as dependencies
+    include_downstream: bool,
+    /// Points contributed by each complexity factor
+    weights: ComplexityWeights,
+}
 
 impl ComplexityCalculator {
     /// Creates a new ComplexityCalculator instance
     ///
     /// # Returns
     ///
-    /// A new ComplexityCalculator
+    /// A new ComplexityCalculator counting only upstream dependencies
     pub fn new() -> Self {
-        Self
+        Self {
+            include_downstream: false,
+            weights: ComplexityWeights::default(),
+        }
+    }
+
+    /// Sets the points each complexity factor contributes
+    ///
+    /// # Arguments
+    ///
+    /// * `weights` - Factor weights, e.g. loaded from a TOML file
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn with_weights(mut self, weights: ComplexityWeights) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    /// Gets the factor weights in use
+    pub fn weights(&self) -> &ComplexityWeights {
+        &self.weights
+    }
+
+    /// Sets whether out-conditions count toward the dependency factor
+    ///
+    /// By default only in-conditions and control resources are dependencies.
+    /// Enabling this also counts each out-condition, so jobs gating many
+    /// downstream jobs score as more complex.
+    pub fn with_downstream_dependencies(mut self, include_downstream: bool) -> Self {
+        self.include_downstream = include_downstream;
+        self
+    }
+
+    /// Sets the points added for multi-agent jobs
+    ///
+    /// Multi-agent jobs run on every agent of a host group, which Airflow
+    /// has to model as one task per host. Zero disables the factor.
+    pub fn with_multi_agent_weight(mut self, weight: u32) -> Self {
+        self.weights.multi_agent = weight;
+        self
+    }
+
+    /// Sets the points added for jobs pinned to a scheduling environment
+    ///
+    /// Zero disables the factor.
+    pub fn with_scheduling_environment_weight(mut self, weight: u32) -> Self {
+        self.weights.scheduling_environment = weight;
+        self
+    }
+
+    /// Scores the execution environment factors of a job
+    ///
+    /// This is the part of the complexity score contributed by distributed
+    /// execution (MULTY_AGENT=Y) and environment pinning (a non-empty
+    /// SCHEDULING_ENVIRONMENT).
+    ///
+    /// # Arguments
+    ///
+    /// * `job` - The job to analyze
+    ///
+    /// # Returns
+    ///
+    /// The configured weight of each factor present, summed
+    pub fn environment_complexity(&self, job: &Job) -> u32 {
+        let multi_agent = job.multy_agent.as_deref()
+            .is_some_and(|value| value.trim().eq_ignore_ascii_case("Y"));
+        let pinned = job.scheduling_environment.as_deref()
+            .is_some_and(|value| !value.trim().is_empty());
+
+        let mut score: u32 = 0;
+        if multi_agent {
+            score = score.saturating_add(self.weights.multi_agent);
+        }
+        if pinned {
+            score = score.saturating_add(self.weights.scheduling_environment);
+        }
+        score
+    }
+
+    /// Scores the automatic rerun behaviour of a job
+    ///
+    /// A job reruns itself when MAXRERUN is above zero or when one of its ON
+    /// conditions has a RERUN action. Airflow models this with task retries
+    /// and retry delays, which have to be set up per task.
+    ///
+    /// # Arguments
+    ///
+    /// * `job` - The job to analyze
+    ///
+    /// # Returns
+    ///
+    /// The configured rerun weight if the job reruns, otherwise 0
+    pub fn rerun_complexity(&self, job: &Job) -> u32 {
+        let max_rerun = job.maxrerun.or(job.scheduling.max_rerun).unwrap_or(0);
+        let rerun_action = job.on_conditions.iter()
+            .flat_map(|on_condition| &on_condition.actions)
+            .any(|action| matches!(action, DoAction::Action(name) if name.trim().eq_ignore_ascii_case("RERUN")));
+
+        if max_rerun > 0 || rerun_action {
+            self.weights.rerun
+        } else {
+            0
+        }
+    }
+
+    /// Counts a job's dependencies under the configured definition
+    ///
+    /// # Arguments
+    ///
+    /// * `job` - The job to analyze
+    ///
+    /// # Returns
+    ///
+    /// In-conditions plus control resources, plus out-conditions when
+    /// downstream dependencies are included
+    pub fn dependency_count(&self, job: &Job) -> usize {
+        if self.include_downstream {
+            job.dependency_count() + job.out_conditions.len()
+        } else {
+            job.dependency_count()
+        }
     }
 
     /// Calculates the complexity score for a job
     ///
     /// This method analyzes all aspects of a job including dependencies,
     /// conditions, resources, variables, and scheduling to produce a
-    /// comprehensive complexity score.
+    /// comprehensive complexity score. Execution environment and rerun
+    /// factors are added on top (see `environment_complexity` and
+    /// `rerun_complexity`). A valid
+    /// `migration_weight` metadata entry replaces or adjusts the result.
     ///
     /// # Arguments
     ///
@@ -36,8 +174,60 @@ impl ComplexityCalculator {
     ///
     /// A ComplexityScore representing the job's overall complexity
     pub fn calculate_job_complexity(&self, job: &Job) -> ComplexityScore {
+        ComplexityScore::new(total_points(&self.factor_scores(job)))
+    }
+
+    /// Breaks a job's complexity score down by factor
+    ///
+    /// Keys are the factors of the scoring methodology (`COMPLEXITY_FACTORS`),
+    /// including `environment` and `rerun`; the values sum to the complexity score. For
+    /// a job with a manual override the only key is `manual_override`.
+    ///
+    /// # Arguments
+    ///
+    /// * `job` - The job to analyze
+    ///
+    /// # Returns
+    ///
+    /// Points contributed by each factor
+    pub fn factor_breakdown(&self, job: &Job) -> BTreeMap<String, u32> {
+        self.factor_scores(job)
+            .into_iter()
+            .map(|(factor, points)| (factor.to_string(), points))
+            .collect()
+    }
+
+    /// Gets the manual complexity override of a job
+    ///
+    /// # Arguments
+    ///
+    /// * `job` - The job to analyze
+    ///
+    /// # Returns
+    ///
+    /// The weight from the job's `migration_weight` metadata, or None when
+    /// it is missing or not a number
+    pub fn migration_weight(&self, job: &Job) -> Option<MigrationWeight> {
+        job.metadata.get(MIGRATION_WEIGHT_KEY)
+            .and_then(|value| MigrationWeight::parse(value))
+    }
+
+    /// Scores every complexity factor of a job, applying any manual override
+    fn factor_scores(&self, job: &Job) -> Vec<(&'static str, u32)> {
+        let factors = self.heuristic_factor_scores(job);
+        match self.migration_weight(job) {
+            Some(weight) => {
+                let computed = total_points(&factors);
+                vec![(MANUAL_OVERRIDE_FACTOR, weight.apply(computed))]
+            }
+            None => factors,
+        }
+    }
+
+    /// Scores every complexity factor of a job from its structure
+    fn heuristic_factor_scores(&self, job: &Job) -> Vec<(&'static str, u32)> {
         // Gather all complexity metrics from the job
-        let dependency_count = job.dependency_count();
+        let dependency_count = self.dependency_count(job);
         let dependency_depth = self.estimate_dependency_depth(job);
         let in_conditions = job.in_conditions.len();
         let out_conditions = job.out_conditions.len();
@@ -52,7 +242,7 @@ impl ComplexityCalculator {
         let control_resources = job.control_resources.len();
         let scheduling_complexity = job.scheduling.complexity();
 
-        ComplexityScore::from_metrics(
+        let mut factors = self.weights.factor_scores(
             dependency_count,
             dependency_depth,
             in_conditions,
@@ -64,7 +254,10 @@ impl ComplexityCalculator {
             quantitative_resources,
             control_resources,
             scheduling_complexity,
-        )
+        );
+        factors.push(("environment", self.environment_complexity(job)));
+        factors.push(("rerun", self.rerun_complexity(job)));
+        factors
     }
 
     /// Calculates the migration difficulty for a job
@@ -88,7 +281,9 @@ impl ComplexityCalculator {
     ///
     /// Priority is based on complexity (easier jobs get higher priority),
     /// criticality (critical jobs get bonus priority), and dependencies
-    /// (fewer dependencies means higher priority).
+    /// (fewer dependencies means higher priority). A numeric Control-M job
+    /// priority adds a small bonus (+1 per 10), so higher-priority jobs
+    /// become earlier migration candidates.
     ///
     /// # Arguments
     ///
@@ -100,9 +295,11 @@ impl ComplexityCalculator {
     pub fn calculate_migration_priority(&self, job: &Job) -> MigrationPriority {
         let complexity = self.calculate_job_complexity(job);
         let is_critical = job.is_critical();
-        let dependency_count = job.dependency_count();
+        let dependency_count = self.dependency_count(job);
         
-        MigrationPriority::calculate(complexity, is_critical, dependency_count)
+        let priority = MigrationPriority::calculate(complexity, is_critical, dependency_count);
+        let job_priority_bonus = job.priority_value().map(|p| p.min(99) / 10).unwrap_or(0);
+        MigrationPriority::new(priority.value() + job_priority_bonus)
     }
 
     /// Estimates the dependency depth for a job
@@ -132,10 +329,15 @@ impl Default for ComplexityCalculator {
     }
 }
 
+/// Sums factor points, saturating instead of wrapping on large weights
+fn total_points(factors: &[(&'static str, u32)]) -> u32 {
+    factors.iter().fold(0, |total, (_, points)| total.saturating_add(*points))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::entities::Condition;
+    use crate::domain::entities::{Condition, OnCondition};
 
     #[test]
     fn test_calculate_simple_job_complexity() {
@@ -161,6 +363,94 @@ mod tests {
         assert!(complexity.value() > 0);
     }
 
+    #[test]
+    fn test_downstream_dependencies_raise_complexity_of_gating_job() {
+        let mut job = Job::new("GATE_JOB".to_string(), "FOLDER".to_string());
+        for i in 0..6 {
+            job.out_conditions.push(Condition::new_out(format!("GATE-OK-{}", i)));
+        }
+
+        let upstream_only = ComplexityCalculator::new();
+        let with_downstream = ComplexityCalculator::new().with_downstream_dependencies(true);
+
+        assert_eq!(upstream_only.dependency_count(&job), 0);
+        assert_eq!(with_downstream.dependency_count(&job), 6);
+        assert!(
+            with_downstream.calculate_job_complexity(&job).value()
+                > upstream_only.calculate_job_complexity(&job).value()
+        );
+    }
+
+    #[test]
+    fn test_multi_agent_job_scores_higher_than_single_agent() {
+        let mut single_agent = Job::new("SPREAD_JOB".to_string(), "FOLDER".to_string());
+        single_agent.in_conditions.push(Condition::new_in("COND1".to_string()));
+        single_agent.multy_agent = Some("N".to_string());
+        let mut multi_agent = single_agent.clone();
+        multi_agent.multy_agent = Some("Y".to_string());
+
+        let calculator = ComplexityCalculator::new();
+        assert_eq!(calculator.environment_complexity(&single_agent), 0);
+        assert_eq!(calculator.environment_complexity(&multi_agent), DEFAULT_MULTI_AGENT_WEIGHT);
+        assert_eq!(
+            calculator.calculate_job_complexity(&multi_agent).value(),
+            calculator.calculate_job_complexity(&single_agent).value() + DEFAULT_MULTI_AGENT_WEIGHT
+        );
+
+        multi_agent.scheduling_environment = Some("PROD_ENV".to_string());
+        let unweighted = ComplexityCalculator::new()
+            .with_multi_agent_weight(0)
+            .with_scheduling_environment_weight(2);
+        assert_eq!(unweighted.environment_complexity(&multi_agent), 2);
+    }
+
+    #[test]
+    fn test_rerun_jobs_score_once_per_job() {
+        let calculator = ComplexityCalculator::new();
+        let plain = Job::new("PLAIN".to_string(), "FOLDER".to_string());
+        assert_eq!(calculator.rerun_complexity(&plain), 0);
+
+        let mut max_rerun = plain.clone();
+        max_rerun.maxrerun = Some(3);
+        assert_eq!(calculator.rerun_complexity(&max_rerun), DEFAULT_RERUN_WEIGHT);
+
+        let mut on_rerun = plain.clone();
+        let mut on_condition = OnCondition::new();
+        on_condition.actions.push(DoAction::Action("RERUN".to_string()));
+        on_rerun.on_conditions.push(on_condition);
+        on_rerun.maxrerun = Some(2);
+        assert_eq!(calculator.factor_breakdown(&on_rerun)["rerun"], DEFAULT_RERUN_WEIGHT);
+
+        let mut no_rerun = plain;
+        no_rerun.maxrerun = Some(0);
+        assert_eq!(calculator.rerun_complexity(&no_rerun), 0);
+    }
+
+    #[test]
+    fn test_factor_breakdown_sums_to_complexity_score() {
+        let mut job = Job::new("BREAKDOWN_JOB".to_string(), "FOLDER".to_string());
+        job.in_conditions.push(Condition::new_in("COND1".to_string()));
+        job.out_conditions.push(Condition::new_out("COND2".to_string()));
+        job.cyclic = true;
+        job.scheduling.days_calendar = Some("WORKDAYS".to_string());
+        job.multy_agent = Some("Y".to_string());
+
+        let calculator = ComplexityCalculator::new();
+        let breakdown = calculator.factor_breakdown(&job);
+
+        let mut factors: Vec<&str> = breakdown.keys().map(String::as_str).collect();
+        factors.sort_unstable();
+        let mut expected = crate::domain::value_objects::COMPLEXITY_FACTORS.to_vec();
+        expected.sort_unstable();
+        assert_eq!(factors, expected);
+        assert_eq!(breakdown["dependencies"], 3);
+        assert_eq!(breakdown["conditions"], 4);
+        assert_eq!(breakdown["cyclic"], 15);
+        assert_eq!(breakdown["environment"], DEFAULT_MULTI_AGENT_WEIGHT);
+        assert!(breakdown["scheduling"] > 0);
+        assert_eq!(breakdown.values().sum::<u32>(), calculator.calculate_job_complexity(&job).value());
+    }
+
     #[test]
     fn test_calculate_migration_difficulty() {
         let calculator = ComplexityCalculator::new();
@@ -169,4 +459,25 @@ mod tests {
         let difficulty = calculator.calculate_migration_difficulty(&job);
         assert_eq!(difficulty, MigrationDifficulty::Easy);
     }
+
+    #[test]
+    fn test_migration_weight_metadata_overrides_score() {
+        let mut job = Job::new("KNOWN_HARD".to_string(), "FOLDER".to_string());
+        job.in_conditions.push(Condition::new_in("COND1".to_string()));
+        let calculator = ComplexityCalculator::new();
+        let computed = calculator.calculate_job_complexity(&job).value();
+
+        job.metadata.insert("migration_weight".to_string(), "85".to_string());
+        assert_eq!(calculator.calculate_job_complexity(&job).value(), 85);
+        assert_eq!(calculator.calculate_migration_difficulty(&job), MigrationDifficulty::Hard);
+        let breakdown = calculator.factor_breakdown(&job);
+        assert_eq!(breakdown.len(), 1);
+        assert_eq!(breakdown["manual_override"], 85);
+
+        job.metadata.insert("migration_weight".to_string(), "+10".to_string());
+        assert_eq!(calculator.calculate_job_complexity(&job).value(), computed + 10);
+
+        job.metadata.insert("migration_weight".to_string(), "unknown".to_string());
+        assert_eq!(calculator.calculate_job_complexity(&job).value(), computed);
+    }
 }
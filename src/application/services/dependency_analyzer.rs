@@ -5,10 +5,22 @@
 
 use std::collections::{HashMap, HashSet};
 use petgraph::graph::{DiGraph, NodeIndex};
-use petgraph::algo::toposort;
-use petgraph::visit::EdgeRef;
+use petgraph::algo::{tarjan_scc, toposort};
+use petgraph::algo::feedback_arc_set::greedy_feedback_arc_set;
+use petgraph::visit::{Bfs, EdgeRef, Reversed};
 use crate::domain::entities::{Job, Dependency, DependencyType};
 
+/// A dependency edge suggested for removal to break a cycle
+#[derive(Debug, Clone, PartialEq)]
+pub struct CycleBreak {
+    /// Job that sets the condition
+    pub from_job: String,
+    /// Job that waits for the condition
+    pub to_job: String,
+    /// Edge label (condition name for producer/consumer graphs)
+    pub condition: String,
+}
+
 /// Service for analyzing job dependencies
 ///
 /// DependencyAnalyzer builds a directed graph of job dependencies and provides
@@ -61,6 +73,78 @@ impl DependencyAnalyzer {
         }
     }
 
+    /// Builds a job-to-job dependency graph by matching conditions
+    ///
+    /// Unlike `build_graph`, edges connect the job that sets an output
+    /// condition to every job waiting for it as an input condition, so
+    /// cycles between jobs become visible. Edge labels are condition names.
+    /// Producers come from `producer_map`. Nodes are named by `job_key`, so
+    /// same-named jobs in different folders stay apart.
+    ///
+    /// # Arguments
+    ///
+    /// * `jobs` - Slice of job references to build the graph from
+    pub fn build_condition_graph(&mut self, jobs: &[&Job]) {
+        let keys: Vec<String> = jobs.iter()
+            .map(|job| Self::job_key(&job.folder_name, &job.job_name))
+            .collect();
+        for key in &keys {
+            self.add_job(key);
+        }
+
+        let producers = Self::producer_map(jobs);
+        for (consumer, job) in jobs.iter().enumerate() {
+            for in_cond in &job.in_conditions {
+                for &producer in producers.get(in_cond.name.as_str()).into_iter().flatten() {
+                    self.add_dependency(&keys[producer], &keys[consumer], &in_cond.name);
+                }
+            }
+        }
+    }
+
+    /// Gets the node name of a job in the graph built by `build_condition_graph`
+    ///
+    /// # Arguments
+    ///
+    /// * `folder_name` - Folder containing the job
+    /// * `job_name` - Name of the job
+    ///
+    /// # Returns
+    ///
+    /// The folder-qualified job name, `folder/job`
+    pub fn job_key(folder_name: &str, job_name: &str) -> String {
+        format!("{}/{}", folder_name, job_name)
+    }
+
+    /// Maps every out-condition name to the jobs that set it
+    ///
+    /// This is the one place conditions are matched to their producers; the
+    /// dependency graphs, run date resolution, cross-datacenter and
+    /// ambiguous producer reports, Airflow sensors and the exported
+    /// `job_dependencies` table all read it. Jobs deleting a condition
+    /// (SIGN "-") don't set it.
+    ///
+    /// # Arguments
+    ///
+    /// * `jobs` - Slice of job references to match
+    ///
+    /// # Returns
+    ///
+    /// Positions in `jobs` of the producers per condition name, each job
+    /// listed once, in job order
+    pub fn producer_map<'a>(jobs: &[&'a Job]) -> HashMap<&'a str, Vec<usize>> {
+        let mut producers: HashMap<&'a str, Vec<usize>> = HashMap::new();
+        for (position, job) in jobs.iter().enumerate() {
+            for out_cond in job.out_conditions.iter().filter(|c| !c.is_deletion()) {
+                let entry = producers.entry(out_cond.name.as_str()).or_default();
+                if entry.last() != Some(&position) {
+                    entry.push(position);
+                }
+            }
+        }
+        producers
+    }
+
     /// Adds a job to the dependency graph
     ///
     /// If the job already exists, this is a no-op.
@@ -203,6 +287,42 @@ impl DependencyAnalyzer {
         }
     }
 
+    /// Groups the nodes of the graph into topological levels
+    ///
+    /// Roots are level 0 and every other node is one level below its
+    /// deepest predecessor, so a node's level is always greater than the
+    /// levels of the nodes it depends on. Nodes in a cycle share a level.
+    ///
+    /// # Returns
+    ///
+    /// Map from node name to its topological level
+    pub fn topological_levels(&self) -> HashMap<String, usize> {
+        let sccs = tarjan_scc(&self.graph);
+        let mut scc_of: HashMap<NodeIndex, usize> = HashMap::new();
+        for (i, scc) in sccs.iter().enumerate() {
+            for &node in scc {
+                scc_of.insert(node, i);
+            }
+        }
+
+        // tarjan_scc yields components in reverse topological order
+        let mut scc_levels = vec![0usize; sccs.len()];
+        for (i, scc) in sccs.iter().enumerate().rev() {
+            let level = scc.iter()
+                .flat_map(|&node| self.graph.edges_directed(node, petgraph::Direction::Incoming))
+                .map(|edge| scc_of[&edge.source()])
+                .filter(|&source_scc| source_scc != i)
+                .map(|source_scc| scc_levels[source_scc] + 1)
+                .max()
+                .unwrap_or(0);
+            scc_levels[i] = level;
+        }
+
+        self.graph.node_indices()
+            .map(|idx| (self.graph[idx].clone(), scc_levels[scc_of[&idx]]))
+            .collect()
+    }
+
     /// Checks if the dependency graph contains circular dependencies
     ///
     /// # Returns
@@ -212,6 +332,77 @@ impl DependencyAnalyzer {
         self.topological_sort().is_err()
     }
 
+    /// Finds all dependency cycles in the graph
+    ///
+    /// Each cycle is reported as a strongly connected component with more
+    /// than one job, or a single job that depends on itself. Members are
+    /// listed in edge order: the walk starts at the alphabetically first
+    /// job and follows the edges within the component depth first, so a
+    /// cycle A → C → B → A is reported as `[A, C, B]`.
+    ///
+    /// # Returns
+    ///
+    /// Vector of cycles, each a list of job names
+    pub fn find_cycles(&self) -> Vec<Vec<String>> {
+        tarjan_scc(&self.graph)
+            .into_iter()
+            .filter(|scc| scc.len() > 1 || self.graph.contains_edge(scc[0], scc[0]))
+            .map(|scc| self.walk_component(&scc))
+            .collect()
+    }
+
+    /// Lists the nodes of a strongly connected component in edge order
+    ///
+    /// # Arguments
+    ///
+    /// * `component` - Nodes of one strongly connected component
+    ///
+    /// # Returns
+    ///
+    /// Node names in the order a depth-first walk along the edges reaches
+    /// them, starting at the alphabetically first node
+    fn walk_component(&self, component: &[NodeIndex]) -> Vec<String> {
+        let members: HashSet<NodeIndex> = component.iter().copied().collect();
+        let Some(&start) = component.iter().min_by_key(|&&idx| &self.graph[idx]) else {
+            return Vec::new();
+        };
+
+        let mut order = Vec::with_capacity(component.len());
+        let mut visited = HashSet::new();
+        let mut stack = vec![start];
+        while let Some(node) = stack.pop() {
+            if !visited.insert(node) {
+                continue;
+            }
+            order.push(self.graph[node].clone());
+            let mut next: Vec<NodeIndex> = self.graph.neighbors(node)
+                .filter(|n| members.contains(n) && !visited.contains(n))
+                .collect();
+            // Push in reverse name order so the alphabetically first successor is walked first
+            next.sort_by(|a, b| self.graph[*b].cmp(&self.graph[*a]));
+            stack.extend(next);
+        }
+        order
+    }
+
+    /// Suggests a small set of dependency edges whose removal breaks all cycles
+    ///
+    /// Uses a greedy approximation of the minimum feedback arc set, so the
+    /// result is not guaranteed to be minimal but always makes the graph acyclic.
+    ///
+    /// # Returns
+    ///
+    /// Vector of edges to review for removal
+    pub fn suggest_cycle_breaks(&self) -> Vec<CycleBreak> {
+        greedy_feedback_arc_set(&self.graph)
+            .map(|edge| CycleBreak {
+                from_job: self.graph[edge.source()].clone(),
+                to_job: self.graph[edge.target()].clone(),
+                condition: edge.weight().clone(),
+            })
+            .collect()
+    }
+
     /// Gets all upstream jobs (direct predecessors) for a job
     ///
     /// # Arguments
@@ -251,6 +442,55 @@ impl DependencyAnalyzer {
             Vec::new()
         }
     }
+
+    /// Counts the jobs a job is connected to end to end
+    ///
+    /// Includes every transitive predecessor and successor, each counted
+    /// once; the job itself is excluded even when it sits on a cycle.
+    ///
+    /// # Arguments
+    ///
+    /// * `job_name` - Name of the job
+    ///
+    /// # Returns
+    ///
+    /// Number of distinct upstream and downstream jobs, 0 for unknown jobs
+    pub fn count_e2e_dependencies(&self, job_name: &str) -> usize {
+        let job_idx = match self.job_indices.get(job_name) {
+            Some(&idx) => idx,
+            None => return 0,
+        };
+
+        let mut connected = HashSet::new();
+        let mut downstream = Bfs::new(&self.graph, job_idx);
+        while let Some(node) = downstream.next(&self.graph) {
+            connected.insert(node);
+        }
+        let reversed = Reversed(&self.graph);
+        let mut upstream = Bfs::new(reversed, job_idx);
+        while let Some(node) = upstream.next(reversed) {
+            connected.insert(node);
+        }
+        connected.remove(&job_idx);
+        connected.len()
+    }
+
+    /// Gets every edge in the graph as (from, to, label) triples
+    ///
+    /// # Returns
+    ///
+    /// Vector of (predecessor, successor, edge label), one entry per edge
+    pub fn get_edges(&self) -> Vec<(String, String, String)> {
+        self.graph
+            .edge_references()
+            .map(|edge| (
+                self.graph[edge.source()].clone(),
+                self.graph[edge.target()].clone(),
+                edge.weight().clone(),
+            ))
+            .collect()
+    }
+
 }
 
 impl Default for DependencyAnalyzer {
@@ -289,6 +529,23 @@ mod tests {
         assert_eq!(analyzer.get_dependency_depth("JOB_C"), 3);
     }
 
+    #[test]
+    fn test_e2e_dependencies_count_transitive_jobs_both_ways() {
+        let mut analyzer = DependencyAnalyzer::new();
+        for job in ["JOB_A", "JOB_B", "JOB_C", "JOB_D", "JOB_X"] {
+            analyzer.add_job(job);
+        }
+        analyzer.add_dependency("JOB_A", "JOB_B", "INCOND");
+        analyzer.add_dependency("JOB_B", "JOB_C", "INCOND");
+        analyzer.add_dependency("JOB_C", "JOB_D", "INCOND");
+        analyzer.add_dependency("JOB_D", "JOB_B", "INCOND");
+
+        assert_eq!(analyzer.count_e2e_dependencies("JOB_A"), 3);
+        assert_eq!(analyzer.count_e2e_dependencies("JOB_C"), 3);
+        assert_eq!(analyzer.count_e2e_dependencies("JOB_X"), 0);
+        assert_eq!(analyzer.count_e2e_dependencies("MISSING"), 0);
+    }
+
     #[test]
     fn test_topological_sort() {
         let mut analyzer = DependencyAnalyzer::new();
@@ -301,4 +558,114 @@ mod tests {
         let sorted = analyzer.topological_sort().unwrap();
         assert_eq!(sorted.len(), 3);
     }
+
+    #[test]
+    fn test_topological_levels_follow_longest_path_and_merge_cycles() {
+        let mut analyzer = DependencyAnalyzer::new();
+        for job in ["JOB_A", "JOB_B", "JOB_C", "JOB_D", "JOB_E"] {
+            analyzer.add_job(job);
+        }
+        analyzer.add_dependency("JOB_A", "JOB_B", "INCOND");
+        analyzer.add_dependency("JOB_B", "JOB_C", "INCOND");
+        analyzer.add_dependency("JOB_A", "JOB_C", "INCOND");
+        analyzer.add_dependency("JOB_C", "JOB_D", "INCOND");
+        analyzer.add_dependency("JOB_D", "JOB_C", "INCOND");
+        analyzer.add_dependency("JOB_D", "JOB_E", "INCOND");
+
+        let levels = analyzer.topological_levels();
+        assert_eq!(levels["JOB_A"], 0);
+        assert_eq!(levels["JOB_B"], 1);
+        assert_eq!(levels["JOB_C"], 2);
+        assert_eq!(levels["JOB_D"], 2);
+        assert_eq!(levels["JOB_E"], 3);
+    }
+
+    #[test]
+    fn test_suggested_cycle_breaks_make_graph_acyclic() {
+        let mut a = Job::new("JOB_A".to_string(), "F".to_string());
+        let mut b = Job::new("JOB_B".to_string(), "F".to_string());
+        let mut c = Job::new("JOB_C".to_string(), "F".to_string());
+        a.out_conditions.push(crate::domain::entities::Condition::new_out("A-OK".to_string()));
+        b.out_conditions.push(crate::domain::entities::Condition::new_out("B-OK".to_string()));
+        c.out_conditions.push(crate::domain::entities::Condition::new_out("C-OK".to_string()));
+        b.in_conditions.push(crate::domain::entities::Condition::new_in("A-OK".to_string()));
+        c.in_conditions.push(crate::domain::entities::Condition::new_in("B-OK".to_string()));
+        a.in_conditions.push(crate::domain::entities::Condition::new_in("C-OK".to_string()));
+
+        let mut analyzer = DependencyAnalyzer::new();
+        analyzer.build_condition_graph(&[&a, &b, &c]);
+        assert!(analyzer.has_circular_dependencies());
+        assert_eq!(analyzer.find_cycles(), vec![vec!["F/JOB_A", "F/JOB_B", "F/JOB_C"]]);
+
+        let breaks = analyzer.suggest_cycle_breaks();
+        assert!(!breaks.is_empty());
+
+        // Rebuild the graph without the suggested edges
+        let mut fixed = DependencyAnalyzer::new();
+        for (from, to, cond) in [("F/JOB_A", "F/JOB_B", "A-OK"), ("F/JOB_B", "F/JOB_C", "B-OK"), ("F/JOB_C", "F/JOB_A", "C-OK")] {
+            let is_removed = breaks.iter().any(|b| b.from_job == from && b.to_job == to && b.condition == cond);
+            if !is_removed {
+                fixed.add_dependency(from, to, cond);
+            }
+        }
+        assert!(!fixed.has_circular_dependencies());
+    }
+
+    #[test]
+    fn test_find_cycles_lists_jobs_in_edge_order() {
+        let mut analyzer = DependencyAnalyzer::new();
+        analyzer.add_dependency("JOB_A", "JOB_C", "A-OK");
+        analyzer.add_dependency("JOB_C", "JOB_B", "C-OK");
+        analyzer.add_dependency("JOB_B", "JOB_A", "B-OK");
+        analyzer.add_dependency("JOB_X", "JOB_X", "X-OK");
+        analyzer.add_dependency("JOB_B", "JOB_Y", "B-OK");
+
+        let mut cycles = analyzer.find_cycles();
+        cycles.sort();
+
+        assert_eq!(cycles, vec![vec!["JOB_A", "JOB_C", "JOB_B"], vec!["JOB_X"]]);
+    }
+
+    #[test]
+    fn test_condition_graph_keeps_same_named_jobs_in_different_folders_apart() {
+        use crate::domain::entities::Condition;
+
+        // Each folder has a LOAD waiting for its own EXTRACT; no job waits for
+        // anything set in the other folder, so there is no cycle
+        let mut extract_a = Job::new("EXTRACT".to_string(), "A".to_string());
+        extract_a.out_conditions.push(Condition::new_out("A-EXTRACTED".to_string()));
+        extract_a.in_conditions.push(Condition::new_in("B-LOADED".to_string()));
+        let mut load_a = Job::new("LOAD".to_string(), "A".to_string());
+        load_a.in_conditions.push(Condition::new_in("A-EXTRACTED".to_string()));
+        let mut load_b = Job::new("LOAD".to_string(), "B".to_string());
+        load_b.out_conditions.push(Condition::new_out("B-LOADED".to_string()));
+
+        let mut analyzer = DependencyAnalyzer::new();
+        analyzer.build_condition_graph(&[&extract_a, &load_a, &load_b]);
+
+        assert!(!analyzer.has_circular_dependencies());
+        assert_eq!(analyzer.get_upstream_jobs("A/LOAD"), vec!["A/EXTRACT".to_string()]);
+        assert_eq!(analyzer.get_upstream_jobs("A/EXTRACT"), vec!["B/LOAD".to_string()]);
+        assert!(analyzer.get_upstream_jobs("B/LOAD").is_empty());
+    }
+
+    #[test]
+    fn test_producer_map_lists_each_setting_job_once() {
+        use crate::domain::entities::Condition;
+
+        let mut extract = Job::new("EXTRACT".to_string(), "ETL".to_string());
+        extract.out_conditions.push(Condition::new_out("DATA-READY".to_string()));
+        extract.out_conditions.push(Condition::new_out("DATA-READY".to_string()));
+        let mut cleanup = Job::new("CLEANUP".to_string(), "ETL".to_string());
+        let mut deleted = Condition::new_out("DATA-READY".to_string());
+        deleted.sign = Some("-".to_string());
+        cleanup.out_conditions.push(deleted);
+        let mut rerun = Job::new("EXTRACT".to_string(), "RERUN".to_string());
+        rerun.out_conditions.push(Condition::new_out("DATA-READY".to_string()));
+
+        let producers = DependencyAnalyzer::producer_map(&[&extract, &cleanup, &rerun]);
+
+        assert_eq!(producers["DATA-READY"], vec![0, 2]);
+        assert_eq!(producers.len(), 1);
+    }
 }
@@ -1,5 +1,9 @@
+pub mod anonymizer;
 pub mod complexity_calculator;
 pub mod dependency_analyzer;
+pub mod operator_mapper;
 
-pub use complexity_calculator::ComplexityCalculator;
-pub use dependency_analyzer::DependencyAnalyzer;
+pub use anonymizer::Anonymizer;
+pub use complexity_calculator::{ComplexityCalculator, DEFAULT_MULTI_AGENT_WEIGHT, DEFAULT_SCHEDULING_ENVIRONMENT_WEIGHT};
+pub use dependency_analyzer::{CycleBreak, DependencyAnalyzer};
+pub use operator_mapper::{OperatorMapper, OperatorMapping};
@@ -0,0 +1,180 @@
+//! Operator mapper service module
+//!
+//! This module suggests the Airflow operator each Control-M job should
+//! become, restricted to the operators available in the migration target.
+//! When the natural operator's provider isn't installed the job falls back
+//! to a core operator and the mapping carries a note explaining why.
+
+use serde::{Deserialize, Serialize};
+use crate::domain::entities::Job;
+use crate::domain::value_objects::{AirflowVersion, ApplTypeAliases, ApplTypeCategory, MigrationTarget};
+
+/// Airflow operator suggested for a job
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OperatorMapping {
+    /// Operator class name, e.g. `BashOperator`
+    pub operator: String,
+    /// Fully qualified import path of the operator
+    pub import_path: String,
+    /// Provider package supplying the operator, None for core operators
+    pub provider: Option<String>,
+    /// Explanation when the mapping fell back to a different operator
+    pub note: Option<String>,
+    /// Whether the fallback is a migration risk: the preferred operator is
+    /// missing from a migration target the user configured
+    #[serde(default)]
+    pub fallback_risk: bool,
+}
+
+impl Default for OperatorMapping {
+    /// A BashOperator for Airflow 2.x
+    fn default() -> Self {
+        OperatorMapper::core_operator(AirflowVersion::V2, "BashOperator", "bash")
+    }
+}
+
+/// Service mapping jobs to Airflow operators for a migration target
+#[derive(Debug, Clone, Default)]
+pub struct OperatorMapper {
+    target: MigrationTarget,
+    appl_type_aliases: ApplTypeAliases,
+}
+
+impl OperatorMapper {
+    /// Creates a mapper for the given migration target
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - Airflow version and providers available after migration
+    pub fn new(target: MigrationTarget) -> Self {
+        Self {
+            target,
+            appl_type_aliases: ApplTypeAliases::default(),
+        }
+    }
+
+    /// Sets the migration target
+    pub fn with_target(mut self, target: MigrationTarget) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Sets the aliases used to categorize job application types
+    pub fn with_appl_type_aliases(mut self, appl_type_aliases: ApplTypeAliases) -> Self {
+        self.appl_type_aliases = appl_type_aliases;
+        self
+    }
+
+    /// Suggests an operator for a job
+    ///
+    /// # Arguments
+    ///
+    /// * `job` - The job to map
+    ///
+    /// # Returns
+    ///
+    /// The operator mapping, with a note if the preferred operator isn't
+    /// available in the target
+    pub fn map(&self, job: &Job) -> OperatorMapping {
+        let version = self.target.airflow_version;
+        if job.cyclic {
+            return Self::core_operator(version, "PythonOperator", "python");
+        }
+
+        match self.appl_type_aliases.categorize(job.appl_type.as_deref()) {
+            ApplTypeCategory::FileTransfer => {
+                self.provider_operator("sftp", "SFTPOperator", "airflow.providers.sftp.operators.sftp")
+            }
+            ApplTypeCategory::Database => self.provider_operator(
+                "common-sql",
+                "SQLExecuteQueryOperator",
+                "airflow.providers.common.sql.operators.sql",
+            ),
+            _ => Self::core_operator(version, "BashOperator", "bash"),
+        }
+    }
+
+    fn provider_operator(&self, provider: &str, operator: &str, module: &str) -> OperatorMapping {
+        if self.target.has_provider(provider) {
+            return OperatorMapping {
+                operator: operator.to_string(),
+                import_path: format!("{}.{}", module, operator),
+                provider: Some(provider.to_string()),
+                note: None,
+                fallback_risk: false,
+            };
+        }
+
+        let mut fallback = Self::core_operator(self.target.airflow_version, "BashOperator", "bash");
+        fallback.note = Some(if self.target.configured {
+            format!("{} requires the '{}' provider, which is not enabled in the migration target", operator, provider)
+        } else {
+            format!("{} requires the '{}' provider; no migration target is configured, so core operators are assumed", operator, provider)
+        });
+        fallback.fallback_risk = self.target.configured;
+        fallback
+    }
+
+    /// Maps a core (non-provider) operator to its import path for an Airflow version
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - Target Airflow major version
+    /// * `operator` - Operator class name, e.g. `BashOperator`
+    /// * `module` - Operator module name, e.g. `bash`
+    pub fn core_operator(version: AirflowVersion, operator: &str, module: &str) -> OperatorMapping {
+        let import_path = match version {
+            AirflowVersion::V2 => format!("airflow.operators.{}.{}", module, operator),
+            AirflowVersion::V3 => format!("airflow.providers.standard.operators.{}.{}", module, operator),
+        };
+        OperatorMapping {
+            operator: operator.to_string(),
+            import_path,
+            provider: None,
+            note: None,
+            fallback_risk: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sftp_job_requires_sftp_provider() {
+        let mut job = Job::new("TRANSFER".to_string(), "FOLDER".to_string());
+        job.appl_type = Some("FILE_TRANS".to_string());
+
+        let with_sftp = OperatorMapper::new(MigrationTarget::new().with_provider("sftp")).map(&job);
+        assert_eq!(with_sftp.operator, "SFTPOperator");
+        assert_eq!(with_sftp.provider.as_deref(), Some("sftp"));
+        assert!(with_sftp.note.is_none());
+
+        let without_sftp = OperatorMapper::new(MigrationTarget::new().with_provider("common-sql")).map(&job);
+        assert_eq!(without_sftp.operator, "BashOperator");
+        assert!(without_sftp.provider.is_none());
+        assert!(without_sftp.fallback_risk);
+        assert!(without_sftp.note.unwrap().contains("sftp"));
+    }
+
+    #[test]
+    fn test_default_target_fallback_is_not_a_risk() {
+        let mut job = Job::new("TRANSFER".to_string(), "FOLDER".to_string());
+        job.appl_type = Some("FILE_TRANS".to_string());
+
+        let mapping = OperatorMapper::default().map(&job);
+        assert_eq!(mapping.operator, "BashOperator");
+        assert!(!mapping.fallback_risk);
+        assert!(mapping.note.unwrap().contains("no migration target is configured"));
+    }
+
+    #[test]
+    fn test_core_operator_import_path_follows_airflow_version() {
+        let job = Job::new("SCRIPT".to_string(), "FOLDER".to_string());
+        let target = MigrationTarget::new().with_airflow_version(AirflowVersion::V3);
+
+        let mapping = OperatorMapper::new(target).map(&job);
+        assert_eq!(mapping.import_path, "airflow.providers.standard.operators.bash.BashOperator");
+    }
+}
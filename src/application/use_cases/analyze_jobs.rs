@@ -1,12 +1,37 @@
 use anyhow::Result;
-use crate::domain::entities::{Job, Folder};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use crate::domain::entities::{Job, Folder, ShoutDestinationTable};
+use crate::domain::value_objects::{
+    ApplTypeAliases, ComplexityWeights, MigrationReadiness, MigrationTarget, MigrationWeight, OrderDate, RunAlignment,
+    MIGRATION_WEIGHT_KEY,
+};
+use crate::application::services::DependencyAnalyzer;
 use crate::application::use_cases::{
-    CalculateComplexity, BuildDependencyGraph, DetermineMigrationWaves,
+    CalculateComplexity, BuildDependencyGraph, BuildFolderMatrix, DetermineMigrationWaves,
+    EstimateTimeline, FolderDependencyMatrix, MigrationTimeline,
 };
 
+/// Cyclic intervals at or below this many minutes are flagged as high-frequency
+pub const HIGH_FREQUENCY_INTERVAL_MINUTES: u32 = 15;
+
+/// Control-M system variables, which a job may use without defining them
+const SYSTEM_VARIABLES: &[&str] = &[
+    "ODATE", "OYEAR", "OMONTH", "ODAY", "OCENT", "OWDAY", "OJULDAY",
+    "DATE", "YEAR", "MONTH", "DAY", "CENT", "WDAY", "JULDAY",
+    "RDATE", "RYEAR", "RMONTH", "RDAY", "RJULDAY", "TIME", "TIMEID",
+    "JOBNAME", "MEMNAME", "ORDERID", "RUNCOUNT", "RN", "APPLIC", "APPLGROUP",
+    "GROUP", "OWNER", "NODEID", "DATACENTER", "BLANK", "SMART_ORDERID",
+];
+
 pub struct AnalyzeJobs {
     calculate_complexity: CalculateComplexity,
     determine_waves: DetermineMigrationWaves,
+    shout_tables: Vec<ShoutDestinationTable>,
+    reference_date: Option<NaiveDate>,
+    parallel: bool,
+    timeline: Option<EstimateTimeline>,
 }
 
 impl AnalyzeJobs {
@@ -14,29 +39,85 @@ impl AnalyzeJobs {
         Self {
             calculate_complexity: CalculateComplexity::new(),
             determine_waves: DetermineMigrationWaves::new(),
+            shout_tables: Vec::new(),
+            reference_date: None,
+            parallel: false,
+            timeline: None,
         }
     }
 
+    /// Sets the shout destination tables used to resolve symbolic shout destinations
+    pub fn with_shout_tables(mut self, shout_tables: Vec<ShoutDestinationTable>) -> Self {
+        self.shout_tables = shout_tables;
+        self
+    }
+
+    /// Sets the run date used to resolve each dependency's ODATE to a producer run date
+    pub fn with_reference_date(mut self, reference_date: NaiveDate) -> Self {
+        self.reference_date = Some(reference_date);
+        self
+    }
+
+    /// Sets the Airflow environment whose operators are suggested for each job
+    pub fn with_migration_target(mut self, target: MigrationTarget) -> Self {
+        self.calculate_complexity = self.calculate_complexity.with_migration_target(target);
+        self
+    }
+
+    /// Sets the aliases grouping raw APPL_TYPE values into categories
+    pub fn with_appl_type_aliases(mut self, appl_type_aliases: ApplTypeAliases) -> Self {
+        self.calculate_complexity = self.calculate_complexity.with_appl_type_aliases(appl_type_aliases);
+        self
+    }
+
+    /// Sets whether out-conditions count as dependencies in complexity scoring
+    pub fn with_downstream_dependencies(mut self, include_downstream: bool) -> Self {
+        self.calculate_complexity = self.calculate_complexity.with_downstream_dependencies(include_downstream);
+        self
+    }
+
+    /// Sets the points each complexity factor contributes
+    pub fn with_complexity_weights(mut self, weights: ComplexityWeights) -> Self {
+        self.calculate_complexity = self.calculate_complexity.with_weights(weights);
+        self
+    }
+
+    /// Estimates the migration timeline with the given team and start date
+    pub fn with_timeline(mut self, timeline: EstimateTimeline) -> Self {
+        self.timeline = Some(timeline);
+        self
+    }
+
+    /// Sets the number of migration waves jobs are spread over
+    pub fn with_wave_count(mut self, wave_count: usize) -> Self {
+        self.determine_waves = self.determine_waves.with_wave_count(wave_count);
+        self
+    }
+
+    /// Scores job complexity on multiple threads
+    pub fn with_parallel(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
     pub fn execute(&self, folders: &[Folder]) -> Result<AnalysisResult> {
         let all_jobs: Vec<&Job> = folders.iter()
             .flat_map(|f| f.all_jobs())
             .collect();
 
-        let mut complexity_results = self.calculate_complexity.execute_batch(&all_jobs);
+        let mut complexity_results = if self.parallel {
+            self.calculate_complexity.execute_batch_parallel(&all_jobs)
+        } else {
+            self.calculate_complexity.execute_batch(&all_jobs)
+        };
 
         let mut build_graph = BuildDependencyGraph::new();
         let graph_result = build_graph.execute(&all_jobs);
 
-        let migration_waves = self.determine_waves.execute(&complexity_results);
+        let folder_matrix = BuildFolderMatrix::new().execute(&all_jobs);
 
-        // Update each job with its wave number
-        for wave in &migration_waves {
-            for job_name in &wave.jobs {
-                if let Some(result) = complexity_results.iter_mut().find(|r| &r.job_name == job_name) {
-                    result.migration_wave = wave.wave;
-                }
-            }
-        }
+        // Also sets each job's wave number
+        let migration_waves = self.determine_waves.execute(&mut complexity_results, build_graph.get_analyzer());
 
         let total_jobs = all_jobs.len();
         let total_folders = folders.len();
@@ -48,6 +129,46 @@ impl AnalyzeJobs {
             0.0
         };
 
+        let resolved_shouts = self.resolve_shouts(&all_jobs);
+
+        let on_demand_jobs = all_jobs.iter()
+            .filter(|job| job.is_on_demand())
+            .map(|job| OnDemandJob {
+                job_name: job.job_name.clone(),
+                folder_name: job.folder_name.clone(),
+            })
+            .collect();
+
+        let producers = DependencyAnalyzer::producer_map(&all_jobs);
+        let dependency_dates = match self.reference_date {
+            Some(reference_date) => Self::resolve_dependency_dates(&all_jobs, &producers, reference_date),
+            None => Vec::new(),
+        };
+
+        let cross_datacenter_dependencies = Self::find_cross_datacenter_dependencies(folders, &all_jobs, &producers);
+        let ambiguous_producers = Self::find_ambiguous_producers(&all_jobs, &producers);
+        let orphaned_conditions = Self::find_orphaned_conditions(&all_jobs, &producers);
+
+        // Keyed by folder too, so same-named jobs in other folders don't share counts
+        let mut unresolved_upstream: HashMap<(&str, &str), usize> = HashMap::new();
+        for dependency in graph_result.dependencies.iter().filter(|d| !d.is_resolved()) {
+            let folder = dependency.to_folder.as_deref().unwrap_or_default();
+            *unresolved_upstream.entry((folder, dependency.to_job.as_str())).or_default() += 1;
+        }
+
+        // Complexity results are in job order
+        for (result, job) in complexity_results.iter_mut().zip(&all_jobs) {
+            result.migration_notes = Self::migration_notes(job, &cross_datacenter_dependencies);
+            result.migration_readiness = MigrationReadiness::assess(
+                unresolved_upstream.get(&(job.folder_name.as_str(), job.job_name.as_str())).copied().unwrap_or(0),
+                cross_datacenter_dependencies.iter().any(|d| d.is_consumer(job)),
+                job.cyclic,
+            );
+        }
+
+        let timeline = self.timeline.as_ref()
+            .map(|estimate| estimate.execute(&migration_waves, &complexity_results));
+
         Ok(AnalysisResult {
             total_jobs,
             total_folders,
@@ -55,8 +176,288 @@ impl AnalyzeJobs {
             complexity_results,
             migration_waves,
             has_circular_dependencies: graph_result.has_circular_dependencies,
+            circular_dependencies: graph_result.cycles,
+            resolved_shouts,
+            folder_matrix,
+            on_demand_jobs,
+            dependency_dates,
+            cross_datacenter_dependencies,
+            ambiguous_producers,
+            orphaned_conditions,
+            timeline,
         })
     }
+
+    /// Resolves every job shout destination against the shout destination tables
+    ///
+    /// Destinations not defined in any table are treated as inline recipients.
+    fn resolve_shouts(&self, jobs: &[&Job]) -> Vec<ResolvedShout> {
+        jobs.iter()
+            .flat_map(|job| job.shouts.iter().map(move |shout| (job, shout)))
+            .map(|(job, shout)| {
+                let resolved = self.shout_tables.iter()
+                    .find_map(|table| table.resolve(&shout.destination));
+                ResolvedShout {
+                    job_name: job.job_name.clone(),
+                    folder_name: job.folder_name.clone(),
+                    destination: shout.destination.clone(),
+                    resolved_destination: resolved.map(|s| s.to_string()),
+                    when: shout.when.clone(),
+                }
+            })
+            .collect()
+    }
+
+    /// Resolves the producer run date of every condition dependency
+    ///
+    /// Each in-condition is matched to the jobs setting it (`producers`, from
+    /// `DependencyAnalyzer::producer_map` over `jobs`), and its ODATE is
+    /// resolved against the consumer running on `reference_date`.
+    fn resolve_dependency_dates(
+        jobs: &[&Job],
+        producers: &HashMap<&str, Vec<usize>>,
+        reference_date: NaiveDate,
+    ) -> Vec<DependencyRunDate> {
+        let mut dates = Vec::new();
+        for job in jobs {
+            for in_cond in &job.in_conditions {
+                let producer_jobs = match producers.get(in_cond.name.as_str()) {
+                    Some(producer_jobs) => producer_jobs,
+                    None => continue,
+                };
+                let order_date = match OrderDate::parse(in_cond.odate.as_deref()) {
+                    Some(order_date) => order_date,
+                    None => continue,
+                };
+                let (alignment, offset_days) = order_date.alignment(reference_date);
+                let producer_run_date = order_date.resolve(reference_date)
+                    .map(|d| d.format("%Y-%m-%d").to_string());
+
+                for &producer in producer_jobs {
+                    dates.push(DependencyRunDate {
+                        producer_job: jobs[producer].job_name.clone(),
+                        consumer_job: job.job_name.clone(),
+                        condition: in_cond.name.clone(),
+                        odate: in_cond.odate.clone(),
+                        alignment,
+                        producer_run_date: producer_run_date.clone(),
+                        offset_days,
+                    });
+                }
+            }
+        }
+        dates
+    }
+
+    /// Collects every migration risk detected for a job
+    ///
+    /// Combines cyclic and high-frequency execution, cross-datacenter
+    /// dependencies, in-stream JCL, variables used in the command line but
+    /// never defined, on-demand jobs and manual complexity overrides, so a
+    /// reviewer sees them together.
+    ///
+    /// # Arguments
+    ///
+    /// * `job` - The job to annotate
+    /// * `cross_datacenter` - Cross-datacenter dependencies of the whole analysis
+    ///
+    /// # Returns
+    ///
+    /// One note per risk found, empty for a job without any
+    fn migration_notes(job: &Job, cross_datacenter: &[CrossDatacenterDependency]) -> Vec<String> {
+        let mut notes = Vec::new();
+
+        if job.cyclic {
+            match job.scheduling.cyclic_interval_minutes() {
+                Some(minutes) if minutes <= HIGH_FREQUENCY_INTERVAL_MINUTES => notes.push(format!(
+                    "High-frequency cyclic job (every {} min) - consider a sensor or streaming trigger instead of a DAG run per cycle",
+                    minutes
+                )),
+                _ => notes.push("Cyclic job - Airflow has no cyclic equivalent; use a schedule interval or a re-triggering DAG".to_string()),
+            }
+        }
+
+        for dependency in cross_datacenter {
+            if dependency.is_consumer(job) {
+                notes.push(format!(
+                    "Waits for {} in datacenter {} ({}) - needs a cross-environment sensor",
+                    dependency.producer_job, dependency.producer_datacenter, dependency.condition
+                ));
+            } else if dependency.is_producer(job) {
+                notes.push(format!(
+                    "Gates {} in datacenter {} ({}) - must signal across environments",
+                    dependency.consumer_job, dependency.consumer_datacenter, dependency.condition
+                ));
+            }
+        }
+
+        let uses_jcl = job.use_instream_jcl.as_deref().is_some_and(|v| v.eq_ignore_ascii_case("Y"))
+            || job.instream_jcl.as_deref().is_some_and(|jcl| !jcl.trim().is_empty());
+        if uses_jcl {
+            notes.push("Uses in-stream JCL - extract the JCL into a script before migration".to_string());
+        }
+
+        let undefined = Self::undefined_variables(job);
+        if !undefined.is_empty() {
+            notes.push(format!(
+                "Command line uses undefined variables: {} - define them as Airflow Variables or params",
+                undefined.join(", ")
+            ));
+        }
+
+        if job.is_on_demand() {
+            notes.push("No schedule and no dependencies - confirm whether it is manually triggered or obsolete".to_string());
+        }
+
+        if let Some(weight) = job.metadata.get(MIGRATION_WEIGHT_KEY) {
+            match MigrationWeight::parse(weight) {
+                Some(_) => notes.push(format!(
+                    "Complexity score manually overridden by migration_weight metadata ({})",
+                    weight.trim()
+                )),
+                None => notes.push(format!(
+                    "Ignored migration_weight metadata '{}' - expected a score such as 40, +15 or -10",
+                    weight
+                )),
+            }
+        }
+
+        notes
+    }
+
+    /// Finds `%%VAR` references in a job's command line with no definition
+    ///
+    /// Variables defined on the job (`VARIABLE` or auto-edit), Control-M system
+    /// variables and global (`%%\`) or pool (`%%@`) variables are not reported.
+    fn undefined_variables(job: &Job) -> Vec<String> {
+        let cmdline = match job.cmdline.as_deref() {
+            Some(cmdline) => cmdline,
+            None => return Vec::new(),
+        };
+        let defined = |name: &str| {
+            job.variables.keys().chain(job.auto_edits.keys())
+                .any(|key| key.trim_start_matches("%%") == name)
+        };
+
+        let mut undefined: Vec<String> = Vec::new();
+        for reference in cmdline.split("%%").skip(1) {
+            let name: String = reference.chars()
+                .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '$' | '#'))
+                .collect();
+            let system = SYSTEM_VARIABLES.contains(&name.trim_start_matches('$'));
+            if name.is_empty() || system || defined(&name) || undefined.contains(&name) {
+                continue;
+            }
+            undefined.push(name);
+        }
+        undefined
+    }
+
+    /// Finds in-conditions whose producing job runs in a different datacenter
+    ///
+    /// Jobs take the datacenter of their top-level folder. Jobs in folders
+    /// without a datacenter are ignored, since their side can't be known.
+    /// `jobs` lists the jobs of `folders` folder by folder, and `producers`
+    /// holds positions in it.
+    fn find_cross_datacenter_dependencies(
+        folders: &[Folder],
+        jobs: &[&Job],
+        producers: &HashMap<&str, Vec<usize>>,
+    ) -> Vec<CrossDatacenterDependency> {
+        let datacenters: Vec<Option<&str>> = folders.iter()
+            .flat_map(|folder| folder.all_jobs().into_iter().map(|_| folder.datacenter.as_deref()))
+            .collect();
+
+        let mut dependencies = Vec::new();
+        for (job, dc) in jobs.iter().zip(&datacenters) {
+            let Some(dc) = dc else {
+                continue;
+            };
+            for in_cond in &job.in_conditions {
+                for &producer in producers.get(in_cond.name.as_str()).into_iter().flatten() {
+                    let Some(producer_dc) = datacenters[producer].filter(|producer_dc| producer_dc != dc) else {
+                        continue;
+                    };
+                    dependencies.push(CrossDatacenterDependency {
+                        consumer_job: job.job_name.clone(),
+                        consumer_folder: job.folder_name.clone(),
+                        consumer_datacenter: dc.to_string(),
+                        producer_job: jobs[producer].job_name.clone(),
+                        producer_folder: jobs[producer].folder_name.clone(),
+                        producer_datacenter: producer_dc.to_string(),
+                        condition: in_cond.name.clone(),
+                    });
+                }
+            }
+        }
+        dependencies
+    }
+
+    /// Finds in-conditions that no job sets as an out-condition
+    ///
+    /// Such jobs would wait forever (a silent hang) unless the condition is
+    /// added by hand or by a job outside the analyzed export. A job waiting
+    /// on the same missing condition several times is reported once.
+    fn find_orphaned_conditions(
+        jobs: &[&Job],
+        producers: &HashMap<&str, Vec<usize>>,
+    ) -> Vec<OrphanedCondition> {
+        let mut orphaned: Vec<OrphanedCondition> = Vec::new();
+        let mut reported: HashSet<(&str, &str, &str)> = HashSet::new();
+        for job in jobs {
+            for in_cond in &job.in_conditions {
+                if producers.contains_key(in_cond.name.as_str()) {
+                    continue;
+                }
+                let key = (job.folder_name.as_str(), job.job_name.as_str(), in_cond.name.as_str());
+                if reported.insert(key) {
+                    orphaned.push(OrphanedCondition {
+                        job_name: job.job_name.clone(),
+                        folder_name: job.folder_name.clone(),
+                        condition_name: in_cond.name.clone(),
+                    });
+                }
+            }
+        }
+        orphaned
+    }
+
+    /// Finds out-conditions raised by more than one job
+    ///
+    /// A job raising the same condition several times counts once; jobs
+    /// deleting a condition don't raise it (both as in
+    /// `DependencyAnalyzer::producer_map`, which `producers` comes from).
+    /// Results are ordered by condition name.
+    fn find_ambiguous_producers(jobs: &[&Job], producers: &HashMap<&str, Vec<usize>>) -> Vec<AmbiguousProducer> {
+        let mut consumers: HashMap<&str, Vec<&str>> = HashMap::new();
+        for job in jobs {
+            for in_cond in &job.in_conditions {
+                let entry = consumers.entry(in_cond.name.as_str()).or_default();
+                if entry.last() != Some(&job.job_name.as_str()) {
+                    entry.push(&job.job_name);
+                }
+            }
+        }
+
+        let ambiguous: BTreeMap<&str, &Vec<usize>> = producers.iter()
+            .filter(|(_, positions)| positions.len() > 1)
+            .map(|(&condition, positions)| (condition, positions))
+            .collect();
+        ambiguous.into_iter()
+            .map(|(condition, positions)| AmbiguousProducer {
+                condition: condition.to_string(),
+                producers: positions.iter()
+                    .map(|&position| ConditionProducer {
+                        job_name: jobs[position].job_name.clone(),
+                        folder_name: jobs[position].folder_name.clone(),
+                    })
+                    .collect(),
+                consumer_jobs: consumers.get(condition)
+                    .map(|names| names.iter().map(|name| name.to_string()).collect())
+                    .unwrap_or_default(),
+            })
+            .collect()
+    }
 }
 
 impl Default for AnalyzeJobs {
@@ -73,12 +474,114 @@ pub struct AnalysisResult {
     pub complexity_results: Vec<super::calculate_complexity::JobComplexityResult>,
     pub migration_waves: Vec<super::determine_migration_waves::MigrationWave>,
     pub has_circular_dependencies: bool,
+    pub circular_dependencies: Vec<Vec<String>>,
+    pub resolved_shouts: Vec<ResolvedShout>,
+    pub folder_matrix: FolderDependencyMatrix,
+    pub on_demand_jobs: Vec<OnDemandJob>,
+    pub dependency_dates: Vec<DependencyRunDate>,
+    pub cross_datacenter_dependencies: Vec<CrossDatacenterDependency>,
+    pub ambiguous_producers: Vec<AmbiguousProducer>,
+    pub orphaned_conditions: Vec<OrphanedCondition>,
+    pub timeline: Option<MigrationTimeline>,
+}
+
+/// A job shout with its destination resolved through the shout destination tables
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolvedShout {
+    pub job_name: String,
+    pub folder_name: String,
+    /// Destination as written in the job definition
+    pub destination: String,
+    /// Physical recipient, or None when the destination is inline or undefined
+    pub resolved_destination: Option<String>,
+    pub when: Option<String>,
+}
+
+/// A job with no scheduling and no dependencies (manually triggered or obsolete)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OnDemandJob {
+    pub job_name: String,
+    pub folder_name: String,
+}
+
+/// A condition dependency with its producer run resolved against a reference date
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyRunDate {
+    pub producer_job: String,
+    pub consumer_job: String,
+    pub condition: String,
+    /// ODATE as written on the in-condition
+    pub odate: Option<String>,
+    pub alignment: RunAlignment,
+    /// Producer run date (YYYY-MM-DD), or None when any run is accepted
+    pub producer_run_date: Option<String>,
+    /// Producer offset in days from the consumer run, usable as an
+    /// Airflow ExternalTaskSensor execution_delta
+    pub offset_days: Option<i64>,
+}
+
+/// A condition dependency whose producer runs in another datacenter
+///
+/// These are cross-DC integration points: the dependency crosses a network
+/// and security boundary and needs a dedicated migration plan.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrossDatacenterDependency {
+    pub consumer_job: String,
+    /// Folder of the consuming job
+    #[serde(default)]
+    pub consumer_folder: String,
+    pub consumer_datacenter: String,
+    pub producer_job: String,
+    /// Folder of the producing job
+    #[serde(default)]
+    pub producer_folder: String,
+    pub producer_datacenter: String,
+    pub condition: String,
+}
+
+impl CrossDatacenterDependency {
+    /// Checks whether `job` is the consuming job, matching folder and name
+    pub fn is_consumer(&self, job: &Job) -> bool {
+        self.consumer_job == job.job_name && self.consumer_folder == job.folder_name
+    }
+
+    /// Checks whether `job` is the producing job, matching folder and name
+    pub fn is_producer(&self, job: &Job) -> bool {
+        self.producer_job == job.job_name && self.producer_folder == job.folder_name
+    }
+}
+
+/// An out-condition raised by more than one job (ambiguous producers)
+///
+/// Consumers waiting on the condition can't tell which producer they
+/// actually depend on, so the migrated dependency must be decided by hand.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AmbiguousProducer {
+    pub condition: String,
+    pub producers: Vec<ConditionProducer>,
+    /// Jobs waiting on the condition
+    pub consumer_jobs: Vec<String>,
+}
+
+/// A job raising a condition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConditionProducer {
+    pub job_name: String,
+    pub folder_name: String,
+}
+
+/// An in-condition no job in the analysis sets (the job would never start)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OrphanedCondition {
+    pub job_name: String,
+    pub folder_name: String,
+    pub condition_name: String,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::entities::FolderType;
+    use crate::domain::entities::{FolderType, Shout};
 
     #[test]
     fn test_analyze_empty_folders() {
@@ -90,6 +593,122 @@ mod tests {
         assert_eq!(result.total_folders, 0);
     }
 
+    #[test]
+    fn test_migration_notes_combine_cycle_and_jcl() {
+        let mut job = Job::new("JCL_LOOP".to_string(), "TEST_FOLDER".to_string());
+        job.cyclic = true;
+        job.scheduling.cyclic_interval = Some("00060M".to_string());
+        job.use_instream_jcl = Some("Y".to_string());
+        job.cmdline = Some("run.sh %%ODATE %%TARGET".to_string());
+        let mut folder = Folder::new("TEST_FOLDER".to_string(), FolderType::Simple);
+        folder.add_job(job);
+        folder.add_job(Job::new("PLAIN".to_string(), "TEST_FOLDER".to_string()));
+
+        let result = AnalyzeJobs::new().execute(&[folder]).unwrap();
+        let notes = &result.complexity_results.iter().find(|r| r.job_name == "JCL_LOOP").unwrap().migration_notes;
+        assert!(notes.iter().any(|n| n.starts_with("Cyclic job")));
+        assert!(notes.iter().any(|n| n.contains("in-stream JCL")));
+        assert!(notes.iter().any(|n| n.ends_with("variables: TARGET - define them as Airflow Variables or params")));
+        assert_eq!(notes.len(), 3);
+    }
+
+    #[test]
+    fn test_migration_readiness_per_job() {
+        use crate::domain::entities::Condition;
+
+        let mut extract = Job::new("EXTRACT".to_string(), "TEST_FOLDER".to_string());
+        extract.out_conditions.push(Condition::new_out("DATA-READY".to_string()));
+        let mut load = Job::new("LOAD".to_string(), "TEST_FOLDER".to_string());
+        load.in_conditions.push(Condition::new_in("DATA-READY".to_string()));
+        let mut report = Job::new("REPORT".to_string(), "TEST_FOLDER".to_string());
+        report.in_conditions.push(Condition::new_in("UPSTREAM-NOT-IN-EXPORT".to_string()));
+        let mut poller = Job::new("POLLER".to_string(), "TEST_FOLDER".to_string());
+        poller.cyclic = true;
+        let mut folder = Folder::new("TEST_FOLDER".to_string(), FolderType::Simple);
+        for job in [extract, load, report, poller] {
+            folder.add_job(job);
+        }
+
+        let result = AnalyzeJobs::new().execute(&[folder]).unwrap();
+        let readiness = |name: &str| result.complexity_results.iter()
+            .find(|r| r.job_name == name).unwrap().migration_readiness;
+        assert_eq!(readiness("EXTRACT"), MigrationReadiness::Ready);
+        assert_eq!(readiness("LOAD"), MigrationReadiness::Ready);
+        assert_eq!(readiness("REPORT"), MigrationReadiness::Blocked);
+        assert_eq!(readiness("POLLER"), MigrationReadiness::NeedsReview);
+    }
+
+    #[test]
+    fn test_migration_readiness_keeps_same_named_jobs_apart() {
+        use crate::domain::entities::Condition;
+
+        let mut blocked = Job::new("REPORT".to_string(), "FINANCE".to_string());
+        blocked.in_conditions.push(Condition::new_in("UPSTREAM-NOT-IN-EXPORT".to_string()));
+        let mut finance = Folder::new("FINANCE".to_string(), FolderType::Simple);
+        finance.add_job(blocked);
+        let mut sales = Folder::new("SALES".to_string(), FolderType::Simple);
+        sales.add_job(Job::new("REPORT".to_string(), "SALES".to_string()));
+
+        let result = AnalyzeJobs::new().execute(&[finance, sales]).unwrap();
+        let readiness = |folder: &str| result.complexity_results.iter()
+            .find(|r| r.folder_name == folder).unwrap().migration_readiness;
+        assert_eq!(readiness("FINANCE"), MigrationReadiness::Blocked);
+        assert_eq!(readiness("SALES"), MigrationReadiness::Ready);
+    }
+
+    #[test]
+    fn test_cross_datacenter_readiness_keeps_same_named_jobs_apart() {
+        use crate::domain::entities::Condition;
+
+        let mut producer = Job::new("PRODUCER".to_string(), "FOLDER_DC1".to_string());
+        producer.out_conditions.push(Condition::new_out("PRODUCER-OK".to_string()));
+        let mut remote = Job::new("REPORT".to_string(), "FOLDER_DC2".to_string());
+        remote.in_conditions.push(Condition::new_in("PRODUCER-OK".to_string()));
+
+        let mut dc1 = Folder::new("FOLDER_DC1".to_string(), FolderType::Simple);
+        dc1.datacenter = Some("DC1".to_string());
+        dc1.add_job(producer);
+        let mut dc2 = Folder::new("FOLDER_DC2".to_string(), FolderType::Simple);
+        dc2.datacenter = Some("DC2".to_string());
+        dc2.add_job(remote);
+        let mut local = Folder::new("LOCAL".to_string(), FolderType::Simple);
+        local.datacenter = Some("DC2".to_string());
+        local.add_job(Job::new("REPORT".to_string(), "LOCAL".to_string()));
+
+        let result = AnalyzeJobs::new().execute(&[dc1, dc2, local]).unwrap();
+        let readiness = |folder: &str| result.complexity_results.iter()
+            .find(|r| r.folder_name == folder && r.job_name == "REPORT").unwrap().migration_readiness;
+        assert_eq!(readiness("FOLDER_DC2"), MigrationReadiness::NeedsReview);
+        assert_eq!(readiness("LOCAL"), MigrationReadiness::Ready);
+    }
+
+    #[test]
+    fn test_cross_datacenter_notes_keep_same_named_jobs_apart() {
+        use crate::domain::entities::Condition;
+
+        let mut producer = Job::new("EXTRACT".to_string(), "FOLDER_DC1".to_string());
+        producer.out_conditions.push(Condition::new_out("EXTRACT-OK".to_string()));
+        let mut consumer = Job::new("LOAD".to_string(), "FOLDER_DC2".to_string());
+        consumer.in_conditions.push(Condition::new_in("EXTRACT-OK".to_string()));
+
+        let mut dc1 = Folder::new("FOLDER_DC1".to_string(), FolderType::Simple);
+        dc1.datacenter = Some("DC1".to_string());
+        dc1.add_job(producer);
+        dc1.add_job(Job::new("LOAD".to_string(), "FOLDER_DC1".to_string()));
+        let mut dc2 = Folder::new("FOLDER_DC2".to_string(), FolderType::Simple);
+        dc2.datacenter = Some("DC2".to_string());
+        dc2.add_job(consumer);
+        dc2.add_job(Job::new("EXTRACT".to_string(), "FOLDER_DC2".to_string()));
+
+        let result = AnalyzeJobs::new().execute(&[dc1, dc2]).unwrap();
+        let notes = |folder: &str, job: &str| result.complexity_results.iter()
+            .find(|r| r.folder_name == folder && r.job_name == job).unwrap().migration_notes.clone();
+        assert!(notes("FOLDER_DC2", "LOAD").iter().any(|n| n.starts_with("Waits for EXTRACT")));
+        assert!(notes("FOLDER_DC1", "EXTRACT").iter().any(|n| n.starts_with("Gates LOAD")));
+        assert!(!notes("FOLDER_DC1", "LOAD").iter().any(|n| n.contains("datacenter")));
+        assert!(!notes("FOLDER_DC2", "EXTRACT").iter().any(|n| n.contains("datacenter")));
+    }
+
     #[test]
     fn test_analyze_folders_with_jobs() {
         let use_case = AnalyzeJobs::new();
@@ -107,4 +726,186 @@ mod tests {
         assert_eq!(result.total_folders, 1);
         assert_eq!(result.complexity_results.len(), 2);
     }
+
+    #[test]
+    fn test_resolve_symbolic_shout_destination() {
+        let mut table = ShoutDestinationTable::new("SHOUTDEST".to_string());
+        table.add_destination("OPS".to_string(), "ops-team@example.com".to_string());
+        let use_case = AnalyzeJobs::new().with_shout_tables(vec![table]);
+
+        let mut folder = Folder::new("TEST_FOLDER".to_string(), FolderType::Simple);
+        let mut job = Job::new("JOB1".to_string(), "TEST_FOLDER".to_string());
+        job.shouts.push(Shout::new("OPS".to_string()));
+        job.shouts.push(Shout::new("someone@example.com".to_string()));
+        folder.add_job(job);
+
+        let result = use_case.execute(&[folder]).unwrap();
+
+        assert_eq!(result.resolved_shouts.len(), 2);
+        assert_eq!(result.resolved_shouts[0].resolved_destination.as_deref(), Some("ops-team@example.com"));
+        assert_eq!(result.resolved_shouts[1].resolved_destination, None);
+    }
+
+    #[test]
+    fn test_unscheduled_job_without_dependencies_is_on_demand() {
+        let use_case = AnalyzeJobs::new();
+
+        let mut folder = Folder::new("TEST_FOLDER".to_string(), FolderType::Simple);
+        folder.add_job(Job::new("MANUAL_JOB".to_string(), "TEST_FOLDER".to_string()));
+        let mut scheduled = Job::new("DAILY_JOB".to_string(), "TEST_FOLDER".to_string());
+        scheduled.scheduling.days_calendar = Some("WORKDAYS".to_string());
+        folder.add_job(scheduled);
+
+        let result = use_case.execute(&[folder]).unwrap();
+
+        assert_eq!(result.on_demand_jobs.len(), 1);
+        assert_eq!(result.on_demand_jobs[0].job_name, "MANUAL_JOB");
+    }
+
+    #[test]
+    fn test_resolve_dependency_dates_against_reference_date() {
+        use crate::domain::entities::Condition;
+
+        let mut folder = Folder::new("TEST_FOLDER".to_string(), FolderType::Simple);
+        let mut producer = Job::new("PRODUCER".to_string(), "TEST_FOLDER".to_string());
+        producer.out_conditions.push(Condition::new_out("PRODUCER-OK".to_string()));
+        let mut same_day = Job::new("SAME_DAY".to_string(), "TEST_FOLDER".to_string());
+        let mut cond = Condition::new_in("PRODUCER-OK".to_string());
+        cond.odate = Some("ODAT".to_string());
+        same_day.in_conditions.push(cond);
+        let mut prior_day = Job::new("PRIOR_DAY".to_string(), "TEST_FOLDER".to_string());
+        let mut cond = Condition::new_in("PRODUCER-OK".to_string());
+        cond.odate = Some("PREV".to_string());
+        prior_day.in_conditions.push(cond);
+        folder.add_job(producer);
+        folder.add_job(same_day);
+        folder.add_job(prior_day);
+
+        let reference = NaiveDate::from_ymd_opt(2026, 1, 12).unwrap();
+        let result = AnalyzeJobs::new().with_reference_date(reference).execute(&[folder]).unwrap();
+
+        assert_eq!(result.dependency_dates.len(), 2);
+        let same = result.dependency_dates.iter().find(|d| d.consumer_job == "SAME_DAY").unwrap();
+        assert_eq!(same.alignment, RunAlignment::SameRun);
+        assert_eq!(same.producer_run_date.as_deref(), Some("2026-01-12"));
+        let prior = result.dependency_dates.iter().find(|d| d.consumer_job == "PRIOR_DAY").unwrap();
+        assert_eq!(prior.alignment, RunAlignment::PriorRun);
+        assert_eq!(prior.producer_run_date.as_deref(), Some("2026-01-11"));
+        assert_eq!(prior.offset_days, Some(-1));
+    }
+
+    #[test]
+    fn test_parallel_analysis_matches_sequential() {
+        use crate::domain::entities::Condition;
+
+        let mut folders = Vec::new();
+        for f in 0..4 {
+            let folder_name = format!("FOLDER_{}", f);
+            let mut folder = Folder::new(folder_name.clone(), FolderType::Simple);
+            for j in 0..25 {
+                let mut job = Job::new(format!("JOB_{}_{}", f, j), folder_name.clone());
+                for d in 0..(j % 4) {
+                    job.in_conditions.push(Condition::new_in(format!("COND_{}_{}", f, d)));
+                }
+                job.critical = j % 7 == 0;
+                folder.add_job(job);
+            }
+            folders.push(folder);
+        }
+
+        let sequential = AnalyzeJobs::new().execute(&folders).unwrap();
+        let parallel = AnalyzeJobs::new().with_parallel(true).execute(&folders).unwrap();
+
+        assert_eq!(format!("{:?}", sequential.complexity_results), format!("{:?}", parallel.complexity_results));
+        assert_eq!(format!("{:?}", sequential.migration_waves), format!("{:?}", parallel.migration_waves));
+        assert_eq!(sequential.average_complexity, parallel.average_complexity);
+    }
+
+    #[test]
+    fn test_cross_datacenter_dependency_is_flagged() {
+        use crate::domain::entities::Condition;
+
+        let mut producer = Job::new("PRODUCER".to_string(), "FOLDER_DC1".to_string());
+        producer.out_conditions.push(Condition::new_out("PRODUCER-OK".to_string()));
+        let mut local = Job::new("LOCAL".to_string(), "FOLDER_DC1".to_string());
+        local.in_conditions.push(Condition::new_in("PRODUCER-OK".to_string()));
+        let mut remote = Job::new("REMOTE".to_string(), "FOLDER_DC2".to_string());
+        remote.in_conditions.push(Condition::new_in("PRODUCER-OK".to_string()));
+
+        let mut dc1 = Folder::new("FOLDER_DC1".to_string(), FolderType::Simple);
+        dc1.datacenter = Some("DC1".to_string());
+        dc1.add_job(producer);
+        dc1.add_job(local);
+        let mut dc2 = Folder::new("FOLDER_DC2".to_string(), FolderType::Simple);
+        dc2.datacenter = Some("DC2".to_string());
+        dc2.add_job(remote);
+
+        let result = AnalyzeJobs::new().execute(&[dc1, dc2]).unwrap();
+
+        assert_eq!(result.cross_datacenter_dependencies.len(), 1);
+        let dependency = &result.cross_datacenter_dependencies[0];
+        assert_eq!(dependency.consumer_job, "REMOTE");
+        assert_eq!(dependency.consumer_folder, "FOLDER_DC2");
+        assert_eq!(dependency.consumer_datacenter, "DC2");
+        assert_eq!(dependency.producer_job, "PRODUCER");
+        assert_eq!(dependency.producer_datacenter, "DC1");
+    }
+
+    #[test]
+    fn test_condition_raised_by_two_jobs_is_ambiguous() {
+        use crate::domain::entities::Condition;
+
+        let mut first = Job::new("FIRST".to_string(), "FOLDER_A".to_string());
+        first.out_conditions.push(Condition::new_out("FOO-ENDED-OK".to_string()));
+        let mut second = Job::new("SECOND".to_string(), "FOLDER_B".to_string());
+        second.out_conditions.push(Condition::new_out("FOO-ENDED-OK".to_string()));
+        second.out_conditions.push(Condition::new_out("SECOND-OK".to_string()));
+        let mut consumer = Job::new("CONSUMER".to_string(), "FOLDER_A".to_string());
+        consumer.in_conditions.push(Condition::new_in("FOO-ENDED-OK".to_string()));
+        // Consuming a condition usually deletes it; that doesn't make the consumer a producer
+        let mut consumed = Condition::new_out("FOO-ENDED-OK".to_string());
+        consumed.sign = Some("-".to_string());
+        consumer.out_conditions.push(consumed);
+
+        let mut folder_a = Folder::new("FOLDER_A".to_string(), FolderType::Simple);
+        folder_a.add_job(first);
+        folder_a.add_job(consumer);
+        let mut folder_b = Folder::new("FOLDER_B".to_string(), FolderType::Simple);
+        folder_b.add_job(second);
+
+        let result = AnalyzeJobs::new().execute(&[folder_a, folder_b]).unwrap();
+
+        assert_eq!(result.ambiguous_producers.len(), 1);
+        let ambiguous = &result.ambiguous_producers[0];
+        assert_eq!(ambiguous.condition, "FOO-ENDED-OK");
+        let producers: Vec<(&str, &str)> = ambiguous.producers.iter()
+            .map(|p| (p.job_name.as_str(), p.folder_name.as_str()))
+            .collect();
+        assert_eq!(producers, vec![("FIRST", "FOLDER_A"), ("SECOND", "FOLDER_B")]);
+        assert_eq!(ambiguous.consumer_jobs, vec!["CONSUMER".to_string()]);
+        assert!(!result.has_circular_dependencies);
+    }
+
+    #[test]
+    fn test_orphaned_conditions_are_reported_once_per_job() {
+        use crate::domain::entities::Condition;
+
+        let mut producer = Job::new("PRODUCER".to_string(), "FOLDER".to_string());
+        producer.out_conditions.push(Condition::new_out("READY".to_string()));
+        let mut consumer = Job::new("CONSUMER".to_string(), "FOLDER".to_string());
+        consumer.in_conditions.push(Condition::new_in("READY".to_string()));
+        consumer.in_conditions.push(Condition::new_in("NEVER-POSTED".to_string()));
+        consumer.in_conditions.push(Condition::new_in("NEVER-POSTED".to_string()));
+        let mut folder = Folder::new("FOLDER".to_string(), FolderType::Simple);
+        folder.add_job(producer);
+        folder.add_job(consumer);
+
+        let result = AnalyzeJobs::new().execute(&[folder]).unwrap();
+
+        assert_eq!(result.orphaned_conditions, vec![OrphanedCondition {
+            job_name: "CONSUMER".to_string(),
+            folder_name: "FOLDER".to_string(),
+            condition_name: "NEVER-POSTED".to_string(),
+        }]);
+    }
 }
@@ -3,7 +3,7 @@
 //! This module provides the use case for building and analyzing job dependency graphs.
 //! It orchestrates the dependency analyzer service to create graph structures.
 
-use crate::domain::entities::Job;
+use crate::domain::entities::{Dependency, DependencyType, Folder, Job};
 use crate::application::services::DependencyAnalyzer;
 
 /// Use case for building job dependency graphs
@@ -29,6 +29,9 @@ impl BuildDependencyGraph {
 
     /// Executes dependency graph building for a collection of jobs
     ///
+    /// The graph links jobs (see `DependencyAnalyzer::build_condition_graph`),
+    /// so cycles are reported between jobs whatever their conditions are named.
+    ///
     /// # Arguments
     ///
     /// * `jobs` - Slice of job references to build the graph from
@@ -37,20 +40,67 @@ impl BuildDependencyGraph {
     ///
     /// A DependencyGraphResult containing graph analysis results
     pub fn execute(&mut self, jobs: &[&Job]) -> DependencyGraphResult {
-        self.analyzer.build_graph(jobs);
+        self.analyzer.build_condition_graph(jobs);
 
         let has_circular = self.analyzer.has_circular_dependencies();
-        let topological_order = if !has_circular {
-            self.analyzer.topological_sort().ok()
+        let (topological_order, cycles) = if !has_circular {
+            (self.analyzer.topological_sort().ok(), Vec::new())
         } else {
-            None
+            (None, self.analyzer.find_cycles())
         };
 
         DependencyGraphResult {
             total_jobs: jobs.len(),
             has_circular_dependencies: has_circular,
             topological_order,
+            cycles,
+            dependencies: Self::resolve_job_dependencies(jobs),
+        }
+    }
+
+    /// Resolves the producer jobs of every in-condition across all folders
+    ///
+    /// Producers are found by matching the in-condition name against the
+    /// out-conditions of every parsed job, so the link holds even when the
+    /// condition name does not contain the producer's job name.
+    ///
+    /// # Arguments
+    ///
+    /// * `folders` - All parsed folders (sub-folders are included)
+    ///
+    /// # Returns
+    ///
+    /// One in-condition dependency per consuming job and condition
+    pub fn resolve_dependencies(folders: &[Folder]) -> Vec<Dependency> {
+        let jobs: Vec<&Job> = folders.iter().flat_map(|f| f.all_jobs()).collect();
+        Self::resolve_job_dependencies(&jobs)
+    }
+
+    /// Names the producers at `positions`, each name listed once
+    fn producer_names(jobs: &[&Job], positions: &[usize]) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for &position in positions {
+            if !names.contains(&jobs[position].job_name) {
+                names.push(jobs[position].job_name.clone());
+            }
         }
+        names
+    }
+
+    fn resolve_job_dependencies(jobs: &[&Job]) -> Vec<Dependency> {
+        let producers = DependencyAnalyzer::producer_map(jobs);
+        jobs.iter()
+            .flat_map(|job| {
+                let producers = &producers;
+                job.in_conditions.iter().map(move |in_cond| {
+                    let positions = producers.get(in_cond.name.as_str()).map(Vec::as_slice).unwrap_or_default();
+                    Dependency::new(in_cond.name.clone(), job.job_name.clone(), DependencyType::InCondition)
+                        .with_to_folder(job.folder_name.clone())
+                        .with_condition(in_cond.name.clone())
+                        .with_producers(Self::producer_names(jobs, positions))
+                })
+            })
+            .collect()
     }
 
     /// Gets a reference to the internal dependency analyzer
@@ -81,6 +131,10 @@ pub struct DependencyGraphResult {
     pub has_circular_dependencies: bool,
     /// Topological order of jobs (None if circular dependencies exist)
     pub topological_order: Option<Vec<String>>,
+    /// Jobs of each dependency cycle (empty if there are none)
+    pub cycles: Vec<Vec<String>>,
+    /// In-condition dependencies with their producer jobs resolved
+    pub dependencies: Vec<Dependency>,
 }
 
 #[cfg(test)]
@@ -99,4 +153,66 @@ mod tests {
         
         assert_eq!(result.total_jobs, 2);
     }
+
+    #[test]
+    fn test_execute_reports_cycle_jobs() {
+        use crate::domain::entities::Condition;
+
+        let mut use_case = BuildDependencyGraph::new();
+        let mut job_a = Job::new("JOB_A".to_string(), "FOLDER".to_string());
+        job_a.in_conditions.push(Condition::new_in("JOB_B".to_string()));
+        job_a.out_conditions.push(Condition::new_out("JOB_A".to_string()));
+        let mut job_b = Job::new("JOB_B".to_string(), "FOLDER".to_string());
+        job_b.in_conditions.push(Condition::new_in("JOB_A".to_string()));
+        job_b.out_conditions.push(Condition::new_out("JOB_B".to_string()));
+        let job_c = Job::new("JOB_C".to_string(), "FOLDER".to_string());
+
+        let result = use_case.execute(&[&job_a, &job_b, &job_c]);
+
+        assert!(result.has_circular_dependencies);
+        assert_eq!(result.cycles, vec![vec!["FOLDER/JOB_A".to_string(), "FOLDER/JOB_B".to_string()]]);
+        assert!(result.topological_order.is_none());
+    }
+
+    #[test]
+    fn test_execute_reports_cycles_through_differently_named_conditions() {
+        use crate::domain::entities::Condition;
+
+        // A sets X, B waits for X and sets Y, A waits for Y
+        let mut job_a = Job::new("JOB_A".to_string(), "FOLDER".to_string());
+        job_a.out_conditions.push(Condition::new_out("X".to_string()));
+        job_a.in_conditions.push(Condition::new_in("Y".to_string()));
+        let mut job_b = Job::new("JOB_B".to_string(), "FOLDER".to_string());
+        job_b.in_conditions.push(Condition::new_in("X".to_string()));
+        job_b.out_conditions.push(Condition::new_out("Y".to_string()));
+
+        let result = BuildDependencyGraph::new().execute(&[&job_a, &job_b]);
+
+        assert!(result.has_circular_dependencies);
+        assert_eq!(result.cycles, vec![vec!["FOLDER/JOB_A".to_string(), "FOLDER/JOB_B".to_string()]]);
+    }
+
+    #[test]
+    fn test_resolve_dependencies_links_producers_by_out_condition() {
+        use crate::domain::entities::{Condition, FolderType};
+
+        let mut extract = Job::new("EXTRACT_DAILY".to_string(), "ETL".to_string());
+        extract.out_conditions.push(Condition::new_out("DATA-READY".to_string()));
+        let mut folder_a = Folder::new("ETL".to_string(), FolderType::Simple);
+        folder_a.jobs.push(extract);
+
+        let mut load = Job::new("LOAD_DW".to_string(), "DW".to_string());
+        load.in_conditions.push(Condition::new_in("DATA-READY".to_string()));
+        load.in_conditions.push(Condition::new_in("NOBODY-SETS-THIS".to_string()));
+        let mut folder_b = Folder::new("DW".to_string(), FolderType::Simple);
+        folder_b.jobs.push(load);
+
+        let folders = vec![folder_a, folder_b];
+        let dependencies = BuildDependencyGraph::resolve_dependencies(&folders);
+
+        assert_eq!(dependencies.len(), 2);
+        assert_eq!(dependencies[0].to_job, "LOAD_DW");
+        assert_eq!(dependencies[0].producer_jobs, vec!["EXTRACT_DAILY".to_string()]);
+        assert!(!dependencies[1].is_resolved());
+    }
 }
@@ -0,0 +1,153 @@
+//! Build Folder Matrix use case module
+//!
+//! This module provides the use case for summarizing job dependencies as a
+//! folder-by-folder edge-count matrix, used to plan which folders must
+//! migrate together.
+
+use std::collections::{BTreeSet, HashMap};
+use serde::{Deserialize, Serialize};
+use crate::domain::entities::Job;
+use crate::application::services::DependencyAnalyzer;
+
+/// Use case for building the folder dependency matrix
+///
+/// Resolves producer/consumer job edges by matching conditions and
+/// aggregates them by the folders of the two jobs.
+pub struct BuildFolderMatrix;
+
+impl BuildFolderMatrix {
+    /// Creates a new BuildFolderMatrix use case
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Executes the folder matrix computation for a collection of jobs
+    ///
+    /// # Arguments
+    ///
+    /// * `jobs` - Slice of job references to analyze
+    ///
+    /// # Returns
+    ///
+    /// A FolderDependencyMatrix with one row and column per folder
+    pub fn execute(&self, jobs: &[&Job]) -> FolderDependencyMatrix {
+        let mut analyzer = DependencyAnalyzer::new();
+        analyzer.build_condition_graph(jobs);
+
+        let job_folders: HashMap<String, &str> = jobs.iter()
+            .map(|job| (DependencyAnalyzer::job_key(&job.folder_name, &job.job_name), job.folder_name.as_str()))
+            .collect();
+
+        let folders: Vec<String> = jobs.iter()
+            .map(|j| j.folder_name.clone())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let folder_index: HashMap<&str, usize> = folders.iter()
+            .enumerate()
+            .map(|(i, f)| (f.as_str(), i))
+            .collect();
+
+        let mut counts = vec![vec![0usize; folders.len()]; folders.len()];
+        for (from, to, _) in analyzer.get_edges() {
+            let from_folder = job_folders.get(&from).and_then(|f| folder_index.get(f));
+            let to_folder = job_folders.get(&to).and_then(|f| folder_index.get(f));
+            if let (Some(&row), Some(&col)) = (from_folder, to_folder) {
+                counts[row][col] += 1;
+            }
+        }
+
+        FolderDependencyMatrix { folders, counts }
+    }
+}
+
+impl Default for BuildFolderMatrix {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Folder-by-folder dependency edge counts
+///
+/// `counts[i][j]` is the number of job edges where a job in `folders[i]`
+/// sets a condition that a job in `folders[j]` waits for. The diagonal
+/// holds intra-folder edges.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FolderDependencyMatrix {
+    /// Folder names, sorted, used as both row and column labels
+    pub folders: Vec<String>,
+    /// Edge counts indexed by [producer folder][consumer folder]
+    pub counts: Vec<Vec<usize>>,
+}
+
+impl FolderDependencyMatrix {
+    /// Gets the number of edges from one folder to another
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - Folder containing the producing jobs
+    /// * `to` - Folder containing the consuming jobs
+    ///
+    /// # Returns
+    ///
+    /// Edge count, or 0 if either folder is unknown
+    pub fn count(&self, from: &str, to: &str) -> usize {
+        let row = self.folders.iter().position(|f| f == from);
+        let col = self.folders.iter().position(|f| f == to);
+        match (row, col) {
+            (Some(row), Some(col)) => self.counts[row][col],
+            _ => 0,
+        }
+    }
+
+    /// Gets the largest cell value in the matrix
+    pub fn max_count(&self) -> usize {
+        self.counts.iter().flatten().copied().max().unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::Condition;
+
+    fn job(name: &str, folder: &str, in_cond: Option<&str>, out_cond: &str) -> Job {
+        let mut job = Job::new(name.to_string(), folder.to_string());
+        if let Some(cond) = in_cond {
+            job.in_conditions.push(Condition::new_in(cond.to_string()));
+        }
+        job.out_conditions.push(Condition::new_out(out_cond.to_string()));
+        job
+    }
+
+    #[test]
+    fn test_two_folder_matrix_counts() {
+        let extract = job("EXTRACT", "FOLDER_A", None, "EXTRACT-OK");
+        let transform = job("TRANSFORM", "FOLDER_A", Some("EXTRACT-OK"), "TRANSFORM-OK");
+        let load = job("LOAD", "FOLDER_B", Some("TRANSFORM-OK"), "LOAD-OK");
+        let jobs = vec![&extract, &transform, &load];
+
+        let matrix = BuildFolderMatrix::new().execute(&jobs);
+
+        assert_eq!(matrix.folders, vec!["FOLDER_A".to_string(), "FOLDER_B".to_string()]);
+        assert_eq!(matrix.count("FOLDER_A", "FOLDER_A"), 1);
+        assert_eq!(matrix.count("FOLDER_A", "FOLDER_B"), 1);
+        assert_eq!(matrix.count("FOLDER_B", "FOLDER_A"), 0);
+        assert_eq!(matrix.count("FOLDER_B", "FOLDER_B"), 0);
+        assert_eq!(matrix.max_count(), 1);
+    }
+
+    #[test]
+    fn test_job_names_shared_across_folders_count_for_their_own_folder() {
+        let load_a = job("LOAD", "FOLDER_A", None, "LOAD-A-OK");
+        let load_b = job("LOAD", "FOLDER_B", None, "LOAD-B-OK");
+        let report = job("REPORT", "FOLDER_C", Some("LOAD-B-OK"), "REPORT-OK");
+        let jobs = vec![&load_a, &load_b, &report];
+
+        let matrix = BuildFolderMatrix::new().execute(&jobs);
+
+        assert_eq!(matrix.count("FOLDER_A", "FOLDER_C"), 0);
+        assert_eq!(matrix.count("FOLDER_B", "FOLDER_C"), 1);
+        assert_eq!(matrix.max_count(), 1);
+    }
+}
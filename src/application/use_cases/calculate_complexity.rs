@@ -3,9 +3,11 @@
 //! This module provides the use case for calculating job complexity metrics.
 //! It orchestrates the complexity calculation service to analyze jobs.
 
+use rayon::prelude::*;
+use std::collections::BTreeMap;
 use crate::domain::entities::Job;
-use crate::domain::value_objects::{ComplexityScore, MigrationDifficulty, MigrationPriority};
-use crate::application::services::ComplexityCalculator;
+use crate::domain::value_objects::{ComplexityScore, ComplexityWeights, MigrationDifficulty, MigrationPriority, MigrationReadiness, MigrationTarget, ApplTypeAliases};
+use crate::application::services::{ComplexityCalculator, OperatorMapper, OperatorMapping};
 
 /// Use case for calculating job complexity
 ///
@@ -14,6 +16,8 @@ use crate::application::services::ComplexityCalculator;
 pub struct CalculateComplexity {
     /// The complexity calculator service
     calculator: ComplexityCalculator,
+    /// Maps jobs to operators available in the migration target
+    operator_mapper: OperatorMapper,
 }
 
 impl CalculateComplexity {
@@ -25,9 +29,34 @@ impl CalculateComplexity {
     pub fn new() -> Self {
         Self {
             calculator: ComplexityCalculator::new(),
+            operator_mapper: OperatorMapper::default(),
         }
     }
 
+    /// Sets the Airflow environment used for operator suggestions
+    pub fn with_migration_target(mut self, target: MigrationTarget) -> Self {
+        self.operator_mapper = self.operator_mapper.with_target(target);
+        self
+    }
+
+    /// Sets the aliases used to categorize job application types
+    pub fn with_appl_type_aliases(mut self, appl_type_aliases: ApplTypeAliases) -> Self {
+        self.operator_mapper = self.operator_mapper.with_appl_type_aliases(appl_type_aliases);
+        self
+    }
+
+    /// Sets whether out-conditions count as dependencies when scoring
+    pub fn with_downstream_dependencies(mut self, include_downstream: bool) -> Self {
+        self.calculator = self.calculator.with_downstream_dependencies(include_downstream);
+        self
+    }
+
+    /// Sets the points each complexity factor contributes
+    pub fn with_weights(mut self, weights: ComplexityWeights) -> Self {
+        self.calculator = self.calculator.with_weights(weights);
+        self
+    }
+
     /// Executes complexity calculation for a single job
     ///
     /// # Arguments
@@ -47,11 +76,15 @@ impl CalculateComplexity {
             folder_name: job.folder_name.clone(),
             complexity_score,
             migration_difficulty,
+            migration_readiness: MigrationReadiness::Ready, // Will be set by job analysis
             migration_priority,
             migration_wave: 0, // Will be set by wave determination
-            dependency_count: job.dependency_count(),
+            dependency_count: self.calculator.dependency_count(job),
             is_critical: job.is_critical(),
             is_cyclic: job.cyclic,
+            operator_mapping: self.operator_mapper.map(job),
+            factor_breakdown: self.calculator.factor_breakdown(job),
+            migration_notes: Vec::new(), // Will be set by job analysis
         }
     }
 
@@ -67,6 +100,22 @@ impl CalculateComplexity {
     pub fn execute_batch(&self, jobs: &[&Job]) -> Vec<JobComplexityResult> {
         jobs.iter().map(|job| self.execute(job)).collect()
     }
+
+    /// Executes complexity calculation for multiple jobs across threads
+    ///
+    /// Jobs are scored independently, so results are identical to
+    /// `execute_batch` and returned in the same order.
+    ///
+    /// # Arguments
+    ///
+    /// * `jobs` - Slice of job references to analyze
+    ///
+    /// # Returns
+    ///
+    /// Vector of JobComplexityResult for all jobs, in input order
+    pub fn execute_batch_parallel(&self, jobs: &[&Job]) -> Vec<JobComplexityResult> {
+        jobs.par_iter().map(|job| self.execute(job)).collect()
+    }
 }
 
 impl Default for CalculateComplexity {
@@ -89,16 +138,49 @@ pub struct JobComplexityResult {
     pub complexity_score: ComplexityScore,
     /// Derived migration difficulty level
     pub migration_difficulty: MigrationDifficulty,
+    /// Whether the job can be migrated now (set by job analysis)
+    pub migration_readiness: MigrationReadiness,
     /// Calculated migration priority
     pub migration_priority: MigrationPriority,
     /// Assigned migration wave (set by wave determination)
     pub migration_wave: usize,
-    /// Number of dependencies
+    /// Number of dependencies (including out-conditions when configured)
     pub dependency_count: usize,
     /// Whether the job is critical
     pub is_critical: bool,
     /// Whether the job is cyclic
     pub is_cyclic: bool,
+    /// Suggested Airflow operator in the migration target
+    pub operator_mapping: OperatorMapping,
+    /// Points contributed by each complexity factor, keyed by the factor
+    /// names of the scoring methodology; they sum to the complexity score
+    pub factor_breakdown: BTreeMap<String, u32>,
+    /// Every migration risk detected for the job, in one place
+    pub migration_notes: Vec<String>,
+}
+
+#[cfg(test)]
+impl JobComplexityResult {
+    /// Builds an easy, ready result with no dependencies for tests
+    ///
+    /// Override the fields a test cares about with struct update syntax.
+    pub(crate) fn for_test(job_name: &str, folder_name: &str) -> Self {
+        Self {
+            job_name: job_name.to_string(),
+            folder_name: folder_name.to_string(),
+            complexity_score: ComplexityScore::new(0),
+            migration_difficulty: MigrationDifficulty::Easy,
+            migration_readiness: MigrationReadiness::Ready,
+            migration_priority: MigrationPriority::new(0),
+            migration_wave: 0,
+            dependency_count: 0,
+            is_critical: false,
+            is_cyclic: false,
+            operator_mapping: OperatorMapping::default(),
+            factor_breakdown: BTreeMap::new(),
+            migration_notes: Vec::new(),
+        }
+    }
 }
 
 #[cfg(test)]
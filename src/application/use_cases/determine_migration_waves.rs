@@ -4,14 +4,21 @@
 //! job complexity and dependencies. It groups jobs into waves for phased migration.
 
 use std::collections::HashMap;
+use crate::application::services::DependencyAnalyzer;
 use crate::application::use_cases::calculate_complexity::JobComplexityResult;
 
 /// Use case for determining migration waves
 ///
 /// This use case analyzes job complexity results and assigns jobs to migration
 /// waves based on complexity, dependencies, and criticality. Lower waves contain
-/// easier jobs that should be migrated first.
-pub struct DetermineMigrationWaves;
+/// easier jobs that should be migrated first. A job is never placed in an
+/// earlier wave than the jobs it depends on.
+pub struct DetermineMigrationWaves {
+    /// Number of waves jobs are spread over
+    wave_count: usize,
+}
+
+pub use crate::domain::value_objects::analysis_profile::DEFAULT_WAVE_COUNT;
 
 impl DetermineMigrationWaves {
     /// Creates a new DetermineMigrationWaves use case
@@ -20,30 +27,119 @@ impl DetermineMigrationWaves {
     ///
     /// A new DetermineMigrationWaves instance
     pub fn new() -> Self {
-        Self
+        Self {
+            wave_count: DEFAULT_WAVE_COUNT,
+        }
+    }
+
+    /// Sets the number of waves jobs are spread over (at least 1)
+    ///
+    /// The five complexity tiers are spread evenly over the waves, so with
+    /// more than five waves some only hold jobs pushed back by dependencies,
+    /// and with fewer, neighbouring tiers share a wave.
+    ///
+    /// # Arguments
+    ///
+    /// * `wave_count` - Target number of waves
+    ///
+    /// # Returns
+    ///
+    /// Self with the wave count set
+    pub fn with_wave_count(mut self, wave_count: usize) -> Self {
+        self.wave_count = wave_count.max(1);
+        self
+    }
+
+    /// Maps a complexity tier (1-5) to a wave within the wave count
+    fn tier_to_wave(&self, tier: usize) -> usize {
+        let last = DEFAULT_WAVE_COUNT - 1;
+        // Rounded to the nearest wave
+        ((tier - 1) * (self.wave_count - 1) * 2 + last) / (last * 2) + 1
+    }
+
+    /// Maps a wave back to the complexity tier (1-5) it mostly holds
+    fn wave_to_tier(&self, wave: usize) -> usize {
+        if self.wave_count == 1 {
+            return 1;
+        }
+        let last = self.wave_count - 1;
+        ((wave.max(1) - 1) * (DEFAULT_WAVE_COUNT - 1) * 2 + last) / (last * 2) + 1
     }
 
     /// Executes migration wave determination for job complexity results
     ///
+    /// Each job first gets a wave from its complexity, then is pushed back to
+    /// the latest wave of its upstream jobs, walking the jobs in topological
+    /// order. Jobs in a dependency cycle end up in the same wave. Within a
+    /// wave, jobs are listed by topological level, then by complexity.
+    ///
+    /// Jobs are matched to graph nodes by folder and job name (see
+    /// `DependencyAnalyzer::job_key`), so same-named jobs in different
+    /// folders keep their own waves and upstream jobs.
+    ///
     /// # Arguments
     ///
-    /// * `results` - Slice of job complexity results to analyze
+    /// * `results` - Job complexity results; each gets its `migration_wave` set
+    /// * `analyzer` - Job-to-job dependency graph (see `build_condition_graph`)
     ///
     /// # Returns
     ///
     /// Vector of MigrationWave objects, sorted by wave number
-    pub fn execute(&self, results: &[JobComplexityResult]) -> Vec<MigrationWave> {
-        let mut waves: HashMap<usize, Vec<String>> = HashMap::new();
+    pub fn execute(&self, results: &mut [JobComplexityResult], analyzer: &DependencyAnalyzer) -> Vec<MigrationWave> {
+        let levels = analyzer.topological_levels();
+        let keys: Vec<String> = results.iter()
+            .map(|result| DependencyAnalyzer::job_key(&result.folder_name, &result.job_name))
+            .collect();
+        let level_of = |index: usize| levels.get(&keys[index]).copied().unwrap_or(0);
 
-        for result in results {
-            let wave = self.determine_wave(result);
-            waves.entry(wave).or_insert_with(Vec::new).push(result.job_name.clone());
+        let mut ordered: Vec<usize> = (0..results.len()).collect();
+        ordered.sort_by(|&a, &b| {
+            level_of(a).cmp(&level_of(b))
+                .then_with(|| results[a].complexity_score.value().cmp(&results[b].complexity_score.value()))
+                .then_with(|| results[a].job_name.cmp(&results[b].job_name))
+                .then_with(|| results[a].folder_name.cmp(&results[b].folder_name))
+        });
+
+        let mut by_key: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (index, key) in keys.iter().enumerate() {
+            by_key.entry(key.as_str()).or_default().push(index);
+        }
+        let upstream: Vec<Vec<usize>> = keys.iter()
+            .map(|key| analyzer.get_upstream_jobs(key).iter()
+                .flat_map(|upstream_key| by_key.get(upstream_key.as_str()).into_iter().flatten().copied())
+                .collect())
+            .collect();
+        let mut job_waves: Vec<usize> = results.iter()
+            .map(|result| self.tier_to_wave(self.determine_wave(result)))
+            .collect();
+
+        // One pass settles acyclic graphs; cycles need a few more to agree
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &index in &ordered {
+                let earliest = upstream[index].iter()
+                    .map(|&upstream_job| job_waves[upstream_job])
+                    .max()
+                    .unwrap_or(0);
+                if earliest > job_waves[index] {
+                    job_waves[index] = earliest;
+                    changed = true;
+                }
+            }
+        }
+
+        let mut waves: HashMap<usize, Vec<String>> = HashMap::new();
+        for &index in &ordered {
+            let result = &mut results[index];
+            result.migration_wave = job_waves[index];
+            waves.entry(result.migration_wave).or_default().push(result.job_name.clone());
         }
 
         let mut wave_list: Vec<MigrationWave> = waves
             .into_iter()
             .map(|(wave_number, jobs)| {
-                let reason = self.get_wave_reason(wave_number);
+                let reason = self.get_wave_reason(self.wave_to_tier(wave_number));
                 MigrationWave {
                     wave: wave_number,
                     jobs,
@@ -56,7 +152,7 @@ impl DetermineMigrationWaves {
         wave_list
     }
 
-    /// Determines the migration wave for a single job
+    /// Determines the migration wave for a single job from its complexity
     ///
     /// Wave assignment strategy:
     /// - Wave 1-2: Low complexity + minimal/no dependencies (quick wins)
@@ -150,7 +246,7 @@ impl DetermineMigrationWaves {
     ///
     /// # Arguments
     ///
-    /// * `wave` - The wave number
+    /// * `wave` - The complexity tier (the wave number with the default wave count)
     ///
     /// # Returns
     ///
@@ -196,15 +292,9 @@ mod tests {
     fn test_determine_wave_easy_no_deps() {
         let use_case = DetermineMigrationWaves::new();
         let result = JobComplexityResult {
-            job_name: "EASY_JOB".to_string(),
-            folder_name: "FOLDER".to_string(),
             complexity_score: ComplexityScore::new(10),
-            migration_difficulty: MigrationDifficulty::Easy,
             migration_priority: MigrationPriority::new(100),
-            migration_wave: 0,
-            dependency_count: 0,
-            is_critical: false,
-            is_cyclic: false,
+            ..JobComplexityResult::for_test("EASY_JOB", "FOLDER")
         };
 
         let wave = use_case.determine_wave(&result);
@@ -214,32 +304,86 @@ mod tests {
     #[test]
     fn test_execute() {
         let use_case = DetermineMigrationWaves::new();
-        let results = vec![
+        let mut results = vec![
             JobComplexityResult {
-                job_name: "JOB1".to_string(),
-                folder_name: "FOLDER".to_string(),
                 complexity_score: ComplexityScore::new(10),
-                migration_difficulty: MigrationDifficulty::Easy,
                 migration_priority: MigrationPriority::new(100),
-                migration_wave: 0,
-                dependency_count: 0,
-                is_critical: false,
-                is_cyclic: false,
+                ..JobComplexityResult::for_test("JOB1", "FOLDER")
             },
             JobComplexityResult {
-                job_name: "JOB2".to_string(),
-                folder_name: "FOLDER".to_string(),
                 complexity_score: ComplexityScore::new(75),
                 migration_difficulty: MigrationDifficulty::Hard,
                 migration_priority: MigrationPriority::new(10),
-                migration_wave: 0,
                 dependency_count: 5,
-                is_critical: false,
                 is_cyclic: true,
+                ..JobComplexityResult::for_test("JOB2", "FOLDER")
             },
         ];
 
-        let waves = use_case.execute(&results);
+        let waves = use_case.execute(&mut results, &DependencyAnalyzer::new());
         assert!(waves.len() >= 1);
     }
+
+    #[test]
+    fn test_job_is_never_in_an_earlier_wave_than_its_upstream() {
+        let use_case = DetermineMigrationWaves::new();
+        let result = |name: &str, score: u32, dependency_count: usize| JobComplexityResult {
+            complexity_score: ComplexityScore::new(score),
+            migration_priority: MigrationPriority::new(100),
+            dependency_count,
+            ..JobComplexityResult::for_test(name, "FOLDER")
+        };
+        let mut results = vec![result("JOB_B", 5, 1), result("JOB_A", 80, 0), result("JOB_C", 5, 0)];
+        assert_eq!(use_case.determine_wave(&results[0]), 1);
+
+        let mut analyzer = DependencyAnalyzer::new();
+        for job in ["JOB_A", "JOB_B", "JOB_C"] {
+            analyzer.add_job(&DependencyAnalyzer::job_key("FOLDER", job));
+        }
+        analyzer.add_dependency("FOLDER/JOB_A", "FOLDER/JOB_B", "A-OK");
+
+        let waves = use_case.execute(&mut results, &analyzer);
+        let wave_of = |name: &str| waves.iter().find(|w| w.jobs.iter().any(|j| j == name)).unwrap().wave;
+        assert_eq!(wave_of("JOB_A"), 5);
+        assert!(wave_of("JOB_B") >= wave_of("JOB_A"));
+        assert_eq!(wave_of("JOB_C"), 1);
+        assert_eq!(waves.last().unwrap().jobs, vec!["JOB_A".to_string(), "JOB_B".to_string()]);
+    }
+
+    #[test]
+    fn test_same_named_jobs_in_different_folders_keep_their_own_waves() {
+        let use_case = DetermineMigrationWaves::new();
+        let result = |folder: &str, score: u32| JobComplexityResult {
+            complexity_score: ComplexityScore::new(score),
+            migration_priority: MigrationPriority::new(100),
+            ..JobComplexityResult::for_test("REPORT", folder)
+        };
+        let mut results = vec![result("FINANCE", 5), result("SALES", 5), result("SALES_UPSTREAM", 80)];
+        results[2].job_name = "LOAD".to_string();
+        // Only the SALES report waits for the hard upstream job
+        let mut analyzer = DependencyAnalyzer::new();
+        analyzer.add_job("FINANCE/REPORT");
+        analyzer.add_dependency("SALES_UPSTREAM/LOAD", "SALES/REPORT", "LOAD-OK");
+
+        let waves = use_case.execute(&mut results, &analyzer);
+
+        assert_eq!(results[0].migration_wave, 1);
+        assert_eq!(results[1].migration_wave, 5);
+        assert_eq!(results[2].migration_wave, 5);
+        assert_eq!(waves.iter().map(|w| w.wave).collect::<Vec<_>>(), vec![1, 5]);
+    }
+
+    #[test]
+    fn test_wave_count_spreads_tiers_over_target_waves() {
+        let eight = DetermineMigrationWaves::new().with_wave_count(8);
+        let waves: Vec<usize> = (1..=5).map(|tier| eight.tier_to_wave(tier)).collect();
+        assert_eq!(waves, vec![1, 3, 5, 6, 8]);
+        assert_eq!(eight.wave_to_tier(8), 5);
+
+        let default = DetermineMigrationWaves::new();
+        assert!((1..=5).all(|tier| default.tier_to_wave(tier) == tier && default.wave_to_tier(tier) == tier));
+
+        let one = DetermineMigrationWaves::new().with_wave_count(0);
+        assert!((1..=5).all(|tier| one.tier_to_wave(tier) == 1));
+    }
 }
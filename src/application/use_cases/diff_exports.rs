@@ -0,0 +1,274 @@
+//! Diff Exports use case module
+//!
+//! This module provides the use case for comparing two Control-M exports,
+//! e.g. last month's and this month's, during a phased migration. Folders
+//! are matched by name and jobs by folder and job name; a matched job is
+//! modified when any attribute of the parsed `Job` differs, and the
+//! differing attributes are listed so unchanged jobs need no re-analysis.
+
+use std::collections::BTreeMap;
+use serde::Serialize;
+use serde_json::Value;
+use crate::domain::entities::{Folder, Job};
+
+/// Folder attributes compared recursively as jobs and folders instead
+const FOLDER_CONTENT_FIELDS: &[&str] = &["jobs", "sub_folders"];
+
+/// Use case for comparing two Control-M exports
+pub struct DiffExports;
+
+impl DiffExports {
+    /// Creates a new DiffExports use case
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Compares the folders and jobs of two exports
+    ///
+    /// Sub-folders are compared like top-level folders. Jobs with the same
+    /// folder and name in one export (e.g. several versions) are paired in
+    /// file order.
+    ///
+    /// # Arguments
+    ///
+    /// * `old` - Folders of the earlier export
+    /// * `new` - Folders of the later export
+    ///
+    /// # Returns
+    ///
+    /// The added, removed and modified folders and jobs, sorted by name
+    pub fn execute(&self, old: &[Folder], new: &[Folder]) -> ExportDiff {
+        let mut diff = ExportDiff::default();
+
+        let old_folders = Self::folders_by_name(old);
+        let new_folders = Self::folders_by_name(new);
+        for (name, old_folder) in &old_folders {
+            match new_folders.get(name) {
+                None => diff.removed_folders.push(name.to_string()),
+                Some(new_folder) => {
+                    let changes = Self::attribute_changes(old_folder, new_folder, FOLDER_CONTENT_FIELDS);
+                    if !changes.is_empty() {
+                        diff.modified_folders.push(FolderChange { folder_name: name.to_string(), changes });
+                    }
+                }
+            }
+        }
+        diff.added_folders = new_folders.keys()
+            .filter(|name| !old_folders.contains_key(*name))
+            .map(|name| name.to_string())
+            .collect();
+
+        let old_jobs = Self::jobs_by_key(old);
+        let new_jobs = Self::jobs_by_key(new);
+        for (key, old_versions) in &old_jobs {
+            let new_versions = new_jobs.get(key).map(Vec::as_slice).unwrap_or_default();
+            for (index, old_job) in old_versions.iter().enumerate() {
+                let Some(new_job) = new_versions.get(index) else {
+                    diff.removed_jobs.push(JobKey::of(old_job));
+                    continue;
+                };
+                let changes = if old_job == new_job { Vec::new() } else { Self::attribute_changes(old_job, new_job, &[]) };
+                if changes.is_empty() {
+                    diff.unchanged_jobs += 1;
+                } else {
+                    diff.modified_jobs.push(JobChange {
+                        folder_name: key.0.to_string(),
+                        job_name: key.1.to_string(),
+                        changes,
+                    });
+                }
+            }
+        }
+        for (key, new_versions) in &new_jobs {
+            let known = old_jobs.get(key).map_or(0, Vec::len);
+            diff.added_jobs.extend(new_versions.iter().skip(known).map(|job| JobKey::of(job)));
+        }
+
+        diff
+    }
+
+    /// Indexes folders and their sub-folders by name; the last one wins
+    fn folders_by_name(folders: &[Folder]) -> BTreeMap<&str, &Folder> {
+        let mut index = BTreeMap::new();
+        let mut pending: Vec<&Folder> = folders.iter().collect();
+        while let Some(folder) = pending.pop() {
+            index.insert(folder.folder_name.as_str(), folder);
+            pending.extend(folder.sub_folders.iter());
+        }
+        index
+    }
+
+    /// Groups jobs by folder and job name, in file order
+    fn jobs_by_key(folders: &[Folder]) -> BTreeMap<(&str, &str), Vec<&Job>> {
+        let mut index: BTreeMap<(&str, &str), Vec<&Job>> = BTreeMap::new();
+        for job in folders.iter().flat_map(|folder| folder.all_jobs()) {
+            index.entry((job.folder_name.as_str(), job.job_name.as_str())).or_default().push(job);
+        }
+        index
+    }
+
+    /// Lists the serialized attributes that differ between two values
+    ///
+    /// # Arguments
+    ///
+    /// * `old` - Value from the earlier export
+    /// * `new` - Value from the later export
+    /// * `skip` - Attributes left out of the comparison
+    ///
+    /// # Returns
+    ///
+    /// One change per differing top-level attribute, sorted by name
+    fn attribute_changes<T: Serialize>(old: &T, new: &T, skip: &[&str]) -> Vec<AttributeChange> {
+        let (Ok(Value::Object(old)), Ok(Value::Object(mut new))) = (serde_json::to_value(old), serde_json::to_value(new)) else {
+            return Vec::new();
+        };
+
+        let mut changes: Vec<AttributeChange> = old.into_iter()
+            .filter(|(field, _)| !skip.contains(&field.as_str()))
+            .filter_map(|(field, old_value)| {
+                let new_value = new.remove(&field).unwrap_or(Value::Null);
+                (old_value != new_value).then_some(AttributeChange { field, old: old_value, new: new_value })
+            })
+            .collect();
+        changes.extend(new.into_iter()
+            .filter(|(field, value)| !skip.contains(&field.as_str()) && !value.is_null())
+            .map(|(field, new_value)| AttributeChange { field, old: Value::Null, new: new_value }));
+        changes.sort_by(|a, b| a.field.cmp(&b.field));
+        changes
+    }
+}
+
+impl Default for DiffExports {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Differences between two Control-M exports
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ExportDiff {
+    /// Folders only in the later export
+    pub added_folders: Vec<String>,
+    /// Folders only in the earlier export
+    pub removed_folders: Vec<String>,
+    /// Folders in both exports whose own attributes differ
+    pub modified_folders: Vec<FolderChange>,
+    /// Jobs only in the later export
+    pub added_jobs: Vec<JobKey>,
+    /// Jobs only in the earlier export
+    pub removed_jobs: Vec<JobKey>,
+    /// Jobs in both exports with differing attributes
+    pub modified_jobs: Vec<JobChange>,
+    /// Number of jobs identical in both exports
+    pub unchanged_jobs: usize,
+}
+
+impl ExportDiff {
+    /// Whether the two exports define the same folders and jobs
+    pub fn is_empty(&self) -> bool {
+        self.added_folders.is_empty() && self.removed_folders.is_empty() && self.modified_folders.is_empty()
+            && self.added_jobs.is_empty() && self.removed_jobs.is_empty() && self.modified_jobs.is_empty()
+    }
+}
+
+/// Identifies a job across exports
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct JobKey {
+    pub folder_name: String,
+    pub job_name: String,
+}
+
+impl JobKey {
+    fn of(job: &Job) -> Self {
+        Self {
+            folder_name: job.folder_name.clone(),
+            job_name: job.job_name.clone(),
+        }
+    }
+}
+
+/// A job whose attributes differ between exports
+#[derive(Debug, Clone, Serialize)]
+pub struct JobChange {
+    pub folder_name: String,
+    pub job_name: String,
+    /// Differing attributes, sorted by name
+    pub changes: Vec<AttributeChange>,
+}
+
+/// A folder whose own attributes differ between exports
+#[derive(Debug, Clone, Serialize)]
+pub struct FolderChange {
+    pub folder_name: String,
+    /// Differing attributes, sorted by name
+    pub changes: Vec<AttributeChange>,
+}
+
+/// One attribute with different values in the two exports
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AttributeChange {
+    /// Attribute name as serialized (e.g. `cmdline`, `in_conditions`)
+    pub field: String,
+    /// Value in the earlier export (null if unset)
+    pub old: Value,
+    /// Value in the later export (null if unset)
+    pub new: Value,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{Condition, FolderType};
+
+    fn folder(name: &str, jobs: Vec<Job>) -> Folder {
+        let mut folder = Folder::new(name.to_string(), FolderType::Simple);
+        for job in jobs {
+            folder.add_job(job);
+        }
+        folder
+    }
+
+    fn job(name: &str, folder: &str) -> Job {
+        Job::new(name.to_string(), folder.to_string())
+    }
+
+    #[test]
+    fn test_diff_reports_added_removed_and_modified_jobs() {
+        let mut extract = job("EXTRACT", "DAILY");
+        extract.cmdline = Some("extract.sh".to_string());
+        let old = vec![
+            folder("DAILY", vec![extract.clone(), job("LOAD", "DAILY"), job("PURGE", "DAILY")]),
+            folder("RETIRED", vec![]),
+        ];
+
+        let mut changed = extract;
+        changed.cmdline = Some("extract.sh --full".to_string());
+        changed.in_conditions.push(Condition::new_in("SRC-READY".to_string()));
+        let mut daily = folder("DAILY", vec![changed, job("LOAD", "DAILY"), job("REPORT", "DAILY")]);
+        daily.datacenter = Some("DC2".to_string());
+        let new = vec![daily, folder("MONTHLY", vec![])];
+
+        let diff = DiffExports::new().execute(&old, &new);
+
+        assert_eq!(diff.added_folders, vec!["MONTHLY"]);
+        assert_eq!(diff.removed_folders, vec!["RETIRED"]);
+        assert_eq!(diff.modified_folders.len(), 1);
+        assert_eq!(diff.modified_folders[0].changes[0].field, "datacenter");
+        assert_eq!(diff.added_jobs, vec![JobKey { folder_name: "DAILY".to_string(), job_name: "REPORT".to_string() }]);
+        assert_eq!(diff.removed_jobs, vec![JobKey { folder_name: "DAILY".to_string(), job_name: "PURGE".to_string() }]);
+        assert_eq!(diff.unchanged_jobs, 1);
+
+        let fields: Vec<&str> = diff.modified_jobs[0].changes.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(diff.modified_jobs[0].job_name, "EXTRACT");
+        assert_eq!(fields, vec!["cmdline", "in_conditions"]);
+        assert_eq!(diff.modified_jobs[0].changes[0].new, Value::from("extract.sh --full"));
+    }
+
+    #[test]
+    fn test_identical_exports_have_empty_diff() {
+        let folders = vec![folder("DAILY", vec![job("EXTRACT", "DAILY")])];
+        let diff = DiffExports::new().execute(&folders, &folders.clone());
+        assert!(diff.is_empty());
+        assert_eq!(diff.unchanged_jobs, 1);
+    }
+}
@@ -0,0 +1,236 @@
+//! Estimate Timeline use case module
+//!
+//! This module projects how long the migration takes in calendar terms.
+//! Waves run one after another; within a wave, jobs are shared among a
+//! configurable number of engineers working in parallel, so a wave lasts as
+//! long as its busiest engineer.
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use super::calculate_complexity::JobComplexityResult;
+use super::determine_migration_waves::MigrationWave;
+
+/// Default working hours per engineer per day
+pub const DEFAULT_HOURS_PER_DAY: u32 = 8;
+
+/// Use case estimating per-wave durations and the overall migration timeline
+pub struct EstimateTimeline {
+    /// Engineers migrating jobs of the same wave in parallel
+    engineers: usize,
+    /// Working hours per engineer per day
+    hours_per_day: u32,
+    /// First working day of the migration, if known
+    start_date: Option<NaiveDate>,
+}
+
+impl EstimateTimeline {
+    /// Creates a timeline estimate for a team of engineers
+    ///
+    /// # Arguments
+    ///
+    /// * `engineers` - Engineers working in parallel (at least 1)
+    pub fn new(engineers: usize) -> Self {
+        Self {
+            engineers: engineers.max(1),
+            hours_per_day: DEFAULT_HOURS_PER_DAY,
+            start_date: None,
+        }
+    }
+
+    /// Sets the working hours per engineer per day (at least 1)
+    pub fn with_hours_per_day(mut self, hours_per_day: u32) -> Self {
+        self.hours_per_day = hours_per_day.max(1);
+        self
+    }
+
+    /// Sets the first working day, enabling projected wave dates
+    pub fn with_start_date(mut self, start_date: NaiveDate) -> Self {
+        self.start_date = Some(start_date);
+        self
+    }
+
+    /// Estimates the timeline of a migration plan
+    ///
+    /// Each job's effort comes from its migration difficulty. Within a wave,
+    /// the largest jobs are handed out first, each to the least busy engineer.
+    /// Waves start on the working day after the previous wave ends; dates skip
+    /// weekends.
+    ///
+    /// # Arguments
+    ///
+    /// * `waves` - Migration waves, in any order
+    /// * `results` - Complexity results with their assigned waves
+    ///
+    /// # Returns
+    ///
+    /// The timeline with one entry per non-empty wave, in wave order
+    pub fn execute(&self, waves: &[MigrationWave], results: &[JobComplexityResult]) -> MigrationTimeline {
+        let mut wave_numbers: Vec<usize> = waves.iter().map(|w| w.wave).collect();
+        wave_numbers.sort_unstable();
+        wave_numbers.dedup();
+
+        let mut timeline_waves = Vec::new();
+        let mut elapsed_days = 0;
+        for wave in wave_numbers {
+            let mut efforts: Vec<u32> = results.iter()
+                .filter(|r| r.migration_wave == wave)
+                .map(|r| r.migration_difficulty.estimated_effort_hours())
+                .collect();
+            if efforts.is_empty() {
+                continue;
+            }
+
+            let duration_hours = self.makespan(&mut efforts);
+            let duration_days = duration_hours.div_ceil(self.hours_per_day);
+            let start_day = elapsed_days + 1;
+            elapsed_days += duration_days;
+
+            timeline_waves.push(WaveTimeline {
+                wave,
+                job_count: efforts.len(),
+                total_effort_hours: efforts.iter().sum(),
+                duration_hours,
+                duration_days,
+                start_day,
+                end_day: elapsed_days,
+                start_date: self.start_date.map(|d| Self::working_day(d, start_day)),
+                end_date: self.start_date.map(|d| Self::working_day(d, elapsed_days)),
+            });
+        }
+
+        let longest_wave = timeline_waves.iter()
+            .max_by_key(|w| (w.duration_hours, std::cmp::Reverse(w.wave)))
+            .map(|w| w.wave);
+        let projected_end_date = timeline_waves.last().and_then(|w| w.end_date);
+
+        MigrationTimeline {
+            engineers: self.engineers,
+            hours_per_day: self.hours_per_day,
+            start_date: self.start_date,
+            total_days: elapsed_days,
+            longest_wave,
+            projected_end_date,
+            waves: timeline_waves,
+        }
+    }
+
+    /// Hours until the last engineer finishes, assigning largest jobs first
+    fn makespan(&self, efforts: &mut [u32]) -> u32 {
+        efforts.sort_unstable_by(|a, b| b.cmp(a));
+        let mut engineer_hours = vec![0u32; self.engineers.min(efforts.len())];
+        for effort in efforts.iter() {
+            if let Some(least_busy) = engineer_hours.iter_mut().min() {
+                *least_busy += effort;
+            }
+        }
+        engineer_hours.into_iter().max().unwrap_or(0)
+    }
+
+    /// Gets the nth working day (1-based, weekends skipped) from a start date
+    ///
+    /// A start date on a weekend counts from the following Monday.
+    fn working_day(start: NaiveDate, n: u32) -> NaiveDate {
+        let mut date = start;
+        let mut counted = 0;
+        loop {
+            if !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+                counted += 1;
+                if counted == n {
+                    return date;
+                }
+            }
+            date += Duration::days(1);
+        }
+    }
+}
+
+/// Estimated schedule of one migration wave
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WaveTimeline {
+    pub wave: usize,
+    pub job_count: usize,
+    /// Sum of all job efforts in the wave
+    pub total_effort_hours: u32,
+    /// Wall-clock hours with the engineers working in parallel
+    pub duration_hours: u32,
+    /// Working days the wave takes
+    pub duration_days: u32,
+    /// First working day of the wave, counted from 1
+    pub start_day: u32,
+    /// Last working day of the wave
+    pub end_day: u32,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+}
+
+/// Estimated schedule of the whole migration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MigrationTimeline {
+    pub engineers: usize,
+    pub hours_per_day: u32,
+    pub start_date: Option<NaiveDate>,
+    /// Working days from the start of the first wave to the end of the last
+    pub total_days: u32,
+    /// Wave with the longest duration
+    pub longest_wave: Option<usize>,
+    /// Last working day of the final wave, when a start date is known
+    pub projected_end_date: Option<NaiveDate>,
+    pub waves: Vec<WaveTimeline>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::MigrationDifficulty;
+
+    fn result(name: &str, wave: usize, difficulty: MigrationDifficulty) -> JobComplexityResult {
+        JobComplexityResult {
+            migration_difficulty: difficulty,
+            migration_wave: wave,
+            ..JobComplexityResult::for_test(name, "FOLDER")
+        }
+    }
+
+    fn wave(wave: usize) -> MigrationWave {
+        MigrationWave {
+            wave,
+            jobs: Vec::new(),
+            reason: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_timeline_runs_waves_in_order_with_parallel_engineers() {
+        // Wave 1: four 4h jobs; wave 2: one 16h job and two 8h jobs
+        let results = vec![
+            result("A", 1, MigrationDifficulty::Easy),
+            result("B", 1, MigrationDifficulty::Easy),
+            result("C", 1, MigrationDifficulty::Easy),
+            result("D", 1, MigrationDifficulty::Easy),
+            result("E", 2, MigrationDifficulty::Hard),
+            result("F", 2, MigrationDifficulty::Medium),
+            result("G", 2, MigrationDifficulty::Medium),
+        ];
+        let waves = vec![wave(2), wave(1), wave(3)];
+
+        let solo = EstimateTimeline::new(1).execute(&waves, &results);
+        assert_eq!(solo.waves.iter().map(|w| w.duration_hours).collect::<Vec<_>>(), vec![16, 32]);
+        assert_eq!(solo.total_days, 6);
+
+        // Monday 2024-01-01; two engineers halve wave 1 but wave 2 is bound by its 16h job
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let team = EstimateTimeline::new(2).with_start_date(start).execute(&waves, &results);
+        assert_eq!(team.waves.len(), 2);
+        assert_eq!((team.waves[0].wave, team.waves[0].duration_hours, team.waves[0].end_day), (1, 8, 1));
+        assert_eq!((team.waves[1].wave, team.waves[1].duration_hours, team.waves[1].start_day), (2, 16, 2));
+        assert_eq!(team.total_days, 3);
+        assert_eq!(team.longest_wave, Some(2));
+        assert_eq!(team.waves[1].start_date, NaiveDate::from_ymd_opt(2024, 1, 2));
+        assert_eq!(team.projected_end_date, NaiveDate::from_ymd_opt(2024, 1, 3));
+
+        // Twelve working days from Monday 1 January skip two weekends
+        let long = EstimateTimeline::new(1).with_hours_per_day(4).with_start_date(start).execute(&waves, &results);
+        assert_eq!(long.total_days, 12);
+        assert_eq!(long.projected_end_date, NaiveDate::from_ymd_opt(2024, 1, 16));
+    }
+}
@@ -1,9 +1,15 @@
 pub mod analyze_jobs;
 pub mod calculate_complexity;
 pub mod build_dependency_graph;
+pub mod build_folder_matrix;
 pub mod determine_migration_waves;
+pub mod diff_exports;
+pub mod estimate_timeline;
 
 pub use analyze_jobs::AnalyzeJobs;
 pub use calculate_complexity::CalculateComplexity;
 pub use build_dependency_graph::BuildDependencyGraph;
+pub use build_folder_matrix::{BuildFolderMatrix, FolderDependencyMatrix};
 pub use determine_migration_waves::DetermineMigrationWaves;
+pub use diff_exports::{AttributeChange, DiffExports, ExportDiff, FolderChange, JobChange, JobKey};
+pub use estimate_timeline::{EstimateTimeline, MigrationTimeline, WaveTimeline};
@@ -32,6 +32,10 @@ pub struct Condition {
     pub odate: Option<String>,
     /// Logical operator for multiple conditions (AND/OR)
     pub and_or: Option<String>,
+    /// Whether an output condition is added or deleted (SIGN, e.g. "+"/"-"
+    /// or "ADD"/"DEL"); None for input conditions
+    #[serde(default)]
+    pub sign: Option<String>,
 }
 
 impl Condition {
@@ -50,6 +54,7 @@ impl Condition {
             condition_type: ConditionType::In,
             odate: None,
             and_or: None,
+            sign: None,
         }
     }
 
@@ -68,8 +73,20 @@ impl Condition {
             condition_type: ConditionType::Out,
             odate: None,
             and_or: None,
+            sign: None,
         }
     }
+
+    /// Checks whether an output condition deletes the condition (SIGN "-"
+    /// or "DEL") instead of adding it
+    ///
+    /// Consumers usually delete the condition they waited for, so a
+    /// deleting job is not a producer of it.
+    pub fn is_deletion(&self) -> bool {
+        self.sign.as_deref()
+            .map(str::trim)
+            .is_some_and(|sign| sign == "-" || sign.eq_ignore_ascii_case("DEL"))
+    }
 }
 
 /// Represents an event-based condition with associated actions
@@ -89,6 +106,21 @@ pub struct OnCondition {
     pub actions: Vec<DoAction>,
 }
 
+/// What an OnCondition reacts to
+///
+/// Status-based conditions map onto Airflow task states and trigger rules;
+/// pattern-based ones scan job output text and need custom log parsing.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum OnConditionKind {
+    /// Matches the job's completion status or return code
+    StatusBased,
+    /// Matches a text pattern in the job output
+    PatternBased,
+}
+
+/// Extra complexity of an OnCondition that matches output text
+const PATTERN_COMPLEXITY: usize = 2;
+
 /// Actions that can be performed when an OnCondition is triggered
 ///
 /// These actions allow jobs to interact with the Control-M environment
@@ -124,9 +156,21 @@ impl OnCondition {
         }
     }
 
+    /// Classifies this OnCondition by what it matches
+    ///
+    /// # Returns
+    ///
+    /// `PatternBased` when a non-blank PATTERN is set, `StatusBased` otherwise
+    pub fn kind(&self) -> OnConditionKind {
+        match self.pattern.as_deref().map(str::trim) {
+            Some(pattern) if !pattern.is_empty() => OnConditionKind::PatternBased,
+            _ => OnConditionKind::StatusBased,
+        }
+    }
+
     /// Calculates the complexity score of this OnCondition
     ///
-    /// Complexity is based on the number of actions and presence of patterns.
+    /// Complexity is based on the number of actions and the condition kind.
     /// More actions and pattern matching increase complexity.
     ///
     /// # Returns
@@ -135,8 +179,8 @@ impl OnCondition {
     pub fn complexity(&self) -> usize {
         let mut score = 1; // Base complexity
         score += self.actions.len(); // Each action adds complexity
-        if self.pattern.is_some() {
-            score += 2; // Pattern matching adds significant complexity
+        if self.kind() == OnConditionKind::PatternBased {
+            score += PATTERN_COMPLEXITY; // Pattern matching adds significant complexity
         }
         score
     }
@@ -148,6 +192,77 @@ impl Default for OnCondition {
     }
 }
 
+/// How a notification is delivered
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum NotificationChannel {
+    /// DOMAIL email
+    Mail,
+    /// DOSHOUT alert
+    Shout,
+}
+
+impl NotificationChannel {
+    /// Gets the channel name as stored in the `do_actions.action_type` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationChannel::Mail => "Mail",
+            NotificationChannel::Shout => "Shout",
+        }
+    }
+}
+
+/// A single recipient of a DOMAIL or DOSHOUT action
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NotificationTarget {
+    /// Delivery channel the destination came from
+    pub channel: NotificationChannel,
+    /// Email address or shout destination (e.g. `EM`, `U-OPS`)
+    pub destination: String,
+}
+
+impl NotificationTarget {
+    /// Splits a DEST attribute into one target per recipient
+    ///
+    /// Control-M accepts several recipients in one DEST, separated by `;`
+    /// or `,`; blank entries are dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - Channel of the action the DEST belongs to
+    /// * `dest` - Raw DEST attribute value
+    ///
+    /// # Returns
+    ///
+    /// One target per non-blank recipient, in the order listed
+    pub fn parse_dest(channel: NotificationChannel, dest: &str) -> Vec<Self> {
+        dest.split([';', ','])
+            .map(str::trim)
+            .filter(|recipient| !recipient.is_empty())
+            .map(|recipient| Self {
+                channel,
+                destination: recipient.to_string(),
+            })
+            .collect()
+    }
+
+    /// Gets the notification targets of a DoAction
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - Action to inspect
+    ///
+    /// # Returns
+    ///
+    /// The recipients of a Mail or Shout action, empty for other actions
+    pub fn from_action(action: &DoAction) -> Vec<Self> {
+        match action {
+            DoAction::Mail { dest, .. } => Self::parse_dest(NotificationChannel::Mail, dest),
+            DoAction::Shout { dest, .. } => Self::parse_dest(NotificationChannel::Shout, dest),
+            _ => Vec::new(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -171,4 +286,17 @@ mod tests {
         on_cond.pattern = Some("ERROR".to_string());
         assert_eq!(on_cond.complexity(), 5);
     }
+
+    #[test]
+    fn test_notification_targets_split_recipients() {
+        let mail = DoAction::Mail {
+            dest: "ops@example.com; dba@example.com,".to_string(),
+            message: "failed".to_string(),
+        };
+        let targets = NotificationTarget::from_action(&mail);
+        let destinations: Vec<_> = targets.iter().map(|t| t.destination.as_str()).collect();
+        assert_eq!(destinations, vec!["ops@example.com", "dba@example.com"]);
+        assert!(targets.iter().all(|t| t.channel == NotificationChannel::Mail));
+        assert!(NotificationTarget::from_action(&DoAction::Action("x".to_string())).is_empty());
+    }
 }
@@ -30,12 +30,20 @@ pub struct Dependency {
     pub from_job: String,
     /// Name of the job that depends on the predecessor (successor)
     pub to_job: String,
+    /// Folder of the successor job, when known; job names are only unique
+    /// within a folder
+    #[serde(default)]
+    pub to_folder: Option<String>,
     /// Type of dependency mechanism
     pub dependency_type: DependencyType,
     /// Name of the condition (if dependency is condition-based)
     pub condition_name: Option<String>,
     /// Name of the resource (if dependency is resource-based)
     pub resource_name: Option<String>,
+    /// Jobs that set the condition as an out-condition, resolved across
+    /// all parsed folders (empty if no job produces it)
+    #[serde(default)]
+    pub producer_jobs: Vec<String>,
 }
 
 impl Dependency {
@@ -58,9 +66,11 @@ impl Dependency {
         Self {
             from_job,
             to_job,
+            to_folder: None,
             dependency_type,
             condition_name: None,
             resource_name: None,
+            producer_jobs: Vec::new(),
         }
     }
 
@@ -78,6 +88,20 @@ impl Dependency {
         self
     }
 
+    /// Sets the folder of the successor job
+    ///
+    /// # Arguments
+    ///
+    /// * `to_folder` - Folder containing the successor job
+    ///
+    /// # Returns
+    ///
+    /// Self with the successor folder set
+    pub fn with_to_folder(mut self, to_folder: String) -> Self {
+        self.to_folder = Some(to_folder);
+        self
+    }
+
     /// Adds a resource name to this dependency
     ///
     /// # Arguments
@@ -91,6 +115,25 @@ impl Dependency {
         self.resource_name = Some(resource_name);
         self
     }
+
+    /// Sets the jobs that produce this dependency's condition
+    ///
+    /// # Arguments
+    ///
+    /// * `producer_jobs` - Names of the jobs that set the condition
+    ///
+    /// # Returns
+    ///
+    /// Self with the producer jobs set
+    pub fn with_producers(mut self, producer_jobs: Vec<String>) -> Self {
+        self.producer_jobs = producer_jobs;
+        self
+    }
+
+    /// Checks whether at least one producer job was found for the condition
+    pub fn is_resolved(&self) -> bool {
+        !self.producer_jobs.is_empty()
+    }
 }
 
 #[cfg(test)]
@@ -6,6 +6,12 @@
 use serde::{Deserialize, Serialize};
 use super::Job;
 
+/// Default maximum nesting depth allowed for folder hierarchies
+///
+/// Guards recursive parsing and exporting against malformed or maliciously
+/// deep exports that would otherwise overflow the stack.
+pub const DEFAULT_MAX_FOLDER_DEPTH: usize = 64;
+
 /// Types of folders in Control-M
 ///
 /// Different folder types have different capabilities and behaviors.
@@ -167,6 +173,20 @@ impl Folder {
         jobs
     }
 
+    /// Keeps only the jobs matching a predicate, in this folder and all sub-folders
+    ///
+    /// Folders left without jobs are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `keep` - Returns `true` for jobs to keep
+    pub fn retain_jobs<F: FnMut(&Job) -> bool>(&mut self, keep: &mut F) {
+        self.jobs.retain(|job| keep(job));
+        for sub_folder in &mut self.sub_folders {
+            sub_folder.retain_jobs(keep);
+        }
+    }
+
     /// Calculates the depth of the folder hierarchy
     ///
     /// # Returns
@@ -3,8 +3,10 @@
 //! This module defines the core Job entity which represents a Control-M job
 //! with all its properties, dependencies, and scheduling information.
 
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use crate::domain::value_objects::FileTransferSpec;
 
 /// Represents a Control-M job with all its configuration and metadata
 ///
@@ -221,6 +223,9 @@ pub struct Job {
     pub out_conditions: Vec<super::Condition>,
     /// Event-based conditions with actions (e.g., on completion, on error)
     pub on_conditions: Vec<super::OnCondition>,
+    /// Shout notifications sent by this job
+    #[serde(default)]
+    pub shouts: Vec<super::Shout>,
     
     /// Control resources (mutexes) required by this job
     pub control_resources: Vec<super::ControlResource>,
@@ -231,6 +236,9 @@ pub struct Job {
     pub variables: HashMap<String, String>,
     /// Auto-edit variables that are automatically set
     pub auto_edits: HashMap<String, String>,
+    /// Files this job transfers or watches, detected at parse time
+    #[serde(default)]
+    pub file_transfers: Vec<FileTransferSpec>,
     
     /// User who created this job
     pub created_by: Option<String>,
@@ -366,10 +374,12 @@ impl Job {
             in_conditions: Vec::new(),
             out_conditions: Vec::new(),
             on_conditions: Vec::new(),
+            shouts: Vec::new(),
             control_resources: Vec::new(),
             quantitative_resources: Vec::new(),
             variables: HashMap::new(),
             auto_edits: HashMap::new(),
+            file_transfers: Vec::new(),
             created_by: None,
             creation_date: None,
             change_userid: None,
@@ -387,6 +397,18 @@ impl Job {
         self.critical
     }
 
+    /// Parses the job priority as a number for ordering
+    ///
+    /// The raw `priority` string is kept as written; this gives the value
+    /// used when comparing priorities, so "10" orders after "2".
+    ///
+    /// # Returns
+    ///
+    /// The numeric priority, or None if unset or not numeric
+    pub fn priority_value(&self) -> Option<u32> {
+        self.priority.as_deref().and_then(|p| p.trim().parse().ok())
+    }
+
     /// Checks if this job has any dependencies
     ///
     /// Dependencies include input conditions and control resources
@@ -417,6 +439,51 @@ impl Job {
     pub fn has_complex_scheduling(&self) -> bool {
         self.scheduling.has_calendar() || self.cyclic
     }
+
+    /// Checks if this job is only ever triggered manually or on demand
+    ///
+    /// Such jobs have no calendar, cyclic run or time window and no
+    /// dependencies, so they fit neither a scheduled DAG nor a sensor-based
+    /// migration pattern and need human classification (or are obsolete).
+    ///
+    /// # Returns
+    ///
+    /// `true` if the job has no scheduling and no dependencies, `false` otherwise
+    pub fn is_on_demand(&self) -> bool {
+        !self.has_complex_scheduling()
+            && !self.scheduling.has_time_window()
+            && !self.has_dependencies()
+    }
+
+    /// Collects every DOMAIL and DOSHOUT recipient across the job's ON conditions
+    ///
+    /// # Returns
+    ///
+    /// Each recipient once, tagged with its channel, in the order first seen
+    pub fn notification_targets(&self) -> Vec<super::NotificationTarget> {
+        let mut targets: Vec<super::NotificationTarget> = Vec::new();
+        for target in self.on_conditions.iter()
+            .flat_map(|on_cond| &on_cond.actions)
+            .flat_map(super::NotificationTarget::from_action)
+        {
+            if !targets.contains(&target) {
+                targets.push(target);
+            }
+        }
+        targets
+    }
+
+    /// Parses the date of the job's last modification
+    ///
+    /// Control-M writes CHANGE_DATE as `YYYYMMDD`.
+    ///
+    /// # Returns
+    ///
+    /// The change date, or None if the job has none or it is not a valid date
+    pub fn last_changed(&self) -> Option<NaiveDate> {
+        self.change_date.as_deref()
+            .and_then(|date| NaiveDate::parse_from_str(date.trim(), "%Y%m%d").ok())
+    }
 }
 
 #[cfg(test)]
@@ -442,8 +509,43 @@ mod tests {
             condition_type: super::super::ConditionType::In,
             odate: None,
             and_or: None,
+            sign: None,
         });
         
         assert_eq!(job.dependency_count(), 1);
     }
+
+    #[test]
+    fn test_priority_value_parses_numeric_priority() {
+        let mut job = Job::new("TEST_JOB".to_string(), "TEST_FOLDER".to_string());
+        assert_eq!(job.priority_value(), None);
+
+        job.priority = Some(" 10".to_string());
+        assert_eq!(job.priority_value(), Some(10));
+
+        job.priority = Some("AA".to_string());
+        assert_eq!(job.priority_value(), None);
+    }
+
+    #[test]
+    fn test_last_changed_parses_controlm_date() {
+        let mut job = Job::new("TEST_JOB".to_string(), "TEST_FOLDER".to_string());
+        assert_eq!(job.last_changed(), None);
+
+        job.change_date = Some("20240315".to_string());
+        assert_eq!(job.last_changed(), NaiveDate::from_ymd_opt(2024, 3, 15));
+
+        job.change_date = Some("2024-03-15".to_string());
+        assert_eq!(job.last_changed(), None);
+    }
+
+    #[test]
+    fn test_job_without_shouts_deserializes() {
+        let job = Job::new("TEST_JOB".to_string(), "TEST_FOLDER".to_string());
+        let mut value = serde_json::to_value(&job).unwrap();
+        value.as_object_mut().unwrap().remove("shouts");
+
+        let job: Job = serde_json::from_value(value).unwrap();
+        assert!(job.shouts.is_empty());
+    }
 }
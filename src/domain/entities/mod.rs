@@ -4,10 +4,14 @@ pub mod dependency;
 pub mod condition;
 pub mod resource;
 pub mod scheduling;
+pub mod shout;
 
 pub use job::Job;
-pub use folder::{Folder, FolderType};
+pub use folder::{Folder, FolderType, DEFAULT_MAX_FOLDER_DEPTH};
 pub use dependency::{Dependency, DependencyType};
-pub use condition::{Condition, ConditionType, OnCondition, DoAction};
+pub use condition::{
+    Condition, ConditionType, OnCondition, OnConditionKind, DoAction, NotificationChannel, NotificationTarget,
+};
 pub use resource::{ControlResource, QuantitativeResource};
 pub use scheduling::SchedulingInfo;
+pub use shout::{Shout, ShoutDestinationTable};
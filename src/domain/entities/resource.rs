@@ -36,6 +36,20 @@ impl ControlResource {
             on_fail: None,
         }
     }
+
+    /// Sets the action taken if resource acquisition fails
+    ///
+    /// # Arguments
+    ///
+    /// * `on_fail` - Release behavior from the ONFAIL attribute
+    ///
+    /// # Returns
+    ///
+    /// Self with the failure action set
+    pub fn with_on_fail(mut self, on_fail: Option<String>) -> Self {
+        self.on_fail = on_fail;
+        self
+    }
 }
 
 /// Represents a quantitative resource (semaphore)
@@ -73,6 +87,22 @@ impl QuantitativeResource {
             on_ok: None,
         }
     }
+
+    /// Sets the release behavior on job failure and success
+    ///
+    /// # Arguments
+    ///
+    /// * `on_fail` - Action from the ONFAIL attribute
+    /// * `on_ok` - Action from the ONOK attribute
+    ///
+    /// # Returns
+    ///
+    /// Self with both actions set
+    pub fn with_fail_ok(mut self, on_fail: Option<String>, on_ok: Option<String>) -> Self {
+        self.on_fail = on_fail;
+        self.on_ok = on_ok;
+        self
+    }
 }
 
 #[cfg(test)]
@@ -148,6 +148,31 @@ impl SchedulingInfo {
         self.cyclic_interval.is_some() || self.cyclic_times.is_some()
     }
 
+    /// Gets the cyclic interval in minutes
+    ///
+    /// Accepts the Control-M `INTERVAL` forms `00005M`, `00002H`, `00001D`,
+    /// a bare number of minutes, and `HH:MM`.
+    ///
+    /// # Returns
+    ///
+    /// The interval in minutes, or None when unset, unrecognized or too
+    /// large for a `u32`
+    pub fn cyclic_interval_minutes(&self) -> Option<u32> {
+        let interval = self.cyclic_interval.as_deref()?.trim();
+        if let Some((hours, minutes)) = interval.split_once(':') {
+            return hours.parse::<u32>().ok()?
+                .checked_mul(60)?
+                .checked_add(minutes.parse::<u32>().ok()?);
+        }
+        let (number, factor) = match interval.chars().last()?.to_ascii_uppercase() {
+            'M' => (&interval[..interval.len() - 1], 1),
+            'H' => (&interval[..interval.len() - 1], 60),
+            'D' => (&interval[..interval.len() - 1], 24 * 60),
+            _ => (interval, 1),
+        };
+        number.parse::<u32>().ok()?.checked_mul(factor)
+    }
+
     /// Calculates the complexity score of this scheduling configuration
     ///
     /// Complexity is based on the number and type of scheduling constraints.
@@ -207,6 +232,22 @@ mod tests {
         
         sched.cyclic_interval = Some("00:15".to_string());
         assert_eq!(sched.complexity(), 8);
+        assert_eq!(sched.cyclic_interval_minutes(), Some(15));
+
+        sched.cyclic_interval = Some("00002H".to_string());
+        assert_eq!(sched.cyclic_interval_minutes(), Some(120));
+    }
+
+    #[test]
+    fn test_cyclic_interval_minutes_overflow_is_none() {
+        let mut sched = SchedulingInfo::new();
+        for interval in ["9999999D", "99999999:00", "4294967295:01"] {
+            sched.cyclic_interval = Some(interval.to_string());
+            assert_eq!(sched.cyclic_interval_minutes(), None, "{}", interval);
+        }
+
+        sched.cyclic_interval = Some("2982616D".to_string());
+        assert_eq!(sched.cyclic_interval_minutes(), Some(2_982_616 * 24 * 60));
     }
 
     #[test]
@@ -0,0 +1,111 @@
+//! Shout entity module
+//!
+//! This module defines job-level shout notifications and shout destination tables.
+//! Shout destination tables map symbolic destinations to the actual recipients.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Represents a job-level shout (alert) notification
+///
+/// Shouts send a message to a destination when a job reaches a given state
+/// (e.g., NOTOK, LATE). The destination may be a symbolic name that is
+/// resolved through a shout destination table.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Shout {
+    /// Destination of the shout (inline or symbolic)
+    pub destination: String,
+    /// Job state that triggers the shout (e.g., OK, NOTOK, LATE_SUB)
+    pub when: Option<String>,
+    /// Urgency of the message (R=Regular, U=Urgent, V=Very urgent)
+    pub urgency: Option<String>,
+    /// Message text
+    pub message: Option<String>,
+}
+
+impl Shout {
+    /// Creates a new shout notification
+    ///
+    /// # Arguments
+    ///
+    /// * `destination` - Destination of the shout
+    ///
+    /// # Returns
+    ///
+    /// A new Shout instance
+    pub fn new(destination: String) -> Self {
+        Self {
+            destination,
+            when: None,
+            urgency: None,
+            message: None,
+        }
+    }
+}
+
+/// Represents a shout destination table
+///
+/// Maps symbolic (logical) destinations to physical recipients such as
+/// mail addresses, users or consoles.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ShoutDestinationTable {
+    /// Name of the destination table
+    pub name: String,
+    /// Logical destination -> physical recipient
+    pub destinations: HashMap<String, String>,
+}
+
+impl ShoutDestinationTable {
+    /// Creates a new, empty shout destination table
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the destination table
+    ///
+    /// # Returns
+    ///
+    /// A new ShoutDestinationTable instance
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            destinations: HashMap::new(),
+        }
+    }
+
+    /// Adds a logical to physical destination mapping
+    ///
+    /// # Arguments
+    ///
+    /// * `logical` - Symbolic destination name
+    /// * `physical` - Actual recipient
+    pub fn add_destination(&mut self, logical: String, physical: String) {
+        self.destinations.insert(logical, physical);
+    }
+
+    /// Resolves a symbolic destination to its physical recipient
+    ///
+    /// # Arguments
+    ///
+    /// * `destination` - Symbolic destination name
+    ///
+    /// # Returns
+    ///
+    /// The physical recipient, or None if the destination is not defined in this table
+    pub fn resolve(&self, destination: &str) -> Option<&str> {
+        self.destinations.get(destination).map(|s| s.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_destination() {
+        let mut table = ShoutDestinationTable::new("SHOUTDEST".to_string());
+        table.add_destination("OPS".to_string(), "ops-team@example.com".to_string());
+
+        assert_eq!(table.resolve("OPS"), Some("ops-team@example.com"));
+        assert_eq!(table.resolve("UNKNOWN"), None);
+    }
+}
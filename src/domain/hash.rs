@@ -0,0 +1,31 @@
+//! Hash module
+//!
+//! This module provides the stable, non-cryptographic hash used wherever a
+//! value needs a short identifier that is the same on every platform and
+//! release, such as analysis profile fingerprints and anonymization tokens.
+
+/// 64-bit FNV-1a hash, stable across platforms and releases
+///
+/// # Arguments
+///
+/// * `value` - Text to hash
+///
+/// # Returns
+///
+/// The FNV-1a hash of the UTF-8 bytes of `value`
+pub fn fnv1a(value: &str) -> u64 {
+    value.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fnv1a_matches_reference_values() {
+        assert_eq!(fnv1a(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a("a"), 0xaf63_dc4c_8601_ec8c);
+    }
+}
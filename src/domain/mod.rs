@@ -1,3 +1,4 @@
 pub mod entities;
+pub mod hash;
 pub mod value_objects;
 pub mod repositories;
@@ -0,0 +1,181 @@
+//! Analysis Profile value object module
+//!
+//! This module defines the AnalysisProfile value object: every setting that
+//! shapes analysis results (complexity weights, difficulty thresholds, wave
+//! strategy and count, migration target, appl_type aliases). It is stored next to the results so the numbers shown on a
+//! dashboard can be explained and reproduced.
+
+use serde::{Deserialize, Serialize};
+use crate::domain::hash::fnv1a;
+use super::migration_difficulty::{EASY_MAX_SCORE, MEDIUM_MAX_SCORE};
+use super::{ApplTypeAliases, ComplexityWeights, MigrationTarget};
+
+/// Name of the wave assignment strategy (complexity, dependencies and criticality)
+pub const DEFAULT_WAVE_STRATEGY: &str = "complexity-dependencies";
+
+/// Number of complexity tiers, and waves by default
+pub const DEFAULT_WAVE_COUNT: usize = 5;
+
+/// Settings that produced a set of analysis results
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AnalysisProfile {
+    /// Points each complexity factor contributes
+    pub complexity_weights: ComplexityWeights,
+    /// Highest complexity score rated Easy
+    pub easy_max_score: u32,
+    /// Highest complexity score rated Medium
+    pub medium_max_score: u32,
+    /// How jobs are assigned to migration waves
+    pub wave_strategy: String,
+    /// Whether out-conditions count as dependencies when scoring
+    pub count_downstream_dependencies: bool,
+    /// Number of migration waves jobs are spread over
+    #[serde(default = "default_wave_count")]
+    pub wave_count: usize,
+    /// Airflow environment operator suggestions are made for
+    #[serde(default)]
+    pub migration_target: MigrationTarget,
+    /// Grouping of raw appl_type values into categories
+    #[serde(default)]
+    pub appl_type_aliases: ApplTypeAliases,
+}
+
+/// Wave count of profiles stored before the count was recorded
+fn default_wave_count() -> usize {
+    DEFAULT_WAVE_COUNT
+}
+
+impl Default for AnalysisProfile {
+    fn default() -> Self {
+        Self {
+            complexity_weights: ComplexityWeights::default(),
+            easy_max_score: EASY_MAX_SCORE,
+            medium_max_score: MEDIUM_MAX_SCORE,
+            wave_strategy: DEFAULT_WAVE_STRATEGY.to_string(),
+            count_downstream_dependencies: false,
+            wave_count: DEFAULT_WAVE_COUNT,
+            migration_target: MigrationTarget::default(),
+            appl_type_aliases: ApplTypeAliases::default(),
+        }
+    }
+}
+
+impl AnalysisProfile {
+    /// Sets the complexity weights
+    ///
+    /// # Arguments
+    ///
+    /// * `complexity_weights` - Points each complexity factor contributes
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn with_complexity_weights(mut self, complexity_weights: ComplexityWeights) -> Self {
+        self.complexity_weights = complexity_weights;
+        self
+    }
+
+    /// Sets whether out-conditions count as dependencies
+    ///
+    /// # Arguments
+    ///
+    /// * `count_downstream_dependencies` - Whether out-conditions are scored
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn with_downstream_dependencies(mut self, count_downstream_dependencies: bool) -> Self {
+        self.count_downstream_dependencies = count_downstream_dependencies;
+        self
+    }
+
+    /// Sets the number of migration waves
+    ///
+    /// # Arguments
+    ///
+    /// * `wave_count` - Number of waves jobs are spread over
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn with_wave_count(mut self, wave_count: usize) -> Self {
+        self.wave_count = wave_count;
+        self
+    }
+
+    /// Sets the Airflow environment targeted by the migration
+    ///
+    /// # Arguments
+    ///
+    /// * `migration_target` - Airflow version and installed providers
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn with_migration_target(mut self, migration_target: MigrationTarget) -> Self {
+        self.migration_target = migration_target;
+        self
+    }
+
+    /// Sets the grouping of raw appl_type values into categories
+    ///
+    /// # Arguments
+    ///
+    /// * `appl_type_aliases` - Raw appl_type values and their categories
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn with_appl_type_aliases(mut self, appl_type_aliases: ApplTypeAliases) -> Self {
+        self.appl_type_aliases = appl_type_aliases;
+        self
+    }
+
+    /// Computes a short, stable fingerprint of the profile
+    ///
+    /// Two analyses with the same fingerprint were produced with the same
+    /// settings. This is a 64-bit FNV-1a hash of the JSON form, not a
+    /// cryptographic digest.
+    ///
+    /// # Returns
+    ///
+    /// 16 lowercase hex digits
+    pub fn fingerprint(&self) -> String {
+        let json = serde_json::to_string(self).unwrap_or_default();
+        format!("{:016x}", fnv1a(&json))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint_changes_with_settings() {
+        let default = AnalysisProfile::default();
+        assert_eq!(default.fingerprint(), AnalysisProfile::default().fingerprint());
+        assert_eq!(default.fingerprint().len(), 16);
+
+        let weights = ComplexityWeights { cyclic: 2, ..Default::default() };
+        let custom = AnalysisProfile::default().with_complexity_weights(weights);
+        assert_ne!(default.fingerprint(), custom.fingerprint());
+
+        let waves = AnalysisProfile::default().with_wave_count(8);
+        assert_ne!(default.fingerprint(), waves.fingerprint());
+        let target = AnalysisProfile::default()
+            .with_migration_target(MigrationTarget::new().with_provider("sftp"));
+        assert_ne!(default.fingerprint(), target.fingerprint());
+        let aliases = AnalysisProfile::default()
+            .with_appl_type_aliases("ORA=database".parse().unwrap());
+        assert_ne!(default.fingerprint(), aliases.fingerprint());
+    }
+
+    #[test]
+    fn test_profiles_stored_before_wave_count_read_with_defaults() {
+        let json = r#"{"complexity_weights":{},"easy_max_score":30,"medium_max_score":60,"wave_strategy":"complexity-dependencies","count_downstream_dependencies":false}"#;
+        let profile: AnalysisProfile = serde_json::from_str(json).unwrap();
+        assert_eq!(profile.wave_count, DEFAULT_WAVE_COUNT);
+        assert_eq!(profile.migration_target, MigrationTarget::default());
+        assert_eq!(profile.appl_type_aliases, ApplTypeAliases::default());
+    }
+}
@@ -0,0 +1,172 @@
+//! Application type value object module
+//!
+//! This module groups raw Control-M APPL_TYPE values into canonical
+//! categories. Sites spell the same logical type differently (`FILE_TRANS`,
+//! `AFT`, `MFT`, ...), so every feature that reasons about application
+//! types goes through an `ApplTypeAliases` map instead of matching strings.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Canonical category of a job's application type
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum ApplTypeCategory {
+    /// Moves files between hosts (Control-M AFT/MFT, SFTP, FTP)
+    FileTransfer,
+    /// Waits for files to appear
+    FileWatch,
+    /// Runs database queries or procedures
+    Database,
+    /// Runs operating system commands or scripts
+    Command,
+    /// Any application type without a known alias
+    Other,
+}
+
+impl fmt::Display for ApplTypeCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ApplTypeCategory::FileTransfer => "file-transfer",
+            ApplTypeCategory::FileWatch => "file-watch",
+            ApplTypeCategory::Database => "database",
+            ApplTypeCategory::Command => "command",
+            ApplTypeCategory::Other => "other",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for ApplTypeCategory {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().replace('_', "-").as_str() {
+            "file-transfer" => Ok(ApplTypeCategory::FileTransfer),
+            "file-watch" => Ok(ApplTypeCategory::FileWatch),
+            "database" => Ok(ApplTypeCategory::Database),
+            "command" => Ok(ApplTypeCategory::Command),
+            "other" => Ok(ApplTypeCategory::Other),
+            other => Err(format!(
+                "Unknown appl_type category '{}' (expected file-transfer, file-watch, database, command or other)",
+                other
+            )),
+        }
+    }
+}
+
+/// Built-in aliases, extended or overridden by configuration
+const DEFAULT_ALIASES: &[(&str, ApplTypeCategory)] = &[
+    ("FILE_TRANS", ApplTypeCategory::FileTransfer),
+    ("AFT", ApplTypeCategory::FileTransfer),
+    ("MFT", ApplTypeCategory::FileTransfer),
+    ("SFTP", ApplTypeCategory::FileTransfer),
+    ("FTP", ApplTypeCategory::FileTransfer),
+    ("FILEWATCH", ApplTypeCategory::FileWatch),
+    ("FILE_WATCH", ApplTypeCategory::FileWatch),
+    ("DATABASE", ApplTypeCategory::Database),
+    ("DB", ApplTypeCategory::Database),
+    ("SQL", ApplTypeCategory::Database),
+    ("OS", ApplTypeCategory::Command),
+    ("COMMAND", ApplTypeCategory::Command),
+    ("SCRIPT", ApplTypeCategory::Command),
+];
+
+/// Map from raw APPL_TYPE values to canonical categories
+///
+/// Lookups ignore case and surrounding whitespace.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ApplTypeAliases {
+    aliases: BTreeMap<String, ApplTypeCategory>,
+}
+
+impl ApplTypeAliases {
+    /// Creates an alias map holding the built-in aliases
+    pub fn new() -> Self {
+        let aliases = DEFAULT_ALIASES.iter()
+            .map(|(raw, category)| (raw.to_string(), *category))
+            .collect();
+        Self { aliases }
+    }
+
+    /// Maps a raw APPL_TYPE value to a category, replacing any existing alias
+    pub fn with_alias(mut self, raw: &str, category: ApplTypeCategory) -> Self {
+        self.aliases.insert(Self::normalize(raw), category);
+        self
+    }
+
+    /// Gets the category of a raw APPL_TYPE value
+    ///
+    /// # Arguments
+    ///
+    /// * `appl_type` - Raw APPL_TYPE value, or None if the job has none
+    ///
+    /// # Returns
+    ///
+    /// The aliased category, or `Other` for unknown or missing values
+    pub fn categorize(&self, appl_type: Option<&str>) -> ApplTypeCategory {
+        appl_type
+            .and_then(|raw| self.aliases.get(&Self::normalize(raw)))
+            .copied()
+            .unwrap_or(ApplTypeCategory::Other)
+    }
+
+    /// Gets every raw value (normalized to upper case) aliased to a category, sorted
+    pub fn aliases_for(&self, category: ApplTypeCategory) -> Vec<String> {
+        let mut raw: Vec<String> = self.aliases.iter()
+            .filter(|(_, c)| **c == category)
+            .map(|(r, _)| r.clone())
+            .collect();
+        raw.sort();
+        raw
+    }
+
+    fn normalize(raw: &str) -> String {
+        raw.trim().to_uppercase()
+    }
+}
+
+impl Default for ApplTypeAliases {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FromStr for ApplTypeAliases {
+    type Err = String;
+
+    /// Parses `RAW=category` pairs separated by commas on top of the built-in aliases
+    ///
+    /// For example `FT_V2=file-transfer,ORA=database`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut aliases = Self::new();
+        for pair in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            match pair.split_once('=') {
+                Some((raw, category)) if !raw.trim().is_empty() => {
+                    aliases = aliases.with_alias(raw, category.parse()?);
+                }
+                _ => return Err(format!("Invalid appl_type alias '{}' (expected RAW=category)", pair)),
+            }
+        }
+        Ok(aliases)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_configured_alias_extends_defaults() {
+        let aliases: ApplTypeAliases = "ft_v2=file-transfer, ORA=database".parse().unwrap();
+
+        assert_eq!(aliases.categorize(Some("FT_V2")), ApplTypeCategory::FileTransfer);
+        assert_eq!(aliases.categorize(Some(" file_trans ")), ApplTypeCategory::FileTransfer);
+        assert_eq!(aliases.categorize(Some("ora")), ApplTypeCategory::Database);
+        assert_eq!(aliases.categorize(Some("SAP")), ApplTypeCategory::Other);
+        assert_eq!(aliases.categorize(None), ApplTypeCategory::Other);
+        assert!("BROKEN".parse::<ApplTypeAliases>().is_err());
+    }
+}
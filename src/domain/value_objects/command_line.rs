@@ -0,0 +1,182 @@
+//! Command Line value object module
+//!
+//! This module splits a job's CMDLINE into a program and its arguments so it
+//! can become an Airflow `BashOperator` command or a `command` list. Quoting
+//! follows Unix shell or Windows rules, picked from the node platform.
+
+use serde::{Deserialize, Serialize};
+
+/// Quoting rules used to split a command line
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum QuotingStyle {
+    /// POSIX shell: single quotes are literal, backslash escapes
+    Unix,
+    /// Windows: only double quotes group, backslashes are path separators
+    Windows,
+}
+
+impl QuotingStyle {
+    /// Guesses the quoting style of a command line
+    ///
+    /// The node platform wins when one of its words starts with a known
+    /// platform name ("WINDOWS", "Win64", "LINUX"; "DARWIN" is not Windows);
+    /// otherwise Windows is assumed for drive-letter paths and
+    /// `.bat`/`.cmd`/`.exe` programs.
+    ///
+    /// # Arguments
+    ///
+    /// * `cmdline` - Raw command line
+    /// * `platform` - Platform of the node running the job, if known
+    pub fn detect(cmdline: &str, platform: Option<&str>) -> Self {
+        if let Some(platform) = platform {
+            let platform = platform.to_uppercase();
+            let names = |prefixes: &[&str]| platform
+                .split(|c: char| !c.is_ascii_alphanumeric())
+                .any(|word| prefixes.iter().any(|prefix| word.starts_with(prefix)));
+            if names(&["WIN"]) {
+                return QuotingStyle::Windows;
+            }
+            if names(&["UNIX", "LINUX", "AIX", "DARWIN"]) {
+                return QuotingStyle::Unix;
+            }
+        }
+
+        let lower = cmdline.trim_start_matches('"').to_lowercase();
+        let has_drive = lower.len() > 2 && lower.as_bytes()[1] == b':' && lower.as_bytes()[2] == b'\\';
+        let first = lower.split_whitespace().next().unwrap_or("");
+        let windows_program = [".bat", ".cmd", ".exe"].iter().any(|ext| first.trim_end_matches('"').ends_with(ext));
+        if has_drive || windows_program {
+            QuotingStyle::Windows
+        } else {
+            QuotingStyle::Unix
+        }
+    }
+}
+
+/// A command line split into program and arguments
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TokenizedCommand {
+    pub program: String,
+    pub args: Vec<String>,
+    pub style: QuotingStyle,
+}
+
+impl TokenizedCommand {
+    /// Splits a command line using the quoting style detected for it
+    ///
+    /// # Arguments
+    ///
+    /// * `cmdline` - Raw command line
+    /// * `platform` - Platform of the node running the job, if known
+    ///
+    /// # Returns
+    ///
+    /// The tokenized command, or None if the command line has no tokens
+    pub fn parse(cmdline: &str, platform: Option<&str>) -> Option<Self> {
+        let style = QuotingStyle::detect(cmdline, platform);
+        let mut tokens = Self::tokenize(cmdline, style).into_iter();
+        let program = tokens.next()?;
+        Some(Self {
+            program,
+            args: tokens.collect(),
+            style,
+        })
+    }
+
+    /// Splits a command line into tokens with the given quoting rules
+    ///
+    /// An unterminated quote runs to the end of the line.
+    pub fn tokenize(cmdline: &str, style: QuotingStyle) -> Vec<String> {
+        let mut tokens = Vec::new();
+        let mut current = String::new();
+        let mut in_token = false;
+        let mut quote: Option<char> = None;
+        let mut chars = cmdline.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match (style, quote, c) {
+                // Closing quote
+                (_, Some(q), c) if c == q => quote = None,
+                // Unix: backslash escapes inside double quotes only for special characters
+                (QuotingStyle::Unix, Some('"'), '\\') => match chars.peek() {
+                    Some(&next) if matches!(next, '"' | '\\' | '$' | '`') => {
+                        current.push(next);
+                        chars.next();
+                    }
+                    _ => current.push('\\'),
+                },
+                // Windows: \" is a literal quote, other backslashes are literal
+                (QuotingStyle::Windows, _, '\\') if chars.peek() == Some(&'"') => {
+                    current.push('"');
+                    chars.next();
+                    in_token = true;
+                }
+                (_, Some(_), c) => current.push(c),
+                // Opening quote
+                (QuotingStyle::Unix, None, '\'' | '"') | (QuotingStyle::Windows, None, '"') => {
+                    quote = Some(c);
+                    in_token = true;
+                }
+                // Unix: backslash escapes the next character outside quotes
+                (QuotingStyle::Unix, None, '\\') => {
+                    if let Some(next) = chars.next() {
+                        current.push(next);
+                    }
+                    in_token = true;
+                }
+                (_, None, c) if c.is_whitespace() => {
+                    if in_token {
+                        tokens.push(std::mem::take(&mut current));
+                        in_token = false;
+                    }
+                }
+                (_, None, c) => {
+                    current.push(c);
+                    in_token = true;
+                }
+            }
+        }
+
+        if in_token {
+            tokens.push(current);
+        }
+        tokens
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unix_quoted_arguments() {
+        let command = TokenizedCommand::parse(
+            r#"/opt/etl/run.sh --name "daily load" 'literal $HOME' escaped\ space"#,
+            Some("Unix"),
+        ).unwrap();
+
+        assert_eq!(command.style, QuotingStyle::Unix);
+        assert_eq!(command.program, "/opt/etl/run.sh");
+        assert_eq!(command.args, vec!["--name", "daily load", "literal $HOME", "escaped space"]);
+    }
+
+    #[test]
+    fn test_windows_quoted_path() {
+        let command = TokenizedCommand::parse(
+            r#""C:\Program Files\ETL\load.exe" /in:C:\data\in.csv "two words""#,
+            None,
+        ).unwrap();
+
+        assert_eq!(command.style, QuotingStyle::Windows);
+        assert_eq!(command.program, r"C:\Program Files\ETL\load.exe");
+        assert_eq!(command.args, vec![r"/in:C:\data\in.csv", "two words"]);
+    }
+
+    #[test]
+    fn test_detect_matches_platform_words() {
+        assert_eq!(QuotingStyle::detect("run.sh", Some("Windows 2019")), QuotingStyle::Windows);
+        assert_eq!(QuotingStyle::detect("run.sh", Some("MS-WIN64")), QuotingStyle::Windows);
+        assert_eq!(QuotingStyle::detect(r"C:\etl\run.bat", Some("DARWIN")), QuotingStyle::Unix);
+        assert_eq!(QuotingStyle::detect("run.sh", Some("Darwin")), QuotingStyle::Unix);
+    }
+}
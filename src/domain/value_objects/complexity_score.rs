@@ -5,6 +5,16 @@
 
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use super::ComplexityWeights;
+
+/// Names of the complexity factors, in the order of the scoring methodology
+///
+/// `environment` and `rerun` are scored by the complexity calculator on top
+/// of the metric-based factors.
+pub const COMPLEXITY_FACTORS: &[&str] = &[
+    "dependencies", "dependency_depth", "conditions", "variables",
+    "on_conditions", "cyclic", "resources", "scheduling", "environment", "rerun",
+];
 
 /// Represents a job's complexity score
 ///
@@ -59,6 +69,7 @@ impl ComplexityScore {
     /// # Returns
     ///
     /// A ComplexityScore calculated from the weighted metrics
+    #[allow(clippy::too_many_arguments)]
     pub fn from_metrics(
         dependency_count: usize,
         dependency_depth: usize,
@@ -72,20 +83,57 @@ impl ComplexityScore {
         control_resources: usize,
         scheduling_complexity: usize,
     ) -> Self {
-        // Weighted complexity calculation
-        let score = (dependency_count * 3)              // Dependencies moderately increase complexity
-            + (dependency_depth * 5)                    // Deep dependency chains are highly complex
-            + (in_conditions * 2)                       // Input conditions add complexity
-            + (out_conditions * 2)                      // Output conditions add complexity
-            + variables_count                           // Variables add minor complexity
-            + (on_conditions * 4)                       // Event conditions are moderately complex
-            + (on_conditions_complexity * 5)            // Complex event actions are highly complex
-            + if is_cyclic { 15 } else { 0 }           // Cyclic jobs are significantly more complex
-            + (quantitative_resources * 3)              // Resource management adds complexity
-            + (control_resources * 3)                   // Mutex management adds complexity
-            + (scheduling_complexity * 2);              // Complex scheduling adds complexity
-
-        Self(score as u32)
+        let factors = Self::factor_scores(
+            dependency_count,
+            dependency_depth,
+            in_conditions,
+            out_conditions,
+            variables_count,
+            on_conditions,
+            on_conditions_complexity,
+            is_cyclic,
+            quantitative_resources,
+            control_resources,
+            scheduling_complexity,
+        );
+        Self(factors.iter().map(|(_, points)| points).sum())
+    }
+
+    /// Calculates the points each complexity factor contributes
+    ///
+    /// Takes the same metrics as `from_metrics`; the points sum to its score.
+    /// Uses the default weights (see `ComplexityWeights`).
+    ///
+    /// # Returns
+    ///
+    /// Points per factor, named as in `COMPLEXITY_FACTORS`
+    #[allow(clippy::too_many_arguments)]
+    pub fn factor_scores(
+        dependency_count: usize,
+        dependency_depth: usize,
+        in_conditions: usize,
+        out_conditions: usize,
+        variables_count: usize,
+        on_conditions: usize,
+        on_conditions_complexity: usize,
+        is_cyclic: bool,
+        quantitative_resources: usize,
+        control_resources: usize,
+        scheduling_complexity: usize,
+    ) -> Vec<(&'static str, u32)> {
+        ComplexityWeights::default().factor_scores(
+            dependency_count,
+            dependency_depth,
+            in_conditions,
+            out_conditions,
+            variables_count,
+            on_conditions,
+            on_conditions_complexity,
+            is_cyclic,
+            quantitative_resources,
+            control_resources,
+            scheduling_complexity,
+        )
     }
 
     /// Adds a value to this complexity score
@@ -0,0 +1,158 @@
+//! Complexity Weights value object module
+//!
+//! This module defines the points each complexity factor contributes per
+//! occurrence. Teams weigh migration pain differently, so the weights can be
+//! loaded from a TOML file; factors left out keep their default weight.
+
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Default points added for a job running on multiple agents
+pub const DEFAULT_MULTI_AGENT_WEIGHT: u32 = 10;
+
+/// Default points added for a job pinned to a scheduling environment
+pub const DEFAULT_SCHEDULING_ENVIRONMENT_WEIGHT: u32 = 5;
+
+/// Default points added for a job that reruns itself automatically
+pub const DEFAULT_RERUN_WEIGHT: u32 = 5;
+
+/// Points contributed by each complexity factor
+///
+/// # Example
+///
+/// ```toml
+/// # Cyclic jobs are trivial for us, ON conditions are agony
+/// cyclic = 2
+/// on_condition = 10
+/// ```
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ComplexityWeights {
+    /// Points per dependency (in-condition or control resource)
+    pub dependency: u32,
+    /// Points per level of dependency depth
+    pub dependency_depth: u32,
+    /// Points per in- or out-condition
+    pub condition: u32,
+    /// Points per variable or auto-edit
+    pub variable: u32,
+    /// Points per ON condition
+    pub on_condition: u32,
+    /// Points per unit of ON condition action complexity
+    pub on_condition_action: u32,
+    /// Points for a cyclic job
+    pub cyclic: u32,
+    /// Points per quantitative or control resource
+    pub resource: u32,
+    /// Points per unit of scheduling complexity
+    pub scheduling: u32,
+    /// Points for a job running on multiple agents (MULTY_AGENT=Y)
+    pub multi_agent: u32,
+    /// Points for a job pinned to a SCHEDULING_ENVIRONMENT
+    pub scheduling_environment: u32,
+    /// Points for a job with MAXRERUN above zero or an ON condition rerunning it
+    pub rerun: u32,
+}
+
+impl Default for ComplexityWeights {
+    fn default() -> Self {
+        Self {
+            dependency: 3,
+            dependency_depth: 5,
+            condition: 2,
+            variable: 1,
+            on_condition: 4,
+            on_condition_action: 5,
+            cyclic: 15,
+            resource: 3,
+            scheduling: 2,
+            multi_agent: DEFAULT_MULTI_AGENT_WEIGHT,
+            scheduling_environment: DEFAULT_SCHEDULING_ENVIRONMENT_WEIGHT,
+            rerun: DEFAULT_RERUN_WEIGHT,
+        }
+    }
+}
+
+impl ComplexityWeights {
+    /// Calculates the points each complexity factor contributes
+    ///
+    /// # Arguments
+    ///
+    /// * `dependency_count` - Number of job dependencies
+    /// * `dependency_depth` - Depth of dependency chain
+    /// * `in_conditions` - Number of input conditions
+    /// * `out_conditions` - Number of output conditions
+    /// * `variables_count` - Number of variables and auto-edits
+    /// * `on_conditions` - Number of event-based conditions
+    /// * `on_conditions_complexity` - Combined complexity of all on-conditions
+    /// * `is_cyclic` - Whether the job runs cyclically
+    /// * `quantitative_resources` - Number of quantitative resources
+    /// * `control_resources` - Number of control resources
+    /// * `scheduling_complexity` - Complexity score from scheduling configuration
+    ///
+    /// # Returns
+    ///
+    /// Points per factor, named as in `COMPLEXITY_FACTORS` (without
+    /// `environment` and `rerun`, which depend on job attributes)
+    #[allow(clippy::too_many_arguments)]
+    pub fn factor_scores(
+        &self,
+        dependency_count: usize,
+        dependency_depth: usize,
+        in_conditions: usize,
+        out_conditions: usize,
+        variables_count: usize,
+        on_conditions: usize,
+        on_conditions_complexity: usize,
+        is_cyclic: bool,
+        quantitative_resources: usize,
+        control_resources: usize,
+        scheduling_complexity: usize,
+    ) -> Vec<(&'static str, u32)> {
+        // Weights come from user files, so large values saturate instead of wrapping
+        let points = |count: usize, weight: u32| u32::try_from(count).unwrap_or(u32::MAX).saturating_mul(weight);
+        vec![
+            ("dependencies", points(dependency_count, self.dependency)),
+            ("dependency_depth", points(dependency_depth, self.dependency_depth)),
+            ("conditions", points(in_conditions.saturating_add(out_conditions), self.condition)),
+            ("variables", points(variables_count, self.variable)),
+            ("on_conditions", points(on_conditions, self.on_condition).saturating_add(points(on_conditions_complexity, self.on_condition_action))),
+            ("cyclic", if is_cyclic { self.cyclic } else { 0 }),
+            ("resources", points(quantitative_resources.saturating_add(control_resources), self.resource)),
+            ("scheduling", points(scheduling_complexity, self.scheduling)),
+        ]
+    }
+}
+
+impl FromStr for ComplexityWeights {
+    type Err = String;
+
+    /// Parses weights from TOML; missing factors keep their default weight
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        toml::from_str(s).map_err(|e| format!("Invalid complexity weights: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_toml_keeps_defaults_for_missing_factors() {
+        let weights: ComplexityWeights = "cyclic = 2\non_condition = 10\n".parse().unwrap();
+        assert_eq!(weights.cyclic, 2);
+        assert_eq!(weights.on_condition, 10);
+        assert_eq!(weights.dependency, ComplexityWeights::default().dependency);
+
+        assert!("cyclc = 2".parse::<ComplexityWeights>().is_err());
+    }
+
+    #[test]
+    fn test_large_weights_saturate_instead_of_wrapping() {
+        let weights: ComplexityWeights = "dependency = 4294967295\non_condition_action = 4294967295\n".parse().unwrap();
+        let factors = weights.factor_scores(3, 0, 0, 0, 0, 1, 2, false, 0, 0, 0);
+        let points = |factor: &str| factors.iter().find(|(name, _)| *name == factor).unwrap().1;
+        assert_eq!(points("dependencies"), u32::MAX);
+        assert_eq!(points("on_conditions"), u32::MAX);
+    }
+}
@@ -0,0 +1,255 @@
+//! File Transfer value object module
+//!
+//! This module extracts the files a job moves or waits for, so file-transfer
+//! and file-watcher jobs can be mapped to Airflow transfer operators and
+//! filesystem/S3 sensors. Paths come from the Control-M AFT/FTP variables
+//! (`%%FTP-LPATHn`, `%%FTP-RPATHn`, `%%FTP-UPLOADn`, `%%FTP-RHOST`), from
+//! `ctmfw` and `scp` command lines, and from the `*PATH`/`*FILENAME`
+//! variables of file-watch jobs. `%%NAME` references to the job's own
+//! variables are resolved; anything else is kept as written.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use super::{ApplTypeCategory, TokenizedCommand};
+
+/// scp options that take a value, so the value is not read as a path
+const SCP_OPTIONS_WITH_VALUE: &[&str] = &["-P", "-i", "-o", "-F", "-c", "-l", "-S", "-J"];
+
+/// What a job does with a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransferDirection {
+    /// Waits for a file to appear (or disappear)
+    Watch,
+    /// Sends a local file to a remote host
+    Upload,
+    /// Fetches a remote file to the local host
+    Download,
+}
+
+impl fmt::Display for TransferDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            TransferDirection::Watch => "watch",
+            TransferDirection::Upload => "upload",
+            TransferDirection::Download => "download",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for TransferDirection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "watch" => Ok(TransferDirection::Watch),
+            "upload" => Ok(TransferDirection::Upload),
+            "download" => Ok(TransferDirection::Download),
+            other => Err(format!("Unknown transfer direction '{}' (expected watch, upload or download)", other)),
+        }
+    }
+}
+
+/// A file a job transfers or watches
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileTransferSpec {
+    pub direction: TransferDirection,
+    /// Local path: the watched file, the upload source or the download target
+    pub path: String,
+    /// Remote path of an upload or download
+    pub remote_path: Option<String>,
+    /// Remote host of an upload or download
+    pub host: Option<String>,
+}
+
+impl FileTransferSpec {
+    /// Detects the files a job transfers or watches
+    ///
+    /// # Arguments
+    ///
+    /// * `cmdline` - Job command line, if any
+    /// * `category` - Category of the job's APPL_TYPE
+    /// * `variables` - Job variables and auto-edits, keyed with or without `%%`
+    ///
+    /// # Returns
+    ///
+    /// One spec per transferred or watched file, empty for other jobs
+    pub fn detect(cmdline: Option<&str>, category: ApplTypeCategory, variables: &HashMap<String, String>) -> Vec<Self> {
+        let variables: HashMap<String, &str> = variables.iter()
+            .map(|(name, value)| (Self::variable_key(name), value.as_str()))
+            .collect();
+
+        let mut specs = Self::from_ftp_variables(&variables);
+        if let Some(command) = cmdline.and_then(|cmdline| TokenizedCommand::parse(cmdline, None)) {
+            specs.extend(Self::from_command(&command, &variables));
+        }
+        if specs.is_empty() && category == ApplTypeCategory::FileWatch {
+            let mut names: Vec<&String> = variables.keys()
+                .filter(|name| name.ends_with("PATH") || name.ends_with("FILENAME") || name.ends_with("FILE_NAME"))
+                .collect();
+            names.sort();
+            specs.extend(names.into_iter().map(|name| Self::watch(Self::resolve(variables[name], &variables))));
+        }
+        specs
+    }
+
+    fn watch(path: String) -> Self {
+        Self {
+            direction: TransferDirection::Watch,
+            path,
+            remote_path: None,
+            host: None,
+        }
+    }
+
+    /// Reads `%%FTP-LPATHn`/`%%FTP-RPATHn` pairs; `%%FTP-UPLOADn` of 1/Y marks an upload
+    fn from_ftp_variables(variables: &HashMap<String, &str>) -> Vec<Self> {
+        let mut indexes: Vec<&str> = variables.keys()
+            .filter_map(|name| name.strip_prefix("FTP-LPATH").or_else(|| name.strip_prefix("FTP-RPATH")))
+            .collect();
+        indexes.sort();
+        indexes.dedup();
+
+        let host = variables.get("FTP-RHOST").map(|host| Self::resolve(host, variables));
+        indexes.into_iter()
+            .filter_map(|index| {
+                let local = variables.get(&format!("FTP-LPATH{}", index))?;
+                let upload = variables.get(&format!("FTP-UPLOAD{}", index))
+                    .is_some_and(|value| matches!(value.trim().to_uppercase().as_str(), "1" | "Y" | "YES" | "TRUE"));
+                Some(Self {
+                    direction: if upload { TransferDirection::Upload } else { TransferDirection::Download },
+                    path: Self::resolve(local, variables),
+                    remote_path: variables.get(&format!("FTP-RPATH{}", index)).map(|remote| Self::resolve(remote, variables)),
+                    host: host.clone(),
+                })
+            })
+            .collect()
+    }
+
+    /// Reads `ctmfw <path> ...` watches and `scp <source>... <target>` copies
+    fn from_command(command: &TokenizedCommand, variables: &HashMap<String, &str>) -> Vec<Self> {
+        let program = command.program.rsplit(['/', '\\']).next().unwrap_or("").to_lowercase();
+        match program.trim_end_matches(".exe") {
+            "ctmfw" => command.args.first()
+                .map(|path| vec![Self::watch(Self::resolve(path, variables))])
+                .unwrap_or_default(),
+            "scp" => {
+                let mut paths = Vec::new();
+                let mut args = command.args.iter();
+                while let Some(arg) = args.next() {
+                    if SCP_OPTIONS_WITH_VALUE.contains(&arg.as_str()) {
+                        args.next();
+                    } else if !arg.starts_with('-') {
+                        paths.push(Self::resolve(arg, variables));
+                    }
+                }
+                let Some((target, sources)) = paths.split_last() else { return Vec::new() };
+                match Self::split_remote(target) {
+                    Some((host, remote)) => sources.iter()
+                        .filter(|source| Self::split_remote(source).is_none())
+                        .map(|source| Self {
+                            direction: TransferDirection::Upload,
+                            path: source.clone(),
+                            remote_path: Some(remote.to_string()),
+                            host: Some(host.to_string()),
+                        })
+                        .collect(),
+                    None => sources.iter()
+                        .filter_map(|source| Self::split_remote(source))
+                        .map(|(host, remote)| Self {
+                            direction: TransferDirection::Download,
+                            path: target.clone(),
+                            remote_path: Some(remote.to_string()),
+                            host: Some(host.to_string()),
+                        })
+                        .collect(),
+                }
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Splits `[user@]host:path` into host and path; drive letters are local
+    fn split_remote(arg: &str) -> Option<(&str, &str)> {
+        let (host, path) = arg.split_once(':')?;
+        if host.is_empty() || host.contains('/') || (host.len() == 1 && path.starts_with('\\')) {
+            return None;
+        }
+        Some((host.rsplit('@').next().unwrap_or(host), path))
+    }
+
+    /// Replaces `%%NAME` references with the job's variable values
+    fn resolve(text: &str, variables: &HashMap<String, &str>) -> String {
+        let mut resolved = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find("%%") {
+            resolved.push_str(&rest[..start]);
+            let after = &rest[start + 2..];
+            let len = after.find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '#' | '@' | '$')))
+                .unwrap_or(after.len());
+            match variables.get(&after[..len].to_uppercase()) {
+                Some(value) if len > 0 => resolved.push_str(value),
+                _ => resolved.push_str(&rest[start..start + 2 + len]),
+            }
+            rest = &after[len..];
+        }
+        resolved.push_str(rest);
+        resolved
+    }
+
+    fn variable_key(name: &str) -> String {
+        name.trim().trim_start_matches("%%").to_uppercase()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect()
+    }
+
+    #[test]
+    fn test_detects_aft_transfers_with_resolved_paths() {
+        let variables = vars(&[
+            ("%%BASE", "/data/out"),
+            ("%%FTP-RHOST", "partner.example.com"),
+            ("%%FTP-LPATH1", "%%BASE/daily.csv"),
+            ("%%FTP-RPATH1", "/in/daily.csv"),
+            ("%%FTP-UPLOAD1", "1"),
+            ("%%FTP-LPATH2", "/data/in/ack.txt"),
+            ("%%FTP-RPATH2", "/out/ack.txt"),
+            ("%%FTP-UPLOAD2", "0"),
+        ]);
+
+        let specs = FileTransferSpec::detect(None, ApplTypeCategory::FileTransfer, &variables);
+
+        assert_eq!(specs.len(), 2);
+        assert_eq!(specs[0].direction, TransferDirection::Upload);
+        assert_eq!(specs[0].path, "/data/out/daily.csv");
+        assert_eq!(specs[0].remote_path.as_deref(), Some("/in/daily.csv"));
+        assert_eq!(specs[0].host.as_deref(), Some("partner.example.com"));
+        assert_eq!(specs[1].direction, TransferDirection::Download);
+    }
+
+    #[test]
+    fn test_detects_command_line_watches_and_copies() {
+        let variables = vars(&[("%%DIR", "/landing")]);
+
+        let watch = FileTransferSpec::detect(Some("ctmfw %%DIR/trigger.ok CREATE 0 60"), ApplTypeCategory::Command, &variables);
+        assert_eq!(watch, vec![FileTransferSpec::watch("/landing/trigger.ok".to_string())]);
+
+        let download = FileTransferSpec::detect(Some("scp -P 2222 etl@src-host:/exports/a.csv /landing/"), ApplTypeCategory::Command, &variables);
+        assert_eq!(download.len(), 1);
+        assert_eq!(download[0].direction, TransferDirection::Download);
+        assert_eq!((download[0].host.as_deref(), download[0].path.as_str()), (Some("src-host"), "/landing/"));
+
+        let watcher = FileTransferSpec::detect(None, ApplTypeCategory::FileWatch, &vars(&[("%%DIR", "/landing"), ("%%FW-PATH", "%%DIR/*.csv")]));
+        assert_eq!(watcher[0].path, "/landing/*.csv");
+        assert!(FileTransferSpec::detect(Some("run_report.sh"), ApplTypeCategory::Command, &variables).is_empty());
+    }
+}
@@ -7,6 +7,12 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use super::ComplexityScore;
 
+/// Highest complexity score rated Easy
+pub const EASY_MAX_SCORE: u32 = 30;
+
+/// Highest complexity score rated Medium
+pub const MEDIUM_MAX_SCORE: u32 = 60;
+
 /// Represents the difficulty level of migrating a job
 ///
 /// Migration difficulty is derived from the complexity score and helps
@@ -37,10 +43,13 @@ impl MigrationDifficulty {
     ///
     /// The corresponding MigrationDifficulty level
     pub fn from_complexity_score(score: ComplexityScore) -> Self {
-        match score.value() {
-            0..=30 => MigrationDifficulty::Easy,
-            31..=60 => MigrationDifficulty::Medium,
-            _ => MigrationDifficulty::Hard,
+        let score = score.value();
+        if score <= EASY_MAX_SCORE {
+            MigrationDifficulty::Easy
+        } else if score <= MEDIUM_MAX_SCORE {
+            MigrationDifficulty::Medium
+        } else {
+            MigrationDifficulty::Hard
         }
     }
 
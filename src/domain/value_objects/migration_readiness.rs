@@ -0,0 +1,79 @@
+//! Migration Readiness value object module
+//!
+//! This module defines the MigrationReadiness enum which tells whether a job
+//! can be migrated now. It is independent of MigrationDifficulty: an easy job
+//! can still be blocked by upstream jobs that are not part of the analysis.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Represents whether a job can be migrated right now
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MigrationReadiness {
+    /// All upstream jobs are known and nothing needs a decision first
+    Ready,
+    /// Depends on another datacenter or runs cyclically, so the migration
+    /// pattern must be decided before porting
+    NeedsReview,
+    /// Waits for conditions no analyzed job sets, so its upstream must be
+    /// located (or migrated) first
+    Blocked,
+}
+
+impl MigrationReadiness {
+    /// Assesses the readiness of a job
+    ///
+    /// Unresolved upstream dependencies block a job; otherwise external
+    /// (cross-datacenter) dependencies or cyclic execution call for review.
+    ///
+    /// # Arguments
+    ///
+    /// * `unresolved_upstream` - In-conditions no analyzed job produces
+    /// * `has_external_dependencies` - Whether the job waits for a job in another datacenter
+    /// * `is_cyclic` - Whether the job runs cyclically
+    ///
+    /// # Returns
+    ///
+    /// The corresponding MigrationReadiness
+    pub fn assess(unresolved_upstream: usize, has_external_dependencies: bool, is_cyclic: bool) -> Self {
+        if unresolved_upstream > 0 {
+            MigrationReadiness::Blocked
+        } else if has_external_dependencies || is_cyclic {
+            MigrationReadiness::NeedsReview
+        } else {
+            MigrationReadiness::Ready
+        }
+    }
+
+    /// Returns the string representation of this readiness
+    ///
+    /// # Returns
+    ///
+    /// A string slice ("Ready", "NeedsReview", or "Blocked")
+    pub fn as_str(&self) -> &str {
+        match self {
+            MigrationReadiness::Ready => "Ready",
+            MigrationReadiness::NeedsReview => "NeedsReview",
+            MigrationReadiness::Blocked => "Blocked",
+        }
+    }
+}
+
+impl fmt::Display for MigrationReadiness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assess_blocked_takes_precedence() {
+        assert_eq!(MigrationReadiness::assess(12, true, true), MigrationReadiness::Blocked);
+        assert_eq!(MigrationReadiness::assess(0, true, false), MigrationReadiness::NeedsReview);
+        assert_eq!(MigrationReadiness::assess(0, false, true), MigrationReadiness::NeedsReview);
+        assert_eq!(MigrationReadiness::assess(0, false, false), MigrationReadiness::Ready);
+    }
+}
@@ -0,0 +1,104 @@
+//! Migration Target value object module
+//!
+//! This module defines the MigrationTarget value object which describes the
+//! Airflow environment jobs are migrated to: its major version and the
+//! provider packages installed in it.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::str::FromStr;
+
+/// Prefix of Airflow provider package names
+const PROVIDER_PACKAGE_PREFIX: &str = "apache-airflow-providers-";
+
+/// Major Airflow version of the target environment
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum AirflowVersion {
+    /// Airflow 2.x: core operators live under `airflow.operators`
+    #[default]
+    V2,
+    /// Airflow 3.x: core operators live in the standard provider
+    V3,
+}
+
+impl FromStr for AirflowVersion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "2" | "2.x" => Ok(AirflowVersion::V2),
+            "3" | "3.x" => Ok(AirflowVersion::V3),
+            other => Err(format!("Unknown Airflow version '{}' (expected 2 or 3)", other)),
+        }
+    }
+}
+
+/// The Airflow environment targeted by the migration
+///
+/// Operator suggestions only use providers enabled here; core operators
+/// (Bash, Python) are always available.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MigrationTarget {
+    /// Major Airflow version
+    pub airflow_version: AirflowVersion,
+    /// Installed provider short names (e.g. "sftp", "common-sql")
+    pub providers: BTreeSet<String>,
+    /// Whether the user configured the version or providers, rather than
+    /// relying on the defaults
+    #[serde(default)]
+    pub configured: bool,
+}
+
+impl MigrationTarget {
+    /// Creates an Airflow 2.x target with no optional providers
+    ///
+    /// The target counts as configured once a version or provider is set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the Airflow version
+    pub fn with_airflow_version(mut self, airflow_version: AirflowVersion) -> Self {
+        self.airflow_version = airflow_version;
+        self.configured = true;
+        self
+    }
+
+    /// Enables a provider package
+    ///
+    /// Accepts either the short name (`sftp`) or the full package name
+    /// (`apache-airflow-providers-sftp`).
+    pub fn with_provider(mut self, provider: &str) -> Self {
+        self.providers.insert(Self::normalize_provider(provider));
+        self.configured = true;
+        self
+    }
+
+    /// Checks whether a provider package is available in the target
+    pub fn has_provider(&self, provider: &str) -> bool {
+        self.providers.contains(&Self::normalize_provider(provider))
+    }
+
+    fn normalize_provider(provider: &str) -> String {
+        let provider = provider.trim().to_lowercase();
+        provider.strip_prefix(PROVIDER_PACKAGE_PREFIX)
+            .unwrap_or(&provider)
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provider_names_are_normalized() {
+        let target = MigrationTarget::new()
+            .with_airflow_version("3".parse().unwrap())
+            .with_provider("apache-airflow-providers-SFTP");
+
+        assert_eq!(target.airflow_version, AirflowVersion::V3);
+        assert!(target.has_provider("sftp"));
+        assert!(!target.has_provider("ssh"));
+    }
+}
@@ -0,0 +1,78 @@
+//! Migration Weight value object module
+//!
+//! This module defines the MigrationWeight value object, a manual complexity
+//! override read from job metadata. It lets business knowledge about a job
+//! (known to be trivial or hard) override the structural heuristic.
+
+use serde::{Deserialize, Serialize};
+
+/// Job metadata key holding a migration weight
+pub const MIGRATION_WEIGHT_KEY: &str = "migration_weight";
+
+/// Factor name reported in the complexity breakdown of overridden jobs
+pub const MANUAL_OVERRIDE_FACTOR: &str = "manual_override";
+
+/// A manual override of a job's computed complexity score
+///
+/// Written as a plain number (`40`) to replace the computed score, or as a
+/// signed number (`+15`, `-10`) to adjust it.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum MigrationWeight {
+    /// Replaces the computed score
+    Absolute(u32),
+    /// Adds to (or subtracts from) the computed score
+    Relative(i64),
+}
+
+impl MigrationWeight {
+    /// Parses a migration weight metadata value
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - Metadata value, e.g. `40`, `+15` or `-10`
+    ///
+    /// # Returns
+    ///
+    /// The parsed weight, or None if the value is not a number
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if value.starts_with('+') || value.starts_with('-') {
+            value.parse().ok().map(Self::Relative)
+        } else {
+            value.parse().ok().map(Self::Absolute)
+        }
+    }
+
+    /// Applies the weight to a computed complexity score
+    ///
+    /// # Arguments
+    ///
+    /// * `computed` - Score computed by the heuristic
+    ///
+    /// # Returns
+    ///
+    /// The overridden score (never below zero)
+    pub fn apply(&self, computed: u32) -> u32 {
+        match self {
+            Self::Absolute(score) => *score,
+            Self::Relative(delta) => (computed as i64 + delta).clamp(0, u32::MAX as i64) as u32,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_apply_absolute_and_relative_weights() {
+        assert_eq!(MigrationWeight::parse(" 40 "), Some(MigrationWeight::Absolute(40)));
+        assert_eq!(MigrationWeight::parse("+15"), Some(MigrationWeight::Relative(15)));
+        assert_eq!(MigrationWeight::parse("-10"), Some(MigrationWeight::Relative(-10)));
+        assert_eq!(MigrationWeight::parse("hard"), None);
+
+        assert_eq!(MigrationWeight::Absolute(40).apply(75), 40);
+        assert_eq!(MigrationWeight::Relative(15).apply(30), 45);
+        assert_eq!(MigrationWeight::Relative(-10).apply(5), 0);
+    }
+}
@@ -1,7 +1,25 @@
+pub mod analysis_profile;
+pub mod appl_type;
+pub mod command_line;
 pub mod complexity_score;
+pub mod complexity_weights;
+pub mod file_transfer;
 pub mod migration_difficulty;
 pub mod migration_priority;
+pub mod migration_readiness;
+pub mod migration_target;
+pub mod migration_weight;
+pub mod order_date;
 
-pub use complexity_score::ComplexityScore;
+pub use analysis_profile::AnalysisProfile;
+pub use appl_type::{ApplTypeAliases, ApplTypeCategory};
+pub use command_line::{QuotingStyle, TokenizedCommand};
+pub use complexity_score::{ComplexityScore, COMPLEXITY_FACTORS};
+pub use complexity_weights::ComplexityWeights;
+pub use file_transfer::{FileTransferSpec, TransferDirection};
 pub use migration_difficulty::MigrationDifficulty;
 pub use migration_priority::MigrationPriority;
+pub use migration_readiness::MigrationReadiness;
+pub use migration_target::{AirflowVersion, MigrationTarget};
+pub use migration_weight::{MigrationWeight, MANUAL_OVERRIDE_FACTOR, MIGRATION_WEIGHT_KEY};
+pub use order_date::{OrderDate, RunAlignment};
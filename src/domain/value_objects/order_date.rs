@@ -0,0 +1,177 @@
+//! Order Date value object module
+//!
+//! This module defines the OrderDate value object which interprets the
+//! ODATE of a condition and resolves it against a run date, telling which
+//! run of the producing job a dependency refers to.
+
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// The order date a condition refers to
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum OrderDate {
+    /// `ODAT` (or no ODATE): the consumer's own order date
+    Current,
+    /// `PREV`: the previous order date
+    Previous,
+    /// `NEXT`: the next order date
+    Next,
+    /// `****` or `$$$$`: any order date
+    Any,
+    /// `MMDD`: a fixed month and day
+    Fixed { month: u32, day: u32 },
+}
+
+/// How a dependency's producer run relates to the consumer run
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum RunAlignment {
+    /// Producer and consumer belong to the same run
+    SameRun,
+    /// Producer ran on the previous order date
+    PriorRun,
+    /// Producer ran on some other specific date
+    OffsetRun,
+    /// Any producer run satisfies the dependency
+    AnyRun,
+    /// The fixed ODATE does not exist in the year it resolves to
+    /// (e.g. 0229 in a non-leap year), so no producer run matches
+    InvalidDate,
+}
+
+impl OrderDate {
+    /// Parses an ODATE attribute value
+    ///
+    /// # Arguments
+    ///
+    /// * `odate` - ODATE value as written in the XML, or None if absent
+    ///
+    /// # Returns
+    ///
+    /// The parsed OrderDate, or None if the value is not recognized
+    pub fn parse(odate: Option<&str>) -> Option<Self> {
+        let value = match odate.map(str::trim) {
+            None | Some("") => return Some(OrderDate::Current),
+            Some(value) => value,
+        };
+
+        match value.to_uppercase().as_str() {
+            "ODAT" => Some(OrderDate::Current),
+            "PREV" => Some(OrderDate::Previous),
+            "NEXT" => Some(OrderDate::Next),
+            "****" | "$$$$" => Some(OrderDate::Any),
+            mmdd if mmdd.len() == 4 && mmdd.chars().all(|c| c.is_ascii_digit()) => {
+                let month = mmdd[..2].parse().ok()?;
+                let day = mmdd[2..].parse().ok()?;
+                NaiveDate::from_ymd_opt(2000, month, day)?;
+                Some(OrderDate::Fixed { month, day })
+            }
+            _ => None,
+        }
+    }
+
+    /// Resolves the producer run date for a consumer running on `run_date`
+    ///
+    /// PREV and NEXT are taken as the adjacent calendar day. A fixed MMDD is
+    /// its most recent occurrence on or before `run_date`: this year's if
+    /// it has passed, last year's otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `run_date` - Order date of the consuming job
+    ///
+    /// # Returns
+    ///
+    /// The producer's run date, or None when any run date is accepted or
+    /// the fixed date does not exist in the year it resolves to
+    pub fn resolve(&self, run_date: NaiveDate) -> Option<NaiveDate> {
+        match self {
+            OrderDate::Current => Some(run_date),
+            OrderDate::Previous => Some(run_date - Duration::days(1)),
+            OrderDate::Next => Some(run_date + Duration::days(1)),
+            OrderDate::Any => None,
+            OrderDate::Fixed { month, day } => {
+                let year = if (*month, *day) <= (run_date.month(), run_date.day()) {
+                    run_date.year()
+                } else {
+                    run_date.year() - 1
+                };
+                NaiveDate::from_ymd_opt(year, *month, *day)
+            }
+        }
+    }
+
+    /// Classifies the producer run relative to a consumer running on `run_date`
+    ///
+    /// # Arguments
+    ///
+    /// * `run_date` - Order date of the consuming job
+    ///
+    /// # Returns
+    ///
+    /// The run alignment and, when a producer run date resolves, the
+    /// producer's offset in days from the consumer run (negative means earlier)
+    pub fn alignment(&self, run_date: NaiveDate) -> (RunAlignment, Option<i64>) {
+        match self.resolve(run_date) {
+            None if *self == OrderDate::Any => (RunAlignment::AnyRun, None),
+            None => (RunAlignment::InvalidDate, None),
+            Some(producer_date) => {
+                let offset = (producer_date - run_date).num_days();
+                let alignment = match offset {
+                    0 => RunAlignment::SameRun,
+                    -1 => RunAlignment::PriorRun,
+                    _ => RunAlignment::OffsetRun,
+                };
+                (alignment, Some(offset))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_day_dependency() {
+        let reference = NaiveDate::from_ymd_opt(2026, 3, 15).unwrap();
+        let odate = OrderDate::parse(Some("ODAT")).unwrap();
+
+        assert_eq!(odate.resolve(reference), Some(reference));
+        assert_eq!(odate.alignment(reference), (RunAlignment::SameRun, Some(0)));
+    }
+
+    #[test]
+    fn test_prior_day_dependency() {
+        let reference = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        let odate = OrderDate::parse(Some("PREV")).unwrap();
+
+        assert_eq!(odate.resolve(reference), NaiveDate::from_ymd_opt(2026, 2, 28));
+        assert_eq!(odate.alignment(reference), (RunAlignment::PriorRun, Some(-1)));
+    }
+
+    #[test]
+    fn test_fixed_date_resolves_to_most_recent_occurrence() {
+        let reference = NaiveDate::from_ymd_opt(2026, 3, 15).unwrap();
+
+        let passed = OrderDate::parse(Some("0301")).unwrap();
+        assert_eq!(passed.resolve(reference), NaiveDate::from_ymd_opt(2026, 3, 1));
+        assert_eq!(passed.alignment(reference), (RunAlignment::OffsetRun, Some(-14)));
+
+        let upcoming = OrderDate::parse(Some("1231")).unwrap();
+        assert_eq!(upcoming.resolve(reference), NaiveDate::from_ymd_opt(2025, 12, 31));
+        assert_eq!(upcoming.alignment(reference), (RunAlignment::OffsetRun, Some(-74)));
+    }
+
+    #[test]
+    fn test_leap_day_in_non_leap_year_is_invalid() {
+        let leap_day = OrderDate::parse(Some("0229")).unwrap();
+
+        let leap_year = NaiveDate::from_ymd_opt(2028, 3, 1).unwrap();
+        assert_eq!(leap_day.resolve(leap_year), NaiveDate::from_ymd_opt(2028, 2, 29));
+
+        let non_leap_year = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        assert_eq!(leap_day.resolve(non_leap_year), None);
+        assert_eq!(leap_day.alignment(non_leap_year), (RunAlignment::InvalidDate, None));
+        assert_eq!(OrderDate::Any.alignment(non_leap_year), (RunAlignment::AnyRun, None));
+    }
+}
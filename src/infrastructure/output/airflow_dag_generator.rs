@@ -0,0 +1,347 @@
+//! Airflow DAG generator module
+//!
+//! This module writes one Airflow DAG file per top-level Control-M folder.
+//! Jobs with a command line become `BashOperator` tasks and all other jobs
+//! `EmptyOperator` placeholders. Dependencies are resolved like the exported
+//! `job_dependencies`: by out-condition first, then by job name. Edges inside
+//! one DAG run become `set_upstream` calls; dependencies on another folder
+//! (or another order date) become `ExternalTaskSensor` stubs, so no
+//! dependency is silently dropped.
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::application::services::OperatorMapper;
+use crate::domain::entities::{Folder, Job};
+use crate::domain::value_objects::{AirflowVersion, OrderDate};
+use crate::presentation::dto::{AnalysisOutput, JobOutput};
+use super::airflow_dependencies::{AirflowDependency, AirflowDependencyGenerator, FIXED_ORDER_DATE_HELPER};
+
+/// Start date written into every generated DAG
+const DAG_START_DATE: &str = "datetime(2024, 1, 1)";
+
+/// Generator for Airflow DAG Python files
+pub struct AirflowDagGenerator {
+    /// Airflow version whose import paths are used
+    airflow_version: AirflowVersion,
+}
+
+impl AirflowDagGenerator {
+    /// Creates a new AirflowDagGenerator targeting Airflow 2.x
+    pub fn new() -> Self {
+        Self {
+            airflow_version: AirflowVersion::default(),
+        }
+    }
+
+    /// Sets the Airflow version whose import paths are used
+    pub fn with_airflow_version(mut self, airflow_version: AirflowVersion) -> Self {
+        self.airflow_version = airflow_version;
+        self
+    }
+
+    /// Writes one DAG file per top-level folder
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - Analysis output, used to annotate each task
+    /// * `folders` - Top-level folders; each becomes one DAG
+    /// * `dir` - Directory to write the `.py` files into
+    ///
+    /// # Returns
+    ///
+    /// Paths of the written DAG files
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the directory or a file cannot be written
+    pub fn generate(&self, output: &AnalysisOutput, folders: &[Folder], dir: &Path) -> Result<Vec<PathBuf>> {
+        fs::create_dir_all(dir).context("Failed to create DAG directory")?;
+
+        let mut files = Vec::new();
+        for (_, file_name, source) in self.render_dags(&output.jobs, folders) {
+            let path = dir.join(file_name);
+            fs::write(&path, source)
+                .with_context(|| format!("Failed to write DAG file {:?}", path))?;
+            files.push(path);
+        }
+        Ok(files)
+    }
+
+    /// Renders one DAG per top-level folder without writing any file
+    ///
+    /// Dependencies are resolved across all given folders, so a caller that
+    /// only needs some DAGs should still pass every folder and filter the
+    /// result; otherwise sensors on the missing folders are dropped. Folders
+    /// whose names convert to the same DAG id get suffixed ids (and file
+    /// names), so no DAG overwrites another.
+    ///
+    /// # Arguments
+    ///
+    /// * `jobs` - Analyzed jobs used to annotate each task; may be empty
+    /// * `folders` - Top-level folders; each becomes one DAG
+    ///
+    /// # Returns
+    ///
+    /// Each folder with the file name and Python source of its DAG
+    pub fn render_dags<'a>(&self, jobs: &[JobOutput], folders: &'a [Folder]) -> Vec<(&'a Folder, String, String)> {
+        let dependencies = AirflowDependencyGenerator::new()
+            .with_name_fallback(true)
+            .build_dependencies(folders);
+        let job_outputs: HashMap<(&str, &str), &JobOutput> = jobs.iter()
+            .map(|job| ((job.folder_name.as_str(), job.job_name.as_str()), job))
+            .collect();
+
+        folders.iter()
+            .zip(AirflowDependencyGenerator::dag_ids(folders))
+            .map(|(folder, dag_id)| {
+                let source = self.render_folder(&job_outputs, folder, &dag_id, &dependencies);
+                (folder, format!("{}.py", dag_id), source)
+            })
+            .collect()
+    }
+
+    fn render_folder(
+        &self,
+        job_outputs: &HashMap<(&str, &str), &JobOutput>,
+        folder: &Folder,
+        dag_id: &str,
+        dependencies: &[AirflowDependency],
+    ) -> String {
+        let incoming: Vec<&AirflowDependency> = dependencies.iter()
+            .filter(|d| d.consumer_dag == dag_id)
+            .collect();
+        let has_sensors = incoming.iter().any(|d| !d.is_in_dag());
+
+        let mut py = String::new();
+        py.push_str(&format!("\"\"\"Airflow DAG generated by JobWeaver from Control-M folder {}\"\"\"\n", comment_text(&folder.folder_name)));
+        py.push_str("from datetime import datetime, timedelta\n\n");
+        py.push_str("from airflow import DAG\n");
+        py.push_str(&python_import(&OperatorMapper::core_operator(self.airflow_version, "BashOperator", "bash").import_path));
+        py.push_str(&python_import(&OperatorMapper::core_operator(self.airflow_version, "EmptyOperator", "empty").import_path));
+        if has_sensors {
+            py.push_str(&python_import(sensor_import_path(self.airflow_version)));
+        }
+        if incoming.iter().any(|d| d.needs_fixed_order_date_helper()) {
+            py.push('\n');
+            py.push_str(FIXED_ORDER_DATE_HELPER);
+        }
+
+        py.push_str("\nwith DAG(\n");
+        py.push_str(&format!("    dag_id={},\n", python_str(dag_id)));
+        py.push_str(&format!("    start_date={},\n", DAG_START_DATE));
+        py.push_str("    schedule=None,\n");
+        py.push_str("    catchup=False,\n");
+        py.push_str("    tags=[\"jobweaver\"],\n");
+        py.push_str(") as dag:\n");
+
+        let jobs = folder.all_jobs();
+        if jobs.is_empty() {
+            py.push_str("    pass\n");
+        }
+        for (job, task_id) in jobs.into_iter().zip(AirflowDependencyGenerator::task_ids(folder)) {
+            let job_output = job_outputs.get(&(job.folder_name.as_str(), job.job_name.as_str())).copied();
+            py.push_str(&Self::render_task(job, &task_id, job_output));
+        }
+
+        let mut sensors: Vec<String> = Vec::new();
+        for dependency in incoming.iter().filter(|d| !d.is_in_dag()) {
+            let sensor = dependency.sensor_id();
+            if sensors.contains(&sensor) {
+                continue;
+            }
+            py.push_str(&format!("\n    # {} ({}) is set in another DAG run\n", comment_text(&dependency.condition), dependency.producer_dag));
+            py.push_str(&format!("    {} = ExternalTaskSensor(\n", sensor));
+            py.push_str(&format!("        task_id={},\n", python_str(&sensor)));
+            py.push_str(&format!("        external_dag_id={},\n", python_str(&dependency.producer_dag)));
+            py.push_str(&format!("        external_task_id={},\n", python_str(&dependency.producer_task)));
+            if let Some(argument) = dependency.sensor_run_argument() {
+                py.push_str(&format!("        {},\n", argument));
+            }
+            if dependency.order_date == OrderDate::Any {
+                py.push_str("        # TODO: ODATE accepts any producer run; narrow this to the intended run\n");
+            }
+            py.push_str("        mode=\"reschedule\",\n    )\n");
+            sensors.push(sensor);
+        }
+
+        let mut edges: Vec<String> = Vec::new();
+        for dependency in &incoming {
+            let upstream = if dependency.is_in_dag() {
+                dependency.producer_task.clone()
+            } else {
+                dependency.sensor_id()
+            };
+            let edge = format!("    {}.set_upstream({})\n", dependency.consumer_task, upstream);
+            if !edges.contains(&edge) {
+                edges.push(edge);
+            }
+        }
+        if !edges.is_empty() {
+            py.push_str("\n    # Dependencies (Control-M in-conditions)\n");
+            py.extend(edges);
+        }
+        py
+    }
+
+    /// Renders one task, annotated with its analysis results
+    fn render_task(job: &Job, task_id: &str, job_output: Option<&JobOutput>) -> String {
+        let mut py = format!("\n    # {}", comment_text(&job.job_name));
+        if let Some(job_output) = job_output {
+            py.push_str(&format!(
+                ": wave {}, complexity {} ({})",
+                job_output.migration_wave, job_output.complexity_score, job_output.migration_difficulty
+            ));
+            let suggested = &job_output.airflow_mapping;
+            if suggested.operator_type != "BashOperator" {
+                py.push_str(&format!("\n    # Suggested operator: {} ({})", suggested.operator_type, suggested.operator_import));
+            }
+            for note in &job_output.migration_notes {
+                py.push_str(&format!("\n    # Note: {}", comment_text(note)));
+            }
+        }
+        py.push('\n');
+
+        if job.cyclic {
+            let interval = job.scheduling.cyclic_interval.as_deref()
+                .map(|interval| format!(" (interval {})", comment_text(interval)))
+                .unwrap_or_default();
+            py.push_str(&format!(
+                "    # TODO: cyclic in Control-M{}; Airflow has no direct equivalent. Use a short schedule, a sensor or a re-triggering DAG\n",
+                interval
+            ));
+        }
+
+        match job.cmdline.as_deref().map(str::trim).filter(|cmdline| !cmdline.is_empty()) {
+            Some(cmdline) => {
+                // Airflow treats a bash_command ending in .sh as a template file path
+                let command = if cmdline.ends_with(".sh") {
+                    format!("{} ", cmdline)
+                } else {
+                    cmdline.to_string()
+                };
+                py.push_str(&format!("    {} = BashOperator(\n", task_id));
+                py.push_str(&format!("        task_id={},\n", python_str(task_id)));
+                py.push_str(&format!("        bash_command={},\n", python_str(&command)));
+                py.push_str("    )\n");
+            }
+            None => py.push_str(&format!("    {} = EmptyOperator(task_id={})\n", task_id, python_str(task_id))),
+        }
+        py
+    }
+}
+
+impl Default for AirflowDagGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Gets the import path of `ExternalTaskSensor` in an Airflow version
+pub(super) fn sensor_import_path(airflow_version: AirflowVersion) -> &'static str {
+    match airflow_version {
+        AirflowVersion::V2 => "airflow.sensors.external_task.ExternalTaskSensor",
+        AirflowVersion::V3 => "airflow.providers.standard.sensors.external_task.ExternalTaskSensor",
+    }
+}
+
+/// Turns `module.path.Class` into `from module.path import Class`
+pub(super) fn python_import(import_path: &str) -> String {
+    match import_path.rsplit_once('.') {
+        Some((module, class)) => format!("from {} import {}\n", module, class),
+        None => format!("import {}\n", import_path),
+    }
+}
+
+/// Quotes text as a Python string literal
+fn python_str(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+        .replace('\t', "\\t");
+    format!("\"{}\"", escaped)
+}
+
+/// Keeps text on one line so it can be used in a Python comment
+pub(super) fn comment_text(text: &str) -> String {
+    text.replace(['\r', '\n'], " ").replace("\"\"\"", "'''")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::use_cases::AnalyzeJobs;
+    use crate::domain::entities::{Condition, FolderType};
+    use crate::infrastructure::output::DagValidator;
+
+    #[test]
+    fn test_folders_become_dags_with_sensors_across_folders() {
+        let mut extract = Job::new("EXTRACT".to_string(), "DAILY".to_string());
+        extract.cmdline = Some("/opt/etl/extract.sh".to_string());
+        extract.out_conditions.push(Condition::new_out("EXTRACT-OK".to_string()));
+        let mut load = Job::new("LOAD".to_string(), "DAILY".to_string());
+        load.in_conditions.push(Condition::new_in("EXTRACT-OK".to_string()));
+        let mut poll = Job::new("POLL".to_string(), "DAILY".to_string());
+        poll.cyclic = true;
+        poll.scheduling.cyclic_interval = Some("00005M".to_string());
+        let mut daily = Folder::new("DAILY".to_string(), FolderType::Simple);
+        daily.add_job(extract);
+        daily.add_job(load);
+        daily.add_job(poll);
+
+        // No job sets LOAD-ENDED-OK; it resolves to LOAD by name
+        let mut report = Job::new("REPORT".to_string(), "REPORTS".to_string());
+        report.in_conditions.push(Condition::new_in("LOAD-ENDED-OK".to_string()));
+        let mut reports = Folder::new("REPORTS".to_string(), FolderType::Simple);
+        reports.add_job(report);
+
+        let folders = vec![daily, reports];
+        let output = AnalysisOutput::from_analysis_result(AnalyzeJobs::new().execute(&folders).unwrap());
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+
+        let files = AirflowDagGenerator::new().generate(&output, &folders, dir).unwrap();
+        assert_eq!(files, vec![dir.join("daily.py"), dir.join("reports.py")]);
+
+        let daily_py = fs::read_to_string(dir.join("daily.py")).unwrap();
+        assert!(daily_py.contains("from airflow.operators.bash import BashOperator\n"));
+        assert!(daily_py.contains("bash_command=\"/opt/etl/extract.sh \",\n"));
+        assert!(daily_py.contains("    load = EmptyOperator(task_id=\"load\")\n"));
+        assert!(daily_py.contains("    load.set_upstream(extract)\n"));
+        assert!(daily_py.contains("# TODO: cyclic in Control-M (interval 00005M)"));
+        assert!(!daily_py.contains("ExternalTaskSensor"));
+
+        let reports_py = fs::read_to_string(dir.join("reports.py")).unwrap();
+        assert!(reports_py.contains("    wait_daily_load = ExternalTaskSensor(\n"));
+        assert!(reports_py.contains("external_dag_id=\"daily\",\n        external_task_id=\"load\",\n"));
+        assert!(reports_py.contains("    report.set_upstream(wait_daily_load)\n"));
+
+        assert!(DagValidator::new().validate(&files).unwrap().is_valid());
+    }
+
+    #[test]
+    fn test_colliding_folder_names_get_distinct_dags() {
+        let mut extract = Job::new("EXTRACT".to_string(), "DAILY-ETL".to_string());
+        extract.out_conditions.push(Condition::new_out("EXTRACT-OK".to_string()));
+        let mut first = Folder::new("DAILY-ETL".to_string(), FolderType::Simple);
+        first.add_job(extract);
+
+        let mut load = Job::new("LOAD".to_string(), "DAILY_ETL".to_string());
+        load.in_conditions.push(Condition::new_in("EXTRACT-OK".to_string()));
+        let mut second = Folder::new("DAILY_ETL".to_string(), FolderType::Simple);
+        second.add_job(load);
+
+        let folders = vec![first, second];
+        let dags = AirflowDagGenerator::new().render_dags(&[], &folders);
+        let file_names: Vec<&str> = dags.iter().map(|(_, file_name, _)| file_name.as_str()).collect();
+        assert_eq!(file_names, vec!["daily_etl.py", "daily_etl_2.py"]);
+
+        let (_, _, second_py) = &dags[1];
+        assert!(second_py.contains("    dag_id=\"daily_etl_2\",\n"));
+        assert!(second_py.contains("external_dag_id=\"daily_etl\",\n        external_task_id=\"extract\",\n"));
+        assert!(second_py.contains("    load.set_upstream(wait_daily_etl_extract)\n"));
+    }
+}
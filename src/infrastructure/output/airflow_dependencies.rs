@@ -0,0 +1,469 @@
+//! Airflow dependency generator module
+//!
+//! This module turns Control-M condition dependencies into Airflow wiring.
+//! Each top-level folder becomes a DAG and each job a task. A dependency on
+//! a job of the same DAG and the same order date is an in-DAG `>>` edge;
+//! every other dependency (another DAG, or another order date such as PREV)
+//! becomes an `ExternalTaskSensor` whose `execution_delta` or
+//! `execution_date_fn` targets the producer run named by the ODATE.
+
+use anyhow::{Context, Result};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use crate::domain::entities::Folder;
+use crate::domain::value_objects::{AirflowVersion, OrderDate};
+use crate::application::services::DependencyAnalyzer;
+use super::airflow_dag_generator::{comment_text, python_import, sensor_import_path};
+use super::sqlite_exporter::condition_base_name;
+
+/// Python keywords, which cannot be used as task variable names
+const PYTHON_KEYWORDS: &[&str] = &[
+    "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del",
+    "elif", "else", "except", "finally", "for", "from", "global", "if", "import", "in",
+    "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+/// Python helper resolving a fixed MMDD order date, emitted once per file
+/// by renderers whose sensors use `OrderDate::Fixed`
+///
+/// `dt.replace(month=..., day=...)` alone would target a future run for a
+/// date later in the year and raise for 29 February in a non-leap year, so
+/// the helper walks back to the most recent year the date exists in.
+pub const FIXED_ORDER_DATE_HELPER: &str = "\
+def fixed_order_date(dt, month, day):
+    \"\"\"Most recent logical date on or before dt falling on month/day\"\"\"
+    year = dt.year
+    while True:
+        try:
+            candidate = dt.replace(year=year, month=month, day=day)
+        except ValueError:
+            candidate = None
+        if candidate is not None and candidate <= dt:
+            return candidate
+        year -= 1
+";
+
+/// A dependency between two Airflow tasks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AirflowDependency {
+    /// DAG of the producing job (its top-level folder)
+    pub producer_dag: String,
+    pub producer_task: String,
+    /// DAG of the consuming job (its top-level folder)
+    pub consumer_dag: String,
+    pub consumer_task: String,
+    /// Condition linking the two jobs
+    pub condition: String,
+    /// Producer run the dependency refers to, from the in-condition ODATE
+    pub order_date: OrderDate,
+}
+
+impl AirflowDependency {
+    /// Checks whether the dependency is a plain edge inside one DAG run
+    pub fn is_in_dag(&self) -> bool {
+        self.producer_dag == self.consumer_dag && self.order_date == OrderDate::Current
+    }
+
+    /// Gets the sensor argument selecting the producer run
+    ///
+    /// # Returns
+    ///
+    /// An `execution_delta` or `execution_date_fn` keyword argument, or None
+    /// when the sensor targets the same logical date (or any run)
+    pub fn sensor_run_argument(&self) -> Option<String> {
+        match self.order_date {
+            OrderDate::Current | OrderDate::Any => None,
+            OrderDate::Previous => Some("execution_delta=timedelta(days=1)".to_string()),
+            OrderDate::Next => Some("execution_delta=timedelta(days=-1)".to_string()),
+            OrderDate::Fixed { month, day } => Some(format!(
+                "execution_date_fn=lambda dt, **_: fixed_order_date(dt, {}, {})",
+                month, day
+            )),
+        }
+    }
+
+    /// Names the sensor waiting for the dependency's producer run
+    ///
+    /// The name includes the order date, so one producer task waited for on
+    /// two different runs gets two sensors.
+    pub fn sensor_id(&self) -> String {
+        let run = match self.order_date {
+            OrderDate::Current => String::new(),
+            OrderDate::Previous => "_prev".to_string(),
+            OrderDate::Next => "_next".to_string(),
+            OrderDate::Any => "_any".to_string(),
+            OrderDate::Fixed { month, day } => format!("_{:02}{:02}", month, day),
+        };
+        format!("wait_{}_{}{}", self.producer_dag, self.producer_task, run)
+    }
+
+    /// Checks whether the sensor needs `FIXED_ORDER_DATE_HELPER`
+    pub fn needs_fixed_order_date_helper(&self) -> bool {
+        !self.is_in_dag() && matches!(self.order_date, OrderDate::Fixed { .. })
+    }
+}
+
+/// Generator for Airflow dependency wiring
+pub struct AirflowDependencyGenerator {
+    /// Whether conditions no job sets resolve to the job named like the condition
+    name_fallback: bool,
+    /// Airflow version whose `ExternalTaskSensor` import is written
+    airflow_version: AirflowVersion,
+}
+
+impl AirflowDependencyGenerator {
+    /// Creates a new AirflowDependencyGenerator instance
+    pub fn new() -> Self {
+        Self {
+            name_fallback: false,
+            airflow_version: AirflowVersion::default(),
+        }
+    }
+
+    /// Sets the Airflow version whose `ExternalTaskSensor` import is written
+    pub fn with_airflow_version(mut self, airflow_version: AirflowVersion) -> Self {
+        self.airflow_version = airflow_version;
+        self
+    }
+
+    /// Sets whether unmatched in-conditions fall back to a job of the same name
+    ///
+    /// When enabled, an in-condition no job sets (e.g. `LOAD-ENDED-OK`)
+    /// depends on the job named like it with the status suffix stripped
+    /// (`LOAD`), the same resolution the exported `job_dependencies` use.
+    pub fn with_name_fallback(mut self, name_fallback: bool) -> Self {
+        self.name_fallback = name_fallback;
+        self
+    }
+
+    /// Resolves every condition dependency between jobs into Airflow terms
+    ///
+    /// In-conditions are matched to their producers through
+    /// `DependencyAnalyzer::producer_map`, or by job name when name fallback
+    /// is enabled. Unrecognized ODATE values are treated as the same order
+    /// date.
+    ///
+    /// # Arguments
+    ///
+    /// * `folders` - Top-level folders; each becomes one DAG
+    ///
+    /// # Returns
+    ///
+    /// One dependency per producer of each in-condition
+    pub fn build_dependencies(&self, folders: &[Folder]) -> Vec<AirflowDependency> {
+        let mut jobs = Vec::new();
+        let mut tasks = Vec::new();
+        for (folder, dag) in folders.iter().zip(Self::dag_ids(folders)) {
+            let task_ids = Self::task_ids(folder);
+            for (job, task_id) in folder.all_jobs().into_iter().zip(task_ids) {
+                jobs.push(job);
+                tasks.push((dag.clone(), task_id));
+            }
+        }
+
+        let producers = DependencyAnalyzer::producer_map(&jobs);
+
+        let mut by_name: HashMap<&str, Vec<usize>> = HashMap::new();
+        if self.name_fallback {
+            for (position, job) in jobs.iter().enumerate() {
+                by_name.entry(job.job_name.as_str()).or_default().push(position);
+            }
+        }
+
+        let mut dependencies = Vec::new();
+        for (position, job) in jobs.iter().enumerate() {
+            let (dag, task_id) = &tasks[position];
+            for in_cond in &job.in_conditions {
+                let producer_jobs = match producers.get(in_cond.name.as_str())
+                    .or_else(|| by_name.get(in_cond.name.as_str()))
+                    .or_else(|| by_name.get(condition_base_name(&in_cond.name)))
+                {
+                    Some(producer_jobs) => producer_jobs,
+                    None => continue,
+                };
+                let order_date = OrderDate::parse(in_cond.odate.as_deref()).unwrap_or(OrderDate::Current);
+                for &producer in producer_jobs.iter().filter(|&&p| p != position) {
+                    let (producer_dag, producer_task) = &tasks[producer];
+                    dependencies.push(AirflowDependency {
+                        producer_dag: producer_dag.clone(),
+                        producer_task: producer_task.clone(),
+                        consumer_dag: dag.clone(),
+                        consumer_task: task_id.clone(),
+                        condition: in_cond.name.clone(),
+                        order_date,
+                    });
+                }
+            }
+        }
+        dependencies
+    }
+
+    /// Renders dependencies as Python wiring, grouped by consuming DAG
+    ///
+    /// Task variables are named after their task ids, so each section can be
+    /// pasted below the task definitions of its DAG.
+    ///
+    /// # Arguments
+    ///
+    /// * `dependencies` - Dependencies to render
+    ///
+    /// # Returns
+    ///
+    /// The Python source text
+    pub fn render(&self, dependencies: &[AirflowDependency]) -> String {
+        let mut by_dag: Vec<(&str, Vec<&AirflowDependency>)> = Vec::new();
+        for dependency in dependencies {
+            match by_dag.iter_mut().find(|(dag, _)| *dag == dependency.consumer_dag) {
+                Some((_, group)) => group.push(dependency),
+                None => by_dag.push((&dependency.consumer_dag, vec![dependency])),
+            }
+        }
+
+        let mut py = String::new();
+        py.push_str("# Airflow dependency wiring generated by JobWeaver\n");
+        py.push_str("from datetime import timedelta\n\n");
+        py.push_str(&python_import(sensor_import_path(self.airflow_version)));
+        if dependencies.iter().any(AirflowDependency::needs_fixed_order_date_helper) {
+            py.push('\n');
+            py.push_str(FIXED_ORDER_DATE_HELPER);
+        }
+
+        for (dag, group) in by_dag {
+            py.push_str(&format!("\n# --- DAG: {} ---\n", dag));
+            let mut sensors = HashSet::new();
+            let mut edges = HashSet::new();
+            for dependency in group {
+                py.push_str(&format!("# {}\n", comment_text(&dependency.condition)));
+                if dependency.is_in_dag() {
+                    let edge = format!("{} >> {}\n", dependency.producer_task, dependency.consumer_task);
+                    if edges.insert(edge.clone()) {
+                        py.push_str(&edge);
+                    }
+                    continue;
+                }
+
+                let sensor = dependency.sensor_id();
+                let edge = format!("{} >> {}\n", sensor, dependency.consumer_task);
+                if !sensors.insert(sensor.clone()) {
+                    if edges.insert(edge.clone()) {
+                        py.push_str(&edge);
+                    }
+                    continue;
+                }
+                py.push_str(&format!("{} = ExternalTaskSensor(\n", sensor));
+                py.push_str(&format!("    task_id=\"{}\",\n", sensor));
+                py.push_str(&format!("    external_dag_id=\"{}\",\n", dependency.producer_dag));
+                py.push_str(&format!("    external_task_id=\"{}\",\n", dependency.producer_task));
+                if let Some(argument) = dependency.sensor_run_argument() {
+                    py.push_str(&format!("    {},\n", argument));
+                }
+                if dependency.order_date == OrderDate::Any {
+                    py.push_str("    # TODO: ODATE accepts any producer run; narrow this to the intended run\n");
+                }
+                py.push_str("    mode=\"reschedule\",\n)\n");
+                edges.insert(edge.clone());
+                py.push_str(&edge);
+            }
+        }
+        py
+    }
+
+    /// Writes the Airflow dependency wiring of all folders to a file
+    ///
+    /// # Arguments
+    ///
+    /// * `folders` - Top-level folders; each becomes one DAG
+    /// * `output_path` - Path of the `.py` file to write
+    ///
+    /// # Returns
+    ///
+    /// The dependencies written
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the file cannot be written
+    pub fn generate<P: AsRef<Path>>(&self, folders: &[Folder], output_path: P) -> Result<Vec<AirflowDependency>> {
+        let dependencies = self.build_dependencies(folders);
+        fs::write(output_path.as_ref(), self.render(&dependencies))
+            .context("Failed to write Airflow dependency file")?;
+        Ok(dependencies)
+    }
+
+    /// Assigns the top-level folders distinct DAG ids
+    ///
+    /// Folder names that convert to the same identifier (`DAILY-ETL` and
+    /// `DAILY_ETL`) get a numeric suffix in folder order, as task ids do:
+    /// `daily_etl`, `daily_etl_2`.
+    ///
+    /// # Arguments
+    ///
+    /// * `folders` - Top-level folders; each becomes one DAG
+    ///
+    /// # Returns
+    ///
+    /// One DAG id per folder, in folder order
+    pub(crate) fn dag_ids(folders: &[Folder]) -> Vec<String> {
+        Self::distinct_ids(folders.iter().map(|folder| folder.folder_name.as_str()))
+    }
+
+    /// Assigns the jobs of a DAG distinct task ids
+    ///
+    /// Names that convert to the same identifier (`JOB-A` and `JOB_A`) get a
+    /// numeric suffix in job order: `job_a`, `job_a_2`.
+    ///
+    /// # Arguments
+    ///
+    /// * `folder` - Top-level folder the DAG is generated from
+    ///
+    /// # Returns
+    ///
+    /// One task id per job, in the order of `Folder::all_jobs`
+    pub(crate) fn task_ids(folder: &Folder) -> Vec<String> {
+        Self::distinct_ids(folder.all_jobs().into_iter().map(|job| job.job_name.as_str()))
+    }
+
+    /// Converts names into Python identifiers, suffixing repeats with `_2`, `_3`, ...
+    fn distinct_ids<'a>(names: impl Iterator<Item = &'a str>) -> Vec<String> {
+        let mut used = HashSet::new();
+        names
+            .map(|name| {
+                let base = Self::python_id(name);
+                let mut task_id = base.clone();
+                let mut suffix = 2;
+                while !used.insert(task_id.clone()) {
+                    task_id = format!("{}_{}", base, suffix);
+                    suffix += 1;
+                }
+                task_id
+            })
+            .collect()
+    }
+
+    /// Converts a Control-M name into a Python identifier usable as a DAG or task id
+    pub(crate) fn python_id(name: &str) -> String {
+        let id: String = name.chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+            .collect();
+        match id.chars().next() {
+            Some(c) if c.is_ascii_alphabetic() && !PYTHON_KEYWORDS.contains(&id.as_str()) => id,
+            Some(c) if c.is_ascii_alphabetic() => format!("{}_", id),
+            _ => format!("t_{}", id),
+        }
+    }
+}
+
+impl Default for AirflowDependencyGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{Condition, FolderType, Job};
+
+    #[test]
+    fn test_prev_dependency_becomes_sensor_and_odat_an_in_dag_edge() {
+        let mut extract = Job::new("EXTRACT".to_string(), "DAILY".to_string());
+        extract.out_conditions.push(Condition::new_out("EXTRACT-OK".to_string()));
+
+        let mut load = Job::new("LOAD".to_string(), "DAILY".to_string());
+        let mut same_day = Condition::new_in("EXTRACT-OK".to_string());
+        same_day.odate = Some("ODAT".to_string());
+        load.in_conditions.push(same_day);
+
+        let mut reconcile = Job::new("RECONCILE".to_string(), "DAILY".to_string());
+        let mut prior_day = Condition::new_in("EXTRACT-OK".to_string());
+        prior_day.odate = Some("PREV".to_string());
+        reconcile.in_conditions.push(prior_day);
+
+        let mut folder = Folder::new("DAILY".to_string(), FolderType::Simple);
+        folder.add_job(extract);
+        folder.add_job(load);
+        folder.add_job(reconcile);
+
+        let generator = AirflowDependencyGenerator::new();
+        let dependencies = generator.build_dependencies(&[folder]);
+        assert_eq!(dependencies.len(), 2);
+        assert!(dependencies.iter().any(|d| d.consumer_task == "load" && d.is_in_dag()));
+
+        let prev = dependencies.iter().find(|d| d.consumer_task == "reconcile").unwrap();
+        assert!(!prev.is_in_dag());
+        assert_eq!(prev.sensor_run_argument().as_deref(), Some("execution_delta=timedelta(days=1)"));
+
+        let py = generator.render(&dependencies);
+        assert!(py.contains("extract >> load\n"));
+        assert!(py.contains("external_task_id=\"extract\",\n    execution_delta=timedelta(days=1),"));
+        assert!(py.contains("wait_daily_extract_prev >> reconcile\n"));
+        assert!(!py.contains("\nextract >> reconcile"));
+    }
+
+    #[test]
+    fn test_render_uses_the_airflow_version_and_keeps_conditions_in_comments() {
+        let dependency = AirflowDependency {
+            producer_dag: "daily".to_string(),
+            producer_task: "extract".to_string(),
+            consumer_dag: "reports".to_string(),
+            consumer_task: "report".to_string(),
+            condition: "EXTRACT-OK\nimport os".to_string(),
+            order_date: OrderDate::Current,
+        };
+
+        let py = AirflowDependencyGenerator::new()
+            .with_airflow_version(AirflowVersion::V3)
+            .render(&[dependency]);
+        assert!(py.contains("from airflow.providers.standard.sensors.external_task import ExternalTaskSensor\n"));
+        assert!(!py.contains("from airflow.sensors.external_task"));
+        assert!(py.contains("# EXTRACT-OK import os\n"));
+        assert!(!py.contains("\nimport os"));
+    }
+
+    #[test]
+    fn test_sensors_are_distinct_per_order_date_and_defined_once() {
+        let mut extract = Job::new("EXTRACT".to_string(), "DAILY".to_string());
+        extract.out_conditions.push(Condition::new_out("EXTRACT-OK".to_string()));
+        extract.out_conditions.push(Condition::new_out("EXTRACT-DONE".to_string()));
+        let mut daily = Folder::new("DAILY".to_string(), FolderType::Simple);
+        daily.add_job(extract);
+
+        let mut report = Job::new("REPORT".to_string(), "REPORTS".to_string());
+        for (name, odate) in [("EXTRACT-OK", "ODAT"), ("EXTRACT-DONE", "ODAT"), ("EXTRACT-OK", "PREV"), ("EXTRACT-OK", "0229")] {
+            let mut condition = Condition::new_in(name.to_string());
+            condition.odate = Some(odate.to_string());
+            report.in_conditions.push(condition);
+        }
+        let mut reports = Folder::new("REPORTS".to_string(), FolderType::Simple);
+        reports.add_job(report);
+
+        let generator = AirflowDependencyGenerator::new();
+        let py = generator.render(&generator.build_dependencies(&[daily, reports]));
+        assert_eq!(py.matches("wait_daily_extract = ExternalTaskSensor(").count(), 1);
+        assert_eq!(py.matches("wait_daily_extract >> report\n").count(), 1);
+        assert!(py.contains("wait_daily_extract_prev = ExternalTaskSensor("));
+        assert!(py.contains("wait_daily_extract_0229 = ExternalTaskSensor("));
+        assert!(py.contains("execution_date_fn=lambda dt, **_: fixed_order_date(dt, 2, 29),"));
+        assert!(py.contains("def fixed_order_date(dt, month, day):"));
+        assert!(!py.contains("dt.replace(month="));
+    }
+
+    #[test]
+    fn test_colliding_job_names_get_distinct_task_ids() {
+        let mut dashed = Job::new("JOB-A".to_string(), "DAILY".to_string());
+        dashed.out_conditions.push(Condition::new_out("A-OK".to_string()));
+        let mut underscored = Job::new("JOB_A".to_string(), "DAILY".to_string());
+        underscored.in_conditions.push(Condition::new_in("A-OK".to_string()));
+        let mut folder = Folder::new("DAILY".to_string(), FolderType::Simple);
+        folder.add_job(dashed);
+        folder.add_job(underscored);
+
+        assert_eq!(AirflowDependencyGenerator::task_ids(&folder), vec!["job_a", "job_a_2"]);
+
+        let dependencies = AirflowDependencyGenerator::new().build_dependencies(&[folder]);
+        assert_eq!(dependencies.len(), 1);
+        assert_eq!(dependencies[0].producer_task, "job_a");
+        assert_eq!(dependencies[0].consumer_task, "job_a_2");
+    }
+}
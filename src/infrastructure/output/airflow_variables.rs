@@ -0,0 +1,141 @@
+//! Airflow variables inventory module
+//!
+//! This module collects the job variables and auto-edits of a Control-M
+//! estate into a single `airflow_variables.json` file in the format accepted
+//! by `airflow variables import`. Identical name/value pairs are written once;
+//! names set to different values by different jobs are reported as
+//! collisions so they can be resolved before the import.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use crate::domain::entities::Folder;
+
+/// A variable name set to more than one value across jobs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VariableCollision {
+    /// Variable name, without the `%%` prefix
+    pub name: String,
+    /// Distinct values, the one written to the import file first
+    pub values: Vec<String>,
+}
+
+/// Deduplicated variables of an estate
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VariableInventory {
+    /// Variable values keyed by name, ready for `airflow variables import`
+    pub variables: BTreeMap<String, String>,
+    /// Names with differing values, sorted by name
+    pub collisions: Vec<VariableCollision>,
+}
+
+/// Generator for the Airflow variables inventory
+pub struct AirflowVariableGenerator;
+
+impl AirflowVariableGenerator {
+    /// Creates a new AirflowVariableGenerator instance
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Aggregates the variables and auto-edits of all jobs
+    ///
+    /// Names are stripped of their `%%` prefix, so `%%RUN_DATE` becomes the
+    /// Airflow Variable `RUN_DATE`. When a name has several values, the value
+    /// used by the most jobs is kept (the smallest on a tie) and the name is
+    /// listed as a collision.
+    ///
+    /// # Arguments
+    ///
+    /// * `folders` - Folders whose jobs (including sub-folder jobs) are read
+    ///
+    /// # Returns
+    ///
+    /// The deduplicated variables and the name collisions
+    pub fn build_inventory(&self, folders: &[Folder]) -> VariableInventory {
+        let mut usage: BTreeMap<String, HashMap<&str, usize>> = BTreeMap::new();
+        for job in folders.iter().flat_map(|folder| folder.all_jobs()) {
+            for (name, value) in job.variables.iter().chain(job.auto_edits.iter()) {
+                *usage.entry(Self::variable_name(name)).or_default()
+                    .entry(value.as_str()).or_insert(0) += 1;
+            }
+        }
+
+        let mut inventory = VariableInventory::default();
+        for (name, counts) in usage {
+            let mut values: Vec<(&str, usize)> = counts.into_iter().collect();
+            values.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then_with(|| a.cmp(b)));
+            inventory.variables.insert(name.clone(), values[0].0.to_string());
+            if values.len() > 1 {
+                inventory.collisions.push(VariableCollision {
+                    name,
+                    values: values.into_iter().map(|(value, _)| value.to_string()).collect(),
+                });
+            }
+        }
+        inventory
+    }
+
+    /// Writes the variables inventory of all folders to a file
+    ///
+    /// # Arguments
+    ///
+    /// * `folders` - Folders whose jobs (including sub-folder jobs) are read
+    /// * `output_path` - Path of the `airflow_variables.json` file to write
+    ///
+    /// # Returns
+    ///
+    /// The inventory written, including its collisions
+    ///
+    /// # Errors
+    ///
+    /// Returns error if serialization or the file write fails
+    pub fn generate<P: AsRef<Path>>(&self, folders: &[Folder], output_path: P) -> Result<VariableInventory> {
+        let inventory = self.build_inventory(folders);
+        let json = serde_json::to_string_pretty(&inventory.variables)
+            .context("Failed to serialize Airflow variables")?;
+        fs::write(output_path.as_ref(), json)
+            .context("Failed to write Airflow variables file")?;
+        Ok(inventory)
+    }
+
+    /// Converts a Control-M variable name into an Airflow Variable key
+    fn variable_name(name: &str) -> String {
+        name.trim().trim_start_matches("%%").to_string()
+    }
+}
+
+impl Default for AirflowVariableGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{FolderType, Job};
+
+    #[test]
+    fn test_inventory_deduplicates_and_flags_collisions() {
+        let mut folder = Folder::new("DAILY".to_string(), FolderType::Simple);
+        for (job_name, env) in [("EXTRACT", "PROD"), ("LOAD", "PROD"), ("REPORT", "TEST")] {
+            let mut job = Job::new(job_name.to_string(), "DAILY".to_string());
+            job.variables.insert("%%ENV".to_string(), env.to_string());
+            job.auto_edits.insert("%%TARGET_DB".to_string(), "DWH".to_string());
+            folder.add_job(job);
+        }
+
+        let inventory = AirflowVariableGenerator::new().build_inventory(&[folder]);
+
+        assert_eq!(inventory.variables.len(), 2);
+        assert_eq!(inventory.variables["ENV"], "PROD");
+        assert_eq!(inventory.variables["TARGET_DB"], "DWH");
+        assert_eq!(inventory.collisions, vec![VariableCollision {
+            name: "ENV".to_string(),
+            values: vec!["PROD".to_string(), "TEST".to_string()],
+        }]);
+    }
+}
@@ -0,0 +1,114 @@
+//! Report bundle writer module
+//!
+//! This module packs generated report files into a single zip archive so a
+//! complete analysis can be shared as one file. Paths inside the archive are
+//! kept relative to the output directory, so the HTML report still finds its
+//! `data/` files once unzipped.
+
+use anyhow::{Context, Result};
+use std::fs::{self, File};
+use std::io::{self, Cursor, Write};
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+/// Writer for zip bundles of report files
+pub struct BundleWriter;
+
+impl BundleWriter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Writes the given report files into a zip archive
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - Directory the entries are relative to
+    /// * `entries` - Files or directories to include, relative to `root`;
+    ///   directories are added recursively and missing entries are skipped
+    /// * `bundle_path` - Destination zip file
+    ///
+    /// # Returns
+    ///
+    /// Number of files written to the archive
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a file cannot be read or the archive cannot be written
+    pub fn write<P: AsRef<Path>, Q: AsRef<Path>>(&self, root: P, entries: &[PathBuf], bundle_path: Q) -> Result<usize> {
+        let root = root.as_ref();
+        let mut files = Vec::new();
+        for entry in entries {
+            Self::collect_files(root, entry, &mut files)?;
+        }
+
+        let bundle = File::create(bundle_path.as_ref())
+            .with_context(|| format!("Failed to create bundle {:?}", bundle_path.as_ref()))?;
+        let mut zip = ZipWriter::new(bundle);
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+        for relative in &files {
+            // Zip entry names always use forward slashes
+            let name = relative.components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            zip.start_file(name, options)?;
+            let mut source = File::open(root.join(relative))
+                .with_context(|| format!("Failed to read {:?}", relative))?;
+            io::copy(&mut source, &mut zip)?;
+        }
+
+        zip.finish()?;
+        Ok(files.len())
+    }
+
+    /// Packs in-memory files into a zip archive
+    ///
+    /// # Arguments
+    ///
+    /// * `entries` - Entry names (forward-slash separated) with their contents
+    ///
+    /// # Returns
+    ///
+    /// The zip archive bytes
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the archive cannot be written
+    pub fn write_to_vec(&self, entries: &[(String, Vec<u8>)]) -> Result<Vec<u8>> {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+        for (name, content) in entries {
+            zip.start_file(name.as_str(), options)?;
+            zip.write_all(content)?;
+        }
+
+        Ok(zip.finish()?.into_inner())
+    }
+
+    fn collect_files(root: &Path, relative: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+        let path = root.join(relative);
+        if path.is_dir() {
+            let mut children: Vec<_> = fs::read_dir(&path)?
+                .filter_map(|e| e.ok())
+                .map(|e| relative.join(e.file_name()))
+                .collect();
+            children.sort();
+            for child in children {
+                Self::collect_files(root, &child, files)?;
+            }
+        } else if path.is_file() {
+            files.push(relative.to_path_buf());
+        }
+        Ok(())
+    }
+}
+
+impl Default for BundleWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,55 +1,121 @@
 use anyhow::Result;
-use std::fs::File;
+use std::fs;
 use std::path::Path;
 use csv::Writer;
+use crate::application::use_cases::FolderDependencyMatrix;
+use crate::domain::value_objects::COMPLEXITY_FACTORS;
 use crate::presentation::dto::AnalysisOutput;
+use super::generation_report::GenerationReport;
+use super::text_encoding::OutputEncoding;
 
-pub struct CsvGenerator;
+pub struct CsvGenerator {
+    /// Character encoding of the written files
+    encoding: OutputEncoding,
+}
 
 impl CsvGenerator {
     pub fn new() -> Self {
-        Self
+        Self {
+            encoding: OutputEncoding::default(),
+        }
     }
 
-    pub fn generate<P: AsRef<Path>>(&self, output: &AnalysisOutput, path: P) -> Result<()> {
-        let file = File::create(path)?;
-        let mut wtr = Writer::from_writer(file);
+    /// Sets the character encoding of the written files (UTF-8 by default)
+    pub fn with_encoding(mut self, encoding: OutputEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
 
-        wtr.write_record(&[
+    pub fn generate<P: AsRef<Path>>(&self, output: &AnalysisOutput, path: P) -> Result<GenerationReport> {
+        let mut report = GenerationReport::for_output(output);
+        let mut wtr = Writer::from_writer(Vec::new());
+        let mut jobs_without_wave = 0;
+
+        let mut header: Vec<String> = [
             "Job Name",
             "Folder",
             "Complexity Score",
             "Migration Difficulty",
+            "Migration Readiness",
             "Priority",
             "Dependencies",
             "Critical",
             "Cyclic",
             "Effort Hours",
             "Wave",
-        ])?;
+            "Migration Notes",
+        ].iter().map(|column| column.to_string()).collect();
+        // One sortable column per complexity factor, e.g. "Score: scheduling"
+        header.extend(COMPLEXITY_FACTORS.iter().map(|factor| format!("Score: {}", factor)));
+        wtr.write_record(&header)?;
 
         for job in &output.jobs {
             let wave = output.migration_waves
                 .iter()
                 .find(|w| w.jobs.contains(&job.job_name))
                 .map(|w| w.wave.to_string())
-                .unwrap_or_else(|| "N/A".to_string());
-
-            wtr.write_record(&[
-                &job.job_name,
-                &job.folder,
-                &job.complexity_score.to_string(),
-                &job.migration_difficulty,
-                &job.migration_priority.to_string(),
-                &job.metrics.dependency_count.to_string(),
-                &job.metrics.is_critical.to_string(),
-                &job.metrics.is_cyclic.to_string(),
-                &job.airflow_mapping.estimated_effort_hours.to_string(),
-                &wave,
-            ])?;
+                .unwrap_or_else(|| {
+                    jobs_without_wave += 1;
+                    "N/A".to_string()
+                });
+
+            let mut record = vec![
+                job.job_name.clone(),
+                job.folder.clone(),
+                job.complexity_score.to_string(),
+                job.migration_difficulty.clone(),
+                job.migration_readiness.clone(),
+                job.migration_priority.to_string(),
+                job.metrics.dependency_count.to_string(),
+                job.metrics.is_critical.to_string(),
+                job.metrics.is_cyclic.to_string(),
+                job.airflow_mapping.estimated_effort_hours.to_string(),
+                wave,
+                job.migration_notes.join("; "),
+            ];
+            record.extend(COMPLEXITY_FACTORS.iter()
+                .map(|factor| job.factor_breakdown.get(*factor).copied().unwrap_or(0).to_string()));
+            wtr.write_record(&record)?;
         }
 
-        wtr.flush()?;
+        if jobs_without_wave > 0 {
+            report.warn(format!("{} job(s) are in no migration wave; Wave written as N/A", jobs_without_wave));
+        }
+        self.write(wtr, path.as_ref(), &mut report)?;
+        Ok(report)
+    }
+
+    /// Writes the folder dependency matrix as CSV
+    ///
+    /// The first column holds the producing folder; each following column
+    /// holds the edge count towards the consuming folder named in the header.
+    pub fn generate_folder_matrix<P: AsRef<Path>>(&self, matrix: &FolderDependencyMatrix, path: P) -> Result<GenerationReport> {
+        let mut report = GenerationReport::new();
+        let mut wtr = Writer::from_writer(Vec::new());
+
+        let mut header = vec!["From/To".to_string()];
+        header.extend(matrix.folders.iter().cloned());
+        wtr.write_record(&header)?;
+
+        for (folder, row) in matrix.folders.iter().zip(&matrix.counts) {
+            let mut record = vec![folder.clone()];
+            record.extend(row.iter().map(|count| count.to_string()));
+            wtr.write_record(&record)?;
+        }
+
+        self.write(wtr, path.as_ref(), &mut report)?;
+        Ok(report)
+    }
+
+    /// Re-encodes the buffered CSV text and writes it to `path`, recording
+    /// characters the encoding cannot represent in `report`
+    fn write(&self, wtr: Writer<Vec<u8>>, path: &Path, report: &mut GenerationReport) -> Result<()> {
+        let text = String::from_utf8(wtr.into_inner()?)?;
+        let (bytes, substitutions) = self.encoding.encode_text(&text);
+        if let Some(warning) = substitutions.warning(&path.display().to_string(), self.encoding) {
+            report.warn(warning);
+        }
+        fs::write(path, bytes)?;
         Ok(())
     }
 }
@@ -69,4 +135,74 @@ mod tests {
         let _generator = CsvGenerator::new();
         assert!(true);
     }
+
+    #[test]
+    fn test_csv_written_in_windows_1252() {
+        use crate::application::use_cases::AnalyzeJobs;
+        use crate::domain::entities::{Folder, FolderType, Job};
+
+        let mut folder = Folder::new("FINANÇAS".to_string(), FolderType::Simple);
+        folder.add_job(Job::new("RELATÓRIO_JOSÉ".to_string(), "FINANÇAS".to_string()));
+        let output = AnalysisOutput::from_analysis_result(AnalyzeJobs::new().execute(&[folder]).unwrap());
+
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("csv_1252.csv");
+        CsvGenerator::new()
+            .with_encoding("windows-1252".parse().unwrap())
+            .generate(&output, &path)
+            .unwrap();
+        let bytes = fs::read(&path).unwrap();
+
+        // É is one byte (0xC9) in Windows-1252, two in UTF-8
+        assert!(bytes.windows(2).any(|pair| pair == b"\xC9,"));
+        assert!(std::str::from_utf8(&bytes).is_err());
+        let (text, _, had_errors) = encoding_rs::WINDOWS_1252.decode(&bytes);
+        assert!(!had_errors);
+        assert!(text.contains("RELATÓRIO_JOSÉ,FINANÇAS,"));
+    }
+
+    #[test]
+    fn test_csv_replaces_unencodable_characters_with_question_marks() {
+        use crate::application::use_cases::AnalyzeJobs;
+        use crate::domain::entities::{Folder, FolderType, Job};
+
+        let mut folder = Folder::new("FIN".to_string(), FolderType::Simple);
+        folder.add_job(Job::new("이동_JOB".to_string(), "FIN".to_string()));
+        let output = AnalysisOutput::from_analysis_result(AnalyzeJobs::new().execute(&[folder]).unwrap());
+
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("csv_unmappable.csv");
+        let report = CsvGenerator::new()
+            .with_encoding("windows-1252".parse().unwrap())
+            .generate(&output, &path)
+            .unwrap();
+        let bytes = fs::read(&path).unwrap();
+
+        let text = String::from_utf8(bytes).unwrap();
+        assert!(text.contains("??_JOB,FIN,"));
+        assert!(!text.contains("&#"));
+        assert!(report.warnings.iter().any(|w| w.contains("2 character(s)") && w.contains("U+C774")));
+    }
+
+    #[test]
+    fn test_csv_quotes_fields_with_quotes_and_newlines() {
+        use crate::application::use_cases::AnalyzeJobs;
+        use crate::domain::entities::{Folder, FolderType, Job};
+
+        let folder_name = "ETL \"daily\"\nbatch";
+        let mut folder = Folder::new(folder_name.to_string(), FolderType::Simple);
+        folder.add_job(Job::new("LOAD, STAGE".to_string(), folder_name.to_string()));
+        let output = AnalysisOutput::from_analysis_result(AnalyzeJobs::new().execute(&[folder]).unwrap());
+
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("csv_quoting.csv");
+        CsvGenerator::new().generate(&output, &path).unwrap();
+        let text = fs::read_to_string(&path).unwrap();
+
+        assert!(text.contains("\"LOAD, STAGE\",\"ETL \"\"daily\"\"\nbatch\","));
+        let mut reader = csv::Reader::from_reader(text.as_bytes());
+        let record = reader.records().next().unwrap().unwrap();
+        assert_eq!(&record[0], "LOAD, STAGE");
+        assert_eq!(&record[1], folder_name);
+    }
 }
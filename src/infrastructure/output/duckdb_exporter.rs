@@ -0,0 +1,323 @@
+//! DuckDB Exporter module
+//!
+//! This module provides functionality to export Control-M job definitions
+//! to a DuckDB database for ad-hoc analytical queries. Jobs are written to a
+//! denormalized `jobs` table, with the job attributes of the SQLite `jobs`
+//! table and pre-computed dependency counts, alongside a flat `conditions`
+//! table.
+//!
+//! Only available with the `duckdb` cargo feature.
+
+use anyhow::{Context, Result};
+use duckdb::types::{ToSql, ToSqlOutput, ValueRef};
+use duckdb::{params, params_from_iter, Connection};
+use std::path::Path;
+
+use crate::domain::entities::*;
+use super::sqlite_exporter::{folder_type_name, JobField, JobValue, JOB_FIELDS};
+
+/// DuckDB exporter for Control-M job definitions
+///
+/// Writes one row per job with the job attributes the SQLite exporter
+/// stores, mapped through the same `JOB_FIELDS` table.
+pub struct DuckDbExporter {
+    /// DuckDB database connection
+    conn: Connection,
+    /// Maximum nesting depth of folders (top-level folders are depth 1)
+    max_folder_depth: usize,
+}
+
+impl ToSql for JobValue<'_> {
+    fn to_sql(&self) -> duckdb::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::Borrowed(match *self {
+            JobValue::Text(Some(text)) => ValueRef::Text(text.as_bytes()),
+            JobValue::Int(Some(value)) => ValueRef::Int(value),
+            JobValue::Flag(flag) => ValueRef::Boolean(flag),
+            JobValue::Text(None) | JobValue::Int(None) => ValueRef::Null,
+        }))
+    }
+}
+
+impl DuckDbExporter {
+    /// Creates a new DuckDB exporter and initializes the database
+    ///
+    /// # Arguments
+    ///
+    /// * `db_path` - Path to the DuckDB database file
+    ///
+    /// # Returns
+    ///
+    /// Result containing the DuckDbExporter or an error
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database cannot be opened or the schema cannot be created
+    pub fn new<P: AsRef<Path>>(db_path: P) -> Result<Self> {
+        let conn = Connection::open(db_path)
+            .context("Failed to open DuckDB database")?;
+
+        let exporter = Self {
+            conn,
+            max_folder_depth: DEFAULT_MAX_FOLDER_DEPTH,
+        };
+        exporter.create_schema()?;
+
+        Ok(exporter)
+    }
+
+    /// Sets the maximum folder nesting depth accepted during export
+    ///
+    /// # Arguments
+    ///
+    /// * `max_folder_depth` - Maximum allowed nesting depth (top-level folders are depth 1)
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn with_max_folder_depth(mut self, max_folder_depth: usize) -> Self {
+        self.max_folder_depth = max_folder_depth;
+        self
+    }
+
+    /// Creates the analytical schema
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or error
+    fn create_schema(&self) -> Result<()> {
+        let attribute_columns: Vec<String> = JOB_FIELDS.iter()
+            .map(|(name, field)| {
+                let column_type = match field {
+                    JobField::Text(_) => "VARCHAR",
+                    JobField::Int(_) => "INTEGER",
+                    JobField::Flag(_) => "BOOLEAN NOT NULL",
+                };
+                format!("{} {},", name, column_type)
+            })
+            .collect();
+
+        self.conn.execute_batch(&format!(
+            r#"
+            CREATE TABLE IF NOT EXISTS jobs (
+                job_name VARCHAR NOT NULL,
+                folder_name VARCHAR NOT NULL,
+                folder_type VARCHAR NOT NULL,
+                datacenter VARCHAR,
+                {}
+                in_condition_count INTEGER NOT NULL,
+                out_condition_count INTEGER NOT NULL,
+                on_condition_count INTEGER NOT NULL,
+                control_resource_count INTEGER NOT NULL,
+                quantitative_resource_count INTEGER NOT NULL,
+                variable_count INTEGER NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS conditions (
+                job_name VARCHAR NOT NULL,
+                folder_name VARCHAR NOT NULL,
+                condition_type VARCHAR NOT NULL,
+                condition_name VARCHAR NOT NULL,
+                odate VARCHAR
+            );
+            "#,
+            attribute_columns.join("\n                ")
+        )).context("Failed to create DuckDB schema")?;
+
+        Ok(())
+    }
+
+    /// Exports all folders (including sub-folders) to the DuckDB database
+    ///
+    /// # Arguments
+    ///
+    /// * `folders` - Slice of Folder entities to export
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or error
+    pub fn export_folders(&self, folders: &[Folder]) -> Result<()> {
+        self.conn.execute_batch("BEGIN TRANSACTION")?;
+
+        let result = folders.iter().try_for_each(|folder| self.export_folder(folder, folder.datacenter.as_deref(), 1));
+
+        match result {
+            Ok(()) => {
+                self.conn.execute_batch("COMMIT")?;
+                Ok(())
+            }
+            Err(e) => {
+                self.conn.execute_batch("ROLLBACK")?;
+                Err(e)
+            }
+        }
+    }
+
+    /// Exports a folder and its sub-folders
+    ///
+    /// # Arguments
+    ///
+    /// * `folder` - Folder entity to export
+    /// * `datacenter` - Datacenter of the top-level folder
+    /// * `depth` - Nesting depth of this folder (top-level folders are depth 1)
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or error
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `depth` exceeds the configured maximum folder depth
+    fn export_folder(&self, folder: &Folder, datacenter: Option<&str>, depth: usize) -> Result<()> {
+        if depth > self.max_folder_depth {
+            anyhow::bail!(
+                "Folder '{}' exceeds maximum nesting depth of {}",
+                folder.folder_name,
+                self.max_folder_depth
+            );
+        }
+
+        let folder_type = folder_type_name(&folder.folder_type);
+
+        for job in &folder.jobs {
+            self.export_job(job, folder_type, datacenter)?;
+        }
+
+        for sub_folder in &folder.sub_folders {
+            self.export_folder(sub_folder, datacenter, depth + 1)?;
+        }
+
+        Ok(())
+    }
+
+    /// Exports a single job row and its conditions
+    ///
+    /// # Arguments
+    ///
+    /// * `job` - Job entity to export
+    /// * `folder_type` - Storage name of the parent folder type
+    /// * `datacenter` - Datacenter of the top-level folder
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or error
+    fn export_job(&self, job: &Job, folder_type: &str, datacenter: Option<&str>) -> Result<()> {
+        let placeholders = vec!["?"; JOB_FIELDS.len() + 10].join(", ");
+        let mut stmt = self.conn.prepare_cached(&format!("INSERT INTO jobs VALUES ({})", placeholders))?;
+
+        let attributes: Vec<JobValue> = JOB_FIELDS.iter().map(|(_, field)| field.read(job)).collect();
+        let counts = [
+            job.in_conditions.len() as i32,
+            job.out_conditions.len() as i32,
+            job.on_conditions.len() as i32,
+            job.control_resources.len() as i32,
+            job.quantitative_resources.len() as i32,
+            job.variables.len() as i32,
+        ];
+        let identity: [&dyn ToSql; 4] = [&job.job_name, &job.folder_name, &folder_type, &datacenter];
+        let row = identity.into_iter()
+            .chain(attributes.iter().map(|value| value as &dyn ToSql))
+            .chain(counts.iter().map(|count| count as &dyn ToSql));
+        stmt.execute(params_from_iter(row))
+            .with_context(|| format!("Failed to insert job {}", job.job_name))?;
+
+        let mut cond_stmt = self.conn.prepare_cached(
+            "INSERT INTO conditions VALUES (?, ?, ?, ?, ?)"
+        )?;
+        for condition in job.in_conditions.iter().chain(job.out_conditions.iter()) {
+            let condition_type = match condition.condition_type {
+                ConditionType::In => "IN",
+                ConditionType::Out => "OUT",
+            };
+            cond_stmt.execute(params![
+                &job.job_name,
+                &job.folder_name,
+                condition_type,
+                &condition.name,
+                &condition.odate,
+            ])?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the underlying connection for running analytical queries
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_and_aggregate() {
+        let tmp = tempfile::tempdir().unwrap();
+        let db_path = tmp.path().join("jobs.duckdb");
+        let exporter = DuckDbExporter::new(&db_path).unwrap();
+
+        let mut folder = Folder::new("TEST_FOLDER".to_string(), FolderType::Simple);
+        for (name, app) in [("JOB1", "APP1"), ("JOB2", "APP1"), ("JOB3", "APP2")] {
+            let mut job = Job::new(name.to_string(), "TEST_FOLDER".to_string());
+            job.application = Some(app.to_string());
+            job.in_conditions.push(Condition::new_in("COND".to_string()));
+            folder.add_job(job);
+        }
+
+        exporter.export_folders(&[folder]).unwrap();
+
+        let (app, count, deps): (String, i64, i64) = exporter.connection().query_row(
+            "SELECT application, COUNT(*), SUM(in_condition_count)::BIGINT FROM jobs
+             GROUP BY application ORDER BY COUNT(*) DESC LIMIT 1",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        ).unwrap();
+
+        assert_eq!(app, "APP1");
+        assert_eq!(count, 2);
+        assert_eq!(deps, 2);
+    }
+
+    #[test]
+    fn test_jobs_share_sqlite_attribute_columns() {
+        let tmp = tempfile::tempdir().unwrap();
+        let db_path = tmp.path().join("columns.duckdb");
+        let exporter = DuckDbExporter::new(&db_path).unwrap();
+
+        let mut folder = Folder::new("TEST_FOLDER".to_string(), FolderType::Simple);
+        let mut job = Job::new("JOB1".to_string(), "TEST_FOLDER".to_string());
+        job.priority = Some("10".to_string());
+        job.memname = Some("load.sh".to_string());
+        job.critical = true;
+        folder.add_job(job);
+
+        exporter.export_folders(&[folder]).unwrap();
+
+        let (priority_num, memname, critical): (i32, String, bool) = exporter.connection().query_row(
+            "SELECT priority_num, memname, critical FROM jobs WHERE job_name = 'JOB1'",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        ).unwrap();
+
+        assert_eq!(priority_num, 10);
+        assert_eq!(memname, "load.sh");
+        assert!(critical);
+    }
+
+    #[test]
+    fn test_export_rejects_folders_nested_too_deep() {
+        let tmp = tempfile::tempdir().unwrap();
+        let db_path = tmp.path().join("depth.duckdb");
+        let exporter = DuckDbExporter::new(&db_path).unwrap().with_max_folder_depth(2);
+
+        let mut inner = Folder::new("INNER".to_string(), FolderType::Smart);
+        inner.add_job(Job::new("JOB1".to_string(), "INNER".to_string()));
+        let mut middle = Folder::new("MIDDLE".to_string(), FolderType::Smart);
+        middle.add_sub_folder(inner);
+        let mut outer = Folder::new("OUTER".to_string(), FolderType::Smart);
+        outer.add_sub_folder(middle);
+
+        let err = exporter.export_folders(&[outer]).unwrap_err();
+        assert!(err.to_string().contains("INNER"));
+    }
+}
@@ -0,0 +1,80 @@
+//! Generation Report module
+//!
+//! This module collects non-fatal issues found while writing a report (an
+//! empty analysis, waves without jobs, a percentage that would divide by
+//! zero). Generators still write their output; the report lets the CLI tell
+//! the user which parts of it are degraded.
+
+use crate::presentation::dto::AnalysisOutput;
+
+/// Non-fatal issues found while generating an output file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GenerationReport {
+    /// Warnings in the order they were found, without duplicates
+    pub warnings: Vec<String>,
+}
+
+impl GenerationReport {
+    /// Creates an empty report
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a report with the issues common to every output format
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - Analysis output about to be written
+    ///
+    /// # Returns
+    ///
+    /// A report warning about an empty analysis and waves without jobs
+    pub fn for_output(output: &AnalysisOutput) -> Self {
+        let mut report = Self::new();
+        if output.summary.total_jobs == 0 {
+            report.warn("The analysis contains no jobs; the report is empty");
+        }
+        for wave in output.migration_waves.iter().filter(|wave| wave.jobs.is_empty()) {
+            report.warn(format!("Migration wave {} has no jobs", wave.wave_number));
+        }
+        report
+    }
+
+    /// Records a warning, ignoring repeats of the same message
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - Description of the issue
+    pub fn warn(&mut self, message: impl Into<String>) {
+        let message = message.into();
+        if !self.warnings.contains(&message) {
+            self.warnings.push(message);
+        }
+    }
+
+    /// Computes `count` as a percentage of `total`, warning instead of
+    /// producing NaN when `total` is zero
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - Part being measured
+    /// * `total` - Whole the percentage is taken of
+    /// * `what` - Name of the figure, used in the warning
+    ///
+    /// # Returns
+    ///
+    /// The percentage, or 0.0 if `total` is zero
+    pub fn percentage(&mut self, count: usize, total: usize, what: &str) -> f64 {
+        if total == 0 {
+            self.warn(format!("{} shown as 0.0% because the total is zero", what));
+            0.0
+        } else {
+            (count as f64 / total as f64) * 100.0
+        }
+    }
+
+    /// Checks whether no issues were found
+    pub fn is_empty(&self) -> bool {
+        self.warnings.is_empty()
+    }
+}
@@ -0,0 +1,387 @@
+//! Dependency graph exporter module
+//!
+//! This module writes the resolved job dependency graph of the whole estate
+//! in Graphviz DOT, Mermaid, GEXF or JSON format. Nodes are jobs, identified
+//! by folder and job name so same-named jobs in different folders stay
+//! apart; edges link the job setting a condition to each job waiting for it.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+use tracing::warn;
+use crate::application::services::DependencyAnalyzer;
+use crate::domain::entities::Job;
+
+/// Default maximum number of nodes written before the graph is truncated
+pub const DEFAULT_MAX_GRAPH_NODES: usize = 5000;
+
+/// Supported graph output formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    Dot,
+    Mermaid,
+    Gexf,
+    Json,
+}
+
+impl GraphFormat {
+    /// File extension for this format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            GraphFormat::Dot => "dot",
+            GraphFormat::Mermaid => "mmd",
+            GraphFormat::Gexf => "gexf",
+            GraphFormat::Json => "json",
+        }
+    }
+}
+
+impl FromStr for GraphFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "dot" => Ok(GraphFormat::Dot),
+            "mermaid" => Ok(GraphFormat::Mermaid),
+            "gexf" => Ok(GraphFormat::Gexf),
+            "json" => Ok(GraphFormat::Json),
+            other => Err(format!("Unknown graph format '{}' (expected dot, mermaid, gexf or json)", other)),
+        }
+    }
+}
+
+/// A job node in the estate graph
+#[derive(Debug, Clone, Serialize)]
+pub struct GraphNode {
+    /// Node id, `folder/job`
+    pub id: String,
+    pub job_name: String,
+    pub folder_name: String,
+    /// Whether the job is marked critical in Control-M
+    pub critical: bool,
+}
+
+/// A resolved dependency between two jobs
+#[derive(Debug, Clone, Serialize)]
+pub struct GraphEdge {
+    /// Node id of the job setting the condition
+    pub from: String,
+    /// Node id of the job waiting for the condition
+    pub to: String,
+    /// Condition linking the two jobs
+    pub condition: String,
+}
+
+/// The resolved dependency graph of a set of jobs
+#[derive(Debug, Clone, Serialize)]
+pub struct EstateGraph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+    /// True when nodes were dropped to respect the node limit
+    pub truncated: bool,
+}
+
+impl EstateGraph {
+    /// Builds the graph by matching job in-conditions to out-conditions
+    ///
+    /// # Arguments
+    ///
+    /// * `jobs` - Slice of job references to include
+    /// * `max_nodes` - Maximum number of nodes to keep; edges touching dropped nodes are dropped too
+    pub fn from_jobs(jobs: &[&Job], max_nodes: usize) -> Self {
+        let ids: Vec<String> = jobs.iter().map(|job| Self::node_id(job)).collect();
+
+        let mut seen = HashSet::new();
+        let all_nodes: Vec<GraphNode> = jobs.iter()
+            .zip(&ids)
+            .filter(|(_, id)| seen.insert(id.as_str()))
+            .map(|(job, id)| GraphNode {
+                id: id.clone(),
+                job_name: job.job_name.clone(),
+                folder_name: job.folder_name.clone(),
+                critical: job.critical,
+            })
+            .collect();
+
+        let truncated = all_nodes.len() > max_nodes;
+        let nodes: Vec<GraphNode> = all_nodes.into_iter().take(max_nodes).collect();
+        let kept: HashSet<&str> = nodes.iter().map(|n| n.id.as_str()).collect();
+
+        let producers: HashMap<&str, Vec<usize>> = DependencyAnalyzer::producer_map(jobs);
+        let mut edges = Vec::new();
+        for (consumer, job) in jobs.iter().enumerate() {
+            for in_cond in &job.in_conditions {
+                for &producer in producers.get(in_cond.name.as_str()).into_iter().flatten() {
+                    if kept.contains(ids[producer].as_str()) && kept.contains(ids[consumer].as_str()) {
+                        edges.push(GraphEdge {
+                            from: ids[producer].clone(),
+                            to: ids[consumer].clone(),
+                            condition: in_cond.name.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        Self { nodes, edges, truncated }
+    }
+
+    /// Gets the node id of a job, `folder/job`
+    pub fn node_id(job: &Job) -> String {
+        DependencyAnalyzer::job_key(&job.folder_name, &job.job_name)
+    }
+}
+
+/// Writer for estate dependency graphs
+pub struct GraphExporter {
+    max_nodes: usize,
+    /// Whether DOT output draws each folder's jobs in one box
+    cluster_by_folder: bool,
+}
+
+impl GraphExporter {
+    pub fn new() -> Self {
+        Self {
+            max_nodes: DEFAULT_MAX_GRAPH_NODES,
+            cluster_by_folder: false,
+        }
+    }
+
+    /// Sets the maximum number of nodes written before truncating
+    pub fn with_max_nodes(mut self, max_nodes: usize) -> Self {
+        self.max_nodes = max_nodes;
+        self
+    }
+
+    /// Sets whether DOT output groups jobs into one `subgraph cluster_N` per folder
+    pub fn with_cluster_by_folder(mut self, cluster_by_folder: bool) -> Self {
+        self.cluster_by_folder = cluster_by_folder;
+        self
+    }
+
+    /// Builds the estate graph for the given jobs and writes it to a file
+    ///
+    /// # Arguments
+    ///
+    /// * `jobs` - Slice of job references to include
+    /// * `format` - Output format
+    /// * `path` - Destination file path
+    ///
+    /// # Returns
+    ///
+    /// The graph that was written
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the file cannot be written
+    pub fn export<P: AsRef<Path>>(&self, jobs: &[&Job], format: GraphFormat, path: P) -> Result<EstateGraph> {
+        let graph = EstateGraph::from_jobs(jobs, self.max_nodes);
+        if graph.truncated {
+            warn!("Dependency graph truncated to {} of {} jobs", self.max_nodes, jobs.len());
+        }
+
+        let content = self.render(&graph, format)?;
+        let mut file = File::create(path)?;
+        file.write_all(content.as_bytes())?;
+        Ok(graph)
+    }
+
+    /// Renders a graph in the given format
+    pub fn render(&self, graph: &EstateGraph, format: GraphFormat) -> Result<String> {
+        Ok(match format {
+            GraphFormat::Dot => self.render_dot(graph),
+            GraphFormat::Mermaid => Self::render_mermaid(graph),
+            GraphFormat::Gexf => Self::render_gexf(graph),
+            GraphFormat::Json => serde_json::to_string_pretty(graph)?,
+        })
+    }
+
+    /// Renders DOT with jobs as boxes, critical jobs in red and edges
+    /// labeled by condition, optionally clustered by folder
+    fn render_dot(&self, graph: &EstateGraph) -> String {
+        let mut dot = String::from("digraph dependencies {\n");
+        if graph.truncated {
+            dot.push_str("    // truncated: not all jobs are shown\n");
+        }
+        dot.push_str("    rankdir=LR;\n");
+        dot.push_str("    node [shape=box];\n");
+
+        let node_line = |node: &GraphNode| {
+            let color = if node.critical { ", color=red, fontcolor=red" } else { "" };
+            format!(
+                "\"{}\" [label=\"{}\", tooltip=\"{}\"{}];\n",
+                Self::escape_quotes(&node.id),
+                Self::escape_quotes(&node.job_name),
+                Self::escape_quotes(&node.folder_name),
+                color
+            )
+        };
+        if self.cluster_by_folder {
+            let mut clusters: BTreeMap<&str, Vec<&GraphNode>> = BTreeMap::new();
+            for node in &graph.nodes {
+                clusters.entry(node.folder_name.as_str()).or_default().push(node);
+            }
+            for (index, (folder, nodes)) in clusters.iter().enumerate() {
+                dot.push_str(&format!("    subgraph cluster_{} {{\n", index));
+                dot.push_str(&format!("        label=\"{}\";\n", Self::escape_quotes(folder)));
+                for node in nodes {
+                    dot.push_str(&format!("        {}", node_line(node)));
+                }
+                dot.push_str("    }\n");
+            }
+        } else {
+            for node in &graph.nodes {
+                dot.push_str(&format!("    {}", node_line(node)));
+            }
+        }
+        for edge in &graph.edges {
+            dot.push_str(&format!(
+                "    \"{}\" -> \"{}\" [label=\"{}\"];\n",
+                Self::escape_quotes(&edge.from),
+                Self::escape_quotes(&edge.to),
+                Self::escape_quotes(&edge.condition)
+            ));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    fn render_mermaid(graph: &EstateGraph) -> String {
+        let mut mermaid = String::from("graph LR\n");
+        if graph.truncated {
+            mermaid.push_str("    %% truncated: not all jobs are shown\n");
+        }
+        let ids: HashMap<&str, usize> = graph.nodes.iter()
+            .enumerate()
+            .map(|(i, n)| (n.id.as_str(), i))
+            .collect();
+        for (i, node) in graph.nodes.iter().enumerate() {
+            mermaid.push_str(&format!("    n{}[\"{}\"]\n", i, node.job_name.replace('"', "#quot;")));
+        }
+        for edge in &graph.edges {
+            mermaid.push_str(&format!(
+                "    n{} -->|\"{}\"| n{}\n",
+                ids[edge.from.as_str()],
+                edge.condition.replace('"', "#quot;"),
+                ids[edge.to.as_str()]
+            ));
+        }
+        mermaid
+    }
+
+    fn render_gexf(graph: &EstateGraph) -> String {
+        let mut gexf = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        gexf.push_str("<gexf xmlns=\"http://gexf.net/1.3\" version=\"1.3\">\n");
+        gexf.push_str("  <graph defaultedgetype=\"directed\">\n");
+        gexf.push_str("    <nodes>\n");
+        for node in &graph.nodes {
+            gexf.push_str(&format!(
+                "      <node id=\"{}\" label=\"{}\"/>\n",
+                Self::escape_xml(&node.id),
+                Self::escape_xml(&node.job_name)
+            ));
+        }
+        gexf.push_str("    </nodes>\n");
+        gexf.push_str("    <edges>\n");
+        for (i, edge) in graph.edges.iter().enumerate() {
+            gexf.push_str(&format!(
+                "      <edge id=\"{}\" source=\"{}\" target=\"{}\" label=\"{}\"/>\n",
+                i,
+                Self::escape_xml(&edge.from),
+                Self::escape_xml(&edge.to),
+                Self::escape_xml(&edge.condition)
+            ));
+        }
+        gexf.push_str("    </edges>\n");
+        gexf.push_str("  </graph>\n");
+        gexf.push_str("</gexf>\n");
+        gexf
+    }
+
+    fn escape_quotes(value: &str) -> String {
+        value.replace('\\', "\\\\").replace('"', "\\\"")
+    }
+
+    fn escape_xml(value: &str) -> String {
+        value.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    }
+}
+
+impl Default for GraphExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::Condition;
+
+    #[test]
+    fn test_graph_truncation_drops_dangling_edges() {
+        let mut job1 = Job::new("JOB1".to_string(), "F".to_string());
+        job1.out_conditions.push(Condition::new_out("JOB1-OK".to_string()));
+        let mut job2 = Job::new("JOB2".to_string(), "F".to_string());
+        job2.in_conditions.push(Condition::new_in("JOB1-OK".to_string()));
+
+        let full = EstateGraph::from_jobs(&[&job1, &job2], 10);
+        assert_eq!(full.nodes.len(), 2);
+        assert_eq!(full.edges.len(), 1);
+        assert!(!full.truncated);
+
+        let truncated = EstateGraph::from_jobs(&[&job1, &job2], 1);
+        assert_eq!(truncated.nodes.len(), 1);
+        assert!(truncated.edges.is_empty());
+        assert!(truncated.truncated);
+    }
+
+    #[test]
+    fn test_dot_links_producers_and_clusters_by_folder() {
+        let mut extract = Job::new("EXTRACT".to_string(), "ETL".to_string());
+        extract.out_conditions.push(Condition::new_out("EXTRACT-OK".to_string()));
+        let mut load = Job::new("LOAD".to_string(), "ETL".to_string());
+        load.critical = true;
+        load.in_conditions.push(Condition::new_in("EXTRACT-OK".to_string()));
+        load.out_conditions.push(Condition::new_out("LOAD-OK".to_string()));
+        let mut report = Job::new("REPORT \"daily\"".to_string(), "BI".to_string());
+        report.in_conditions.push(Condition::new_in("LOAD-OK".to_string()));
+
+        let graph = EstateGraph::from_jobs(&[&extract, &load, &report], usize::MAX);
+        let dot = GraphExporter::new().render(&graph, GraphFormat::Dot).unwrap();
+        assert!(dot.starts_with("digraph dependencies {"));
+        assert!(dot.contains("    \"ETL/LOAD\" [label=\"LOAD\", tooltip=\"ETL\", color=red, fontcolor=red];"));
+        assert!(dot.contains("\"ETL/EXTRACT\" -> \"ETL/LOAD\" [label=\"EXTRACT-OK\"];"));
+        assert!(dot.contains("\"ETL/LOAD\" -> \"BI/REPORT \\\"daily\\\"\" [label=\"LOAD-OK\"];"));
+        assert!(!dot.contains("subgraph"));
+
+        let clustered = GraphExporter::new().with_cluster_by_folder(true).render(&graph, GraphFormat::Dot).unwrap();
+        assert!(clustered.contains("subgraph cluster_0 {\n        label=\"BI\";"));
+        assert!(clustered.contains("subgraph cluster_1 {\n        label=\"ETL\";\n        \"ETL/EXTRACT\" [label=\"EXTRACT\", tooltip=\"ETL\"];"));
+    }
+
+    #[test]
+    fn test_same_named_jobs_in_different_folders_stay_apart() {
+        let mut extract = Job::new("EXTRACT".to_string(), "SALES".to_string());
+        extract.out_conditions.push(Condition::new_out("SALES-EXTRACT-OK".to_string()));
+        let mut sales_load = Job::new("LOAD".to_string(), "SALES".to_string());
+        sales_load.in_conditions.push(Condition::new_in("SALES-EXTRACT-OK".to_string()));
+        let finance_load = Job::new("LOAD".to_string(), "FINANCE".to_string());
+
+        let graph = EstateGraph::from_jobs(&[&extract, &sales_load, &finance_load], usize::MAX);
+        let ids: Vec<&str> = graph.nodes.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, vec!["SALES/EXTRACT", "SALES/LOAD", "FINANCE/LOAD"]);
+        assert_eq!(graph.edges.len(), 1);
+        assert_eq!(graph.edges[0].to, "SALES/LOAD");
+
+        let gexf = GraphExporter::new().render(&graph, GraphFormat::Gexf).unwrap();
+        assert!(gexf.contains("<node id=\"FINANCE/LOAD\" label=\"LOAD\"/>"));
+    }
+}
@@ -1,33 +1,190 @@
-use anyhow::Result;
-use std::fs::{File, create_dir_all};
+use anyhow::{Context, Result};
+use std::fs::{self, File, create_dir_all};
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use crate::domain::value_objects::ComplexityWeights;
 use crate::presentation::dto::AnalysisOutput;
+use super::generation_report::GenerationReport;
+use super::text_encoding::OutputEncoding;
+use std::str::FromStr;
 use serde_json;
 
-pub struct HtmlGenerator;
+/// A section of the HTML report that can be included or left out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HtmlSection {
+    /// Summary statistic cards
+    Stats,
+    /// Migration difficulty distribution
+    Difficulty,
+    /// Migration waves summary
+    Waves,
+    /// Folder dependency matrix heatmap
+    FolderMatrix,
+    /// Complexity scoring methodology
+    Methodology,
+    /// Interactive job details table
+    Jobs,
+}
+
+impl HtmlSection {
+    /// Every section, in report order
+    pub const ALL: [HtmlSection; 6] = [
+        HtmlSection::Stats,
+        HtmlSection::Difficulty,
+        HtmlSection::Waves,
+        HtmlSection::FolderMatrix,
+        HtmlSection::Methodology,
+        HtmlSection::Jobs,
+    ];
+}
+
+impl FromStr for HtmlSection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "stats" => Ok(HtmlSection::Stats),
+            "difficulty" => Ok(HtmlSection::Difficulty),
+            "waves" => Ok(HtmlSection::Waves),
+            "matrix" => Ok(HtmlSection::FolderMatrix),
+            "methodology" => Ok(HtmlSection::Methodology),
+            "jobs" => Ok(HtmlSection::Jobs),
+            other => Err(format!(
+                "Unknown HTML section '{}' (expected stats, difficulty, waves, matrix, methodology or jobs)",
+                other
+            )),
+        }
+    }
+}
+
+/// Default job count above which the job table is loaded in shards
+pub const DEFAULT_SHARD_THRESHOLD: usize = 50_000;
+
+/// Default number of jobs per shard file
+pub const DEFAULT_SHARD_SIZE: usize = 10_000;
+
+/// Files read from the offline assets directory: DataTables CSS, jQuery, DataTables JS
+pub const OFFLINE_ASSET_FILES: [&str; 3] = [
+    "jquery.dataTables.min.css",
+    "jquery-3.7.0.min.js",
+    "jquery.dataTables.min.js",
+];
+
+pub struct HtmlGenerator {
+    sections: Vec<HtmlSection>,
+    /// Job count above which wave data is split into shard files
+    shard_threshold: usize,
+    /// Jobs per shard file
+    shard_size: usize,
+    /// Character encoding of the HTML file (wave JSON data stays UTF-8)
+    encoding: OutputEncoding,
+    /// Complexity weights shown in the methodology section
+    weights: ComplexityWeights,
+    /// Directory of jQuery/DataTables files inlined instead of loaded from CDNs
+    offline_assets: Option<PathBuf>,
+}
 
 impl HtmlGenerator {
     pub fn new() -> Self {
-        Self
+        Self {
+            sections: HtmlSection::ALL.to_vec(),
+            shard_threshold: DEFAULT_SHARD_THRESHOLD,
+            shard_size: DEFAULT_SHARD_SIZE,
+            encoding: OutputEncoding::default(),
+            weights: ComplexityWeights::default(),
+            offline_assets: None,
+        }
+    }
+
+    /// Restricts the report to the given sections
+    pub fn with_sections(mut self, sections: Vec<HtmlSection>) -> Self {
+        self.sections = sections;
+        self
+    }
+
+    /// Sets the job count above which the job table loads one shard at a time
+    /// and points to the `serve` web UI for estate-wide search
+    pub fn with_shard_threshold(mut self, shard_threshold: usize) -> Self {
+        self.shard_threshold = shard_threshold;
+        self
+    }
+
+    /// Sets the number of jobs per shard file (at least 1)
+    pub fn with_shard_size(mut self, shard_size: usize) -> Self {
+        self.shard_size = shard_size.max(1);
+        self
     }
 
-    pub fn generate<P: AsRef<Path>>(&self, output: &AnalysisOutput, path: P) -> Result<()> {
+    /// Sets the character encoding of the HTML file (UTF-8 by default)
+    pub fn with_encoding(mut self, encoding: OutputEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Sets the complexity weights described in the methodology section
+    pub fn with_weights(mut self, weights: ComplexityWeights) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    /// Inlines jQuery and DataTables from a local directory instead of CDN links
+    ///
+    /// The directory must hold the files named in [`OFFLINE_ASSET_FILES`], so
+    /// the report opens on machines without internet access.
+    pub fn with_offline_assets(mut self, dir: Option<PathBuf>) -> Self {
+        self.offline_assets = dir;
+        self
+    }
+
+    fn includes(&self, section: HtmlSection) -> bool {
+        self.sections.contains(&section)
+    }
+
+    /// Checks whether the report is too large for one client-side table per wave
+    fn is_sharded(&self, output: &AnalysisOutput) -> bool {
+        output.jobs.len() > self.shard_threshold
+    }
+
+    /// Gets the wave numbers to write data files for, up to the last wave
+    ///
+    /// The last wave is taken from the migration waves, or from the jobs when
+    /// they carry a later wave, so no job is left out of the table.
+    fn wave_numbers(output: &AnalysisOutput) -> std::ops::RangeInclusive<usize> {
+        let last_wave = output.migration_waves.iter().map(|w| w.wave)
+            .chain(output.jobs.iter().map(|j| j.migration_wave))
+            .max()
+            .unwrap_or(0);
+        1..=last_wave
+    }
+
+    /// Gets the number of shard files written for each wave that has jobs
+    fn shard_counts(&self, output: &AnalysisOutput) -> Vec<(usize, usize)> {
+        Self::wave_numbers(output)
+            .map(|wave| (wave, output.jobs.iter().filter(|j| j.migration_wave == wave).count()))
+            .filter(|(_, jobs)| *jobs > 0)
+            .map(|(wave, jobs)| (wave, jobs.div_ceil(self.shard_size)))
+            .collect()
+    }
+
+    pub fn generate<P: AsRef<Path>>(&self, output: &AnalysisOutput, path: P) -> Result<GenerationReport> {
         let path_ref = path.as_ref();
         
-        // Create data directory next to HTML file
-        let parent_dir = path_ref.parent().unwrap_or(Path::new("."));
-        let data_dir = parent_dir.join("data");
-        create_dir_all(&data_dir)?;
-        
-        // Generate wave JSON files
-        self.generate_wave_json_files(output, &data_dir)?;
+        // Wave JSON files feed the job details table
+        if self.includes(HtmlSection::Jobs) {
+            // Create data directory next to HTML file
+            let parent_dir = path_ref.parent().unwrap_or(Path::new("."));
+            let data_dir = parent_dir.join("data");
+            create_dir_all(&data_dir)?;
+            
+            // Generate wave JSON files
+            self.generate_wave_json_files(output, &data_dir)?;
+        }
         
         // Generate HTML
-        let html = self.generate_string(output)?;
-        let mut file = File::create(path)?;
-        file.write_all(html.as_bytes())?;
-        Ok(())
+        let (html, report) = self.generate_string_with_report(output)?;
+        let mut file = File::create(path_ref)?;
+        file.write_all(&self.encoding.encode_html(&html, &path_ref.display().to_string()))?;
+        Ok(report)
     }
     
     fn generate_wave_json_files(&self, output: &AnalysisOutput, data_dir: &Path) -> Result<()> {
@@ -40,11 +197,12 @@ impl HtmlGenerator {
             migration_wave: usize,
             complexity_score: u32,
             migration_difficulty: String,
+            migration_readiness: String,
             dependency_count: usize,
         }
         
         // Group jobs by wave
-        for wave_num in 1..=5 {
+        for wave_num in Self::wave_numbers(output) {
             let wave_jobs: Vec<WaveJobData> = output.jobs.iter()
                 .filter(|j| j.migration_wave == wave_num)
                 .map(|j| WaveJobData {
@@ -53,11 +211,22 @@ impl HtmlGenerator {
                     migration_wave: j.migration_wave,
                     complexity_score: j.complexity_score,
                     migration_difficulty: j.migration_difficulty.clone(),
+                    migration_readiness: j.migration_readiness.clone(),
                     dependency_count: j.dependency_count,
                 })
                 .collect();
             
-            if !wave_jobs.is_empty() {
+            if wave_jobs.is_empty() {
+                continue;
+            }
+
+            if self.is_sharded(output) {
+                for (part, shard) in wave_jobs.chunks(self.shard_size).enumerate() {
+                    let json_path = data_dir.join(format!("wave_{}_part_{}.json", wave_num, part + 1));
+                    let mut file = File::create(json_path)?;
+                    file.write_all(serde_json::to_string(shard)?.as_bytes())?;
+                }
+            } else {
                 let json_path = data_dir.join(format!("wave_{}.json", wave_num));
                 let json_content = serde_json::to_string(&wave_jobs)?;
                 let mut file = File::create(json_path)?;
@@ -69,15 +238,43 @@ impl HtmlGenerator {
     }
 
     pub fn generate_string(&self, output: &AnalysisOutput) -> Result<String> {
+        Ok(self.generate_string_with_report(output)?.0)
+    }
+
+    /// Reads the offline asset files, in [`OFFLINE_ASSET_FILES`] order
+    fn read_offline_assets(dir: &Path) -> Result<[String; 3]> {
+        let read = |name: &str| fs::read_to_string(dir.join(name))
+            .with_context(|| format!("Failed to read offline asset {:?}", dir.join(name)));
+        Ok([
+            read(OFFLINE_ASSET_FILES[0])?,
+            read(OFFLINE_ASSET_FILES[1])?,
+            read(OFFLINE_ASSET_FILES[2])?,
+        ])
+    }
+
+    /// Renders the HTML report and collects the non-fatal issues met on the way
+    pub fn generate_string_with_report(&self, output: &AnalysisOutput) -> Result<(String, GenerationReport)> {
+        let mut report = GenerationReport::for_output(output);
         let mut html = String::new();
 
         html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n");
-        html.push_str("    <meta charset=\"UTF-8\">\n");
+        html.push_str(&format!("    <meta charset=\"{}\">\n", self.encoding.name()));
         html.push_str("    <meta name=\"viewport\" content=\"width=device-width, initial-scale=1.0\">\n");
         html.push_str("    <title>Control-M Migration Analysis</title>\n");
-        html.push_str("    <link rel=\"stylesheet\" href=\"https://cdn.datatables.net/1.13.7/css/jquery.dataTables.min.css\">\n");
-        html.push_str("    <script src=\"https://code.jquery.com/jquery-3.7.0.min.js\"></script>\n");
-        html.push_str("    <script src=\"https://cdn.datatables.net/1.13.7/js/jquery.dataTables.min.js\"></script>\n");
+        match &self.offline_assets {
+            Some(dir) => {
+                let [css, jquery, datatables] = Self::read_offline_assets(dir)?;
+                html.push_str(&format!("    <style>\n{}\n    </style>\n", css.replace("</style", "<\\/style")));
+                for script in [jquery, datatables] {
+                    html.push_str(&format!("    <script>\n{}\n    </script>\n", script.replace("</script", "<\\/script")));
+                }
+            }
+            None => {
+                html.push_str("    <link rel=\"stylesheet\" href=\"https://cdn.datatables.net/1.13.7/css/jquery.dataTables.min.css\">\n");
+                html.push_str("    <script src=\"https://code.jquery.com/jquery-3.7.0.min.js\"></script>\n");
+                html.push_str("    <script src=\"https://cdn.datatables.net/1.13.7/js/jquery.dataTables.min.js\"></script>\n");
+            }
+        }
         html.push_str("    <style>\n");
         html.push_str(Self::get_css());
         html.push_str("    </style>\n");
@@ -86,285 +283,396 @@ impl HtmlGenerator {
         html.push_str("    <div class=\"container\">\n");
         html.push_str("        <h1>📊 Control-M to Airflow Migration Analysis</h1>\n");
 
-        // Statistics Cards
         let easy_count = output.jobs.iter().filter(|j| j.migration_difficulty == "Easy").count();
         let medium_count = output.jobs.iter().filter(|j| j.migration_difficulty == "Medium").count();
         let hard_count = output.jobs.iter().filter(|j| j.migration_difficulty == "Hard").count();
         let critical_count = output.jobs.iter().filter(|j| j.is_critical).count();
         
-        html.push_str("        <div class=\"stats-grid\">\n");
-        html.push_str(&format!("            <div class=\"stat-card\">\n"));
-        html.push_str(&format!("                <div class=\"stat-value\">{}</div>\n", output.summary.total_jobs));
-        html.push_str("                <div class=\"stat-label\">Total Jobs</div>\n");
-        html.push_str("            </div>\n");
-        html.push_str(&format!("            <div class=\"stat-card\">\n"));
-        html.push_str(&format!("                <div class=\"stat-value\">{}</div>\n", output.summary.total_folders));
-        html.push_str("                <div class=\"stat-label\">Folders</div>\n");
-        html.push_str("            </div>\n");
-        html.push_str(&format!("            <div class=\"stat-card\">\n"));
-        html.push_str(&format!("                <div class=\"stat-value\">{:.1}</div>\n", output.summary.average_complexity_score));
-        html.push_str("                <div class=\"stat-label\">Avg Complexity</div>\n");
-        html.push_str("            </div>\n");
-        html.push_str(&format!("            <div class=\"stat-card\">\n"));
-        html.push_str(&format!("                <div class=\"stat-value\">{}</div>\n", output.migration_waves.len()));
-        html.push_str("                <div class=\"stat-label\">Migration Waves</div>\n");
-        html.push_str("            </div>\n");
-        html.push_str("        </div>\n");
+        // Statistics Cards
+        if self.includes(HtmlSection::Stats) {
+            html.push_str("        <div class=\"stats-grid\">\n");
+            html.push_str(&format!("            <div class=\"stat-card\">\n"));
+            html.push_str(&format!("                <div class=\"stat-value\">{}</div>\n", output.summary.total_jobs));
+            html.push_str("                <div class=\"stat-label\">Total Jobs</div>\n");
+            html.push_str("            </div>\n");
+            html.push_str(&format!("            <div class=\"stat-card\">\n"));
+            html.push_str(&format!("                <div class=\"stat-value\">{}</div>\n", output.summary.total_folders));
+            html.push_str("                <div class=\"stat-label\">Folders</div>\n");
+            html.push_str("            </div>\n");
+            html.push_str(&format!("            <div class=\"stat-card\">\n"));
+            html.push_str(&format!("                <div class=\"stat-value\">{:.1}</div>\n", output.summary.average_complexity_score));
+            html.push_str("                <div class=\"stat-label\">Avg Complexity</div>\n");
+            html.push_str("            </div>\n");
+            html.push_str(&format!("            <div class=\"stat-card\">\n"));
+            html.push_str(&format!("                <div class=\"stat-value\">{}</div>\n", output.migration_waves.len()));
+            html.push_str("                <div class=\"stat-label\">Migration Waves</div>\n");
+            html.push_str("            </div>\n");
+            html.push_str("        </div>\n");
+        }
 
-        html.push_str("        <div class=\"summary\">\n");
-        html.push_str("            <h2>📈 Migration Difficulty Distribution</h2>\n");
-        html.push_str("            <div class=\"difficulty-grid\">\n");
-        html.push_str(&format!("                <div class=\"difficulty-card easy-bg\">\n"));
-        html.push_str(&format!("                    <div class=\"difficulty-count\">{}</div>\n", easy_count));
-        html.push_str(&format!("                    <div class=\"difficulty-label\">Easy ({:.1}%)</div>\n", 
-            (easy_count as f64 / output.summary.total_jobs as f64) * 100.0));
-        html.push_str("                </div>\n");
-        html.push_str(&format!("                <div class=\"difficulty-card medium-bg\">\n"));
-        html.push_str(&format!("                    <div class=\"difficulty-count\">{}</div>\n", medium_count));
-        html.push_str(&format!("                    <div class=\"difficulty-label\">Medium ({:.1}%)</div>\n", 
-            (medium_count as f64 / output.summary.total_jobs as f64) * 100.0));
-        html.push_str("                </div>\n");
-        html.push_str(&format!("                <div class=\"difficulty-card hard-bg\">\n"));
-        html.push_str(&format!("                    <div class=\"difficulty-count\">{}</div>\n", hard_count));
-        html.push_str(&format!("                    <div class=\"difficulty-label\">Hard ({:.1}%)</div>\n", 
-            (hard_count as f64 / output.summary.total_jobs as f64) * 100.0));
-        html.push_str("                </div>\n");
-        html.push_str(&format!("                <div class=\"difficulty-card critical-bg\">\n"));
-        html.push_str(&format!("                    <div class=\"difficulty-count\">{}</div>\n", critical_count));
-        html.push_str("                    <div class=\"difficulty-label\">Critical</div>\n");
-        html.push_str("                </div>\n");
-        html.push_str("            </div>\n");
-        html.push_str("        </div>\n");
+        // Difficulty Distribution
+        if self.includes(HtmlSection::Difficulty) {
+            html.push_str("        <div class=\"summary\">\n");
+            html.push_str("            <h2>📈 Migration Difficulty Distribution</h2>\n");
+            html.push_str("            <div class=\"difficulty-grid\">\n");
+            html.push_str(&format!("                <div class=\"difficulty-card easy-bg\">\n"));
+            html.push_str(&format!("                    <div class=\"difficulty-count\">{}</div>\n", easy_count));
+            html.push_str(&format!("                    <div class=\"difficulty-label\">Easy ({:.1}%)</div>\n", 
+                report.percentage(easy_count, output.summary.total_jobs, "Easy job percentage")));
+            html.push_str("                </div>\n");
+            html.push_str(&format!("                <div class=\"difficulty-card medium-bg\">\n"));
+            html.push_str(&format!("                    <div class=\"difficulty-count\">{}</div>\n", medium_count));
+            html.push_str(&format!("                    <div class=\"difficulty-label\">Medium ({:.1}%)</div>\n", 
+                report.percentage(medium_count, output.summary.total_jobs, "Medium job percentage")));
+            html.push_str("                </div>\n");
+            html.push_str(&format!("                <div class=\"difficulty-card hard-bg\">\n"));
+            html.push_str(&format!("                    <div class=\"difficulty-count\">{}</div>\n", hard_count));
+            html.push_str(&format!("                    <div class=\"difficulty-label\">Hard ({:.1}%)</div>\n", 
+                report.percentage(hard_count, output.summary.total_jobs, "Hard job percentage")));
+            html.push_str("                </div>\n");
+            html.push_str(&format!("                <div class=\"difficulty-card critical-bg\">\n"));
+            html.push_str(&format!("                    <div class=\"difficulty-count\">{}</div>\n", critical_count));
+            html.push_str("                    <div class=\"difficulty-label\">Critical</div>\n");
+            html.push_str("                </div>\n");
+            html.push_str("            </div>\n");
+            html.push_str("        </div>\n");
+        }
 
-        html.push_str("        <div class=\"waves\">\n");
-        html.push_str("            <h2>🌊 Migration Waves Summary</h2>\n");
-        html.push_str("            <div class=\"wave-grid\">\n");
-        for wave in &output.migration_waves {
-            let wave_jobs: Vec<_> = output.jobs.iter()
-                .filter(|j| j.migration_wave == wave.wave)
-                .collect();
-            let avg_complexity: f64 = if !wave_jobs.is_empty() {
-                wave_jobs.iter()
-                    .map(|j| j.complexity_score as f64)
-                    .sum::<f64>() / wave_jobs.len() as f64
-            } else {
-                0.0
-            };
+        // Migration Waves Summary
+        if self.includes(HtmlSection::Waves) {
+            html.push_str("        <div class=\"waves\">\n");
+            html.push_str("            <h2>🌊 Migration Waves Summary</h2>\n");
+            html.push_str("            <div class=\"wave-grid\">\n");
+            for wave in &output.migration_waves {
+                let wave_jobs: Vec<_> = output.jobs.iter()
+                    .filter(|j| j.migration_wave == wave.wave)
+                    .collect();
+                let avg_complexity: f64 = if !wave_jobs.is_empty() {
+                    wave_jobs.iter()
+                        .map(|j| j.complexity_score as f64)
+                        .sum::<f64>() / wave_jobs.len() as f64
+                } else {
+                    0.0
+                };
             
-            html.push_str(&format!("            <div class=\"wave-card\">\n"));
-            html.push_str(&format!("                <div class=\"wave-header\">Wave {}</div>\n", wave.wave_number));
-            html.push_str(&format!("                <div class=\"wave-count\">{} jobs</div>\n", wave.jobs.len()));
-            html.push_str(&format!("                <div class=\"wave-complexity\">Avg: {:.1}</div>\n", avg_complexity));
-            html.push_str(&format!("                <div class=\"wave-reason\">{}</div>\n", wave.reason));
+                html.push_str(&format!("            <div class=\"wave-card\">\n"));
+                html.push_str(&format!("                <div class=\"wave-header\">Wave {}</div>\n", wave.wave_number));
+                html.push_str(&format!("                <div class=\"wave-count\">{} jobs</div>\n", wave.jobs.len()));
+                html.push_str(&format!("                <div class=\"wave-complexity\">Avg: {:.1}</div>\n", avg_complexity));
+                html.push_str(&format!("                <div class=\"wave-reason\">{}</div>\n", wave.reason));
+                html.push_str("            </div>\n");
+            }
             html.push_str("            </div>\n");
+            html.push_str("        </div>\n");
+        }
+
+        // Folder Dependency Matrix
+        if self.includes(HtmlSection::FolderMatrix) && !output.folder_matrix.folders.is_empty() {
+            self.push_folder_matrix(&mut html, output);
         }
-        html.push_str("            </div>\n");
-        html.push_str("        </div>\n");
 
         // Complexity Scoring Methodology
-        html.push_str("        <div class=\"methodology\">\n");
-        html.push_str("            <h2>📐 Complexity Scoring Methodology</h2>\n");
-        html.push_str("            <p class=\"methodology-intro\">The complexity score is calculated based on multiple factors that affect migration difficulty:<br>\n");
-        html.push_str("            <span class=\"thai-text\">คะแนนความซับซ้อนคำนวณจากปัจจัยต่างๆ ของ Control-M ที่ส่งผลต่อความยากในการย้ายไป Airflow</span></p>\n");
-        html.push_str("            <div class=\"scoring-grid\">\n");
-        html.push_str("                <div class=\"scoring-item\">\n");
-        html.push_str("                    <div class=\"scoring-icon\">🔗</div>\n");
-        html.push_str("                    <div class=\"scoring-title\">Dependencies</div>\n");
-        html.push_str("                    <div class=\"scoring-value\">3 points each</div>\n");
-        html.push_str("                    <div class=\"scoring-desc\">In-conditions and control resources<br>\n");
-        html.push_str("                    <span class=\"thai-text\">In-Condition และ Control Resource ที่ Job ต้องรอ</span></div>\n");
-        html.push_str("                </div>\n");
-        html.push_str("                <div class=\"scoring-item\">\n");
-        html.push_str("                    <div class=\"scoring-icon\">📊</div>\n");
-        html.push_str("                    <div class=\"scoring-title\">Dependency Depth</div>\n");
-        html.push_str("                    <div class=\"scoring-value\">5 points per level</div>\n");
-        html.push_str("                    <div class=\"scoring-desc\">Depth of dependency chain<br>\n");
-        html.push_str("                    <span class=\"thai-text\">ความลึกของ Job ที่ต้องรอกัน (A รอ B, B รอ C)</span></div>\n");
-        html.push_str("                </div>\n");
-        html.push_str("                <div class=\"scoring-item\">\n");
-        html.push_str("                    <div class=\"scoring-icon\">🎯</div>\n");
-        html.push_str("                    <div class=\"scoring-title\">Conditions</div>\n");
-        html.push_str("                    <div class=\"scoring-value\">2 points each</div>\n");
-        html.push_str("                    <div class=\"scoring-desc\">In/out conditions<br>\n");
-        html.push_str("                    <span class=\"thai-text\">In-Condition และ Out-Condition ทั้งหมด</span></div>\n");
-        html.push_str("                </div>\n");
-        html.push_str("                <div class=\"scoring-item\">\n");
-        html.push_str("                    <div class=\"scoring-icon\">📝</div>\n");
-        html.push_str("                    <div class=\"scoring-title\">Variables</div>\n");
-        html.push_str("                    <div class=\"scoring-value\">1 point each</div>\n");
-        html.push_str("                    <div class=\"scoring-desc\">Job variables and auto-edits<br>\n");
-        html.push_str("                    <span class=\"thai-text\">Variable และ Auto-Edit ที่ใช้ใน Job</span></div>\n");
-        html.push_str("                </div>\n");
-        html.push_str("                <div class=\"scoring-item\">\n");
-        html.push_str("                    <div class=\"scoring-icon\">⚙️</div>\n");
-        html.push_str("                    <div class=\"scoring-title\">ON Conditions</div>\n");
-        html.push_str("                    <div class=\"scoring-value\">4+ points each</div>\n");
-        html.push_str("                    <div class=\"scoring-desc\">Conditional logic complexity<br>\n");
-        html.push_str("                    <span class=\"thai-text\">ON Statement (OK/NOTOK/RERUN) ที่มีใน Job</span></div>\n");
-        html.push_str("                </div>\n");
-        html.push_str("                <div class=\"scoring-item\">\n");
-        html.push_str("                    <div class=\"scoring-icon\">🔄</div>\n");
-        html.push_str("                    <div class=\"scoring-title\">Cyclic Jobs</div>\n");
-        html.push_str("                    <div class=\"scoring-value\">15 points</div>\n");
-        html.push_str("                    <div class=\"scoring-desc\">Jobs with cyclic execution<br>\n");
-        html.push_str("                    <span class=\"thai-text\">Job ที่ Cyclic = Y (รันซ้ำตามเงื่อนไข)</span></div>\n");
-        html.push_str("                </div>\n");
-        html.push_str("                <div class=\"scoring-item\">\n");
-        html.push_str("                    <div class=\"scoring-icon\">💾</div>\n");
-        html.push_str("                    <div class=\"scoring-title\">Resources</div>\n");
-        html.push_str("                    <div class=\"scoring-value\">3 points each</div>\n");
-        html.push_str("                    <div class=\"scoring-desc\">Quantitative and control resources<br>\n");
-        html.push_str("                    <span class=\"thai-text\">Quantitative Resource และ Control Resource</span></div>\n");
-        html.push_str("                </div>\n");
-        html.push_str("                <div class=\"scoring-item\">\n");
-        html.push_str("                    <div class=\"scoring-icon\">📅</div>\n");
-        html.push_str("                    <div class=\"scoring-title\">Scheduling</div>\n");
-        html.push_str("                    <div class=\"scoring-value\">2 points per feature</div>\n");
-        html.push_str("                    <div class=\"scoring-desc\">Calendars, time windows, etc.<br>\n");
-        html.push_str("                    <span class=\"thai-text\">Calendar, Time Window, Schedule ต่างๆ</span></div>\n");
-        html.push_str("                </div>\n");
-        html.push_str("            </div>\n");
-        html.push_str("            <div class=\"difficulty-legend\">\n");
-        html.push_str("                <h3>Migration Difficulty Levels:</h3>\n");
-        html.push_str("                <div class=\"legend-items\">\n");
-        html.push_str("                    <div class=\"legend-item\">\n");
-        html.push_str("                        <span class=\"legend-badge easy\">Easy</span>\n");
-        html.push_str("                        <span class=\"legend-text\">0-30 points: Simple jobs with minimal dependencies<br>\n");
-        html.push_str("                        <span class=\"thai-text\">Job ง่าย ไม่มี/มีน้อย Dependency - ย้ายได้เร็ว</span></span>\n");
-        html.push_str("                    </div>\n");
-        html.push_str("                    <div class=\"legend-item\">\n");
-        html.push_str("                        <span class=\"legend-badge medium\">Medium</span>\n");
-        html.push_str("                        <span class=\"legend-text\">31-60 points: Moderate complexity with some dependencies<br>\n");
-        html.push_str("                        <span class=\"thai-text\">Job ปานกลาง มี Condition/Resource บ้าง - ต้องวางแผน</span></span>\n");
-        html.push_str("                    </div>\n");
-        html.push_str("                    <div class=\"legend-item\">\n");
-        html.push_str("                        <span class=\"legend-badge hard\">Hard</span>\n");
-        html.push_str("                        <span class=\"legend-text\">61+ points: Complex jobs requiring careful planning<br>\n");
-        html.push_str("                        <span class=\"thai-text\">Job ซับซ้อน มี Dependency/ON Condition เยอะ - ต้องระวัง</span></span>\n");
-        html.push_str("                    </div>\n");
-        html.push_str("                </div>\n");
-        html.push_str("            </div>\n");
-        html.push_str("        </div>\n");
-        
-        html.push_str("        <div class=\"jobs\">\n");
-        html.push_str("            <h2>📋 Job Details</h2>\n");
-        html.push_str("            <div class=\"filter-container\">\n");
-        html.push_str("                <label for=\"waveFilter\">Filter by Wave: </label>\n");
-        html.push_str("                <select id=\"waveFilter\">\n");
-        html.push_str("                    <option value=\"\">All Waves</option>\n");
-        for wave in &output.migration_waves {
-            let selected = if wave.wave == 1 { " selected" } else { "" };
-            html.push_str(&format!("                    <option value=\"{}\"{}>Wave {} ({} jobs)</option>\n", wave.wave, selected, wave.wave, wave.jobs.len()));
-        }
-        html.push_str("                </select>\n");
-        html.push_str("            </div>\n");
-        html.push_str("            <p class=\"table-info\">Interactive table with search, sort, and pagination. Use the dropdown above to filter by wave.</p>\n");
-        html.push_str("            <div id=\"loadingMessage\" style=\"text-align: center; padding: 20px; color: #7f8c8d;\">\n");
-        html.push_str("                <p>⏳ Loading Wave 1 jobs...</p>\n");
-        html.push_str("            </div>\n");
-        html.push_str("            <table id=\"jobsTable\" class=\"display\" style=\"display: none;\">\n");
-        html.push_str("                <thead>\n");
-        html.push_str("                    <tr>\n");
-        html.push_str("                        <th>Job Name</th>\n");
-        html.push_str("                        <th>Folder</th>\n");
-        html.push_str("                        <th>Wave</th>\n");
-        html.push_str("                        <th>Complexity</th>\n");
-        html.push_str("                        <th>Difficulty</th>\n");
-        html.push_str("                        <th>Dependencies</th>\n");
-        html.push_str("                    </tr>\n");
-        html.push_str("                </thead>\n");
-        html.push_str("                <tbody id=\"jobsTableBody\">\n");
-        html.push_str("                </tbody>\n");
-        html.push_str("            </table>\n");
-        html.push_str("        </div>\n");
+        if self.includes(HtmlSection::Methodology) {
+            html.push_str("        <div class=\"methodology\">\n");
+            html.push_str("            <h2>📐 Complexity Scoring Methodology</h2>\n");
+            html.push_str("            <p class=\"methodology-intro\">The complexity score is calculated based on multiple factors that affect migration difficulty:<br>\n");
+            html.push_str("            <span class=\"thai-text\">คะแนนความซับซ้อนคำนวณจากปัจจัยต่างๆ ของ Control-M ที่ส่งผลต่อความยากในการย้ายไป Airflow</span></p>\n");
+            html.push_str("            <div class=\"scoring-grid\">\n");
+            html.push_str("                <div class=\"scoring-item\">\n");
+            html.push_str("                    <div class=\"scoring-icon\">🔗</div>\n");
+            html.push_str("                    <div class=\"scoring-title\">Dependencies</div>\n");
+            html.push_str(&format!("                    <div class=\"scoring-value\">{}</div>\n", points(self.weights.dependency, "each")));
+            html.push_str("                    <div class=\"scoring-desc\">In-conditions and control resources<br>\n");
+            html.push_str("                    <span class=\"thai-text\">In-Condition และ Control Resource ที่ Job ต้องรอ</span></div>\n");
+            html.push_str("                </div>\n");
+            html.push_str("                <div class=\"scoring-item\">\n");
+            html.push_str("                    <div class=\"scoring-icon\">📊</div>\n");
+            html.push_str("                    <div class=\"scoring-title\">Dependency Depth</div>\n");
+            html.push_str(&format!("                    <div class=\"scoring-value\">{}</div>\n", points(self.weights.dependency_depth, "per level")));
+            html.push_str("                    <div class=\"scoring-desc\">Depth of dependency chain<br>\n");
+            html.push_str("                    <span class=\"thai-text\">ความลึกของ Job ที่ต้องรอกัน (A รอ B, B รอ C)</span></div>\n");
+            html.push_str("                </div>\n");
+            html.push_str("                <div class=\"scoring-item\">\n");
+            html.push_str("                    <div class=\"scoring-icon\">🎯</div>\n");
+            html.push_str("                    <div class=\"scoring-title\">Conditions</div>\n");
+            html.push_str(&format!("                    <div class=\"scoring-value\">{}</div>\n", points(self.weights.condition, "each")));
+            html.push_str("                    <div class=\"scoring-desc\">In/out conditions<br>\n");
+            html.push_str("                    <span class=\"thai-text\">In-Condition และ Out-Condition ทั้งหมด</span></div>\n");
+            html.push_str("                </div>\n");
+            html.push_str("                <div class=\"scoring-item\">\n");
+            html.push_str("                    <div class=\"scoring-icon\">📝</div>\n");
+            html.push_str("                    <div class=\"scoring-title\">Variables</div>\n");
+            html.push_str(&format!("                    <div class=\"scoring-value\">{}</div>\n", points(self.weights.variable, "each")));
+            html.push_str("                    <div class=\"scoring-desc\">Job variables and auto-edits<br>\n");
+            html.push_str("                    <span class=\"thai-text\">Variable และ Auto-Edit ที่ใช้ใน Job</span></div>\n");
+            html.push_str("                </div>\n");
+            html.push_str("                <div class=\"scoring-item\">\n");
+            html.push_str("                    <div class=\"scoring-icon\">⚙️</div>\n");
+            html.push_str("                    <div class=\"scoring-title\">ON Conditions</div>\n");
+            html.push_str(&format!("                    <div class=\"scoring-value\">{}</div>\n", points(self.weights.on_condition, "each")));
+            html.push_str(&format!("                    <div class=\"scoring-desc\">Conditional logic complexity, plus {}<br>\n", points(self.weights.on_condition_action, "per action")));
+            html.push_str("                    <span class=\"thai-text\">ON Statement (OK/NOTOK/RERUN) ที่มีใน Job</span></div>\n");
+            html.push_str("                </div>\n");
+            html.push_str("                <div class=\"scoring-item\">\n");
+            html.push_str("                    <div class=\"scoring-icon\">🔄</div>\n");
+            html.push_str("                    <div class=\"scoring-title\">Cyclic Jobs</div>\n");
+            html.push_str(&format!("                    <div class=\"scoring-value\">{}</div>\n", points(self.weights.cyclic, "")));
+            html.push_str("                    <div class=\"scoring-desc\">Jobs with cyclic execution<br>\n");
+            html.push_str("                    <span class=\"thai-text\">Job ที่ Cyclic = Y (รันซ้ำตามเงื่อนไข)</span></div>\n");
+            html.push_str("                </div>\n");
+            html.push_str("                <div class=\"scoring-item\">\n");
+            html.push_str("                    <div class=\"scoring-icon\">💾</div>\n");
+            html.push_str("                    <div class=\"scoring-title\">Resources</div>\n");
+            html.push_str(&format!("                    <div class=\"scoring-value\">{}</div>\n", points(self.weights.resource, "each")));
+            html.push_str("                    <div class=\"scoring-desc\">Quantitative and control resources<br>\n");
+            html.push_str("                    <span class=\"thai-text\">Quantitative Resource และ Control Resource</span></div>\n");
+            html.push_str("                </div>\n");
+            html.push_str("                <div class=\"scoring-item\">\n");
+            html.push_str("                    <div class=\"scoring-icon\">📅</div>\n");
+            html.push_str("                    <div class=\"scoring-title\">Scheduling</div>\n");
+            html.push_str(&format!("                    <div class=\"scoring-value\">{}</div>\n", points(self.weights.scheduling, "per feature")));
+            html.push_str("                    <div class=\"scoring-desc\">Calendars, time windows, etc.<br>\n");
+            html.push_str("                    <span class=\"thai-text\">Calendar, Time Window, Schedule ต่างๆ</span></div>\n");
+            html.push_str("                </div>\n");
+            html.push_str("                <div class=\"scoring-item\">\n");
+            html.push_str("                    <div class=\"scoring-icon\">🖥️</div>\n");
+            html.push_str("                    <div class=\"scoring-title\">Execution Environment</div>\n");
+            html.push_str(&format!("                    <div class=\"scoring-value\">up to {}</div>\n", points(self.weights.multi_agent.saturating_add(self.weights.scheduling_environment), "")));
+            html.push_str(&format!("                    <div class=\"scoring-desc\">Multi-agent ({}) and pinned scheduling environment ({})<br>\n", self.weights.multi_agent, self.weights.scheduling_environment));
+            html.push_str("                    <span class=\"thai-text\">Job ที่ MULTY_AGENT = Y หรือกำหนด SCHEDULING_ENVIRONMENT</span></div>\n");
+            html.push_str("                </div>\n");
+            html.push_str("                <div class=\"scoring-item\">\n");
+            html.push_str("                    <div class=\"scoring-icon\">🔁</div>\n");
+            html.push_str("                    <div class=\"scoring-title\">Auto Rerun</div>\n");
+            html.push_str(&format!("                    <div class=\"scoring-value\">{}</div>\n", points(self.weights.rerun, "")));
+            html.push_str("                    <div class=\"scoring-desc\">MAXRERUN above zero or an ON condition with a RERUN action<br>\n");
+            html.push_str("                    <span class=\"thai-text\">Job ที่กำหนด MAXRERUN หรือมี ON Condition สั่ง RERUN (ต้องตั้ง retries ใน Airflow)</span></div>\n");
+            html.push_str("                </div>\n");
+            html.push_str("            </div>\n");
+            html.push_str("            <div class=\"difficulty-legend\">\n");
+            html.push_str("                <h3>Migration Difficulty Levels:</h3>\n");
+            html.push_str("                <div class=\"legend-items\">\n");
+            html.push_str("                    <div class=\"legend-item\">\n");
+            html.push_str("                        <span class=\"legend-badge easy\">Easy</span>\n");
+            html.push_str("                        <span class=\"legend-text\">0-30 points: Simple jobs with minimal dependencies<br>\n");
+            html.push_str("                        <span class=\"thai-text\">Job ง่าย ไม่มี/มีน้อย Dependency - ย้ายได้เร็ว</span></span>\n");
+            html.push_str("                    </div>\n");
+            html.push_str("                    <div class=\"legend-item\">\n");
+            html.push_str("                        <span class=\"legend-badge medium\">Medium</span>\n");
+            html.push_str("                        <span class=\"legend-text\">31-60 points: Moderate complexity with some dependencies<br>\n");
+            html.push_str("                        <span class=\"thai-text\">Job ปานกลาง มี Condition/Resource บ้าง - ต้องวางแผน</span></span>\n");
+            html.push_str("                    </div>\n");
+            html.push_str("                    <div class=\"legend-item\">\n");
+            html.push_str("                        <span class=\"legend-badge hard\">Hard</span>\n");
+            html.push_str("                        <span class=\"legend-text\">61+ points: Complex jobs requiring careful planning<br>\n");
+            html.push_str("                        <span class=\"thai-text\">Job ซับซ้อน มี Dependency/ON Condition เยอะ - ต้องระวัง</span></span>\n");
+            html.push_str("                    </div>\n");
+            html.push_str("                </div>\n");
+            html.push_str("            </div>\n");
+            html.push_str("        </div>\n");
+        }
+
+        // Job Details Table
+        if self.includes(HtmlSection::Jobs) {
+            html.push_str("        <div class=\"jobs\">\n");
+            html.push_str("            <h2>📋 Job Details</h2>\n");
+            html.push_str("            <div class=\"filter-container\">\n");
+            html.push_str("                <label for=\"waveFilter\">Filter by Wave: </label>\n");
+            html.push_str("                <select id=\"waveFilter\">\n");
+            html.push_str("                    <option value=\"\">All Waves</option>\n");
+            for wave in &output.migration_waves {
+                let selected = if wave.wave == 1 { " selected" } else { "" };
+                html.push_str(&format!("                    <option value=\"{}\"{}>Wave {} ({} jobs)</option>\n", wave.wave, selected, wave.wave, wave.jobs.len()));
+            }
+            html.push_str("                </select>\n");
+            if self.is_sharded(output) {
+                html.push_str("                <label for=\"shardFilter\">Part: </label>\n");
+                html.push_str("                <select id=\"shardFilter\"></select>\n");
+            }
+            html.push_str("            </div>\n");
+            if self.is_sharded(output) {
+                html.push_str(&format!(
+                    "            <p class=\"table-info\">⚠️ {} jobs are too many for one table, so each wave is loaded in parts of up to {} jobs and search only covers the part shown. For search across all jobs, export with <code>jobweaver export-sqlite</code> and browse with <code>jobweaver serve</code>.</p>\n",
+                    output.jobs.len(), self.shard_size
+                ));
+            }
+            html.push_str("            <p class=\"table-info\">Interactive table with search, sort, and pagination. Use the dropdown above to filter by wave.</p>\n");
+            html.push_str("            <div id=\"loadingMessage\" style=\"text-align: center; padding: 20px; color: #7f8c8d;\">\n");
+            html.push_str("                <p>⏳ Loading Wave 1 jobs...</p>\n");
+            html.push_str("            </div>\n");
+            html.push_str("            <table id=\"jobsTable\" class=\"display\" style=\"display: none;\">\n");
+            html.push_str("                <thead>\n");
+            html.push_str("                    <tr>\n");
+            html.push_str("                        <th>Job Name</th>\n");
+            html.push_str("                        <th>Folder</th>\n");
+            html.push_str("                        <th>Wave</th>\n");
+            html.push_str("                        <th>Complexity</th>\n");
+            html.push_str("                        <th>Difficulty</th>\n");
+            html.push_str("                        <th>Readiness</th>\n");
+            html.push_str("                        <th>Dependencies</th>\n");
+            html.push_str("                    </tr>\n");
+            html.push_str("                </thead>\n");
+            html.push_str("                <tbody id=\"jobsTableBody\">\n");
+            html.push_str("                </tbody>\n");
+            html.push_str("            </table>\n");
+            html.push_str("        </div>\n");
+        }
 
         html.push_str("    </div>\n");
         
-        // Add DataTables initialization with dynamic data loading
-        html.push_str("    <script>\n");
-        html.push_str("        var table = null;\n");
-        html.push_str("        var currentWave = 1;\n");
-        html.push_str("        \n");
-        html.push_str("        function getDifficultyClass(difficulty) {\n");
-        html.push_str("            switch(difficulty) {\n");
-        html.push_str("                case 'Easy': return 'easy';\n");
-        html.push_str("                case 'Medium': return 'medium';\n");
-        html.push_str("                case 'Hard': return 'hard';\n");
-        html.push_str("                default: return '';\n");
-        html.push_str("            }\n");
-        html.push_str("        }\n");
-        html.push_str("        \n");
-        html.push_str("        function loadWaveData(waveNum) {\n");
-        html.push_str("            $('#loadingMessage').show();\n");
-        html.push_str("            $('#loadingMessage p').text('⏳ Loading Wave ' + waveNum + ' jobs...');\n");
-        html.push_str("            $('#jobsTable').hide();\n");
-        html.push_str("            \n");
-        html.push_str("            $.getJSON('data/wave_' + waveNum + '.json', function(data) {\n");
-        html.push_str("                if (table) {\n");
-        html.push_str("                    table.destroy();\n");
-        html.push_str("                }\n");
-        html.push_str("                \n");
-        html.push_str("                var tbody = $('#jobsTableBody');\n");
-        html.push_str("                tbody.empty();\n");
-        html.push_str("                \n");
-        html.push_str("                data.forEach(function(job) {\n");
-        html.push_str("                    var diffClass = getDifficultyClass(job.migration_difficulty);\n");
-        html.push_str("                    var row = '<tr>' +\n");
-        html.push_str("                        '<td>' + job.job_name + '</td>' +\n");
-        html.push_str("                        '<td>' + job.folder_name + '</td>' +\n");
-        html.push_str("                        '<td><span class=\"wave-badge\">Wave ' + job.migration_wave + '</span></td>' +\n");
-        html.push_str("                        '<td>' + job.complexity_score + '</td>' +\n");
-        html.push_str("                        '<td><span class=\"' + diffClass + '\">' + job.migration_difficulty + '</span></td>' +\n");
-        html.push_str("                        '<td>' + job.dependency_count + '</td>' +\n");
-        html.push_str("                        '</tr>';\n");
-        html.push_str("                    tbody.append(row);\n");
-        html.push_str("                });\n");
-        html.push_str("                \n");
-        html.push_str("                $('#loadingMessage').hide();\n");
-        html.push_str("                $('#jobsTable').show();\n");
-        html.push_str("                \n");
-        html.push_str("                table = $('#jobsTable').DataTable({\n");
-        html.push_str("                    pageLength: 50,\n");
-        html.push_str("                    order: [[3, 'desc']],\n");
-        html.push_str("                    lengthMenu: [[25, 50, 100, 500, -1], [25, 50, 100, 500, 'All']],\n");
-        html.push_str("                    columnDefs: [\n");
-        html.push_str("                        { width: '30%', targets: 0 },\n");
-        html.push_str("                        { width: '25%', targets: 1 },\n");
-        html.push_str("                        { width: '15%', targets: 2 },\n");
-        html.push_str("                        { width: '12%', targets: 3 },\n");
-        html.push_str("                        { width: '12%', targets: 4 },\n");
-        html.push_str("                        { width: '10%', targets: 5 }\n");
-        html.push_str("                    ],\n");
-        html.push_str("                    language: {\n");
-        html.push_str("                        search: 'Search jobs:',\n");
-        html.push_str("                        lengthMenu: 'Show _MENU_ jobs per page',\n");
-        html.push_str("                        info: 'Showing _START_ to _END_ of _TOTAL_ jobs',\n");
-        html.push_str("                        infoFiltered: '(filtered from _MAX_ total jobs)'\n");
-        html.push_str("                    }\n");
-        html.push_str("                });\n");
-        html.push_str("            }).fail(function() {\n");
-        html.push_str("                $('#loadingMessage p').text('❌ Error loading wave data. Please refresh the page.');\n");
-        html.push_str("            });\n");
-        html.push_str("        }\n");
-        html.push_str("        \n");
-        html.push_str("        $(document).ready(function() {\n");
-        html.push_str("            // Wave filter functionality\n");
-        html.push_str("            $('#waveFilter').on('change', function() {\n");
-        html.push_str("                var selectedWave = parseInt(this.value);\n");
-        html.push_str("                if (selectedWave && selectedWave !== currentWave) {\n");
-        html.push_str("                    currentWave = selectedWave;\n");
-        html.push_str("                    loadWaveData(selectedWave);\n");
-        html.push_str("                }\n");
-        html.push_str("            });\n");
-        html.push_str("            \n");
-        html.push_str("            // Load Wave 1 by default\n");
-        html.push_str("            loadWaveData(1);\n");
-        html.push_str("        });\n");
-        html.push_str("    </script>\n");
+        if self.includes(HtmlSection::Jobs) {
+            // Add DataTables initialization with dynamic data loading
+            html.push_str("    <script>\n");
+            html.push_str("        var table = null;\n");
+            html.push_str("        var currentWave = 1;\n");
+            if self.is_sharded(output) {
+                let counts: Vec<String> = self.shard_counts(output).iter()
+                    .map(|(wave, parts)| format!("{}: {}", wave, parts))
+                    .collect();
+                html.push_str(&format!("        var shardCounts = {{{}}};\n", counts.join(", ")));
+                html.push_str("        \n");
+                html.push_str("        function dataUrl(waveNum, part) {\n");
+                html.push_str("            return 'data/wave_' + waveNum + '_part_' + part + '.json';\n");
+                html.push_str("        }\n");
+                html.push_str("        \n");
+                html.push_str("        function fillShardFilter(waveNum) {\n");
+                html.push_str("            var select = $('#shardFilter');\n");
+                html.push_str("            select.empty();\n");
+                html.push_str("            for (var part = 1; part <= (shardCounts[waveNum] || 1); part++) {\n");
+                html.push_str("                select.append('<option value=\"' + part + '\">' + part + ' of ' + shardCounts[waveNum] + '</option>');\n");
+                html.push_str("            }\n");
+                html.push_str("        }\n");
+            } else {
+                html.push_str("        \n");
+                html.push_str("        function dataUrl(waveNum, part) {\n");
+                html.push_str("            return 'data/wave_' + waveNum + '.json';\n");
+                html.push_str("        }\n");
+            }
+            html.push_str("        \n");
+            html.push_str("        function getDifficultyClass(difficulty) {\n");
+            html.push_str("            switch(difficulty) {\n");
+            html.push_str("                case 'Easy': return 'easy';\n");
+            html.push_str("                case 'Medium': return 'medium';\n");
+            html.push_str("                case 'Hard': return 'hard';\n");
+            html.push_str("                default: return '';\n");
+            html.push_str("            }\n");
+            html.push_str("        }\n");
+            html.push_str("        \n");
+            html.push_str("        function loadWaveData(waveNum, part) {\n");
+            html.push_str("            $('#loadingMessage').show();\n");
+            html.push_str("            $('#loadingMessage p').text('⏳ Loading Wave ' + waveNum + ' jobs...');\n");
+            html.push_str("            $('#jobsTable').hide();\n");
+            html.push_str("            \n");
+            html.push_str("            $.getJSON(dataUrl(waveNum, part || 1), function(data) {\n");
+            html.push_str("                if (table) {\n");
+            html.push_str("                    table.destroy();\n");
+            html.push_str("                }\n");
+            html.push_str("                \n");
+            html.push_str("                var tbody = $('#jobsTableBody');\n");
+            html.push_str("                tbody.empty();\n");
+            html.push_str("                \n");
+            html.push_str("                data.forEach(function(job) {\n");
+            html.push_str("                    var diffClass = getDifficultyClass(job.migration_difficulty);\n");
+            html.push_str("                    var row = '<tr>' +\n");
+            html.push_str("                        '<td>' + job.job_name + '</td>' +\n");
+            html.push_str("                        '<td>' + job.folder_name + '</td>' +\n");
+            html.push_str("                        '<td><span class=\"wave-badge\">Wave ' + job.migration_wave + '</span></td>' +\n");
+            html.push_str("                        '<td>' + job.complexity_score + '</td>' +\n");
+            html.push_str("                        '<td><span class=\"' + diffClass + '\">' + job.migration_difficulty + '</span></td>' +\n");
+            html.push_str("                        '<td>' + job.migration_readiness + '</td>' +\n");
+            html.push_str("                        '<td>' + job.dependency_count + '</td>' +\n");
+            html.push_str("                        '</tr>';\n");
+            html.push_str("                    tbody.append(row);\n");
+            html.push_str("                });\n");
+            html.push_str("                \n");
+            html.push_str("                $('#loadingMessage').hide();\n");
+            html.push_str("                $('#jobsTable').show();\n");
+            html.push_str("                \n");
+            html.push_str("                table = $('#jobsTable').DataTable({\n");
+            html.push_str("                    pageLength: 50,\n");
+            html.push_str("                    order: [[3, 'desc']],\n");
+            html.push_str("                    lengthMenu: [[25, 50, 100, 500, -1], [25, 50, 100, 500, 'All']],\n");
+            html.push_str("                    columnDefs: [\n");
+            html.push_str("                        { width: '25%', targets: 0 },\n");
+            html.push_str("                        { width: '20%', targets: 1 },\n");
+            html.push_str("                        { width: '10%', targets: 2 },\n");
+            html.push_str("                        { width: '10%', targets: 3 },\n");
+            html.push_str("                        { width: '10%', targets: 4 },\n");
+            html.push_str("                        { width: '13%', targets: 5 },\n");
+            html.push_str("                        { width: '12%', targets: 6 }\n");
+            html.push_str("                    ],\n");
+            html.push_str("                    language: {\n");
+            html.push_str("                        search: 'Search jobs:',\n");
+            html.push_str("                        lengthMenu: 'Show _MENU_ jobs per page',\n");
+            html.push_str("                        info: 'Showing _START_ to _END_ of _TOTAL_ jobs',\n");
+            html.push_str("                        infoFiltered: '(filtered from _MAX_ total jobs)'\n");
+            html.push_str("                    }\n");
+            html.push_str("                });\n");
+            html.push_str("            }).fail(function() {\n");
+            html.push_str("                $('#loadingMessage p').text('❌ Error loading wave data. Please refresh the page.');\n");
+            html.push_str("            });\n");
+            html.push_str("        }\n");
+            html.push_str("        \n");
+            html.push_str("        $(document).ready(function() {\n");
+            html.push_str("            // Wave filter functionality\n");
+            html.push_str("            $('#waveFilter').on('change', function() {\n");
+            html.push_str("                var selectedWave = parseInt(this.value);\n");
+            html.push_str("                if (selectedWave && selectedWave !== currentWave) {\n");
+            html.push_str("                    currentWave = selectedWave;\n");
+            if self.is_sharded(output) {
+                html.push_str("                    fillShardFilter(selectedWave);\n");
+            }
+            html.push_str("                    loadWaveData(selectedWave);\n");
+            html.push_str("                }\n");
+            html.push_str("            });\n");
+            if self.is_sharded(output) {
+                html.push_str("            $('#shardFilter').on('change', function() {\n");
+                html.push_str("                loadWaveData(currentWave, parseInt(this.value));\n");
+                html.push_str("            });\n");
+                html.push_str("            fillShardFilter(1);\n");
+            }
+            html.push_str("            \n");
+            html.push_str("            // Load Wave 1 by default\n");
+            html.push_str("            loadWaveData(1);\n");
+            html.push_str("        });\n");
+            html.push_str("    </script>\n");
+        }
         
         html.push_str("</body>\n</html>");
 
-        Ok(html)
+        Ok((html, report))
+    }
+
+    fn push_folder_matrix(&self, html: &mut String, output: &AnalysisOutput) {
+        let matrix = &output.folder_matrix;
+        let max_count = matrix.max_count().max(1) as f64;
+
+        html.push_str("        <div class=\"folder-matrix\">\n");
+        html.push_str("            <h2>🗺️ Folder Dependency Matrix</h2>\n");
+        html.push_str("            <p class=\"table-info\">Rows are folders setting conditions, columns are folders waiting for them. Cells count job-to-job dependencies.</p>\n");
+        html.push_str("            <div class=\"matrix-scroll\">\n");
+        html.push_str("            <table class=\"matrix-table\">\n");
+        html.push_str("                <tr>\n");
+        html.push_str("                    <th>From \\ To</th>\n");
+        for folder in &matrix.folders {
+            html.push_str(&format!("                    <th>{}</th>\n", escape_html(folder)));
+        }
+        html.push_str("                </tr>\n");
+        for (folder, row) in matrix.folders.iter().zip(&matrix.counts) {
+            html.push_str("                <tr>\n");
+            html.push_str(&format!("                    <th>{}</th>\n", escape_html(folder)));
+            for &count in row {
+                let intensity = count as f64 / max_count;
+                html.push_str(&format!(
+                    "                    <td style=\"background: rgba(231, 76, 60, {:.2});\">{}</td>\n",
+                    intensity, count
+                ));
+            }
+            html.push_str("                </tr>\n");
+        }
+        html.push_str("            </table>\n");
+        html.push_str("            </div>\n");
+        html.push_str("        </div>\n");
     }
 
     fn get_css() -> &'static str {
@@ -610,6 +918,26 @@ impl HtmlGenerator {
             font-size: 1em;
         }
         
+        /* Folder Matrix */
+        .matrix-scroll {
+            overflow-x: auto;
+        }
+        .matrix-table {
+            border-collapse: collapse;
+            font-size: 0.9em;
+        }
+        .matrix-table th,
+        .matrix-table td {
+            border: 1px solid #ddd;
+            padding: 6px 10px;
+            text-align: center;
+            white-space: nowrap;
+        }
+        .matrix-table th {
+            background: #f8f9fa;
+            color: #2c3e50;
+        }
+        
         /* Table Styles */
         .jobs {
             margin: 40px 0;
@@ -710,3 +1038,195 @@ impl Default for HtmlGenerator {
         Self::new()
     }
 }
+
+/// Formats a methodology weight, e.g. `1 point each` or `15 points`
+fn points(weight: u32, suffix: &str) -> String {
+    let unit = if weight == 1 { "point" } else { "points" };
+    if suffix.is_empty() {
+        format!("{} {}", weight, unit)
+    } else {
+        format!("{} {} {}", weight, unit, suffix)
+    }
+}
+
+/// Escapes text for use in HTML element content or attribute values
+fn escape_html(value: &str) -> String {
+    value.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::presentation::dto::{AirflowMappingOutput, JobOutput, MetricsOutput, WaveOutput};
+
+    fn job(name: &str, wave: usize) -> JobOutput {
+        JobOutput {
+            job_name: name.to_string(),
+            folder: "FOLDER".to_string(),
+            folder_name: "FOLDER".to_string(),
+            complexity_score: 10,
+            migration_difficulty: "Easy".to_string(),
+            migration_readiness: "Ready".to_string(),
+            migration_priority: 0,
+            migration_wave: wave,
+            is_critical: false,
+            dependency_count: 0,
+            estimated_effort_hours: 4,
+            metrics: MetricsOutput { dependency_count: 0, is_critical: false, is_cyclic: false },
+            risks: vec![],
+            migration_notes: vec![],
+            factor_breakdown: Default::default(),
+            airflow_mapping: AirflowMappingOutput {
+                suggested_dag_name: "folder".to_string(),
+                operator_type: "BashOperator".to_string(),
+                operator_import: String::new(),
+                provider: None,
+                mapping_note: None,
+                estimated_effort_hours: 4,
+            },
+        }
+    }
+
+    #[test]
+    fn test_folder_matrix_escapes_folder_names() {
+        let mut output = AnalysisOutput::for_test(vec![]);
+        output.folder_matrix.folders = vec!["R&D <ETL>".to_string()];
+        output.folder_matrix.counts = vec![vec![1]];
+
+        let generator = HtmlGenerator::new().with_sections(vec![HtmlSection::FolderMatrix]);
+        let html = generator.generate_string(&output).unwrap();
+
+        assert!(html.contains("<th>R&amp;D &lt;ETL&gt;</th>"));
+        assert!(!html.contains("<ETL>"));
+    }
+
+    #[test]
+    fn test_generate_jobs_table_only() {
+        let output = AnalysisOutput::for_test(vec![]);
+
+        let generator = HtmlGenerator::new().with_sections(vec![HtmlSection::Jobs]);
+        let html = generator.generate_string(&output).unwrap();
+
+        assert!(html.contains("id=\"jobsTable\""));
+        assert!(!html.contains("<div class=\"methodology\">"));
+        assert!(!html.contains("<div class=\"stats-grid\">"));
+    }
+
+    #[test]
+    fn test_offline_assets_are_inlined_without_cdn_links() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        for (name, content) in OFFLINE_ASSET_FILES.iter().zip(["table.dataTable{}", "/*jq*/ var s='</script>';", "/*dt*/"]) {
+            fs::write(dir.join(name), content).unwrap();
+        }
+        let output = AnalysisOutput::for_test(vec![]);
+
+        let html = HtmlGenerator::new().with_offline_assets(Some(dir.to_path_buf())).generate_string(&output).unwrap();
+
+        assert!(!html.contains("https://"));
+        assert!(html.contains("table.dataTable{}") && html.contains("/*dt*/"));
+        assert!(html.contains("var s='<\\/script>';"));
+
+        fs::remove_file(dir.join(OFFLINE_ASSET_FILES[2])).unwrap();
+        assert!(HtmlGenerator::new().with_offline_assets(Some(dir.to_path_buf())).generate_string(&output).is_err());
+    }
+
+    #[test]
+    fn test_zero_total_jobs_reports_warning_instead_of_nan() {
+        let mut output = AnalysisOutput::for_test(vec![]);
+        output.summary.total_jobs = 0;
+
+        let (html, report) = HtmlGenerator::new()
+            .with_sections(vec![HtmlSection::Difficulty])
+            .generate_string_with_report(&output)
+            .unwrap();
+
+        assert!(!html.contains("NaN"));
+        assert!(html.contains("Easy (0.0%)"));
+        assert!(report.warnings.iter().any(|w| w.contains("Easy job percentage") && w.contains("total is zero")));
+        assert!(report.warnings.iter().any(|w| w.contains("no jobs")));
+    }
+
+    #[test]
+    fn test_methodology_shows_weights_in_use() {
+        let output = AnalysisOutput::for_test(vec![]);
+        let weights = ComplexityWeights { cyclic: 2, variable: 1, on_condition: 10, ..Default::default() };
+
+        let generator = HtmlGenerator::new()
+            .with_sections(vec![HtmlSection::Methodology])
+            .with_weights(weights);
+        let html = generator.generate_string(&output).unwrap();
+
+        assert!(html.contains("<div class=\"scoring-value\">2 points</div>"));
+        assert!(html.contains("<div class=\"scoring-value\">10 points each</div>"));
+        assert!(html.contains("<div class=\"scoring-value\">1 point each</div>"));
+        assert!(!html.contains("<div class=\"scoring-value\">15 points</div>"));
+    }
+
+    #[test]
+    fn test_large_reports_are_sharded_above_threshold() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+
+        let jobs = (1..=5).map(|i| job(&format!("W1_{}", i), 1)).chain([job("W2_1", 2)]).collect();
+        let output = AnalysisOutput::for_test(jobs);
+
+        // At the threshold the whole wave stays in one file
+        HtmlGenerator::new().with_shard_threshold(6).generate(&output, dir.join("small.html")).unwrap();
+        assert!(dir.join("data/wave_1.json").exists());
+
+        let _ = std::fs::remove_dir_all(dir.join("data"));
+        let generator = HtmlGenerator::new().with_shard_threshold(5).with_shard_size(2);
+        generator.generate(&output, dir.join("analysis.html")).unwrap();
+
+        assert!(!dir.join("data/wave_1.json").exists());
+        for part in 1..=3 {
+            assert!(dir.join(format!("data/wave_1_part_{}.json", part)).exists());
+        }
+        assert!(!dir.join("data/wave_1_part_4.json").exists());
+        let last: Vec<serde_json::Value> = serde_json::from_str(
+            &std::fs::read_to_string(dir.join("data/wave_1_part_3.json")).unwrap()
+        ).unwrap();
+        assert_eq!(last.len(), 1);
+
+        let html = std::fs::read_to_string(dir.join("analysis.html")).unwrap();
+        assert!(html.contains("var shardCounts = {1: 3, 2: 1};"));
+        assert!(html.contains("jobweaver serve"));
+    }
+
+    #[test]
+    fn test_every_job_lands_in_one_wave_file_beyond_wave_five() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+
+        let jobs: Vec<JobOutput> = (1..=8).flat_map(|wave| [
+            job(&format!("W{}_A", wave), wave),
+            job(&format!("W{}_B", wave), wave),
+        ]).collect();
+        let mut output = AnalysisOutput::for_test(jobs);
+        output.migration_waves = (1..=8).map(|wave| WaveOutput {
+            wave,
+            wave_number: wave,
+            jobs: vec![format!("W{}_A", wave), format!("W{}_B", wave)],
+            reason: String::new(),
+        }).collect();
+
+        HtmlGenerator::new().generate(&output, dir.join("analysis.html")).unwrap();
+
+        let mut seen: Vec<String> = Vec::new();
+        for wave in 1..=8 {
+            let jobs: Vec<serde_json::Value> = serde_json::from_str(
+                &std::fs::read_to_string(dir.join(format!("data/wave_{}.json", wave))).unwrap()
+            ).unwrap();
+            seen.extend(jobs.iter().map(|j| j["job_name"].as_str().unwrap().to_string()));
+        }
+        seen.sort();
+        let mut expected: Vec<String> = output.jobs.iter().map(|j| j.job_name.clone()).collect();
+        expected.sort();
+        assert_eq!(seen, expected);
+        assert!(!dir.join("data/wave_9.json").exists());
+    }
+}
@@ -3,6 +3,7 @@ use std::fs::File;
 use std::io::Write;
 use std::path::Path;
 use crate::presentation::dto::AnalysisOutput;
+use super::generation_report::GenerationReport;
 
 pub struct JsonGenerator;
 
@@ -11,11 +12,11 @@ impl JsonGenerator {
         Self
     }
 
-    pub fn generate<P: AsRef<Path>>(&self, output: &AnalysisOutput, path: P) -> Result<()> {
+    pub fn generate<P: AsRef<Path>>(&self, output: &AnalysisOutput, path: P) -> Result<GenerationReport> {
         let json = serde_json::to_string_pretty(output)?;
         let mut file = File::create(path)?;
         file.write_all(json.as_bytes())?;
-        Ok(())
+        Ok(GenerationReport::for_output(output))
     }
 
     pub fn generate_string(&self, output: &AnalysisOutput) -> Result<String> {
@@ -32,22 +33,11 @@ impl Default for JsonGenerator {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::presentation::dto::SummaryOutput;
 
     #[test]
     fn test_generate_string() {
         let generator = JsonGenerator::new();
-        let output = AnalysisOutput {
-            summary: SummaryOutput {
-                total_jobs: 10,
-                total_folders: 2,
-                analysis_date: "2026-01-12".to_string(),
-                average_complexity_score: 42.5,
-                has_circular_dependencies: false,
-            },
-            jobs: vec![],
-            migration_waves: vec![],
-        };
+        let output = AnalysisOutput::for_test(vec![]);
 
         let json = generator.generate_string(&output).unwrap();
         assert!(json.contains("total_jobs"));
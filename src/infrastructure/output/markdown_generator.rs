@@ -1,21 +1,53 @@
 use anyhow::Result;
-use std::fs::File;
-use std::io::Write;
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
 use std::path::Path;
-use crate::presentation::dto::AnalysisOutput;
+use crate::presentation::dto::{AnalysisOutput, JobOutput};
+use super::generation_report::GenerationReport;
+use super::graph_exporter::{EstateGraph, GraphExporter, GraphFormat};
+use super::text_encoding::OutputEncoding;
 
-pub struct MarkdownGenerator;
+/// Folders with more jobs than this get a note instead of an inline Mermaid diagram
+pub const MAX_MERMAID_NODES: usize = 50;
+
+pub struct MarkdownGenerator {
+    /// Character encoding of the written file
+    encoding: OutputEncoding,
+    /// Estate dependency graph used to draw a diagram per folder
+    dependency_graph: Option<EstateGraph>,
+}
 
 impl MarkdownGenerator {
     pub fn new() -> Self {
-        Self
+        Self {
+            encoding: OutputEncoding::default(),
+            dependency_graph: None,
+        }
+    }
+
+    /// Sets the character encoding of the written file (UTF-8 by default)
+    pub fn with_encoding(mut self, encoding: OutputEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Draws each folder's internal dependencies as a Mermaid diagram
+    ///
+    /// Without a graph, folder sections list their jobs only.
+    pub fn with_dependency_graph(mut self, graph: EstateGraph) -> Self {
+        self.dependency_graph = Some(graph);
+        self
     }
 
-    pub fn generate<P: AsRef<Path>>(&self, output: &AnalysisOutput, path: P) -> Result<()> {
+    pub fn generate<P: AsRef<Path>>(&self, output: &AnalysisOutput, path: P) -> Result<GenerationReport> {
         let markdown = self.generate_string(output)?;
-        let mut file = File::create(path)?;
-        file.write_all(markdown.as_bytes())?;
-        Ok(())
+        let mut report = GenerationReport::for_output(output);
+        let (bytes, substitutions) = self.encoding.encode_text(&markdown);
+        if let Some(warning) = substitutions.warning(&path.as_ref().display().to_string(), self.encoding) {
+            report.warn(warning);
+        }
+        fs::write(path.as_ref(), bytes)?;
+        Ok(report)
     }
 
     pub fn generate_string(&self, output: &AnalysisOutput) -> Result<String> {
@@ -42,21 +74,59 @@ impl MarkdownGenerator {
             md.push_str("\n");
         }
 
-        md.push_str("## Job Details\n\n");
-        md.push_str("| Job Name | Folder | Complexity | Difficulty | Priority | Dependencies | Effort (hrs) |\n");
-        md.push_str("|----------|--------|------------|------------|----------|--------------|-------------|\n");
-        
-        for job in &output.jobs {
+        if let Some(timeline) = &output.timeline {
+            md.push_str("## Migration Timeline\n\n");
             md.push_str(&format!(
-                "| {} | {} | {} | {} | {} | {} | {} |\n",
-                job.job_name,
-                job.folder,
-                job.complexity_score,
-                job.migration_difficulty,
-                job.migration_priority,
-                job.metrics.dependency_count,
-                job.airflow_mapping.estimated_effort_hours
+                "Estimated with {} engineer(s) working {} hours per day in parallel; waves run one after another. Total: **{} working days**",
+                timeline.engineers, timeline.hours_per_day, timeline.total_days
             ));
+            if let Some(end) = timeline.projected_end_date {
+                md.push_str(&format!(", projected to finish on **{}**", end));
+            }
+            md.push_str(".\n\n");
+            md.push_str("| Wave | Jobs | Effort (hrs) | Duration (hrs) | Working Days | Start | End |\n");
+            md.push_str("|------|------|--------------|----------------|--------------|-------|-----|\n");
+            for wave in &timeline.waves {
+                let (start, end) = match (wave.start_date, wave.end_date) {
+                    (Some(start), Some(end)) => (start.to_string(), end.to_string()),
+                    _ => (format!("Day {}", wave.start_day), format!("Day {}", wave.end_day)),
+                };
+                let longest = if timeline.longest_wave == Some(wave.wave) { " (longest)" } else { "" };
+                md.push_str(&format!(
+                    "| {}{} | {} | {} | {} | {} | {} | {} |\n",
+                    wave.wave, longest, wave.job_count, wave.total_effort_hours,
+                    wave.duration_hours, wave.duration_days, start, end
+                ));
+            }
+            md.push_str("\n");
+        }
+
+        md.push_str("## Job Details by Folder\n\n");
+        let mut jobs_by_folder: BTreeMap<&str, Vec<&JobOutput>> = BTreeMap::new();
+        for job in &output.jobs {
+            jobs_by_folder.entry(job.folder_name.as_str()).or_default().push(job);
+        }
+
+        for (folder, jobs) in &jobs_by_folder {
+            md.push_str(&format!("### {} - {} jobs\n\n", folder, jobs.len()));
+            md.push_str("| Job Name | Complexity | Difficulty | Wave | Priority | Dependencies | Effort (hrs) |\n");
+            md.push_str("|----------|------------|------------|------|----------|--------------|-------------|\n");
+            for job in jobs {
+                md.push_str(&format!(
+                    "| {} | {} | {} | {} | {} | {} | {} |\n",
+                    job.job_name,
+                    job.complexity_score,
+                    job.migration_difficulty,
+                    job.migration_wave,
+                    job.migration_priority,
+                    job.metrics.dependency_count,
+                    job.airflow_mapping.estimated_effort_hours
+                ));
+            }
+            md.push('\n');
+            if let Some(graph) = &self.dependency_graph {
+                md.push_str(&Self::folder_diagram(graph, folder)?);
+            }
         }
 
         md.push_str("\n## High-Risk Jobs\n\n");
@@ -75,10 +145,62 @@ impl MarkdownGenerator {
                 for risk in &job.risks {
                     md.push_str(&format!("  - {}\n", risk));
                 }
+                if !job.migration_notes.is_empty() {
+                    md.push_str("- **Migration Notes**:\n");
+                    for note in &job.migration_notes {
+                        md.push_str(&format!("  - {}\n", note));
+                    }
+                }
                 md.push_str("\n");
             }
         }
 
+        if !output.on_demand_jobs.is_empty() {
+            md.push_str("## Manual / On-Demand Jobs\n\n");
+            md.push_str("These jobs have no schedule and no dependencies. Classify them as manually triggered or obsolete before migration.\n\n");
+            for job in &output.on_demand_jobs {
+                md.push_str(&format!("- {} ({})\n", job.job_name, job.folder_name));
+            }
+            md.push_str("\n");
+        }
+
+        if !output.cross_datacenter_dependencies.is_empty() {
+            md.push_str("## Cross-Datacenter Integration Points\n\n");
+            md.push_str(&format!(
+                "{} dependencies wait on a job in another datacenter. Each crosses a network and security boundary and needs its own integration plan.\n\n",
+                output.cross_datacenter_dependencies.len()
+            ));
+            md.push_str("| Consumer | Datacenter | Producer | Datacenter | Condition |\n");
+            md.push_str("|----------|------------|----------|------------|-----------|\n");
+            for dep in &output.cross_datacenter_dependencies {
+                md.push_str(&format!(
+                    "| {} | {} | {} | {} | {} |\n",
+                    dep.consumer_job, dep.consumer_datacenter, dep.producer_job, dep.producer_datacenter, dep.condition
+                ));
+            }
+            md.push_str("\n");
+        }
+
+        if !output.ambiguous_producers.is_empty() {
+            md.push_str("## Ambiguous Producers\n\n");
+            md.push_str(&format!(
+                "{} conditions are raised by more than one job. Consumers can't tell which producer they depend on; decide each mapping before migration.\n\n",
+                output.ambiguous_producers.len()
+            ));
+            md.push_str("| Condition | Producers | Consumers |\n");
+            md.push_str("|-----------|-----------|-----------|\n");
+            for ambiguous in &output.ambiguous_producers {
+                let producers: Vec<String> = ambiguous.producers.iter()
+                    .map(|p| format!("{} ({})", p.job_name, p.folder_name))
+                    .collect();
+                md.push_str(&format!(
+                    "| {} | {} | {} |\n",
+                    ambiguous.condition, producers.join(", "), ambiguous.consumer_jobs.join(", ")
+                ));
+            }
+            md.push_str("\n");
+        }
+
         md.push_str("## Recommendations\n\n");
         md.push_str("1. Start with Wave 1 jobs for quick wins and team familiarization\n");
         md.push_str("2. Address circular dependencies before migration\n");
@@ -88,6 +210,36 @@ impl MarkdownGenerator {
 
         Ok(md)
     }
+
+    /// Renders the dependencies between jobs of one folder as a Mermaid block
+    ///
+    /// Folders above `MAX_MERMAID_NODES` jobs, and folders without internal
+    /// dependencies, get a one-line note instead.
+    fn folder_diagram(graph: &EstateGraph, folder: &str) -> Result<String> {
+        let nodes: Vec<_> = graph.nodes.iter()
+            .filter(|n| n.folder_name == folder)
+            .cloned()
+            .collect();
+        if nodes.len() > MAX_MERMAID_NODES {
+            return Ok(format!(
+                "_{} jobs are too many to diagram inline (limit {}); write the full graph with `--graph-format mermaid`._\n\n",
+                nodes.len(), MAX_MERMAID_NODES
+            ));
+        }
+
+        let ids: HashSet<&str> = nodes.iter().map(|n| n.id.as_str()).collect();
+        let edges: Vec<_> = graph.edges.iter()
+            .filter(|e| ids.contains(e.from.as_str()) && ids.contains(e.to.as_str()))
+            .cloned()
+            .collect();
+        if edges.is_empty() {
+            return Ok("_No dependencies between jobs in this folder._\n\n".to_string());
+        }
+
+        let folder_graph = EstateGraph { nodes, edges, truncated: false };
+        let mermaid = GraphExporter::new().render(&folder_graph, GraphFormat::Mermaid)?;
+        Ok(format!("```mermaid\n{}```\n\n", mermaid))
+    }
 }
 
 impl Default for MarkdownGenerator {
@@ -99,25 +251,47 @@ impl Default for MarkdownGenerator {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::presentation::dto::SummaryOutput;
 
     #[test]
     fn test_generate_string() {
         let generator = MarkdownGenerator::new();
-        let output = AnalysisOutput {
-            summary: SummaryOutput {
-                total_jobs: 10,
-                total_folders: 2,
-                analysis_date: "2026-01-12".to_string(),
-                average_complexity_score: 42.5,
-                has_circular_dependencies: false,
-            },
-            jobs: vec![],
-            migration_waves: vec![],
-        };
+        let output = AnalysisOutput::for_test(vec![]);
 
         let md = generator.generate_string(&output).unwrap();
         assert!(md.contains("# Control-M to Airflow Migration Analysis Report"));
         assert!(md.contains("Total Jobs"));
     }
+
+    #[test]
+    fn test_folder_sections_embed_internal_dependencies_as_mermaid() {
+        use crate::application::use_cases::AnalyzeJobs;
+        use crate::domain::entities::{Condition, Folder, FolderType, Job};
+
+        let mut etl = Folder::new("ETL".to_string(), FolderType::Simple);
+        let mut extract = Job::new("EXTRACT".to_string(), "ETL".to_string());
+        extract.out_conditions.push(Condition::new_out("EXTRACT-OK".to_string()));
+        let mut load = Job::new("LOAD".to_string(), "ETL".to_string());
+        load.in_conditions.push(Condition::new_in("EXTRACT-OK".to_string()));
+        etl.add_job(extract);
+        etl.add_job(load);
+        let mut big = Folder::new("BIG".to_string(), FolderType::Simple);
+        for i in 0..=MAX_MERMAID_NODES {
+            big.add_job(Job::new(format!("JOB_{}", i), "BIG".to_string()));
+        }
+        let folders = vec![etl, big];
+        let all_jobs: Vec<_> = folders.iter().flat_map(|f| f.all_jobs()).collect();
+        let output = AnalysisOutput::from_analysis_result(AnalyzeJobs::new().execute(&folders).unwrap());
+
+        let md = MarkdownGenerator::new()
+            .with_dependency_graph(EstateGraph::from_jobs(&all_jobs, usize::MAX))
+            .generate_string(&output)
+            .unwrap();
+
+        assert!(md.contains("# Control-M to Airflow Migration Analysis Report"));
+        let etl_section = &md[md.find("### ETL - 2 jobs").unwrap()..];
+        assert!(etl_section.contains("```mermaid\ngraph LR\n"));
+        assert!(etl_section.contains("n0[\"EXTRACT\"]\n    n1[\"LOAD\"]\n    n0 -->|\"EXTRACT-OK\"| n1\n```"));
+        assert!(md.contains(&format!("### BIG - {} jobs", MAX_MERMAID_NODES + 1)));
+        assert!(md.contains("too many to diagram inline"));
+    }
 }
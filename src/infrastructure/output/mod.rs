@@ -1,11 +1,37 @@
+pub mod generation_report;
 pub mod json_generator;
 pub mod csv_generator;
 pub mod html_generator;
 pub mod markdown_generator;
 pub mod sqlite_exporter;
+pub mod sqlite_integrity;
+pub mod dag_validator;
+pub mod graph_exporter;
+pub mod bundle_writer;
+pub mod airflow_dependencies;
+pub mod airflow_dag_generator;
+pub mod airflow_variables;
+pub mod text_encoding;
+#[cfg(feature = "duckdb")]
+pub mod duckdb_exporter;
+#[cfg(feature = "parquet")]
+pub mod parquet_exporter;
 
+pub use generation_report::GenerationReport;
 pub use json_generator::JsonGenerator;
 pub use csv_generator::CsvGenerator;
-pub use html_generator::HtmlGenerator;
+pub use html_generator::{HtmlGenerator, HtmlSection};
 pub use markdown_generator::MarkdownGenerator;
-pub use sqlite_exporter::SqliteExporter;
+pub use sqlite_exporter::{ExportMode, SqliteExporter};
+pub use sqlite_integrity::{IntegrityReport, OrphanedRows, SqliteIntegrityChecker};
+pub use dag_validator::DagValidator;
+pub use graph_exporter::{EstateGraph, GraphExporter, GraphFormat};
+pub use bundle_writer::BundleWriter;
+pub use airflow_dependencies::{AirflowDependency, AirflowDependencyGenerator};
+pub use airflow_dag_generator::AirflowDagGenerator;
+pub use airflow_variables::{AirflowVariableGenerator, VariableCollision, VariableInventory};
+pub use text_encoding::OutputEncoding;
+#[cfg(feature = "duckdb")]
+pub use duckdb_exporter::DuckDbExporter;
+#[cfg(feature = "parquet")]
+pub use parquet_exporter::ParquetExporter;
@@ -0,0 +1,420 @@
+//! Parquet Exporter module
+//!
+//! This module provides functionality to export Control-M job definitions
+//! to Parquet files for loading into a data lake. Unlike CSV, Parquet keeps
+//! column types: integers stay integers, flags are booleans and missing
+//! attributes are nulls rather than empty strings.
+//!
+//! Two files are written into the output directory:
+//! - `jobs.parquet`: one row per job, with the attribute columns the database
+//!   exporters share (`JOB_FIELDS`)
+//! - `conditions.parquet`: one row per in- or out-condition, keyed by job `id`
+//!
+//! Only available with the `parquet` cargo feature.
+
+use anyhow::{Context, Result};
+use arrow::array::{ArrayRef, BooleanArray, Int32Array, Int64Array, StringArray};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use parquet::arrow::ArrowWriter;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use super::sqlite_exporter::{JobField, JOB_FIELDS};
+use crate::domain::entities::*;
+use crate::domain::value_objects::TokenizedCommand;
+
+/// File name of the jobs table inside the output directory
+pub const JOBS_FILE: &str = "jobs.parquet";
+
+/// File name of the conditions table inside the output directory
+pub const CONDITIONS_FILE: &str = "conditions.parquet";
+
+/// Number of `jobs.parquet` columns besides `JOB_FIELDS`: `id`, `job_name`,
+/// `folder_name`, `datacenter`, `cmdline_tokens` and `created_at`
+const EXTRA_JOB_COLUMNS: usize = 6;
+
+/// A job together with the folder it was exported from
+struct JobRow<'a> {
+    job: &'a Job,
+    folder: &'a Folder,
+    /// Datacenter of the top-level folder
+    datacenter: Option<&'a str>,
+}
+
+/// Parquet exporter for Control-M job definitions
+///
+/// Writes `jobs.parquet` and `conditions.parquet` into an output directory.
+pub struct ParquetExporter {
+    /// Directory receiving the Parquet files
+    output_dir: PathBuf,
+    /// Whether to fill `cmdline_tokens`
+    tokenize_cmdline: bool,
+    /// Maximum folder nesting depth (top-level folders are depth 1)
+    max_folder_depth: usize,
+}
+
+impl ParquetExporter {
+    /// Creates a new Parquet exporter, creating the output directory if needed
+    ///
+    /// # Arguments
+    ///
+    /// * `output_dir` - Directory receiving the Parquet files
+    ///
+    /// # Returns
+    ///
+    /// Result containing the ParquetExporter or an error
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the output directory cannot be created
+    pub fn new<P: AsRef<Path>>(output_dir: P) -> Result<Self> {
+        let output_dir = output_dir.as_ref().to_path_buf();
+        fs::create_dir_all(&output_dir)
+            .with_context(|| format!("Failed to create output directory {:?}", output_dir))?;
+
+        Ok(Self {
+            output_dir,
+            tokenize_cmdline: false,
+            max_folder_depth: DEFAULT_MAX_FOLDER_DEPTH,
+        })
+    }
+
+    /// Also stores each job's cmdline split into program and arguments
+    ///
+    /// # Arguments
+    ///
+    /// * `tokenize_cmdline` - Whether to fill the `cmdline_tokens` column
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn with_tokenize_cmdline(mut self, tokenize_cmdline: bool) -> Self {
+        self.tokenize_cmdline = tokenize_cmdline;
+        self
+    }
+
+    /// Sets the maximum folder nesting depth
+    ///
+    /// # Arguments
+    ///
+    /// * `max_folder_depth` - Maximum allowed nesting depth (top-level folders are depth 1)
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn with_max_folder_depth(mut self, max_folder_depth: usize) -> Self {
+        self.max_folder_depth = max_folder_depth;
+        self
+    }
+
+    /// Exports all folders (including sub-folders) to Parquet files
+    ///
+    /// # Arguments
+    ///
+    /// * `folders` - Slice of Folder entities to export
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or error
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if folders are nested deeper than the maximum folder
+    /// depth, or if a file cannot be created or written
+    pub fn export_folders(&self, folders: &[Folder]) -> Result<()> {
+        let mut rows = Vec::new();
+        for folder in folders {
+            self.collect_rows(folder, folder.datacenter.as_deref(), 1, &mut rows)?;
+        }
+
+        self.write_batch(JOBS_FILE, self.jobs_batch(&rows)?)?;
+        self.write_batch(CONDITIONS_FILE, conditions_batch(&rows)?)?;
+
+        Ok(())
+    }
+
+    /// Builds the `jobs` record batch
+    ///
+    /// # Arguments
+    ///
+    /// * `rows` - Jobs to export, in export order
+    ///
+    /// # Returns
+    ///
+    /// Result containing one record batch with a row per job
+    fn jobs_batch(&self, rows: &[JobRow]) -> Result<RecordBatch> {
+        let created_at = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        let mut fields = Vec::with_capacity(JOB_FIELDS.len() + EXTRA_JOB_COLUMNS);
+        let mut arrays: Vec<ArrayRef> = Vec::with_capacity(JOB_FIELDS.len() + EXTRA_JOB_COLUMNS);
+        let mut push = |name: &str, data_type: DataType, nullable: bool, array: ArrayRef| {
+            fields.push(Field::new(name, data_type, nullable));
+            arrays.push(array);
+        };
+
+        push("id", DataType::Int64, false, Arc::new(Int64Array::from_iter_values(1..=rows.len() as i64)));
+        push("job_name", DataType::Utf8, false,
+            Arc::new(StringArray::from_iter_values(rows.iter().map(|r| r.job.job_name.as_str()))));
+        push("folder_name", DataType::Utf8, false,
+            Arc::new(StringArray::from_iter_values(rows.iter().map(|r| r.job.folder_name.as_str()))));
+        push("datacenter", DataType::Utf8, true,
+            Arc::new(rows.iter().map(|r| r.datacenter).collect::<StringArray>()));
+
+        for (name, field) in JOB_FIELDS {
+            match field {
+                JobField::Text(get) => push(name, DataType::Utf8, true,
+                    Arc::new(rows.iter().map(|r| get(r.job)).collect::<StringArray>())),
+                JobField::Int(get) => push(name, DataType::Int32, true,
+                    Arc::new(rows.iter().map(|r| get(r.job)).collect::<Int32Array>())),
+                JobField::Flag(get) => push(name, DataType::Boolean, false,
+                    Arc::new(rows.iter().map(|r| Some(get(r.job))).collect::<BooleanArray>())),
+            }
+        }
+
+        let tokens = rows.iter()
+            .map(|r| self.cmdline_tokens(r))
+            .collect::<Result<Vec<_>>>()?;
+        push("cmdline_tokens", DataType::Utf8, true, Arc::new(StringArray::from(tokens)));
+        push("created_at", DataType::Utf8, false,
+            Arc::new(StringArray::from_iter_values(rows.iter().map(|_| created_at.as_str()))));
+
+        RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)
+            .context("Failed to build jobs record batch")
+    }
+
+    /// Tokenizes a job's cmdline as JSON, when tokenizing is enabled
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - Job and its containing folder (for the platform)
+    ///
+    /// # Returns
+    ///
+    /// Result containing the JSON tokens, or None if disabled or not tokenizable
+    fn cmdline_tokens(&self, row: &JobRow) -> Result<Option<String>> {
+        if !self.tokenize_cmdline {
+            return Ok(None);
+        }
+        row.job.cmdline.as_deref()
+            .and_then(|cmdline| TokenizedCommand::parse(cmdline, row.folder.platform.as_deref()))
+            .map(|tokens| serde_json::to_string(&tokens))
+            .transpose()
+            .context("Failed to serialize cmdline tokens")
+    }
+
+    /// Collects the jobs of a folder and its sub-folders, depth first
+    ///
+    /// # Arguments
+    ///
+    /// * `folder` - Folder to collect from
+    /// * `datacenter` - Datacenter of the top-level folder
+    /// * `depth` - Nesting depth of this folder (top-level folders are depth 1)
+    /// * `rows` - Collected jobs, appended to
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or error
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `depth` exceeds the configured maximum folder depth
+    fn collect_rows<'a>(
+        &self,
+        folder: &'a Folder,
+        datacenter: Option<&'a str>,
+        depth: usize,
+        rows: &mut Vec<JobRow<'a>>,
+    ) -> Result<()> {
+        if depth > self.max_folder_depth {
+            anyhow::bail!(
+                "Folder '{}' exceeds maximum nesting depth of {}",
+                folder.folder_name,
+                self.max_folder_depth
+            );
+        }
+
+        rows.extend(folder.jobs.iter().map(|job| JobRow { job, folder, datacenter }));
+        for sub_folder in &folder.sub_folders {
+            self.collect_rows(sub_folder, datacenter, depth + 1, rows)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes a record batch to a Parquet file in the output directory
+    ///
+    /// # Arguments
+    ///
+    /// * `file_name` - Name of the file inside the output directory
+    /// * `batch` - Record batch to write
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or error
+    fn write_batch(&self, file_name: &str, batch: RecordBatch) -> Result<()> {
+        let path = self.output_dir.join(file_name);
+        let file = File::create(&path)
+            .with_context(|| format!("Failed to create {:?}", path))?;
+        let mut writer = ArrowWriter::try_new(file, batch.schema(), None)?;
+        writer.write(&batch)
+            .with_context(|| format!("Failed to write {:?}", path))?;
+        writer.close()
+            .with_context(|| format!("Failed to finish {:?}", path))?;
+
+        Ok(())
+    }
+}
+
+/// Builds the `conditions` record batch
+///
+/// # Arguments
+///
+/// * `rows` - Exported jobs; a job's position plus one is its `id`
+///
+/// # Returns
+///
+/// Result containing one record batch with a row per in- or out-condition
+fn conditions_batch(rows: &[JobRow]) -> Result<RecordBatch> {
+    let mut job_ids = Vec::new();
+    let mut job_names = Vec::new();
+    let mut folder_names = Vec::new();
+    let mut condition_types = Vec::new();
+    let mut condition_names = Vec::new();
+    let mut odates = Vec::new();
+    let mut and_ors = Vec::new();
+    let mut signs = Vec::new();
+
+    for (index, row) in rows.iter().enumerate() {
+        for condition in row.job.in_conditions.iter().chain(row.job.out_conditions.iter()) {
+            job_ids.push(index as i64 + 1);
+            job_names.push(row.job.job_name.as_str());
+            folder_names.push(row.job.folder_name.as_str());
+            condition_types.push(match condition.condition_type {
+                ConditionType::In => "IN",
+                ConditionType::Out => "OUT",
+            });
+            condition_names.push(condition.name.as_str());
+            odates.push(condition.odate.as_deref());
+            and_ors.push(condition.and_or.as_deref());
+            signs.push(condition.sign.as_deref());
+        }
+    }
+
+    let schema = Schema::new(vec![
+        Field::new("job_id", DataType::Int64, false),
+        Field::new("job_name", DataType::Utf8, false),
+        Field::new("folder_name", DataType::Utf8, false),
+        Field::new("condition_type", DataType::Utf8, false),
+        Field::new("condition_name", DataType::Utf8, false),
+        Field::new("odate", DataType::Utf8, true),
+        Field::new("and_or", DataType::Utf8, true),
+        Field::new("sign", DataType::Utf8, true),
+    ]);
+    let arrays: Vec<ArrayRef> = vec![
+        Arc::new(Int64Array::from(job_ids)),
+        Arc::new(StringArray::from(job_names)),
+        Arc::new(StringArray::from(folder_names)),
+        Arc::new(StringArray::from(condition_types)),
+        Arc::new(StringArray::from(condition_names)),
+        Arc::new(StringArray::from(odates)),
+        Arc::new(StringArray::from(and_ors)),
+        Arc::new(StringArray::from(signs)),
+    ];
+
+    RecordBatch::try_new(Arc::new(schema), arrays)
+        .context("Failed to build conditions record batch")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::Array;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    fn read_batch(path: &Path) -> RecordBatch {
+        let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(path).unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+        let batches: Vec<RecordBatch> = reader.map(|b| b.unwrap()).collect();
+        arrow::compute::concat_batches(&batches[0].schema(), &batches).unwrap()
+    }
+
+    #[test]
+    fn test_export_keeps_types_and_nulls() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+
+        let mut folder = Folder::new("FOLDER".to_string(), FolderType::Simple);
+        let mut job_a = Job::new("JOB_A".to_string(), "FOLDER".to_string());
+        job_a.application = Some("APP".to_string());
+        job_a.critical = true;
+        job_a.maxrerun = Some(3);
+        job_a.out_conditions.push(Condition::new_out("JOB_A-OK".to_string()));
+        let mut job_b = Job::new("JOB_B".to_string(), "FOLDER".to_string());
+        job_b.in_conditions.push(Condition::new_in("JOB_A-OK".to_string()));
+        folder.add_job(job_a);
+        folder.add_job(job_b);
+
+        ParquetExporter::new(dir).unwrap().export_folders(&[folder]).unwrap();
+
+        let jobs = read_batch(&dir.join(JOBS_FILE));
+        assert_eq!(jobs.num_rows(), 2);
+        assert_eq!(jobs.num_columns(), JOB_FIELDS.len() + EXTRA_JOB_COLUMNS);
+
+        let schema = jobs.schema();
+        let critical = schema.field_with_name("critical").unwrap();
+        assert_eq!(critical.data_type(), &DataType::Boolean);
+        assert!(!critical.is_nullable());
+        assert_eq!(schema.field_with_name("application").unwrap().data_type(), &DataType::Utf8);
+        assert!(schema.field_with_name("application").unwrap().is_nullable());
+
+        let application = jobs.column_by_name("application").unwrap();
+        assert!(application.is_valid(0));
+        assert!(application.is_null(1));
+        let maxrerun = jobs.column_by_name("maxrerun").unwrap()
+            .as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(maxrerun.value(0), 3);
+        assert!(maxrerun.is_null(1));
+
+        let conditions = read_batch(&dir.join(CONDITIONS_FILE));
+        assert_eq!(conditions.num_rows(), 2);
+    }
+
+    #[test]
+    fn test_sub_folder_jobs_keep_the_top_level_datacenter() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+
+        let mut sub_folder = Folder::new("SUB".to_string(), FolderType::Simple);
+        sub_folder.add_job(Job::new("SUB_JOB".to_string(), "SUB".to_string()));
+        let mut folder = Folder::new("TOP".to_string(), FolderType::Smart);
+        folder.datacenter = Some("DC1".to_string());
+        folder.add_job(Job::new("TOP_JOB".to_string(), "TOP".to_string()));
+        folder.sub_folders.push(sub_folder);
+
+        ParquetExporter::new(dir).unwrap().export_folders(&[folder]).unwrap();
+
+        let jobs = read_batch(&dir.join(JOBS_FILE));
+        let datacenter = jobs.column_by_name("datacenter").unwrap()
+            .as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(datacenter.value(0), "DC1");
+        assert_eq!(datacenter.value(1), "DC1");
+    }
+
+    #[test]
+    fn test_export_rejects_folders_nested_too_deep() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+
+        let mut inner = Folder::new("INNER".to_string(), FolderType::Simple);
+        inner.sub_folders.push(Folder::new("INNERMOST".to_string(), FolderType::Simple));
+        let mut outer = Folder::new("OUTER".to_string(), FolderType::Smart);
+        outer.sub_folders.push(inner);
+
+        let exporter = ParquetExporter::new(dir).unwrap().with_max_folder_depth(2);
+        let error = exporter.export_folders(&[outer]).unwrap_err();
+        assert!(error.to_string().contains("INNERMOST"));
+    }
+}
@@ -6,14 +6,46 @@
 
 use anyhow::{Context, Result};
 use rusqlite::{Connection, params, Transaction};
+use std::collections::{HashMap, VecDeque};
 use std::path::Path;
+use std::str::FromStr;
+use crate::application::services::DependencyAnalyzer;
+use crate::application::use_cases::calculate_complexity::JobComplexityResult;
 use crate::domain::entities::*;
+use crate::domain::value_objects::{AnalysisProfile, FileTransferSpec, TokenizedCommand};
 
 /// Type alias for progress callback function
 ///
 /// Callbacks receive progress messages during export operations
 pub type ProgressCallback = Box<dyn Fn(&str)>;
 
+/// How an export treats rows already in the database
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportMode {
+    /// Adds every folder and job; re-exporting a folder already stored with
+    /// the same (folder_name, datacenter) fails
+    #[default]
+    Append,
+    /// Deletes all existing folders and jobs first
+    Replace,
+    /// Replaces folders with the same (folder_name, datacenter) and jobs with
+    /// the same (folder_name, job_name); other rows are kept
+    Upsert,
+}
+
+impl FromStr for ExportMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "append" => Ok(ExportMode::Append),
+            "replace" => Ok(ExportMode::Replace),
+            "upsert" => Ok(ExportMode::Upsert),
+            other => Err(format!("Unknown export mode '{}' (expected replace, append or upsert)", other)),
+        }
+    }
+}
+
 /// SQLite database exporter for Control-M job definitions
 ///
 /// Exports folders, jobs, and all related entities (conditions, resources, variables)
@@ -26,6 +58,17 @@ pub struct SqliteExporter {
     progress_callback: Option<ProgressCallback>,
     /// Counter for tracking exported jobs (used for throttled progress reporting)
     job_counter: std::cell::Cell<usize>,
+    /// Highest job id stored before the running export; upserts only replace
+    /// jobs up to it, so versions inserted by the same export are kept
+    preexisting_max_job_id: std::cell::Cell<i64>,
+    /// Maximum folder nesting depth before export is aborted
+    max_folder_depth: usize,
+    /// Whether to store each job's cmdline split into program and arguments
+    tokenize_cmdline: bool,
+    /// Settings the stored job analysis was computed with
+    analysis_profile: AnalysisProfile,
+    /// Complexity and wave of each job, computed by the caller
+    job_analysis: Vec<JobComplexityResult>,
 }
 
 impl SqliteExporter {
@@ -59,6 +102,7 @@ impl SqliteExporter {
             PRAGMA synchronous = NORMAL;      -- Balance between safety and speed
             PRAGMA cache_size = 10000;        -- Larger cache for better performance
             PRAGMA temp_store = MEMORY;       -- Store temp tables in memory
+            PRAGMA foreign_keys = ON;         -- Reject child rows without a parent job
             "#
         )?;
         
@@ -66,6 +110,11 @@ impl SqliteExporter {
             conn,
             progress_callback: None,
             job_counter: std::cell::Cell::new(0),
+            preexisting_max_job_id: std::cell::Cell::new(0),
+            max_folder_depth: DEFAULT_MAX_FOLDER_DEPTH,
+            tokenize_cmdline: false,
+            analysis_profile: AnalysisProfile::default(),
+            job_analysis: Vec::new(),
         };
         exporter.create_schema()?;
         
@@ -91,6 +140,77 @@ impl SqliteExporter {
         self
     }
 
+    /// Sets the maximum folder nesting depth
+    ///
+    /// Exporting a folder tree deeper than this limit fails with an error
+    /// instead of recursing without bound.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_folder_depth` - Maximum allowed nesting depth (top-level folders are depth 1)
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn with_max_folder_depth(mut self, max_folder_depth: usize) -> Self {
+        self.max_folder_depth = max_folder_depth;
+        self
+    }
+
+    /// Enables storing tokenized command lines
+    ///
+    /// Each job's cmdline is split into program and arguments, using Unix or
+    /// Windows quoting depending on the folder platform, and stored as JSON
+    /// in the `cmdline_tokens` column.
+    ///
+    /// # Arguments
+    ///
+    /// * `tokenize_cmdline` - Whether to store tokenized command lines
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn with_cmdline_tokens(mut self, tokenize_cmdline: bool) -> Self {
+        self.tokenize_cmdline = tokenize_cmdline;
+        self
+    }
+
+    /// Sets the analysis profile the stored job analysis was computed with
+    ///
+    /// The profile is stored in the `analysis_profile` table so the results
+    /// can be traced back to the settings that produced them.
+    ///
+    /// # Arguments
+    ///
+    /// * `analysis_profile` - Complexity weights and other analysis settings
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn with_analysis_profile(mut self, analysis_profile: AnalysisProfile) -> Self {
+        self.analysis_profile = analysis_profile;
+        self
+    }
+
+    /// Sets the job analysis stored in the `job_analysis` table
+    ///
+    /// The exporter does not analyze jobs itself; run `AnalyzeJobs` with the
+    /// analysis profile and pass its complexity results here. Without them
+    /// no `job_analysis` rows are written.
+    ///
+    /// # Arguments
+    ///
+    /// * `job_analysis` - Complexity results, matched by folder and job name to
+    ///   the job rows the export inserts
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn with_job_analysis(mut self, job_analysis: Vec<JobComplexityResult>) -> Self {
+        self.job_analysis = job_analysis;
+        self
+    }
+
     /// Reports progress to the callback if one is set
     ///
     /// # Arguments
@@ -127,6 +247,9 @@ impl SqliteExporter {
     /// - Comprehensive indexes for query performance
     /// - Foreign key constraints with CASCADE delete
     ///
+    /// An existing database keeps its tables; `jobs` columns it lacks are
+    /// added before the indexes are created.
+    ///
     /// # Returns
     ///
     /// Result indicating success or error
@@ -176,11 +299,13 @@ impl SqliteExporter {
                 owner TEXT,
                 run_as TEXT,
                 priority TEXT,
+                priority_num INTEGER,
                 critical INTEGER DEFAULT 0,
                 task_type TEXT,
                 cyclic INTEGER DEFAULT 0,
                 node_id TEXT,
                 cmdline TEXT,
+                cmdline_tokens TEXT,
                 jobisn INTEGER,
                 job_group TEXT,
                 memname TEXT,
@@ -264,6 +389,8 @@ impl SqliteExporter {
                 fprocs TEXT,
                 tpgms TEXT,
                 tprocs TEXT,
+                -- Longest chain of upstream jobs, set once all jobs are exported
+                dependency_depth INTEGER NOT NULL DEFAULT 0,
                 created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
                 UNIQUE(job_name, folder_name, datacenter, jobisn)
             );
@@ -384,6 +511,17 @@ impl SqliteExporter {
                 FOREIGN KEY (job_id) REFERENCES jobs(id) ON DELETE CASCADE
             );
 
+            -- Files each job transfers or watches (direction: watch, upload, download)
+            CREATE TABLE IF NOT EXISTS job_file_transfers (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                job_id INTEGER NOT NULL,
+                direction TEXT NOT NULL,
+                path TEXT NOT NULL,
+                remote_path TEXT,
+                host TEXT,
+                FOREIGN KEY (job_id) REFERENCES jobs(id) ON DELETE CASCADE
+            );
+
             -- Metadata table (for additional job metadata)
             CREATE TABLE IF NOT EXISTS job_metadata (
                 id INTEGER PRIMARY KEY AUTOINCREMENT,
@@ -393,6 +531,49 @@ impl SqliteExporter {
                 FOREIGN KEY (job_id) REFERENCES jobs(id) ON DELETE CASCADE
             );
 
+            -- Analysis results (complexity, migration wave and connectivity per job)
+            CREATE TABLE IF NOT EXISTS job_analysis (
+                job_id INTEGER PRIMARY KEY,
+                complexity_score INTEGER NOT NULL,
+                migration_difficulty TEXT NOT NULL,
+                migration_wave INTEGER NOT NULL,
+                total_dependencies_e2e INTEGER NOT NULL DEFAULT 0,
+                FOREIGN KEY (job_id) REFERENCES jobs(id) ON DELETE CASCADE
+            );
+
+            -- Resolved job-to-job dependencies (rebuilt on every export)
+            -- edge_type: 'condition' when a job sets the awaited condition,
+            -- 'name' when the condition only resembles a job name
+            CREATE TABLE IF NOT EXISTS job_dependencies (
+                from_job_id INTEGER NOT NULL,
+                to_job_id INTEGER NOT NULL,
+                condition_name TEXT NOT NULL,
+                edge_type TEXT NOT NULL,
+                PRIMARY KEY (from_job_id, to_job_id, condition_name),
+                FOREIGN KEY (from_job_id) REFERENCES jobs(id) ON DELETE CASCADE,
+                FOREIGN KEY (to_job_id) REFERENCES jobs(id) ON DELETE CASCADE
+            );
+
+            -- Settings that produced job_analysis (one row, replaced on every export)
+            CREATE TABLE IF NOT EXISTS analysis_profile (
+                id INTEGER PRIMARY KEY CHECK (id = 1),
+                profile_json TEXT NOT NULL,
+                fingerprint TEXT NOT NULL,
+                stored_at TEXT NOT NULL
+            );
+
+            -- Full-text index over jobs, rebuilt on every export
+            CREATE VIRTUAL TABLE IF NOT EXISTS jobs_fts USING fts5(
+                job_name, description, cmdline,
+                content = 'jobs', content_rowid = 'id'
+            );
+            "#
+        ).context("Failed to create database schema")?;
+
+        self.add_missing_job_columns()?;
+
+        self.conn.execute_batch(
+            r#"
             -- Create indexes for better query performance
             
             -- Folder indexes
@@ -405,12 +586,16 @@ impl SqliteExporter {
             CREATE INDEX IF NOT EXISTS idx_jobs_folder ON jobs(folder_name);
             CREATE INDEX IF NOT EXISTS idx_jobs_application ON jobs(application);
             CREATE INDEX IF NOT EXISTS idx_jobs_critical ON jobs(critical);
+            CREATE INDEX IF NOT EXISTS idx_jobs_priority_num ON jobs(priority_num);
             CREATE INDEX IF NOT EXISTS idx_jobs_cyclic ON jobs(cyclic);
             CREATE INDEX IF NOT EXISTS idx_jobs_appl_type ON jobs(appl_type);
             CREATE INDEX IF NOT EXISTS idx_jobs_appl_ver ON jobs(appl_ver);
             CREATE INDEX IF NOT EXISTS idx_jobs_task_type ON jobs(task_type);
             CREATE INDEX IF NOT EXISTS idx_jobs_cmdline ON jobs(cmdline);
             CREATE INDEX IF NOT EXISTS idx_jobs_owner ON jobs(owner);
+            CREATE INDEX IF NOT EXISTS idx_job_analysis_wave ON job_analysis(migration_wave, complexity_score);
+            CREATE INDEX IF NOT EXISTS idx_job_analysis_e2e ON job_analysis(total_dependencies_e2e);
+            CREATE INDEX IF NOT EXISTS idx_jobs_run_as ON jobs(run_as);
             CREATE INDEX IF NOT EXISTS idx_jobs_jobisn ON jobs(jobisn);
             CREATE INDEX IF NOT EXISTS idx_jobs_group ON jobs(job_group);
             CREATE INDEX IF NOT EXISTS idx_jobs_timezone ON jobs(timezone);
@@ -438,7 +623,9 @@ impl SqliteExporter {
             CREATE INDEX IF NOT EXISTS idx_job_scheduling_job ON job_scheduling(job_id);
             CREATE INDEX IF NOT EXISTS idx_job_variables_job ON job_variables(job_id);
             CREATE INDEX IF NOT EXISTS idx_job_auto_edits_job ON job_auto_edits(job_id);
+            CREATE INDEX IF NOT EXISTS idx_job_file_transfers_job ON job_file_transfers(job_id);
             CREATE INDEX IF NOT EXISTS idx_job_metadata_job ON job_metadata(job_id);
+            CREATE INDEX IF NOT EXISTS idx_job_dependencies_to ON job_dependencies(to_job_id);
             
             -- ODATE indexes for performance (composite indexes for ODATE filters)
             CREATE INDEX IF NOT EXISTS idx_in_conditions_odate ON in_conditions(job_id, odate);
@@ -460,11 +647,40 @@ impl SqliteExporter {
             CREATE INDEX IF NOT EXISTS idx_jobs_datacenter ON jobs(datacenter);
             CREATE INDEX IF NOT EXISTS idx_folders_datacenter_order ON folders(datacenter, folder_order_method);
             "#
-        ).context("Failed to create database schema")?;
+        ).context("Failed to create database indexes")?;
 
         Ok(())
     }
 
+    /// Adds the `jobs` columns a database created by an earlier version lacks
+    ///
+    /// `CREATE TABLE IF NOT EXISTS` keeps an existing `jobs` table as it is,
+    /// so columns introduced later are added here before they are indexed or
+    /// written, and backfilled from the existing rows where possible.
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or error
+    fn add_missing_job_columns(&self) -> Result<()> {
+        for (column, definition, backfill) in ADDED_JOB_COLUMNS {
+            let exists: bool = self.conn.query_row(
+                "SELECT EXISTS (SELECT 1 FROM pragma_table_info('jobs') WHERE name = ?)",
+                [column],
+                |row| row.get(0),
+            )?;
+            if exists {
+                continue;
+            }
+            self.conn.execute_batch(&format!("ALTER TABLE jobs ADD COLUMN {} {}", column, definition))
+                .with_context(|| format!("Failed to add column {} to jobs", column))?;
+            if let Some(backfill) = backfill {
+                self.conn.execute_batch(backfill)
+                    .with_context(|| format!("Failed to backfill column {} of jobs", column))?;
+            }
+        }
+        Ok(())
+    }
+
     /// Exports folders and all their jobs to the database
     ///
     /// Exports all folders recursively, including jobs and sub-folders,
@@ -478,16 +694,72 @@ impl SqliteExporter {
     ///
     /// Result indicating success or error
     pub fn export_folders(&self, folders: &[Folder]) -> Result<()> {
+        self.export_folders_with_mode(folders, ExportMode::Append)
+    }
+
+    /// Exports folders, updating previously exported folders and jobs in place
+    ///
+    /// Each job replaces the job with the same folder and job name (and all
+    /// its conditions, resources and analysis rows); each folder replaces the
+    /// folder with the same name and datacenter. Use this to refresh a
+    /// database after a Control-M change.
+    ///
+    /// # Arguments
+    ///
+    /// * `folders` - Slice of Folder entities to export
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or error
+    pub fn export_folders_upsert(&self, folders: &[Folder]) -> Result<()> {
+        self.export_folders_with_mode(folders, ExportMode::Upsert)
+    }
+
+    /// Exports folders, treating existing rows as `mode` says
+    ///
+    /// All deletes and inserts happen in one transaction, so a failed export
+    /// leaves the database unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `folders` - Slice of Folder entities to export
+    /// * `mode` - Whether to append, replace everything, or upsert
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or error
+    pub fn export_folders_with_mode(&self, folders: &[Folder], mode: ExportMode) -> Result<()> {
         self.report_progress("Starting export...");
         
         // Use transaction for all exports (atomic and faster)
         let tx = self.conn.unchecked_transaction()?;
+
+        if mode == ExportMode::Replace {
+            self.report_progress("🧹 Removing previously exported folders and jobs...");
+            // Child rows of jobs are removed by ON DELETE CASCADE
+            tx.execute_batch("DELETE FROM jobs; DELETE FROM folders;")
+                .context("Failed to clear previous export")?;
+        }
+        self.preexisting_max_job_id.set(
+            tx.query_row("SELECT COALESCE(MAX(id), 0) FROM jobs", [], |row| row.get(0))?
+        );
         
         for (idx, folder) in folders.iter().enumerate() {
             self.report_progress(&format!("📁 Exporting folder {}/{}: {}", 
                 idx + 1, folders.len(), folder.folder_name));
-            self.export_folder_tx(&tx, folder)?;
+            self.export_folder_tx(&tx, folder, 1, mode)?;
         }
+
+        self.report_progress("🔗 Resolving job dependencies...");
+        self.export_job_dependencies_tx(&tx)?;
+
+        self.report_progress("🔎 Building full-text search index...");
+        // Rebuilding covers jobs deleted by replace and upsert exports too
+        tx.execute("INSERT INTO jobs_fts(jobs_fts) VALUES ('rebuild')", [])
+            .context("Failed to build full-text search index")?;
+
+        self.report_progress("🧮 Storing complexity and migration waves...");
+        self.export_job_analysis_tx(&tx)?;
         
         self.report_progress("💾 Committing to database...");
         tx.commit()?;
@@ -496,6 +768,160 @@ impl SqliteExporter {
         Ok(())
     }
 
+    /// Resolves every in-condition to the jobs it waits for and stores the edges
+    ///
+    /// An in-condition depends on every other job setting the same condition,
+    /// as matched by `DependencyAnalyzer::producer_map` over the stored jobs
+    /// and out-conditions. Conditions no job sets fall back to the job named like the condition,
+    /// with `-ENDED-OK`-style suffixes stripped. The table is rebuilt from all
+    /// rows in the database, so exports appended to an existing file stay linked.
+    ///
+    /// # Arguments
+    ///
+    /// * `tx` - Active database transaction
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or error
+    fn export_job_dependencies_tx(&self, tx: &Transaction) -> Result<()> {
+        let mut job_ids = Vec::new();
+        let mut jobs = Vec::new();
+        let mut positions: HashMap<i64, usize> = HashMap::new();
+        let mut jobs_by_name: HashMap<String, Vec<i64>> = HashMap::new();
+        let mut stmt = tx.prepare("SELECT id, job_name, folder_name FROM jobs ORDER BY id")?;
+        for row in stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?)))? {
+            let (job_id, job_name, folder_name) = row?;
+            positions.insert(job_id, jobs.len());
+            jobs_by_name.entry(job_name.clone()).or_default().push(job_id);
+            job_ids.push(job_id);
+            jobs.push(Job::new(job_name, folder_name));
+        }
+
+        let mut stmt = tx.prepare("SELECT job_id, condition_name, sign FROM out_conditions ORDER BY id")?;
+        for row in stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?, row.get::<_, Option<String>>(2)?)))? {
+            let (job_id, condition, sign) = row?;
+            if let Some(&position) = positions.get(&job_id) {
+                let mut out_cond = Condition::new_out(condition);
+                out_cond.sign = sign;
+                jobs[position].out_conditions.push(out_cond);
+            }
+        }
+
+        let job_refs: Vec<&Job> = jobs.iter().collect();
+        let producers: HashMap<&str, Vec<i64>> = DependencyAnalyzer::producer_map(&job_refs).into_iter()
+            .map(|(condition, producers)| (condition, producers.into_iter().map(|p| job_ids[p]).collect()))
+            .collect();
+
+        let mut stmt = tx.prepare("SELECT DISTINCT job_id, condition_name FROM in_conditions")?;
+        let in_conditions: Vec<(i64, String)> = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+
+        tx.execute("DELETE FROM job_dependencies", [])?;
+        let mut insert = tx.prepare_cached(
+            "INSERT OR IGNORE INTO job_dependencies (from_job_id, to_job_id, condition_name, edge_type) VALUES (?1, ?2, ?3, ?4)"
+        )?;
+        for (job_id, condition) in &in_conditions {
+            let (from_ids, edge_type): (Vec<i64>, &str) = match producers.get(condition.as_str()) {
+                Some(producer_ids) => (producer_ids.clone(), "condition"),
+                None => {
+                    let mut ids = jobs_by_name.get(condition).cloned().unwrap_or_default();
+                    ids.extend(jobs_by_name.get(condition_base_name(condition)).into_iter().flatten());
+                    (ids, "name")
+                }
+            };
+            for from_id in from_ids.into_iter().filter(|id| id != job_id) {
+                insert.execute(params![from_id, job_id, condition, edge_type])
+                    .context("Failed to insert job dependency")?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Stores the job analysis given to the exporter with the end-to-end
+    /// dependency count, and the dependency depth of every stored job
+    ///
+    /// Both are computed from the persisted `job_dependencies` edges, with
+    /// jobs identified by row id, so jobs sharing a name across datacenters
+    /// or versions are kept apart and jobs from earlier exports count too.
+    /// The end-to-end count is the number of jobs transitively upstream or
+    /// downstream of a job; the depth is the length of the longest chain of
+    /// upstream jobs (0 for jobs waiting for nothing, one shared depth for
+    /// jobs in a cycle), taken from the topological levels so large graphs
+    /// stay linear. Analysis rows are keyed by the row ids this export
+    /// inserted, each result taking the next inserted job with its folder and
+    /// job name, so rows stored by earlier exports keep their analysis. The
+    /// analysis profile is stored alongside.
+    ///
+    /// # Arguments
+    ///
+    /// * `tx` - Active database transaction
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or error
+    fn export_job_analysis_tx(&self, tx: &Transaction) -> Result<()> {
+        let profile = &self.analysis_profile;
+        let mut dependencies = DependencyAnalyzer::new();
+        let mut stmt = tx.prepare("SELECT id FROM jobs ORDER BY id")?;
+        let job_ids: Vec<i64> = stmt.query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+        for job_id in &job_ids {
+            dependencies.add_job(&job_id.to_string());
+        }
+        let mut stmt = tx.prepare("SELECT from_job_id, to_job_id, condition_name FROM job_dependencies ORDER BY rowid")?;
+        for row in stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?, row.get::<_, String>(2)?)))? {
+            let (from_id, to_id, condition) = row?;
+            dependencies.add_dependency(&from_id.to_string(), &to_id.to_string(), &condition);
+        }
+
+        let mut inserted: HashMap<(String, String), VecDeque<i64>> = HashMap::new();
+        let mut stmt = tx.prepare("SELECT id, folder_name, job_name FROM jobs WHERE id > ?1 ORDER BY id")?;
+        for row in stmt.query_map([self.preexisting_max_job_id.get()], |row| Ok((row.get::<_, i64>(0)?, row.get(1)?, row.get(2)?)))? {
+            let (job_id, folder_name, job_name) = row?;
+            inserted.entry((folder_name, job_name)).or_default().push_back(job_id);
+        }
+
+        let mut stmt = tx.prepare_cached(
+            r#"
+            INSERT OR REPLACE INTO job_analysis (job_id, complexity_score, migration_difficulty, migration_wave, total_dependencies_e2e)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            "#,
+        )?;
+        for result in &self.job_analysis {
+            let Some(job_id) = inserted.get_mut(&(result.folder_name.clone(), result.job_name.clone()))
+                .and_then(VecDeque::pop_front)
+            else {
+                continue;
+            };
+            stmt.execute(params![
+                job_id,
+                result.complexity_score.value(),
+                result.migration_difficulty.to_string(),
+                result.migration_wave as i64,
+                dependencies.count_e2e_dependencies(&job_id.to_string()) as i64,
+            ]).context("Failed to insert job analysis")?;
+        }
+
+        let levels = dependencies.topological_levels();
+        let mut stmt = tx.prepare_cached("UPDATE jobs SET dependency_depth = ?1 WHERE id = ?2")?;
+        for job_id in &job_ids {
+            let depth = levels.get(&job_id.to_string()).copied().unwrap_or(0);
+            stmt.execute(params![depth as i64, job_id])
+                .context("Failed to store dependency depth")?;
+        }
+
+        let profile_json = serde_json::to_string(profile)
+            .context("Failed to serialize analysis profile")?;
+        tx.execute(
+            "INSERT OR REPLACE INTO analysis_profile (id, profile_json, fingerprint, stored_at) VALUES (1, ?1, ?2, ?3)",
+            params![profile_json, profile.fingerprint(), chrono::Utc::now().to_rfc3339()],
+        ).context("Failed to store analysis profile")?;
+
+        Ok(())
+    }
+
     /// Exports a single folder within a transaction
     ///
     /// Recursively exports the folder, all its jobs, and sub-folders.
@@ -504,18 +930,33 @@ impl SqliteExporter {
     ///
     /// * `tx` - Active database transaction
     /// * `folder` - Folder entity to export
+    /// * `depth` - Nesting depth of this folder (top-level folders are depth 1)
+    /// * `mode` - How existing rows with the same key are treated
     ///
     /// # Returns
     ///
     /// Result indicating success or error
-    fn export_folder_tx(&self, tx: &Transaction, folder: &Folder) -> Result<()> {
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `depth` exceeds the configured maximum folder depth
+    fn export_folder_tx(&self, tx: &Transaction, folder: &Folder, depth: usize, mode: ExportMode) -> Result<()> {
+        if depth > self.max_folder_depth {
+            anyhow::bail!(
+                "Folder '{}' exceeds maximum nesting depth of {}",
+                folder.folder_name,
+                self.max_folder_depth
+            );
+        }
+
         // Convert folder type enum to string for database storage
-        let folder_type_str = match folder.folder_type {
-            FolderType::Simple => "Simple",
-            FolderType::Smart => "Smart",
-            FolderType::Table => "Table",
-            FolderType::SmartTable => "SmartTable",
-        };
+        let folder_type_str = folder_type_name(&folder.folder_type);
+
+        if mode == ExportMode::Upsert {
+            tx.prepare_cached("DELETE FROM folders WHERE folder_name = ?1 AND datacenter IS ?2")?
+                .execute(params![&folder.folder_name, &folder.datacenter])
+                .context("Failed to replace folder")?;
+        }
 
         tx.execute(
             r#"
@@ -556,12 +997,12 @@ impl SqliteExporter {
 
         // Export all jobs in this folder
         for job in &folder.jobs {
-            self.export_job_tx(tx, folder, job)?;
+            self.export_job_tx(tx, folder, job, mode)?;
         }
 
         // Recursively export sub-folders
         for sub_folder in &folder.sub_folders {
-            self.export_folder_tx(tx, sub_folder)?;
+            self.export_folder_tx(tx, sub_folder, depth + 1, mode)?;
         }
 
         Ok(())
@@ -577,84 +1018,46 @@ impl SqliteExporter {
     /// * `tx` - Active database transaction
     /// * `folder` - Parent folder entity (for datacenter info)
     /// * `job` - Job entity to export
+    /// * `mode` - How an existing job with the same key is treated
     ///
     /// # Returns
     ///
     /// Result indicating success or error
-    fn export_job_tx(&self, tx: &Transaction, folder: &Folder, job: &Job) -> Result<()> {
+    fn export_job_tx(&self, tx: &Transaction, folder: &Folder, job: &Job, mode: ExportMode) -> Result<()> {
         // Increment job counter for progress reporting
         let count = self.job_counter.get() + 1;
         self.job_counter.set(count);
         
         // Throttled progress reporting (every 10 jobs to reduce overhead)
         self.report_progress_throttled(&format!("Job: {}", job.job_name), false);
+
+        if mode == ExportMode::Upsert {
+            // Child rows of the previous job are removed by ON DELETE CASCADE
+            tx.prepare_cached(
+                "DELETE FROM jobs WHERE folder_name = ?1 AND job_name = ?2 AND datacenter IS ?3 AND id <= ?4"
+            )?
+                .execute(params![&job.folder_name, &job.job_name, &folder.datacenter, self.preexisting_max_job_id.get()])
+                .with_context(|| format!("Failed to replace job {}", job.job_name))?;
+        }
         
-        tx.prepare_cached(
-            r#"
-            INSERT INTO jobs (
-                job_name, folder_name, datacenter, application, sub_application, appl_type, appl_ver,
-                description, owner, run_as, priority, critical, task_type, cyclic,
-                node_id, cmdline, jobisn, job_group, memname, author, doclib, docmem,
-                job_interval, override_path, overlib, memlib, confirm, retro, maxwait,
-                maxrerun, autoarch, maxdays, maxruns, days, weekdays, jan, feb, mar,
-                apr, may, jun, jul, aug, sep, oct, nov, dec, date, rerunmem,
-                days_and_or, category, shift, shiftnum, pdsname, minimum, preventnct2,
-                option_field, from_field, par, sysdb, due_out, reten_days, reten_gen,
-                task_class, prev_day, adjust_cond, jobs_in_group, large_size, ind_cyclic,
-                creation_user, creation_time, created_by, creation_date, change_userid,
-                change_date, change_time, job_version, version_opcode, is_current_version,
-                version_serial, version_host, rule_based_calendar_relationship,
-                tag_relationship, timezone, appl_form, cm_ver, multy_agent, active_from,
-                active_till, scheduling_environment, system_affinity, request_nje_node,
-                stat_cal, instream_jcl, use_instream_jcl, due_out_daysoffset,
-                from_daysoffset, to_daysoffset, cyclic_interval_sequence,
-                cyclic_times_sequence, cyclic_tolerance, cyclic_type, parent_folder,
-                parent_table, end_folder, odate, fprocs, tpgms, tprocs
-            ) VALUES (
-                ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15,
-                ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28,
-                ?29, ?30, ?31, ?32, ?33, ?34, ?35, ?36, ?37, ?38, ?39, ?40, ?41,
-                ?42, ?43, ?44, ?45, ?46, ?47, ?48, ?49, ?50, ?51, ?52, ?53, ?54,
-                ?55, ?56, ?57, ?58, ?59, ?60, ?61, ?62, ?63, ?64, ?65, ?66, ?67,
-                ?68, ?69, ?70, ?71, ?72, ?73, ?74, ?75, ?76, ?77, ?78, ?79, ?80,
-                ?81, ?82, ?83, ?84, ?85, ?86, ?87, ?88, ?89, ?90, ?91, ?92, ?93,
-                ?94, ?95, ?96, ?97, ?98, ?99, ?100, ?101, ?102, ?103, ?104, ?105,
-                ?106, ?107, ?108, ?109
-            )
-            "#,
-        )?
-        .execute(params![
-            &job.job_name, &job.folder_name, &folder.datacenter, &job.application, &job.sub_application,
-            &job.appl_type, &job.appl_ver, &job.description, &job.owner, &job.run_as,
-            &job.priority, if job.critical { 1 } else { 0 }, &job.task_type,
-            if job.cyclic { 1 } else { 0 }, &job.node_id, &job.cmdline, &job.jobisn,
-            &job.group, &job.memname, &job.author, &job.doclib, &job.docmem,
-            &job.interval, &job.override_path, &job.overlib, &job.memlib, &job.confirm,
-            &job.retro, &job.maxwait, &job.maxrerun, &job.autoarch, &job.maxdays,
-            &job.maxruns, &job.days, &job.weekdays, &job.jan, &job.feb, &job.mar,
-            &job.apr, &job.may, &job.jun, &job.jul, &job.aug, &job.sep, &job.oct,
-            &job.nov, &job.dec, &job.date, &job.rerunmem, &job.days_and_or,
-            &job.category, &job.shift, &job.shiftnum, &job.pdsname, &job.minimum,
-            &job.preventnct2, &job.option, &job.from, &job.par, &job.sysdb,
-            &job.due_out, &job.reten_days, &job.reten_gen, &job.task_class,
-            &job.prev_day, &job.adjust_cond, &job.jobs_in_group, &job.large_size,
-            &job.ind_cyclic, &job.creation_user, &job.creation_time, &job.created_by,
-            &job.creation_date, &job.change_userid, &job.change_date, &job.change_time,
-            &job.job_version, &job.version_opcode, &job.is_current_version,
-            &job.version_serial, &job.version_host, &job.rule_based_calendar_relationship,
-            &job.tag_relationship, &job.timezone, &job.appl_form, &job.cm_ver,
-            &job.multy_agent, &job.active_from, &job.active_till,
-            &job.scheduling_environment, &job.system_affinity, &job.request_nje_node,
-            &job.stat_cal, &job.instream_jcl, &job.use_instream_jcl,
-            &job.due_out_daysoffset, &job.from_daysoffset, &job.to_daysoffset,
-            &job.cyclic_interval_sequence, &job.cyclic_times_sequence,
-            &job.cyclic_tolerance, &job.cyclic_type, &job.parent_folder,
-            &job.parent_table, &job.end_folder, &job.odate, &job.fprocs, &job.tpgms,
-            &job.tprocs,
-        ])?;
+        let attributes: Vec<JobValue> = JOB_FIELDS.iter().map(|(_, field)| field.read(job)).collect();
+        let identity: [&dyn rusqlite::ToSql; 3] = [&job.job_name, &job.folder_name, &folder.datacenter];
+        tx.prepare_cached(insert_job_sql())?
+            .execute(rusqlite::params_from_iter(
+                identity.into_iter().chain(attributes.iter().map(|value| value as &dyn rusqlite::ToSql))
+            ))?;
 
         let job_id = tx.last_insert_rowid();
 
+        if self.tokenize_cmdline {
+            let tokens = job.cmdline.as_deref()
+                .and_then(|cmdline| TokenizedCommand::parse(cmdline, folder.platform.as_deref()));
+            if let Some(tokens) = tokens {
+                tx.prepare_cached("UPDATE jobs SET cmdline_tokens = ?1 WHERE id = ?2")?
+                    .execute(params![serde_json::to_string(&tokens)?, job_id])?;
+            }
+        }
+
         self.export_job_scheduling_tx(tx, job_id, &job.scheduling)?;
         self.export_in_conditions_tx(tx, job_id, &job.in_conditions)?;
         self.export_out_conditions_tx(tx, job_id, &job.out_conditions)?;
@@ -663,6 +1066,7 @@ impl SqliteExporter {
         self.export_quantitative_resources_tx(tx, job_id, &job.quantitative_resources)?;
         self.export_variables_tx(tx, job_id, &job.variables)?;
         self.export_auto_edits_tx(tx, job_id, &job.auto_edits)?;
+        self.export_file_transfers_tx(tx, job_id, &job.file_transfers)?;
         self.export_metadata_tx(tx, job_id, &job.metadata)?;
 
         Ok(())
@@ -786,7 +1190,7 @@ impl SqliteExporter {
 
         // Use prepared statement for better performance
         let mut stmt = tx.prepare_cached(
-            "INSERT INTO out_conditions (job_id, condition_name, odate) VALUES (?1, ?2, ?3)"
+            "INSERT INTO out_conditions (job_id, condition_name, odate, sign) VALUES (?1, ?2, ?3, ?4)"
         )?;
 
         for condition in conditions {
@@ -795,6 +1199,7 @@ impl SqliteExporter {
                     job_id,
                     &condition.name,
                     &condition.odate,
+                    &condition.sign,
                 ]).context("Failed to insert out condition")?;
             }
         }
@@ -1018,6 +1423,33 @@ impl SqliteExporter {
         Ok(())
     }
 
+    /// Exports the files a job transfers or watches within a transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `tx` - Active database transaction
+    /// * `job_id` - ID of the parent job
+    /// * `file_transfers` - Files detected at parse time
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or error
+    fn export_file_transfers_tx(&self, tx: &Transaction, job_id: i64, file_transfers: &[FileTransferSpec]) -> Result<()> {
+        if file_transfers.is_empty() {
+            return Ok(());
+        }
+
+        let mut stmt = tx.prepare_cached(
+            "INSERT INTO job_file_transfers (job_id, direction, path, remote_path, host) VALUES (?1, ?2, ?3, ?4, ?5)"
+        )?;
+
+        for spec in file_transfers {
+            stmt.execute(params![job_id, spec.direction.to_string(), &spec.path, &spec.remote_path, &spec.host])
+                .context("Failed to insert file transfer")?;
+        }
+        Ok(())
+    }
+
     /// Exports job metadata within a transaction
     ///
     /// Metadata stores additional key-value pairs for jobs.
@@ -1095,6 +1527,223 @@ impl SqliteExporter {
     }
 }
 
+/// Returns the storage name of a folder type
+///
+/// Shared by the database exporters so folder types are stored consistently.
+///
+/// # Arguments
+///
+/// * `folder_type` - Folder type to convert
+///
+/// # Returns
+///
+/// The folder type name used in exported databases
+pub(crate) fn folder_type_name(folder_type: &FolderType) -> &'static str {
+    match folder_type {
+        FolderType::Simple => "Simple",
+        FolderType::Smart => "Smart",
+        FolderType::Table => "Table",
+        FolderType::SmartTable => "SmartTable",
+    }
+}
+
+/// How a job attribute is read for a database row
+pub(crate) enum JobField {
+    /// Optional text attribute
+    Text(fn(&Job) -> Option<&str>),
+    /// Optional integer attribute
+    Int(fn(&Job) -> Option<i32>),
+    /// Yes/no attribute
+    Flag(fn(&Job) -> bool),
+}
+
+impl JobField {
+    /// Reads this attribute from a job
+    pub(crate) fn read<'a>(&self, job: &'a Job) -> JobValue<'a> {
+        match self {
+            JobField::Text(read) => JobValue::Text(read(job)),
+            JobField::Int(read) => JobValue::Int(read(job)),
+            JobField::Flag(read) => JobValue::Flag(read(job)),
+        }
+    }
+}
+
+/// A job attribute value, borrowed from the job
+pub(crate) enum JobValue<'a> {
+    /// Text, NULL if unset
+    Text(Option<&'a str>),
+    /// Integer, NULL if unset
+    Int(Option<i32>),
+    /// Yes/no, stored by SQLite as 1 or 0
+    Flag(bool),
+}
+
+impl rusqlite::ToSql for JobValue<'_> {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        use rusqlite::types::{ToSqlOutput, ValueRef};
+        Ok(ToSqlOutput::Borrowed(match *self {
+            JobValue::Text(Some(text)) => ValueRef::Text(text.as_bytes()),
+            JobValue::Int(Some(value)) => ValueRef::Integer(value as i64),
+            JobValue::Flag(flag) => ValueRef::Integer(if flag { 1 } else { 0 }),
+            JobValue::Text(None) | JobValue::Int(None) => ValueRef::Null,
+        }))
+    }
+}
+
+/// Columns added to the `jobs` table after its first release: (name,
+/// definition, optional statement filling it in for existing rows)
+const ADDED_JOB_COLUMNS: &[(&str, &str, Option<&str>)] = &[
+    (
+        "priority_num",
+        "INTEGER",
+        Some("UPDATE jobs SET priority_num = CAST(TRIM(priority) AS INTEGER) \
+              WHERE TRIM(priority) GLOB '[0-9]*' AND TRIM(priority) NOT GLOB '*[^0-9]*'"),
+    ),
+    ("cmdline_tokens", "TEXT", None),
+    ("dependency_depth", "INTEGER NOT NULL DEFAULT 0", None),
+];
+
+/// Attribute columns of the `jobs` table, after `job_name`, `folder_name`
+/// and `datacenter`
+///
+/// Shared by the database exporters so jobs are mapped to rows the same way.
+pub(crate) const JOB_FIELDS: &[(&str, JobField)] = &[
+    ("application", JobField::Text(|j| j.application.as_deref())),
+    ("sub_application", JobField::Text(|j| j.sub_application.as_deref())),
+    ("appl_type", JobField::Text(|j| j.appl_type.as_deref())),
+    ("appl_ver", JobField::Text(|j| j.appl_ver.as_deref())),
+    ("description", JobField::Text(|j| j.description.as_deref())),
+    ("owner", JobField::Text(|j| j.owner.as_deref())),
+    ("run_as", JobField::Text(|j| j.run_as.as_deref())),
+    ("priority", JobField::Text(|j| j.priority.as_deref())),
+    ("critical", JobField::Flag(|j| j.critical)),
+    ("task_type", JobField::Text(|j| j.task_type.as_deref())),
+    ("cyclic", JobField::Flag(|j| j.cyclic)),
+    ("node_id", JobField::Text(|j| j.node_id.as_deref())),
+    ("cmdline", JobField::Text(|j| j.cmdline.as_deref())),
+    ("jobisn", JobField::Int(|j| j.jobisn)),
+    ("job_group", JobField::Text(|j| j.group.as_deref())),
+    ("memname", JobField::Text(|j| j.memname.as_deref())),
+    ("author", JobField::Text(|j| j.author.as_deref())),
+    ("doclib", JobField::Text(|j| j.doclib.as_deref())),
+    ("docmem", JobField::Text(|j| j.docmem.as_deref())),
+    ("job_interval", JobField::Text(|j| j.interval.as_deref())),
+    ("override_path", JobField::Text(|j| j.override_path.as_deref())),
+    ("overlib", JobField::Text(|j| j.overlib.as_deref())),
+    ("memlib", JobField::Text(|j| j.memlib.as_deref())),
+    ("confirm", JobField::Text(|j| j.confirm.as_deref())),
+    ("retro", JobField::Text(|j| j.retro.as_deref())),
+    ("maxwait", JobField::Int(|j| j.maxwait)),
+    ("maxrerun", JobField::Int(|j| j.maxrerun)),
+    ("autoarch", JobField::Text(|j| j.autoarch.as_deref())),
+    ("maxdays", JobField::Int(|j| j.maxdays)),
+    ("maxruns", JobField::Int(|j| j.maxruns)),
+    ("days", JobField::Text(|j| j.days.as_deref())),
+    ("weekdays", JobField::Text(|j| j.weekdays.as_deref())),
+    ("jan", JobField::Text(|j| j.jan.as_deref())),
+    ("feb", JobField::Text(|j| j.feb.as_deref())),
+    ("mar", JobField::Text(|j| j.mar.as_deref())),
+    ("apr", JobField::Text(|j| j.apr.as_deref())),
+    ("may", JobField::Text(|j| j.may.as_deref())),
+    ("jun", JobField::Text(|j| j.jun.as_deref())),
+    ("jul", JobField::Text(|j| j.jul.as_deref())),
+    ("aug", JobField::Text(|j| j.aug.as_deref())),
+    ("sep", JobField::Text(|j| j.sep.as_deref())),
+    ("oct", JobField::Text(|j| j.oct.as_deref())),
+    ("nov", JobField::Text(|j| j.nov.as_deref())),
+    ("dec", JobField::Text(|j| j.dec.as_deref())),
+    ("date", JobField::Text(|j| j.date.as_deref())),
+    ("rerunmem", JobField::Text(|j| j.rerunmem.as_deref())),
+    ("days_and_or", JobField::Text(|j| j.days_and_or.as_deref())),
+    ("category", JobField::Text(|j| j.category.as_deref())),
+    ("shift", JobField::Text(|j| j.shift.as_deref())),
+    ("shiftnum", JobField::Text(|j| j.shiftnum.as_deref())),
+    ("pdsname", JobField::Text(|j| j.pdsname.as_deref())),
+    ("minimum", JobField::Text(|j| j.minimum.as_deref())),
+    ("preventnct2", JobField::Text(|j| j.preventnct2.as_deref())),
+    ("option_field", JobField::Text(|j| j.option.as_deref())),
+    ("from_field", JobField::Text(|j| j.from.as_deref())),
+    ("par", JobField::Text(|j| j.par.as_deref())),
+    ("sysdb", JobField::Text(|j| j.sysdb.as_deref())),
+    ("due_out", JobField::Text(|j| j.due_out.as_deref())),
+    ("reten_days", JobField::Text(|j| j.reten_days.as_deref())),
+    ("reten_gen", JobField::Text(|j| j.reten_gen.as_deref())),
+    ("task_class", JobField::Text(|j| j.task_class.as_deref())),
+    ("prev_day", JobField::Text(|j| j.prev_day.as_deref())),
+    ("adjust_cond", JobField::Text(|j| j.adjust_cond.as_deref())),
+    ("jobs_in_group", JobField::Text(|j| j.jobs_in_group.as_deref())),
+    ("large_size", JobField::Text(|j| j.large_size.as_deref())),
+    ("ind_cyclic", JobField::Text(|j| j.ind_cyclic.as_deref())),
+    ("creation_user", JobField::Text(|j| j.creation_user.as_deref())),
+    ("creation_time", JobField::Text(|j| j.creation_time.as_deref())),
+    ("created_by", JobField::Text(|j| j.created_by.as_deref())),
+    ("creation_date", JobField::Text(|j| j.creation_date.as_deref())),
+    ("change_userid", JobField::Text(|j| j.change_userid.as_deref())),
+    ("change_date", JobField::Text(|j| j.change_date.as_deref())),
+    ("change_time", JobField::Text(|j| j.change_time.as_deref())),
+    ("job_version", JobField::Text(|j| j.job_version.as_deref())),
+    ("version_opcode", JobField::Text(|j| j.version_opcode.as_deref())),
+    ("is_current_version", JobField::Text(|j| j.is_current_version.as_deref())),
+    ("version_serial", JobField::Int(|j| j.version_serial)),
+    ("version_host", JobField::Text(|j| j.version_host.as_deref())),
+    ("rule_based_calendar_relationship", JobField::Text(|j| j.rule_based_calendar_relationship.as_deref())),
+    ("tag_relationship", JobField::Text(|j| j.tag_relationship.as_deref())),
+    ("timezone", JobField::Text(|j| j.timezone.as_deref())),
+    ("appl_form", JobField::Text(|j| j.appl_form.as_deref())),
+    ("cm_ver", JobField::Text(|j| j.cm_ver.as_deref())),
+    ("multy_agent", JobField::Text(|j| j.multy_agent.as_deref())),
+    ("active_from", JobField::Text(|j| j.active_from.as_deref())),
+    ("active_till", JobField::Text(|j| j.active_till.as_deref())),
+    ("scheduling_environment", JobField::Text(|j| j.scheduling_environment.as_deref())),
+    ("system_affinity", JobField::Text(|j| j.system_affinity.as_deref())),
+    ("request_nje_node", JobField::Text(|j| j.request_nje_node.as_deref())),
+    ("stat_cal", JobField::Text(|j| j.stat_cal.as_deref())),
+    ("instream_jcl", JobField::Text(|j| j.instream_jcl.as_deref())),
+    ("use_instream_jcl", JobField::Text(|j| j.use_instream_jcl.as_deref())),
+    ("due_out_daysoffset", JobField::Text(|j| j.due_out_daysoffset.as_deref())),
+    ("from_daysoffset", JobField::Text(|j| j.from_daysoffset.as_deref())),
+    ("to_daysoffset", JobField::Text(|j| j.to_daysoffset.as_deref())),
+    ("cyclic_interval_sequence", JobField::Text(|j| j.cyclic_interval_sequence.as_deref())),
+    ("cyclic_times_sequence", JobField::Text(|j| j.cyclic_times_sequence.as_deref())),
+    ("cyclic_tolerance", JobField::Int(|j| j.cyclic_tolerance)),
+    ("cyclic_type", JobField::Text(|j| j.cyclic_type.as_deref())),
+    ("parent_folder", JobField::Text(|j| j.parent_folder.as_deref())),
+    ("parent_table", JobField::Text(|j| j.parent_table.as_deref())),
+    ("end_folder", JobField::Text(|j| j.end_folder.as_deref())),
+    ("odate", JobField::Text(|j| j.odate.as_deref())),
+    ("fprocs", JobField::Text(|j| j.fprocs.as_deref())),
+    ("tpgms", JobField::Text(|j| j.tpgms.as_deref())),
+    ("tprocs", JobField::Text(|j| j.tprocs.as_deref())),
+    ("priority_num", JobField::Int(|j| j.priority_value().and_then(|p| i32::try_from(p).ok()))),
+];
+
+/// Gets the statement inserting a job row, built once from `JOB_FIELDS`
+fn insert_job_sql() -> &'static str {
+    static SQL: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+    SQL.get_or_init(|| {
+        let columns: Vec<&str> = JOB_FIELDS.iter().map(|(name, _)| *name).collect();
+        let placeholders: Vec<String> = (1..=columns.len() + 3).map(|i| format!("?{}", i)).collect();
+        format!(
+            "INSERT INTO jobs (job_name, folder_name, datacenter, {}) VALUES ({})",
+            columns.join(", "),
+            placeholders.join(", ")
+        )
+    })
+}
+
+/// Strips the status suffix (`-ENDED-OK`, `-NOTOK`, ...) from a condition name
+///
+/// Conditions are conventionally named after the job that sets them, so the
+/// result is the job name a condition most likely refers to.
+pub(crate) fn condition_base_name(condition: &str) -> &str {
+    condition
+        .trim_end_matches("-ENDED-OK")
+        .trim_end_matches("-ENDED-NOTOK")
+        .trim_end_matches("-ENDED")
+        .trim_end_matches("-OK")
+        .trim_end_matches("-NOTOK")
+}
+
 /// Database statistics structure
 ///
 /// Contains counts of various entities in the exported database.
@@ -1176,4 +1825,264 @@ mod tests {
         let stats = exporter.get_statistics().unwrap();
         assert_eq!(stats.control_resource_count, 1);
     }
+
+    #[test]
+    fn test_job_analysis_is_stored_only_when_given() {
+        use crate::application::use_cases::AnalyzeJobs;
+
+        let mut folder = Folder::new("TEST_FOLDER".to_string(), FolderType::Simple);
+        folder.add_job(Job::new("TEST_JOB".to_string(), "TEST_FOLDER".to_string()));
+        let analysis_rows = |exporter: &SqliteExporter| -> i64 {
+            exporter.conn.query_row("SELECT COUNT(*) FROM job_analysis", [], |row| row.get(0)).unwrap()
+        };
+
+        let exporter = SqliteExporter::new(":memory:").unwrap();
+        exporter.export_folders(std::slice::from_ref(&folder)).unwrap();
+        assert_eq!(analysis_rows(&exporter), 0);
+
+        let analysis = AnalyzeJobs::new().execute(std::slice::from_ref(&folder)).unwrap();
+        let exporter = SqliteExporter::new(":memory:").unwrap()
+            .with_job_analysis(analysis.complexity_results);
+        exporter.export_folders(&[folder]).unwrap();
+        assert_eq!(analysis_rows(&exporter), 1);
+    }
+
+    #[test]
+    fn test_export_rejects_folders_beyond_max_depth() {
+        // Build a 4-level deep folder chain, one level more than the limit
+        let mut folder = Folder::new("LEVEL_4".to_string(), FolderType::Smart);
+        for level in (1..4).rev() {
+            let mut parent = Folder::new(format!("LEVEL_{}", level), FolderType::Smart);
+            parent.add_sub_folder(folder);
+            folder = parent;
+        }
+
+        let exporter = SqliteExporter::new(":memory:").unwrap().with_max_folder_depth(3);
+        let err = exporter.export_folders(std::slice::from_ref(&folder)).unwrap_err();
+        assert!(err.to_string().contains("LEVEL_4"));
+
+        let exporter = SqliteExporter::new(":memory:").unwrap().with_max_folder_depth(4);
+        exporter.export_folders(&[folder]).unwrap();
+    }
+
+    #[test]
+    fn test_job_dependencies_match_condition_graph() {
+        let mut folder = Folder::new("CHAIN".to_string(), FolderType::Simple);
+        for (name, waits_for) in [("EXTRACT", None), ("LOAD", Some("EXTRACT-OK")), ("REPORT", Some("LOAD-OK"))] {
+            let mut job = Job::new(name.to_string(), "CHAIN".to_string());
+            job.out_conditions.push(Condition::new_out(format!("{}-OK", name)));
+            if let Some(condition) = waits_for {
+                job.in_conditions.push(Condition::new_in(condition.to_string()));
+            }
+            folder.add_job(job);
+        }
+        // Deleting a condition doesn't make REPORT a producer of it
+        let mut deleted = Condition::new_out("EXTRACT-OK".to_string());
+        deleted.sign = Some("-".to_string());
+        folder.jobs[2].out_conditions.push(deleted);
+        // No job sets this condition; it resolves to EXTRACT by name only
+        let mut audit = Job::new("AUDIT".to_string(), "CHAIN".to_string());
+        audit.in_conditions.push(Condition::new_in("EXTRACT-ENDED-OK".to_string()));
+        folder.add_job(audit);
+
+        let exporter = SqliteExporter::new(":memory:").unwrap();
+        exporter.export_folders(std::slice::from_ref(&folder)).unwrap();
+
+        let edges_of = |edge_type: &str| -> Vec<(String, String, String)> {
+            let mut stmt = exporter.conn.prepare(
+                "SELECT f.folder_name || '/' || f.job_name, t.folder_name || '/' || t.job_name, d.condition_name
+                 FROM job_dependencies d
+                 JOIN jobs f ON f.id = d.from_job_id JOIN jobs t ON t.id = d.to_job_id
+                 WHERE d.edge_type = ? ORDER BY 1, 2"
+            ).unwrap();
+            stmt.query_map([edge_type], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+                .unwrap().map(|r| r.unwrap()).collect()
+        };
+
+        let jobs = folder.all_jobs();
+        let mut analyzer = DependencyAnalyzer::new();
+        analyzer.build_condition_graph(&jobs);
+        let mut expected = analyzer.get_edges();
+        expected.sort();
+
+        assert_eq!(edges_of("condition"), expected);
+        assert_eq!(expected.len(), 2);
+        assert_eq!(edges_of("name"), vec![("CHAIN/EXTRACT".to_string(), "CHAIN/AUDIT".to_string(), "EXTRACT-ENDED-OK".to_string())]);
+    }
+
+    /// Tests that upserting a re-exported job updates it instead of duplicating it
+    #[test]
+    fn test_upsert_replaces_job_and_its_children() {
+        let export = |exporter: &SqliteExporter, application: &str, mode: ExportMode| {
+            let mut folder = Folder::new("TEST_FOLDER".to_string(), FolderType::Simple);
+            folder.datacenter = Some("DC1".to_string());
+            let mut job = Job::new("TEST_JOB".to_string(), "TEST_FOLDER".to_string());
+            job.application = Some(application.to_string());
+            job.in_conditions.push(Condition::new_in("COND1".to_string()));
+            folder.add_job(job);
+            exporter.export_folders_with_mode(&[folder], mode)
+        };
+        let count = |exporter: &SqliteExporter, table: &str| -> i64 {
+            exporter.conn.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| row.get(0)).unwrap()
+        };
+
+        let exporter = SqliteExporter::new(":memory:").unwrap();
+        export(&exporter, "OLD_APP", ExportMode::Append).unwrap();
+        export(&exporter, "NEW_APP", ExportMode::Upsert).unwrap();
+
+        assert_eq!(count(&exporter, "folders"), 1);
+        assert_eq!(count(&exporter, "jobs"), 1);
+        assert_eq!(count(&exporter, "in_conditions"), 1);
+        assert_eq!(count(&exporter, "job_scheduling"), 1);
+        let application: String = exporter.conn
+            .query_row("SELECT application FROM jobs", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(application, "NEW_APP");
+
+        export(&exporter, "NEW_APP", ExportMode::Replace).unwrap();
+        assert_eq!(count(&exporter, "jobs"), 1);
+        assert!(export(&exporter, "NEW_APP", ExportMode::Append).is_err(), "UNIQUE(folder_name, datacenter) rejects a duplicate folder");
+    }
+
+    /// Tests that upserting one datacenter keeps the other's jobs and the new versions
+    #[test]
+    fn test_upsert_is_scoped_by_datacenter_and_keeps_new_versions() {
+        let folder = |datacenter: &str, versions: &[&str]| {
+            let mut folder = Folder::new("TEST_FOLDER".to_string(), FolderType::Simple);
+            folder.datacenter = Some(datacenter.to_string());
+            for version in versions {
+                let mut job = Job::new("TEST_JOB".to_string(), "TEST_FOLDER".to_string());
+                job.job_version = Some(version.to_string());
+                folder.add_job(job);
+            }
+            folder
+        };
+        let versions = |exporter: &SqliteExporter, datacenter: &str| -> Vec<String> {
+            let mut stmt = exporter.conn
+                .prepare("SELECT job_version FROM jobs WHERE datacenter = ? ORDER BY id")
+                .unwrap();
+            stmt.query_map([datacenter], |row| row.get(0)).unwrap().map(|v| v.unwrap()).collect()
+        };
+
+        let exporter = SqliteExporter::new(":memory:").unwrap();
+        exporter.export_folders(&[folder("DC1", &["1"]), folder("DC2", &["1"])]).unwrap();
+        exporter.export_folders_upsert(&[folder("DC1", &["2", "3"])]).unwrap();
+
+        assert_eq!(versions(&exporter, "DC1"), vec!["2", "3"]);
+        assert_eq!(versions(&exporter, "DC2"), vec!["1"]);
+    }
+
+    /// Tests that dependency depths come from the stored graph and stay per row
+    #[test]
+    fn test_dependency_depth_is_kept_per_datacenter_across_exports() {
+        let mut dc1 = Folder::new("CHAIN".to_string(), FolderType::Simple);
+        dc1.datacenter = Some("DC1".to_string());
+        let mut extract = Job::new("EXTRACT".to_string(), "CHAIN".to_string());
+        extract.out_conditions.push(Condition::new_out("EXTRACT-OK".to_string()));
+        let mut load = Job::new("LOAD".to_string(), "CHAIN".to_string());
+        load.in_conditions.push(Condition::new_in("EXTRACT-OK".to_string()));
+        dc1.add_job(extract);
+        dc1.add_job(load);
+        // Same folder and job name in another datacenter, waiting for nothing
+        let mut dc2 = Folder::new("CHAIN".to_string(), FolderType::Simple);
+        dc2.datacenter = Some("DC2".to_string());
+        dc2.add_job(Job::new("LOAD".to_string(), "CHAIN".to_string()));
+
+        let exporter = SqliteExporter::new(":memory:").unwrap();
+        exporter.export_folders(&[dc1]).unwrap();
+        exporter.export_folders_with_mode(&[dc2], ExportMode::Append).unwrap();
+
+        let depth = |datacenter: &str| -> i64 {
+            exporter.conn.query_row(
+                "SELECT dependency_depth FROM jobs WHERE job_name = 'LOAD' AND datacenter = ?",
+                [datacenter],
+                |row| row.get(0),
+            ).unwrap()
+        };
+        assert_eq!(depth("DC1"), 1);
+        assert_eq!(depth("DC2"), 0);
+    }
+
+    /// Tests exporting into a database whose jobs table predates the added columns
+    #[test]
+    fn test_export_adds_missing_job_columns_to_older_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("jobs.db");
+        // Strip the jobs table back to the layout earlier versions created
+        let exporter = SqliteExporter::new(&db_path).unwrap();
+        exporter.conn.execute_batch(
+            "DROP INDEX idx_jobs_priority_num;
+             ALTER TABLE jobs DROP COLUMN priority_num;
+             ALTER TABLE jobs DROP COLUMN cmdline_tokens;
+             ALTER TABLE jobs DROP COLUMN dependency_depth;
+             INSERT INTO jobs (job_name, folder_name, priority) VALUES ('OLD_JOB', 'OLD_FOLDER', ' 7 ');"
+        ).unwrap();
+        drop(exporter);
+
+        let mut folder = Folder::new("NEW_FOLDER".to_string(), FolderType::Simple);
+        let mut job = Job::new("NEW_JOB".to_string(), "NEW_FOLDER".to_string());
+        job.priority = Some("3".to_string());
+        job.cmdline = Some("run.sh --full".to_string());
+        job.out_conditions.push(Condition::new_out("NEW_JOB-OK".to_string()));
+        let mut next = Job::new("NEXT_JOB".to_string(), "NEW_FOLDER".to_string());
+        next.in_conditions.push(Condition::new_in("NEW_JOB-OK".to_string()));
+        folder.add_job(job);
+        folder.add_job(next);
+        let exporter = SqliteExporter::new(&db_path).unwrap().with_cmdline_tokens(true);
+        exporter.export_folders(&[folder]).unwrap();
+
+        let mut stmt = exporter.conn.prepare("SELECT job_name, priority_num FROM jobs ORDER BY id").unwrap();
+        let priorities: Vec<(String, Option<i64>)> = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .map(|r| r.unwrap())
+            .collect();
+        assert_eq!(priorities, vec![
+            ("OLD_JOB".to_string(), Some(7)),
+            ("NEW_JOB".to_string(), Some(3)),
+            ("NEXT_JOB".to_string(), None),
+        ]);
+        let tokens: Option<String> = exporter.conn
+            .query_row("SELECT cmdline_tokens FROM jobs WHERE job_name = 'NEW_JOB'", [], |row| row.get(0))
+            .unwrap();
+        assert!(tokens.unwrap().contains("run.sh"));
+        let depth: i64 = exporter.conn
+            .query_row("SELECT dependency_depth FROM jobs WHERE job_name = 'NEXT_JOB'", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(depth, 1);
+    }
+
+    /// Tests that job analysis rows go to the jobs the export inserted
+    #[test]
+    fn test_job_analysis_is_keyed_by_inserted_job() {
+        use crate::application::use_cases::AnalyzeJobs;
+
+        let folder = |datacenter: &str| {
+            let mut folder = Folder::new("TEST_FOLDER".to_string(), FolderType::Simple);
+            folder.datacenter = Some(datacenter.to_string());
+            folder.add_job(Job::new("TEST_JOB".to_string(), "TEST_FOLDER".to_string()));
+            folder
+        };
+        let export = |exporter: SqliteExporter, datacenter: &str, wave: usize, mode: ExportMode| {
+            let folders = [folder(datacenter)];
+            let mut results = AnalyzeJobs::new().execute(&folders).unwrap().complexity_results;
+            results[0].migration_wave = wave;
+            let exporter = exporter.with_job_analysis(results);
+            exporter.export_folders_with_mode(&folders, mode).unwrap();
+            exporter
+        };
+
+        let exporter = export(SqliteExporter::new(":memory:").unwrap(), "DC1", 1, ExportMode::Replace);
+        let exporter = export(exporter, "DC2", 2, ExportMode::Append);
+
+        let wave = |datacenter: &str| -> i64 {
+            exporter.conn.query_row(
+                "SELECT a.migration_wave FROM job_analysis a JOIN jobs j ON j.id = a.job_id WHERE j.datacenter = ?",
+                [datacenter],
+                |row| row.get(0),
+            ).unwrap()
+        };
+        assert_eq!(wave("DC1"), 1);
+        assert_eq!(wave("DC2"), 2);
+    }
 }
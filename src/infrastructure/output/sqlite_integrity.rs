@@ -0,0 +1,225 @@
+//! SQLite integrity checker module
+//!
+//! This module checks an exported job database for damage left behind by
+//! interrupted or improperly merged exports: child rows pointing at jobs (or
+//! ON conditions) that no longer exist, foreign key violations and SQLite
+//! page-level corruption. Orphaned child rows can optionally be deleted.
+
+use anyhow::{Context, Result};
+use rusqlite::Connection;
+use std::path::Path;
+
+/// Child tables and the parent each row must reference: (table, column, parent table)
+///
+/// Ordered so that repairing a table never leaves new orphans in a table
+/// already repaired (do_actions hang off on_conditions).
+const CHILD_TABLES: &[(&str, &str, &str)] = &[
+    ("job_scheduling", "job_id", "jobs"),
+    ("in_conditions", "job_id", "jobs"),
+    ("out_conditions", "job_id", "jobs"),
+    ("on_conditions", "job_id", "jobs"),
+    ("control_resources", "job_id", "jobs"),
+    ("quantitative_resources", "job_id", "jobs"),
+    ("job_variables", "job_id", "jobs"),
+    ("job_auto_edits", "job_id", "jobs"),
+    ("job_file_transfers", "job_id", "jobs"),
+    ("job_metadata", "job_id", "jobs"),
+    ("job_analysis", "job_id", "jobs"),
+    ("job_dependencies", "from_job_id", "jobs"),
+    ("job_dependencies", "to_job_id", "jobs"),
+    ("do_actions", "on_condition_id", "on_conditions"),
+];
+
+/// Orphaned rows found in one child table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrphanedRows {
+    pub table: String,
+    /// Column holding the dangling reference
+    pub column: String,
+    /// Table the column should reference
+    pub parent_table: String,
+    pub count: usize,
+}
+
+/// Result of an integrity check or repair
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntegrityReport {
+    /// Problems reported by `PRAGMA integrity_check`
+    pub integrity_errors: Vec<String>,
+    /// Rows reported by `PRAGMA foreign_key_check`
+    pub foreign_key_violations: usize,
+    /// Child rows whose parent row is missing (removed rows after a repair)
+    pub orphans: Vec<OrphanedRows>,
+}
+
+impl IntegrityReport {
+    /// Checks whether no problems were found
+    pub fn is_clean(&self) -> bool {
+        self.integrity_errors.is_empty() && self.foreign_key_violations == 0 && self.orphans.is_empty()
+    }
+
+    /// Total number of orphaned child rows
+    pub fn orphan_count(&self) -> usize {
+        self.orphans.iter().map(|o| o.count).sum()
+    }
+}
+
+/// Integrity checker for exported job databases
+pub struct SqliteIntegrityChecker {
+    conn: Connection,
+}
+
+impl SqliteIntegrityChecker {
+    /// Opens an existing database for checking
+    ///
+    /// Foreign key enforcement is switched on for the connection so repairs
+    /// cascade to rows that depend on the deleted ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `db_path` - Path to the SQLite database file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file doesn't exist or can't be opened
+    pub fn open<P: AsRef<Path>>(db_path: P) -> Result<Self> {
+        let path = db_path.as_ref();
+        if !path.exists() {
+            anyhow::bail!("Database not found: {:?}", path);
+        }
+        let conn = Connection::open(path)
+            .with_context(|| format!("Failed to open SQLite database {:?}", path))?;
+        conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+        Ok(Self { conn })
+    }
+
+    /// Scans the database without changing it
+    ///
+    /// # Returns
+    ///
+    /// Report of every problem found
+    pub fn check(&self) -> Result<IntegrityReport> {
+        let mut stmt = self.conn.prepare("PRAGMA integrity_check")?;
+        let integrity_errors = stmt.query_map([], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .filter(|message| message != "ok")
+            .collect();
+
+        let mut stmt = self.conn.prepare("PRAGMA foreign_key_check")?;
+        let foreign_key_violations = stmt.query_map([], |_| Ok(()))?.count();
+
+        let mut orphans = Vec::new();
+        for &(table, column, parent_table) in CHILD_TABLES {
+            if !self.table_exists(table)? {
+                continue;
+            }
+            let count: i64 = self.conn.query_row(&Self::orphan_query("SELECT COUNT(*)", table, column, parent_table), [], |row| row.get(0))?;
+            if count > 0 {
+                orphans.push(Self::orphaned_rows(table, column, parent_table, count as usize));
+            }
+        }
+
+        Ok(IntegrityReport {
+            integrity_errors,
+            foreign_key_violations,
+            orphans,
+        })
+    }
+
+    /// Deletes every orphaned child row in a single transaction
+    ///
+    /// Page-level corruption can't be repaired this way; run `check` again
+    /// afterwards to see what remains.
+    ///
+    /// # Returns
+    ///
+    /// Report listing the rows removed per table
+    pub fn repair(&self) -> Result<IntegrityReport> {
+        let tx = self.conn.unchecked_transaction()?;
+        let mut removed = Vec::new();
+        for &(table, column, parent_table) in CHILD_TABLES {
+            if !self.table_exists(table)? {
+                continue;
+            }
+            let count = tx.execute(&Self::orphan_query("DELETE", table, column, parent_table), [])
+                .with_context(|| format!("Failed to delete orphaned rows from {}", table))?;
+            if count > 0 {
+                removed.push(Self::orphaned_rows(table, column, parent_table, count));
+            }
+        }
+        tx.commit()?;
+
+        Ok(IntegrityReport {
+            orphans: removed,
+            ..Default::default()
+        })
+    }
+
+    fn orphan_query(action: &str, table: &str, column: &str, parent_table: &str) -> String {
+        format!(
+            "{} FROM {} WHERE {} NOT IN (SELECT id FROM {})",
+            action, table, column, parent_table
+        )
+    }
+
+    fn orphaned_rows(table: &str, column: &str, parent_table: &str, count: usize) -> OrphanedRows {
+        OrphanedRows {
+            table: table.to_string(),
+            column: column.to_string(),
+            parent_table: parent_table.to_string(),
+            count,
+        }
+    }
+
+    fn table_exists(&self, table: &str) -> Result<bool> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?",
+            [table],
+            |row| row.get(0),
+        )?;
+        Ok(count > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{Condition, Folder, FolderType, Job};
+    use crate::infrastructure::output::SqliteExporter;
+
+    #[test]
+    fn test_orphaned_child_rows_are_detected_and_removed() {
+        let tmp = tempfile::tempdir().unwrap();
+        let db_path = tmp.path().join("fsck.db");
+
+        let mut folder = Folder::new("FOLDER".to_string(), FolderType::Simple);
+        let mut job = Job::new("JOB".to_string(), "FOLDER".to_string());
+        job.in_conditions.push(Condition::new_in("UPSTREAM-OK".to_string()));
+        folder.add_job(job);
+        SqliteExporter::new(&db_path).unwrap().export_folders(&[folder]).unwrap();
+
+        // Write dangling rows the way an interrupted export would, without FK enforcement
+        let raw = Connection::open(&db_path).unwrap();
+        raw.execute_batch("PRAGMA foreign_keys = OFF;").unwrap();
+        raw.execute("INSERT INTO in_conditions (job_id, condition_name) VALUES (9999, 'GHOST-OK')", []).unwrap();
+        raw.execute("INSERT INTO out_conditions (job_id, condition_name) VALUES (9999, 'GHOST-OK')", []).unwrap();
+        drop(raw);
+
+        let checker = SqliteIntegrityChecker::open(&db_path).unwrap();
+        let report = checker.check().unwrap();
+        assert!(!report.is_clean());
+        assert_eq!(report.orphan_count(), 2);
+        assert!(report.orphans.iter().any(|o| o.table == "in_conditions" && o.count == 1));
+        assert_eq!(report.foreign_key_violations, 2);
+
+        let repaired = checker.repair().unwrap();
+        assert_eq!(repaired.orphan_count(), 2);
+        assert!(checker.check().unwrap().is_clean());
+
+        let remaining: i64 = checker.conn
+            .query_row("SELECT COUNT(*) FROM in_conditions", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(remaining, 1);
+    }
+}
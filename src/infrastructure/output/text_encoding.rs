@@ -0,0 +1,154 @@
+//! Output text encoding module
+//!
+//! Text reports are built as UTF-8 strings and re-encoded just before they
+//! are written, for downstream tools that expect a legacy code page such as
+//! Windows-1252.
+
+use encoding_rs::{Encoding, EncoderResult, UTF_8};
+use std::fmt;
+use std::str::FromStr;
+use tracing::warn;
+
+/// Character encoding of generated text reports (UTF-8 by default)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutputEncoding(&'static Encoding);
+
+impl OutputEncoding {
+    /// Creates an output encoding
+    ///
+    /// Encodings that cannot be produced as output (UTF-16, replacement)
+    /// fall back to UTF-8, as in `encoding_rs`.
+    pub fn new(encoding: &'static Encoding) -> Self {
+        Self(encoding.output_encoding())
+    }
+
+    /// Gets the canonical name of the encoding, e.g. `windows-1252`
+    pub fn name(&self) -> &'static str {
+        self.0.name()
+    }
+
+    /// Checks whether reports are written as UTF-8 unchanged
+    pub fn is_utf8(&self) -> bool {
+        self.0 == UTF_8
+    }
+
+    /// Encodes HTML report text for writing
+    ///
+    /// Characters the encoding cannot represent are written as numeric
+    /// character references (`&#8364;`), which browsers render as the
+    /// original character, and a warning is logged.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - HTML text
+    /// * `report` - Report name used in the warning
+    ///
+    /// # Returns
+    ///
+    /// The encoded bytes
+    pub fn encode_html(&self, text: &str, report: &str) -> Vec<u8> {
+        let (bytes, _, unmappable) = self.0.encode(text);
+        if unmappable {
+            warn!(
+                "{} contains characters {} cannot represent; they were written as &#NNNN; references",
+                report,
+                self.name()
+            );
+        }
+        bytes.into_owned()
+    }
+
+    /// Encodes plain report text (CSV, Markdown) for writing
+    ///
+    /// Numeric character references would show up literally in these
+    /// formats, so characters the encoding cannot represent are written
+    /// as `?` instead and recorded in the returned substitutions.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Report text
+    ///
+    /// # Returns
+    ///
+    /// The encoded bytes and the characters replaced by `?`
+    pub fn encode_text(&self, text: &str) -> (Vec<u8>, Substitutions) {
+        let mut substitutions = Substitutions::default();
+        if self.is_utf8() {
+            return (text.as_bytes().to_vec(), substitutions);
+        }
+
+        let mut encoder = self.0.new_encoder();
+        let mut bytes = Vec::with_capacity(text.len());
+        let mut buffer = [0u8; 4096];
+        let mut rest = text;
+        loop {
+            let (result, read, written) = encoder.encode_from_utf8_without_replacement(rest, &mut buffer, true);
+            bytes.extend_from_slice(&buffer[..written]);
+            rest = &rest[read..];
+            match result {
+                EncoderResult::InputEmpty => break,
+                EncoderResult::OutputFull => {}
+                EncoderResult::Unmappable(c) => {
+                    bytes.push(b'?');
+                    substitutions.count += 1;
+                    substitutions.first.get_or_insert(c);
+                }
+            }
+        }
+        (bytes, substitutions)
+    }
+}
+
+/// Characters replaced by `?` while encoding plain report text
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Substitutions {
+    /// Number of characters replaced
+    pub count: usize,
+    /// First character replaced, named in warnings
+    pub first: Option<char>,
+}
+
+impl Substitutions {
+    /// Describes the substitutions for a generation report warning
+    ///
+    /// # Arguments
+    ///
+    /// * `report` - Report name
+    /// * `encoding` - Encoding the report was written in
+    ///
+    /// # Returns
+    ///
+    /// The warning, or `None` if nothing was replaced
+    pub fn warning(&self, report: &str, encoding: OutputEncoding) -> Option<String> {
+        self.first.map(|first| format!(
+            "{}: {} character(s) {} cannot represent were written as '?' (first: '{}' U+{:04X})",
+            report,
+            self.count,
+            encoding.name(),
+            first,
+            first as u32
+        ))
+    }
+}
+
+impl Default for OutputEncoding {
+    fn default() -> Self {
+        Self(UTF_8)
+    }
+}
+
+impl FromStr for OutputEncoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Encoding::for_label(s.trim().as_bytes())
+            .map(Self::new)
+            .ok_or_else(|| format!("Unknown output encoding '{}' (expected a label such as utf-8 or windows-1252)", s))
+    }
+}
+
+impl fmt::Display for OutputEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
@@ -0,0 +1,124 @@
+//! XML attribute alias module
+//!
+//! Control-M exports spell some attribute names differently across versions
+//! (`TASKTYPE` vs `TASK_TYPE`, `JOBNAME` vs `JOB_NAME`). The parser reads
+//! every attribute through an `AttributeAliases` map so that a field is
+//! populated from whichever spelling an export uses.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Built-in alternate spellings as (alias, canonical attribute) pairs
+const DEFAULT_ALIASES: &[(&str, &str)] = &[
+    ("JOB_NAME", "JOBNAME"),
+    ("TASK_TYPE", "TASKTYPE"),
+    ("NODE_ID", "NODEID"),
+    ("CMD_LINE", "CMDLINE"),
+    ("MEM_NAME", "MEMNAME"),
+    ("MEM_LIB", "MEMLIB"),
+    ("APPLTYPE", "APPL_TYPE"),
+    ("APPLVER", "APPL_VER"),
+    ("SUBAPPLICATION", "SUB_APPLICATION"),
+    ("RUNAS", "RUN_AS"),
+    ("MAX_WAIT", "MAXWAIT"),
+    ("MAX_RERUN", "MAXRERUN"),
+    ("TIME_FROM", "TIMEFROM"),
+    ("TIME_TO", "TIMETO"),
+    ("DAYS_CAL", "DAYSCAL"),
+    ("WEEKS_CAL", "WEEKSCAL"),
+    ("CONF_CAL", "CONFCAL"),
+    ("FOLDERNAME", "FOLDER_NAME"),
+    ("PARENTFOLDER", "PARENT_FOLDER"),
+];
+
+/// Maps alternate XML attribute spellings to the canonical names the parser reads
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttributeAliases {
+    /// Canonical attribute name to its alternate spellings, in lookup order
+    aliases: HashMap<String, Vec<String>>,
+}
+
+impl AttributeAliases {
+    /// Creates an alias map holding the built-in aliases
+    pub fn new() -> Self {
+        DEFAULT_ALIASES.iter()
+            .fold(Self { aliases: HashMap::new() }, |aliases, (alias, canonical)| aliases.with_alias(alias, canonical))
+    }
+
+    /// Adds an alternate spelling for a canonical attribute name
+    ///
+    /// Names are case-sensitive, matching XML attribute names.
+    pub fn with_alias(mut self, alias: &str, canonical: &str) -> Self {
+        let spellings = self.aliases.entry(canonical.trim().to_string()).or_default();
+        let alias = alias.trim().to_string();
+        if !spellings.contains(&alias) {
+            spellings.push(alias);
+        }
+        self
+    }
+
+    /// Gets the alternate spellings of a canonical attribute name
+    pub fn aliases_of(&self, canonical: &str) -> &[String] {
+        self.aliases.get(canonical).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Reads an attribute by its canonical name, falling back to its aliases
+    ///
+    /// # Arguments
+    ///
+    /// * `node` - XML node containing the attribute
+    /// * `canonical` - Canonical attribute name
+    ///
+    /// # Returns
+    ///
+    /// The value of the first spelling present on the node
+    pub fn attribute<'a>(&self, node: &roxmltree::Node<'a, '_>, canonical: &str) -> Option<&'a str> {
+        node.attribute(canonical).or_else(|| {
+            self.aliases_of(canonical).iter()
+                .find_map(|alias| node.attribute(alias.as_str()))
+        })
+    }
+}
+
+impl Default for AttributeAliases {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FromStr for AttributeAliases {
+    type Err = String;
+
+    /// Parses `ALIAS=CANONICAL` pairs separated by commas on top of the built-in aliases
+    ///
+    /// For example `JOB_TYPE=TASKTYPE,OWNER_ID=OWNER`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut aliases = Self::new();
+        for pair in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            match pair.split_once('=') {
+                Some((alias, canonical)) if !alias.trim().is_empty() && !canonical.trim().is_empty() => {
+                    aliases = aliases.with_alias(alias, canonical);
+                }
+                _ => return Err(format!("Invalid attribute alias '{}' (expected ALIAS=CANONICAL)", pair)),
+            }
+        }
+        Ok(aliases)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonical_spelling_wins_over_aliases() {
+        let aliases: AttributeAliases = "JOB_TYPE=TASKTYPE".parse().unwrap();
+        let doc = roxmltree::Document::parse(r#"<JOB TASKTYPE="Command" TASK_TYPE="Job" JOB_TYPE="Dummy"/>"#).unwrap();
+        assert_eq!(aliases.attribute(&doc.root_element(), "TASKTYPE"), Some("Command"));
+
+        let doc = roxmltree::Document::parse(r#"<JOB JOB_TYPE="Dummy"/>"#).unwrap();
+        assert_eq!(aliases.attribute(&doc.root_element(), "TASKTYPE"), Some("Dummy"));
+        assert_eq!(aliases.aliases_of("TASKTYPE"), ["TASK_TYPE", "JOB_TYPE"]);
+        assert!("TASKTYPE".parse::<AttributeAliases>().is_err());
+    }
+}
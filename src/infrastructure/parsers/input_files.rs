@@ -0,0 +1,109 @@
+//! Input Files module
+//!
+//! This module expands the `--input` of a command into the Control-M XML
+//! files to parse: a single file, every `.xml` file in a directory, or the
+//! files matching a glob in the last path component (e.g. `exports/dc*.xml`).
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Expands an input path into the XML files it names
+///
+/// Directories are not searched recursively. Matches are sorted so runs
+/// over the same input parse files in the same order.
+///
+/// # Arguments
+///
+/// * `input` - File, directory or glob (`*` and `?` in the file name)
+///
+/// # Returns
+///
+/// The files to parse, at least one
+///
+/// # Errors
+///
+/// Returns an error if the directory cannot be read or nothing matches
+pub fn expand_input_paths(input: &Path) -> Result<Vec<PathBuf>> {
+    if input.is_file() {
+        return Ok(vec![input.to_path_buf()]);
+    }
+
+    let (dir, pattern) = if input.is_dir() {
+        (input.to_path_buf(), "*.xml".to_string())
+    } else {
+        let pattern = input.file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .filter(|name| name.contains(['*', '?']))
+            .with_context(|| format!("Input {} does not exist", input.display()))?;
+        let dir = match input.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        (dir, pattern)
+    };
+
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(&dir).with_context(|| format!("Failed to read directory {}", dir.display()))? {
+        let path = entry?.path();
+        let matches = path.file_name()
+            .is_some_and(|name| glob_matches(&pattern.to_lowercase(), &name.to_string_lossy().to_lowercase()));
+        if matches && path.is_file() {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    if paths.is_empty() {
+        anyhow::bail!("No XML files match {}", input.display());
+    }
+    Ok(paths)
+}
+
+/// Matches a file name against a pattern where `*` is any run of characters
+/// and `?` is any single character
+fn glob_matches(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, n));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            n = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_directory_and_glob() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        for name in ["dc2.xml", "dc1.XML", "notes.txt", "other.xml"] {
+            fs::write(dir.join(name), "<DEFTABLE/>").unwrap();
+        }
+
+        let all = expand_input_paths(dir).unwrap();
+        assert_eq!(all, vec![dir.join("dc1.XML"), dir.join("dc2.xml"), dir.join("other.xml")]);
+        let globbed = expand_input_paths(&dir.join("dc?.xml")).unwrap();
+        assert_eq!(globbed, vec![dir.join("dc1.XML"), dir.join("dc2.xml")]);
+        assert_eq!(expand_input_paths(&dir.join("notes.txt")).unwrap(), vec![dir.join("notes.txt")]);
+        assert!(expand_input_paths(&dir.join("missing*.xml")).is_err());
+        assert!(expand_input_paths(&dir.join("missing.xml")).is_err());
+    }
+}
@@ -1,5 +1,10 @@
 pub mod xml_parser;
+pub mod input_files;
+pub mod attribute_aliases;
 pub mod control_m_models;
+pub(crate) mod xml_sanitizer;
 
-pub use xml_parser::ControlMXmlParser;
+pub use xml_parser::{ControlMXmlParser, FolderMismatch, FolderNameSource, IssueSeverity, ParseIssue, ParseReport, SYNTHETIC_JOB_NAME_PREFIX};
+pub use attribute_aliases::AttributeAliases;
+pub use input_files::expand_input_paths;
 pub use control_m_models::*;
@@ -5,15 +5,109 @@
 //! Handles various encoding issues and XML sanitization.
 
 use anyhow::{Context, Result};
+use quick_xml::events::Event;
+use quick_xml::{Reader, Writer};
 use roxmltree::Document;
 use std::fs::File;
-use std::io::Read;
-use std::path::Path;
-use encoding_rs_io::DecodeReaderBytesBuilder;
+use std::io::{BufReader, Read};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use encoding_rs_io::{DecodeReaderBytes, DecodeReaderBytesBuilder};
+use serde::Serialize;
 
 use crate::domain::entities::*;
 use crate::domain::entities::condition::DoAction;
 use crate::domain::entities::folder::FolderType;
+use crate::domain::value_objects::{ApplTypeAliases, FileTransferSpec};
+use super::attribute_aliases::AttributeAliases;
+use super::xml_sanitizer::SanitizingReader;
+
+/// Prefix marking job names synthesized for jobs without a JOBNAME
+pub const SYNTHETIC_JOB_NAME_PREFIX: &str = "__UNNAMED__";
+
+/// Child elements of a JOB the parser reads; others are reported and skipped
+const KNOWN_JOB_CHILDREN: [&str; 7] = ["INCOND", "OUTCOND", "VARIABLE", "CONTROL", "QUANTITATIVE", "ON", "SHOUT"];
+
+/// Problems found while parsing that did not stop the parse
+#[derive(Debug, Clone, Default)]
+pub struct ParseReport {
+    /// Human-readable warnings, one per problem
+    pub warnings: Vec<String>,
+    /// Synthetic names assigned to jobs that had no JOBNAME
+    pub synthetic_job_names: Vec<String>,
+    /// Jobs whose PARENT_FOLDER disagrees with the folder containing them
+    pub folder_mismatches: Vec<FolderMismatch>,
+    /// Elements that were skipped or only partly understood, with their location
+    pub issues: Vec<ParseIssue>,
+    /// Shout destination tables (SHOUT_DEST_TABLE) found next to the folders;
+    /// not collected by `parse_file_streaming`
+    pub shout_tables: Vec<ShoutDestinationTable>,
+}
+
+impl ParseReport {
+    /// Number of issues with `IssueSeverity::Error`
+    pub fn error_count(&self) -> usize {
+        self.issues.iter().filter(|i| i.severity == IssueSeverity::Error).count()
+    }
+
+    fn push_issue(&mut self, severity: IssueSeverity, line: Option<u32>, message: String) {
+        self.issues.push(ParseIssue { severity, line, message });
+    }
+}
+
+/// How serious a parse issue is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueSeverity {
+    /// Data was lost or invented (e.g. a job or folder without a name)
+    Error,
+    /// An element was skipped or ignored
+    Warning,
+}
+
+/// A problem at a specific place in the XML
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ParseIssue {
+    pub severity: IssueSeverity,
+    /// 1-based line of the element; for streamed files, within the folder's XML
+    pub line: Option<u32>,
+    pub message: String,
+}
+
+/// A job whose PARENT_FOLDER attribute names a different folder than the
+/// one it is nested in (typically a promoted or moved job)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FolderMismatch {
+    pub job_name: String,
+    /// Folder the job is nested in
+    pub structural_folder: String,
+    /// Folder named by the job's PARENT_FOLDER attribute
+    pub parent_folder: String,
+}
+
+/// Which folder name a job is assigned when PARENT_FOLDER disagrees with
+/// the containing folder
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FolderNameSource {
+    /// The folder the job is nested in
+    #[default]
+    Structural,
+    /// The job's PARENT_FOLDER attribute
+    ParentFolder,
+}
+
+impl FromStr for FolderNameSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().replace('_', "-").as_str() {
+            "structural" => Ok(FolderNameSource::Structural),
+            "parent-folder" => Ok(FolderNameSource::ParentFolder),
+            other => Err(format!("Unknown folder name source '{}' (expected structural or parent-folder)", other)),
+        }
+    }
+}
 
 /// Parser for Control-M XML export files
 ///
@@ -23,7 +117,16 @@ use crate::domain::entities::folder::FolderType;
 /// - Dependencies (conditions, resources)
 /// - Scheduling information
 /// - Windows-1252 encoding
-pub struct ControlMXmlParser;
+pub struct ControlMXmlParser {
+    /// Maximum folder nesting depth before parsing is aborted
+    max_folder_depth: usize,
+    /// Folder name given to jobs whose PARENT_FOLDER disagrees with their folder
+    folder_name_source: FolderNameSource,
+    /// Alternate attribute spellings consulted when the canonical one is missing
+    attribute_aliases: AttributeAliases,
+    /// APPL_TYPE categories, used to recognize file-watch jobs
+    appl_type_aliases: ApplTypeAliases,
+}
 
 impl ControlMXmlParser {
     /// Creates a new ControlMXmlParser instance
@@ -32,7 +135,52 @@ impl ControlMXmlParser {
     ///
     /// A new ControlMXmlParser
     pub fn new() -> Self {
-        Self
+        Self {
+            max_folder_depth: DEFAULT_MAX_FOLDER_DEPTH,
+            folder_name_source: FolderNameSource::default(),
+            attribute_aliases: AttributeAliases::default(),
+            appl_type_aliases: ApplTypeAliases::default(),
+        }
+    }
+
+    /// Sets which folder name is authoritative for jobs whose PARENT_FOLDER
+    /// disagrees with the folder containing them
+    ///
+    /// Mismatches are reported in the parse report either way.
+    pub fn with_folder_name_source(mut self, folder_name_source: FolderNameSource) -> Self {
+        self.folder_name_source = folder_name_source;
+        self
+    }
+
+    /// Sets the attribute alias map, replacing the built-in one
+    ///
+    /// Build the map with `AttributeAliases::new()` to keep the built-in aliases.
+    pub fn with_attribute_aliases(mut self, attribute_aliases: AttributeAliases) -> Self {
+        self.attribute_aliases = attribute_aliases;
+        self
+    }
+
+    /// Sets the APPL_TYPE alias map used to recognize file-watch jobs
+    pub fn with_appl_type_aliases(mut self, appl_type_aliases: ApplTypeAliases) -> Self {
+        self.appl_type_aliases = appl_type_aliases;
+        self
+    }
+
+    /// Sets the maximum folder nesting depth
+    ///
+    /// Folders nested deeper than this limit are rejected with an error
+    /// instead of being parsed recursively.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_folder_depth` - Maximum allowed nesting depth (top-level folders are depth 1)
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn with_max_folder_depth(mut self, max_folder_depth: usize) -> Self {
+        self.max_folder_depth = max_folder_depth;
+        self
     }
 
     /// Parses a Control-M XML file from disk
@@ -55,54 +203,128 @@ impl ControlMXmlParser {
     /// - File cannot be read or decoded
     /// - XML is malformed
     pub fn parse_file<P: AsRef<Path>>(&self, path: P) -> Result<Vec<Folder>> {
-        // Open file and set up Windows-1252 decoder
+        let sanitized = self.read_file(path)?;
+        self.parse_xml(&sanitized)
+    }
+
+    /// Reads and decodes a Control-M XML file
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the XML file
+    ///
+    /// # Returns
+    ///
+    /// Result containing the decoded and sanitized XML content
+    fn read_file<P: AsRef<Path>>(&self, path: P) -> Result<String> {
+        let mut xml_content = String::new();
+        self.open_sanitized(path)?
+            .read_to_string(&mut xml_content)
+            .context("Failed to read XML file")?;
+        Ok(xml_content)
+    }
+
+    /// Opens a Control-M XML file as a decoded, sanitized UTF-8 stream
+    ///
+    /// Control-M exports often use Windows-1252 encoding and may contain
+    /// control characters that are invalid in XML; both are handled while
+    /// the file is read.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the XML file
+    ///
+    /// # Returns
+    ///
+    /// Result containing a reader over the sanitized UTF-8 content
+    fn open_sanitized<P: AsRef<Path>>(&self, path: P) -> Result<SanitizingReader<DecodeReaderBytes<File, Vec<u8>>>> {
         let file = File::open(path)
             .context("Failed to open XML file")?;
-        
-        // Control-M exports often use Windows-1252 encoding
-        let mut decoder = DecodeReaderBytesBuilder::new()
+
+        let decoder = DecodeReaderBytesBuilder::new()
             .encoding(Some(encoding_rs::WINDOWS_1252))
             .build(file);
-        
-        let mut xml_content = String::new();
-        decoder.read_to_string(&mut xml_content)
-            .context("Failed to read XML file")?;
-        
-        // Sanitize XML by removing invalid control characters
-        let sanitized = self.sanitize_xml(&xml_content);
-        
-        self.parse_xml(&sanitized)
+
+        Ok(SanitizingReader::new(decoder))
     }
 
-    /// Sanitizes XML content by removing invalid control characters
+    /// Parses a Control-M XML file from disk one folder at a time
     ///
-    /// Control-M XML exports may contain invalid control characters that
-    /// cause XML parsing to fail. This method filters them out while
-    /// preserving valid whitespace.
-    ///
-    /// According to XML 1.0 spec, valid characters are:
-    /// - #x9 | #xA | #xD | [#x20-#xD7FF] | [#xE000-#xFFFD] | [#x10000-#x10FFFF]
+    /// Unlike `parse_file`, the export is never held in memory as a whole:
+    /// the file is decoded, sanitized and scanned as a stream, and each
+    /// top-level FOLDER, SMART_FOLDER, TABLE or SMART_TABLE is parsed and
+    /// handed to `on_folder` as soon as its closing tag is read. Memory use
+    /// is bounded by the largest single folder. Folders are found at any
+    /// nesting level, as with `parse_xml`.
     ///
     /// # Arguments
     ///
-    /// * `xml` - Raw XML string to sanitize
+    /// * `path` - Path to the XML file
+    /// * `on_folder` - Called with each folder in document order
     ///
     /// # Returns
     ///
-    /// Sanitized XML string with only valid characters
-    fn sanitize_xml(&self, xml: &str) -> String {
-        xml.chars()
-            .filter(|&c| {
-                let code = c as u32;
-                // Valid XML 1.0 characters
-                code == 0x09      // Tab
-                || code == 0x0A   // Line Feed
-                || code == 0x0D   // Carriage Return
-                || (code >= 0x20 && code <= 0xD7FF)
-                || (code >= 0xE000 && code <= 0xFFFD)
-                || (code >= 0x10000 && code <= 0x10FFFF)
-            })
-            .collect()
+    /// Result containing the report of recoverable problems
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or decoded, the XML is
+    /// malformed, or folders are nested deeper than the configured maximum
+    /// depth. Folders passed to `on_folder` before the error are kept.
+    pub fn parse_file_streaming<P: AsRef<Path>>(&self, path: P, mut on_folder: impl FnMut(Folder)) -> Result<ParseReport> {
+        let mut reader = Reader::from_reader(BufReader::new(self.open_sanitized(path)?));
+        let mut report = ParseReport::default();
+        let mut buf = Vec::new();
+        // Folder being collected: its type, raw XML and open element count
+        let mut current: Option<(FolderType, Writer<Vec<u8>>, usize)> = None;
+
+        loop {
+            let event = reader.read_event_into(&mut buf)
+                .with_context(|| format!("Failed to parse XML at byte {}", reader.buffer_position()))?;
+
+            if matches!(event, Event::Eof) {
+                break;
+            }
+            match current.as_mut() {
+                Some((_, _, open)) => match event {
+                    Event::Start(_) => *open += 1,
+                    Event::End(_) => *open -= 1,
+                    _ => {}
+                },
+                None => {
+                    if let Event::Start(e) | Event::Empty(e) = &event {
+                        let tag_name = String::from_utf8_lossy(e.local_name().as_ref()).into_owned();
+                        if let Some(folder_type) = Self::folder_type_for_tag(&tag_name) {
+                            let open = usize::from(matches!(event, Event::Start(_)));
+                            current = Some((folder_type, Writer::new(Vec::new()), open));
+                        }
+                    }
+                }
+            }
+
+            let complete = match &mut current {
+                Some((_, writer, open)) => {
+                    writer.write_event(event).context("Failed to buffer folder XML")?;
+                    *open == 0
+                }
+                None => false,
+            };
+            if complete {
+                if let Some((folder_type, writer, _)) = current.take() {
+                    let folder_xml = String::from_utf8(writer.into_inner())
+                        .context("Folder XML is not valid UTF-8")?;
+                    let doc = Document::parse(&folder_xml)
+                        .context("Failed to parse XML")?;
+                    on_folder(self.parse_folder_node(&doc.root_element(), folder_type, 1, &mut report)?);
+                }
+            }
+            buf.clear();
+        }
+
+        if current.is_some() {
+            anyhow::bail!("Failed to parse XML: the file ends inside a folder");
+        }
+        Ok(report)
     }
 
     /// Parses XML content into domain entities
@@ -117,115 +339,380 @@ impl ControlMXmlParser {
     /// # Returns
     ///
     /// Result containing a vector of Folder entities or an error
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the XML is malformed or folders are nested deeper
+    /// than the configured maximum depth
     pub fn parse_xml(&self, xml_content: &str) -> Result<Vec<Folder>> {
+        self.parse_xml_with_report(xml_content).map(|(folders, _)| folders)
+    }
+
+    /// Parses a Control-M XML file from disk, also returning a parse report
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the XML file
+    ///
+    /// # Returns
+    ///
+    /// Result containing the folders and the report of recoverable problems
+    ///
+    /// # Errors
+    ///
+    /// Returns an error under the same conditions as `parse_file`
+    pub fn parse_file_with_report<P: AsRef<Path>>(&self, path: P) -> Result<(Vec<Folder>, ParseReport)> {
+        let sanitized = self.read_file(path)?;
+        self.parse_xml_with_report(&sanitized)
+    }
+
+    /// Parses several Control-M XML files into one list of folders
+    ///
+    /// Analyzing the merged list resolves dependencies that cross file
+    /// boundaries (e.g. exports split per datacenter).
+    ///
+    /// # Arguments
+    ///
+    /// * `paths` - Paths to the XML files, parsed in order
+    ///
+    /// # Returns
+    ///
+    /// Result containing the folders of every file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the file if any of them fails to parse
+    pub fn parse_files(&self, paths: &[PathBuf]) -> Result<Vec<Folder>> {
+        self.parse_files_with_report(paths).map(|(folders, _)| folders)
+    }
+
+    /// Parses several Control-M XML files, also returning a combined report
+    ///
+    /// Folders with the same name in different files are kept apart, not
+    /// merged; each such name is reported as a warning.
+    ///
+    /// # Arguments
+    ///
+    /// * `paths` - Paths to the XML files, parsed in order
+    ///
+    /// # Returns
+    ///
+    /// Result containing the folders of every file and the combined report
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the file if any of them fails to parse
+    pub fn parse_files_with_report(&self, paths: &[PathBuf]) -> Result<(Vec<Folder>, ParseReport)> {
+        let mut folders = Vec::new();
+        let mut report = ParseReport::default();
+        let mut folder_files: HashMap<String, &PathBuf> = HashMap::new();
+
+        for path in paths {
+            let (file_folders, file_report) = self.parse_file_with_report(path)
+                .with_context(|| format!("Failed to parse {}", path.display()))?;
+            for folder in &file_folders {
+                match folder_files.get(&folder.folder_name) {
+                    Some(first) if *first != path => report.warnings.push(format!(
+                        "Folder {} appears in both {} and {}; kept as separate folders",
+                        folder.folder_name, first.display(), path.display()
+                    )),
+                    Some(_) => {}
+                    None => {
+                        folder_files.insert(folder.folder_name.clone(), path);
+                    }
+                }
+            }
+            report.warnings.extend(file_report.warnings);
+            report.synthetic_job_names.extend(file_report.synthetic_job_names);
+            report.folder_mismatches.extend(file_report.folder_mismatches);
+            report.issues.extend(file_report.issues);
+            report.shout_tables.extend(file_report.shout_tables);
+            folders.extend(file_folders);
+        }
+
+        Ok((folders, report))
+    }
+
+    /// Parses XML content into domain entities, also returning a parse report
+    ///
+    /// The report also carries the shout destination tables of the document,
+    /// so they need no second parse.
+    ///
+    /// # Arguments
+    ///
+    /// * `xml_content` - XML string to parse
+    ///
+    /// # Returns
+    ///
+    /// Result containing the folders and the report of recoverable problems
+    ///
+    /// # Errors
+    ///
+    /// Returns an error under the same conditions as `parse_xml`
+    pub fn parse_xml_with_report(&self, xml_content: &str) -> Result<(Vec<Folder>, ParseReport)> {
         let doc = Document::parse(xml_content)
             .context("Failed to parse XML")?;
 
         let mut folders = Vec::new();
+        let mut report = ParseReport::default();
         
-        let root = doc.root_element();
-        
-        // Iterate through root-level elements to find folders
-        for node in root.children() {
-            if !node.is_element() {
-                continue;
-            }
-            
-            let tag_name = node.tag_name().name();
-            
-            match tag_name {
-                "FOLDER" => {
-                    if let Ok(folder) = self.parse_folder_node(&node, FolderType::Simple) {
-                        folders.push(folder);
-                    }
-                }
-                "SMART_FOLDER" => {
-                    if let Ok(folder) = self.parse_folder_node(&node, FolderType::Smart) {
-                        folders.push(folder);
-                    }
-                }
-                "TABLE" => {
-                    if let Ok(folder) = self.parse_folder_node(&node, FolderType::Table) {
-                        folders.push(folder);
-                    }
-                }
-                "SMART_TABLE" => {
-                    if let Ok(folder) = self.parse_folder_node(&node, FolderType::SmartTable) {
-                        folders.push(folder);
-                    }
-                }
-                _ => {}
+        // Folders may sit directly under DEFTABLE, under another wrapper
+        // element, or be the document root itself
+        let mut folder_nodes = Vec::new();
+        Self::collect_folder_nodes(doc.root_element(), &mut folder_nodes);
+
+        for (node, folder_type) in folder_nodes {
+            folders.push(self.parse_folder_node(&node, folder_type, 1, &mut report)?);
+        }
+        report.shout_tables = self.collect_shout_tables(&doc);
+
+        Ok((folders, report))
+    }
+
+    /// Gets the folder type for a folder-type tag name
+    ///
+    /// # Returns
+    ///
+    /// The folder type, or None if the tag isn't a folder
+    fn folder_type_for_tag(tag_name: &str) -> Option<FolderType> {
+        match tag_name {
+            "FOLDER" => Some(FolderType::Simple),
+            "SMART_FOLDER" => Some(FolderType::Smart),
+            "TABLE" => Some(FolderType::Table),
+            "SMART_TABLE" => Some(FolderType::SmartTable),
+            "SUB_FOLDER" => Some(FolderType::Simple),
+            _ => None,
+        }
+    }
+
+    /// Collects the outermost folder-type elements at any nesting level
+    ///
+    /// Search stops at each folder found, so folders nested inside another
+    /// folder are left to the folder parser.
+    ///
+    /// # Arguments
+    ///
+    /// * `node` - Element to search from (included in the search)
+    /// * `found` - Receives folder nodes with their types in document order
+    fn collect_folder_nodes<'a, 'input>(
+        node: roxmltree::Node<'a, 'input>,
+        found: &mut Vec<(roxmltree::Node<'a, 'input>, FolderType)>,
+    ) {
+        if let Some(folder_type) = Self::folder_type_for_tag(node.tag_name().name()) {
+            found.push((node, folder_type));
+            return;
+        }
+        for child in node.children().filter(|n| n.is_element()) {
+            Self::collect_folder_nodes(child, found);
+        }
+    }
+
+    /// Parses shout destination tables from XML content
+    ///
+    /// Shout destination tables (SHOUT_DEST_TABLE) map symbolic destinations
+    /// used by job shouts to the actual recipients. Like folders, tables are
+    /// found at any depth, e.g. inside a wrapper element.
+    ///
+    /// # Arguments
+    ///
+    /// * `xml_content` - XML string to parse
+    ///
+    /// # Returns
+    ///
+    /// Result containing the shout destination tables found in the document
+    pub fn parse_shout_tables(&self, xml_content: &str) -> Result<Vec<ShoutDestinationTable>> {
+        let doc = Document::parse(xml_content)
+            .context("Failed to parse XML")?;
+
+        Ok(self.collect_shout_tables(&doc))
+    }
+
+    /// Parses every shout destination table of a parsed document
+    fn collect_shout_tables(&self, doc: &Document) -> Vec<ShoutDestinationTable> {
+        doc.root_element()
+            .descendants()
+            .filter(|n| n.is_element() && n.tag_name().name() == "SHOUT_DEST_TABLE")
+            .map(|node| self.parse_shout_table_node(&node))
+            .collect()
+    }
+
+    /// Parses a shout destination table node from XML
+    ///
+    /// # Arguments
+    ///
+    /// * `node` - XML node representing the destination table
+    ///
+    /// # Returns
+    ///
+    /// A ShoutDestinationTable entity
+    fn parse_shout_table_node(&self, node: &roxmltree::Node) -> ShoutDestinationTable {
+        let name = node.attribute("NAME").unwrap_or("UNKNOWN").to_string();
+        let mut table = ShoutDestinationTable::new(name);
+
+        for dest in node.children().filter(|n| n.is_element() && n.tag_name().name() == "DEST") {
+            if let (Some(logical), Some(physical)) = (dest.attribute("LOGICAL"), dest.attribute("PHYSICAL")) {
+                table.add_destination(logical.to_string(), physical.to_string());
             }
         }
 
-        Ok(folders)
+        table
     }
     
     /// Parses a folder node from XML
     ///
-    /// Extracts folder attributes and recursively parses all jobs within the folder.
+    /// Extracts folder attributes, parses the jobs within the folder and
+    /// recursively parses nested folder elements (e.g. a FOLDER or
+    /// SUB_FOLDER inside a SMART_FOLDER) into `sub_folders`.
     ///
     /// # Arguments
     ///
     /// * `node` - XML node representing the folder
     /// * `folder_type` - Type of folder (Simple, Smart, Table, SmartTable)
+    /// * `depth` - Nesting depth of this folder (top-level folders are depth 1)
+    /// * `report` - Parse report receiving recoverable problems
     ///
     /// # Returns
     ///
     /// Result containing a Folder entity or an error
-    fn parse_folder_node(&self, node: &roxmltree::Node, folder_type: FolderType) -> Result<Folder> {
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `depth`, or the depth of a nested folder, exceeds
+    /// the configured maximum folder depth
+    fn parse_folder_node(
+        &self,
+        node: &roxmltree::Node,
+        folder_type: FolderType,
+        depth: usize,
+        report: &mut ParseReport,
+    ) -> Result<Folder> {
+        if depth > self.max_folder_depth {
+            anyhow::bail!("Folder nesting exceeds maximum depth of {}", self.max_folder_depth);
+        }
+
         // Folder name can be in FOLDER_NAME or TABLE_NAME attribute
-        let folder_name = node.attribute("FOLDER_NAME")
-            .or_else(|| node.attribute("TABLE_NAME"))
-            .unwrap_or("UNKNOWN")
-            .to_string();
+        let folder_name = match self.attr(node, "FOLDER_NAME").or_else(|| self.attr(node, "TABLE_NAME")) {
+            Some(name) => name.to_string(),
+            None => {
+                let message = format!("{} element has no FOLDER_NAME or TABLE_NAME; named it 'UNKNOWN'", node.tag_name().name());
+                report.warnings.push(message.clone());
+                report.push_issue(IssueSeverity::Error, Some(Self::line_of(node)), message);
+                "UNKNOWN".to_string()
+            }
+        };
         
         let mut folder = Folder::new(folder_name, folder_type);
         
         // Core attributes
-        folder.datacenter = node.attribute("DATACENTER").map(|s| s.to_string());
-        folder.application = node.attribute("APPLICATION").map(|s| s.to_string());
-        folder.description = node.attribute("DESCRIPTION").map(|s| s.to_string());
-        folder.owner = node.attribute("OWNER").map(|s| s.to_string());
+        folder.datacenter = self.get_str_attr(node, "DATACENTER");
+        folder.application = self.get_str_attr(node, "APPLICATION");
+        folder.description = self.get_str_attr(node, "DESCRIPTION");
+        folder.owner = self.get_str_attr(node, "OWNER");
         
         // Additional folder metadata
-        folder.version = node.attribute("VERSION").map(|s| s.to_string());
-        folder.platform = node.attribute("PLATFORM").map(|s| s.to_string());
-        folder.table_name = node.attribute("TABLE_NAME").map(|s| s.to_string());
-        folder.folder_dsn = node.attribute("FOLDER_DSN").map(|s| s.to_string());
-        folder.table_dsn = node.attribute("TABLE_DSN").map(|s| s.to_string());
-        folder.modified = node.attribute("MODIFIED").and_then(|s| match s {
+        folder.version = self.get_str_attr(node, "VERSION");
+        folder.platform = self.get_str_attr(node, "PLATFORM");
+        folder.table_name = self.get_str_attr(node, "TABLE_NAME");
+        folder.folder_dsn = self.get_str_attr(node, "FOLDER_DSN");
+        folder.table_dsn = self.get_str_attr(node, "TABLE_DSN");
+        folder.modified = self.attr(node, "MODIFIED").and_then(|s| match s {
             "1" | "true" | "True" => Some(true),
             "0" | "false" | "False" => Some(false),
             _ => None,
         });
-        folder.last_upload = node.attribute("LAST_UPLOAD").map(|s| s.to_string());
-        folder.folder_order_method = node.attribute("FOLDER_ORDER_METHOD").map(|s| s.to_string());
-        folder.table_userdaily = node.attribute("TABLE_USERDAILY").map(|s| s.to_string());
-        folder.real_folder_id = node.attribute("REAL_FOLDER_ID").and_then(|s| s.parse().ok());
-        folder.real_tableid = node.attribute("REAL_TABLEID").and_then(|s| s.parse().ok());
-        folder.type_code = node.attribute("TYPE").and_then(|s| s.parse().ok());
-        folder.used_by = node.attribute("USED_BY").map(|s| s.to_string());
-        folder.used_by_code = node.attribute("USED_BY_CODE").and_then(|s| s.parse().ok());
-        folder.enforce_validation = node.attribute("ENFORCE_VALIDATION").map(|s| s.to_string());
-        folder.site_standard_name = node.attribute("SITE_STANDARD_NAME").map(|s| s.to_string());
+        folder.last_upload = self.get_str_attr(node, "LAST_UPLOAD");
+        folder.folder_order_method = self.get_str_attr(node, "FOLDER_ORDER_METHOD");
+        folder.table_userdaily = self.get_str_attr(node, "TABLE_USERDAILY");
+        folder.real_folder_id = self.attr(node, "REAL_FOLDER_ID").and_then(|s| s.parse().ok());
+        folder.real_tableid = self.attr(node, "REAL_TABLEID").and_then(|s| s.parse().ok());
+        folder.type_code = self.attr(node, "TYPE").and_then(|s| s.parse().ok());
+        folder.used_by = self.get_str_attr(node, "USED_BY");
+        folder.used_by_code = self.attr(node, "USED_BY_CODE").and_then(|s| s.parse().ok());
+        folder.enforce_validation = self.get_str_attr(node, "ENFORCE_VALIDATION");
+        folder.site_standard_name = self.get_str_attr(node, "SITE_STANDARD_NAME");
         
-        // Parse all jobs within this folder
+        // Parse all jobs and nested folders within this folder
+        let mut unnamed_jobs = 0;
+        let mut job_position = 0;
         for child in node.children() {
             if !child.is_element() {
                 continue;
             }
+
+            if let Some(sub_folder_type) = Self::folder_type_for_tag(child.tag_name().name()) {
+                let sub_folder = self.parse_folder_node(&child, sub_folder_type, depth + 1, report)?;
+                folder.add_sub_folder(sub_folder);
+                continue;
+            }
             
-            if child.tag_name().name() == "JOB" {
-                if let Ok(job) = self.parse_job_node(&child, folder.folder_name.clone()) {
+            if child.tag_name().name() != "JOB" {
+                report.push_issue(IssueSeverity::Warning, Some(Self::line_of(&child)), format!(
+                    "Skipped <{}> element in folder '{}'", child.tag_name().name(), folder.folder_name
+                ));
+                continue;
+            }
+
+            job_position += 1;
+            match self.parse_job_node(&child, folder.folder_name.clone(), report) {
+                Ok(mut job) => {
+                    if self.attr(&child, "JOBNAME").is_none_or(|name| name.trim().is_empty()) {
+                        // Give nameless jobs a unique, clearly synthetic name so they never merge
+                        unnamed_jobs += 1;
+                        job.job_name = format!("{}{}_{}", SYNTHETIC_JOB_NAME_PREFIX, folder.folder_name, unnamed_jobs);
+                        let message = format!(
+                            "Job #{} in folder '{}' has no JOBNAME; named it '{}'",
+                            job_position, folder.folder_name, job.job_name
+                        );
+                        report.warnings.push(message.clone());
+                        report.push_issue(IssueSeverity::Error, Some(Self::line_of(&child)), message);
+                        report.synthetic_job_names.push(job.job_name.clone());
+                    }
+                    self.reconcile_parent_folder(&mut job, report);
                     folder.add_job(job);
                 }
+                Err(e) => report.push_issue(IssueSeverity::Error, Some(Self::line_of(&child)), format!(
+                    "Skipped job #{} in folder '{}': {}", job_position, folder.folder_name, e
+                )),
             }
         }
         
         Ok(folder)
     }
     
+    /// Reports a job whose PARENT_FOLDER differs from its containing folder
+    ///
+    /// The job's folder name is switched to PARENT_FOLDER when that is the
+    /// configured authoritative source.
+    ///
+    /// # Arguments
+    ///
+    /// * `job` - Job parsed from the folder, carrying the structural folder name
+    /// * `report` - Parse report receiving the mismatch
+    fn reconcile_parent_folder(&self, job: &mut Job, report: &mut ParseReport) {
+        let parent_folder = match job.parent_folder.as_deref().map(str::trim) {
+            Some(parent) if !parent.is_empty() && parent != job.folder_name => parent.to_string(),
+            _ => return,
+        };
+
+        let authoritative = match self.folder_name_source {
+            FolderNameSource::Structural => &job.folder_name,
+            FolderNameSource::ParentFolder => &parent_folder,
+        };
+        report.warnings.push(format!(
+            "Job '{}' is in folder '{}' but its PARENT_FOLDER is '{}'; using '{}'",
+            job.job_name, job.folder_name, parent_folder, authoritative
+        ));
+        report.folder_mismatches.push(FolderMismatch {
+            job_name: job.job_name.clone(),
+            structural_folder: job.folder_name.clone(),
+            parent_folder: parent_folder.clone(),
+        });
+
+        if self.folder_name_source == FolderNameSource::ParentFolder {
+            job.folder_name = parent_folder;
+        }
+    }
+
     /// Parses a job node from XML
     ///
     /// Extracts all job attributes, scheduling information, and child elements
@@ -235,17 +722,23 @@ impl ControlMXmlParser {
     ///
     /// * `node` - XML node representing the job
     /// * `folder_name` - Name of the parent folder
+    /// * `report` - Parse report receiving skipped child elements
     ///
     /// # Returns
     ///
     /// Result containing a Job entity or an error
-    fn parse_job_node(&self, node: &roxmltree::Node, folder_name: String) -> Result<Job> {
-        let job_name = node.attribute("JOBNAME").unwrap_or("UNKNOWN").to_string();
+    fn parse_job_node(&self, node: &roxmltree::Node, folder_name: String, report: &mut ParseReport) -> Result<Job> {
+        let job_name = self.attr(node, "JOBNAME").unwrap_or("UNKNOWN").to_string();
         let mut job = Job::new(job_name, folder_name);
         
         self.parse_basic_attributes(node, &mut job);
         self.parse_scheduling_attributes(node, &mut job);
-        self.parse_child_elements(node, &mut job);
+        self.parse_child_elements(node, &mut job, report);
+        job.file_transfers = FileTransferSpec::detect(
+            job.cmdline.as_deref(),
+            self.appl_type_aliases.categorize(job.appl_type.as_deref()),
+            &job.variables,
+        );
         
         Ok(job)
     }
@@ -260,126 +753,126 @@ impl ControlMXmlParser {
     /// * `job` - Mutable reference to Job to populate
     fn parse_basic_attributes(&self, node: &roxmltree::Node, job: &mut Job) {
         // Core attributes
-        job.application = node.attribute("APPLICATION").map(|s| s.to_string());
-        job.sub_application = node.attribute("SUB_APPLICATION").map(|s| s.to_string());
-        job.appl_type = node.attribute("APPL_TYPE").map(|s| s.to_string());
-        job.appl_ver = node.attribute("APPL_VER").map(|s| s.to_string());
-        job.description = node.attribute("DESCRIPTION").map(|s| s.to_string());
-        job.owner = node.attribute("OWNER").map(|s| s.to_string());
-        job.run_as = node.attribute("RUN_AS").map(|s| s.to_string());
-        job.priority = node.attribute("PRIORITY").map(|s| s.to_string());
-        job.critical = node.attribute("CRITICAL") == Some("Y");
-        job.task_type = node.attribute("TASKTYPE").map(|s| s.to_string());
-        job.cyclic = node.attribute("CYCLIC") == Some("Y");
-        job.node_id = node.attribute("NODEID").map(|s| s.to_string());
-        job.cmdline = node.attribute("CMDLINE").map(|s| s.to_string());
+        job.application = self.get_str_attr(node, "APPLICATION");
+        job.sub_application = self.get_str_attr(node, "SUB_APPLICATION");
+        job.appl_type = self.get_str_attr(node, "APPL_TYPE");
+        job.appl_ver = self.get_str_attr(node, "APPL_VER");
+        job.description = self.get_str_attr(node, "DESCRIPTION");
+        job.owner = self.get_str_attr(node, "OWNER");
+        job.run_as = self.get_str_attr(node, "RUN_AS");
+        job.priority = self.get_str_attr(node, "PRIORITY");
+        job.critical = self.attr(node, "CRITICAL") == Some("Y");
+        job.task_type = self.get_str_attr(node, "TASKTYPE");
+        job.cyclic = self.attr(node, "CYCLIC") == Some("Y");
+        job.node_id = self.get_str_attr(node, "NODEID");
+        job.cmdline = self.get_str_attr(node, "CMDLINE");
         
         // Additional job metadata
         job.jobisn = self.get_int_attr(node, "JOBISN");
-        job.group = node.attribute("GROUP").map(|s| s.to_string());
-        job.memname = node.attribute("MEMNAME").map(|s| s.to_string());
-        job.author = node.attribute("AUTHOR").map(|s| s.to_string());
-        job.doclib = node.attribute("DOCLIB").map(|s| s.to_string());
-        job.docmem = node.attribute("DOCMEM").map(|s| s.to_string());
-        job.interval = node.attribute("INTERVAL").map(|s| s.to_string());
-        job.override_path = node.attribute("OVERRIDE_PATH").map(|s| s.to_string());
-        job.overlib = node.attribute("OVERLIB").map(|s| s.to_string());
-        job.memlib = node.attribute("MEMLIB").map(|s| s.to_string());
-        job.confirm = node.attribute("CONFIRM").map(|s| s.to_string());
-        job.retro = node.attribute("RETRO").map(|s| s.to_string());
+        job.group = self.get_str_attr(node, "GROUP");
+        job.memname = self.get_str_attr(node, "MEMNAME");
+        job.author = self.get_str_attr(node, "AUTHOR");
+        job.doclib = self.get_str_attr(node, "DOCLIB");
+        job.docmem = self.get_str_attr(node, "DOCMEM");
+        job.interval = self.get_str_attr(node, "INTERVAL");
+        job.override_path = self.get_str_attr(node, "OVERRIDE_PATH");
+        job.overlib = self.get_str_attr(node, "OVERLIB");
+        job.memlib = self.get_str_attr(node, "MEMLIB");
+        job.confirm = self.get_str_attr(node, "CONFIRM");
+        job.retro = self.get_str_attr(node, "RETRO");
         job.maxwait = self.get_int_attr(node, "MAXWAIT");
         job.maxrerun = self.get_int_attr(node, "MAXRERUN");
-        job.autoarch = node.attribute("AUTOARCH").map(|s| s.to_string());
+        job.autoarch = self.get_str_attr(node, "AUTOARCH");
         job.maxdays = self.get_int_attr(node, "MAXDAYS");
         job.maxruns = self.get_int_attr(node, "MAXRUNS");
         
         // Scheduling attributes
-        job.days = node.attribute("DAYS").map(|s| s.to_string());
-        job.weekdays = node.attribute("WEEKDAYS").map(|s| s.to_string());
-        job.jan = node.attribute("JAN").map(|s| s.to_string());
-        job.feb = node.attribute("FEB").map(|s| s.to_string());
-        job.mar = node.attribute("MAR").map(|s| s.to_string());
-        job.apr = node.attribute("APR").map(|s| s.to_string());
-        job.may = node.attribute("MAY").map(|s| s.to_string());
-        job.jun = node.attribute("JUN").map(|s| s.to_string());
-        job.jul = node.attribute("JUL").map(|s| s.to_string());
-        job.aug = node.attribute("AUG").map(|s| s.to_string());
-        job.sep = node.attribute("SEP").map(|s| s.to_string());
-        job.oct = node.attribute("OCT").map(|s| s.to_string());
-        job.nov = node.attribute("NOV").map(|s| s.to_string());
-        job.dec = node.attribute("DEC").map(|s| s.to_string());
-        job.date = node.attribute("DATE").map(|s| s.to_string());
-        job.rerunmem = node.attribute("RERUNMEM").map(|s| s.to_string());
-        job.days_and_or = node.attribute("DAYS_AND_OR").map(|s| s.to_string());
-        job.category = node.attribute("CATEGORY").map(|s| s.to_string());
-        job.shift = node.attribute("SHIFT").map(|s| s.to_string());
-        job.shiftnum = node.attribute("SHIFTNUM").map(|s| s.to_string());
-        job.pdsname = node.attribute("PDSNAME").map(|s| s.to_string());
-        job.minimum = node.attribute("MINIMUM").map(|s| s.to_string());
-        job.preventnct2 = node.attribute("PREVENTNCT2").map(|s| s.to_string());
-        job.option = node.attribute("OPTION").map(|s| s.to_string());
-        job.from = node.attribute("FROM").map(|s| s.to_string());
-        job.par = node.attribute("PAR").map(|s| s.to_string());
-        job.sysdb = node.attribute("SYSDB").map(|s| s.to_string());
-        job.due_out = node.attribute("DUE_OUT").map(|s| s.to_string());
-        job.reten_days = node.attribute("RETEN_DAYS").map(|s| s.to_string());
-        job.reten_gen = node.attribute("RETEN_GEN").map(|s| s.to_string());
-        job.task_class = node.attribute("TASK_CLASS").map(|s| s.to_string());
-        job.prev_day = node.attribute("PREV_DAY").map(|s| s.to_string());
-        job.adjust_cond = node.attribute("ADJUST_COND").map(|s| s.to_string());
-        job.jobs_in_group = node.attribute("JOBS_IN_GROUP").map(|s| s.to_string());
-        job.large_size = node.attribute("LARGE_SIZE").map(|s| s.to_string());
-        job.ind_cyclic = node.attribute("IND_CYCLIC").map(|s| s.to_string());
+        job.days = self.get_str_attr(node, "DAYS");
+        job.weekdays = self.get_str_attr(node, "WEEKDAYS");
+        job.jan = self.get_str_attr(node, "JAN");
+        job.feb = self.get_str_attr(node, "FEB");
+        job.mar = self.get_str_attr(node, "MAR");
+        job.apr = self.get_str_attr(node, "APR");
+        job.may = self.get_str_attr(node, "MAY");
+        job.jun = self.get_str_attr(node, "JUN");
+        job.jul = self.get_str_attr(node, "JUL");
+        job.aug = self.get_str_attr(node, "AUG");
+        job.sep = self.get_str_attr(node, "SEP");
+        job.oct = self.get_str_attr(node, "OCT");
+        job.nov = self.get_str_attr(node, "NOV");
+        job.dec = self.get_str_attr(node, "DEC");
+        job.date = self.get_str_attr(node, "DATE");
+        job.rerunmem = self.get_str_attr(node, "RERUNMEM");
+        job.days_and_or = self.get_str_attr(node, "DAYS_AND_OR");
+        job.category = self.get_str_attr(node, "CATEGORY");
+        job.shift = self.get_str_attr(node, "SHIFT");
+        job.shiftnum = self.get_str_attr(node, "SHIFTNUM");
+        job.pdsname = self.get_str_attr(node, "PDSNAME");
+        job.minimum = self.get_str_attr(node, "MINIMUM");
+        job.preventnct2 = self.get_str_attr(node, "PREVENTNCT2");
+        job.option = self.get_str_attr(node, "OPTION");
+        job.from = self.get_str_attr(node, "FROM");
+        job.par = self.get_str_attr(node, "PAR");
+        job.sysdb = self.get_str_attr(node, "SYSDB");
+        job.due_out = self.get_str_attr(node, "DUE_OUT");
+        job.reten_days = self.get_str_attr(node, "RETEN_DAYS");
+        job.reten_gen = self.get_str_attr(node, "RETEN_GEN");
+        job.task_class = self.get_str_attr(node, "TASK_CLASS");
+        job.prev_day = self.get_str_attr(node, "PREV_DAY");
+        job.adjust_cond = self.get_str_attr(node, "ADJUST_COND");
+        job.jobs_in_group = self.get_str_attr(node, "JOBS_IN_GROUP");
+        job.large_size = self.get_str_attr(node, "LARGE_SIZE");
+        job.ind_cyclic = self.get_str_attr(node, "IND_CYCLIC");
         
         // Audit fields
-        job.creation_user = node.attribute("CREATION_USER").map(|s| s.to_string());
-        job.creation_time = node.attribute("CREATION_TIME").map(|s| s.to_string());
-        job.created_by = node.attribute("CREATED_BY").map(|s| s.to_string());
-        job.creation_date = node.attribute("CREATION_DATE").map(|s| s.to_string());
-        job.change_userid = node.attribute("CHANGE_USERID").map(|s| s.to_string());
-        job.change_date = node.attribute("CHANGE_DATE").map(|s| s.to_string());
-        job.change_time = node.attribute("CHANGE_TIME").map(|s| s.to_string());
+        job.creation_user = self.get_str_attr(node, "CREATION_USER");
+        job.creation_time = self.get_str_attr(node, "CREATION_TIME");
+        job.created_by = self.get_str_attr(node, "CREATED_BY");
+        job.creation_date = self.get_str_attr(node, "CREATION_DATE");
+        job.change_userid = self.get_str_attr(node, "CHANGE_USERID");
+        job.change_date = self.get_str_attr(node, "CHANGE_DATE");
+        job.change_time = self.get_str_attr(node, "CHANGE_TIME");
         
         // Version control
-        job.job_version = node.attribute("JOB_VERSION").map(|s| s.to_string());
-        job.version_opcode = node.attribute("VERSION_OPCODE").map(|s| s.to_string());
-        job.is_current_version = node.attribute("IS_CURRENT_VERSION").map(|s| s.to_string());
+        job.job_version = self.get_str_attr(node, "JOB_VERSION");
+        job.version_opcode = self.get_str_attr(node, "VERSION_OPCODE");
+        job.is_current_version = self.get_str_attr(node, "IS_CURRENT_VERSION");
         job.version_serial = self.get_int_attr(node, "VERSION_SERIAL");
-        job.version_host = node.attribute("VERSION_HOST").map(|s| s.to_string());
+        job.version_host = self.get_str_attr(node, "VERSION_HOST");
         
         // Advanced features
-        job.rule_based_calendar_relationship = node.attribute("RULE_BASED_CALENDAR_RELATIONSHIP").map(|s| s.to_string());
-        job.tag_relationship = node.attribute("TAG_RELATIONSHIP").map(|s| s.to_string());
-        job.timezone = node.attribute("TIMEZONE").map(|s| s.to_string());
-        job.appl_form = node.attribute("APPL_FORM").map(|s| s.to_string());
-        job.cm_ver = node.attribute("CM_VER").map(|s| s.to_string());
-        job.multy_agent = node.attribute("MULTY_AGENT").map(|s| s.to_string());
-        job.active_from = node.attribute("ACTIVE_FROM").map(|s| s.to_string());
-        job.active_till = node.attribute("ACTIVE_TILL").map(|s| s.to_string());
-        job.scheduling_environment = node.attribute("SCHEDULING_ENVIRONMENT").map(|s| s.to_string());
-        job.system_affinity = node.attribute("SYSTEM_AFFINITY").map(|s| s.to_string());
-        job.request_nje_node = node.attribute("REQUEST_NJE_NODE").map(|s| s.to_string());
-        job.stat_cal = node.attribute("STAT_CAL").map(|s| s.to_string());
-        job.instream_jcl = node.attribute("INSTREAM_JCL").map(|s| s.to_string());
-        job.use_instream_jcl = node.attribute("USE_INSTREAM_JCL").map(|s| s.to_string());
-        job.due_out_daysoffset = node.attribute("DUE_OUT_DAYSOFFSET").map(|s| s.to_string());
-        job.from_daysoffset = node.attribute("FROM_DAYSOFFSET").map(|s| s.to_string());
-        job.to_daysoffset = node.attribute("TO_DAYSOFFSET").map(|s| s.to_string());
+        job.rule_based_calendar_relationship = self.get_str_attr(node, "RULE_BASED_CALENDAR_RELATIONSHIP");
+        job.tag_relationship = self.get_str_attr(node, "TAG_RELATIONSHIP");
+        job.timezone = self.get_str_attr(node, "TIMEZONE");
+        job.appl_form = self.get_str_attr(node, "APPL_FORM");
+        job.cm_ver = self.get_str_attr(node, "CM_VER");
+        job.multy_agent = self.get_str_attr(node, "MULTY_AGENT");
+        job.active_from = self.get_str_attr(node, "ACTIVE_FROM");
+        job.active_till = self.get_str_attr(node, "ACTIVE_TILL");
+        job.scheduling_environment = self.get_str_attr(node, "SCHEDULING_ENVIRONMENT");
+        job.system_affinity = self.get_str_attr(node, "SYSTEM_AFFINITY");
+        job.request_nje_node = self.get_str_attr(node, "REQUEST_NJE_NODE");
+        job.stat_cal = self.get_str_attr(node, "STAT_CAL");
+        job.instream_jcl = self.get_str_attr(node, "INSTREAM_JCL");
+        job.use_instream_jcl = self.get_str_attr(node, "USE_INSTREAM_JCL");
+        job.due_out_daysoffset = self.get_str_attr(node, "DUE_OUT_DAYSOFFSET");
+        job.from_daysoffset = self.get_str_attr(node, "FROM_DAYSOFFSET");
+        job.to_daysoffset = self.get_str_attr(node, "TO_DAYSOFFSET");
         
         // Cyclic attributes
-        job.cyclic_interval_sequence = node.attribute("CYCLIC_INTERVAL_SEQUENCE").map(|s| s.to_string());
-        job.cyclic_times_sequence = node.attribute("CYCLIC_TIMES_SEQUENCE").map(|s| s.to_string());
+        job.cyclic_interval_sequence = self.get_str_attr(node, "CYCLIC_INTERVAL_SEQUENCE");
+        job.cyclic_times_sequence = self.get_str_attr(node, "CYCLIC_TIMES_SEQUENCE");
         job.cyclic_tolerance = self.get_int_attr(node, "CYCLIC_TOLERANCE");
-        job.cyclic_type = node.attribute("CYCLIC_TYPE").map(|s| s.to_string());
+        job.cyclic_type = self.get_str_attr(node, "CYCLIC_TYPE");
         
         // Hierarchy
-        job.parent_folder = node.attribute("PARENT_FOLDER").map(|s| s.to_string());
-        job.parent_table = node.attribute("PARENT_TABLE").map(|s| s.to_string());
-        job.end_folder = node.attribute("END_FOLDER").map(|s| s.to_string());
-        job.odate = node.attribute("ODATE").map(|s| s.to_string());
-        job.fprocs = node.attribute("FPROCS").map(|s| s.to_string());
-        job.tpgms = node.attribute("TPGMS").map(|s| s.to_string());
-        job.tprocs = node.attribute("TPROCS").map(|s| s.to_string());
+        job.parent_folder = self.get_str_attr(node, "PARENT_FOLDER");
+        job.parent_table = self.get_str_attr(node, "PARENT_TABLE");
+        job.end_folder = self.get_str_attr(node, "END_FOLDER");
+        job.odate = self.get_str_attr(node, "ODATE");
+        job.fprocs = self.get_str_attr(node, "FPROCS");
+        job.tpgms = self.get_str_attr(node, "TPGMS");
+        job.tprocs = self.get_str_attr(node, "TPROCS");
     }
     
     /// Parses scheduling-related attributes from XML node
@@ -391,61 +884,86 @@ impl ControlMXmlParser {
     /// * `node` - XML node containing scheduling attributes
     /// * `job` - Mutable reference to Job to populate
     fn parse_scheduling_attributes(&self, node: &roxmltree::Node, job: &mut Job) {
-        job.scheduling.time_from = node.attribute("TIMEFROM").map(|s| s.to_string());
-        job.scheduling.time_to = node.attribute("TIMETO").map(|s| s.to_string());
-        job.scheduling.days = node.attribute("DAYS").map(|s| s.to_string());
-        job.scheduling.weekdays = node.attribute("WEEKDAYS").map(|s| s.to_string());
-        job.scheduling.days_calendar = node.attribute("DAYSCAL").map(|s| s.to_string());
-        job.scheduling.weeks_calendar = node.attribute("WEEKSCAL").map(|s| s.to_string());
-        job.scheduling.conf_calendar = node.attribute("CONFCAL").map(|s| s.to_string());
-        job.scheduling.cyclic_interval = node.attribute("INTERVAL").map(|s| s.to_string());
+        job.scheduling.time_from = self.get_str_attr(node, "TIMEFROM");
+        job.scheduling.time_to = self.get_str_attr(node, "TIMETO");
+        job.scheduling.days = self.get_str_attr(node, "DAYS");
+        job.scheduling.weekdays = self.get_str_attr(node, "WEEKDAYS");
+        job.scheduling.days_calendar = self.get_str_attr(node, "DAYSCAL");
+        job.scheduling.weeks_calendar = self.get_str_attr(node, "WEEKSCAL");
+        job.scheduling.conf_calendar = self.get_str_attr(node, "CONFCAL");
+        job.scheduling.cyclic_interval = self.get_str_attr(node, "INTERVAL");
         job.scheduling.max_wait = self.get_int_attr(node, "MAXWAIT");
         job.scheduling.max_rerun = self.get_int_attr(node, "MAXRERUN");
         
         // Additional scheduling attributes
-        job.scheduling.shift = node.attribute("SHIFT").map(|s| s.to_string());
-        job.scheduling.shift_num = node.attribute("SHIFTNUM").map(|s| s.to_string());
-        job.scheduling.retro = node.attribute("RETRO").map(|s| s.to_string());
-        job.scheduling.stat_cal = node.attribute("STAT_CAL").map(|s| s.to_string());
-        job.scheduling.date = node.attribute("DATE").map(|s| s.to_string());
-        job.scheduling.days_and_or = node.attribute("DAYS_AND_OR").map(|s| s.to_string());
+        job.scheduling.shift = self.get_str_attr(node, "SHIFT");
+        job.scheduling.shift_num = self.get_str_attr(node, "SHIFTNUM");
+        job.scheduling.retro = self.get_str_attr(node, "RETRO");
+        job.scheduling.stat_cal = self.get_str_attr(node, "STAT_CAL");
+        job.scheduling.date = self.get_str_attr(node, "DATE");
+        job.scheduling.days_and_or = self.get_str_attr(node, "DAYS_AND_OR");
         job.scheduling.maxdays = self.get_int_attr(node, "MAXDAYS");
         job.scheduling.maxruns = self.get_int_attr(node, "MAXRUNS");
-        job.scheduling.autoarch = node.attribute("AUTOARCH").map(|s| s.to_string());
-        job.scheduling.confirm = node.attribute("CONFIRM").map(|s| s.to_string());
-        job.scheduling.timezone = node.attribute("TIMEZONE").map(|s| s.to_string());
-        job.scheduling.active_from = node.attribute("ACTIVE_FROM").map(|s| s.to_string());
-        job.scheduling.active_till = node.attribute("ACTIVE_TILL").map(|s| s.to_string());
-        job.scheduling.due_out = node.attribute("DUE_OUT").map(|s| s.to_string());
-        job.scheduling.due_out_daysoffset = node.attribute("DUE_OUT_DAYSOFFSET").map(|s| s.to_string());
-        job.scheduling.from_daysoffset = node.attribute("FROM_DAYSOFFSET").map(|s| s.to_string());
-        job.scheduling.to_daysoffset = node.attribute("TO_DAYSOFFSET").map(|s| s.to_string());
-        job.scheduling.prev_day = node.attribute("PREV_DAY").map(|s| s.to_string());
-        job.scheduling.adjust_cond = node.attribute("ADJUST_COND").map(|s| s.to_string());
+        job.scheduling.autoarch = self.get_str_attr(node, "AUTOARCH");
+        job.scheduling.confirm = self.get_str_attr(node, "CONFIRM");
+        job.scheduling.timezone = self.get_str_attr(node, "TIMEZONE");
+        job.scheduling.active_from = self.get_str_attr(node, "ACTIVE_FROM");
+        job.scheduling.active_till = self.get_str_attr(node, "ACTIVE_TILL");
+        job.scheduling.due_out = self.get_str_attr(node, "DUE_OUT");
+        job.scheduling.due_out_daysoffset = self.get_str_attr(node, "DUE_OUT_DAYSOFFSET");
+        job.scheduling.from_daysoffset = self.get_str_attr(node, "FROM_DAYSOFFSET");
+        job.scheduling.to_daysoffset = self.get_str_attr(node, "TO_DAYSOFFSET");
+        job.scheduling.prev_day = self.get_str_attr(node, "PREV_DAY");
+        job.scheduling.adjust_cond = self.get_str_attr(node, "ADJUST_COND");
     }
     
     /// Parses child elements of a job node
     ///
-    /// Processes INCOND, OUTCOND, VARIABLE, CONTROL, QUANTITATIVE, and ON elements.
+    /// Processes INCOND, OUTCOND, VARIABLE, CONTROL, QUANTITATIVE, ON, and SHOUT elements.
     ///
     /// # Arguments
     ///
     /// * `node` - XML node containing child elements
     /// * `job` - Mutable reference to Job to populate
-    fn parse_child_elements(&self, node: &roxmltree::Node, job: &mut Job) {
+    /// * `report` - Parse report receiving unknown or incomplete elements
+    fn parse_child_elements(&self, node: &roxmltree::Node, job: &mut Job, report: &mut ParseReport) {
         for child in node.children().filter(|n| n.is_element()) {
-            match child.tag_name().name() {
+            let tag = child.tag_name().name();
+            if !KNOWN_JOB_CHILDREN.contains(&tag) {
+                report.push_issue(IssueSeverity::Warning, Some(Self::line_of(&child)), format!(
+                    "Ignored unknown <{}> element in job '{}'", tag, job.job_name
+                ));
+                continue;
+            }
+            let required: &[&str] = match tag {
+                "INCOND" | "OUTCOND" | "CONTROL" | "QUANTITATIVE" => &["NAME"],
+                "VARIABLE" => &["NAME", "VALUE"],
+                _ => &[],
+            };
+            if let Some(missing) = required.iter().find(|attr| self.attr(&child, attr).is_none()) {
+                report.push_issue(IssueSeverity::Warning, Some(Self::line_of(&child)), format!(
+                    "Skipped <{}> in job '{}': no {} attribute", tag, job.job_name, missing
+                ));
+                continue;
+            }
+            match tag {
                 "INCOND" => self.parse_in_condition(&child, job),
                 "OUTCOND" => self.parse_out_condition(&child, job),
                 "VARIABLE" => self.parse_variable(&child, job),
                 "CONTROL" => self.parse_control_resource(&child, job),
                 "QUANTITATIVE" => self.parse_quantitative_resource(&child, job),
                 "ON" => self.parse_on_condition(&child, job),
+                "SHOUT" => self.parse_shout(&child, job),
                 _ => {}
             }
         }
     }
     
+    /// Gets the 1-based line an element starts on
+    fn line_of(node: &roxmltree::Node) -> u32 {
+        node.document().text_pos_at(node.range().start).row
+    }
+
     /// Parses an input condition (INCOND) element
     ///
     /// # Arguments
@@ -453,10 +971,10 @@ impl ControlMXmlParser {
     /// * `node` - XML node representing the input condition
     /// * `job` - Mutable reference to Job to add condition to
     fn parse_in_condition(&self, node: &roxmltree::Node, job: &mut Job) {
-        if let Some(name) = node.attribute("NAME") {
+        if let Some(name) = self.attr(node, "NAME") {
             let mut condition = Condition::new_in(name.to_string());
-            condition.odate = node.attribute("ODATE").map(|s| s.to_string());
-            condition.and_or = node.attribute("AND_OR").map(|s| s.to_string());
+            condition.odate = self.get_str_attr(node, "ODATE");
+            condition.and_or = self.get_str_attr(node, "AND_OR");
             job.in_conditions.push(condition);
         }
     }
@@ -468,11 +986,10 @@ impl ControlMXmlParser {
     /// * `node` - XML node representing the output condition
     /// * `job` - Mutable reference to Job to add condition to
     fn parse_out_condition(&self, node: &roxmltree::Node, job: &mut Job) {
-        if let Some(name) = node.attribute("NAME") {
+        if let Some(name) = self.attr(node, "NAME") {
             let mut condition = Condition::new_out(name.to_string());
-            condition.odate = node.attribute("ODATE").map(|s| s.to_string());
-            // Note: SIGN attribute is stored in and_or field for out conditions
-            condition.and_or = node.attribute("SIGN").map(|s| s.to_string());
+            condition.odate = self.get_str_attr(node, "ODATE");
+            condition.sign = self.get_str_attr(node, "SIGN");
             job.out_conditions.push(condition);
         }
     }
@@ -484,39 +1001,46 @@ impl ControlMXmlParser {
     /// * `node` - XML node representing the variable
     /// * `job` - Mutable reference to Job to add variable to
     fn parse_variable(&self, node: &roxmltree::Node, job: &mut Job) {
-        if let (Some(name), Some(value)) = (node.attribute("NAME"), node.attribute("VALUE")) {
+        if let (Some(name), Some(value)) = (self.attr(node, "NAME"), self.attr(node, "VALUE")) {
             job.variables.insert(name.to_string(), value.to_string());
         }
     }
     
     /// Parses a control resource (CONTROL) element
     ///
-    /// Control resources act as mutexes for job synchronization.
+    /// Control resources act as mutexes for job synchronization. ONFAIL,
+    /// when present, is kept as the resource's release behavior.
     ///
     /// # Arguments
     ///
     /// * `node` - XML node representing the control resource
     /// * `job` - Mutable reference to Job to add resource to
     fn parse_control_resource(&self, node: &roxmltree::Node, job: &mut Job) {
-        if let Some(name) = node.attribute("NAME") {
-            job.control_resources.push(ControlResource::new(name.to_string()));
+        if let Some(name) = self.attr(node, "NAME") {
+            let on_fail = self.get_str_attr(node, "ONFAIL");
+            job.control_resources.push(ControlResource::new(name.to_string()).with_on_fail(on_fail));
         }
     }
     
     /// Parses a quantitative resource (QUANTITATIVE) element
     ///
-    /// Quantitative resources manage limited resource pools.
+    /// Quantitative resources manage limited resource pools. ONFAIL and ONOK
+    /// are kept as the resource's release behavior.
     ///
     /// # Arguments
     ///
     /// * `node` - XML node representing the quantitative resource
     /// * `job` - Mutable reference to Job to add resource to
     fn parse_quantitative_resource(&self, node: &roxmltree::Node, job: &mut Job) {
-        if let Some(name) = node.attribute("NAME") {
-            let quant = node.attribute("QUANT")
+        if let Some(name) = self.attr(node, "NAME") {
+            let quant = self.attr(node, "QUANT")
                 .and_then(|q| q.parse::<i32>().ok())
                 .unwrap_or(1);
-            job.quantitative_resources.push(QuantitativeResource::new(name.to_string(), quant));
+            let on_fail = self.get_str_attr(node, "ONFAIL");
+            let on_ok = self.get_str_attr(node, "ONOK");
+            job.quantitative_resources.push(
+                QuantitativeResource::new(name.to_string(), quant).with_fail_ok(on_fail, on_ok),
+            );
         }
     }
     
@@ -531,20 +1055,104 @@ impl ControlMXmlParser {
     /// * `job` - Mutable reference to Job to add ON condition to
     fn parse_on_condition(&self, node: &roxmltree::Node, job: &mut Job) {
         let mut on_cond = OnCondition::new();
-        on_cond.stmt = node.attribute("STMT").map(|s| s.to_string());
-        on_cond.code = node.attribute("CODE").map(|s| s.to_string());
+        on_cond.stmt = self.get_str_attr(node, "STMT");
+        on_cond.code = self.get_str_attr(node, "CODE");
+        on_cond.pattern = self.get_str_attr(node, "PATTERN");
         
         for action_node in node.children().filter(|n| n.is_element()) {
-            if action_node.tag_name().name() == "DOACTION" {
-                if let Some(action) = action_node.attribute("ACTION") {
-                    on_cond.actions.push(DoAction::Action(action.to_string()));
-                }
+            if let Some(action) = self.parse_do_action(&action_node) {
+                on_cond.actions.push(action);
             }
         }
         
         job.on_conditions.push(on_cond);
     }
+
+    /// Parses one DO* action element of an ON block
+    ///
+    /// Recognizes DOACTION, DOCOND, DOFORCEJOB, DOMAIL, DOSHOUT, DOVARIABLE
+    /// and DOAUTOEDIT (whose `EXP` holds `%%NAME=VALUE`).
+    ///
+    /// # Arguments
+    ///
+    /// * `node` - XML node of the action
+    ///
+    /// # Returns
+    ///
+    /// The action, or None if the element is unknown or lacks its
+    /// identifying attribute
+    fn parse_do_action(&self, node: &roxmltree::Node) -> Option<DoAction> {
+        let text = |name: &str| self.get_str_attr(node, name).unwrap_or_default();
+        match node.tag_name().name() {
+            "DOACTION" => self.get_str_attr(node, "ACTION").map(DoAction::Action),
+            "DOCOND" => self.get_str_attr(node, "NAME").map(|name| DoAction::Condition {
+                name,
+                sign: self.get_str_attr(node, "SIGN"),
+            }),
+            "DOFORCEJOB" => self.get_str_attr(node, "NAME").map(|name| DoAction::ForceJob {
+                name,
+                table_name: self.get_str_attr(node, "TABLE_NAME")
+                    .or_else(|| self.get_str_attr(node, "FOLDER_NAME")),
+            }),
+            "DOMAIL" => self.get_str_attr(node, "DEST").map(|dest| DoAction::Mail {
+                dest,
+                message: text("MESSAGE"),
+            }),
+            "DOSHOUT" => self.get_str_attr(node, "DEST").map(|dest| DoAction::Shout {
+                dest,
+                message: text("MESSAGE"),
+            }),
+            "DOVARIABLE" => self.get_str_attr(node, "NAME").map(|name| DoAction::SetVariable {
+                name,
+                value: text("VALUE"),
+            }),
+            "DOAUTOEDIT" => {
+                let expression = self.get_str_attr(node, "EXP")?;
+                let (name, value) = expression.split_once('=').unwrap_or((expression.as_str(), ""));
+                Some(DoAction::SetVariable {
+                    name: name.trim().to_string(),
+                    value: value.trim().to_string(),
+                })
+            }
+            _ => None,
+        }
+    }
     
+    /// Parses a job-level shout (SHOUT) element
+    ///
+    /// # Arguments
+    ///
+    /// * `node` - XML node representing the shout
+    /// * `job` - Mutable reference to Job to add shout to
+    fn parse_shout(&self, node: &roxmltree::Node, job: &mut Job) {
+        if let Some(dest) = self.attr(node, "DEST") {
+            let mut shout = Shout::new(dest.to_string());
+            shout.when = self.get_str_attr(node, "WHEN");
+            shout.urgency = self.get_str_attr(node, "URGENCY");
+            shout.message = self.get_str_attr(node, "MESSAGE");
+            job.shouts.push(shout);
+        }
+    }
+    
+    /// Helper method to read string attributes
+    ///
+    /// Empty attribute values are normalized to None so that a missing
+    /// attribute and an empty one are stored identically (as NULL).
+    ///
+    /// # Arguments
+    ///
+    /// * `node` - XML node containing the attribute
+    /// * `attr_name` - Name of the attribute to read
+    ///
+    /// # Returns
+    ///
+    /// Option containing the attribute value or None if missing or empty
+    fn get_str_attr(&self, node: &roxmltree::Node, attr_name: &str) -> Option<String> {
+        self.attr(node, attr_name)
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+    }
+
     /// Helper method to parse integer attributes
     ///
     /// # Arguments
@@ -556,7 +1164,21 @@ impl ControlMXmlParser {
     ///
     /// Option containing the parsed integer or None if parsing fails
     fn get_int_attr(&self, node: &roxmltree::Node, attr_name: &str) -> Option<i32> {
-        node.attribute(attr_name).and_then(|s| s.parse().ok())
+        self.attr(node, attr_name).and_then(|s| s.parse().ok())
+    }
+
+    /// Helper method to read a raw attribute under any of its known spellings
+    ///
+    /// # Arguments
+    ///
+    /// * `node` - XML node containing the attribute
+    /// * `attr_name` - Canonical name of the attribute
+    ///
+    /// # Returns
+    ///
+    /// Option containing the attribute value or None if no spelling is present
+    fn attr<'a>(&self, node: &roxmltree::Node<'a, '_>, attr_name: &str) -> Option<&'a str> {
+        self.attribute_aliases.attribute(node, attr_name)
     }
 }
 
@@ -591,4 +1213,381 @@ mod tests {
         assert_eq!(folders[0].jobs[0].job_name, "JOB1");
         assert!(folders[0].jobs[0].critical);
     }
+
+    #[test]
+    fn test_resources_keep_onfail_and_onok() {
+        let xml = r#"<DEFTABLE>
+    <FOLDER FOLDER_NAME="RES">
+        <JOB JOBNAME="JOB1">
+            <CONTROL NAME="DB_LOCK" TYPE="E" ONFAIL="R"/>
+            <QUANTITATIVE NAME="CPU_POOL" QUANT="3" ONFAIL="K" ONOK="R"/>
+            <QUANTITATIVE NAME="IO_POOL"/>
+            <CONTROL NAME="FILE_LOCK" TYPE="S" ONFAIL=""/>
+            <QUANTITATIVE NAME="NET_POOL" QUANT="2" ONFAIL="" ONOK=""/>
+        </JOB>
+    </FOLDER>
+</DEFTABLE>"#;
+
+        let folders = ControlMXmlParser::new().parse_xml(xml).unwrap();
+        let job = &folders[0].jobs[0];
+
+        assert_eq!(job.control_resources[0].on_fail.as_deref(), Some("R"));
+        let cpu = &job.quantitative_resources[0];
+        assert_eq!((cpu.quantity, cpu.on_fail.as_deref(), cpu.on_ok.as_deref()), (3, Some("K"), Some("R")));
+        let io = &job.quantitative_resources[1];
+        assert_eq!((io.quantity, io.on_fail.as_deref(), io.on_ok.as_deref()), (1, None, None));
+        assert_eq!(job.control_resources[1].on_fail, None);
+        let net = &job.quantitative_resources[2];
+        assert_eq!((net.quantity, net.on_fail.as_deref(), net.on_ok.as_deref()), (2, None, None));
+    }
+
+    #[test]
+    fn test_alternate_attribute_spellings_populate_same_fields() {
+        let canonical = r#"<DEFTABLE>
+    <FOLDER FOLDER_NAME="F">
+        <JOB JOBNAME="JOB1" TASKTYPE="Command" NODEID="host1" CMDLINE="run.sh" TIMEFROM="0100" MAXRERUN="3"/>
+    </FOLDER>
+</DEFTABLE>"#;
+        let alternate = r#"<DEFTABLE>
+    <FOLDER FOLDERNAME="F">
+        <JOB JOB_NAME="JOB1" TASK_TYPE="Command" NODE_ID="host1" CMD_LINE="run.sh" TIME_FROM="0100" MAX_RERUN="3"/>
+    </FOLDER>
+</DEFTABLE>"#;
+
+        let parser = ControlMXmlParser::new();
+        let expected = parser.parse_xml(canonical).unwrap();
+        let actual = parser.parse_xml(alternate).unwrap();
+
+        assert_eq!(actual[0].folder_name, "F");
+        assert_eq!(actual[0].jobs[0].task_type.as_deref(), Some("Command"));
+        assert_eq!(actual[0].jobs[0], expected[0].jobs[0]);
+
+        // Custom aliases extend the built-in ones
+        let custom = r#"<DEFTABLE><FOLDER FOLDER_NAME="F"><JOB JOBNAME="J" JOB_TYPE="Dummy"/></FOLDER></DEFTABLE>"#;
+        let parser = ControlMXmlParser::new()
+            .with_attribute_aliases(AttributeAliases::new().with_alias("JOB_TYPE", "TASKTYPE"));
+        assert_eq!(parser.parse_xml(custom).unwrap()[0].jobs[0].task_type.as_deref(), Some("Dummy"));
+    }
+
+    #[test]
+    fn test_on_condition_pattern_is_parsed_and_classified() {
+        let xml = r#"<DEFTABLE>
+    <FOLDER FOLDER_NAME="F">
+        <JOB JOBNAME="J">
+            <ON STMT="*" CODE="NOTOK"><DOACTION ACTION="OK"/></ON>
+            <ON STMT="*" CODE="*" PATTERN="*ORA-00060*"><DOACTION ACTION="NOTOK"/></ON>
+        </JOB>
+    </FOLDER>
+</DEFTABLE>"#;
+
+        let folders = ControlMXmlParser::new().parse_xml(xml).unwrap();
+        let on_conditions = &folders[0].jobs[0].on_conditions;
+
+        assert_eq!(on_conditions[0].pattern, None);
+        assert_eq!(on_conditions[0].kind(), OnConditionKind::StatusBased);
+        assert_eq!(on_conditions[1].pattern.as_deref(), Some("*ORA-00060*"));
+        assert_eq!(on_conditions[1].kind(), OnConditionKind::PatternBased);
+        assert!(on_conditions[1].complexity() > on_conditions[0].complexity());
+    }
+
+    #[test]
+    fn test_finds_folders_nested_under_unexpected_root() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<EXPORT>
+    <TERMTABLE>
+        <SMART_FOLDER FOLDER_NAME="NESTED_SMART">
+            <JOB JOBNAME="JOB1"/>
+        </SMART_FOLDER>
+        <FOLDER FOLDER_NAME="NESTED_SIMPLE">
+            <JOB JOBNAME="JOB2"/>
+        </FOLDER>
+    </TERMTABLE>
+</EXPORT>"#;
+
+        let folders = ControlMXmlParser::new().parse_xml(xml).unwrap();
+
+        let names: Vec<_> = folders.iter().map(|f| f.folder_name.as_str()).collect();
+        assert_eq!(names, vec!["NESTED_SMART", "NESTED_SIMPLE"]);
+        assert_eq!(folders[0].folder_type, FolderType::Smart);
+        assert_eq!(folders[1].jobs[0].job_name, "JOB2");
+
+        let bare = ControlMXmlParser::new()
+            .parse_xml(r#"<FOLDER FOLDER_NAME="ROOT"><JOB JOBNAME="JOB3"/></FOLDER>"#)
+            .unwrap();
+        assert_eq!(bare.len(), 1);
+        assert_eq!(bare[0].folder_name, "ROOT");
+    }
+
+    #[test]
+    fn test_parent_folder_mismatch_is_reported() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<DEFTABLE>
+    <FOLDER FOLDER_NAME="NEW_HOME">
+        <JOB JOBNAME="MOVED" PARENT_FOLDER="OLD_HOME"/>
+        <JOB JOBNAME="STAYED" PARENT_FOLDER="NEW_HOME"/>
+    </FOLDER>
+</DEFTABLE>"#;
+
+        let (folders, report) = ControlMXmlParser::new().parse_xml_with_report(xml).unwrap();
+        assert_eq!(report.folder_mismatches, vec![FolderMismatch {
+            job_name: "MOVED".to_string(),
+            structural_folder: "NEW_HOME".to_string(),
+            parent_folder: "OLD_HOME".to_string(),
+        }]);
+        assert!(report.warnings.iter().any(|w| w.contains("MOVED")));
+        assert_eq!(folders[0].jobs[0].folder_name, "NEW_HOME");
+
+        let (folders, _) = ControlMXmlParser::new()
+            .with_folder_name_source(FolderNameSource::ParentFolder)
+            .parse_xml_with_report(xml)
+            .unwrap();
+        assert_eq!(folders[0].jobs[0].folder_name, "OLD_HOME");
+        assert_eq!(folders[0].jobs[1].folder_name, "NEW_HOME");
+    }
+
+    #[test]
+    fn test_jobs_without_jobname_get_unique_synthetic_names() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<DEFTABLE>
+    <FOLDER FOLDER_NAME="TEST_FOLDER">
+        <JOB APPLICATION="APP1"/>
+        <JOB JOBNAME="" APPLICATION="APP2"/>
+        <JOB JOBNAME="JOB3"/>
+    </FOLDER>
+</DEFTABLE>"#;
+
+        let (folders, report) = ControlMXmlParser::new().parse_xml_with_report(xml).unwrap();
+        let names: Vec<_> = folders[0].jobs.iter().map(|j| j.job_name.as_str()).collect();
+
+        assert_eq!(names.len(), 3);
+        assert_ne!(names[0], names[1]);
+        assert!(names[0].starts_with(SYNTHETIC_JOB_NAME_PREFIX));
+        assert!(names[1].starts_with(SYNTHETIC_JOB_NAME_PREFIX));
+        assert_eq!(names[2], "JOB3");
+        assert_eq!(report.warnings.len(), 2);
+        assert_eq!(report.synthetic_job_names, vec![names[0].to_string(), names[1].to_string()]);
+    }
+
+    #[test]
+    fn test_parse_rejects_folders_beyond_max_depth() {
+        // Three levels of folders: one more than the limit below
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<DEFTABLE>
+    <SMART_FOLDER FOLDER_NAME="LEVEL_1">
+        <SUB_FOLDER FOLDER_NAME="LEVEL_2">
+            <SUB_FOLDER FOLDER_NAME="LEVEL_3">
+                <JOB JOBNAME="JOB1"/>
+            </SUB_FOLDER>
+        </SUB_FOLDER>
+    </SMART_FOLDER>
+</DEFTABLE>"#;
+
+        let err = ControlMXmlParser::new().with_max_folder_depth(2).parse_xml(xml).unwrap_err();
+        assert!(err.to_string().contains("maximum depth of 2"));
+
+        let folders = ControlMXmlParser::new().with_max_folder_depth(3).parse_xml(xml).unwrap();
+        assert_eq!(folders[0].total_jobs(), 1);
+    }
+
+    #[test]
+    fn test_parse_nested_folders_recursively() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<DEFTABLE>
+    <SMART_FOLDER FOLDER_NAME="OUTER" DATACENTER="DC1">
+        <JOB JOBNAME="OUTER_JOB"/>
+        <FOLDER FOLDER_NAME="INNER">
+            <JOB JOBNAME="INNER_JOB"/>
+            <SUB_FOLDER FOLDER_NAME="DEEPEST">
+                <JOB JOBNAME="DEEPEST_JOB"/>
+            </SUB_FOLDER>
+        </FOLDER>
+    </SMART_FOLDER>
+</DEFTABLE>"#;
+
+        let folders = ControlMXmlParser::new().parse_xml(xml).unwrap();
+
+        assert_eq!(folders.len(), 1);
+        assert_eq!(folders[0].jobs.len(), 1);
+        let inner = &folders[0].sub_folders[0];
+        assert_eq!(inner.folder_name, "INNER");
+        assert_eq!(inner.folder_type, FolderType::Simple);
+        assert_eq!(inner.jobs[0].job_name, "INNER_JOB");
+        assert_eq!(inner.jobs[0].folder_name, "INNER");
+        assert_eq!(inner.sub_folders[0].jobs[0].job_name, "DEEPEST_JOB");
+        assert_eq!(folders[0].total_jobs(), 3);
+
+        let shallow = ControlMXmlParser::new().with_max_folder_depth(2);
+        assert!(shallow.parse_xml(xml).unwrap_err().to_string().contains("maximum depth of 2"));
+    }
+
+    #[test]
+    fn test_parse_shout_tables() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<DEFTABLE>
+    <SHOUT_DEST_TABLE NAME="SHOUTDEST">
+        <DEST LOGICAL="OPS" PHYSICAL="ops-team@example.com"/>
+    </SHOUT_DEST_TABLE>
+    <FOLDER FOLDER_NAME="TEST_FOLDER">
+        <JOB JOBNAME="JOB1">
+            <SHOUT WHEN="NOTOK" URGENCY="R" DEST="OPS" MESSAGE="Job failed"/>
+        </JOB>
+    </FOLDER>
+</DEFTABLE>"#;
+
+        let parser = ControlMXmlParser::new();
+        let tables = parser.parse_shout_tables(xml).unwrap();
+        let folders = parser.parse_xml(xml).unwrap();
+
+        assert_eq!(tables.len(), 1);
+        assert_eq!(tables[0].resolve("OPS"), Some("ops-team@example.com"));
+        assert_eq!(folders[0].jobs[0].shouts.len(), 1);
+        assert_eq!(folders[0].jobs[0].shouts[0].destination, "OPS");
+        assert_eq!(folders[0].jobs[0].shouts[0].when.as_deref(), Some("NOTOK"));
+    }
+
+    #[test]
+    fn test_parse_shout_tables_inside_wrapper_element() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<EXPORT>
+    <DEFTABLE>
+        <SHOUT_DEST_TABLE NAME="SHOUTDEST">
+            <DEST LOGICAL="OPS" PHYSICAL="ops-team@example.com"/>
+        </SHOUT_DEST_TABLE>
+        <FOLDER FOLDER_NAME="TEST_FOLDER">
+            <JOB JOBNAME="JOB1">
+                <SHOUT WHEN="NOTOK" URGENCY="R" DEST="OPS" MESSAGE="Job failed"/>
+            </JOB>
+        </FOLDER>
+    </DEFTABLE>
+</EXPORT>"#;
+
+        let tables = ControlMXmlParser::new().parse_shout_tables(xml).unwrap();
+
+        assert_eq!(tables.len(), 1);
+        assert_eq!(tables[0].resolve("OPS"), Some("ops-team@example.com"));
+
+        let (folders, report) = ControlMXmlParser::new().parse_xml_with_report(xml).unwrap();
+        assert_eq!(folders.len(), 1);
+        assert_eq!(report.shout_tables, tables);
+    }
+
+    #[test]
+    fn test_streaming_parse_matches_full_parse() {
+        // Windows-1252 bytes (0xE9 = é) and a stray control character (0x01)
+        let mut xml = b"<?xml version=\"1.0\" encoding=\"windows-1252\"?>\n<DEFTABLE xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\">\n".to_vec();
+        xml.extend_from_slice(b"    <FOLDER FOLDER_NAME=\"CAF\xC9\"><JOB JOBNAME=\"JOB1\" DESCRIPTION=\"a\x01b &amp; c\"><OUTCOND NAME=\"JOB1-OK\"/></JOB></FOLDER>\n");
+        xml.extend_from_slice(b"    <SMART_FOLDER FOLDER_NAME=\"SMART\"><JOB JOBNAME=\"JOB2\"><INCOND NAME=\"JOB1-OK\"/></JOB><JOB JOBNAME=\"JOB3\"/></SMART_FOLDER>\n");
+        xml.extend_from_slice(b"    <TABLE TABLE_NAME=\"EMPTY\"/>\n</DEFTABLE>\n");
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("streaming.xml");
+        std::fs::write(&path, &xml).unwrap();
+
+        let parser = ControlMXmlParser::new();
+        let expected = parser.parse_file(&path).unwrap();
+        let mut streamed = Vec::new();
+        let report = parser.parse_file_streaming(&path, |folder| streamed.push(folder)).unwrap();
+
+        assert!(report.warnings.is_empty());
+        let summary = |folders: &[Folder]| -> Vec<(String, FolderType, Vec<String>)> {
+            folders.iter()
+                .map(|f| (f.folder_name.clone(), f.folder_type.clone(), f.all_jobs().iter().map(|j| j.job_name.clone()).collect()))
+                .collect()
+        };
+        assert_eq!(summary(&streamed), summary(&expected));
+        assert_eq!(streamed[0].folder_name, "CAFÉ");
+        assert_eq!(streamed[0].jobs[0].description.as_deref(), Some("ab & c"));
+        assert_eq!(streamed[2].folder_type, FolderType::Table);
+    }
+
+    #[test]
+    fn test_on_block_parses_each_do_action_variant() {
+        let xml = r#"<DEFTABLE>
+    <FOLDER FOLDER_NAME="F">
+        <JOB JOBNAME="JOB1">
+            <ON STMT="*" CODE="NOTOK">
+                <DOACTION ACTION="OK"/>
+                <DOCOND NAME="JOB1-FAILED" ODATE="ODAT" SIGN="ADD"/>
+                <DOFORCEJOB NAME="CLEANUP" TABLE_NAME="MAINT" ODATE="ODAT"/>
+                <DOMAIL DEST="ops@example.com" MESSAGE="JOB1 failed" URGENCY="R"/>
+                <DOSHOUT DEST="EM" MESSAGE="JOB1 failed"/>
+                <DOVARIABLE NAME="%%RETRY" VALUE="1"/>
+                <DOAUTOEDIT EXP="%%STATUS=FAILED"/>
+                <DOFORCEJOB TABLE_NAME="NO_JOB_NAME"/>
+            </ON>
+        </JOB>
+    </FOLDER>
+</DEFTABLE>"#;
+
+        let folders = ControlMXmlParser::new().parse_xml(xml).unwrap();
+        let actions = &folders[0].jobs[0].on_conditions[0].actions;
+
+        assert_eq!(actions, &vec![
+            DoAction::Action("OK".to_string()),
+            DoAction::Condition { name: "JOB1-FAILED".to_string(), sign: Some("ADD".to_string()) },
+            DoAction::ForceJob { name: "CLEANUP".to_string(), table_name: Some("MAINT".to_string()) },
+            DoAction::Mail { dest: "ops@example.com".to_string(), message: "JOB1 failed".to_string() },
+            DoAction::Shout { dest: "EM".to_string(), message: "JOB1 failed".to_string() },
+            DoAction::SetVariable { name: "%%RETRY".to_string(), value: "1".to_string() },
+            DoAction::SetVariable { name: "%%STATUS".to_string(), value: "FAILED".to_string() },
+        ]);
+    }
+
+    #[test]
+    fn test_parse_files_merges_and_warns_about_duplicate_folders() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        std::fs::write(dir.join("dc1.xml"), r#"<DEFTABLE>
+    <FOLDER FOLDER_NAME="ETL" DATACENTER="DC1">
+        <JOB JOBNAME="EXTRACT"><OUTCOND NAME="DATA-READY" SIGN="+"/></JOB>
+    </FOLDER>
+    <FOLDER FOLDER_NAME="SHARED" DATACENTER="DC1"/>
+</DEFTABLE>"#).unwrap();
+        std::fs::write(dir.join("dc2.xml"), r#"<DEFTABLE>
+    <FOLDER FOLDER_NAME="DW" DATACENTER="DC2">
+        <JOB JOBNAME="LOAD"><INCOND NAME="DATA-READY" ODATE="ODAT"/></JOB>
+    </FOLDER>
+    <FOLDER FOLDER_NAME="SHARED" DATACENTER="DC2"/>
+</DEFTABLE>"#).unwrap();
+        let paths = vec![dir.join("dc1.xml"), dir.join("dc2.xml")];
+
+        let (folders, report) = ControlMXmlParser::new().parse_files_with_report(&paths).unwrap();
+
+        let names: Vec<_> = folders.iter().map(|f| f.folder_name.as_str()).collect();
+        assert_eq!(names, vec!["ETL", "SHARED", "DW", "SHARED"]);
+        assert_eq!(report.warnings.len(), 1);
+        assert!(report.warnings[0].contains("Folder SHARED appears in both"));
+        let jobs: Vec<&Job> = folders.iter().flat_map(|f| f.all_jobs()).collect();
+        let producers = crate::application::services::DependencyAnalyzer::producer_map(&jobs);
+        let producer_names: Vec<&str> = producers["DATA-READY"].iter().map(|&p| jobs[p].job_name.as_str()).collect();
+        assert_eq!(producer_names, vec!["EXTRACT"]);
+        let out_cond = &folders[0].jobs[0].out_conditions[0];
+        assert_eq!(out_cond.sign.as_deref(), Some("+"));
+        assert_eq!(out_cond.and_or, None);
+    }
+
+    #[test]
+    fn test_report_lists_skipped_and_unknown_elements_with_lines() {
+        let xml = r#"<DEFTABLE>
+    <FOLDER DATACENTER="DC1">
+        <JOB APPLICATION="APP">
+            <INCOND ODATE="ODAT"/>
+            <STEP_RANGE FROM="1"/>
+        </JOB>
+        <RULE_BASED_CALENDARS/>
+    </FOLDER>
+</DEFTABLE>"#;
+
+        let (_, report) = ControlMXmlParser::new().parse_xml_with_report(xml).unwrap();
+
+        let issues: Vec<_> = report.issues.iter().map(|i| (i.severity, i.line)).collect();
+        assert_eq!(issues, vec![
+            (IssueSeverity::Error, Some(2)),
+            (IssueSeverity::Warning, Some(4)),
+            (IssueSeverity::Warning, Some(5)),
+            (IssueSeverity::Error, Some(3)),
+            (IssueSeverity::Warning, Some(7)),
+        ]);
+        assert_eq!(report.error_count(), 2);
+        assert!(report.issues[2].message.contains("<STEP_RANGE>"));
+    }
 }
@@ -0,0 +1,223 @@
+//! XML sanitizer module
+//!
+//! Control-M exports may contain control characters that are not allowed in
+//! XML 1.0, or bare `&` in free text such as `BATCH & LOAD`, and either makes
+//! the parse fail. This module filters the characters out and escapes the bare
+//! ampersands of a decoded UTF-8 byte stream as it is read, so exports of any
+//! size can be sanitized without holding the whole file in memory.
+
+use std::io::{self, Read};
+
+/// Size of the chunks read from the underlying reader
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Longest reference looked at after a `&`, semicolon included
+///
+/// Covers the predefined entities and any character reference up to
+/// `#x10FFFF;` with a few leading zeros.
+const MAX_REFERENCE_LEN: usize = 16;
+
+/// Entities predefined by XML 1.0, the only ones usable without a DTD
+const PREDEFINED_ENTITIES: [&str; 5] = ["amp", "lt", "gt", "apos", "quot"];
+
+/// What follows a `&` in the stream
+#[derive(Debug, PartialEq, Eq)]
+enum Reference {
+    /// A predefined entity or character reference of this many bytes, `;` included
+    Valid(usize),
+    /// Anything else: the ampersand must be escaped
+    Bare,
+    /// The stream may still complete a reference; wait for more input
+    Incomplete,
+}
+
+/// Classifies the text following a `&`
+///
+/// # Arguments
+///
+/// * `rest` - Text right after the ampersand
+/// * `at_eof` - Whether `rest` runs to the end of the stream
+fn classify_reference(rest: &str, at_eof: bool) -> Reference {
+    let window = &rest.as_bytes()[..rest.len().min(MAX_REFERENCE_LEN)];
+    let Some(end) = window.iter().position(|&b| b == b';') else {
+        return if rest.len() < MAX_REFERENCE_LEN && !at_eof {
+            Reference::Incomplete
+        } else {
+            Reference::Bare
+        };
+    };
+    let name = &rest[..end];
+    let valid = if let Some(hex) = name.strip_prefix("#x") {
+        !hex.is_empty() && hex.bytes().all(|b| b.is_ascii_hexdigit())
+    } else if let Some(digits) = name.strip_prefix('#') {
+        !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit())
+    } else {
+        PREDEFINED_ENTITIES.contains(&name)
+    };
+    if valid {
+        Reference::Valid(end + 1)
+    } else {
+        Reference::Bare
+    }
+}
+
+/// Appends the sanitized form of `text` to `output`
+///
+/// Drops characters not allowed in XML 1.0 and escapes every `&` that does
+/// not start a predefined entity or character reference. HTML entities such
+/// as `&nbsp;` are undeclared in a Control-M export, so they are escaped too
+/// and keep their literal text.
+///
+/// # Returns
+///
+/// Number of bytes of `text` consumed; a trailing `&` that may still start a
+/// reference is left for the next call unless `at_eof` is set
+fn sanitize_into(text: &str, at_eof: bool, output: &mut Vec<u8>) -> usize {
+    let mut utf8 = [0u8; 4];
+    let mut chars = text.char_indices();
+    while let Some((index, c)) = chars.next() {
+        if c == '&' {
+            match classify_reference(&text[index + 1..], at_eof) {
+                Reference::Incomplete => return index,
+                Reference::Bare => output.extend_from_slice(b"&amp;"),
+                Reference::Valid(len) => {
+                    output.extend_from_slice(&text.as_bytes()[index..=index + len]);
+                    // The reference is ASCII: one char per byte
+                    chars.nth(len - 1);
+                }
+            }
+        } else if is_xml_char(c) {
+            output.extend_from_slice(c.encode_utf8(&mut utf8).as_bytes());
+        }
+    }
+    text.len()
+}
+
+/// Checks whether a character is allowed in XML 1.0
+///
+/// According to XML 1.0 spec, valid characters are:
+/// - #x9 | #xA | #xD | [#x20-#xD7FF] | [#xE000-#xFFFD] | [#x10000-#x10FFFF]
+pub(crate) fn is_xml_char(c: char) -> bool {
+    matches!(c as u32,
+        0x09 | 0x0A | 0x0D
+        | 0x20..=0xD7FF
+        | 0xE000..=0xFFFD
+        | 0x10000..=0x10FFFF)
+}
+
+/// Reader adapter removing characters not allowed in XML 1.0 and escaping bare `&`
+///
+/// The inner reader must produce UTF-8. A character or entity reference split
+/// across two reads is held back until it is complete.
+pub(crate) struct SanitizingReader<R> {
+    inner: R,
+    /// Bytes read but not yet sanitized (an incomplete UTF-8 sequence)
+    pending: Vec<u8>,
+    /// Sanitized bytes not yet returned
+    output: Vec<u8>,
+    /// Position of the next byte of `output` to return
+    position: usize,
+}
+
+impl<R: Read> SanitizingReader<R> {
+    /// Wraps a reader producing UTF-8
+    pub(crate) fn new(inner: R) -> Self {
+        Self {
+            inner,
+            pending: Vec::new(),
+            output: Vec::new(),
+            position: 0,
+        }
+    }
+
+    /// Reads and sanitizes the next chunk
+    ///
+    /// # Returns
+    ///
+    /// False once the inner reader is exhausted
+    fn fill(&mut self) -> io::Result<bool> {
+        let mut chunk = vec![0u8; CHUNK_SIZE];
+        let read = self.inner.read(&mut chunk)?;
+        let at_eof = read == 0;
+        if at_eof && self.pending.is_empty() {
+            return Ok(false);
+        }
+        self.pending.extend_from_slice(&chunk[..read]);
+
+        let complete = match std::str::from_utf8(&self.pending) {
+            Ok(text) => text,
+            Err(e) if e.error_len().is_none() && at_eof => {
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "XML stream ends inside a UTF-8 sequence"));
+            }
+            // Only an incomplete sequence at the end is expected; keep it for the next read
+            Err(e) if e.error_len().is_none() => std::str::from_utf8(&self.pending[..e.valid_up_to()])
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+            Err(e) => return Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+        };
+
+        self.output.clear();
+        self.position = 0;
+        let consumed = sanitize_into(complete, at_eof, &mut self.output);
+        self.pending.drain(..consumed);
+        Ok(true)
+    }
+}
+
+impl<R: Read> Read for SanitizingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.position == self.output.len() {
+            if !self.fill()? {
+                return Ok(0);
+            }
+        }
+        let count = buf.len().min(self.output.len() - self.position);
+        buf[..count].copy_from_slice(&self.output[self.position..self.position + count]);
+        self.position += count;
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reader returning one byte per read, splitting every multi-byte character
+    struct ByteByByte<'a>(&'a [u8]);
+
+    impl Read for ByteByByte<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            match self.0.split_first() {
+                Some((byte, rest)) if !buf.is_empty() => {
+                    buf[0] = *byte;
+                    self.0 = rest;
+                    Ok(1)
+                }
+                _ => Ok(0),
+            }
+        }
+    }
+
+    #[test]
+    fn test_removes_control_characters_across_split_reads() {
+        let input = "<A N=\"caf\u{e9}\u{1}\"/>\u{b}\n<B N=\"\u{20ac}\u{fffe}\"/>\t";
+        let mut sanitized = String::new();
+        SanitizingReader::new(ByteByByte(input.as_bytes()))
+            .read_to_string(&mut sanitized)
+            .unwrap();
+        assert_eq!(sanitized, "<A N=\"caf\u{e9}\"/>\n<B N=\"\u{20ac}\"/>\t");
+    }
+
+    #[test]
+    fn test_escapes_bare_ampersands_and_keeps_references() {
+        let input = "<JOB DESCRIPTION=\"R&D BATCH & LOAD &amp; &#38; &#x26; &nbsp;\"/>&";
+        let mut sanitized = String::new();
+        SanitizingReader::new(ByteByByte(input.as_bytes()))
+            .read_to_string(&mut sanitized)
+            .unwrap();
+        assert_eq!(
+            sanitized,
+            "<JOB DESCRIPTION=\"R&amp;D BATCH &amp; LOAD &amp; &#38; &#x26; &amp;nbsp;\"/>&amp;"
+        );
+        assert!(roxmltree::Document::parse(&sanitized.replace("/>&amp;", "/>")).is_ok());
+    }
+}
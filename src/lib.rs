@@ -1,3 +1,12 @@
+//! JobWeaver library
+//!
+//! Analyzes Control-M job definitions for migration to Apache Airflow. The
+//! [`analyze_folders`] and [`analyze_xml_str`] entry points run the full
+//! pipeline (complexity scoring, dependency graph, migration waves) in memory,
+//! without reading or writing files or printing anything, so the analysis can
+//! be embedded in another service. For custom settings, configure an
+//! [`AnalyzeJobs`] use case directly.
+
 pub mod domain;
 pub mod application;
 pub mod infrastructure;
@@ -9,3 +18,75 @@ pub use domain::value_objects;
 pub use application::use_cases;
 pub use infrastructure::parsers;
 pub use infrastructure::output;
+
+pub use application::use_cases::analyze_jobs::AnalysisResult;
+pub use application::use_cases::AnalyzeJobs;
+pub use domain::entities::Folder;
+
+use anyhow::{Context, Result};
+use infrastructure::parsers::ControlMXmlParser;
+
+/// Analyzes parsed folders with the default settings
+///
+/// # Arguments
+///
+/// * `folders` - Folders whose jobs (including sub-folder jobs) are analyzed
+///
+/// # Returns
+///
+/// Result containing the in-memory analysis
+///
+/// # Errors
+///
+/// Returns an error if the analysis fails
+pub fn analyze_folders(folders: &[Folder]) -> Result<AnalysisResult> {
+    AnalyzeJobs::new().execute(folders)
+}
+
+/// Parses a Control-M XML export held in memory and analyzes it
+///
+/// Shout destination tables in the export are used to resolve shout
+/// destinations, as the `analyze` command does.
+///
+/// # Arguments
+///
+/// * `xml_content` - Control-M XML export as UTF-8 text
+///
+/// # Returns
+///
+/// Result containing the in-memory analysis
+///
+/// # Errors
+///
+/// Returns an error if the XML is malformed or the analysis fails
+pub fn analyze_xml_str(xml_content: &str) -> Result<AnalysisResult> {
+    let parser = ControlMXmlParser::new();
+    let (folders, report) = parser.parse_xml_with_report(xml_content)
+        .context("Failed to parse Control-M XML")?;
+    AnalyzeJobs::new()
+        .with_shout_tables(report.shout_tables)
+        .execute(&folders)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_analyze_xml_str_runs_full_pipeline() {
+        let xml = r#"<DEFTABLE>
+    <FOLDER FOLDER_NAME="ETL">
+        <JOB JOBNAME="EXTRACT"><OUTCOND NAME="DATA-READY" SIGN="+"/></JOB>
+        <JOB JOBNAME="LOAD"><INCOND NAME="DATA-READY"/></JOB>
+    </FOLDER>
+</DEFTABLE>"#;
+
+        let result = analyze_xml_str(xml).unwrap();
+
+        assert_eq!((result.total_folders, result.total_jobs), (1, 2));
+        assert_eq!(result.complexity_results.len(), 2);
+        let wave_of = |name: &str| result.complexity_results.iter().find(|r| r.job_name == name).unwrap().migration_wave;
+        assert!(wave_of("EXTRACT") <= wave_of("LOAD"));
+        assert!(analyze_xml_str("<DEFTABLE>").is_err());
+    }
+}
@@ -1,10 +1,12 @@
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use tracing_subscriber::{EnvFilter, fmt};
-use anyhow::Result;
+use anyhow::{Context, Result};
 use std::env;
+use std::fs;
 
-use jobweaver::presentation::cli::{Cli, Commands};
-use jobweaver::presentation::cli::commands::{AnalyzeCommand, ExportSqliteCommand};
+use jobweaver::presentation::cli::{Cli, Commands, LogFormat};
+use jobweaver::presentation::cli::commands::{AnalyzeCommand, AnalyzeOptions, CycleReportCommand, DbStatsCommand, DiffCommand, ExportSqliteCommand, FsckCommand, ValidateCommand};
+use jobweaver::domain::value_objects::{ComplexityWeights, MigrationTarget};
 use jobweaver::web::{WebConfig, start_web_server};
 
 #[tokio::main]
@@ -15,36 +17,126 @@ async fn main() -> Result<()> {
     
     let cli = Cli::parse();
 
-    let filter = if cli.verbose {
-        EnvFilter::new("jobweaver=debug,info")
-    } else {
-        EnvFilter::new("jobweaver=info")
+    // RUST_LOG takes per-module directives, e.g.
+    // "jobweaver=info,jobweaver::web::repository=trace" for the full search breakdown
+    let filter = match EnvFilter::try_from_default_env() {
+        Ok(filter) => filter,
+        Err(_) if cli.verbose => EnvFilter::new("jobweaver=debug,info"),
+        Err(_) => EnvFilter::new("jobweaver=info"),
     };
 
-    fmt()
-        .with_env_filter(filter)
-        .with_target(false)
-        .init();
+    // JSON logs (e.g. for a log aggregator under Kubernetes) keep structured
+    // fields such as job_id, total and elapsed_ms as separate JSON fields
+    let log_format = cli.log_format
+        .or_else(|| env::var("LOG_FORMAT").ok().and_then(|value| LogFormat::from_str(value.trim(), true).ok()))
+        .unwrap_or_default();
+
+    match log_format {
+        LogFormat::Pretty => fmt()
+            .with_env_filter(filter)
+            .with_target(false)
+            .init(),
+        LogFormat::Json => fmt()
+            .json()
+            .with_env_filter(filter)
+            .with_current_span(false)
+            .init(),
+    }
 
     match &cli.command {
-        Commands::Analyze { input, output, format } => {
-            AnalyzeCommand::execute(
-                input,
-                output,
-                format.should_generate_json(),
-                format.should_generate_csv(),
-                format.should_generate_html(),
-                format.should_generate_markdown(),
+        Commands::Analyze { input, output, format, no_per_wave_csv, graph, airflow_dependencies, airflow_variables, output_encoding, html_sections, html_shard_threshold, reference_date, parallel, airflow_version, airflow_providers, appl_type_aliases, attribute_aliases, count_downstream_dependencies, weights, engineers, start_date, bundle, dot_clusters, validate, offline_assets, waves, changed_since, include_undated, anonymize, anonymize_mapping } => {
+            let mut migration_target = MigrationTarget::new();
+            if let Some(airflow_version) = airflow_version {
+                migration_target = migration_target.with_airflow_version(*airflow_version);
+            }
+            for provider in airflow_providers {
+                migration_target = migration_target.with_provider(provider);
+            }
+
+            let complexity_weights = match weights {
+                Some(path) => fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read complexity weights file {}", path.display()))?
+                    .parse::<ComplexityWeights>()
+                    .map_err(anyhow::Error::msg)
+                    .with_context(|| format!("Failed to parse complexity weights file {}", path.display()))?,
+                None => ComplexityWeights::default(),
+            };
+
+            let options = AnalyzeOptions {
+                generate_json: format.should_generate_json(),
+                generate_csv: format.should_generate_csv(),
+                generate_html: format.should_generate_html(),
+                generate_markdown: format.should_generate_markdown(),
+                generate_per_wave_csv: !no_per_wave_csv,
+                graph_format: *graph,
+                generate_airflow_dependencies: *airflow_dependencies,
+                generate_airflow_variables: *airflow_variables,
+                generate_airflow_dags: format.should_generate_airflow(),
+                validate_airflow_dags: *validate,
+                output_encoding: *output_encoding,
+                html_sections: html_sections.clone(),
+                html_shard_threshold: *html_shard_threshold,
+                html_offline_assets: offline_assets.clone(),
+                reference_date: *reference_date,
+                parallel: *parallel,
+                migration_target,
+                appl_type_aliases: appl_type_aliases.clone().unwrap_or_default(),
+                attribute_aliases: attribute_aliases.clone().unwrap_or_default(),
+                count_downstream_dependencies: *count_downstream_dependencies,
+                complexity_weights,
+                engineers: *engineers,
+                start_date: *start_date,
+                bundle_path: bundle.clone(),
+                generate_dot: format.should_generate_dot(),
+                dot_cluster_by_folder: *dot_clusters,
+                wave_count: *waves,
+                changed_since: *changed_since,
+                include_undated: *include_undated,
+                anonymize: *anonymize,
+                anonymize_mapping: anonymize_mapping.clone(),
+            };
+            AnalyzeCommand::execute(input, output, &options)?;
+        }
+        Commands::ExportSqlite { input, output, tokenize_cmdline, folder_name_source, attribute_aliases, mode, anonymize, anonymize_mapping } => {
+            ExportSqliteCommand::execute(
+                input, output, *tokenize_cmdline, *folder_name_source, attribute_aliases.clone().unwrap_or_default(), *mode,
+                *anonymize, anonymize_mapping.as_deref(),
+            )?;
+        }
+        Commands::Validate { input, json, attribute_aliases } => {
+            ValidateCommand::execute(input, *json, attribute_aliases.clone().unwrap_or_default())?;
+        }
+        Commands::Cycles { input, output } => {
+            CycleReportCommand::execute(input, output.as_ref())?;
+        }
+        Commands::Diff { old, new, json, output, attribute_aliases } => {
+            DiffCommand::execute(old, new, *json, output.as_deref(), attribute_aliases.clone().unwrap_or_default())?;
+        }
+        Commands::Fsck { database, fix } => {
+            FsckCommand::execute(database, *fix)?;
+        }
+        Commands::DbStats { database } => {
+            DbStatsCommand::execute(database)?;
+        }
+        #[cfg(feature = "duckdb")]
+        Commands::ExportDuckdb { input, output, folder_name_source, attribute_aliases } => {
+            jobweaver::presentation::cli::commands::ExportDuckDbCommand::execute(
+                input, output, *folder_name_source, attribute_aliases.clone().unwrap_or_default(),
             )?;
         }
-        Commands::ExportSqlite { input, output } => {
-            ExportSqliteCommand::execute(input, output)?;
+        #[cfg(feature = "parquet")]
+        Commands::ExportParquet { input, output, tokenize_cmdline, folder_name_source, attribute_aliases } => {
+            jobweaver::presentation::cli::commands::ExportParquetCommand::execute(
+                input, output, *tokenize_cmdline, *folder_name_source, attribute_aliases.clone().unwrap_or_default(),
+            )?;
         }
-        Commands::Serve { database, port, host } => {
+        Commands::Serve { database, port, host, read_only, analysis_only } => {
             // Create base configuration from command-line arguments
             let mut config = WebConfig::new(database.to_string_lossy().to_string())
                 .with_port(*port)
-                .with_host(host.clone());
+                .with_host(host.clone())
+                .with_read_only(*read_only)
+                .with_analysis_only(*analysis_only);
             
             // Override configuration with environment variables from .env file
             // This allows secure configuration without hardcoding credentials
@@ -64,6 +156,44 @@ async fn main() -> Result<()> {
             if let Ok(password) = env::var("AUTH_PASSWORD") {
                 config.auth_password = password;
             }
+            if let Ok(users_file) = env::var("USERS_FILE") {
+                if !users_file.trim().is_empty() {
+                    config.users_file = Some(users_file);
+                }
+            }
+            if let Ok(admins) = env::var("ADMIN_USERNAMES") {
+                config = config.with_admin_usernames(
+                    admins.split(',')
+                        .map(|name| name.trim().to_string())
+                        .filter(|name| !name.is_empty())
+                        .collect()
+                );
+            }
+            
+            // Server tuning
+            if let Ok(workers) = env::var("WEB_WORKERS") {
+                if let Ok(val) = workers.parse::<usize>() {
+                    config = config.with_workers(val);
+                }
+            }
+            
+            if let Ok(ttl) = env::var("DASHBOARD_CACHE_TTL_SECS") {
+                if let Ok(val) = ttl.parse::<u64>() {
+                    config.dashboard_cache_ttl_secs = val;
+                }
+            }
+            if let Ok(aliases) = env::var("APPL_TYPE_ALIASES") {
+                match aliases.parse() {
+                    Ok(val) => config.appl_type_aliases = val,
+                    Err(e) => tracing::warn!("Ignoring APPL_TYPE_ALIASES: {}", e),
+                }
+            }
+            
+            if let Ok(token_ttl) = env::var("TOKEN_TTL_HOURS") {
+                if let Ok(val) = token_ttl.parse::<u64>() {
+                    config.token_ttl_hours = val;
+                }
+            }
             
             // Login security settings
             if let Ok(max_attempts) = env::var("MAX_LOGIN_ATTEMPTS") {
@@ -76,8 +206,16 @@ async fn main() -> Result<()> {
                     config.lockout_duration_minutes = val;
                 }
             }
+            if let Ok(persist) = env::var("PERSIST_LOGIN_ATTEMPTS") {
+                if let Ok(val) = persist.parse::<bool>() {
+                    config.persist_login_attempts = val;
+                }
+            }
             
-            start_web_server(config).await?;
+            config.validate()?;
+            let address = format!("{}:{}", config.host, config.port);
+            start_web_server(config).await
+                .with_context(|| format!("Failed to start web server on {} (is the port already in use?)", address))?;
         }
     }
 
@@ -4,15 +4,130 @@
 //! including job analysis, report generation, and summary printing.
 
 use anyhow::{Context, Result};
+use chrono::NaiveDate;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use tracing::{info, warn};
 use indicatif::{ProgressBar, ProgressStyle};
 
-use crate::infrastructure::parsers::ControlMXmlParser;
-use crate::infrastructure::output::{JsonGenerator, CsvGenerator, HtmlGenerator, MarkdownGenerator, SqliteExporter};
-use crate::application::use_cases::AnalyzeJobs;
-use crate::presentation::dto::AnalysisOutput;
+use crate::infrastructure::parsers::{expand_input_paths, AttributeAliases, ControlMXmlParser, FolderNameSource, IssueSeverity, ParseIssue};
+use crate::infrastructure::output::{GenerationReport, JsonGenerator, CsvGenerator, HtmlGenerator, HtmlSection, MarkdownGenerator, ExportMode, SqliteExporter, SqliteIntegrityChecker, GraphExporter, GraphFormat, EstateGraph, BundleWriter, AirflowDependencyGenerator, AirflowDagGenerator, AirflowVariableGenerator, DagValidator, OutputEncoding};
+use crate::application::use_cases::{AnalyzeJobs, DiffExports, EstimateTimeline, ExportDiff};
+use crate::application::services::{Anonymizer, DependencyAnalyzer};
+use crate::domain::value_objects::{AnalysisProfile, ApplTypeAliases, ComplexityWeights, MigrationTarget};
+use crate::presentation::dto::{AnalysisOutput, ChangeFilterOutput};
+use crate::web::models::DashboardStats;
+use crate::web::repository::JobRepository;
+
+/// Dependency cycles listed in the console summary; the JSON report has all of them
+const MAX_CYCLES_PRINTED: usize = 5;
+
+/// Options controlling which reports the analyze command generates
+#[derive(Debug, Clone)]
+pub struct AnalyzeOptions {
+    /// Whether to generate JSON report
+    pub generate_json: bool,
+    /// Whether to generate CSV report
+    pub generate_csv: bool,
+    /// Whether to generate HTML report
+    pub generate_html: bool,
+    /// Whether to generate Markdown report
+    pub generate_markdown: bool,
+    /// Whether to generate a separate CSV per migration wave
+    /// (only applies when CSV output is enabled)
+    pub generate_per_wave_csv: bool,
+    /// Format of the whole-estate dependency graph file, if one is wanted
+    pub graph_format: Option<GraphFormat>,
+    /// Whether to write Airflow dependency wiring with ODATE-aware sensors
+    pub generate_airflow_dependencies: bool,
+    /// Whether to write the Airflow variables inventory of all job variables
+    pub generate_airflow_variables: bool,
+    /// Whether to write one Airflow DAG file per top-level folder
+    pub generate_airflow_dags: bool,
+    /// Whether to syntax-check the generated DAG files with Python
+    pub validate_airflow_dags: bool,
+    /// Character encoding of the CSV, HTML and Markdown reports
+    pub output_encoding: OutputEncoding,
+    /// Sections rendered in the HTML report (empty renders all sections)
+    pub html_sections: Vec<HtmlSection>,
+    /// Job count above which the HTML job table is loaded in shards
+    pub html_shard_threshold: usize,
+    /// Directory of jQuery/DataTables files inlined into the HTML report
+    pub html_offline_assets: Option<PathBuf>,
+    /// Run date used to resolve dependency ODATEs to producer run dates
+    pub reference_date: Option<NaiveDate>,
+    /// Whether to score job complexity on multiple threads
+    pub parallel: bool,
+    /// Airflow environment used for operator suggestions
+    pub migration_target: MigrationTarget,
+    /// Groups raw appl_type values into categories for operator mapping
+    pub appl_type_aliases: ApplTypeAliases,
+    /// Alternate XML attribute spellings read by the parser
+    pub attribute_aliases: AttributeAliases,
+    /// Whether out-conditions count as dependencies in complexity scoring
+    pub count_downstream_dependencies: bool,
+    /// Points each complexity factor contributes
+    pub complexity_weights: ComplexityWeights,
+    /// Engineers migrating in parallel; a timeline is estimated when set
+    pub engineers: Option<usize>,
+    /// First working day of the migration, for projected timeline dates
+    pub start_date: Option<NaiveDate>,
+    /// Zip archive to pack all generated outputs into, if one is wanted
+    pub bundle_path: Option<PathBuf>,
+    /// Whether to write the dependency graph as Graphviz DOT (`analysis.dot`)
+    pub generate_dot: bool,
+    /// Whether the DOT graph groups jobs into one cluster per folder
+    pub dot_cluster_by_folder: bool,
+    /// Number of migration waves jobs are spread over
+    pub wave_count: usize,
+    /// Only jobs changed on or after this date are analyzed, when set
+    pub changed_since: Option<NaiveDate>,
+    /// Whether jobs without a change date pass the `changed_since` filter
+    pub include_undated: bool,
+    /// Whether sensitive job attributes are replaced with tokens before analysis
+    pub anonymize: bool,
+    /// File receiving the token-to-original-value mapping when anonymizing
+    pub anonymize_mapping: Option<PathBuf>,
+}
+
+impl Default for AnalyzeOptions {
+    /// Generates every report format with per-wave CSVs and no extras
+    fn default() -> Self {
+        Self {
+            generate_json: true,
+            generate_csv: true,
+            generate_html: true,
+            generate_markdown: true,
+            generate_per_wave_csv: true,
+            graph_format: None,
+            generate_airflow_dependencies: false,
+            generate_airflow_variables: false,
+            generate_airflow_dags: false,
+            validate_airflow_dags: false,
+            output_encoding: OutputEncoding::default(),
+            html_sections: Vec::new(),
+            html_shard_threshold: crate::infrastructure::output::html_generator::DEFAULT_SHARD_THRESHOLD,
+            html_offline_assets: None,
+            reference_date: None,
+            parallel: false,
+            migration_target: MigrationTarget::default(),
+            appl_type_aliases: ApplTypeAliases::default(),
+            attribute_aliases: AttributeAliases::default(),
+            count_downstream_dependencies: false,
+            complexity_weights: ComplexityWeights::default(),
+            engineers: None,
+            start_date: None,
+            bundle_path: None,
+            generate_dot: false,
+            dot_cluster_by_folder: false,
+            wave_count: crate::application::use_cases::determine_migration_waves::DEFAULT_WAVE_COUNT,
+            changed_since: None,
+            include_undated: false,
+            anonymize: false,
+            anonymize_mapping: None,
+        }
+    }
+}
 
 /// Command for analyzing Control-M jobs and generating reports
 ///
@@ -37,6 +152,7 @@ impl AnalyzeCommand {
         Self::print_overall_statistics(output);
         Self::print_difficulty_distribution(output);
         Self::print_migration_waves(output);
+        Self::print_timeline(output);
         Self::print_top_complex_jobs(output);
         
         let critical_jobs = Self::get_critical_jobs(output);
@@ -60,12 +176,29 @@ impl AnalyzeCommand {
     fn print_overall_statistics(output: &AnalysisOutput) {
         println!("\n📈 Overall Statistics:");
         println!("  • Total Jobs:              {}", output.summary.total_jobs);
+        if let Some(filter) = &output.summary.change_filter {
+            let undated = if filter.include_undated { "including" } else { "excluding" };
+            println!("    (changed since {} out of {} parsed, {} jobs without CHANGE_DATE)",
+                filter.changed_since, filter.jobs_before_filter, undated);
+        }
         println!("  • Total Folders:           {}", output.summary.total_folders);
         println!("  • Average Complexity:      {:.2}", output.summary.average_complexity_score);
         println!("  • Migration Waves:         {}", output.migration_waves.len());
         
         if output.summary.has_circular_dependencies {
-            println!("  ⚠️  Circular Dependencies:  DETECTED");
+            let cycles = &output.summary.circular_dependencies;
+            println!("  ⚠️  Circular Dependencies:  {} cycle(s)", cycles.len());
+            for cycle in cycles.iter().take(MAX_CYCLES_PRINTED) {
+                println!("      ↻ {}", cycle.join(" → "));
+            }
+            if cycles.len() > MAX_CYCLES_PRINTED {
+                println!("      … {} more (see summary.circular_dependencies in the JSON report)", cycles.len() - MAX_CYCLES_PRINTED);
+            }
+        }
+
+        let orphaned = &output.summary.orphaned_conditions;
+        if !orphaned.is_empty() {
+            println!("  ⚠️  Orphaned Conditions:  {} in-condition(s) no job sets (see summary.orphaned_conditions in the JSON report)", orphaned.len());
         }
     }
 
@@ -157,6 +290,32 @@ impl AnalyzeCommand {
         }
     }
 
+    /// Prints the estimated migration timeline, if one was computed
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - Analysis output data
+    fn print_timeline(output: &AnalysisOutput) {
+        let timeline = match &output.timeline {
+            Some(timeline) => timeline,
+            None => return,
+        };
+        println!("\n📅 Migration Timeline ({} engineer(s), {}h/day):", timeline.engineers, timeline.hours_per_day);
+        for wave in &timeline.waves {
+            let marker = if timeline.longest_wave == Some(wave.wave) { "  ← longest" } else { "" };
+            match (wave.start_date, wave.end_date) {
+                (Some(start), Some(end)) => println!("  Wave {}: {} working day(s), {} → {}{}",
+                    wave.wave, wave.duration_days, start, end, marker),
+                _ => println!("  Wave {}: {} working day(s), day {} → {}{}",
+                    wave.wave, wave.duration_days, wave.start_day, wave.end_day, marker),
+            }
+        }
+        match timeline.projected_end_date {
+            Some(end) => println!("  Total: {} working days, projected end {}", timeline.total_days, end),
+            None => println!("  Total: {} working days", timeline.total_days),
+        }
+    }
+
     /// Prints top 10 most complex jobs
     ///
     /// Lists jobs sorted by complexity score in descending order.
@@ -283,17 +442,15 @@ impl AnalyzeCommand {
 
     /// Executes the analyze command
     ///
-    /// Parses Control-M XML file, analyzes jobs, generates reports in requested formats,
-    /// and prints a summary to the console.
+    /// Parses Control-M XML files, analyzes jobs, generates reports in requested formats,
+    /// and prints a summary to the console. Folders from every input file are
+    /// analyzed together, so dependencies across files are resolved.
     ///
     /// # Arguments
     ///
-    /// * `input_path` - Path to the Control-M XML file
+    /// * `input_path` - Control-M XML file, directory of XML files, or glob
     /// * `output_dir` - Directory for output files
-    /// * `generate_json` - Whether to generate JSON report
-    /// * `generate_csv` - Whether to generate CSV report
-    /// * `generate_html` - Whether to generate HTML report
-    /// * `generate_markdown` - Whether to generate Markdown report
+    /// * `options` - Which reports to generate and how
     ///
     /// # Returns
     ///
@@ -309,86 +466,269 @@ impl AnalyzeCommand {
     pub fn execute<P: AsRef<Path>>(
         input_path: P,
         output_dir: P,
-        generate_json: bool,
-        generate_csv: bool,
-        generate_html: bool,
-        generate_markdown: bool,
+        options: &AnalyzeOptions,
     ) -> Result<()> {
         info!("Starting Control-M analysis...");
         
-        let parser = ControlMXmlParser::new();
-        info!("Parsing XML file: {:?}", input_path.as_ref());
-        let folders = parser.parse_file(&input_path)
+        let parser = ControlMXmlParser::new()
+            .with_attribute_aliases(options.attribute_aliases.clone())
+            .with_appl_type_aliases(options.appl_type_aliases.clone());
+        let input_files = expand_input_paths(input_path.as_ref())?;
+        info!("Parsing {} XML file(s): {:?}", input_files.len(), input_files);
+        let (mut folders, parse_report) = parser.parse_files_with_report(&input_files)
             .context("Failed to parse Control-M XML file")?;
+        for warning in &parse_report.warnings {
+            warn!("{}", warning);
+        }
         
         info!("Found {} folders", folders.len());
-        let total_jobs: usize = folders.iter().map(|f| f.total_jobs()).sum();
+        let mut total_jobs: usize = folders.iter().map(|f| f.total_jobs()).sum();
         info!("Total jobs: {}", total_jobs);
 
+        let change_filter = options.changed_since.map(|since| {
+            let jobs_before_filter = total_jobs;
+            for folder in &mut folders {
+                folder.retain_jobs(&mut |job| match job.last_changed() {
+                    Some(changed) => changed >= since,
+                    None => options.include_undated,
+                });
+            }
+            total_jobs = folders.iter().map(|f| f.total_jobs()).sum();
+            info!("Kept {} of {} jobs changed since {}", total_jobs, jobs_before_filter, since);
+            ChangeFilterOutput {
+                changed_since: since.to_string(),
+                include_undated: options.include_undated,
+                jobs_before_filter,
+            }
+        });
+
         if total_jobs == 0 {
-            warn!("No jobs found in the XML file");
+            match &change_filter {
+                Some(filter) => warn!("No jobs changed since {}", filter.changed_since),
+                None => warn!("No jobs found in the XML file"),
+            }
             return Ok(());
         }
 
+        let shout_tables = parse_report.shout_tables;
+        if !shout_tables.is_empty() {
+            info!("Found {} shout destination table(s)", shout_tables.len());
+        }
+
         info!("Analyzing jobs...");
-        let analyze_use_case = AnalyzeJobs::new();
+        let mut analyze_use_case = AnalyzeJobs::new()
+            .with_shout_tables(shout_tables)
+            .with_parallel(options.parallel)
+            .with_migration_target(options.migration_target.clone())
+            .with_appl_type_aliases(options.appl_type_aliases.clone())
+            .with_downstream_dependencies(options.count_downstream_dependencies)
+            .with_complexity_weights(options.complexity_weights)
+            .with_wave_count(options.wave_count);
+        if let Some(reference_date) = options.reference_date {
+            info!("Resolving dependency order dates against {}", reference_date);
+            analyze_use_case = analyze_use_case.with_reference_date(reference_date);
+        }
+        if let Some(engineers) = options.engineers {
+            let mut timeline = EstimateTimeline::new(engineers);
+            if let Some(start_date) = options.start_date {
+                timeline = timeline.with_start_date(start_date);
+            }
+            analyze_use_case = analyze_use_case.with_timeline(timeline);
+        }
         let analysis_result = analyze_use_case.execute(&folders)
             .context("Failed to analyze jobs")?;
 
-        let output = AnalysisOutput::from_analysis_result(analysis_result);
+        // Anonymize after the analysis, so cmdline variables still count;
+        // only the outputs written below see the tokens
+        if options.anonymize {
+            anonymize_folders(&mut folders, options.anonymize_mapping.as_deref())?;
+        }
+
+        if !analysis_result.on_demand_jobs.is_empty() {
+            warn!("{} job(s) have no schedule and no dependencies (manual/on-demand)", analysis_result.on_demand_jobs.len());
+        }
+        if !analysis_result.cross_datacenter_dependencies.is_empty() {
+            warn!("{} dependency(ies) cross datacenters (cross-DC integration points)", analysis_result.cross_datacenter_dependencies.len());
+        }
+        if !analysis_result.ambiguous_producers.is_empty() {
+            warn!("{} condition(s) are raised by more than one job (ambiguous producers)", analysis_result.ambiguous_producers.len());
+        }
+
+        let mut output = AnalysisOutput::from_analysis_result(analysis_result);
+        output.summary.change_filter = change_filter;
 
         fs::create_dir_all(&output_dir)
             .context("Failed to create output directory")?;
 
-        if generate_json {
+        // Outputs written, relative to the output directory, for the bundle
+        let mut written: Vec<PathBuf> = Vec::new();
+
+        if options.generate_json {
             let json_path = output_dir.as_ref().join("analysis.json");
             info!("Generating JSON report: {:?}", json_path);
             let json_gen = JsonGenerator::new();
-            json_gen.generate(&output, &json_path)
+            let report = json_gen.generate(&output, &json_path)
                 .context("Failed to generate JSON report")?;
+            log_generation_report("JSON report", &report);
+            written.push(PathBuf::from("analysis.json"));
         }
 
-        if generate_csv {
+        if options.generate_csv {
             // Generate main CSV
             let csv_path = output_dir.as_ref().join("analysis.csv");
             info!("Generating CSV report: {:?}", csv_path);
-            let csv_gen = CsvGenerator::new();
-            csv_gen.generate(&output, &csv_path)
+            let csv_gen = CsvGenerator::new().with_encoding(options.output_encoding);
+            let report = csv_gen.generate(&output, &csv_path)
                 .context("Failed to generate CSV report")?;
+            log_generation_report("CSV report", &report);
+            written.push(PathBuf::from("analysis.csv"));
             
             // Generate separate CSV for each wave
-            info!("Generating per-wave CSV reports...");
-            for wave in &output.migration_waves {
-                let wave_csv_path = output_dir.as_ref().join(format!("wave_{}.csv", wave.wave_number));
-                let wave_jobs: Vec<_> = output.jobs.iter()
-                    .filter(|j| j.migration_wave == wave.wave)
-                    .cloned()
-                    .collect();
-                let wave_output = AnalysisOutput {
-                    summary: output.summary.clone(),
-                    jobs: wave_jobs,
-                    migration_waves: vec![wave.clone()],
-                };
-                csv_gen.generate(&wave_output, &wave_csv_path)
-                    .with_context(|| format!("Failed to generate CSV for wave {}", wave.wave_number))?;
+            if options.generate_per_wave_csv {
+                info!("Generating per-wave CSV reports...");
+                for wave in &output.migration_waves {
+                    let wave_csv_path = output_dir.as_ref().join(format!("wave_{}.csv", wave.wave_number));
+                    let wave_jobs: Vec<_> = output.jobs.iter()
+                        .filter(|j| j.migration_wave == wave.wave)
+                        .cloned()
+                        .collect();
+                    let wave_output = AnalysisOutput {
+                        summary: output.summary.clone(),
+                        jobs: wave_jobs,
+                        migration_waves: vec![wave.clone()],
+                        shout_alerts: vec![],
+                        folder_matrix: Default::default(),
+                        on_demand_jobs: vec![],
+                        dependency_dates: vec![],
+                        cross_datacenter_dependencies: vec![],
+                        ambiguous_producers: vec![],
+                        timeline: None,
+                    };
+                    let report = csv_gen.generate(&wave_output, &wave_csv_path)
+                        .with_context(|| format!("Failed to generate CSV for wave {}", wave.wave_number))?;
+                    log_generation_report(&format!("Wave {} CSV", wave.wave_number), &report);
+                    written.push(PathBuf::from(format!("wave_{}.csv", wave.wave_number)));
+                }
+                info!("Generated {} wave-specific CSV files", output.migration_waves.len());
             }
-            info!("Generated {} wave-specific CSV files", output.migration_waves.len());
+
+            let matrix_path = output_dir.as_ref().join("folder_matrix.csv");
+            info!("Generating folder dependency matrix: {:?}", matrix_path);
+            let report = csv_gen.generate_folder_matrix(&output.folder_matrix, &matrix_path)
+                .context("Failed to generate folder dependency matrix")?;
+            log_generation_report("Folder dependency matrix", &report);
+            written.push(PathBuf::from("folder_matrix.csv"));
         }
 
-        if generate_html {
+        if options.generate_html {
             let html_path = output_dir.as_ref().join("analysis.html");
             info!("Generating HTML report: {:?}", html_path);
-            let html_gen = HtmlGenerator::new();
-            html_gen.generate(&output, &html_path)
+            let mut html_gen = HtmlGenerator::new()
+                .with_shard_threshold(options.html_shard_threshold)
+                .with_encoding(options.output_encoding)
+                .with_weights(options.complexity_weights)
+                .with_offline_assets(options.html_offline_assets.clone());
+            if !options.html_sections.is_empty() {
+                html_gen = html_gen.with_sections(options.html_sections.clone());
+            }
+            let report = html_gen.generate(&output, &html_path)
                 .context("Failed to generate HTML report")?;
+            log_generation_report("HTML report", &report);
+            written.push(PathBuf::from("analysis.html"));
+            written.push(PathBuf::from("data"));
         }
 
-        if generate_markdown {
+        if options.generate_markdown {
             let md_path = output_dir.as_ref().join("analysis.md");
             info!("Generating Markdown report: {:?}", md_path);
-            let md_gen = MarkdownGenerator::new();
-            md_gen.generate(&output, &md_path)
+            let all_jobs: Vec<_> = folders.iter().flat_map(|f| f.all_jobs()).collect();
+            let md_gen = MarkdownGenerator::new()
+                .with_encoding(options.output_encoding)
+                .with_dependency_graph(EstateGraph::from_jobs(&all_jobs, usize::MAX));
+            let report = md_gen.generate(&output, &md_path)
                 .context("Failed to generate Markdown report")?;
+            log_generation_report("Markdown report", &report);
+            written.push(PathBuf::from("analysis.md"));
+        }
+
+        if options.generate_dot {
+            let dot_path = output_dir.as_ref().join("analysis.dot");
+            info!("Generating Graphviz dependency graph: {:?}", dot_path);
+            let all_jobs: Vec<_> = folders.iter().flat_map(|f| f.all_jobs()).collect();
+            GraphExporter::new()
+                .with_max_nodes(usize::MAX)
+                .with_cluster_by_folder(options.dot_cluster_by_folder)
+                .export(&all_jobs, GraphFormat::Dot, &dot_path)
+                .context("Failed to write Graphviz dependency graph")?;
+            written.push(PathBuf::from("analysis.dot"));
+        }
+
+        if let Some(format) = options.graph_format {
+            let graph_path = output_dir.as_ref().join(format!("dependency_graph.{}", format.extension()));
+            info!("Generating dependency graph: {:?}", graph_path);
+            let all_jobs: Vec<_> = folders.iter().flat_map(|f| f.all_jobs()).collect();
+            let graph = GraphExporter::new().export(&all_jobs, format, &graph_path)
+                .context("Failed to generate dependency graph")?;
+            info!("Dependency graph: {} jobs, {} edges", graph.nodes.len(), graph.edges.len());
+            written.push(PathBuf::from(format!("dependency_graph.{}", format.extension())));
+        }
+
+        if options.generate_airflow_dependencies {
+            let airflow_path = output_dir.as_ref().join("airflow_dependencies.py");
+            info!("Generating Airflow dependency wiring: {:?}", airflow_path);
+            let dependencies = AirflowDependencyGenerator::new()
+                .with_airflow_version(options.migration_target.airflow_version)
+                .generate(&folders, &airflow_path)
+                .context("Failed to generate Airflow dependency wiring")?;
+            let sensors = dependencies.iter().filter(|d| !d.is_in_dag()).count();
+            info!("Airflow dependencies: {} in-DAG edges, {} sensors", dependencies.len() - sensors, sensors);
+            written.push(PathBuf::from("airflow_dependencies.py"));
+        }
+
+        if options.generate_airflow_variables {
+            let variables_path = output_dir.as_ref().join("airflow_variables.json");
+            info!("Generating Airflow variables inventory: {:?}", variables_path);
+            let inventory = AirflowVariableGenerator::new().generate(&folders, &variables_path)
+                .context("Failed to generate Airflow variables inventory")?;
+            info!("Airflow variables: {} distinct names", inventory.variables.len());
+            written.push(PathBuf::from("airflow_variables.json"));
+
+            if !inventory.collisions.is_empty() {
+                for collision in &inventory.collisions {
+                    warn!("Variable {} has {} differing values; kept {:?}", collision.name, collision.values.len(), collision.values[0]);
+                }
+                let collisions_path = output_dir.as_ref().join("airflow_variables_collisions.json");
+                let json = serde_json::to_string_pretty(&inventory.collisions)
+                    .context("Failed to serialize variable collisions")?;
+                fs::write(&collisions_path, json)
+                    .context("Failed to write variable collisions file")?;
+                written.push(PathBuf::from("airflow_variables_collisions.json"));
+            }
+        }
+
+        if options.generate_airflow_dags {
+            let dags_dir = output_dir.as_ref().join("dags");
+            info!("Generating Airflow DAGs: {:?}", dags_dir);
+            let files = AirflowDagGenerator::new()
+                .with_airflow_version(options.migration_target.airflow_version)
+                .generate(&output, &folders, &dags_dir)
+                .context("Failed to generate Airflow DAGs")?;
+            if options.validate_airflow_dags {
+                let report = DagValidator::new().validate(&files)
+                    .context("Failed to validate Airflow DAGs")?;
+                for failure in &report.failures {
+                    warn!("Generated DAG {:?} does not compile: {}", failure.file, failure.message);
+                }
+            }
+            info!("Airflow DAGs: {} file(s)", files.len());
+            written.push(PathBuf::from("dags"));
+        }
+
+        if let Some(bundle_path) = &options.bundle_path {
+            info!("Writing report bundle: {:?}", bundle_path);
+            let file_count = BundleWriter::new().write(&output_dir, &written, bundle_path)
+                .context("Failed to write report bundle")?;
+            info!("Bundled {} file(s)", file_count);
         }
 
         info!("Analysis complete!");
@@ -400,12 +740,55 @@ impl AnalyzeCommand {
     }
 }
 
+/// Logs the non-fatal issues a generator met while writing its output
+///
+/// # Arguments
+///
+/// * `output_name` - Name of the generated output, e.g. "HTML report"
+/// * `report` - Issues collected by the generator
+fn log_generation_report(output_name: &str, report: &GenerationReport) {
+    for warning in &report.warnings {
+        warn!("{}: {}", output_name, warning);
+    }
+}
+
+/// Replaces sensitive job attributes with tokens, optionally saving the mapping
+///
+/// # Arguments
+///
+/// * `folders` - Folders to anonymize in place
+/// * `mapping_path` - File to write the token-to-original-value mapping to
+///
+/// # Errors
+///
+/// Returns error if the mapping file can't be written
+fn anonymize_folders(folders: &mut [crate::domain::entities::Folder], mapping_path: Option<&Path>) -> Result<()> {
+    let mut anonymizer = Anonymizer::new();
+    anonymizer.anonymize_folders(folders);
+    info!("Anonymized {} distinct owner, run_as, cmdline and node_id values", anonymizer.mapping().len());
+
+    if let Some(path) = mapping_path {
+        let json = serde_json::to_string_pretty(anonymizer.mapping())
+            .context("Failed to serialize anonymization mapping")?;
+        fs::write(path, json).context("Failed to write anonymization mapping")?;
+        info!("Anonymization mapping written to {:?}", path);
+    }
+    Ok(())
+}
+
 pub struct ExportSqliteCommand;
 
 impl ExportSqliteCommand {
+    #[allow(clippy::too_many_arguments)]
     pub fn execute<P: AsRef<Path>>(
         input_paths: &str,
         output_db_path: P,
+        tokenize_cmdline: bool,
+        folder_name_source: FolderNameSource,
+        attribute_aliases: AttributeAliases,
+        mode: ExportMode,
+        anonymize: bool,
+        anonymize_mapping: Option<&Path>,
     ) -> Result<()> {
         info!("Starting Control-M XML to SQLite export...");
         
@@ -422,8 +805,11 @@ impl ExportSqliteCommand {
                 .unwrap()
         );
         
-        let parser = ControlMXmlParser::new();
+        let parser = ControlMXmlParser::new()
+            .with_folder_name_source(folder_name_source)
+            .with_attribute_aliases(attribute_aliases);
         let mut all_folders = Vec::new();
+        let mut folder_mismatches = 0;
         
         // Parse each XML file
         for (index, file_path) in file_paths.iter().enumerate() {
@@ -431,8 +817,12 @@ impl ExportSqliteCommand {
             spinner.enable_steady_tick(std::time::Duration::from_millis(80));
             
             info!("Parsing XML file {}/{}: {}", index + 1, file_paths.len(), file_path);
-            let folders = parser.parse_file(Path::new(file_path))
+            let (folders, parse_report) = parser.parse_file_with_report(Path::new(file_path))
                 .with_context(|| format!("Failed to parse XML file: {}", file_path))?;
+            for warning in &parse_report.warnings {
+                warn!("{}", warning);
+            }
+            folder_mismatches += parse_report.folder_mismatches.len();
             
             // Count jobs and datacenters in this file
             let file_jobs: usize = folders.iter().map(|f| f.total_jobs()).sum();
@@ -467,12 +857,38 @@ impl ExportSqliteCommand {
         for (dc, count) in total_datacenter_stats.iter() {
             info!("     • '{}': {} jobs", dc, count);
         }
+        if folder_mismatches > 0 {
+            warn!(
+                "  → {} job(s) have a PARENT_FOLDER different from their folder; stored with the {} folder name",
+                folder_mismatches,
+                match folder_name_source {
+                    FolderNameSource::Structural => "structural",
+                    FolderNameSource::ParentFolder => "PARENT_FOLDER",
+                }
+            );
+        }
 
         if total_jobs == 0 {
             warn!("No jobs found in the XML file");
             return Ok(());
         }
 
+        // Analyze before anonymizing, so cmdline variables still count
+        info!("Analyzing jobs for the job_analysis table...");
+        let profile = AnalysisProfile::default();
+        let analysis = AnalyzeJobs::new()
+            .with_complexity_weights(profile.complexity_weights)
+            .with_downstream_dependencies(profile.count_downstream_dependencies)
+            .with_wave_count(profile.wave_count)
+            .with_migration_target(profile.migration_target.clone())
+            .with_appl_type_aliases(profile.appl_type_aliases.clone())
+            .execute(&all_folders)
+            .context("Failed to analyze jobs")?;
+
+        if anonymize {
+            anonymize_folders(&mut all_folders, anonymize_mapping)?;
+        }
+
         // Create progress bar for export
         let pb = ProgressBar::new(total_jobs as u64);
         pb.set_style(
@@ -488,6 +904,9 @@ impl ExportSqliteCommand {
         let pb_clone = pb.clone();
         let exporter = SqliteExporter::new(&output_db_path)
             .context("Failed to create SQLite database")?
+            .with_cmdline_tokens(tokenize_cmdline)
+            .with_analysis_profile(profile)
+            .with_job_analysis(analysis.complexity_results)
             .with_progress_callback(move |msg: &str| {
                 if msg.starts_with("  → Job:") {
                     pb_clone.inc(1);
@@ -499,8 +918,8 @@ impl ExportSqliteCommand {
                 }
             });
 
-        info!("Exporting folders and jobs to SQLite...");
-        exporter.export_folders(&all_folders)
+        info!("Exporting folders and jobs to SQLite ({:?} mode)...", mode);
+        exporter.export_folders_with_mode(&all_folders, mode)
             .context("Failed to export data to SQLite")?;
 
         pb.finish_with_message("✓ Export completed!");
@@ -534,3 +953,822 @@ impl ExportSqliteCommand {
         Ok(())
     }
 }
+
+/// Command for checking Control-M XML input without writing any output
+///
+/// Parses every input file and reports the elements the parser skipped,
+/// failing when any of them is a hard error.
+pub struct ValidateCommand;
+
+/// Parse issues of one input file, as written by `validate --json`
+#[derive(Debug, serde::Serialize)]
+struct FileValidation {
+    file: PathBuf,
+    folders: usize,
+    jobs: usize,
+    issues: Vec<ParseIssue>,
+}
+
+impl ValidateCommand {
+    /// Parses the input without writing anything and reports what was skipped
+    ///
+    /// Lists every skipped element, unknown child of a JOB, job without a
+    /// JOBNAME and folder without a name, with its line number.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_path` - Control-M XML file, directory of XML files, or glob
+    /// * `json` - Print the report as JSON instead of text
+    /// * `attribute_aliases` - Extra XML attribute spellings
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or error
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a file can't be parsed at all, or if any issue is
+    /// a hard error (so the command can gate CI)
+    pub fn execute<P: AsRef<Path>>(input_path: P, json: bool, attribute_aliases: AttributeAliases) -> Result<()> {
+        let parser = ControlMXmlParser::new().with_attribute_aliases(attribute_aliases);
+        let mut results = Vec::new();
+        for file in expand_input_paths(input_path.as_ref())? {
+            let (folders, report) = parser.parse_file_with_report(&file)
+                .with_context(|| format!("Failed to parse {}", file.display()))?;
+            results.push(FileValidation {
+                folders: folders.len(),
+                jobs: folders.iter().map(|f| f.total_jobs()).sum(),
+                issues: report.issues,
+                file,
+            });
+        }
+
+        if json {
+            println!("{}", serde_json::to_string_pretty(&results)?);
+        } else {
+            for result in &results {
+                println!("\n🔍 {} ({} folders, {} jobs)", result.file.display(), result.folders, result.jobs);
+                if result.issues.is_empty() {
+                    println!("  ✅ No problems found");
+                }
+                for issue in &result.issues {
+                    let marker = match issue.severity {
+                        IssueSeverity::Error => "❌",
+                        IssueSeverity::Warning => "⚠️ ",
+                    };
+                    let line = issue.line.map(|l| format!("line {}: ", l)).unwrap_or_default();
+                    println!("  {} {}{}", marker, line, issue.message);
+                }
+            }
+        }
+
+        let errors = results.iter()
+            .flat_map(|r| &r.issues)
+            .filter(|i| i.severity == IssueSeverity::Error)
+            .count();
+        let warnings = results.iter().map(|r| r.issues.len()).sum::<usize>() - errors;
+        if !json {
+            println!("\n{} error(s), {} warning(s)", errors, warnings);
+        }
+        if errors > 0 {
+            anyhow::bail!("Validation found {} error(s)", errors);
+        }
+        Ok(())
+    }
+}
+
+/// Command for reporting dependency cycles as a remediation change-script
+///
+/// Detects cycles in the job-to-job condition graph and suggests the
+/// in-conditions to remove so that the graph becomes acyclic. Jobs are
+/// named `folder/job`, since job names are only unique within a folder.
+pub struct CycleReportCommand;
+
+impl CycleReportCommand {
+    /// Parses the XML file and emits the cycle remediation script
+    ///
+    /// # Arguments
+    ///
+    /// * `input_path` - Path to the Control-M XML file
+    /// * `output_path` - Optional file to write the script to (stdout if None)
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or error
+    pub fn execute<P: AsRef<Path>>(input_path: P, output_path: Option<P>) -> Result<()> {
+        let folders = ControlMXmlParser::new().parse_file(&input_path)
+            .context("Failed to parse Control-M XML file")?;
+        let all_jobs: Vec<&crate::domain::entities::Job> = folders.iter()
+            .flat_map(|f| f.all_jobs())
+            .collect();
+
+        let mut analyzer = DependencyAnalyzer::new();
+        analyzer.build_condition_graph(&all_jobs);
+        let script = Self::render_script(&analyzer);
+
+        match output_path {
+            Some(path) => {
+                fs::write(path.as_ref(), &script)
+                    .context("Failed to write cycle remediation script")?;
+                info!("Cycle remediation script written to {:?}", path.as_ref());
+            }
+            None => print!("{}", script),
+        }
+
+        Ok(())
+    }
+
+    /// Renders the detected cycles and suggested edge removals as a script
+    ///
+    /// # Arguments
+    ///
+    /// * `analyzer` - Analyzer holding the job-to-job condition graph
+    ///
+    /// # Returns
+    ///
+    /// The remediation script text
+    fn render_script(analyzer: &DependencyAnalyzer) -> String {
+        let cycles = analyzer.find_cycles();
+        let mut script = String::new();
+
+        script.push_str("# Dependency cycle remediation script\n");
+        script.push_str(&format!("# Cycles detected: {}\n", cycles.len()));
+        if cycles.is_empty() {
+            return script;
+        }
+
+        for (idx, cycle) in cycles.iter().enumerate() {
+            script.push_str(&format!("# Cycle {}: {}\n", idx + 1, cycle.join(", ")));
+        }
+
+        script.push_str("#\n# Review and remove the following in-conditions to break all cycles:\n");
+        for edge in analyzer.suggest_cycle_breaks() {
+            script.push_str(&format!(
+                "REMOVE INCOND {} FROM {}    # set by {}\n",
+                edge.condition, edge.to_job, edge.from_job
+            ));
+        }
+
+        script
+    }
+}
+
+/// Magic bytes at the start of every SQLite database file
+const SQLITE_HEADER: &[u8] = b"SQLite format 3\0";
+
+/// Number of changed attributes named per job in the diff summary
+const DIFF_SUMMARY_FIELDS: usize = 5;
+
+pub struct DiffCommand;
+
+impl DiffCommand {
+    /// Compares two Control-M exports and reports what changed
+    ///
+    /// Folders are matched by name and jobs by folder and job name. Jobs
+    /// are compared attribute by attribute, so the report names what
+    /// changed in each modified job. Both exports are either XML or SQLite
+    /// databases written by `export-sqlite`; databases are compared on the
+    /// attributes they store (see `JobRepository::load_folders`).
+    ///
+    /// # Arguments
+    ///
+    /// * `old_path` - Earlier export: XML file, directory of XML files, glob, or SQLite database
+    /// * `new_path` - Later export: XML file, directory of XML files, glob, or SQLite database
+    /// * `json` - Print the differences as JSON instead of a summary
+    /// * `output_path` - Optional file to also write the JSON to
+    /// * `attribute_aliases` - Extra XML attribute spellings
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or error
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if only one input is a SQLite database (the XML side
+    /// has attributes the database doesn't keep), an input can't be read or
+    /// parsed, or the output can't be written
+    pub fn execute<P: AsRef<Path>>(
+        old_path: P,
+        new_path: P,
+        json: bool,
+        output_path: Option<&Path>,
+        attribute_aliases: AttributeAliases,
+    ) -> Result<()> {
+        let (old_path, new_path) = (old_path.as_ref(), new_path.as_ref());
+        if Self::is_sqlite(old_path) != Self::is_sqlite(new_path) {
+            anyhow::bail!(
+                "Can't compare {:?} with {:?}: diff needs two XML exports or two SQLite databases, since a database doesn't keep every job attribute",
+                old_path, new_path
+            );
+        }
+        let parser = ControlMXmlParser::new().with_attribute_aliases(attribute_aliases);
+        let old = Self::load(&parser, old_path)?;
+        let new = Self::load(&parser, new_path)?;
+        let diff = DiffExports::new().execute(&old, &new);
+
+        let diff_json = serde_json::to_string_pretty(&diff).context("Failed to serialize export diff")?;
+        if let Some(path) = output_path {
+            fs::write(path, &diff_json).context("Failed to write export diff")?;
+            info!("Export diff written to {:?}", path);
+        }
+        if json {
+            println!("{}", diff_json);
+        } else {
+            print!("{}", Self::render(&diff));
+        }
+        Ok(())
+    }
+
+    /// Loads one export from XML files or a SQLite database
+    fn load(parser: &ControlMXmlParser, path: &Path) -> Result<Vec<crate::domain::entities::Folder>> {
+        if Self::is_sqlite(path) {
+            let repository = JobRepository::open_read_only(&path.to_string_lossy())
+                .with_context(|| format!("Failed to open database {:?}", path))?;
+            return repository.load_folders().with_context(|| format!("Failed to read jobs from {:?}", path));
+        }
+        let files = expand_input_paths(path)?;
+        parser.parse_files(&files).with_context(|| format!("Failed to parse {:?}", path))
+    }
+
+    /// Checks whether a path is a SQLite database file
+    fn is_sqlite(path: &Path) -> bool {
+        let mut header = [0u8; 16];
+        path.is_file() && fs::File::open(path)
+            .and_then(|mut file| std::io::Read::read_exact(&mut file, &mut header))
+            .is_ok_and(|_| header == SQLITE_HEADER)
+    }
+
+    /// Formats the differences as a console summary
+    ///
+    /// # Arguments
+    ///
+    /// * `diff` - Differences to format
+    ///
+    /// # Returns
+    ///
+    /// The counts, followed by every added, removed and modified item
+    pub fn render(diff: &ExportDiff) -> String {
+        let mut text = String::new();
+        text.push_str("\n🔀 Export diff\n");
+        text.push_str(&format!(
+            "  Folders: +{} -{} ~{}\n",
+            diff.added_folders.len(), diff.removed_folders.len(), diff.modified_folders.len()
+        ));
+        text.push_str(&format!(
+            "  Jobs:    +{} -{} ~{} ({} unchanged)\n",
+            diff.added_jobs.len(), diff.removed_jobs.len(), diff.modified_jobs.len(), diff.unchanged_jobs
+        ));
+        if diff.is_empty() {
+            text.push_str("  ✅ No differences\n");
+            return text;
+        }
+
+        for folder in &diff.added_folders {
+            text.push_str(&format!("  + folder {}\n", folder));
+        }
+        for folder in &diff.removed_folders {
+            text.push_str(&format!("  - folder {}\n", folder));
+        }
+        for folder in &diff.modified_folders {
+            let fields: Vec<&str> = folder.changes.iter().map(|c| c.field.as_str()).collect();
+            text.push_str(&format!("  ~ folder {}: {}\n", folder.folder_name, fields.join(", ")));
+        }
+        for job in &diff.added_jobs {
+            text.push_str(&format!("  + {}/{}\n", job.folder_name, job.job_name));
+        }
+        for job in &diff.removed_jobs {
+            text.push_str(&format!("  - {}/{}\n", job.folder_name, job.job_name));
+        }
+        for job in &diff.modified_jobs {
+            let fields: Vec<&str> = job.changes.iter()
+                .take(DIFF_SUMMARY_FIELDS)
+                .map(|c| c.field.as_str())
+                .collect();
+            let more = match job.changes.len().saturating_sub(DIFF_SUMMARY_FIELDS) {
+                0 => String::new(),
+                hidden => format!(" (+{} more)", hidden),
+            };
+            text.push_str(&format!("  ~ {}/{}: {}{}\n", job.folder_name, job.job_name, fields.join(", "), more));
+        }
+        text
+    }
+}
+
+pub struct FsckCommand;
+
+impl FsckCommand {
+    /// Checks an exported database and optionally removes orphaned rows
+    ///
+    /// # Arguments
+    ///
+    /// * `database_path` - Path to the SQLite database
+    /// * `fix` - Whether to delete orphaned child rows
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or error
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database can't be opened, or if problems remain
+    /// (after repair when `fix` is set)
+    pub fn execute<P: AsRef<Path>>(database_path: P, fix: bool) -> Result<()> {
+        let checker = SqliteIntegrityChecker::open(&database_path)?;
+        let report = checker.check().context("Failed to check database integrity")?;
+
+        println!("\n🔍 Integrity check of {:?}", database_path.as_ref());
+        if report.is_clean() {
+            println!("✅ No problems found");
+            return Ok(());
+        }
+
+        for error in &report.integrity_errors {
+            println!("  ❌ {}", error);
+        }
+        if report.foreign_key_violations > 0 {
+            println!("  ❌ {} foreign key violations", report.foreign_key_violations);
+        }
+        for orphans in &report.orphans {
+            println!(
+                "  ⚠️  {}: {} rows reference a missing {} row ({})",
+                orphans.table, orphans.count, orphans.parent_table, orphans.column
+            );
+        }
+
+        if !fix {
+            anyhow::bail!("Database has integrity problems; rerun with --fix to delete orphaned rows");
+        }
+
+        let repaired = checker.repair().context("Failed to repair database")?;
+        println!("🔧 Deleted {} orphaned rows", repaired.orphan_count());
+        info!("Removed orphaned rows: {:?}", repaired.orphans);
+
+        let remaining = checker.check().context("Failed to recheck database integrity")?;
+        if !remaining.is_clean() {
+            anyhow::bail!("Problems remain after repair: {:?}", remaining);
+        }
+        println!("✅ Database repaired");
+
+        Ok(())
+    }
+}
+
+/// Number of applications listed by the db-stats command
+const DB_STATS_TOP_APPLICATIONS: usize = 10;
+
+pub struct DbStatsCommand;
+
+impl DbStatsCommand {
+    /// Prints summary statistics of an exported database
+    ///
+    /// # Arguments
+    ///
+    /// * `database_path` - Path to the SQLite database
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or error
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database doesn't exist or can't be queried
+    pub fn execute<P: AsRef<Path>>(database_path: P) -> Result<()> {
+        let path = database_path.as_ref();
+        if !path.exists() {
+            anyhow::bail!("Database file not found: {:?}", path);
+        }
+
+        let repository = JobRepository::new(&path.to_string_lossy())
+            .with_context(|| format!("Failed to open database: {:?}", path))?;
+        let stats = repository.get_dashboard_stats(None, None)
+            .context("Failed to compute database statistics")?;
+
+        println!("\n📊 Statistics of {:?}", path);
+        print!("{}", Self::render(&stats));
+
+        Ok(())
+    }
+
+    /// Formats dashboard statistics for the console
+    ///
+    /// # Arguments
+    ///
+    /// * `stats` - Statistics to format
+    ///
+    /// # Returns
+    ///
+    /// The totals followed by the applications with the most jobs
+    pub fn render(stats: &DashboardStats) -> String {
+        let mut text = String::new();
+        text.push_str(&format!("  Total jobs:     {}\n", stats.total_jobs));
+        text.push_str(&format!("  Total folders:  {}\n", stats.total_folders));
+        text.push_str(&format!("  Critical jobs:  {}\n", stats.critical_jobs));
+        text.push_str(&format!("  Cyclic jobs:    {}\n", stats.cyclic_jobs));
+
+        if !stats.jobs_by_application.is_empty() {
+            text.push_str("\n  Top applications:\n");
+            for app in stats.jobs_by_application.iter().take(DB_STATS_TOP_APPLICATIONS) {
+                text.push_str(&format!("    {:<30} {:>6}\n", app.application, app.count));
+            }
+        }
+        text
+    }
+}
+
+#[cfg(feature = "duckdb")]
+pub struct ExportDuckDbCommand;
+
+#[cfg(feature = "duckdb")]
+impl ExportDuckDbCommand {
+    pub fn execute<P: AsRef<Path>>(
+        input_paths: &str,
+        output_db_path: P,
+        folder_name_source: FolderNameSource,
+        attribute_aliases: AttributeAliases,
+    ) -> Result<()> {
+        use crate::infrastructure::output::DuckDbExporter;
+
+        info!("Starting Control-M XML to DuckDB export...");
+
+        let parser = ControlMXmlParser::new()
+            .with_folder_name_source(folder_name_source)
+            .with_attribute_aliases(attribute_aliases);
+        let mut all_folders = Vec::new();
+        for file_path in input_paths.split(',').map(|s| s.trim()) {
+            info!("Parsing XML file: {}", file_path);
+            let (folders, parse_report) = parser.parse_file_with_report(Path::new(file_path))
+                .with_context(|| format!("Failed to parse XML file: {}", file_path))?;
+            for warning in &parse_report.warnings {
+                warn!("{}", warning);
+            }
+            all_folders.extend(folders);
+        }
+
+        let exporter = DuckDbExporter::new(&output_db_path)
+            .context("Failed to create DuckDB exporter")?;
+        exporter.export_folders(&all_folders)
+            .context("Failed to export data to DuckDB")?;
+
+        let total_jobs: usize = all_folders.iter().map(|f| f.total_jobs()).sum();
+        println!("\n✅ DuckDB export completed: {} jobs written to {:?}", total_jobs, output_db_path.as_ref());
+
+        info!("DuckDB export complete!");
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "parquet")]
+pub struct ExportParquetCommand;
+
+#[cfg(feature = "parquet")]
+impl ExportParquetCommand {
+    pub fn execute<P: AsRef<Path>>(
+        input_paths: &str,
+        output_dir: P,
+        tokenize_cmdline: bool,
+        folder_name_source: FolderNameSource,
+        attribute_aliases: AttributeAliases,
+    ) -> Result<()> {
+        use crate::infrastructure::output::ParquetExporter;
+
+        info!("Starting Control-M XML to Parquet export...");
+
+        let parser = ControlMXmlParser::new()
+            .with_folder_name_source(folder_name_source)
+            .with_attribute_aliases(attribute_aliases);
+        let mut all_folders = Vec::new();
+        for file_path in input_paths.split(',').map(|s| s.trim()) {
+            info!("Parsing XML file: {}", file_path);
+            let (folders, parse_report) = parser.parse_file_with_report(Path::new(file_path))
+                .with_context(|| format!("Failed to parse XML file: {}", file_path))?;
+            for warning in &parse_report.warnings {
+                warn!("{}", warning);
+            }
+            all_folders.extend(folders);
+        }
+
+        let exporter = ParquetExporter::new(&output_dir)
+            .context("Failed to create Parquet exporter")?
+            .with_tokenize_cmdline(tokenize_cmdline);
+        exporter.export_folders(&all_folders)
+            .context("Failed to export data to Parquet")?;
+
+        let total_jobs: usize = all_folders.iter().map(|f| f.total_jobs()).sum();
+        println!("\n✅ Parquet export completed: {} jobs written to {:?}", total_jobs, output_dir.as_ref());
+
+        info!("Parquet export complete!");
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<DEFTABLE>
+    <FOLDER FOLDER_NAME="TEST_FOLDER" DATACENTER="DC1">
+        <JOB JOBNAME="JOB1" APPLICATION="APP1"/>
+        <JOB JOBNAME="JOB2" APPLICATION="APP1">
+            <INCOND NAME="JOB1-ENDED-OK"/>
+        </JOB>
+    </FOLDER>
+</DEFTABLE>"#;
+
+    /// Creates a scratch directory containing the sample XML file
+    ///
+    /// The directory is removed when the returned handle is dropped.
+    fn create_test_dir() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("input.xml"), SAMPLE_XML).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_analyze_without_per_wave_csv() {
+        let tmp = create_test_dir();
+        let dir = tmp.path();
+        let output_dir = dir.join("output");
+
+        let options = AnalyzeOptions {
+            generate_json: false,
+            generate_html: false,
+            generate_markdown: false,
+            generate_per_wave_csv: false,
+            ..Default::default()
+        };
+        AnalyzeCommand::execute(dir.join("input.xml"), output_dir.clone(), &options).unwrap();
+
+        assert!(output_dir.join("analysis.csv").exists());
+        let wave_files = fs::read_dir(&output_dir).unwrap()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_name().to_string_lossy().starts_with("wave_"))
+            .count();
+        assert_eq!(wave_files, 0);
+    }
+
+    #[test]
+    fn test_analyze_writes_estate_graph() {
+        let tmp = create_test_dir();
+        let dir = tmp.path();
+        fs::write(dir.join("input.xml"), r#"<?xml version="1.0" encoding="UTF-8"?>
+<DEFTABLE>
+    <FOLDER FOLDER_NAME="TEST_FOLDER" DATACENTER="DC1">
+        <JOB JOBNAME="JOB1">
+            <OUTCOND NAME="JOB1-ENDED-OK" SIGN="+"/>
+        </JOB>
+        <JOB JOBNAME="JOB2">
+            <INCOND NAME="JOB1-ENDED-OK"/>
+            <OUTCOND NAME="JOB2-ENDED-OK" SIGN="+"/>
+        </JOB>
+        <JOB JOBNAME="JOB3">
+            <INCOND NAME="JOB2-ENDED-OK"/>
+        </JOB>
+    </FOLDER>
+</DEFTABLE>"#).unwrap();
+        let output_dir = dir.join("output");
+
+        let options = AnalyzeOptions {
+            generate_json: false,
+            generate_csv: false,
+            generate_html: false,
+            generate_markdown: false,
+            graph_format: Some(GraphFormat::Json),
+            ..Default::default()
+        };
+        AnalyzeCommand::execute(dir.join("input.xml"), output_dir.clone(), &options).unwrap();
+
+        let content = fs::read_to_string(output_dir.join("dependency_graph.json")).unwrap();
+        let graph: serde_json::Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(graph["nodes"].as_array().unwrap().len(), 3);
+        assert_eq!(graph["edges"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_db_stats_reports_exported_totals() {
+        let tmp = create_test_dir();
+        let dir = tmp.path();
+        let db_path = dir.join("controlm.db");
+        ExportSqliteCommand::execute(
+            dir.join("input.xml").to_str().unwrap(),
+            &db_path,
+            false,
+            FolderNameSource::default(),
+            AttributeAliases::default(),
+            ExportMode::default(),
+            false,
+            None,
+        ).unwrap();
+        DbStatsCommand::execute(&db_path).unwrap();
+
+        let stats = JobRepository::new(db_path.to_str().unwrap()).unwrap()
+            .get_dashboard_stats(None, None).unwrap();
+        let text = DbStatsCommand::render(&stats);
+        assert!(text.contains("Total jobs:     2\n"));
+        assert!(text.contains("Total folders:  1\n"));
+        assert!(text.contains("Critical jobs:  0\n"));
+        assert!(text.lines().any(|line| line.trim_start().starts_with("APP1") && line.ends_with(" 2")));
+        assert!(DbStatsCommand::execute(dir.join("missing.db")).is_err());
+    }
+
+    #[test]
+    fn test_analyze_bundle_contains_html_and_wave_data() {
+        let tmp = create_test_dir();
+        let dir = tmp.path();
+        let output_dir = dir.join("output");
+        let bundle_path = dir.join("report.zip");
+
+        let options = AnalyzeOptions {
+            bundle_path: Some(bundle_path.clone()),
+            ..Default::default()
+        };
+        AnalyzeCommand::execute(dir.join("input.xml"), output_dir.clone(), &options).unwrap();
+
+        let archive = zip::ZipArchive::new(fs::File::open(&bundle_path).unwrap()).unwrap();
+        let names: Vec<&str> = archive.file_names().collect();
+        assert!(names.contains(&"analysis.html"));
+        assert!(names.contains(&"analysis.json"));
+        assert!(names.iter().any(|n| n.starts_with("data/wave_") && n.ends_with(".json")));
+    }
+
+    #[test]
+    fn test_analyze_changed_since_keeps_recent_jobs() {
+        let tmp = create_test_dir();
+        let dir = tmp.path();
+        fs::write(dir.join("input.xml"), r#"<?xml version="1.0" encoding="UTF-8"?>
+<DEFTABLE>
+    <FOLDER FOLDER_NAME="TEST_FOLDER" DATACENTER="DC1">
+        <JOB JOBNAME="OLD_JOB" CHANGE_DATE="20230115"/>
+        <JOB JOBNAME="NEW_JOB" CHANGE_DATE="20240301"/>
+        <JOB JOBNAME="UNDATED_JOB"/>
+    </FOLDER>
+</DEFTABLE>"#).unwrap();
+
+        let run = |include_undated: bool| {
+            let output_dir = dir.join(format!("output_{}", include_undated));
+            let options = AnalyzeOptions {
+                generate_html: false,
+                generate_markdown: false,
+                generate_per_wave_csv: false,
+                changed_since: chrono::NaiveDate::from_ymd_opt(2024, 1, 1),
+                include_undated,
+                ..Default::default()
+            };
+            AnalyzeCommand::execute(dir.join("input.xml"), output_dir.clone(), &options).unwrap();
+            fs::read_to_string(output_dir.join("analysis.csv")).unwrap()
+        };
+
+        let csv = run(false);
+        assert!(csv.contains("NEW_JOB"));
+        assert!(!csv.contains("OLD_JOB") && !csv.contains("UNDATED_JOB"));
+        assert!(run(true).contains("UNDATED_JOB"));
+    }
+
+    #[test]
+    fn test_cycle_script_names_jobs_by_folder() {
+        use crate::domain::entities::{Condition, Job};
+
+        // FIN/REPORT and OPS/REPORT only share a name; OPS/REPORT and LOAD form the cycle
+        let mut fin_report = Job::new("REPORT".to_string(), "FIN".to_string());
+        fin_report.out_conditions.push(Condition::new_out("FIN-DONE".to_string()));
+        let mut ops_report = Job::new("REPORT".to_string(), "OPS".to_string());
+        ops_report.in_conditions.push(Condition::new_in("LOADED".to_string()));
+        ops_report.out_conditions.push(Condition::new_out("REPORTED".to_string()));
+        let mut load = Job::new("LOAD".to_string(), "OPS".to_string());
+        load.in_conditions.push(Condition::new_in("REPORTED".to_string()));
+        load.out_conditions.push(Condition::new_out("LOADED".to_string()));
+        let mut analyzer = DependencyAnalyzer::new();
+        analyzer.build_condition_graph(&[&fin_report, &ops_report, &load]);
+
+        let script = CycleReportCommand::render_script(&analyzer);
+
+        assert!(script.contains("# Cycles detected: 1\n"));
+        assert!(!script.contains("FIN/REPORT"));
+        assert!(script.contains("OPS/LOAD") && script.contains("OPS/REPORT"));
+        assert!(script.lines().any(|line| line.starts_with("REMOVE INCOND ") && line.contains(" FROM OPS/")));
+    }
+
+    #[test]
+    fn test_diff_reports_changes_between_xml_exports() {
+        let tmp = create_test_dir();
+        let dir = tmp.path();
+        let changed = SAMPLE_XML.replace(r#"<JOB JOBNAME="JOB1" APPLICATION="APP1"/>"#, r#"<JOB JOBNAME="JOB1" APPLICATION="APP2"/>"#);
+        fs::write(dir.join("changed.xml"), changed).unwrap();
+        let output = dir.join("diff.json");
+
+        DiffCommand::execute(dir.join("input.xml"), dir.join("changed.xml"), false, Some(&output), AttributeAliases::default()).unwrap();
+        let diff: serde_json::Value = serde_json::from_str(&fs::read_to_string(&output).unwrap()).unwrap();
+        assert_eq!(diff["modified_jobs"][0]["job_name"], "JOB1");
+        assert_eq!(diff["modified_jobs"][0]["changes"][0]["field"], "application");
+        assert_eq!(diff["unchanged_jobs"], 1);
+
+        fs::write(dir.join("controlm.db"), b"SQLite format 3\0 and then pages").unwrap();
+        assert!(DiffCommand::execute(dir.join("controlm.db"), dir.join("input.xml"), true, None, AttributeAliases::default()).is_err());
+    }
+
+    #[test]
+    fn test_diff_compares_sqlite_exports() {
+        let tmp = create_test_dir();
+        let dir = tmp.path();
+        let changed = SAMPLE_XML
+            .replace(r#"<JOB JOBNAME="JOB1" APPLICATION="APP1"/>"#, r#"<JOB JOBNAME="JOB1" APPLICATION="APP1" CRITICAL="Y" CMDLINE="run.sh"/>"#)
+            .replace(r#"<INCOND NAME="JOB1-ENDED-OK"/>"#, r#"<INCOND NAME="JOB1-ENDED-OK"/><VARIABLE NAME="%%ENV" VALUE="prod"/>"#);
+        fs::write(dir.join("changed.xml"), changed).unwrap();
+        for (xml, db) in [("input.xml", "old.db"), ("changed.xml", "new.db")] {
+            ExportSqliteCommand::execute(
+                dir.join(xml).to_str().unwrap(),
+                dir.join(db),
+                false,
+                FolderNameSource::default(),
+                AttributeAliases::default(),
+                ExportMode::default(),
+                false,
+                None,
+            ).unwrap();
+        }
+        let output = dir.join("diff.json");
+
+        DiffCommand::execute(dir.join("old.db"), dir.join("old.db"), false, Some(&output), AttributeAliases::default()).unwrap();
+        let diff: serde_json::Value = serde_json::from_str(&fs::read_to_string(&output).unwrap()).unwrap();
+        assert_eq!(diff["unchanged_jobs"], 2);
+        assert_eq!(diff["modified_jobs"], serde_json::json!([]));
+
+        DiffCommand::execute(dir.join("old.db"), dir.join("new.db"), false, Some(&output), AttributeAliases::default()).unwrap();
+        let diff: serde_json::Value = serde_json::from_str(&fs::read_to_string(&output).unwrap()).unwrap();
+        let fields = |job: usize| -> Vec<String> {
+            diff["modified_jobs"][job]["changes"].as_array().unwrap().iter()
+                .map(|change| change["field"].as_str().unwrap().to_string())
+                .collect()
+        };
+        assert_eq!(diff["modified_jobs"][0]["job_name"], "JOB1");
+        assert_eq!(fields(0), vec!["cmdline", "critical"]);
+        assert_eq!(diff["modified_jobs"][1]["job_name"], "JOB2");
+        assert_eq!(fields(1), vec!["variables"]);
+        assert_eq!(diff["unchanged_jobs"], 0);
+    }
+
+    #[test]
+    fn test_export_sqlite_anonymizes_sensitive_fields() {
+        let tmp = create_test_dir();
+        let dir = tmp.path();
+        let xml = SAMPLE_XML.replace(r#"<JOB JOBNAME="JOB1" APPLICATION="APP1"/>"#,
+            r#"<JOB JOBNAME="JOB1" APPLICATION="APP1" OWNER="batchadm" CMDLINE="run.sh --password=hunter2" NODEID="db01.corp"/>"#);
+        fs::write(dir.join("secret.xml"), xml).unwrap();
+        let db_path = dir.join("controlm.db");
+        let mapping_path = dir.join("mapping.json");
+
+        ExportSqliteCommand::execute(
+            dir.join("secret.xml").to_str().unwrap(),
+            &db_path,
+            false,
+            FolderNameSource::default(),
+            AttributeAliases::default(),
+            ExportMode::default(),
+            true,
+            Some(mapping_path.as_path()),
+        ).unwrap();
+
+        let conn = rusqlite::Connection::open(&db_path).unwrap();
+        let (owner, cmdline): (String, String) = conn.query_row(
+            "SELECT owner, cmdline FROM jobs WHERE job_name = 'JOB1'", [], |row| Ok((row.get(0)?, row.get(1)?)),
+        ).unwrap();
+        assert!(owner.starts_with("OWNER_"));
+        assert!(cmdline.starts_with("CMDLINE_"));
+        let in_conditions: u32 = conn.query_row("SELECT COUNT(*) FROM in_conditions", [], |row| row.get(0)).unwrap();
+        assert_eq!(in_conditions, 1);
+
+        let mapping: std::collections::BTreeMap<String, String> =
+            serde_json::from_str(&fs::read_to_string(&mapping_path).unwrap()).unwrap();
+        assert_eq!(mapping[&owner], "batchadm");
+        assert_eq!(mapping[&cmdline], "run.sh --password=hunter2");
+    }
+
+    #[test]
+    fn test_analyze_anonymizes_outputs_after_analysis() {
+        let tmp = create_test_dir();
+        let dir = tmp.path();
+        let xml = SAMPLE_XML.replace(r#"<JOB JOBNAME="JOB1" APPLICATION="APP1"/>"#,
+            r#"<JOB JOBNAME="JOB1" APPLICATION="APP1" TASKTYPE="Command" CMDLINE="run.sh %%TARGET --password=hunter2"/>"#);
+        fs::write(dir.join("secret.xml"), xml).unwrap();
+        let output_dir = dir.join("output");
+
+        let options = AnalyzeOptions {
+            generate_html: false,
+            generate_csv: false,
+            generate_markdown: false,
+            generate_airflow_dags: true,
+            anonymize: true,
+            ..Default::default()
+        };
+        AnalyzeCommand::execute(dir.join("secret.xml"), output_dir.clone(), &options).unwrap();
+
+        let json = fs::read_to_string(output_dir.join("analysis.json")).unwrap();
+        assert!(json.contains("Command line uses undefined variables: TARGET"));
+        assert!(!json.contains("hunter2"));
+        for entry in fs::read_dir(output_dir.join("dags")).unwrap() {
+            assert!(!fs::read_to_string(entry.unwrap().path()).unwrap().contains("hunter2"));
+        }
+    }
+}
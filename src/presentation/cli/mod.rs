@@ -1,7 +1,11 @@
 pub mod commands;
 
+use chrono::NaiveDate;
 use clap::{Parser, Subcommand, ValueEnum};
 use std::path::PathBuf;
+use crate::domain::value_objects::{AirflowVersion, ApplTypeAliases};
+use crate::infrastructure::output::{ExportMode, GraphFormat, HtmlSection, OutputEncoding};
+use crate::infrastructure::parsers::{AttributeAliases, FolderNameSource};
 
 #[derive(Parser, Debug)]
 #[command(name = "jobweaver")]
@@ -14,13 +18,26 @@ pub struct Cli {
 
     #[arg(short, long)]
     pub verbose: bool,
+
+    #[arg(long, value_enum, global = true, help = "Log output format; falls back to the LOG_FORMAT environment variable [default: pretty]")]
+    pub log_format: Option<LogFormat>,
+}
+
+/// Format of the log lines written to stdout
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// Human-readable lines
+    #[default]
+    Pretty,
+    /// One JSON object per line, with event fields as JSON fields
+    Json,
 }
 
 #[derive(Subcommand, Debug)]
 pub enum Commands {
     #[command(about = "Analyze Control-M XML and generate migration reports")]
     Analyze {
-        #[arg(short, long, value_name = "FILE", help = "Input Control-M XML file")]
+        #[arg(short, long, value_name = "PATH", help = "Input Control-M XML file, directory of XML files, or glob (e.g. \"exports/*.xml\")")]
         input: PathBuf,
 
         #[arg(short, long, value_name = "DIR", default_value = "output", help = "Output directory for reports")]
@@ -28,6 +45,84 @@ pub enum Commands {
 
         #[arg(short, long, value_enum, default_value = "all", help = "Output format")]
         format: OutputFormat,
+
+        #[arg(long, help = "Do not generate a separate wave_N.csv file per migration wave")]
+        no_per_wave_csv: bool,
+
+        #[arg(long, value_name = "FORMAT", help = "Also write the whole-estate dependency graph (dot, mermaid, gexf, json)")]
+        graph: Option<GraphFormat>,
+
+        #[arg(long, help = "Also write airflow_dependencies.py: in-DAG edges for same-run dependencies, ExternalTaskSensors with ODATE-derived execution_delta otherwise")]
+        airflow_dependencies: bool,
+
+        #[arg(long, help = "Also write airflow_variables.json (for `airflow variables import`) from all job variables and auto-edits; names set to differing values are listed in airflow_variables_collisions.json")]
+        airflow_variables: bool,
+
+        #[arg(long, value_name = "ENCODING", default_value = "utf-8", help = "Character encoding of the CSV, HTML and Markdown reports (e.g. windows-1252); unencodable characters are written as &#NNNN; in HTML and as ? in CSV and Markdown, with a warning")]
+        output_encoding: OutputEncoding,
+
+        #[arg(long, value_name = "SECTIONS", value_delimiter = ',', help = "HTML report sections to render, comma-separated (stats, difficulty, waves, matrix, methodology, jobs) [default: all]")]
+        html_sections: Vec<HtmlSection>,
+
+        #[arg(long, value_name = "N", default_value_t = crate::infrastructure::output::html_generator::DEFAULT_SHARD_THRESHOLD, help = "Above N jobs, the HTML job table loads each wave in parts and points to the serve web UI")]
+        html_shard_threshold: usize,
+
+        #[arg(long, value_name = "YYYY-MM-DD", help = "Run date used to resolve dependency ODATEs (ODAT, PREV, ...) to producer run dates")]
+        reference_date: Option<NaiveDate>,
+
+        #[arg(long, help = "Score job complexity in parallel across all CPU cores")]
+        parallel: bool,
+
+        #[arg(long, value_name = "VERSION", help = "Target Airflow major version for operator suggestions (2 or 3, default 2)")]
+        airflow_version: Option<AirflowVersion>,
+
+        #[arg(long, value_name = "PROVIDERS", value_delimiter = ',', help = "Airflow provider packages installed in the target, comma-separated (e.g. sftp,common-sql)")]
+        airflow_providers: Vec<String>,
+
+        #[arg(long, value_name = "ALIASES", help = "Extra appl_type aliases as RAW=category pairs, comma-separated (e.g. FT_V2=file-transfer,ORA=database)")]
+        appl_type_aliases: Option<ApplTypeAliases>,
+
+        #[arg(long, value_name = "ALIASES", help = "Extra XML attribute spellings as ALIAS=CANONICAL pairs, comma-separated (e.g. JOB_TYPE=TASKTYPE)")]
+        attribute_aliases: Option<AttributeAliases>,
+
+        #[arg(long, help = "Count out-conditions (downstream jobs gated) as dependencies when scoring complexity")]
+        count_downstream_dependencies: bool,
+
+        #[arg(long, value_name = "FILE", help = "TOML file overriding complexity scoring weights (e.g. cyclic = 2); missing factors keep their defaults")]
+        weights: Option<PathBuf>,
+
+        #[arg(long, value_name = "N", help = "Estimate the migration timeline with N engineers migrating each wave in parallel")]
+        engineers: Option<usize>,
+
+        #[arg(long, value_name = "YYYY-MM-DD", requires = "engineers", help = "First working day of the migration, for projected wave and end dates")]
+        start_date: Option<NaiveDate>,
+
+        #[arg(long, value_name = "FILE.zip", help = "Also pack all generated outputs into a single zip archive")]
+        bundle: Option<PathBuf>,
+
+        #[arg(long, help = "With --format dot, group jobs into one box per folder")]
+        dot_clusters: bool,
+
+        #[arg(long, help = "With --format airflow, syntax-check each generated DAG with Python (skipped with a warning if Python isn't installed)")]
+        validate: bool,
+
+        #[arg(long, value_name = "DIR", help = "Inline jQuery and DataTables into the HTML report from DIR (jquery.dataTables.min.css, jquery-3.7.0.min.js, jquery.dataTables.min.js) so it works without internet access")]
+        offline_assets: Option<PathBuf>,
+
+        #[arg(long, value_name = "N", default_value_t = crate::application::use_cases::determine_migration_waves::DEFAULT_WAVE_COUNT, help = "Number of migration waves to spread jobs over")]
+        waves: usize,
+
+        #[arg(long, value_name = "YYYYMMDD", value_parser = parse_controlm_date, help = "Only analyze jobs whose CHANGE_DATE is on or after this date (Control-M YYYYMMDD)")]
+        changed_since: Option<NaiveDate>,
+
+        #[arg(long, requires = "changed_since", help = "With --changed-since, also keep jobs that have no CHANGE_DATE")]
+        include_undated: bool,
+
+        #[arg(long, help = "Replace owner, run_as, cmdline and node_id values with stable tokens (e.g. OWNER_a1b2) in every output")]
+        anonymize: bool,
+
+        #[arg(long, value_name = "FILE", requires = "anonymize", help = "With --anonymize, write the token-to-original-value mapping as JSON to FILE (keep it out of shared outputs)")]
+        anonymize_mapping: Option<PathBuf>,
     },
 
     #[command(about = "Export Control-M XML raw data to SQLite database")]
@@ -37,6 +132,113 @@ pub enum Commands {
 
         #[arg(short, long, value_name = "FILE", default_value = "controlm.db", help = "Output SQLite database file")]
         output: PathBuf,
+
+        #[arg(long, help = "Also store each job's cmdline split into program and arguments")]
+        tokenize_cmdline: bool,
+
+        #[arg(long, value_name = "SOURCE", default_value = "structural", help = "Folder name stored for jobs whose PARENT_FOLDER differs from their containing folder (structural or parent-folder)")]
+        folder_name_source: FolderNameSource,
+
+        #[arg(long, value_name = "ALIASES", help = "Extra XML attribute spellings as ALIAS=CANONICAL pairs, comma-separated (e.g. JOB_TYPE=TASKTYPE)")]
+        attribute_aliases: Option<AttributeAliases>,
+
+        #[arg(long, value_name = "MODE", default_value = "append", help = "How rows already in the database are treated: append (fails on folders already stored), replace (clear first) or upsert (replace jobs with the same folder and job name)")]
+        mode: ExportMode,
+
+        #[arg(long, help = "Replace owner, run_as, cmdline and node_id values with stable tokens (e.g. OWNER_a1b2) in the database")]
+        anonymize: bool,
+
+        #[arg(long, value_name = "FILE", requires = "anonymize", help = "With --anonymize, write the token-to-original-value mapping as JSON to FILE (keep it out of shared outputs)")]
+        anonymize_mapping: Option<PathBuf>,
+    },
+
+    #[cfg(feature = "duckdb")]
+    #[command(about = "Export Control-M XML to a DuckDB database for analytical queries")]
+    ExportDuckdb {
+        #[arg(short, long, value_name = "FILES", help = "Input Control-M XML file(s) - comma-separated for multiple files")]
+        input: String,
+
+        #[arg(short, long, value_name = "FILE", default_value = "controlm.duckdb", help = "Output DuckDB database file")]
+        output: PathBuf,
+
+        #[arg(long, value_name = "SOURCE", default_value = "structural", help = "Folder name stored for jobs whose PARENT_FOLDER differs from their containing folder (structural or parent-folder)")]
+        folder_name_source: FolderNameSource,
+
+        #[arg(long, value_name = "ALIASES", help = "Extra XML attribute spellings as ALIAS=CANONICAL pairs, comma-separated (e.g. JOB_TYPE=TASKTYPE)")]
+        attribute_aliases: Option<AttributeAliases>,
+    },
+
+    #[cfg(feature = "parquet")]
+    #[command(about = "Export Control-M XML to Parquet files (jobs and conditions) for data lakes")]
+    ExportParquet {
+        #[arg(short, long, value_name = "FILES", help = "Input Control-M XML file(s) - comma-separated for multiple files")]
+        input: String,
+
+        #[arg(short, long, value_name = "DIR", default_value = "controlm-parquet", help = "Output directory for jobs.parquet and conditions.parquet")]
+        output: PathBuf,
+
+        #[arg(long, help = "Also store each job's cmdline split into program and arguments")]
+        tokenize_cmdline: bool,
+
+        #[arg(long, value_name = "SOURCE", default_value = "structural", help = "Folder name stored for jobs whose PARENT_FOLDER differs from their containing folder (structural or parent-folder)")]
+        folder_name_source: FolderNameSource,
+
+        #[arg(long, value_name = "ALIASES", help = "Extra XML attribute spellings as ALIAS=CANONICAL pairs, comma-separated (e.g. JOB_TYPE=TASKTYPE)")]
+        attribute_aliases: Option<AttributeAliases>,
+    },
+
+    #[command(about = "Parse Control-M XML without writing anything and report skipped or unknown elements")]
+    Validate {
+        #[arg(short, long, value_name = "PATH", help = "Input Control-M XML file, directory of XML files, or glob (e.g. \"exports/*.xml\")")]
+        input: PathBuf,
+
+        #[arg(long, help = "Print the report as JSON")]
+        json: bool,
+
+        #[arg(long, value_name = "ALIASES", help = "Extra XML attribute spellings as ALIAS=CANONICAL pairs, comma-separated (e.g. JOB_TYPE=TASKTYPE)")]
+        attribute_aliases: Option<AttributeAliases>,
+    },
+
+    #[command(about = "Detect dependency cycles and emit a remediation change-script")]
+    Cycles {
+        #[arg(short, long, value_name = "FILE", help = "Input Control-M XML file")]
+        input: PathBuf,
+
+        #[arg(short, long, value_name = "FILE", help = "Output file for the script (prints to stdout if omitted)")]
+        output: Option<PathBuf>,
+    },
+
+    #[command(about = "Compare two Control-M exports and list added, removed and modified folders and jobs")]
+    Diff {
+        #[arg(value_name = "OLD", help = "Earlier Control-M XML file, directory of XML files, glob, or SQLite database")]
+        old: PathBuf,
+
+        #[arg(value_name = "NEW", help = "Later Control-M XML file, directory of XML files, glob, or SQLite database")]
+        new: PathBuf,
+
+        #[arg(long, help = "Print the differences as JSON instead of a summary")]
+        json: bool,
+
+        #[arg(short, long, value_name = "FILE", help = "Also write the differences as JSON to this file")]
+        output: Option<PathBuf>,
+
+        #[arg(long, value_name = "ALIASES", help = "Extra XML attribute spellings as ALIAS=CANONICAL pairs, comma-separated (e.g. JOB_TYPE=TASKTYPE)")]
+        attribute_aliases: Option<AttributeAliases>,
+    },
+
+    #[command(about = "Check an exported SQLite database for orphaned rows and corruption")]
+    Fsck {
+        #[arg(short, long, value_name = "FILE", default_value = "controlm.db", help = "SQLite database file")]
+        database: PathBuf,
+
+        #[arg(long, help = "Delete orphaned child rows that were found")]
+        fix: bool,
+    },
+
+    #[command(about = "Show summary statistics of an exported SQLite database")]
+    DbStats {
+        #[arg(short, long, value_name = "FILE", default_value = "controlm.db", help = "SQLite database file")]
+        database: PathBuf,
     },
 
     #[command(about = "Start web server for interactive job exploration")]
@@ -49,6 +251,12 @@ pub enum Commands {
 
         #[arg(long, default_value = "127.0.0.1", help = "Host to bind to")]
         host: String,
+
+        #[arg(long, help = "Open the database read-only, e.g. a replica refreshed elsewhere and picked up with POST /api/admin/reload")]
+        read_only: bool,
+
+        #[arg(long, help = "Serve only analysis endpoints to viewers; job search, detail, graphs and export require an admin (ADMIN_USERNAMES)")]
+        analysis_only: bool,
     },
 }
 
@@ -58,6 +266,10 @@ pub enum OutputFormat {
     Csv,
     Html,
     Markdown,
+    /// One Airflow DAG file per top-level folder, written to `dags/`
+    Airflow,
+    /// Graphviz DOT picture of the whole dependency graph, written to `analysis.dot`
+    Dot,
     All,
 }
 
@@ -77,4 +289,18 @@ impl OutputFormat {
     pub fn should_generate_markdown(&self) -> bool {
         matches!(self, OutputFormat::Markdown | OutputFormat::All)
     }
+
+    pub fn should_generate_airflow(&self) -> bool {
+        matches!(self, OutputFormat::Airflow)
+    }
+
+    pub fn should_generate_dot(&self) -> bool {
+        matches!(self, OutputFormat::Dot)
+    }
+}
+
+/// Parses a date in Control-M's `YYYYMMDD` format
+fn parse_controlm_date(value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value.trim(), "%Y%m%d")
+        .map_err(|e| format!("expected a YYYYMMDD date: {}", e))
 }
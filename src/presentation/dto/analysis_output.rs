@@ -1,9 +1,15 @@
 use serde::{Deserialize, Serialize};
 use chrono::Utc;
+use std::collections::BTreeMap;
 use crate::application::use_cases::{
-    analyze_jobs::AnalysisResult,
+    analyze_jobs::{
+        AmbiguousProducer, AnalysisResult, CrossDatacenterDependency, DependencyRunDate, OnDemandJob, OrphanedCondition,
+        ResolvedShout,
+    },
+    build_folder_matrix::FolderDependencyMatrix,
     calculate_complexity::JobComplexityResult,
     determine_migration_waves::MigrationWave,
+    estimate_timeline::MigrationTimeline,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,6 +17,21 @@ pub struct AnalysisOutput {
     pub summary: SummaryOutput,
     pub jobs: Vec<JobOutput>,
     pub migration_waves: Vec<WaveOutput>,
+    #[serde(default)]
+    pub shout_alerts: Vec<ResolvedShout>,
+    #[serde(default)]
+    pub folder_matrix: FolderDependencyMatrix,
+    #[serde(default)]
+    pub on_demand_jobs: Vec<OnDemandJob>,
+    #[serde(default)]
+    pub dependency_dates: Vec<DependencyRunDate>,
+    #[serde(default)]
+    pub cross_datacenter_dependencies: Vec<CrossDatacenterDependency>,
+    /// Out-conditions raised by more than one job
+    #[serde(default)]
+    pub ambiguous_producers: Vec<AmbiguousProducer>,
+    #[serde(default)]
+    pub timeline: Option<MigrationTimeline>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,6 +41,26 @@ pub struct SummaryOutput {
     pub analysis_date: String,
     pub average_complexity_score: f64,
     pub has_circular_dependencies: bool,
+    /// Jobs of each dependency cycle, so consumers can show where cycles are
+    #[serde(default)]
+    pub circular_dependencies: Vec<Vec<String>>,
+    /// In-conditions no job sets; these jobs would wait forever
+    #[serde(default)]
+    pub orphaned_conditions: Vec<OrphanedCondition>,
+    /// Change-date filter applied before analysis, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub change_filter: Option<ChangeFilterOutput>,
+}
+
+/// Change-date filter limiting the analysis to recently modified jobs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeFilterOutput {
+    /// Earliest CHANGE_DATE kept (YYYY-MM-DD)
+    pub changed_since: String,
+    /// Whether jobs without a CHANGE_DATE were kept
+    pub include_undated: bool,
+    /// Jobs parsed before filtering; `total_jobs` counts the jobs kept
+    pub jobs_before_filter: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,6 +70,10 @@ pub struct JobOutput {
     pub folder_name: String,
     pub complexity_score: u32,
     pub migration_difficulty: String,
+    /// Whether the job can be migrated now ("Ready", "NeedsReview" or
+    /// "Blocked"), independent of its difficulty
+    #[serde(default)]
+    pub migration_readiness: String,
     pub migration_priority: u32,
     pub migration_wave: usize,
     pub is_critical: bool,
@@ -36,6 +81,14 @@ pub struct JobOutput {
     pub estimated_effort_hours: u32,
     pub metrics: MetricsOutput,
     pub risks: Vec<String>,
+    /// Every migration risk detected for the job (cycles, cross-datacenter
+    /// dependencies, JCL, undefined variables, ...)
+    #[serde(default)]
+    pub migration_notes: Vec<String>,
+    /// Points contributed by each complexity factor (dependencies,
+    /// scheduling, ...); they sum to `complexity_score`
+    #[serde(default)]
+    pub factor_breakdown: BTreeMap<String, u32>,
     pub airflow_mapping: AirflowMappingOutput,
 }
 
@@ -50,6 +103,12 @@ pub struct MetricsOutput {
 pub struct AirflowMappingOutput {
     pub suggested_dag_name: String,
     pub operator_type: String,
+    #[serde(default)]
+    pub operator_import: String,
+    #[serde(default)]
+    pub provider: Option<String>,
+    #[serde(default)]
+    pub mapping_note: Option<String>,
     pub estimated_effort_hours: u32,
 }
 
@@ -69,6 +128,9 @@ impl AnalysisOutput {
             analysis_date: Utc::now().format("%Y-%m-%d").to_string(),
             average_complexity_score: result.average_complexity,
             has_circular_dependencies: result.has_circular_dependencies,
+            circular_dependencies: result.circular_dependencies,
+            orphaned_conditions: result.orphaned_conditions,
+            change_filter: None,
         };
 
         let jobs: Vec<JobOutput> = result.complexity_results
@@ -85,6 +147,39 @@ impl AnalysisOutput {
             summary,
             jobs,
             migration_waves,
+            shout_alerts: result.resolved_shouts,
+            folder_matrix: result.folder_matrix,
+            on_demand_jobs: result.on_demand_jobs,
+            dependency_dates: result.dependency_dates,
+            cross_datacenter_dependencies: result.cross_datacenter_dependencies,
+            ambiguous_producers: result.ambiguous_producers,
+            timeline: result.timeline,
+        }
+    }
+
+    /// Builds an output with a fixed summary and the given jobs for tests
+    #[cfg(test)]
+    pub(crate) fn for_test(jobs: Vec<JobOutput>) -> Self {
+        Self {
+            summary: SummaryOutput {
+                total_jobs: 10,
+                total_folders: 2,
+                analysis_date: "2026-01-12".to_string(),
+                average_complexity_score: 42.5,
+                has_circular_dependencies: false,
+                circular_dependencies: vec![],
+                orphaned_conditions: vec![],
+                change_filter: None,
+            },
+            jobs,
+            migration_waves: vec![],
+            shout_alerts: vec![],
+            folder_matrix: Default::default(),
+            on_demand_jobs: vec![],
+            dependency_dates: vec![],
+            cross_datacenter_dependencies: vec![],
+            ambiguous_producers: vec![],
+            timeline: None,
         }
     }
 }
@@ -94,11 +189,10 @@ impl JobOutput {
         let risks = Self::generate_risks(&result);
         let airflow_mapping = AirflowMappingOutput {
             suggested_dag_name: Self::generate_dag_name(&result.job_name),
-            operator_type: if result.is_cyclic {
-                "PythonOperator".to_string()
-            } else {
-                "BashOperator".to_string()
-            },
+            operator_type: result.operator_mapping.operator.clone(),
+            operator_import: result.operator_mapping.import_path.clone(),
+            provider: result.operator_mapping.provider.clone(),
+            mapping_note: result.operator_mapping.note.clone(),
             estimated_effort_hours: result.migration_difficulty.estimated_effort_hours(),
         };
 
@@ -108,6 +202,7 @@ impl JobOutput {
             folder_name: result.folder_name,
             complexity_score: result.complexity_score.value(),
             migration_difficulty: result.migration_difficulty.to_string(),
+            migration_readiness: result.migration_readiness.to_string(),
             migration_priority: result.migration_priority.value(),
             migration_wave: result.migration_wave,
             is_critical: result.is_critical,
@@ -119,6 +214,8 @@ impl JobOutput {
                 is_cyclic: result.is_cyclic,
             },
             risks,
+            migration_notes: result.migration_notes,
+            factor_breakdown: result.factor_breakdown,
             airflow_mapping,
         }
     }
@@ -142,6 +239,11 @@ impl JobOutput {
             risks.push("Very high complexity - consider breaking into smaller DAGs".to_string());
         }
 
+        // Fallbacks against the assumed default target aren't risks
+        if let Some(note) = result.operator_mapping.note.as_ref().filter(|_| result.operator_mapping.fallback_risk) {
+            risks.push(format!("Operator fallback - {}", note));
+        }
+
         if risks.is_empty() {
             risks.push("Low risk migration".to_string());
         }
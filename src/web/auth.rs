@@ -11,9 +11,14 @@ use serde::{Deserialize, Serialize};
 use chrono::{Duration, Utc, DateTime};
 use bcrypt::{hash, verify, DEFAULT_COST};
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Mutex;
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection};
+use tracing::warn;
 
-use crate::web::models::{AuthType, UserInfo};
+use crate::web::config::{WebConfig, DEFAULT_TOKEN_TTL_HOURS};
+use crate::web::models::{AuthType, UserInfo, UserRole};
 
 /// JWT claims structure
 ///
@@ -28,6 +33,9 @@ pub struct Claims {
     pub display_name: String,
     /// Authentication type (Local or EntraId)
     pub auth_type: AuthType,
+    /// Access level (tokens issued before roles existed decode as viewer)
+    #[serde(default)]
+    pub role: UserRole,
     /// Token expiration timestamp (Unix epoch)
     pub exp: i64,
 }
@@ -38,6 +46,8 @@ pub struct Claims {
 pub struct AuthService {
     /// Secret key for JWT signing and verification
     jwt_secret: String,
+    /// Hours a generated token stays valid
+    token_ttl_hours: u64,
 }
 
 impl AuthService {
@@ -51,12 +61,29 @@ impl AuthService {
     ///
     /// A new AuthService instance
     pub fn new(jwt_secret: String) -> Self {
-        Self { jwt_secret }
+        Self {
+            jwt_secret,
+            token_ttl_hours: DEFAULT_TOKEN_TTL_HOURS,
+        }
+    }
+
+    /// Sets how long generated tokens stay valid
+    ///
+    /// # Arguments
+    ///
+    /// * `token_ttl_hours` - Token lifetime in hours
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn with_token_ttl_hours(mut self, token_ttl_hours: u64) -> Self {
+        self.token_ttl_hours = token_ttl_hours;
+        self
     }
 
     /// Generates a JWT token for a user
     ///
-    /// Token is valid for 24 hours from creation.
+    /// Token is valid for the configured TTL (24 hours by default) from creation.
     ///
     /// # Arguments
     ///
@@ -65,10 +92,16 @@ impl AuthService {
     /// # Returns
     ///
     /// Result containing the JWT token string or an error
-    pub fn generate_token(&self, user: &UserInfo) -> Result<String, jsonwebtoken::errors::Error> {
-        let expiration = Utc::now()
-            .checked_add_signed(Duration::hours(24))
-            .expect("valid timestamp")
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the TTL puts the expiry beyond the representable
+    /// timestamps, or if encoding fails
+    pub fn generate_token(&self, user: &UserInfo) -> Result<String> {
+        let expiration = i64::try_from(self.token_ttl_hours).ok()
+            .and_then(Duration::try_hours)
+            .and_then(|ttl| Utc::now().checked_add_signed(ttl))
+            .with_context(|| format!("Token lifetime of {} hours is out of range", self.token_ttl_hours))?
             .timestamp();
 
         let claims = Claims {
@@ -76,6 +109,7 @@ impl AuthService {
             username: user.username.clone(),
             display_name: user.display_name.clone(),
             auth_type: user.auth_type.clone(),
+            role: user.role,
             exp: expiration,
         };
 
@@ -83,7 +117,7 @@ impl AuthService {
             &Header::default(),
             &claims,
             &EncodingKey::from_secret(self.jwt_secret.as_bytes()),
-        )
+        ).context("Failed to encode token")
     }
 
     /// Verifies and decodes a JWT token
@@ -171,9 +205,19 @@ struct LoginAttempt {
     locked_until: Option<DateTime<Utc>>,
 }
 
+/// Table keeping failed login attempts across server restarts
+const LOGIN_ATTEMPTS_SCHEMA: &str = "CREATE TABLE IF NOT EXISTS login_attempts (
+    username TEXT PRIMARY KEY,
+    attempts INTEGER NOT NULL,
+    locked_until INTEGER
+)";
+
 /// Login attempt tracker for rate limiting
 ///
 /// Tracks failed login attempts per username and enforces lockout periods.
+/// Attempts are kept in memory; with persistence enabled they are also
+/// written through to a SQLite `login_attempts` table and loaded back at
+/// startup, so a restart does not clear lockouts.
 pub struct LoginAttemptTracker {
     /// Map of username to login attempt info
     attempts: Mutex<HashMap<String, LoginAttempt>>,
@@ -181,6 +225,8 @@ pub struct LoginAttemptTracker {
     max_attempts: u32,
     /// Lockout duration in minutes
     lockout_duration_minutes: u64,
+    /// Database the attempts are persisted to, if enabled
+    store: Option<Mutex<Connection>>,
 }
 
 impl LoginAttemptTracker {
@@ -195,6 +241,73 @@ impl LoginAttemptTracker {
             attempts: Mutex::new(HashMap::new()),
             max_attempts,
             lockout_duration_minutes,
+            store: None,
+        }
+    }
+
+    /// Persists attempts to a SQLite database and loads the ones already stored
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - SQLite file holding the `login_attempts` table (created if missing)
+    ///
+    /// # Returns
+    ///
+    /// Result containing the tracker with persistence enabled
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database cannot be opened, created or read
+    pub fn with_persistence<P: AsRef<Path>>(mut self, path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let conn = Connection::open(path)
+            .with_context(|| format!("Failed to open login attempts database {}", path.display()))?;
+        conn.execute(LOGIN_ATTEMPTS_SCHEMA, [])
+            .context("Failed to create login_attempts table")?;
+
+        {
+            let attempts = self.attempts.get_mut().unwrap();
+            let mut stmt = conn.prepare("SELECT username, attempts, locked_until FROM login_attempts")?;
+            let rows = stmt.query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, u32>(1)?, row.get::<_, Option<i64>>(2)?))
+            })?;
+            for row in rows {
+                let (username, count, locked_until) = row.context("Failed to read login_attempts")?;
+                attempts.insert(username, LoginAttempt {
+                    attempts: count,
+                    locked_until: locked_until.and_then(|secs| DateTime::from_timestamp(secs, 0)),
+                });
+            }
+        }
+
+        self.store = Some(Mutex::new(conn));
+        Ok(self)
+    }
+
+    /// Writes a username's attempts through to the database, if persistence is enabled
+    ///
+    /// A failed write is logged rather than failing the login; the in-memory
+    /// state still applies until the next restart.
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - Username whose attempts changed
+    /// * `attempt` - Current attempts, or None once they are cleared
+    fn persist(&self, username: &str, attempt: Option<&LoginAttempt>) {
+        let Some(store) = &self.store else {
+            return;
+        };
+        let conn = store.lock().unwrap();
+        let result = match attempt {
+            Some(attempt) => conn.execute(
+                "INSERT INTO login_attempts (username, attempts, locked_until) VALUES (?1, ?2, ?3)
+                 ON CONFLICT(username) DO UPDATE SET attempts = excluded.attempts, locked_until = excluded.locked_until",
+                params![username, attempt.attempts, attempt.locked_until.map(|t| t.timestamp())],
+            ),
+            None => conn.execute("DELETE FROM login_attempts WHERE username = ?1", params![username]),
+        };
+        if let Err(e) = result {
+            warn!("Failed to persist login attempts for {}: {}", username, e);
         }
     }
 
@@ -217,6 +330,7 @@ impl LoginAttemptTracker {
                 } else {
                     // Lockout period expired, reset attempts
                     attempts.remove(username);
+                    self.persist(username, None);
                     return false;
                 }
             }
@@ -247,9 +361,11 @@ impl LoginAttemptTracker {
             // Lock the account
             let lockout_until = Utc::now() + Duration::minutes(self.lockout_duration_minutes as i64);
             attempt.locked_until = Some(lockout_until);
+            self.persist(username, Some(&*attempt));
             return 0;
         }
         
+        self.persist(username, Some(&*attempt));
         self.max_attempts - attempt.attempts
     }
 
@@ -260,7 +376,9 @@ impl LoginAttemptTracker {
     /// * `username` - Username to reset attempts for
     pub fn reset_attempts(&self, username: &str) {
         let mut attempts = self.attempts.lock().unwrap();
-        attempts.remove(username);
+        if attempts.remove(username).is_some() {
+            self.persist(username, None);
+        }
     }
 
     /// Gets the remaining time until lockout expires
@@ -290,17 +408,16 @@ impl LoginAttemptTracker {
 
 /// In-memory user store for local authentication
 ///
-/// Validates credentials against configuration from .env file.
+/// Holds a bcrypt hash per username, loaded from the file named by
+/// `USERS_FILE` or, for compatibility, from the single `.env` credential pair.
 #[derive(Debug)]
 pub struct UserStore {
-    /// Configured username from .env
-    username: String,
-    /// Configured password hash from .env
-    password_hash: String,
+    /// Map of username to bcrypt password hash
+    users: HashMap<String, String>,
 }
 
 impl UserStore {
-    /// Creates a new UserStore with credentials from configuration
+    /// Creates a UserStore with a single user from configuration
     ///
     /// # Arguments
     ///
@@ -314,14 +431,104 @@ impl UserStore {
         let auth_service = AuthService::new("temp-secret".to_string());
         let password_hash = auth_service.hash_password(&password)
             .expect("Failed to hash password");
-        
-        Self { 
-            username,
-            password_hash,
+
+        Self {
+            users: HashMap::from([(username, password_hash)]),
         }
     }
 
-    /// Verifies user credentials against configured username and password
+    /// Creates a UserStore from a map of usernames to bcrypt hashes
+    ///
+    /// # Arguments
+    ///
+    /// * `users` - Map of username to bcrypt password hash
+    ///
+    /// # Returns
+    ///
+    /// A new UserStore instance
+    pub fn from_hashes(users: HashMap<String, String>) -> Self {
+        Self { users }
+    }
+
+    /// Loads users from a JSON or TOML credentials file
+    ///
+    /// The file maps each username to a bcrypt hash, e.g. `{"alice": "$2b$12$..."}`
+    /// in JSON or `alice = "$2b$12$..."` in TOML. Files ending in `.toml` are
+    /// read as TOML, anything else as JSON.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the credentials file
+    ///
+    /// # Returns
+    ///
+    /// A new UserStore instance
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed, lists no users,
+    /// or holds a value that is not a bcrypt hash (e.g. a plain-text password)
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read users file {}", path.display()))?;
+        let users: HashMap<String, String> = if path.extension().is_some_and(|ext| ext == "toml") {
+            toml::from_str(&content)
+                .with_context(|| format!("Failed to parse users file {} as TOML", path.display()))?
+        } else {
+            serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse users file {} as JSON", path.display()))?
+        };
+
+        if users.is_empty() {
+            anyhow::bail!("Users file {} lists no users", path.display());
+        }
+        if let Some(username) = users.iter()
+            .find(|(_, hash)| !hash.starts_with("$2"))
+            .map(|(username, _)| username)
+        {
+            anyhow::bail!(
+                "Users file {}: the password for '{}' is not a bcrypt hash",
+                path.display(), username
+            );
+        }
+
+        Ok(Self::from_hashes(users))
+    }
+
+    /// Creates the UserStore described by the web configuration
+    ///
+    /// Uses `users_file` when set, otherwise the single `.env` credential pair.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Web server configuration
+    ///
+    /// # Returns
+    ///
+    /// A new UserStore instance
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the users file cannot be loaded
+    pub fn from_config(config: &WebConfig) -> Result<Self> {
+        match &config.users_file {
+            Some(path) => Self::from_file(path),
+            None => Ok(Self::new(config.auth_username.clone(), config.auth_password.clone())),
+        }
+    }
+
+    /// Gets the number of users that can log in
+    pub fn len(&self) -> usize {
+        self.users.len()
+    }
+
+    /// Checks whether the store has no users
+    pub fn is_empty(&self) -> bool {
+        self.users.is_empty()
+    }
+
+    /// Verifies user credentials against the stored hash for the username
     ///
     /// # Arguments
     ///
@@ -332,12 +539,75 @@ impl UserStore {
     ///
     /// `true` if credentials are valid, `false` otherwise
     pub fn verify_user(&self, username: &str, password: &str) -> bool {
-        if username != self.username {
+        let Some(password_hash) = self.users.get(username) else {
             return false;
-        }
-        
+        };
+
         let auth_service = AuthService::new("temp-secret".to_string());
-        auth_service.verify_password(password, &self.password_hash).unwrap_or(false)
+        auth_service.verify_password(password, password_hash).unwrap_or(false)
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_token_rejects_out_of_range_ttl() {
+        let user = UserInfo {
+            username: "analyst".to_string(),
+            display_name: "Analyst".to_string(),
+            auth_type: AuthType::Local,
+            role: UserRole::Viewer,
+        };
+        let auth_service = AuthService::new("test-secret".to_string()).with_token_ttl_hours(u64::MAX);
+        let err = auth_service.generate_token(&user).unwrap_err();
+        assert!(err.to_string().contains("out of range"));
+    }
+
+    #[test]
+    fn test_user_store_loads_users_from_json_and_toml() {
+        let alice = hash("alice-pass", 4).unwrap();
+        let bob = hash("bob-pass", 4).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let json_path = dir.path().join("users.json");
+        let toml_path = dir.path().join("users.toml");
+        std::fs::write(&json_path, serde_json::json!({"alice": alice, "bob": bob}).to_string()).unwrap();
+        std::fs::write(&toml_path, format!("alice = '{}'\nbob = '{}'\n", alice, bob)).unwrap();
+
+        for path in [&json_path, &toml_path] {
+            let store = UserStore::from_file(path).unwrap();
+            assert_eq!(store.len(), 2);
+            assert!(store.verify_user("alice", "alice-pass"));
+            assert!(store.verify_user("bob", "bob-pass"));
+            assert!(!store.verify_user("alice", "bob-pass"));
+            assert!(!store.verify_user("carol", "alice-pass"));
+        }
+
+        std::fs::write(&json_path, r#"{"alice": "plain-text"}"#).unwrap();
+        let err = UserStore::from_file(&json_path).unwrap_err();
+        assert!(err.to_string().contains("not a bcrypt hash"), "{}", err);
+    }
+
+    #[test]
+    fn test_persisted_lockout_survives_new_tracker() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("login_attempts.db");
+
+        let tracker = LoginAttemptTracker::new(2, 30).with_persistence(&path).unwrap();
+        assert_eq!(tracker.record_failed_attempt("alice"), 1);
+        assert_eq!(tracker.record_failed_attempt("alice"), 0);
+        assert_eq!(tracker.record_failed_attempt("bob"), 1);
+        tracker.reset_attempts("bob");
+        drop(tracker);
+
+        // A restarted server reloads the lockout
+        let restarted = LoginAttemptTracker::new(2, 30).with_persistence(&path).unwrap();
+        assert!(restarted.is_locked_out("alice"));
+        assert!(restarted.get_lockout_remaining_minutes("alice").is_some());
+        assert_eq!(restarted.record_failed_attempt("bob"), 1);
+
+        // The in-memory default forgets everything
+        assert!(!LoginAttemptTracker::new(2, 30).is_locked_out("alice"));
+    }
+}
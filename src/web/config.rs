@@ -4,6 +4,21 @@
 //! including authentication settings, JWT configuration, and Entra ID integration.
 
 use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use crate::domain::value_objects::ApplTypeAliases;
+use crate::web::models::UserRole;
+
+/// Minimum JWT secret length in bytes (HS256 keys should be at least 256 bits)
+pub const MIN_JWT_SECRET_LEN: usize = 32;
+
+/// Default lifetime of issued JWT tokens in hours
+pub const DEFAULT_TOKEN_TTL_HOURS: u64 = 24;
+
+/// Longest accepted lifetime of issued JWT tokens in hours (30 days)
+pub const MAX_TOKEN_TTL_HOURS: u64 = 24 * 30;
+
+/// Fragments of the placeholder secrets shipped in defaults and `.env.example`
+const EXAMPLE_SECRET_MARKERS: &[&str] = &["change-in-production", "your-secret-key", "changeme"];
 
 /// Web server configuration
 ///
@@ -16,10 +31,26 @@ pub struct WebConfig {
     pub host: String,
     /// Server port number
     pub port: u16,
+    /// Number of HTTP worker threads
+    pub workers: usize,
     /// Path to SQLite database file
     pub database_path: String,
+    /// Seconds dashboard stats are cached before being recomputed (0 disables caching)
+    pub dashboard_cache_ttl_secs: u64,
+    /// Groups raw appl_type values into categories for dashboard counts
+    pub appl_type_aliases: ApplTypeAliases,
+    /// Whether the database is opened read-only (a replica reloaded via /api/admin/reload)
+    pub read_only: bool,
+    /// Whether only analysis endpoints are routed for viewers; raw job data
+    /// (search, detail, export) and owner/run-as breakdowns then require the
+    /// admin role
+    pub analysis_only: bool,
+    /// Usernames granted the admin role at login
+    pub admin_usernames: Vec<String>,
     /// Secret key for JWT token signing and verification
     pub jwt_secret: String,
+    /// Hours an issued or refreshed JWT token stays valid
+    pub token_ttl_hours: u64,
     /// Secret key for session management
     pub session_key: String,
     /// Whether Entra ID (Azure AD) authentication is enabled
@@ -32,10 +63,16 @@ pub struct WebConfig {
     pub auth_username: String,
     /// Password for local authentication (loaded from .env)
     pub auth_password: String,
+    /// JSON or TOML file mapping usernames to bcrypt hashes; replaces the
+    /// single `auth_username`/`auth_password` pair when set
+    pub users_file: Option<String>,
     /// Maximum number of failed login attempts before account lockout
     pub max_login_attempts: u32,
     /// Duration of account lockout in minutes after exceeding max attempts
     pub lockout_duration_minutes: u64,
+    /// Whether failed login attempts are kept in a SQLite file next to the
+    /// database so lockouts survive restarts (in memory only by default)
+    pub persist_login_attempts: bool,
 }
 
 /// Entra ID (Azure AD) OAuth configuration
@@ -60,14 +97,22 @@ impl Default for WebConfig {
     ///
     /// - Host: "0.0.0.0" (all interfaces)
     /// - Port: 8080
+    /// - Workers: number of available CPUs (falls back to 1)
     /// - Database: "controlm.db"
+    /// - Dashboard Cache TTL: 60 seconds
+    /// - Appl Type Aliases: built-in aliases only
+    /// - Read Only: false
+    /// - Analysis Only: false (no admin usernames)
     /// - JWT Secret: "your-secret-key-change-in-production" (MUST change in production)
+    /// - Token TTL: 24 hours
     /// - Session Key: "your-session-key-change-in-production" (MUST change in production)
     /// - Entra ID: Disabled
     /// - Auth Username: "admin"
     /// - Auth Password: "admin" (MUST change in production)
+    /// - Users File: None (use the single username/password pair)
     /// - Max Login Attempts: 3
     /// - Lockout Duration: 30 minutes
+    /// - Persist Login Attempts: false (kept in memory)
     ///
     /// # Security Warning
     ///
@@ -77,15 +122,24 @@ impl Default for WebConfig {
         Self {
             host: "0.0.0.0".to_string(),
             port: 8080,
+            workers: std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
             database_path: "controlm.db".to_string(),
+            dashboard_cache_ttl_secs: 60,
+            appl_type_aliases: ApplTypeAliases::default(),
+            read_only: false,
+            analysis_only: false,
+            admin_usernames: Vec::new(),
             jwt_secret: "your-secret-key-change-in-production".to_string(),
+            token_ttl_hours: DEFAULT_TOKEN_TTL_HOURS,
             session_key: "your-session-key-change-in-production".to_string(),
             enable_entra_id: false,
             entra_id_config: None,
             auth_username: "admin".to_string(),
             auth_password: "admin".to_string(),
+            users_file: None,
             max_login_attempts: 3,
             lockout_duration_minutes: 30,
+            persist_login_attempts: false,
         }
     }
 }
@@ -137,6 +191,79 @@ impl WebConfig {
         self
     }
 
+    /// Sets the number of HTTP worker threads
+    ///
+    /// # Arguments
+    ///
+    /// * `workers` - Number of worker threads (values below 1 are raised to 1)
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn with_workers(mut self, workers: usize) -> Self {
+        self.workers = workers.max(1);
+        self
+    }
+
+    /// Sets whether the database is opened read-only
+    ///
+    /// # Arguments
+    ///
+    /// * `read_only` - Whether to open the database read-only
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Sets whether only analysis endpoints are served to viewers
+    ///
+    /// # Arguments
+    ///
+    /// * `analysis_only` - Whether raw job data requires the admin role
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn with_analysis_only(mut self, analysis_only: bool) -> Self {
+        self.analysis_only = analysis_only;
+        self
+    }
+
+    /// Sets the usernames granted the admin role
+    ///
+    /// # Arguments
+    ///
+    /// * `admin_usernames` - Usernames that may read raw job data in analysis-only mode
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn with_admin_usernames(mut self, admin_usernames: Vec<String>) -> Self {
+        self.admin_usernames = admin_usernames;
+        self
+    }
+
+    /// Gets the role granted to a user at login
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - Authenticated username
+    ///
+    /// # Returns
+    ///
+    /// `UserRole::Admin` for listed admin usernames, `UserRole::Viewer` otherwise
+    pub fn role_for(&self, username: &str) -> UserRole {
+        if self.admin_usernames.iter().any(|admin| admin == username) {
+            UserRole::Admin
+        } else {
+            UserRole::Viewer
+        }
+    }
+
     /// Enables Entra ID authentication with the given configuration
     ///
     /// # Arguments
@@ -151,4 +278,204 @@ impl WebConfig {
         self.entra_id_config = Some(config);
         self
     }
+
+    /// Gets the SQLite file failed login attempts are persisted to
+    ///
+    /// # Returns
+    ///
+    /// The database path with a `.logins.db` extension, e.g. `controlm.logins.db`
+    pub fn login_attempts_path(&self) -> std::path::PathBuf {
+        std::path::Path::new(&self.database_path).with_extension("logins.db")
+    }
+
+    /// Checks the configuration for settings that would make the server
+    /// insecure or fail at runtime
+    ///
+    /// # Returns
+    ///
+    /// One actionable message per problem found (empty when valid)
+    pub fn validation_errors(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        let secret = self.jwt_secret.trim();
+        let lowered = secret.to_lowercase();
+        if secret.is_empty() {
+            errors.push("JWT_SECRET is empty; set it to a long random string".to_string());
+        } else if EXAMPLE_SECRET_MARKERS.iter().any(|marker| lowered.contains(marker)) {
+            errors.push("JWT_SECRET is the default/example value; replace it with a long random string (e.g. `openssl rand -hex 32`)".to_string());
+        } else if secret.len() < MIN_JWT_SECRET_LEN {
+            errors.push(format!(
+                "JWT_SECRET is too weak ({} bytes); use at least {} bytes",
+                secret.len(), MIN_JWT_SECRET_LEN
+            ));
+        }
+
+        if !(1..=MAX_TOKEN_TTL_HOURS).contains(&self.token_ttl_hours) {
+            errors.push(format!("TOKEN_TTL_HOURS must be between 1 and {}", MAX_TOKEN_TTL_HOURS));
+        }
+
+        // Local login is always available, so it needs credentials
+        match &self.users_file {
+            Some(path) => {
+                if !std::path::Path::new(path).is_file() {
+                    errors.push(format!("USERS_FILE '{}' does not exist", path));
+                }
+            }
+            None => {
+                if self.auth_username.trim().is_empty() {
+                    errors.push("AUTH_USERNAME is empty; local login needs a username".to_string());
+                }
+                if self.auth_password.is_empty() {
+                    errors.push("AUTH_PASSWORD is empty; local login needs a password".to_string());
+                }
+            }
+        }
+        if self.max_login_attempts == 0 {
+            errors.push("MAX_LOGIN_ATTEMPTS must be at least 1".to_string());
+        }
+
+        if self.port == 0 {
+            errors.push("Port must be between 1 and 65535".to_string());
+        }
+        if !Self::is_valid_host(&self.host) {
+            errors.push(format!("Host '{}' is not an IP address or host name", self.host));
+        }
+
+        if self.enable_entra_id {
+            match &self.entra_id_config {
+                None => errors.push("Entra ID is enabled but not configured".to_string()),
+                Some(entra) => {
+                    let fields = [
+                        ("ENTRA_CLIENT_ID", &entra.client_id),
+                        ("ENTRA_CLIENT_SECRET", &entra.client_secret),
+                        ("ENTRA_TENANT_ID", &entra.tenant_id),
+                        ("ENTRA_REDIRECT_URI", &entra.redirect_uri),
+                    ];
+                    for (name, value) in fields {
+                        if value.trim().is_empty() {
+                            errors.push(format!("{} is empty but Entra ID is enabled", name));
+                        }
+                    }
+                }
+            }
+        }
+
+        errors
+    }
+
+    /// Validates the configuration before the server starts
+    ///
+    /// # Errors
+    ///
+    /// Returns an error listing every problem found by `validation_errors`
+    pub fn validate(&self) -> anyhow::Result<()> {
+        let errors = self.validation_errors();
+        if errors.is_empty() {
+            return Ok(());
+        }
+        anyhow::bail!(
+            "Invalid web server configuration:\n  - {}",
+            errors.join("\n  - ")
+        )
+    }
+
+    /// Checks whether a host is an IP address or a syntactically valid host name
+    fn is_valid_host(host: &str) -> bool {
+        if host.parse::<IpAddr>().is_ok() {
+            return true;
+        }
+        !host.is_empty()
+            && host.len() <= 253
+            && host.split('.').all(|label| {
+                !label.is_empty()
+                    && label.len() <= 63
+                    && !label.starts_with('-')
+                    && !label.ends_with('-')
+                    && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_workers_uses_available_parallelism() {
+        let config = WebConfig::default();
+        assert!(config.workers >= 1);
+    }
+
+    fn valid_config() -> WebConfig {
+        let mut config = WebConfig::new("test.db".to_string());
+        config.jwt_secret = "0123456789abcdef0123456789abcdef".to_string();
+        config.auth_password = "s3cret-pass".to_string();
+        config
+    }
+
+    fn assert_single_error(config: &WebConfig, expected: &str) {
+        let errors = config.validation_errors();
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert!(errors[0].contains(expected), "{:?}", errors);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_rejects_each_invalid_setting() {
+        assert!(valid_config().validate().is_ok());
+        assert_single_error(&WebConfig::new("test.db".to_string()).with_port(8080), "default/example");
+
+        let mut config = valid_config();
+        config.jwt_secret = String::new();
+        assert_single_error(&config, "JWT_SECRET is empty");
+
+        config.jwt_secret = "your-secret-key-change-in-production-use-long-random-string".to_string();
+        assert_single_error(&config, "default/example");
+
+        config.jwt_secret = "short".to_string();
+        assert_single_error(&config, "too weak");
+
+        let mut config = valid_config();
+        config.auth_username = " ".to_string();
+        assert_single_error(&config, "AUTH_USERNAME");
+
+        let mut config = valid_config();
+        config.auth_password = String::new();
+        assert_single_error(&config, "AUTH_PASSWORD");
+
+        config.users_file = Some("/nonexistent/users.json".to_string());
+        assert_single_error(&config, "USERS_FILE");
+
+        let mut config = valid_config();
+        config.token_ttl_hours = 0;
+        assert_single_error(&config, "TOKEN_TTL_HOURS");
+        config.token_ttl_hours = MAX_TOKEN_TTL_HOURS + 1;
+        assert_single_error(&config, "TOKEN_TTL_HOURS");
+        config.token_ttl_hours = MAX_TOKEN_TTL_HOURS;
+        assert!(config.validation_errors().is_empty());
+
+        assert_single_error(&valid_config().with_port(0), "Port");
+        assert_single_error(&valid_config().with_host("bad host!".to_string()), "Host");
+        assert!(valid_config().with_host("jobweaver.example.com".to_string()).validate().is_ok());
+
+        let mut config = valid_config();
+        config.enable_entra_id = true;
+        assert_single_error(&config, "not configured");
+        let config = valid_config().with_entra_id(EntraIdConfig {
+            client_id: "id".to_string(),
+            client_secret: String::new(),
+            tenant_id: "tenant".to_string(),
+            redirect_uri: "http://localhost/callback".to_string(),
+        });
+        assert_single_error(&config, "ENTRA_CLIENT_SECRET");
+    }
+
+    #[test]
+    fn test_with_workers() {
+        let config = WebConfig::new("test.db".to_string()).with_workers(4);
+        assert_eq!(config.workers, 4);
+
+        let config = WebConfig::new("test.db".to_string()).with_workers(0);
+        assert_eq!(config.workers, 1);
+    }
 }
@@ -7,12 +7,16 @@ use actix_web::{web, HttpResponse, HttpRequest, HttpMessage, Responder};
 use actix_web_httpauth::extractors::bearer::BearerAuth;
 use serde::Deserialize;
 use std::sync::Arc;
-use tracing::{info, error};
+use tracing::{debug, error, info, trace};
+use utoipa::OpenApi;
 
 use crate::web::auth::{AuthService, UserStore, Claims, LoginAttemptTracker};
 use crate::web::models::*;
+use crate::web::openapi::ApiDoc;
 use crate::web::repository::JobRepository;
 use crate::web::config::WebConfig;
+use crate::infrastructure::output::{AirflowDagGenerator, BundleWriter};
+use crate::domain::value_objects::TransferDirection;
 
 /// Health check endpoint
 ///
@@ -25,6 +29,17 @@ pub async fn health_check() -> HttpResponse {
     HttpResponse::Ok().json(ApiResponse::success("OK"))
 }
 
+/// Serves the OpenAPI 3 document describing the API
+///
+/// Public, so client code generators can fetch it without a token.
+///
+/// # Returns
+///
+/// HTTP 200 with the OpenAPI document
+pub async fn openapi_spec() -> HttpResponse {
+    HttpResponse::Ok().json(ApiDoc::openapi())
+}
+
 /// Handles user login with username and password
 ///
 /// Validates credentials and generates a JWT token on success.
@@ -40,6 +55,17 @@ pub async fn health_check() -> HttpResponse {
 /// # Returns
 ///
 /// HTTP 200 with token on success, HTTP 401 on invalid credentials, HTTP 429 on lockout
+#[utoipa::path(
+    post,
+    path = "/auth/login",
+    tag = "auth",
+    request_body = LoginRequest,
+    responses(
+        (status = 200, description = "Token issued", body = LoginApiResponse),
+        (status = 401, description = "Invalid username or password", body = ErrorApiResponse),
+        (status = 429, description = "Account locked after too many failed attempts", body = ErrorApiResponse),
+    )
+)]
 pub async fn login(
     request: web::Json<LoginRequest>,
     config: web::Data<WebConfig>,
@@ -79,9 +105,11 @@ pub async fn login(
         username: request.username.clone(),
         display_name: request.username.clone(),
         auth_type: AuthType::Local,
+        role: config.role_for(&request.username),
     };
     
-    let auth_service = AuthService::new(config.jwt_secret.clone());
+    let auth_service = AuthService::new(config.jwt_secret.clone())
+        .with_token_ttl_hours(config.token_ttl_hours);
     match auth_service.generate_token(&user) {
         Ok(token) => HttpResponse::Ok().json(ApiResponse::success(LoginResponse {
             token,
@@ -105,6 +133,16 @@ pub async fn login(
 /// # Returns
 ///
 /// HTTP 200 with token on success, HTTP 400 if Entra ID is disabled
+#[utoipa::path(
+    post,
+    path = "/auth/entra-callback",
+    tag = "auth",
+    request_body = EntraIdAuthRequest,
+    responses(
+        (status = 200, description = "Token issued", body = LoginApiResponse),
+        (status = 400, description = "Entra ID authentication is not enabled", body = ErrorApiResponse),
+    )
+)]
 pub async fn entra_id_callback(
     request: web::Json<EntraIdAuthRequest>,
     config: web::Data<WebConfig>,
@@ -115,13 +153,71 @@ pub async fn entra_id_callback(
         ));
     }
     
+    let username = format!("entra_user_{}", &request.code[..8]);
     let user = UserInfo {
-        username: format!("entra_user_{}", &request.code[..8]),
+        role: config.role_for(&username),
+        username,
         display_name: "Entra ID User".to_string(),
         auth_type: AuthType::EntraId,
     };
     
-    let auth_service = AuthService::new(config.jwt_secret.clone());
+    let auth_service = AuthService::new(config.jwt_secret.clone())
+        .with_token_ttl_hours(config.token_ttl_hours);
+    match auth_service.generate_token(&user) {
+        Ok(token) => HttpResponse::Ok().json(ApiResponse::success(LoginResponse {
+            token,
+            user,
+        })),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error(
+            "Failed to generate token".to_string()
+        )),
+    }
+}
+
+/// Issues a fresh token for a still-valid bearer token
+///
+/// The presented token is re-validated (expired tokens are rejected) and a
+/// new token for the same user is returned, so sessions can be extended
+/// without logging in again. The role is looked up again in
+/// `ADMIN_USERNAMES`, so a revoked admin loses the role on refresh.
+///
+/// # Arguments
+///
+/// * `auth` - Bearer token to refresh
+/// * `config` - Web configuration
+///
+/// # Returns
+///
+/// HTTP 200 with the new token, HTTP 401 if the token is invalid or expired
+#[utoipa::path(
+    post,
+    path = "/auth/refresh",
+    tag = "auth",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "New token for the same user", body = LoginApiResponse),
+        (status = 401, description = "Invalid or expired token", body = ErrorApiResponse),
+    )
+)]
+pub async fn refresh_token(
+    auth: BearerAuth,
+    config: web::Data<WebConfig>,
+) -> HttpResponse {
+    let auth_service = AuthService::new(config.jwt_secret.clone())
+        .with_token_ttl_hours(config.token_ttl_hours);
+    let claims = match auth_service.verify_token(auth.token()) {
+        Ok(claims) => claims,
+        Err(_) => return HttpResponse::Unauthorized().json(ApiResponse::<()>::error(
+            "Invalid or expired token".to_string()
+        )),
+    };
+
+    let user = UserInfo {
+        role: config.role_for(&claims.username),
+        username: claims.username,
+        display_name: claims.display_name,
+        auth_type: claims.auth_type,
+    };
     match auth_service.generate_token(&user) {
         Ok(token) => HttpResponse::Ok().json(ApiResponse::success(LoginResponse {
             token,
@@ -144,6 +240,16 @@ pub async fn entra_id_callback(
 /// # Returns
 ///
 /// HTTP 200 with user info on success, HTTP 401 if not authenticated
+#[utoipa::path(
+    get,
+    path = "/auth/me",
+    tag = "auth",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Authenticated user", body = UserInfoApiResponse),
+        (status = 401, description = "Not authenticated", body = ErrorApiResponse),
+    )
+)]
 pub async fn get_current_user(
     req: HttpRequest,
 ) -> HttpResponse {
@@ -152,6 +258,7 @@ pub async fn get_current_user(
             username: claims.username.clone(),
             display_name: claims.display_name.clone(),
             auth_type: claims.auth_type.clone(),
+            role: claims.role,
         };
         HttpResponse::Ok().json(ApiResponse::success(user))
     } else {
@@ -161,6 +268,57 @@ pub async fn get_current_user(
     }
 }
 
+/// Checks whether owner and run-as accounts and notification targets are
+/// hidden from the requester
+///
+/// In analysis-only mode viewers may only use the analysis endpoints (the
+/// raw job data endpoints answer them with HTTP 403, see
+/// `deny_raw_job_data`); the identity breakdowns those endpoints share are
+/// stripped for them as well.
+///
+/// # Arguments
+///
+/// * `http_request` - Request carrying the server config and JWT claims
+///
+/// # Returns
+///
+/// true for non-admin users on an analysis-only server
+fn hides_identities(http_request: &HttpRequest) -> bool {
+    let analysis_only = http_request.app_data::<web::Data<WebConfig>>()
+        .is_some_and(|config| config.analysis_only);
+    analysis_only && !is_admin(http_request)
+}
+
+/// Rejects non-admin users from the raw job data endpoints in analysis-only mode
+///
+/// # Arguments
+///
+/// * `http_request` - Request carrying the server config and JWT claims
+///
+/// # Returns
+///
+/// An HTTP 403 response to send instead of the data, or `None` when the
+/// requester may see raw job data
+fn deny_raw_job_data(http_request: &HttpRequest) -> Option<HttpResponse> {
+    hides_identities(http_request).then(|| HttpResponse::Forbidden().json(ApiResponse::<()>::error(
+        "Raw job data is not available in analysis-only mode".to_string()
+    )))
+}
+
+/// Checks whether the requester holds the admin role
+///
+/// # Arguments
+///
+/// * `http_request` - Request carrying the JWT claims
+///
+/// # Returns
+///
+/// true only when the validated claims carry `UserRole::Admin`
+fn is_admin(http_request: &HttpRequest) -> bool {
+    http_request.extensions().get::<Claims>()
+        .is_some_and(|claims| claims.role == UserRole::Admin)
+}
+
 /// Searches for jobs with filtering, sorting, and pagination
 ///
 /// Supports filtering by various job attributes, sorting, and pagination.
@@ -169,32 +327,51 @@ pub async fn get_current_user(
 ///
 /// * `query` - Search request with filters and pagination parameters
 /// * `repository` - Job repository for database access
+/// * `http_request` - Request used to check analysis-only access
 /// * `_auth` - Bearer token authentication (validates user is authenticated)
 ///
 /// # Returns
 ///
-/// HTTP 200 with search results on success, HTTP 500 on error
+/// HTTP 200 with search results on success, HTTP 403 for viewers in
+/// analysis-only mode, HTTP 500 on error
+#[utoipa::path(
+    post,
+    path = "/jobs/search",
+    tag = "jobs",
+    security(("bearer_auth" = [])),
+    request_body = JobSearchRequest,
+    responses(
+        (status = 200, description = "One page of matching jobs", body = JobSearchApiResponse),
+        (status = 403, description = "Raw job data is restricted to admins (analysis-only mode)", body = ErrorApiResponse),
+        (status = 500, description = "Search failed", body = ErrorApiResponse),
+    )
+)]
 pub async fn search_jobs(
     query: web::Json<JobSearchRequest>,
     repository: web::Data<Arc<JobRepository>>,
+    http_request: HttpRequest,
     _auth: BearerAuth,
 ) -> HttpResponse {
+    if let Some(forbidden) = deny_raw_job_data(&http_request) {
+        return forbidden;
+    }
     let request = query.into_inner();
-    info!("🌐 [API] POST /jobs/search");
-    info!("📋 [API] Basic filters: job_name={:?}, folder={:?}, app={:?}, appl_type={:?}, appl_ver={:?}, task_type={:?}, critical={:?}, datacenter={:?}, folder_order_method={:?}, has_odate={:?}",
+    debug!("🌐 [API] POST /jobs/search");
+    trace!("📋 [API] Basic filters: job_name={:?}, folder={:?}, app={:?}, appl_type={:?}, appl_ver={:?}, task_type={:?}, critical={:?}, datacenter={:?}, folder_order_method={:?}, has_odate={:?}",
           request.job_name, request.folder_name, request.application, 
           request.appl_type, request.appl_ver, request.task_type, request.critical,
           request.datacenter, request.folder_order_method, request.has_odate);
-    info!("📊 [API] Dependency filters: min_deps={:?}, max_deps={:?}, min_on_conds={:?}, max_on_conds={:?}",
-          request.min_dependencies, request.max_dependencies, request.min_on_conditions, request.max_on_conditions);
-    info!("💾 [API] Variable filters: has_vars={:?}, min_vars={:?}",
+    trace!("📊 [API] Dependency filters: min_deps={:?}, max_deps={:?}, min_on_conds={:?}, max_on_conds={:?}, min_maxrerun={:?}",
+          request.min_dependencies, request.max_dependencies, request.min_on_conditions, request.max_on_conditions,
+          request.min_maxrerun);
+    trace!("💾 [API] Variable filters: has_vars={:?}, min_vars={:?}",
           request.has_variables, request.min_variables);
-    info!("📄 [API] Pagination: page={:?}, per_page={:?}, sort_by={:?}, sort_order={:?}",
+    trace!("📄 [API] Pagination: page={:?}, per_page={:?}, sort_by={:?}, sort_order={:?}",
           request.page, request.per_page, request.sort_by, request.sort_order);
     
     match repository.search_jobs(&request) {
         Ok(response) => {
-            info!("✅ [API] Search completed: found {} jobs (page {}/{})", 
+            debug!("✅ [API] Search completed: found {} jobs (page {}/{})", 
                   response.total, response.page, response.total_pages);
             HttpResponse::Ok().json(ApiResponse::success(response))
         },
@@ -215,16 +392,35 @@ pub async fn search_jobs(
 ///
 /// * `job_id` - Job ID from URL path
 /// * `repository` - Job repository for database access
+/// * `http_request` - Request used to check analysis-only access
 /// * `_auth` - Bearer token authentication
 ///
 /// # Returns
 ///
-/// HTTP 200 with job details, HTTP 404 if not found, HTTP 500 on error
+/// HTTP 200 with job details, HTTP 403 for viewers in analysis-only mode,
+/// HTTP 404 if not found, HTTP 500 on error
+#[utoipa::path(
+    get,
+    path = "/jobs/{id}",
+    tag = "jobs",
+    security(("bearer_auth" = [])),
+    params(("id" = i64, Path, description = "Job ID")),
+    responses(
+        (status = 200, description = "Job with its conditions, resources and variables", body = JobDetailApiResponse),
+        (status = 403, description = "Raw job data is restricted to admins (analysis-only mode)", body = ErrorApiResponse),
+        (status = 404, description = "Job not found", body = ErrorApiResponse),
+        (status = 500, description = "Lookup failed", body = ErrorApiResponse),
+    )
+)]
 pub async fn get_job_detail(
     job_id: web::Path<i64>,
     repository: web::Data<Arc<JobRepository>>,
+    http_request: HttpRequest,
     _auth: BearerAuth,
 ) -> HttpResponse {
+    if let Some(forbidden) = deny_raw_job_data(&http_request) {
+        return forbidden;
+    }
     match repository.get_job_detail(*job_id) {
         Ok(Some(job)) => HttpResponse::Ok().json(ApiResponse::success(job)),
         Ok(None) => HttpResponse::NotFound().json(ApiResponse::<()>::error(
@@ -244,15 +440,21 @@ pub async fn get_job_detail(
 ///
 /// * `job_id` - ID of the job
 /// * `repository` - Job repository
+/// * `http_request` - Request used to check analysis-only access
 ///
 /// # Returns
 ///
-/// HTTP 200 with dependency graph, HTTP 404 if not found, HTTP 500 on error
+/// HTTP 200 with dependency graph, HTTP 403 for viewers in analysis-only
+/// mode, HTTP 404 if not found, HTTP 500 on error
 pub async fn get_dependency_graph(
     job_id: web::Path<i64>,
     repository: web::Data<Arc<JobRepository>>,
+    http_request: HttpRequest,
     _auth: BearerAuth,
 ) -> HttpResponse {
+    if let Some(forbidden) = deny_raw_job_data(&http_request) {
+        return forbidden;
+    }
     match repository.get_dependency_graph(*job_id) {
         Ok(graph) => HttpResponse::Ok().json(ApiResponse::success(graph)),
         Err(e) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error(
@@ -306,26 +508,89 @@ pub async fn get_top_root_jobs(
 ///   - "with": Only jobs in folders with folder_order_method
 ///   - "without": Only jobs in folders without folder_order_method
 ///   - None/other: All jobs
+/// * `http_request` - Request used to check whether identities are hidden
 /// * `_auth` - Bearer token authentication
 ///
 /// # Returns
 ///
-/// HTTP 200 with statistics on success, HTTP 500 on error
+/// HTTP 200 with statistics on success, HTTP 500 on error. The owner,
+/// run-as and notification target breakdowns are empty for viewers in
+/// analysis-only mode.
+#[utoipa::path(
+    get,
+    path = "/dashboard/stats",
+    tag = "dashboard",
+    security(("bearer_auth" = [])),
+    params(DashboardFilter),
+    responses(
+        (status = 200, description = "Aggregated job statistics", body = DashboardStatsApiResponse),
+        (status = 500, description = "Statistics query failed", body = ErrorApiResponse),
+    )
+)]
 pub async fn get_dashboard_stats(
     repository: web::Data<Arc<JobRepository>>,
     filter: web::Query<DashboardFilter>,
+    http_request: HttpRequest,
     _auth: BearerAuth,
 ) -> HttpResponse {
     let folder_filter = filter.folder_order_method_filter.as_deref();
     let datacenter_filter = filter.datacenter.as_deref();
     match repository.get_dashboard_stats(folder_filter, datacenter_filter) {
-        Ok(stats) => HttpResponse::Ok().json(ApiResponse::success(stats)),
+        Ok(mut stats) => {
+            if hides_identities(&http_request) {
+                stats.jobs_by_owner.clear();
+                stats.jobs_by_run_as.clear();
+                stats.jobs_by_notification_target.clear();
+            }
+            HttpResponse::Ok().json(ApiResponse::success(stats))
+        }
         Err(e) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error(
             format!("Failed to get dashboard stats: {}", e)
         )),
     }
 }
 
+/// Gets job counts per datacenter
+///
+/// Returns the `jobs_by_datacenter` breakdown of the dashboard statistics,
+/// with critical and cyclic job counts, for planning waves per datacenter.
+/// Accepts the same filters as the dashboard.
+///
+/// # Arguments
+///
+/// * `repository` - Job repository for database access
+/// * `filter` - Optional folder_order_method and datacenter filters
+/// * `_auth` - Bearer token authentication
+///
+/// # Returns
+///
+/// HTTP 200 with per-datacenter statistics on success, HTTP 500 on error
+#[utoipa::path(
+    get,
+    path = "/stats/datacenter",
+    tag = "dashboard",
+    security(("bearer_auth" = [])),
+    params(DashboardFilter),
+    responses(
+        (status = 200, description = "Job, critical and cyclic counts per datacenter", body = DatacenterStatsApiResponse),
+        (status = 500, description = "Statistics query failed", body = ErrorApiResponse),
+    )
+)]
+pub async fn get_datacenter_stats(
+    repository: web::Data<Arc<JobRepository>>,
+    filter: web::Query<DashboardFilter>,
+    _auth: BearerAuth,
+) -> HttpResponse {
+    let folder_filter = filter.folder_order_method_filter.as_deref();
+    let datacenter_filter = filter.datacenter.as_deref();
+    match repository.get_dashboard_stats(folder_filter, datacenter_filter) {
+        Ok(stats) => HttpResponse::Ok().json(ApiResponse::success(stats.jobs_by_datacenter)),
+        Err(e) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error(
+            format!("Failed to get datacenter stats: {}", e)
+        )),
+    }
+}
+
 /// Gets available filter options for job search
 ///
 /// Returns lists of unique values for filterable fields.
@@ -340,6 +605,17 @@ pub async fn get_dashboard_stats(
 /// # Returns
 ///
 /// HTTP 200 with filter options on success, HTTP 500 on error
+#[utoipa::path(
+    get,
+    path = "/filters",
+    tag = "jobs",
+    security(("bearer_auth" = [])),
+    params(DatacenterFilter),
+    responses(
+        (status = 200, description = "Distinct values of the filterable job fields", body = FilterOptionsApiResponse),
+        (status = 500, description = "Query failed", body = ErrorApiResponse),
+    )
+)]
 pub async fn get_filter_options(
     repository: web::Data<Arc<JobRepository>>,
     datacenter: web::Query<DatacenterFilter>,
@@ -354,33 +630,441 @@ pub async fn get_filter_options(
     }
 }
 
+/// Re-opens the database file, picking up a refreshed replica
+///
+/// # Arguments
+///
+/// * `repository` - Repository instance
+/// * `http_request` - Request carrying the caller's JWT claims
+/// * `_auth` - Bearer token authentication
+///
+/// # Returns
+///
+/// HTTP 200 once the new file is being served, HTTP 403 for non-admin
+/// users, HTTP 500 if it can't be opened (the previous database stays in use)
+#[utoipa::path(
+    post,
+    path = "/admin/reload",
+    tag = "admin",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "The refreshed database file is being served", body = MessageApiResponse),
+        (status = 403, description = "Reloading requires an admin account", body = ErrorApiResponse),
+        (status = 500, description = "The file can't be opened; the previous database stays in use", body = ErrorApiResponse),
+    )
+)]
+pub async fn reload_database(
+    repository: web::Data<Arc<JobRepository>>,
+    http_request: HttpRequest,
+    _auth: BearerAuth,
+) -> HttpResponse {
+    info!("🔄 [API] POST /admin/reload");
+    if !is_admin(&http_request) {
+        return HttpResponse::Forbidden().json(ApiResponse::<()>::error(
+            "Reloading the database requires an admin account".to_string()
+        ));
+    }
+    match repository.reload() {
+        Ok(()) => HttpResponse::Ok().json(ApiResponse::success("Database reloaded")),
+        Err(e) => {
+            error!("❌ [API] Failed to reload database: {}", e);
+            HttpResponse::InternalServerError().json(ApiResponse::<()>::error(
+                format!("Failed to reload database: {}", e)
+            ))
+        },
+    }
+}
+
+/// Gets distinct values of a jobs column for type-ahead filters
+///
+/// # Arguments
+///
+/// * `repository` - Repository instance
+/// * `query` - Column, optional prefix `q` and optional `limit`
+/// * `http_request` - Request used to check whether identities are hidden
+/// * `_auth` - Bearer token authentication
+///
+/// # Returns
+///
+/// HTTP 200 with values and counts on success, HTTP 400 if the column is
+/// not allowed, HTTP 403 for `owner` and `run_as` when identities are
+/// hidden, HTTP 500 on error
+#[utoipa::path(
+    get,
+    path = "/meta/distinct",
+    tag = "jobs",
+    security(("bearer_auth" = [])),
+    params(DistinctValuesQuery),
+    responses(
+        (status = 200, description = "Distinct values of the column with their job counts", body = DistinctValuesApiResponse),
+        (status = 400, description = "Column not available for distinct values", body = ErrorApiResponse),
+        (status = 403, description = "Identity columns are restricted to admins (analysis-only mode)", body = ErrorApiResponse),
+        (status = 500, description = "Query failed", body = ErrorApiResponse),
+    )
+)]
+pub async fn get_distinct_values(
+    repository: web::Data<Arc<JobRepository>>,
+    query: web::Query<DistinctValuesQuery>,
+    http_request: HttpRequest,
+    _auth: BearerAuth,
+) -> HttpResponse {
+    if !crate::web::repository::DISTINCT_VALUE_COLUMNS.contains(&query.column.as_str()) {
+        return HttpResponse::BadRequest().json(ApiResponse::<()>::error(
+            format!("Column '{}' is not available for distinct values", query.column)
+        ));
+    }
+
+    if matches!(query.column.as_str(), "owner" | "run_as") && hides_identities(&http_request) {
+        return HttpResponse::Forbidden().json(ApiResponse::<()>::error(
+            "Identity columns are not available in analysis-only mode".to_string()
+        ));
+    }
+
+    match repository.get_distinct_values(&query.column, query.q.as_deref(), query.limit) {
+        Ok(values) => HttpResponse::Ok().json(ApiResponse::success(values)),
+        Err(e) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error(
+            format!("Failed to get distinct values: {}", e)
+        )),
+    }
+}
+
+/// Maximum number of job names accepted by one lookup request
+const MAX_JOB_NAMES_PER_REQUEST: usize = 10_000;
+
+/// Gets all jobs matching a list of job names
+///
+/// # Arguments
+///
+/// * `request` - Job names to look up
+/// * `repository` - Job repository for database access
+/// * `http_request` - Request used to check analysis-only access
+/// * `_auth` - Bearer token authentication
+///
+/// # Returns
+///
+/// HTTP 200 with the matching jobs and the names not found, HTTP 400 if no
+/// or too many names are given, HTTP 403 for viewers in analysis-only mode,
+/// HTTP 500 on error
+#[utoipa::path(
+    post,
+    path = "/jobs/by-names",
+    tag = "jobs",
+    security(("bearer_auth" = [])),
+    request_body = JobsByNamesRequest,
+    responses(
+        (status = 200, description = "Jobs with the requested names and the names not found", body = JobsByNamesApiResponse),
+        (status = 400, description = "No or too many names", body = ErrorApiResponse),
+        (status = 403, description = "Raw job data is restricted to admins (analysis-only mode)", body = ErrorApiResponse),
+        (status = 500, description = "Lookup failed", body = ErrorApiResponse),
+    )
+)]
+pub async fn get_jobs_by_names(
+    request: web::Json<JobsByNamesRequest>,
+    repository: web::Data<Arc<JobRepository>>,
+    http_request: HttpRequest,
+    _auth: BearerAuth,
+) -> HttpResponse {
+    if let Some(forbidden) = deny_raw_job_data(&http_request) {
+        return forbidden;
+    }
+    let names: Vec<String> = request.into_inner().names
+        .into_iter()
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .collect();
+
+    info!("📋 [API] POST /jobs/by-names ({} names)", names.len());
+
+    if names.is_empty() || names.len() > MAX_JOB_NAMES_PER_REQUEST {
+        return HttpResponse::BadRequest().json(ApiResponse::<()>::error(
+            format!("Between 1 and {} job names are required", MAX_JOB_NAMES_PER_REQUEST)
+        ));
+    }
+
+    match repository.get_jobs_by_names(&names) {
+        Ok(result) => HttpResponse::Ok().json(ApiResponse::success(result)),
+        Err(e) => {
+            error!("❌ [API] Failed to get jobs by names: {}", e);
+            HttpResponse::InternalServerError().json(ApiResponse::<()>::error(
+                format!("Failed to get jobs by names: {}", e)
+            ))
+        },
+    }
+}
+
 /// Exports job search results to CSV format
 ///
-/// Generates a CSV file with filtered job data.
+/// Generates a CSV file with filtered job data. Full exports are streamed:
+/// rows are sent as they are read from the database, so memory stays flat
+/// however many jobs match, and a failure mid-export aborts the download.
+/// Supports single-range HTTP `Range` requests (`bytes=start-end`,
+/// `bytes=start-`, `bytes=-suffix`) so interrupted downloads can be resumed;
+/// the export is run once to size it and again with the same deterministic
+/// ordering to stream the requested byte slice, so ranges are not buffered
+/// either. Every response carries an `ETag`; a Range request whose
+/// `If-Range` doesn't match it (the data changed since the first part was
+/// downloaded) gets the full export instead of a mismatched slice.
 ///
 /// # Arguments
 ///
+/// * `http_request` - Raw HTTP request (for the Range and If-Range headers
+///   and analysis-only access)
 /// * `query` - Search filters from query parameters
 /// * `repository` - Job repository for database access
 /// * `_auth` - Bearer token authentication
 ///
 /// # Returns
 ///
-/// HTTP 200 with CSV file on success, HTTP 500 on error
+/// HTTP 200 with CSV file (also when the Range header is malformed, asks
+/// for several ranges or fails the If-Range check), HTTP 206 with a partial CSV for ranged requests,
+/// HTTP 403 for viewers in analysis-only mode, HTTP 416 for valid but
+/// unsatisfiable ranges, HTTP 500 on error
 pub async fn export_jobs_csv(
+    http_request: HttpRequest,
     request: web::Json<JobSearchRequest>,
     repository: web::Data<Arc<JobRepository>>,
     _auth: BearerAuth,
 ) -> HttpResponse {
+    if let Some(forbidden) = deny_raw_job_data(&http_request) {
+        return forbidden;
+    }
     tracing::info!("📥 [EXPORT_HANDLER] Received export request: {:?}", request);
-    match repository.export_search_to_csv(&request.into_inner()) {
-        Ok(csv_data) => HttpResponse::Ok()
+    let range_header = http_request.headers()
+        .get(actix_web::http::header::RANGE)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let repository = repository.get_ref().clone();
+    let request = request.into_inner();
+    let etag = repository.csv_export_etag(&request);
+    let range = range_header.as_deref()
+        .and_then(parse_byte_range)
+        .filter(|_| if_range_matches(&http_request, etag.as_deref()));
+    let Some(range) = range else {
+        return csv_export_response(actix_web::http::StatusCode::OK, etag.as_deref())
             .content_type("text/csv")
+            .insert_header(("Accept-Ranges", "bytes"))
             .insert_header(("Content-Disposition", "attachment; filename=\"jobs_export.csv\""))
-            .body(csv_data),
-        Err(e) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error(
+            .streaming(stream_csv_export(repository, request, 0, usize::MAX));
+    };
+    
+    // Size the export without buffering it, then stream only the requested slice
+    let (count_repository, count_request) = (repository.clone(), request.clone());
+    let total_len = match web::block(move || {
+        let mut counter = ByteCounter::default();
+        count_repository.write_search_csv(&count_request, &mut counter).map(|_| counter.len)
+    }).await {
+        Ok(Ok(total_len)) => total_len,
+        Ok(Err(e)) => return HttpResponse::InternalServerError().json(ApiResponse::<()>::error(
+            format!("Failed to export CSV: {}", e)
+        )),
+        Err(e) => return HttpResponse::InternalServerError().json(ApiResponse::<()>::error(
             format!("Failed to export CSV: {}", e)
         )),
+    };
+    
+    match range.resolve(total_len) {
+        Some((start, end)) => {
+            tracing::info!("📥 [EXPORT_HANDLER] Serving bytes {}-{}/{}", start, end, total_len);
+            csv_export_response(actix_web::http::StatusCode::PARTIAL_CONTENT, etag.as_deref())
+                .content_type("text/csv")
+                .insert_header(("Accept-Ranges", "bytes"))
+                .insert_header(("Content-Range", format!("bytes {}-{}/{}", start, end, total_len)))
+                .insert_header(("Content-Disposition", "attachment; filename=\"jobs_export.csv\""))
+                .streaming(stream_csv_export(repository, request, start, end - start + 1))
+        }
+        None => csv_export_response(actix_web::http::StatusCode::RANGE_NOT_SATISFIABLE, etag.as_deref())
+            .insert_header(("Content-Range", format!("bytes */{}", total_len)))
+            .finish(),
+    }
+}
+
+/// Starts a CSV export response, tagged with the export's ETag when known
+fn csv_export_response(status: actix_web::http::StatusCode, etag: Option<&str>) -> actix_web::HttpResponseBuilder {
+    let mut response = HttpResponse::build(status);
+    if let Some(etag) = etag {
+        response.insert_header((actix_web::http::header::ETAG, etag));
+    }
+    response
+}
+
+/// Checks a request's If-Range header against the current entity tag
+///
+/// Only exact strong tags match; dates, weak tags and stale tags don't, so
+/// the caller sends the full body rather than a slice of different data
+/// (RFC 9110, section 13.1.5).
+///
+/// # Arguments
+///
+/// * `http_request` - Request that may carry an If-Range header
+/// * `etag` - Entity tag of the current export, if one could be computed
+///
+/// # Returns
+///
+/// true if there is no If-Range header or it names the current tag
+fn if_range_matches(http_request: &HttpRequest, etag: Option<&str>) -> bool {
+    match http_request.headers().get(actix_web::http::header::IF_RANGE) {
+        None => true,
+        Some(value) => etag.is_some_and(|etag| value.to_str().is_ok_and(|value| value.trim() == etag)),
+    }
+}
+
+/// Number of CSV chunks buffered between the export thread and the response
+const CSV_STREAM_BUFFERED_CHUNKS: usize = 16;
+
+/// Counts the bytes written by a CSV export without keeping them
+#[derive(Default)]
+struct ByteCounter {
+    len: usize,
+}
+
+impl std::io::Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.len += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Forwards the bytes written by a CSV export to a streaming response body
+///
+/// Writes block while the channel is full, so a slow client throttles the
+/// database read; a dropped receiver (client gone) fails the write and ends
+/// the export. Bytes before the requested range are dropped, and once the
+/// range has been sent writes fail with `WriteZero` to stop the export early.
+struct ChannelWriter {
+    tx: tokio::sync::mpsc::Sender<Result<web::Bytes, std::io::Error>>,
+    /// Bytes still to drop before the range starts
+    skip: usize,
+    /// Bytes still to send before the range ends
+    remaining: usize,
+}
+
+impl std::io::Write for ChannelWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.remaining == 0 {
+            return Err(std::io::Error::new(std::io::ErrorKind::WriteZero, "CSV download range was sent"));
+        }
+        let start = self.skip.min(buf.len());
+        self.skip -= start;
+        let len = (buf.len() - start).min(self.remaining);
+        if len > 0 {
+            self.remaining -= len;
+            self.tx.blocking_send(Ok(web::Bytes::copy_from_slice(&buf[start..start + len])))
+                .map_err(|_| std::io::Error::new(std::io::ErrorKind::BrokenPipe, "CSV download was closed"))?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Streams a CSV export from a blocking thread into a response body
+///
+/// # Arguments
+///
+/// * `repository` - Job repository to export from
+/// * `request` - Search filters
+/// * `skip` - Bytes to drop from the start of the export
+/// * `len` - Bytes to send after the skipped ones (`usize::MAX` for the rest)
+///
+/// # Returns
+///
+/// Stream of CSV chunks; a failed export ends it with an error
+fn stream_csv_export(
+    repository: Arc<JobRepository>,
+    request: JobSearchRequest,
+    skip: usize,
+    len: usize,
+) -> impl futures_util::Stream<Item = Result<web::Bytes, std::io::Error>> {
+    let (tx, rx) = tokio::sync::mpsc::channel(CSV_STREAM_BUFFERED_CHUNKS);
+    tokio::task::spawn_blocking(move || {
+        let error_tx = tx.clone();
+        if let Err(e) = repository.write_search_csv(&request, ChannelWriter { tx, skip, remaining: len }) {
+            if is_range_sent(&e) {
+                return;
+            }
+            error!("📥 [EXPORT_HANDLER] Streaming CSV export failed: {:#}", e);
+            let _ = error_tx.blocking_send(Err(std::io::Error::other(format!("Failed to export CSV: {}", e))));
+        }
+    });
+    
+    futures_util::stream::unfold(rx, |mut rx| async move {
+        rx.recv().await.map(|chunk| (chunk, rx))
+    })
+}
+
+/// Whether a CSV export stopped because `ChannelWriter` had sent its whole range
+fn is_range_sent(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        let io_error = cause.downcast_ref::<std::io::Error>().or_else(|| match cause.downcast_ref::<csv::Error>()?.kind() {
+            csv::ErrorKind::Io(io_error) => Some(io_error),
+            _ => None,
+        });
+        io_error.is_some_and(|e| e.kind() == std::io::ErrorKind::WriteZero)
+    })
+}
+
+/// A single byte range requested by a Range header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ByteRange {
+    /// `first-last` or open-ended `first-`
+    FromTo(usize, Option<usize>),
+    /// `-N`: the last N bytes
+    Suffix(usize),
+}
+
+impl ByteRange {
+    /// Resolves the range against a body of known length
+    ///
+    /// # Arguments
+    ///
+    /// * `total_len` - Length of the full body in bytes
+    ///
+    /// # Returns
+    ///
+    /// Inclusive (start, end) byte offsets, or None if the range is unsatisfiable
+    fn resolve(self, total_len: usize) -> Option<(usize, usize)> {
+        let last = total_len.checked_sub(1)?;
+        match self {
+            ByteRange::FromTo(start, _) if start > last => None,
+            ByteRange::FromTo(start, end) => Some((start, end.map_or(last, |end| end.min(last)))),
+            ByteRange::Suffix(0) => None,
+            ByteRange::Suffix(suffix) => Some((total_len.saturating_sub(suffix), last)),
+        }
+    }
+}
+
+/// Parses a Range header holding a single byte range
+///
+/// Headers that are malformed or ask for several ranges are not errors:
+/// servers ignore them and send the full body (RFC 9110, section 14.2).
+///
+/// # Arguments
+///
+/// * `header` - Value of the Range header (e.g., "bytes=0-499")
+///
+/// # Returns
+///
+/// The requested range, or None if the header should be ignored
+fn parse_byte_range(header: &str) -> Option<ByteRange> {
+    let spec = header.trim().strip_prefix("bytes=")?;
+    if spec.contains(',') {
+        return None;
+    }
+
+    let (start, end) = spec.split_once('-')?;
+    match (start.trim(), end.trim()) {
+        ("", suffix) => suffix.parse().ok().map(ByteRange::Suffix),
+        (start, "") => start.parse().ok().map(|start| ByteRange::FromTo(start, None)),
+        (start, end) => {
+            let (start, end): (usize, usize) = (start.parse().ok()?, end.parse().ok()?);
+            (start <= end).then_some(ByteRange::FromTo(start, Some(end)))
+        }
     }
 }
 
@@ -392,21 +1076,38 @@ pub async fn export_jobs_csv(
 ///
 /// * `repo` - Job repository for database access
 /// * `path` - Job ID from URL path
+/// * `http_request` - Request used to check analysis-only access
 ///
 /// # Returns
 ///
-/// HTTP 200 with graph data on success, HTTP 500 on error
+/// HTTP 200 with graph data on success, HTTP 403 for viewers in
+/// analysis-only mode, HTTP 500 on error
+#[utoipa::path(
+    get,
+    path = "/jobs/{id}/graph",
+    tag = "jobs",
+    security(("bearer_auth" = [])),
+    params(("id" = i64, Path, description = "Job ID")),
+    responses(
+        (status = 200, description = "Direct upstream and downstream jobs", body = JobGraphApiResponse),
+        (status = 403, description = "Raw job data is restricted to admins (analysis-only mode)", body = ErrorApiResponse),
+        (status = 500, description = "Graph query failed", body = ErrorApiResponse),
+    )
+)]
 pub async fn get_job_graph(
     repo: web::Data<Arc<JobRepository>>,
     path: web::Path<i64>,
+    http_request: HttpRequest,
 ) -> impl Responder {
+    if let Some(forbidden) = deny_raw_job_data(&http_request) {
+        return forbidden;
+    }
     let job_id = path.into_inner();
-    info!("🌐 [API] GET /jobs/{}/graph", job_id);
+    info!(job_id, "🌐 [API] GET /jobs/{{id}}/graph");
     
     match repo.get_job_graph(job_id) {
         Ok(graph_data) => {
-            info!("✅ [API] Successfully retrieved graph for job_id={} ({} nodes, {} edges)", 
-                  job_id, graph_data.nodes.len(), graph_data.edges.len());
+            info!(job_id, nodes = graph_data.nodes.len(), edges = graph_data.edges.len(), "✅ [API] Retrieved graph");
             HttpResponse::Ok().json(ApiResponse {
                 success: true,
                 data: Some(graph_data),
@@ -425,6 +1126,241 @@ pub async fn get_job_graph(
     }
 }
 
+/// Lists every folder with its job counts
+///
+/// Returns the folder name, datacenter, order method and the number of
+/// current, critical and cyclic jobs of each folder in one request.
+///
+/// # Arguments
+///
+/// * `repo` - Job repository for database access
+/// * `_auth` - Bearer token authentication
+///
+/// # Returns
+///
+/// HTTP 200 with the folders on success, HTTP 500 on error
+#[utoipa::path(
+    get,
+    path = "/folders",
+    tag = "jobs",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Folders with their job counts", body = FolderListApiResponse),
+        (status = 500, description = "Query failed", body = ErrorApiResponse),
+    )
+)]
+pub async fn list_folders(
+    repo: web::Data<Arc<JobRepository>>,
+    _auth: BearerAuth,
+) -> HttpResponse {
+    info!("🌐 [API] GET /folders");
+
+    match repo.list_folders() {
+        Ok(folders) => HttpResponse::Ok().json(ApiResponse::success(folders)),
+        Err(e) => {
+            error!("❌ [API] Failed to list folders: {}", e);
+            HttpResponse::InternalServerError().json(ApiResponse::<()>::error(
+                format!("Failed to list folders: {}", e)
+            ))
+        },
+    }
+}
+
+/// Gets the dependency graph of a whole folder in one request
+///
+/// Returns every job of the folder with the condition edges between them;
+/// jobs depending on or feeding jobs outside the folder are colored as
+/// boundary nodes.
+///
+/// # Arguments
+///
+/// * `repo` - Job repository for database access
+/// * `path` - Folder name from URL path
+/// * `http_request` - Request used to check analysis-only access
+///
+/// # Returns
+///
+/// HTTP 200 with graph data on success, HTTP 403 for viewers in
+/// analysis-only mode, HTTP 404 if the folder has no jobs, HTTP 500 on error
+#[utoipa::path(
+    get,
+    path = "/folders/{name}/graph",
+    tag = "jobs",
+    security(("bearer_auth" = [])),
+    params(("name" = String, Path, description = "Folder name")),
+    responses(
+        (status = 200, description = "Folder jobs and their internal edges", body = JobGraphApiResponse),
+        (status = 403, description = "Raw job data is restricted to admins (analysis-only mode)", body = ErrorApiResponse),
+        (status = 404, description = "Folder has no jobs", body = ErrorApiResponse),
+        (status = 500, description = "Graph query failed", body = ErrorApiResponse),
+    )
+)]
+pub async fn get_folder_graph(
+    repo: web::Data<Arc<JobRepository>>,
+    path: web::Path<String>,
+    http_request: HttpRequest,
+) -> impl Responder {
+    if let Some(forbidden) = deny_raw_job_data(&http_request) {
+        return forbidden;
+    }
+    let folder_name = path.into_inner();
+    info!("🌐 [API] GET /folders/{}/graph", folder_name);
+
+    match repo.get_folder_graph(&folder_name) {
+        Ok(graph_data) if graph_data.nodes.is_empty() => HttpResponse::NotFound().json(ApiResponse::<()>::error(
+            format!("Folder '{}' has no jobs", folder_name)
+        )),
+        Ok(graph_data) => HttpResponse::Ok().json(ApiResponse::success(graph_data)),
+        Err(e) => {
+            error!("❌ [API] Failed to get graph for folder '{}': {}", folder_name, e);
+            HttpResponse::InternalServerError().json(ApiResponse::<()>::error(
+                format!("Failed to get folder graph: {}", e)
+            ))
+        },
+    }
+}
+
+/// Lists the files transferred or watched by jobs
+///
+/// Paths come from AFT/FTP variables, `ctmfw`/`scp` command lines and the
+/// path variables of file-watch jobs, with the job's `%%` variables resolved,
+/// so they can be mapped to Airflow filesystem/S3 sensors and transfers.
+///
+/// # Arguments
+///
+/// * `query` - Optional direction filter
+/// * `repo` - Job repository for database access
+/// * `http_request` - Request used to check analysis-only access
+/// * `_auth` - Bearer token authentication
+///
+/// # Returns
+///
+/// HTTP 200 with the entries, HTTP 400 for an unknown direction, HTTP 403
+/// for viewers in analysis-only mode, HTTP 500 on error
+#[utoipa::path(
+    get,
+    path = "/jobs/file-transfers",
+    tag = "jobs",
+    security(("bearer_auth" = [])),
+    params(FileTransferFilter),
+    responses(
+        (status = 200, description = "Files transferred or watched by jobs", body = FileTransferApiResponse),
+        (status = 400, description = "Unknown direction", body = ErrorApiResponse),
+        (status = 403, description = "Raw job data is restricted to admins (analysis-only mode)", body = ErrorApiResponse),
+        (status = 500, description = "Query failed", body = ErrorApiResponse),
+    )
+)]
+pub async fn get_file_transfers(
+    query: web::Query<FileTransferFilter>,
+    repo: web::Data<Arc<JobRepository>>,
+    http_request: HttpRequest,
+    _auth: BearerAuth,
+) -> HttpResponse {
+    if let Some(forbidden) = deny_raw_job_data(&http_request) {
+        return forbidden;
+    }
+    info!(direction = ?query.direction, "🌐 [API] GET /jobs/file-transfers");
+
+    let direction = match query.direction.as_deref().map(str::parse::<TransferDirection>).transpose() {
+        Ok(direction) => direction,
+        Err(e) => return HttpResponse::BadRequest().json(ApiResponse::<()>::error(e)),
+    };
+
+    match repo.get_file_transfers(direction) {
+        Ok(transfers) => HttpResponse::Ok().json(ApiResponse::success(transfers)),
+        Err(e) => {
+            error!("❌ [API] Failed to get file transfers: {}", e);
+            HttpResponse::InternalServerError().json(ApiResponse::<()>::error(
+                format!("Failed to get file transfers: {}", e)
+            ))
+        },
+    }
+}
+
+/// Exports generated Airflow DAGs as a zip archive
+///
+/// Rebuilds the folders from the database, renders one DAG per folder with
+/// `AirflowDagGenerator` and returns the selected `.py` files zipped in
+/// memory. Dependencies are resolved across all folders, so a dependency on
+/// a folder that is not exported still becomes an `ExternalTaskSensor`.
+///
+/// # Arguments
+///
+/// * `query` - Folders to export
+/// * `repo` - Job repository for database access
+/// * `http_request` - Request used to check analysis-only access
+/// * `_auth` - Bearer token authentication
+///
+/// # Returns
+///
+/// HTTP 200 with `dags.zip`, HTTP 403 for viewers in analysis-only mode,
+/// HTTP 404 if no folder matches, HTTP 500 on error
+#[utoipa::path(
+    get,
+    path = "/export/airflow",
+    tag = "jobs",
+    security(("bearer_auth" = [])),
+    params(AirflowExportQuery),
+    responses(
+        (status = 200, description = "Zip archive (application/zip) of the DAG files"),
+        (status = 403, description = "Raw job data is restricted to admins (analysis-only mode)", body = ErrorApiResponse),
+        (status = 404, description = "No folder matches", body = ErrorApiResponse),
+        (status = 500, description = "DAG generation failed", body = ErrorApiResponse),
+    )
+)]
+pub async fn export_airflow_dags(
+    query: web::Query<AirflowExportQuery>,
+    repo: web::Data<Arc<JobRepository>>,
+    http_request: HttpRequest,
+    _auth: BearerAuth,
+) -> HttpResponse {
+    if let Some(forbidden) = deny_raw_job_data(&http_request) {
+        return forbidden;
+    }
+    let selected: Vec<&str> = query.folder.as_deref()
+        .map(|folders| folders.split(',').map(str::trim).filter(|name| !name.is_empty()).collect())
+        .unwrap_or_default();
+    info!("🌐 [API] GET /export/airflow?folder={:?}", selected);
+
+    let folders = match repo.load_dag_folders() {
+        Ok(folders) => folders,
+        Err(e) => {
+            error!("❌ [API] Failed to load jobs for DAG export: {}", e);
+            return HttpResponse::InternalServerError().json(ApiResponse::<()>::error(
+                format!("Failed to load jobs: {}", e)
+            ));
+        }
+    };
+
+    let entries: Vec<(String, Vec<u8>)> = AirflowDagGenerator::new()
+        .render_dags(&[], &folders)
+        .into_iter()
+        .filter(|(folder, _, _)| selected.is_empty() || selected.contains(&folder.folder_name.as_str()))
+        .map(|(_, file_name, source)| (file_name, source.into_bytes()))
+        .collect();
+    if entries.is_empty() {
+        return HttpResponse::NotFound().json(ApiResponse::<()>::error(
+            format!("No folder matches {:?}", selected)
+        ));
+    }
+
+    match BundleWriter::new().write_to_vec(&entries) {
+        Ok(zip) => {
+            info!(dags = entries.len(), bytes = zip.len(), "✅ [API] Exported DAGs");
+            HttpResponse::Ok()
+                .content_type("application/zip")
+                .insert_header(("Content-Disposition", "attachment; filename=\"dags.zip\""))
+                .body(zip)
+        }
+        Err(e) => {
+            error!("❌ [API] Failed to zip DAGs: {}", e);
+            HttpResponse::InternalServerError().json(ApiResponse::<()>::error(
+                format!("Failed to export DAGs: {}", e)
+            ))
+        }
+    }
+}
+
 /// Gets end-to-end dependency graph data for a specific job
 ///
 /// Returns nodes and edges for visualizing full dependency chain (upstream and downstream).
@@ -434,25 +1370,30 @@ pub async fn get_job_graph(
 /// * `repo` - Job repository for database access
 /// * `path` - Job ID from URL path
 /// * `query` - Query parameters (depth)
+/// * `http_request` - Request used to check analysis-only access
 ///
 /// # Returns
 ///
-/// HTTP 200 with graph data on success, HTTP 500 on error
+/// HTTP 200 with graph data on success, HTTP 403 for viewers in
+/// analysis-only mode, HTTP 500 on error
 pub async fn get_job_graph_end_to_end(
     repo: web::Data<Arc<JobRepository>>,
     path: web::Path<i64>,
     query: web::Query<std::collections::HashMap<String, String>>,
+    http_request: HttpRequest,
 ) -> impl Responder {
+    if let Some(forbidden) = deny_raw_job_data(&http_request) {
+        return forbidden;
+    }
     let job_id = path.into_inner();
     let depth = query.get("depth")
         .and_then(|d| d.parse::<i32>().ok());
     
-    info!("🌐 [API] GET /jobs/{}/graph/end-to-end?depth={:?}", job_id, depth);
+    info!(job_id, ?depth, "🌐 [API] GET /jobs/{{id}}/graph/end-to-end");
     
     match repo.get_end_to_end_graph(job_id, depth) {
         Ok(graph_data) => {
-            info!("✅ [API] Successfully retrieved end-to-end graph for job_id={} ({} nodes, {} edges)", 
-                  job_id, graph_data.nodes.len(), graph_data.edges.len());
+            info!(job_id, nodes = graph_data.nodes.len(), edges = graph_data.edges.len(), "✅ [API] Retrieved end-to-end graph");
             HttpResponse::Ok().json(ApiResponse {
                 success: true,
                 data: Some(graph_data),
@@ -471,6 +1412,213 @@ pub async fn get_job_graph_end_to_end(
     }
 }
 
+/// Gets the full lineage of a job
+///
+/// Returns all ancestors and descendants of the job (up to a depth limit)
+/// with each node tagged as root, ancestor or descendant.
+///
+/// # Arguments
+///
+/// * `repo` - Job repository for database access
+/// * `path` - Job ID from URL path
+/// * `query` - Query parameters (depth)
+/// * `http_request` - Request used to check analysis-only access
+///
+/// # Returns
+///
+/// HTTP 200 with lineage graph on success, HTTP 403 for viewers in
+/// analysis-only mode, HTTP 500 on error
+#[utoipa::path(
+    get,
+    path = "/jobs/{id}/lineage",
+    tag = "jobs",
+    security(("bearer_auth" = [])),
+    params(
+        ("id" = i64, Path, description = "Job ID"),
+        ("depth" = Option<i32>, Query, description = "Maximum number of hops upstream and downstream"),
+    ),
+    responses(
+        (status = 200, description = "Ancestors and descendants of the job", body = LineageApiResponse),
+        (status = 403, description = "Raw job data is restricted to admins (analysis-only mode)", body = ErrorApiResponse),
+        (status = 500, description = "Lineage query failed", body = ErrorApiResponse),
+    )
+)]
+pub async fn get_job_lineage(
+    repo: web::Data<Arc<JobRepository>>,
+    path: web::Path<i64>,
+    query: web::Query<std::collections::HashMap<String, String>>,
+    http_request: HttpRequest,
+) -> impl Responder {
+    if let Some(forbidden) = deny_raw_job_data(&http_request) {
+        return forbidden;
+    }
+    let job_id = path.into_inner();
+    let depth = query.get("depth")
+        .and_then(|d| d.parse::<i32>().ok());
+    
+    info!(job_id, ?depth, "🌐 [API] GET /jobs/{{id}}/lineage");
+    
+    match repo.get_job_lineage(job_id, depth) {
+        Ok(lineage) => {
+            info!(job_id, nodes = lineage.nodes.len(), edges = lineage.edges.len(), "✅ [API] Retrieved lineage");
+            HttpResponse::Ok().json(ApiResponse::success(lineage))
+        },
+        Err(e) => {
+            error!("❌ [API] Failed to get lineage for job_id={}: {}", job_id, e);
+            HttpResponse::InternalServerError().json(ApiResponse::<()>::error(
+                format!("Failed to get job lineage: {}", e)
+            ))
+        },
+    }
+}
+
+/// Maximum number of applications accepted by the application graph endpoint
+const MAX_GRAPH_APPLICATIONS: usize = 50;
+
+/// Gets the combined dependency graph of a set of applications
+///
+/// # Arguments
+///
+/// * `repo` - Job repository for database access
+/// * `request` - Application names to include
+/// * `http_request` - Request used to check analysis-only access
+///
+/// # Returns
+///
+/// HTTP 200 with the graph on success, HTTP 400 if no or too many
+/// applications are given, HTTP 403 for viewers in analysis-only mode,
+/// HTTP 500 on error
+#[utoipa::path(
+    post,
+    path = "/graph/applications",
+    tag = "jobs",
+    security(("bearer_auth" = [])),
+    request_body = ApplicationGraphRequest,
+    responses(
+        (status = 200, description = "Jobs of the applications and their direct neighbors", body = ApplicationGraphApiResponse),
+        (status = 400, description = "No or too many applications", body = ErrorApiResponse),
+        (status = 403, description = "Raw job data is restricted to admins (analysis-only mode)", body = ErrorApiResponse),
+        (status = 500, description = "Graph query failed", body = ErrorApiResponse),
+    )
+)]
+pub async fn get_applications_graph(
+    repo: web::Data<Arc<JobRepository>>,
+    request: web::Json<ApplicationGraphRequest>,
+    http_request: HttpRequest,
+) -> impl Responder {
+    if let Some(forbidden) = deny_raw_job_data(&http_request) {
+        return forbidden;
+    }
+    let applications: Vec<String> = request.into_inner().applications
+        .into_iter()
+        .map(|a| a.trim().to_string())
+        .filter(|a| !a.is_empty())
+        .collect();
+
+    info!("🌐 [API] POST /graph/applications ({} applications)", applications.len());
+
+    if applications.is_empty() || applications.len() > MAX_GRAPH_APPLICATIONS {
+        return HttpResponse::BadRequest().json(ApiResponse::<()>::error(
+            format!("Between 1 and {} applications are required", MAX_GRAPH_APPLICATIONS)
+        ));
+    }
+
+    match repo.get_applications_graph(&applications) {
+        Ok(graph) => HttpResponse::Ok().json(ApiResponse::success(graph)),
+        Err(e) => {
+            error!("❌ [API] Failed to get application graph: {}", e);
+            HttpResponse::InternalServerError().json(ApiResponse::<()>::error(
+                format!("Failed to get application graph: {}", e)
+            ))
+        },
+    }
+}
+
+/// Gets the analysis profile that produced the stored job analysis
+///
+/// Lets users auditing a dashboard see which complexity weights, difficulty
+/// thresholds and wave strategy produced the numbers shown.
+///
+/// # Arguments
+///
+/// * `repository` - Job repository for database access
+/// * `_auth` - Bearer token authentication
+///
+/// # Returns
+///
+/// HTTP 200 with the profile, HTTP 404 if the database has none (exported
+/// by an older version), HTTP 500 on error
+#[utoipa::path(
+    get,
+    path = "/analysis/profile",
+    tag = "analysis",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Settings that produced the stored analysis", body = AnalysisProfileApiResponse),
+        (status = 404, description = "No profile stored (database exported by an older version)", body = ErrorApiResponse),
+        (status = 500, description = "Lookup failed", body = ErrorApiResponse),
+    )
+)]
+pub async fn get_analysis_profile(
+    repository: web::Data<Arc<JobRepository>>,
+    _auth: BearerAuth,
+) -> HttpResponse {
+    debug!("🌐 [API] GET /analysis/profile");
+    match repository.get_analysis_profile() {
+        Ok(Some(profile)) => HttpResponse::Ok().json(ApiResponse::success(profile)),
+        Ok(None) => HttpResponse::NotFound().json(ApiResponse::<()>::error(
+            "No analysis profile stored; re-export the database to record one".to_string()
+        )),
+        Err(e) => {
+            error!("❌ [API] Failed to get analysis profile: {}", e);
+            HttpResponse::InternalServerError().json(ApiResponse::<()>::error(
+                format!("Failed to get analysis profile: {}", e)
+            ))
+        },
+    }
+}
+
+/// Gets critical jobs whose upstream chain contains non-critical jobs
+///
+/// # Arguments
+///
+/// * `repo` - Job repository for database access
+/// * `query` - Query parameters (depth)
+///
+/// # Returns
+///
+/// HTTP 200 with the list of conflicts on success, HTTP 500 on error
+#[utoipa::path(
+    get,
+    path = "/analysis/critical-conflicts",
+    tag = "analysis",
+    security(("bearer_auth" = [])),
+    params(("depth" = Option<i32>, Query, description = "Maximum number of hops searched upstream")),
+    responses(
+        (status = 200, description = "Critical jobs with non-critical upstream jobs", body = CriticalPathConflictsApiResponse),
+        (status = 500, description = "Query failed", body = ErrorApiResponse),
+    )
+)]
+pub async fn get_critical_path_conflicts(
+    repo: web::Data<Arc<JobRepository>>,
+    query: web::Query<std::collections::HashMap<String, String>>,
+) -> impl Responder {
+    let depth = query.get("depth")
+        .and_then(|d| d.parse::<i32>().ok());
+    
+    info!("🌐 [API] GET /analysis/critical-conflicts?depth={:?}", depth);
+    
+    match repo.get_critical_path_conflicts(depth) {
+        Ok(conflicts) => HttpResponse::Ok().json(ApiResponse::success(conflicts)),
+        Err(e) => {
+            error!("❌ [API] Failed to get critical path conflicts: {}", e);
+            HttpResponse::InternalServerError().json(ApiResponse::<()>::error(
+                format!("Failed to get critical path conflicts: {}", e)
+            ))
+        },
+    }
+}
+
 /// Get wave migration analysis
 ///
 /// Returns jobs and folders categorized by migration wave based on dependency patterns.
@@ -508,3 +1656,502 @@ pub async fn get_wave_migration_analysis(
         },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{test as actix_test, App};
+    use crate::domain::entities::{Folder, FolderType, Job};
+    use crate::infrastructure::output::SqliteExporter;
+
+    #[test]
+    fn test_parse_byte_range() {
+        let resolve = |header: &str| parse_byte_range(header).and_then(|range| range.resolve(100));
+        assert_eq!(resolve("bytes=0-9"), Some((0, 9)));
+        assert_eq!(resolve("bytes=90-"), Some((90, 99)));
+        assert_eq!(resolve("bytes=-10"), Some((90, 99)));
+        assert_eq!(resolve("bytes=50-500"), Some((50, 99)));
+
+        // Valid but unsatisfiable
+        assert_eq!(parse_byte_range("bytes=100-"), Some(ByteRange::FromTo(100, None)));
+        assert_eq!(resolve("bytes=100-"), None);
+        assert_eq!(resolve("bytes=-0"), None);
+
+        // Malformed or multi-range headers are ignored
+        assert_eq!(parse_byte_range("bytes=abc"), None);
+        assert_eq!(parse_byte_range("bytes=0-1,5-6"), None);
+        assert_eq!(parse_byte_range("bytes=9-0"), None);
+        assert_eq!(parse_byte_range("items=0-9"), None);
+    }
+
+    #[test]
+    fn test_channel_writer_stops_export_after_range() {
+        let tmp = tempfile::tempdir().unwrap();
+        let db_path = tmp.path().join("csv_stop.db");
+        let mut folder = Folder::new("FOLDER".to_string(), FolderType::Simple);
+        for i in 0..500 {
+            folder.add_job(Job::new(format!("JOB_{:03}", i), "FOLDER".to_string()));
+        }
+        SqliteExporter::new(&db_path).unwrap().export_folders(&[folder]).unwrap();
+        let repository = JobRepository::new(db_path.to_str().unwrap()).unwrap();
+
+        let (tx, mut rx) = tokio::sync::mpsc::channel(CSV_STREAM_BUFFERED_CHUNKS);
+        let err = repository
+            .write_search_csv(&JobSearchRequest::default(), ChannelWriter { tx, skip: 4, remaining: 8 })
+            .unwrap_err();
+
+        assert!(is_range_sent(&err), "unexpected export error: {:#}", err);
+        let mut sent = Vec::new();
+        while let Ok(chunk) = rx.try_recv() {
+            sent.extend_from_slice(&chunk.unwrap());
+        }
+        assert_eq!(sent.len(), 8);
+        assert!(!is_range_sent(&anyhow::anyhow!("CSV query error")));
+    }
+
+    #[actix_web::test]
+    async fn test_ranged_csv_export_matches_full_export_slice() {
+        let tmp = tempfile::tempdir().unwrap();
+        let db_path = tmp.path().join("csv_range.db");
+        let mut folder = Folder::new("FOLDER".to_string(), FolderType::Simple);
+        for i in 0..20 {
+            folder.add_job(Job::new(format!("JOB_{:02}", i), "FOLDER".to_string()));
+        }
+        SqliteExporter::new(&db_path).unwrap().export_folders(&[folder]).unwrap();
+        let repository = Arc::new(JobRepository::new(db_path.to_str().unwrap()).unwrap());
+
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(web::Data::new(repository))
+                .route("/export", web::post().to(export_jobs_csv))
+        ).await;
+
+        let full_req = actix_test::TestRequest::post()
+            .uri("/export")
+            .insert_header(("Authorization", "Bearer test"))
+            .set_json(serde_json::json!({}))
+            .to_request();
+        let full_resp = actix_test::call_service(&app, full_req).await;
+        assert_eq!(full_resp.status(), 200);
+        let etag = full_resp.headers().get("ETag").unwrap().to_str().unwrap().to_string();
+        let full_body = actix_test::read_body(full_resp).await;
+
+        let ranged_req = actix_test::TestRequest::post()
+            .uri("/export")
+            .insert_header(("Authorization", "Bearer test"))
+            .insert_header(("Range", "bytes=10-99"))
+            .set_json(serde_json::json!({}))
+            .to_request();
+        let ranged_resp = actix_test::call_service(&app, ranged_req).await;
+        assert_eq!(ranged_resp.status(), 206);
+        assert_eq!(ranged_resp.headers().get("ETag").unwrap().to_str().unwrap(), etag);
+        let ranged_body = actix_test::read_body(ranged_resp).await;
+
+        assert_eq!(&ranged_body[..], &full_body[10..100]);
+
+        // Resuming against the tag of the first download only succeeds while it still matches
+        for (if_range, status) in [(etag.as_str(), 206), ("\"stale\"", 200), ("Wed, 21 Oct 2015 07:28:00 GMT", 200)] {
+            let req = actix_test::TestRequest::post()
+                .uri("/export")
+                .insert_header(("Authorization", "Bearer test"))
+                .insert_header(("Range", "bytes=10-99"))
+                .insert_header(("If-Range", if_range))
+                .set_json(serde_json::json!({}))
+                .to_request();
+            let resp = actix_test::call_service(&app, req).await;
+            assert_eq!(resp.status(), status, "If-Range: {}", if_range);
+            let body = actix_test::read_body(resp).await;
+            assert_eq!(body, if status == 206 { ranged_body.clone() } else { full_body.clone() });
+        }
+
+        let filtered_req = actix_test::TestRequest::post()
+            .uri("/export")
+            .insert_header(("Authorization", "Bearer test"))
+            .set_json(serde_json::json!({ "job_name": "JOB_01" }))
+            .to_request();
+        let filtered_resp = actix_test::call_service(&app, filtered_req).await;
+        assert_ne!(filtered_resp.headers().get("ETag").unwrap().to_str().unwrap(), etag);
+
+        for (range, status) in [("bytes=abc", 200), ("bytes=0-1,5-6", 200), ("bytes=100000-", 416)] {
+            let req = actix_test::TestRequest::post()
+                .uri("/export")
+                .insert_header(("Authorization", "Bearer test"))
+                .insert_header(("Range", range))
+                .set_json(serde_json::json!({}))
+                .to_request();
+            let resp = actix_test::call_service(&app, req).await;
+            assert_eq!(resp.status(), status, "Range: {}", range);
+            if status == 200 {
+                assert_eq!(actix_test::read_body(resp).await, full_body);
+            }
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_full_csv_export_is_streamed() {
+        use actix_web::body::{BodySize, MessageBody};
+
+        let tmp = tempfile::tempdir().unwrap();
+        let db_path = tmp.path().join("csv_stream.db");
+        let mut folder = Folder::new("FOLDER".to_string(), FolderType::Simple);
+        for i in 0..2000 {
+            let mut job = Job::new(format!("JOB_{:04}", i), "FOLDER".to_string());
+            job.description = Some("x".repeat(100));
+            folder.add_job(job);
+        }
+        SqliteExporter::new(&db_path).unwrap().export_folders(&[folder]).unwrap();
+        let repository = Arc::new(JobRepository::new(db_path.to_str().unwrap()).unwrap());
+        let expected = repository.export_search_to_csv(&JobSearchRequest::default()).unwrap();
+
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(web::Data::new(repository))
+                .route("/export", web::post().to(export_jobs_csv))
+        ).await;
+        let req = actix_test::TestRequest::post()
+            .uri("/export")
+            .insert_header(("Authorization", "Bearer test"))
+            .set_json(serde_json::json!({}))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 200);
+        assert_eq!(resp.response().body().size(), BodySize::Stream);
+        let body = actix_test::read_body(resp).await;
+
+        assert_eq!(std::str::from_utf8(&body).unwrap(), expected);
+        assert_eq!(expected.lines().count(), 2001);
+    }
+
+    #[actix_web::test]
+    async fn test_reload_serves_swapped_replica() {
+        use actix_web_httpauth::middleware::HttpAuthentication;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let export = |file: &str, job_count: usize| {
+            let mut folder = Folder::new("FOLDER".to_string(), FolderType::Simple);
+            for i in 0..job_count {
+                folder.add_job(Job::new(format!("JOB_{}", i), "FOLDER".to_string()));
+            }
+            SqliteExporter::new(dir.join(file)).unwrap().export_folders(&[folder]).unwrap();
+        };
+        export("replica.db", 1);
+        let replica = dir.join("replica.db");
+        let repository = Arc::new(JobRepository::open_read_only(replica.to_str().unwrap()).unwrap());
+        let config = WebConfig::new(replica.to_string_lossy().to_string())
+            .with_admin_usernames(vec!["admin".to_string()]);
+        let auth_service = AuthService::new(config.jwt_secret.clone());
+        let token_for = |username: &str| {
+            auth_service.generate_token(&UserInfo {
+                username: username.to_string(),
+                display_name: username.to_string(),
+                auth_type: AuthType::Local,
+                role: config.role_for(username),
+            }).unwrap()
+        };
+
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(web::Data::new(config.clone()))
+                .app_data(web::Data::new(repository.clone()))
+                .service(
+                    web::scope("")
+                        .wrap(HttpAuthentication::bearer(crate::web::auth::validator))
+                        .route("/admin/reload", web::post().to(reload_database))
+                )
+        ).await;
+        let reload = |token: String| {
+            actix_test::TestRequest::post()
+                .uri("/admin/reload")
+                .insert_header(("Authorization", format!("Bearer {}", token)))
+                .to_request()
+        };
+
+        let total = || repository.search_jobs(&JobSearchRequest::default()).unwrap().total;
+        assert_eq!(total(), 1);
+
+        // Refresh the replica the way a sync job would: write aside, then rename over
+        export("replica.db.new", 3);
+        std::fs::rename(dir.join("replica.db.new"), &replica).unwrap();
+        assert_eq!(total(), 1);
+
+        let resp = actix_test::call_service(&app, reload(token_for("viewer"))).await;
+        assert_eq!(resp.status(), 403);
+        assert_eq!(total(), 1);
+
+        let resp = actix_test::call_service(&app, reload(token_for("admin"))).await;
+        assert_eq!(resp.status(), 200);
+        assert_eq!(total(), 3);
+    }
+
+    #[actix_web::test]
+    async fn test_distinct_owners_filtered_by_prefix() {
+        let tmp = tempfile::tempdir().unwrap();
+        let db_path = tmp.path().join("distinct.db");
+        let mut folder = Folder::new("FOLDER".to_string(), FolderType::Simple);
+        for (i, owner) in ["batch_prod", "batch_prod", "batch_dev", "etl_user", "Batch_QA"].iter().enumerate() {
+            let mut job = Job::new(format!("JOB_{}", i), "FOLDER".to_string());
+            job.owner = Some(owner.to_string());
+            folder.add_job(job);
+        }
+        SqliteExporter::new(&db_path).unwrap().export_folders(&[folder]).unwrap();
+        let repository = Arc::new(JobRepository::new(db_path.to_str().unwrap()).unwrap());
+
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(web::Data::new(repository))
+                .route("/meta/distinct", web::get().to(get_distinct_values))
+        ).await;
+
+        let req = actix_test::TestRequest::get()
+            .uri("/meta/distinct?column=owner&q=batch&limit=10")
+            .insert_header(("Authorization", "Bearer test"))
+            .to_request();
+        let body: serde_json::Value = actix_test::call_and_read_body_json(&app, req).await;
+        let values: Vec<(String, u64)> = body["data"].as_array().unwrap().iter()
+            .map(|v| (v["value"].as_str().unwrap().to_string(), v["count"].as_u64().unwrap()))
+            .collect();
+        assert_eq!(values[0], ("batch_prod".to_string(), 2));
+        assert_eq!(values.len(), 3);
+        assert!(values.iter().all(|(value, _)| value.to_lowercase().starts_with("batch")));
+
+        let req = actix_test::TestRequest::get()
+            .uri("/meta/distinct?column=cmdline")
+            .insert_header(("Authorization", "Bearer test"))
+            .to_request();
+        assert_eq!(actix_test::call_service(&app, req).await.status(), 400);
+    }
+
+    #[actix_web::test]
+    async fn test_analysis_only_mode_routes_viewers_to_analysis_endpoints() {
+        use actix_web_httpauth::middleware::HttpAuthentication;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let db_path = tmp.path().join("analysis_only.db");
+        let mut folder = Folder::new("FOLDER".to_string(), FolderType::Simple);
+        let mut job = Job::new("JOB_A".to_string(), "FOLDER".to_string());
+        job.owner = Some("batch_owner".to_string());
+        job.run_as = Some("svc_batch".to_string());
+        let mut on_cond = crate::domain::entities::OnCondition::new();
+        on_cond.actions.push(crate::domain::entities::DoAction::Mail {
+            dest: "ops@example.com".to_string(),
+            message: "failed".to_string(),
+        });
+        job.on_conditions.push(on_cond);
+        folder.add_job(job);
+        SqliteExporter::new(&db_path).unwrap().export_folders(&[folder]).unwrap();
+        let repository = Arc::new(JobRepository::new(db_path.to_str().unwrap()).unwrap());
+        let config = WebConfig::new(db_path.to_string_lossy().to_string())
+            .with_analysis_only(true)
+            .with_admin_usernames(vec!["admin".to_string()]);
+        let auth_service = AuthService::new(config.jwt_secret.clone());
+        let token_for = |username: &str| {
+            auth_service.generate_token(&UserInfo {
+                username: username.to_string(),
+                display_name: username.to_string(),
+                auth_type: AuthType::Local,
+                role: config.role_for(username),
+            }).unwrap()
+        };
+        let (viewer_token, admin_token) = (token_for("viewer"), token_for("admin"));
+
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(web::Data::new(config.clone()))
+                .app_data(web::Data::new(repository))
+                .service(
+                    web::scope("")
+                        .wrap(HttpAuthentication::bearer(crate::web::auth::validator))
+                        .configure(crate::web::server::configure_protected_routes)
+                )
+        ).await;
+        let request = |uri: &'static str, token: &str| {
+            actix_test::TestRequest::get()
+                .uri(uri)
+                .insert_header(("Authorization", format!("Bearer {}", token)))
+                .to_request()
+        };
+
+        let resp = actix_test::call_service(&app, request("/jobs/1", &viewer_token)).await;
+        assert_eq!(resp.status(), 403);
+        let resp = actix_test::call_service(&app, request("/jobs/file-transfers", &viewer_token)).await;
+        assert_eq!(resp.status(), 403);
+        let resp = actix_test::call_service(&app, request("/export/airflow", &viewer_token)).await;
+        assert_eq!(resp.status(), 403);
+        for graph_uri in ["/jobs/1/graph", "/jobs/1/graph/end-to-end", "/jobs/1/lineage", "/jobs/1/dependencies", "/folders/FOLDER/graph"] {
+            let resp = actix_test::call_service(&app, request(graph_uri, &viewer_token)).await;
+            assert_eq!(resp.status(), 403, "{}", graph_uri);
+        }
+        let applications_graph = actix_test::TestRequest::post()
+            .uri("/graph/applications")
+            .insert_header(("Authorization", format!("Bearer {}", viewer_token)))
+            .set_json(serde_json::json!({ "applications": ["APP"] }))
+            .to_request();
+        assert_eq!(actix_test::call_service(&app, applications_graph).await.status(), 403);
+        let search = actix_test::TestRequest::post()
+            .uri("/jobs/search")
+            .insert_header(("Authorization", format!("Bearer {}", viewer_token)))
+            .set_json(JobSearchRequest::default())
+            .to_request();
+        assert_eq!(actix_test::call_service(&app, search).await.status(), 403);
+        let resp = actix_test::call_service(&app, request("/dashboard/stats", &viewer_token)).await;
+        assert_eq!(resp.status(), 200);
+        let body: serde_json::Value = actix_test::read_body_json(resp).await;
+        assert_eq!(body["data"]["jobs_by_owner"], serde_json::json!([]));
+        assert_eq!(body["data"]["jobs_by_run_as"], serde_json::json!([]));
+        assert_eq!(body["data"]["jobs_by_notification_target"], serde_json::json!([]));
+        let resp = actix_test::call_service(&app, request("/meta/distinct?column=run_as", &viewer_token)).await;
+        assert_eq!(resp.status(), 403);
+        let resp = actix_test::call_service(&app, request("/meta/distinct?column=folder_name", &viewer_token)).await;
+        assert_eq!(resp.status(), 200);
+        let resp = actix_test::call_service(&app, request("/wave-migration/analysis", &viewer_token)).await;
+        assert_eq!(resp.status(), 200);
+        let resp = actix_test::call_service(&app, request("/jobs/1", &admin_token)).await;
+        assert_eq!(resp.status(), 200);
+        let resp = actix_test::call_service(&app, request("/folders/FOLDER/graph", &admin_token)).await;
+        assert_eq!(resp.status(), 200);
+        let resp = actix_test::call_service(&app, request("/meta/distinct?column=run_as", &admin_token)).await;
+        assert_eq!(resp.status(), 200);
+        let resp = actix_test::call_service(&app, request("/dashboard/stats", &admin_token)).await;
+        let body: serde_json::Value = actix_test::read_body_json(resp).await;
+        assert_eq!(body["data"]["jobs_by_notification_target"][0]["destination"], "ops@example.com");
+    }
+
+    #[actix_web::test]
+    async fn test_analysis_profile_round_trips_through_export() {
+        use crate::domain::value_objects::{AnalysisProfile, ComplexityWeights};
+
+        let tmp = tempfile::tempdir().unwrap();
+        let db_path = tmp.path().join("profile.db");
+        let mut folder = Folder::new("FOLDER".to_string(), FolderType::Simple);
+        let mut job = Job::new("JOB_A".to_string(), "FOLDER".to_string());
+        job.cyclic = true;
+        folder.add_job(job);
+        let profile = AnalysisProfile::default()
+            .with_complexity_weights(ComplexityWeights { cyclic: 2, on_condition: 10, ..Default::default() })
+            .with_downstream_dependencies(true);
+        SqliteExporter::new(&db_path).unwrap()
+            .with_analysis_profile(profile.clone())
+            .export_folders(&[folder])
+            .unwrap();
+        let repository = Arc::new(JobRepository::new(db_path.to_str().unwrap()).unwrap());
+
+        let stored = repository.get_analysis_profile().unwrap().unwrap();
+        assert_eq!(stored.profile, profile);
+        assert_eq!(stored.fingerprint, profile.fingerprint());
+
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(web::Data::new(repository))
+                .route("/analysis/profile", web::get().to(get_analysis_profile))
+        ).await;
+        let req = actix_test::TestRequest::get()
+            .uri("/analysis/profile")
+            .insert_header(("Authorization", "Bearer test"))
+            .to_request();
+        let body: serde_json::Value = actix_test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["data"]["profile"]["complexity_weights"]["cyclic"], 2);
+        assert_eq!(body["data"]["profile"]["count_downstream_dependencies"], true);
+        assert_eq!(body["data"]["fingerprint"], profile.fingerprint());
+    }
+
+    #[actix_web::test]
+    async fn test_refresh_token_reissues_only_valid_tokens() {
+        let mut config = WebConfig::new("unused.db".to_string());
+        config.token_ttl_hours = 2;
+        let user = UserInfo {
+            username: "analyst".to_string(),
+            display_name: "Analyst".to_string(),
+            auth_type: AuthType::Local,
+            role: UserRole::Viewer,
+        };
+        let auth_service = AuthService::new(config.jwt_secret.clone());
+        let token = auth_service.generate_token(&user).unwrap();
+        let expired = jsonwebtoken::encode(
+            &jsonwebtoken::Header::default(),
+            &Claims {
+                sub: user.username.clone(),
+                username: user.username.clone(),
+                display_name: user.display_name.clone(),
+                auth_type: AuthType::Local,
+                role: UserRole::Viewer,
+                exp: chrono::Utc::now().timestamp() - 3600,
+            },
+            &jsonwebtoken::EncodingKey::from_secret(config.jwt_secret.as_bytes()),
+        ).unwrap();
+
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(web::Data::new(config.clone()))
+                .route("/auth/refresh", web::post().to(refresh_token))
+        ).await;
+        let request = |token: &str| {
+            actix_test::TestRequest::post()
+                .uri("/auth/refresh")
+                .insert_header(("Authorization", format!("Bearer {}", token)))
+                .to_request()
+        };
+
+        let body: serde_json::Value = actix_test::call_and_read_body_json(&app, request(&token)).await;
+        let refreshed = auth_service.verify_token(body["data"]["token"].as_str().unwrap()).unwrap();
+        assert_eq!(refreshed.username, "analyst");
+        assert_eq!(refreshed.display_name, "Analyst");
+        let ttl = refreshed.exp - chrono::Utc::now().timestamp();
+        assert!(ttl > 3600 && ttl <= 2 * 3600, "{}", ttl);
+
+        let resp = actix_test::call_service(&app, request(&expired)).await;
+        assert_eq!(resp.status(), 401);
+        let resp = actix_test::call_service(&app, request("not-a-token")).await;
+        assert_eq!(resp.status(), 401);
+
+        // An admin token for a user no longer listed in ADMIN_USERNAMES refreshes as viewer
+        let stale_admin = auth_service.generate_token(&UserInfo { role: UserRole::Admin, ..user }).unwrap();
+        let body: serde_json::Value = actix_test::call_and_read_body_json(&app, request(&stale_admin)).await;
+        let refreshed = auth_service.verify_token(body["data"]["token"].as_str().unwrap()).unwrap();
+        assert_eq!(refreshed.role, UserRole::Viewer);
+    }
+
+    #[actix_web::test]
+    async fn test_airflow_export_zips_selected_folder_dags() {
+        use crate::domain::entities::Condition;
+        use std::io::Read;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let db_path = tmp.path().join("airflow_export.db");
+        let mut extract = Job::new("EXTRACT".to_string(), "DAILY".to_string());
+        extract.cmdline = Some("/opt/etl/extract.sh".to_string());
+        extract.out_conditions.push(Condition::new_out("EXTRACT-OK".to_string()));
+        let mut daily = Folder::new("DAILY".to_string(), FolderType::Simple);
+        daily.add_job(extract);
+        let mut report = Job::new("REPORT".to_string(), "REPORTS".to_string());
+        report.in_conditions.push(Condition::new_in("EXTRACT-OK".to_string()));
+        let mut reports = Folder::new("REPORTS".to_string(), FolderType::Simple);
+        reports.add_job(report);
+        SqliteExporter::new(&db_path).unwrap().export_folders(&[daily, reports]).unwrap();
+        let repository = Arc::new(JobRepository::new(db_path.to_str().unwrap()).unwrap());
+
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(web::Data::new(repository))
+                .route("/export/airflow", web::get().to(export_airflow_dags))
+        ).await;
+        let get = |uri: &'static str| actix_test::TestRequest::get()
+            .uri(uri)
+            .insert_header(("Authorization", "Bearer test"))
+            .to_request();
+
+        let resp = actix_test::call_service(&app, get("/export/airflow?folder=REPORTS")).await;
+        assert_eq!(resp.status(), 200);
+        assert_eq!(resp.headers().get("Content-Disposition").unwrap(), "attachment; filename=\"dags.zip\"");
+        let body = actix_test::read_body(resp).await;
+
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(body.to_vec())).unwrap();
+        assert_eq!(archive.len(), 1);
+        let mut reports_py = String::new();
+        archive.by_name("reports.py").unwrap().read_to_string(&mut reports_py).unwrap();
+        assert!(reports_py.contains("external_dag_id=\"daily\",\n        external_task_id=\"extract\",\n"));
+
+        let resp = actix_test::call_service(&app, get("/export/airflow?folder=MISSING")).await;
+        assert_eq!(resp.status(), 404);
+    }
+}
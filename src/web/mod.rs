@@ -2,6 +2,7 @@ pub mod config;
 pub mod auth;
 pub mod handlers;
 pub mod models;
+pub mod openapi;
 pub mod repository;
 pub mod server;
 
@@ -1,44 +1,74 @@
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use crate::domain::value_objects::TokenizedCommand;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct LoginRequest {
     pub username: String,
     pub password: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct LoginResponse {
     pub token: String,
     pub user: UserInfo,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub struct UserInfo {
     pub username: String,
     pub display_name: String,
     pub auth_type: AuthType,
+    #[serde(default)]
+    pub role: UserRole,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum AuthType {
     Local,
     EntraId,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Access level of an authenticated user
+///
+/// Only matters in analysis-only mode, where raw job data (command lines,
+/// run-as accounts) is restricted to admins.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum UserRole {
+    #[default]
+    Viewer,
+    Admin,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct EntraIdAuthRequest {
     pub code: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
 pub struct JobSearchRequest {
+    /// Free-text search over job name, description and cmdline; results are
+    /// ranked by relevance unless `sort_by` is given
+    pub search_text: Option<String>,
     pub job_name: Option<String>,
     pub folder_name: Option<String>,
+    /// How `job_name` and `folder_name` are matched (default: contains)
+    pub match_mode: Option<MatchMode>,
+    /// Whether `job_name` and `folder_name` matching is case sensitive;
+    /// unset keeps SQLite's default (case-insensitive LIKE, case-sensitive `=`)
+    pub case_sensitive: Option<bool>,
     pub application: Option<String>,
     pub appl_type: Option<String>,
     pub appl_ver: Option<String>,
     pub task_type: Option<String>,
+    /// Job owner; "(Unspecified)" matches jobs without an owner
+    pub owner: Option<String>,
+    /// Run-as account; "(Unspecified)" matches jobs without one
+    pub run_as: Option<String>,
+    /// Migration wave stored in the job_analysis table
+    pub migration_wave: Option<i32>,
     pub critical: Option<bool>,
     pub datacenter: Option<String>,
     pub folder_order_method: Option<String>,
@@ -49,20 +79,39 @@ pub struct JobSearchRequest {
     pub max_on_conditions: Option<i32>,
     pub has_variables: Option<bool>,
     pub min_variables: Option<i32>,
+    /// Jobs rerun automatically at least this many times (MAXRERUN)
+    pub min_maxrerun: Option<i32>,
+    /// Current job versions only (the default, with NULL counted as current);
+    /// false returns every version, historical ones (IS_CURRENT_VERSION="N")
+    /// included
+    pub is_current_version: Option<bool>,
     pub page: Option<u32>,
     pub per_page: Option<u32>,
     pub sort_by: Option<String>,
     pub sort_order: Option<SortOrder>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum SortOrder {
     Asc,
     Desc,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// How a name filter is compared with the stored name
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchMode {
+    /// The name contains the filter text
+    #[default]
+    Contains,
+    /// The name equals the filter text
+    Exact,
+    /// The name begins with the filter text
+    StartsWith,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct JobSearchResponse {
     pub jobs: Vec<JobDetail>,
     pub total: u32,
@@ -71,7 +120,7 @@ pub struct JobSearchResponse {
     pub total_pages: u32,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub struct JobDetail {
     pub id: i64,
     pub job_name: String,
@@ -210,12 +259,19 @@ pub struct JobDetail {
     pub control_resources_count: u32,
     pub variables_count: u32,
     pub total_dependencies_e2e: u32,
+    /// Longest chain of upstream jobs (0 when the job waits for nothing)
+    pub dependency_depth: u32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct JobDetailFull {
     pub job: JobDetail,
-    pub scheduling: Option<JobScheduling>,
+    /// Command line split into program and arguments, when stored at export
+    #[schema(value_type = Option<Object>)]
+    pub cmdline_tokens: Option<TokenizedCommand>,
+    /// Scheduling definitions in export order; empty for event-only jobs,
+    /// several for jobs with scheduling variants
+    pub scheduling: Vec<JobScheduling>,
     pub in_conditions: Vec<Condition>,
     pub out_conditions: Vec<Condition>,
     pub on_conditions: Vec<OnCondition>,
@@ -226,7 +282,7 @@ pub struct JobDetailFull {
     pub metadata: Vec<JobMetadata>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct JobScheduling {
     pub time_from: Option<String>,
     pub time_to: Option<String>,
@@ -235,14 +291,16 @@ pub struct JobScheduling {
     pub conf_calendar: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct Condition {
     pub condition_name: String,
     pub odate: Option<String>,
     pub and_or: Option<String>,
+    /// Whether an out-condition is added or deleted; None for in-conditions
+    pub sign: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct OnCondition {
     pub stmt: Option<String>,
     pub code: Option<String>,
@@ -250,21 +308,21 @@ pub struct OnCondition {
     pub actions: Vec<DoAction>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct DoAction {
     pub action_type: String,
     pub action_value: String,
     pub additional_data: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct Resource {
     pub resource_name: String,
     pub resource_type: Option<String>,
     pub on_fail: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct QuantitativeResource {
     pub resource_name: String,
     pub quantity: Option<i32>,
@@ -272,23 +330,65 @@ pub struct QuantitativeResource {
     pub on_ok: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct Variable {
     pub name: String,
     pub value: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct DashboardFilter {
     pub folder_order_method_filter: Option<String>,
     pub datacenter: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct DatacenterFilter {
     pub datacenter: Option<String>,
 }
 
+#[derive(Debug, Default, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FileTransferFilter {
+    /// Only list `watch`, `upload` or `download` entries
+    pub direction: Option<String>,
+}
+
+/// A file a job transfers or watches
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct FileTransferJob {
+    pub job_id: i64,
+    pub job_name: String,
+    pub folder_name: String,
+    /// `watch`, `upload` or `download`
+    pub direction: String,
+    /// Local path with the job's `%%` variables resolved: the watched file,
+    /// the upload source or the download target
+    pub path: String,
+    pub remote_path: Option<String>,
+    pub host: Option<String>,
+}
+
+/// A folder with the counts of its current job versions
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FolderSummary {
+    pub folder_name: String,
+    pub datacenter: Option<String>,
+    pub folder_order_method: Option<String>,
+    pub job_count: u32,
+    pub critical_count: u32,
+    pub cyclic_count: u32,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AirflowExportQuery {
+    /// Folder names to export, comma-separated; all folders when unset
+    pub folder: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DependencyNode {
     pub id: i64,
@@ -322,7 +422,7 @@ pub struct DependencyStats {
     pub max_depth: usize,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct JobMetadata {
     pub key: String,
     pub value: String,
@@ -337,7 +437,7 @@ pub struct RootJobStat {
     pub downstream_count: u32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct DashboardStats {
     pub total_jobs: u32,
     pub total_folders: u32,
@@ -349,40 +449,114 @@ pub struct DashboardStats {
     pub jobs_by_folder: Vec<FolderStat>,
     pub jobs_by_task_type: Vec<TaskTypeStat>,
     pub jobs_by_appl_type: Vec<ApplTypeStat>,
+    pub jobs_by_owner: Vec<IdentityStat>,
+    pub jobs_by_run_as: Vec<IdentityStat>,
+    pub most_connected_jobs: Vec<ConnectedJobStat>,
+    pub jobs_by_notification_target: Vec<NotificationTargetStat>,
+    pub jobs_by_datacenter: Vec<DatacenterStat>,
+    pub complexity_distribution: ComplexityDistribution,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ApplicationStat {
     pub application: String,
     pub count: u32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct FolderStat {
     pub folder_name: String,
     pub job_count: u32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct TaskTypeStat {
     pub task_type: String,
     pub count: u32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ApplTypeStat {
     pub appl_type: String,
     pub count: u32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Job count for an owner or run-as account ("(Unspecified)" when NULL)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct IdentityStat {
+    pub name: String,
+    pub count: u32,
+}
+
+/// Number of jobs notifying a DOMAIL or DOSHOUT recipient
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct NotificationTargetStat {
+    /// Email address or shout destination
+    pub destination: String,
+    /// "Mail" or "Shout"
+    pub channel: String,
+    pub job_count: u32,
+}
+
+/// Job counts for one datacenter ("(Unspecified)" when the folder has none)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DatacenterStat {
+    pub datacenter: String,
+    pub job_count: u32,
+    pub critical_jobs: u32,
+    pub cyclic_jobs: u32,
+}
+
+/// Job with the number of jobs it is transitively connected to
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ConnectedJobStat {
+    pub job_id: i64,
+    pub job_name: String,
+    pub folder_name: String,
+    pub total_dependencies_e2e: u32,
+}
+
+/// Analyzed jobs per complexity bucket: low (0-30), medium (31-60), high (61+)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 pub struct ComplexityDistribution {
     pub low: u32,
     pub medium: u32,
     pub high: u32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DistinctValuesQuery {
+    /// Jobs column to list; must be in the repository allowlist
+    pub column: String,
+    /// Case-insensitive prefix the values must start with
+    pub q: Option<String>,
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct DistinctValue {
+    pub value: String,
+    pub count: u32,
+}
+
+/// Request for the jobs matching a list of job names
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct JobsByNamesRequest {
+    pub names: Vec<String>,
+}
+
+/// Jobs matching a list of job names
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct JobsByNamesResponse {
+    /// Every job row whose name was requested (a name may match several
+    /// rows, e.g. in different folders or versions)
+    pub jobs: Vec<JobDetail>,
+    /// Requested names without any matching job, in request order
+    pub missing: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct FilterOptions {
     pub folders: Vec<String>,
     pub applications: Vec<String>,
@@ -393,14 +567,35 @@ pub struct FilterOptions {
     pub folder_order_methods: Vec<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Envelope of every API response; `data` is set on success, `error` on failure
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[aliases(
+    LoginApiResponse = ApiResponse<LoginResponse>,
+    UserInfoApiResponse = ApiResponse<UserInfo>,
+    JobSearchApiResponse = ApiResponse<JobSearchResponse>,
+    JobDetailApiResponse = ApiResponse<JobDetailFull>,
+    JobGraphApiResponse = ApiResponse<JobGraphData>,
+    DashboardStatsApiResponse = ApiResponse<DashboardStats>,
+    FilterOptionsApiResponse = ApiResponse<FilterOptions>,
+    FileTransferApiResponse = ApiResponse<Vec<FileTransferJob>>,
+    FolderListApiResponse = ApiResponse<Vec<FolderSummary>>,
+    DatacenterStatsApiResponse = ApiResponse<Vec<DatacenterStat>>,
+    DistinctValuesApiResponse = ApiResponse<Vec<DistinctValue>>,
+    JobsByNamesApiResponse = ApiResponse<JobsByNamesResponse>,
+    LineageApiResponse = ApiResponse<LineageGraph>,
+    ApplicationGraphApiResponse = ApiResponse<ApplicationGraphData>,
+    AnalysisProfileApiResponse = ApiResponse<StoredAnalysisProfile>,
+    CriticalPathConflictsApiResponse = ApiResponse<Vec<CriticalPathConflict>>,
+    MessageApiResponse = ApiResponse<String>,
+    ErrorApiResponse = ApiResponse<String>,
+)]
 pub struct ApiResponse<T> {
     pub success: bool,
     pub data: Option<T>,
     pub error: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct JobGraphData {
     pub job_id: i64,
     pub job_name: String,
@@ -409,7 +604,7 @@ pub struct JobGraphData {
     pub edges: Vec<GraphEdge>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct GraphNode {
     pub id: i64,
     pub label: String,
@@ -420,7 +615,7 @@ pub struct GraphNode {
     pub is_current: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct GraphEdge {
     pub from: i64,
     pub to: i64,
@@ -446,6 +641,74 @@ impl<T> ApiResponse<T> {
     }
 }
 
+// Application Graph Models
+
+/// Request for the combined dependency graph of a set of applications
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ApplicationGraphRequest {
+    pub applications: Vec<String>,
+}
+
+/// Dependency subgraph of all jobs in a set of applications
+///
+/// Nodes of the requested applications have `is_current` set; their direct
+/// neighbors in other applications are included with `is_current` false.
+/// Edges between two in-scope jobs are typed "internal", edges to or from
+/// an outside job "external".
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ApplicationGraphData {
+    pub applications: Vec<String>,
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+    /// Whether nodes or edges were dropped to stay within the size limit
+    pub truncated: bool,
+}
+
+// Lineage Models
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LineageGraph {
+    pub job_id: i64,
+    pub job_name: String,
+    pub folder_name: String,
+    pub max_depth: i32,
+    pub nodes: Vec<LineageNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LineageNode {
+    pub id: i64,
+    pub label: String,
+    pub folder: String,
+    pub application: Option<String>,
+    pub description: Option<String>,
+    pub color: String,
+    /// "root", "ancestor" or "descendant"
+    pub role: String,
+    /// Distance from the root job (0 for the root itself)
+    pub depth: i32,
+}
+
+// Critical Path Models
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CriticalPathConflict {
+    pub job_id: i64,
+    pub job_name: String,
+    pub folder_name: String,
+    pub non_critical_ancestors: Vec<CriticalPathAncestor>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CriticalPathAncestor {
+    pub job_id: i64,
+    pub job_name: String,
+    pub folder_name: String,
+    /// Number of hops upstream from the critical job
+    pub depth: i32,
+}
+
 // Wave Migration Analysis Models
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -489,3 +752,18 @@ pub struct WaveFolder {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub jobs_with_external_deps: Option<i64>,
 }
+
+// Analysis Profile Models
+
+/// Analysis settings stored with the job analysis at export time
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct StoredAnalysisProfile {
+    /// Complexity weights, difficulty thresholds, waves, migration target
+    /// and appl_type aliases, as described in the web server docs
+    #[schema(value_type = Object)]
+    pub profile: crate::domain::value_objects::AnalysisProfile,
+    /// Hash of the profile; equal fingerprints mean equal settings
+    pub fingerprint: String,
+    /// Export time (RFC 3339)
+    pub stored_at: String,
+}
@@ -0,0 +1,108 @@
+//! OpenAPI document module
+//!
+//! This module assembles the OpenAPI 3 description of the web API from the
+//! `utoipa` annotations on the handlers and the schemas derived on the
+//! request/response models. It is served at `/api/openapi.json` so frontend
+//! types can be generated instead of maintained by hand.
+
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi};
+
+use crate::web::handlers;
+use crate::web::models::*;
+
+/// OpenAPI description of the authentication, job, dashboard, analysis and admin routes
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "JobWeaver API", description = "Control-M job search and migration analysis"),
+    servers((url = "/api")),
+    paths(
+        handlers::login,
+        handlers::entra_id_callback,
+        handlers::refresh_token,
+        handlers::get_current_user,
+        handlers::search_jobs,
+        handlers::get_job_detail,
+        handlers::get_jobs_by_names,
+        handlers::get_job_graph,
+        handlers::get_job_lineage,
+        handlers::get_applications_graph,
+        handlers::list_folders,
+        handlers::get_folder_graph,
+        handlers::get_file_transfers,
+        handlers::export_airflow_dags,
+        handlers::get_dashboard_stats,
+        handlers::get_datacenter_stats,
+        handlers::get_filter_options,
+        handlers::get_distinct_values,
+        handlers::get_analysis_profile,
+        handlers::get_critical_path_conflicts,
+        handlers::reload_database,
+    ),
+    components(schemas(
+        LoginRequest, LoginResponse, UserInfo, AuthType, UserRole, EntraIdAuthRequest,
+        JobSearchRequest, SortOrder, MatchMode, JobSearchResponse, JobDetail, JobDetailFull,
+        JobScheduling, Condition, OnCondition, DoAction, Resource, QuantitativeResource,
+        Variable, JobMetadata, JobGraphData, GraphNode, GraphEdge,
+        DashboardStats, ApplicationStat, FolderStat, TaskTypeStat, ApplTypeStat, IdentityStat,
+        NotificationTargetStat, DatacenterStat, ConnectedJobStat, ComplexityDistribution, FilterOptions,
+        LoginApiResponse, UserInfoApiResponse, JobSearchApiResponse, JobDetailApiResponse,
+        JobGraphApiResponse, DashboardStatsApiResponse, FilterOptionsApiResponse, ErrorApiResponse,
+        FileTransferJob, FileTransferApiResponse, FolderSummary, FolderListApiResponse,
+        DistinctValue, JobsByNamesRequest, JobsByNamesResponse, LineageGraph, LineageNode,
+        ApplicationGraphRequest, ApplicationGraphData, StoredAnalysisProfile,
+        CriticalPathConflict, CriticalPathAncestor,
+        DatacenterStatsApiResponse, DistinctValuesApiResponse, JobsByNamesApiResponse, LineageApiResponse,
+        ApplicationGraphApiResponse, AnalysisProfileApiResponse, CriticalPathConflictsApiResponse,
+        MessageApiResponse,
+    )),
+    modifiers(&BearerAuthScheme),
+    tags(
+        (name = "auth", description = "Login and token management"),
+        (name = "jobs", description = "Job search and details"),
+        (name = "dashboard", description = "Aggregated statistics"),
+        (name = "analysis", description = "Migration analysis results and settings"),
+        (name = "admin", description = "Server administration"),
+    )
+)]
+pub struct ApiDoc;
+
+/// Registers the JWT bearer scheme referenced by the protected routes
+struct BearerAuthScheme;
+
+impl Modify for BearerAuthScheme {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer_auth",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).bearer_format("JWT").build()),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_document_describes_search_and_auth_routes() {
+        let doc = serde_json::to_value(ApiDoc::openapi()).unwrap();
+
+        assert!(doc["openapi"].as_str().unwrap().starts_with("3."));
+        for path in ["/auth/login", "/auth/refresh", "/auth/me", "/jobs/search", "/jobs/{id}", "/jobs/{id}/graph", "/dashboard/stats", "/filters"] {
+            assert!(doc["paths"].get(path).is_some(), "missing {}", path);
+        }
+        for path in [
+            "/stats/datacenter", "/meta/distinct", "/jobs/by-names", "/admin/reload", "/jobs/{id}/lineage",
+            "/graph/applications", "/analysis/profile", "/analysis/critical-conflicts",
+        ] {
+            assert!(doc["paths"].get(path).is_some(), "missing {}", path);
+        }
+        let schemas = &doc["components"]["schemas"];
+        assert!(schemas["JobSearchRequest"]["properties"].get("search_text").is_some());
+        assert!(schemas["JobSearchApiResponse"]["properties"].get("data").is_some());
+        assert!(schemas["LineageNode"]["properties"].get("role").is_some());
+        assert!(schemas["StoredAnalysisProfile"]["properties"].get("fingerprint").is_some());
+        assert!(doc["components"]["securitySchemes"].get("bearer_auth").is_some());
+    }
+}
@@ -1,25 +1,202 @@
 use anyhow::Result;
-use rusqlite::{Connection, params, OptionalExtension};
+use rusqlite::{Connection, OpenFlags, params, OptionalExtension};
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
 
+use crate::domain::entities::{NotificationChannel, NotificationTarget};
+use crate::domain::value_objects::{ApplTypeAliases, ApplTypeCategory, TokenizedCommand, TransferDirection};
+use crate::domain::value_objects::migration_difficulty::{EASY_MAX_SCORE, MEDIUM_MAX_SCORE};
 use crate::web::models::*;
 
+/// Default time-to-live for cached dashboard stats
+pub const DEFAULT_STATS_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Label used for jobs without an owner or run-as account
+pub const UNSPECIFIED_IDENTITY: &str = "(Unspecified)";
+
+/// Column headers of the search results CSV export
+const CSV_EXPORT_HEADER: [&str; 13] = [
+    "Job Name", "Folder", "Application", "Sub Application", "APPL_TYPE", "APPL_VER", "Task Type",
+    "Critical", "Cyclic", "Owner", "Priority", "Description", "Command Line",
+];
+
+/// One row of the search results CSV export, in `CSV_EXPORT_HEADER` order
+type CsvRow = (String, Option<String>, Option<String>, Option<String>, String, String, Option<String>, i32, i32, Option<String>, Option<String>, Option<String>, Option<String>);
+
+/// Maximum number of nodes returned by an application graph
+pub const MAX_APPLICATION_GRAPH_NODES: usize = 2000;
+
+/// Maximum number of edges returned by an application graph
+pub const MAX_APPLICATION_GRAPH_EDGES: usize = 10000;
+
+/// Jobs columns whose distinct values can be listed for type-ahead
+pub const DISTINCT_VALUE_COLUMNS: &[&str] = &[
+    "folder_name", "datacenter", "application", "sub_application", "appl_type", "appl_ver",
+    "owner", "run_as", "node_id", "task_type", "category", "priority", "memlib",
+    "timezone", "cm_ver", "scheduling_environment", "system_affinity",
+];
+
+/// Default number of distinct values returned
+pub const DEFAULT_DISTINCT_VALUES_LIMIT: u32 = 20;
+
+/// Maximum number of distinct values returned
+pub const MAX_DISTINCT_VALUES_LIMIT: u32 = 500;
+
+/// Job names bound per `IN (...)` query, below SQLite's variable limit
+const JOB_NAMES_BATCH_SIZE: usize = 500;
+
+/// Job ids bound per `IN (...)` query, below SQLite's variable limit
+const JOB_IDS_BATCH_SIZE: usize = 500;
+
+/// Maximum number of filter combinations whose dashboard stats are cached;
+/// the least recently used entry is evicted beyond it
+const MAX_STATS_CACHE_ENTRIES: usize = 64;
+
+/// SQL condition on the `jobs` alias `j` matching current job versions:
+/// only an explicit IS_CURRENT_VERSION="N" marks a historical one
+const CURRENT_VERSION_PREDICATE: &str = "(j.is_current_version IS NULL OR NOT UPPER(TRIM(j.is_current_version)) = 'N')";
+
+/// Cache key for dashboard stats: (folder filter, datacenter filter)
+type StatsCacheKey = (Option<String>, Option<String>);
+
+/// Graph node columns of a job: (id, job_name, folder_name, application, description)
+type GraphJobRow = (i64, String, String, Option<String>, Option<String>);
+
+/// Search filter as SQL: (full-text join, WHERE clause, bound parameters)
+type SearchFilterSql = (String, String, Vec<Box<dyn rusqlite::ToSql>>);
+
+/// Dashboard stats computed at a point in time
+struct CachedStats {
+    stats: DashboardStats,
+    computed_at: Instant,
+    /// Last time the entry was served or stored, for LRU eviction
+    last_used: Instant,
+    /// Database modification time when the stats were computed
+    db_modified: Option<SystemTime>,
+}
+
 pub struct JobRepository {
     conn: Arc<Mutex<Connection>>,
+    database_path: String,
+    stats_cache: Mutex<HashMap<StatsCacheKey, CachedStats>>,
+    stats_cache_ttl: Duration,
+    /// Number of times dashboard stats were computed from the database
+    stats_computations: AtomicUsize,
+    /// Groups raw appl_type values into categories for dashboard counts
+    appl_type_aliases: ApplTypeAliases,
+    /// Whether the database is opened read-only (e.g. a replica refreshed elsewhere)
+    read_only: bool,
 }
 
 impl JobRepository {
     pub fn new(database_path: &str) -> Result<Self> {
-        let conn = Connection::open(database_path)?;
+        Self::open(database_path, false)
+    }
+
+    /// Opens the database read-only
+    ///
+    /// Meant for replicas that are refreshed by another process: replace the
+    /// file atomically (write a copy, then rename it over the old one) and
+    /// call `reload` to pick up the new data.
+    ///
+    /// # Arguments
+    ///
+    /// * `database_path` - Path to the SQLite database
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file doesn't exist or isn't a database
+    pub fn open_read_only(database_path: &str) -> Result<Self> {
+        Self::open(database_path, true)
+    }
+
+    fn open(database_path: &str, read_only: bool) -> Result<Self> {
+        let conn = Self::open_connection(database_path, read_only)?;
         Ok(Self {
             conn: Arc::new(Mutex::new(conn)),
+            database_path: database_path.to_string(),
+            stats_cache: Mutex::new(HashMap::new()),
+            stats_cache_ttl: DEFAULT_STATS_CACHE_TTL,
+            stats_computations: AtomicUsize::new(0),
+            appl_type_aliases: ApplTypeAliases::default(),
+            read_only,
         })
     }
 
+    fn open_connection(database_path: &str, read_only: bool) -> Result<Connection> {
+        let conn = if read_only {
+            Connection::open_with_flags(database_path, OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX)?
+        } else {
+            Connection::open(database_path)?
+        };
+        // Fail on open rather than on the first query if the file isn't a database
+        conn.query_row("SELECT COUNT(*) FROM sqlite_master", [], |row| row.get::<_, i64>(0))?;
+        Ok(conn)
+    }
+
+    /// Re-opens the database file and swaps it in for the current connection
+    ///
+    /// The new connection is opened before the old one is replaced, so
+    /// requests see either the old or the new database, and a failed reload
+    /// keeps serving the old one. Cached dashboard stats are discarded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be opened
+    pub fn reload(&self) -> Result<()> {
+        let conn = Self::open_connection(&self.database_path, self.read_only)?;
+        *self.conn.lock().unwrap() = conn;
+        self.invalidate_stats_cache();
+        tracing::info!("🔄 Reloaded database {}", self.database_path);
+        Ok(())
+    }
+
+    /// Sets how long dashboard stats are served from cache (zero disables caching)
+    pub fn with_stats_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.stats_cache_ttl = ttl;
+        self
+    }
+
+    /// Sets the aliases used to decide which appl_type values count as file transfers
+    pub fn with_appl_type_aliases(mut self, appl_type_aliases: ApplTypeAliases) -> Self {
+        self.appl_type_aliases = appl_type_aliases;
+        self
+    }
+
+    /// Builds a SQL condition matching jobs whose appl_type falls in any of the categories
+    fn appl_type_category_condition(&self, categories: &[ApplTypeCategory]) -> String {
+        let raw_values: Vec<String> = categories.iter()
+            .flat_map(|c| self.appl_type_aliases.aliases_for(*c))
+            .map(|raw| format!("'{}'", raw.replace('\'', "''")))
+            .collect();
+        if raw_values.is_empty() {
+            "0".to_string()
+        } else {
+            format!("UPPER(TRIM(appl_type)) IN ({})", raw_values.join(", "))
+        }
+    }
+
+    /// Drops all cached dashboard stats so the next request recomputes them
+    ///
+    /// Call after importing or recomputing data in the database.
+    pub fn invalidate_stats_cache(&self) {
+        self.stats_cache.lock().unwrap().clear();
+        tracing::info!("🧹 [CACHE] Dashboard stats cache invalidated");
+    }
+
+    /// Latest modification time of the database file or its WAL file
+    fn database_modified(&self) -> Option<SystemTime> {
+        let wal_path = format!("{}-wal", self.database_path);
+        [self.database_path.as_str(), wal_path.as_str()].iter()
+            .filter_map(|path| std::fs::metadata(path).and_then(|m| m.modified()).ok())
+            .max()
+    }
+
     pub fn search_jobs(&self, request: &JobSearchRequest) -> Result<JobSearchResponse> {
-        tracing::info!("🔍 [SEARCH] Backend received search request");
-        tracing::debug!("[SEARCH] Request: job_name={:?}, folder={:?}, app={:?}, task_type={:?}", 
-                       request.job_name, request.folder_name, request.application, request.task_type);
+        tracing::trace!("[SEARCH] Request: {:?}", request);
+        let start = Instant::now();
         
         let conn = self.conn.lock().unwrap();
         
@@ -27,17 +204,29 @@ impl JobRepository {
         let per_page = request.per_page.unwrap_or(50);
         let offset = (page - 1) * per_page;
         
-        let (where_clause, params_vec) = self.build_where_clause(request);
-        tracing::info!("📋 [SEARCH] WHERE clause: {}", if where_clause.is_empty() { "(none)" } else { &where_clause });
-        tracing::debug!("[SEARCH] Params count: {}", params_vec.len());
+        let (where_clause, mut params_vec) = self.build_where_clause(&conn, request)?;
+        tracing::debug!("[SEARCH] WHERE clause: {}", if where_clause.is_empty() { "(none)" } else { &where_clause });
+
+        let (text_join, where_clause) = self.build_text_search(&conn, request, where_clause, &mut params_vec)?;
         
-        let (sort_by, sort_order) = self.get_sort_params(request);
+        let (mut sort_by, sort_order) = self.get_sort_params(&conn, request)?;
+        if !text_join.is_empty() && request.sort_by.is_none() {
+            // bm25() is lower for better matches
+            sort_by = "fts.score".to_string();
+        }
         
-        let total = self.count_total_jobs(&conn, &where_clause, &params_vec)?;
-        tracing::info!("✅ [SEARCH] Found {} total jobs matching criteria", total);
+        let total = self.count_total_jobs(&conn, &text_join, &where_clause, &params_vec)?;
+        let jobs = self.execute_search_query(&conn, &text_join, &where_clause, &params_vec, &sort_by, &sort_order, per_page, offset)?;
         
-        let jobs = self.execute_search_query(&conn, &where_clause, &params_vec, &sort_by, &sort_order, per_page, offset)?;
-        tracing::info!("📦 [SEARCH] Returning {} jobs for page {}", jobs.len(), page);
+        // One summary record per search; per-filter detail is at trace level
+        tracing::info!(
+            filters = params_vec.len(),
+            total,
+            returned = jobs.len(),
+            page,
+            elapsed_ms = start.elapsed().as_millis() as u64,
+            "🔍 [SEARCH] completed"
+        );
         
         let total_pages = (total + per_page - 1) / per_page;
         
@@ -51,8 +240,55 @@ impl JobRepository {
     }
     
 
-    fn build_where_clause(&self, request: &JobSearchRequest) -> (String, Vec<Box<dyn rusqlite::ToSql>>) {
-        tracing::debug!("🔨 [WHERE] Building WHERE clause for search");
+    /// Adds the free-text search of a request to the query
+    ///
+    /// Uses the `jobs_fts` index when the database has one, joined so the
+    /// other filters still apply. Databases exported before the index existed
+    /// fall back to substring matching (no ranking).
+    ///
+    /// # Returns
+    ///
+    /// The join to add after `FROM jobs j` (empty if none) and the updated
+    /// WHERE clause; the join's parameter is inserted before the filters'
+    fn build_text_search(
+        &self,
+        conn: &Connection,
+        request: &JobSearchRequest,
+        where_clause: String,
+        params_vec: &mut Vec<Box<dyn rusqlite::ToSql>>,
+    ) -> Result<(String, String)> {
+        let Some(fts_query) = request.search_text.as_deref().and_then(fts_match_query) else {
+            return Ok((String::new(), where_clause));
+        };
+
+        let has_fts: bool = conn.query_row(
+            "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE name = 'jobs_fts')",
+            [],
+            |row| row.get(0),
+        )?;
+        if has_fts {
+            tracing::trace!("  ➕ Adding full-text search: {}", fts_query);
+            params_vec.insert(0, Box::new(fts_query));
+            let join = "JOIN (SELECT rowid AS job_id, bm25(jobs_fts) AS score FROM jobs_fts WHERE jobs_fts MATCH ?) fts ON fts.job_id = j.id";
+            return Ok((join.to_string(), where_clause));
+        }
+
+        tracing::debug!("No jobs_fts table; falling back to LIKE search");
+        let text = request.search_text.as_deref().unwrap_or_default().trim();
+        let clause = "(j.job_name LIKE ? OR j.description LIKE ? OR j.cmdline LIKE ?)";
+        for _ in 0..3 {
+            params_vec.push(Box::new(format!("%{}%", text)));
+        }
+        let where_clause = if where_clause.is_empty() {
+            format!("WHERE {}", clause)
+        } else {
+            format!("{} AND {}", where_clause, clause)
+        };
+        Ok((String::new(), where_clause))
+    }
+
+    fn build_where_clause(&self, conn: &Connection, request: &JobSearchRequest) -> Result<(String, Vec<Box<dyn rusqlite::ToSql>>)> {
+        tracing::trace!("🔨 [WHERE] Building WHERE clause for search");
         let mut where_clauses = Vec::new();
         let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
         
@@ -60,11 +296,14 @@ impl JobRepository {
         self.apply_basic_filters(&mut where_clauses, &mut params_vec, request);
         self.apply_folder_filters(&mut where_clauses, &mut params_vec, request);
         self.apply_critical_filter(&mut where_clauses, &mut params_vec, request);
+        self.apply_identity_filters(&mut where_clauses, &mut params_vec, request);
         self.apply_dependency_filters(&mut where_clauses, &mut params_vec, request);
         self.apply_odate_filter(&mut where_clauses, request);
         self.apply_variable_filters(&mut where_clauses, &mut params_vec, request);
+        self.apply_wave_filter(&mut where_clauses, &mut params_vec, request, &Self::job_analysis_column(conn, "migration_wave")?);
+        self.apply_version_filter(&mut where_clauses, request);
         
-        self.format_where_clause(where_clauses, params_vec)
+        Ok(self.format_where_clause(where_clauses, params_vec))
     }
     
     fn apply_basic_filters(
@@ -73,8 +312,9 @@ impl JobRepository {
         params_vec: &mut Vec<Box<dyn rusqlite::ToSql>>,
         request: &JobSearchRequest
     ) {
-        self.add_string_filter_owned(where_clauses, params_vec, &request.job_name, "j.job_name LIKE ?", "LIKE", "job_name");
-        self.add_string_filter_owned(where_clauses, params_vec, &request.folder_name, "j.folder_name LIKE ?", "LIKE", "folder_name");
+        let match_mode = request.match_mode.unwrap_or_default();
+        self.add_name_filter(where_clauses, params_vec, &request.job_name, "j.job_name", match_mode, request.case_sensitive);
+        self.add_name_filter(where_clauses, params_vec, &request.folder_name, "j.folder_name", match_mode, request.case_sensitive);
         self.add_string_filter_owned(where_clauses, params_vec, &request.application, "j.application = ?", "=", "application");
         self.add_string_filter_owned(where_clauses, params_vec, &request.task_type, "j.task_type = ?", "=", "task_type");
         self.add_string_filter_owned(where_clauses, params_vec, &request.appl_type, "j.appl_type = ?", "=", "appl_type");
@@ -90,7 +330,7 @@ impl JobRepository {
         // Datacenter filter - use datacenter column from jobs table
         if let Some(ref datacenter) = request.datacenter {
             if !datacenter.is_empty() {
-                tracing::debug!("  ➕ Adding datacenter filter: {}", datacenter);
+                tracing::trace!("  ➕ Adding datacenter filter: {}", datacenter);
                 where_clauses.push("j.datacenter = ?".to_string());
                 params_vec.push(Box::new(datacenter.clone()));
             }
@@ -99,10 +339,11 @@ impl JobRepository {
         // Folder order method with special "(Empty)" handling
         if let Some(ref folder_order_method) = request.folder_order_method {
             if folder_order_method == "(Empty)" {
-                tracing::debug!("  ➕ Adding folder_order_method filter: (Empty) - searching for NULL or empty");
+                tracing::trace!("  ➕ Adding folder_order_method filter: (Empty) - searching for NULL or empty");
+                // Parsing normalizes empty attributes to NULL, but older databases hold ''
                 where_clauses.push("(f.folder_order_method IS NULL OR f.folder_order_method = '')".to_string());
             } else {
-                tracing::debug!("  ➕ Adding folder_order_method filter: {}", folder_order_method);
+                tracing::trace!("  ➕ Adding folder_order_method filter: {}", folder_order_method);
                 where_clauses.push("f.folder_order_method = ?".to_string());
                 params_vec.push(Box::new(folder_order_method.clone()));
             }
@@ -116,12 +357,32 @@ impl JobRepository {
         request: &JobSearchRequest
     ) {
         if let Some(critical) = request.critical {
-            tracing::debug!("  ➕ Adding critical filter: {}", critical);
+            tracing::trace!("  ➕ Adding critical filter: {}", critical);
             where_clauses.push("j.critical = ?".to_string());
             params_vec.push(Box::new(if critical { 1 } else { 0 }));
         }
     }
     
+    fn apply_identity_filters(
+        &self,
+        where_clauses: &mut Vec<String>,
+        params_vec: &mut Vec<Box<dyn rusqlite::ToSql>>,
+        request: &JobSearchRequest
+    ) {
+        for (value, column) in [(&request.owner, "j.owner"), (&request.run_as, "j.run_as")] {
+            if let Some(ref val) = value {
+                if val == UNSPECIFIED_IDENTITY {
+                    tracing::trace!("  ➕ Adding {} filter: (Unspecified) - searching for NULL", column);
+                    where_clauses.push(format!("{} IS NULL", column));
+                } else {
+                    tracing::trace!("  ➕ Adding {} filter: {}", column, val);
+                    where_clauses.push(format!("{} = ?", column));
+                    params_vec.push(Box::new(val.clone()));
+                }
+            }
+        }
+    }
+    
     fn apply_dependency_filters(
         &self,
         where_clauses: &mut Vec<String>,
@@ -130,15 +391,19 @@ impl JobRepository {
     ) {
         // In conditions (dependencies)
         self.add_count_filter_owned(where_clauses, params_vec, request.min_dependencies,
-            "(SELECT COUNT(*) FROM in_conditions WHERE in_conditions.job_id = j.id) >= ?", ">=", "min_dependencies", false);
+            "(SELECT COUNT(*) FROM in_conditions WHERE in_conditions.job_id = j.id) >= ?", ">=", "min_dependencies");
         self.add_count_filter_owned(where_clauses, params_vec, request.max_dependencies,
-            "(SELECT COUNT(*) FROM in_conditions WHERE in_conditions.job_id = j.id) <= ?", "<=", "max_dependencies", false);
+            "(SELECT COUNT(*) FROM in_conditions WHERE in_conditions.job_id = j.id) <= ?", "<=", "max_dependencies");
         
         // ON conditions
         self.add_count_filter_owned(where_clauses, params_vec, request.min_on_conditions,
-            "(SELECT COUNT(*) FROM on_conditions WHERE on_conditions.job_id = j.id) >= ?", ">=", "min_on_conditions", true);
+            "(SELECT COUNT(*) FROM on_conditions WHERE on_conditions.job_id = j.id) >= ?", ">=", "min_on_conditions");
         self.add_count_filter_owned(where_clauses, params_vec, request.max_on_conditions,
-            "(SELECT COUNT(*) FROM on_conditions WHERE on_conditions.job_id = j.id) <= ?", "<=", "max_on_conditions", true);
+            "(SELECT COUNT(*) FROM on_conditions WHERE on_conditions.job_id = j.id) <= ?", "<=", "max_on_conditions");
+
+        // Automatic reruns
+        self.add_count_filter_owned(where_clauses, params_vec, request.min_maxrerun,
+            "COALESCE(j.maxrerun, 0) >= ?", ">=", "min_maxrerun");
     }
     
     fn apply_odate_filter(
@@ -147,7 +412,7 @@ impl JobRepository {
         request: &JobSearchRequest
     ) {
         if let Some(has_odate) = request.has_odate {
-            tracing::debug!("  ➕ Adding has_odate filter: {}", has_odate);
+            tracing::trace!("  ➕ Adding has_odate filter: {}", has_odate);
             let clause = if has_odate {
                 "(EXISTS (SELECT 1 FROM in_conditions ic WHERE ic.job_id = j.id AND ic.odate IS NOT NULL AND ic.odate != '') OR EXISTS (SELECT 1 FROM out_conditions oc WHERE oc.job_id = j.id AND oc.odate IS NOT NULL AND oc.odate != ''))".to_string()
             } else {
@@ -165,7 +430,7 @@ impl JobRepository {
     ) {
         // Has variables boolean filter
         if let Some(has_vars) = request.has_variables {
-            tracing::debug!("  ➕ Adding has_variables filter: {}", has_vars);
+            tracing::trace!("  ➕ Adding has_variables filter: {}", has_vars);
             let clause = if has_vars {
                 "(SELECT COUNT(*) FROM job_variables WHERE job_id = j.id) > 0".to_string()
             } else {
@@ -176,7 +441,32 @@ impl JobRepository {
         
         // Minimum variables count
         self.add_count_filter_owned(where_clauses, params_vec, request.min_variables,
-            "(SELECT COUNT(*) FROM job_variables WHERE job_id = j.id) >= ?", ">=", "min_variables", false);
+            "(SELECT COUNT(*) FROM job_variables WHERE job_id = j.id) >= ?", ">=", "min_variables");
+    }
+    
+    fn apply_wave_filter(
+        &self,
+        where_clauses: &mut Vec<String>,
+        params_vec: &mut Vec<Box<dyn rusqlite::ToSql>>,
+        request: &JobSearchRequest,
+        wave_column: &str,
+    ) {
+        self.add_count_filter_owned(where_clauses, params_vec, request.migration_wave,
+            &format!("{} = ?", wave_column), "=", "migration_wave");
+    }
+    
+    fn apply_version_filter(
+        &self,
+        where_clauses: &mut Vec<String>,
+        request: &JobSearchRequest
+    ) {
+        // Exports keep every job version; false asks for all of them
+        if request.is_current_version.unwrap_or(true) {
+            tracing::trace!("  ➕ Adding is_current_version filter: current only");
+            where_clauses.push(CURRENT_VERSION_PREDICATE.to_string());
+        } else {
+            tracing::trace!("  ➕ No is_current_version filter: all versions");
+        }
     }
     
     fn format_where_clause(
@@ -185,11 +475,11 @@ impl JobRepository {
         params_vec: Vec<Box<dyn rusqlite::ToSql>>
     ) -> (String, Vec<Box<dyn rusqlite::ToSql>>) {
         let where_clause = if where_clauses.is_empty() {
-            tracing::info!("🔨 [WHERE] No filters applied - returning all jobs");
+            tracing::trace!("🔨 [WHERE] No filters applied - returning all jobs");
             String::new()
         } else {
             let clause = format!("WHERE {}", where_clauses.join(" AND "));
-            tracing::info!("🔨 [WHERE] Built clause with {} conditions: {}", where_clauses.len(), clause);
+            tracing::trace!("🔨 [WHERE] Built clause with {} conditions: {}", where_clauses.len(), clause);
             clause
         };
         
@@ -206,7 +496,7 @@ impl JobRepository {
         filter_name: &str,
     ) {
         if let Some(ref val) = value {
-            tracing::debug!("  ➕ Adding {} filter: {}", filter_name, val);
+            tracing::trace!("  ➕ Adding {} filter: {}", filter_name, val);
             if operator == "LIKE" {
                 clauses.push(column.to_string());
                 params.push(Box::new(format!("%{}%", val)));
@@ -217,6 +507,38 @@ impl JobRepository {
         }
     }
     
+    /// Adds a name filter honoring the requested match mode and case sensitivity
+    ///
+    /// SQLite's LIKE ignores ASCII case and `=` compares bytes, so a case
+    /// sensitive contains/starts-with match uses `instr` and a case
+    /// insensitive exact match uses `COLLATE NOCASE`. Wildcards in a
+    /// starts-with filter are escaped so `LOAD_` only matches a literal `_`.
+    fn add_name_filter(
+        &self,
+        clauses: &mut Vec<String>,
+        params: &mut Vec<Box<dyn rusqlite::ToSql>>,
+        value: &Option<String>,
+        column: &str,
+        match_mode: MatchMode,
+        case_sensitive: Option<bool>,
+    ) {
+        let Some(ref val) = value else { return };
+        tracing::trace!("  ➕ Adding {} filter ({:?}): {}", column, match_mode, val);
+        let (clause, param) = match (match_mode, case_sensitive) {
+            (MatchMode::Contains, Some(true)) => (format!("instr({}, ?) > 0", column), val.clone()),
+            (MatchMode::Contains, _) => (format!("{} LIKE ?", column), format!("%{}%", val)),
+            (MatchMode::StartsWith, Some(true)) => (format!("instr({}, ?) = 1", column), val.clone()),
+            (MatchMode::StartsWith, _) => {
+                let escaped = val.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+                (format!("{} LIKE ? ESCAPE '\\'", column), format!("{}%", escaped))
+            }
+            (MatchMode::Exact, Some(false)) => (format!("{} = ? COLLATE NOCASE", column), val.clone()),
+            (MatchMode::Exact, _) => (format!("{} = ?", column), val.clone()),
+        };
+        clauses.push(clause);
+        params.push(Box::new(param));
+    }
+
     fn add_count_filter_owned(
         &self,
         clauses: &mut Vec<String>,
@@ -225,37 +547,56 @@ impl JobRepository {
         table: &str,
         operator: &str,
         filter_name: &str,
-        use_info_log: bool,
     ) {
         if let Some(val) = value {
-            if use_info_log {
-                tracing::info!("  ➕ Adding {} filter: {} {}", filter_name, operator, val);
-            } else {
-                tracing::debug!("  ➕ Adding {} filter: {} {}", filter_name, operator, val);
-            }
+            tracing::trace!("  ➕ Adding {} filter: {} {}", filter_name, operator, val);
             clauses.push(table.to_string());
             params.push(Box::new(val));
         }
     }
     
-    fn get_sort_params(&self, request: &JobSearchRequest) -> (String, String) {
-        let sort_by = request.sort_by.as_deref().unwrap_or("job_name").to_string();
+    /// Resolves the requested sort into an ORDER BY expression and direction
+    ///
+    /// Only allowlisted columns are accepted because the expression is
+    /// interpolated into SQL; anything else sorts by job name.
+    fn get_sort_params(&self, conn: &Connection, request: &JobSearchRequest) -> Result<(String, String)> {
+        let requested = request.sort_by.as_deref().unwrap_or("job_name");
+        let sort_by = match requested {
+            "job_name" | "folder_name" | "datacenter" | "application" | "sub_application"
+            | "appl_type" | "appl_ver" | "task_type" | "critical" | "cyclic" | "node_id"
+            | "job_group" | "memname" | "owner" | "run_as" | "maxwait" | "maxrerun" | "shift" => {
+                format!("j.{}", requested)
+            }
+            "folder_order_method" => "f.folder_order_method".to_string(),
+            // Priority is stored as text; sort by its parsed numeric value instead
+            "priority" => Self::priority_column(conn)?.to_string(),
+            "complexity_score" => Self::job_analysis_column(conn, "complexity_score")?,
+            // Selected column alias, see execute_search_query
+            "total_dependencies_e2e" => "total_dependencies_e2e".to_string(),
+            // Selected column alias, see execute_search_query
+            "dependency_depth" => "dependency_depth".to_string(),
+            other => {
+                tracing::debug!("Ignoring unsupported sort column '{}'", other);
+                "j.job_name".to_string()
+            }
+        };
         let sort_order = match request.sort_order {
             Some(SortOrder::Desc) => "DESC".to_string(),
             _ => "ASC".to_string(),
         };
-        (sort_by, sort_order)
+        Ok((sort_by, sort_order))
     }
     
     fn count_total_jobs(
         &self,
         conn: &rusqlite::Connection,
+        text_join: &str,
         where_clause: &str,
         params_vec: &[Box<dyn rusqlite::ToSql>]
     ) -> Result<u32> {
-        let count_query = format!("SELECT COUNT(*) FROM jobs j LEFT JOIN folders f ON j.folder_name = f.folder_name AND j.datacenter = f.datacenter {}", where_clause);
-        tracing::info!("🔢 [COUNT] Executing count query: {}", count_query);
-        tracing::debug!("🔢 [COUNT] With {} parameters", params_vec.len());
+        let count_query = format!("SELECT COUNT(*) FROM jobs j {} LEFT JOIN folders f ON j.folder_name = f.folder_name AND j.datacenter = f.datacenter {}", text_join, where_clause);
+        tracing::trace!("🔢 [COUNT] Executing count query: {}", count_query);
+        tracing::trace!("🔢 [COUNT] With {} parameters", params_vec.len());
         
         let start = std::time::Instant::now();
         let total: u32 = conn.query_row(
@@ -268,13 +609,68 @@ impl JobRepository {
         })?;
         
         let duration = start.elapsed();
-        tracing::info!("✅ [COUNT] Query completed in {:?}, found {} jobs", duration, total);
+        tracing::trace!("✅ [COUNT] Query completed in {:?}, found {} jobs", duration, total);
         Ok(total)
     }
     
+    /// Gets the expression selecting a job's dependency depth
+    ///
+    /// Databases exported before `jobs.dependency_depth` existed read as 0
+    /// instead of failing every query.
+    fn dependency_depth_column(conn: &Connection) -> Result<&'static str> {
+        Ok(if Self::jobs_column_exists(conn, "dependency_depth")? { "j.dependency_depth" } else { "0" })
+    }
+
+    /// Gets the expression selecting a job's numeric priority
+    ///
+    /// Databases exported before `jobs.priority_num` existed parse the raw
+    /// priority text instead, NULL unless it is all digits, as
+    /// `Job::priority_value` does.
+    fn priority_column(conn: &Connection) -> Result<&'static str> {
+        Ok(if Self::jobs_column_exists(conn, "priority_num")? {
+            "j.priority_num"
+        } else {
+            "(CASE WHEN TRIM(j.priority) GLOB '[0-9]*' AND TRIM(j.priority) NOT GLOB '*[^0-9]*' \
+              THEN CAST(TRIM(j.priority) AS INTEGER) END)"
+        })
+    }
+
+    /// Checks whether the `jobs` table has a column
+    fn jobs_column_exists(conn: &Connection, column: &str) -> Result<bool> {
+        let exists = conn.query_row(
+            "SELECT EXISTS (SELECT 1 FROM pragma_table_info('jobs') WHERE name = ?)",
+            [column],
+            |row| row.get(0),
+        )?;
+        Ok(exists)
+    }
+
+    /// Gets the expression selecting a job's value from `job_analysis`
+    ///
+    /// Databases exported before `job_analysis` existed read as NULL
+    /// instead of failing every query.
+    fn job_analysis_column(conn: &Connection, column: &str) -> Result<String> {
+        Ok(if Self::table_exists(conn, "job_analysis")? {
+            format!("(SELECT ja.{} FROM job_analysis ja WHERE ja.job_id = j.id)", column)
+        } else {
+            "NULL".to_string()
+        })
+    }
+
+    /// Checks whether the database has a table
+    fn table_exists(conn: &Connection, table: &str) -> Result<bool> {
+        let exists = conn.query_row(
+            "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?)",
+            [table],
+            |row| row.get(0),
+        )?;
+        Ok(exists)
+    }
+
     fn execute_search_query(
         &self,
         conn: &rusqlite::Connection,
+        text_join: &str,
         where_clause: &str,
         params_vec: &[Box<dyn rusqlite::ToSql>],
         sort_by: &String,
@@ -317,14 +713,17 @@ impl JobRepository {
                 (SELECT COUNT(*) FROM on_conditions WHERE job_id = j.id) as on_cond_count,
                 (SELECT COUNT(*) FROM control_resources WHERE job_id = j.id) as ctrl_res_count,
                 (SELECT COUNT(*) FROM job_variables WHERE job_id = j.id) as var_count,
-                0 as total_dependencies_e2e
+                COALESCE({}, 0) as total_dependencies_e2e,
+                {} as dependency_depth
             FROM jobs j
+            {}
             LEFT JOIN folders f ON j.folder_name = f.folder_name AND j.datacenter = f.datacenter
             {}
             ORDER BY {} {}
             LIMIT ? OFFSET ?
             "#,
-            where_clause, sort_by, sort_order
+            Self::job_analysis_column(conn, "total_dependencies_e2e")?, Self::dependency_depth_column(conn)?,
+            text_join, where_clause, sort_by, sort_order
         );
         
         let mut stmt = conn.prepare(&query)?;
@@ -463,6 +862,7 @@ impl JobRepository {
             control_resources_count: row.get(114)?,
             variables_count: row.get(115)?,
             total_dependencies_e2e: row.get(116)?,
+            dependency_depth: row.get(117)?,
         })
     }
 
@@ -470,7 +870,7 @@ impl JobRepository {
         let conn = self.conn.lock().unwrap();
         
         let job: Option<JobDetail> = conn.query_row(
-            r#"
+            &format!(r#"
             SELECT 
                 j.id, j.job_name, j.folder_name,
                 j.datacenter, f.folder_order_method,
@@ -504,11 +904,12 @@ impl JobRepository {
                 (SELECT COUNT(*) FROM on_conditions WHERE job_id = j.id),
                 (SELECT COUNT(*) FROM control_resources WHERE job_id = j.id),
                 (SELECT COUNT(*) FROM job_variables WHERE job_id = j.id),
-                0 as total_dependencies_e2e
+                COALESCE({}, 0) as total_dependencies_e2e,
+                {} as dependency_depth
             FROM jobs j
             LEFT JOIN folders f ON j.folder_name = f.folder_name AND j.datacenter = f.datacenter
             WHERE j.id = ?
-            "#,
+            "#, Self::job_analysis_column(&conn, "total_dependencies_e2e")?, Self::dependency_depth_column(&conn)?),
             params![job_id],
             Self::map_row_to_job_detail,
         ).optional()?;
@@ -523,9 +924,11 @@ impl JobRepository {
             let variables = self.get_variables(&conn, job_id)?;
             let auto_edits = self.get_auto_edits(&conn, job_id)?;
             let metadata = self.get_metadata(&conn, job_id)?;
+            let cmdline_tokens = self.get_cmdline_tokens(&conn, job_id);
             
             Ok(Some(JobDetailFull {
                 job,
+                cmdline_tokens,
                 scheduling,
                 in_conditions,
                 out_conditions,
@@ -541,20 +944,67 @@ impl JobRepository {
         }
     }
 
-    fn get_job_scheduling(&self, conn: &Connection, job_id: i64) -> Result<Option<JobScheduling>> {
-        conn.query_row(
-            "SELECT time_from, time_to, days_calendar, weeks_calendar, conf_calendar FROM job_scheduling WHERE job_id = ?",
+    /// Gets the analysis profile stored with the job analysis
+    ///
+    /// # Returns
+    ///
+    /// The stored profile, or None when the database predates profiles
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails or the stored JSON is invalid
+    pub fn get_analysis_profile(&self) -> Result<Option<StoredAnalysisProfile>> {
+        let conn = self.conn.lock().unwrap();
+        let has_table: bool = conn.query_row(
+            "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'analysis_profile')",
+            [],
+            |row| row.get(0),
+        )?;
+        if !has_table {
+            return Ok(None);
+        }
+
+        let row: Option<(String, String, String)> = conn.query_row(
+            "SELECT profile_json, fingerprint, stored_at FROM analysis_profile WHERE id = 1",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        ).optional()?;
+        match row {
+            Some((profile_json, fingerprint, stored_at)) => Ok(Some(StoredAnalysisProfile {
+                profile: serde_json::from_str(&profile_json)?,
+                fingerprint,
+                stored_at,
+            })),
+            None => Ok(None),
+        }
+    }
+
+    /// Reads the tokenized command line stored at export
+    ///
+    /// Returns None when tokens weren't stored or the database predates the column.
+    fn get_cmdline_tokens(&self, conn: &Connection, job_id: i64) -> Option<TokenizedCommand> {
+        let tokens: Option<String> = conn.query_row(
+            "SELECT cmdline_tokens FROM jobs WHERE id = ?",
             params![job_id],
-            |row| {
-                Ok(JobScheduling {
-                    time_from: row.get(0)?,
-                    time_to: row.get(1)?,
-                    days_calendar: row.get(2)?,
-                    weeks_calendar: row.get(3)?,
-                    conf_calendar: row.get(4)?,
-                })
-            },
-        ).optional().map_err(Into::into)
+            |row| row.get(0),
+        ).ok()?;
+        tokens.and_then(|json| serde_json::from_str(&json).ok())
+    }
+
+    fn get_job_scheduling(&self, conn: &Connection, job_id: i64) -> Result<Vec<JobScheduling>> {
+        let mut stmt = conn.prepare(
+            "SELECT time_from, time_to, days_calendar, weeks_calendar, conf_calendar FROM job_scheduling WHERE job_id = ? ORDER BY id"
+        )?;
+        let scheduling = stmt.query_map(params![job_id], |row| {
+            Ok(JobScheduling {
+                time_from: row.get(0)?,
+                time_to: row.get(1)?,
+                days_calendar: row.get(2)?,
+                weeks_calendar: row.get(3)?,
+                conf_calendar: row.get(4)?,
+            })
+        })?.collect::<Result<Vec<_>, _>>()?;
+        Ok(scheduling)
     }
 
     fn get_in_conditions(&self, conn: &Connection, job_id: i64) -> Result<Vec<Condition>> {
@@ -564,18 +1014,20 @@ impl JobRepository {
                 condition_name: row.get(0)?,
                 odate: row.get(1)?,
                 and_or: row.get(2)?,
+                sign: None,
             })
         })?.collect::<Result<Vec<_>, _>>()?;
         Ok(conditions)
     }
 
     fn get_out_conditions(&self, conn: &Connection, job_id: i64) -> Result<Vec<Condition>> {
-        let mut stmt = conn.prepare("SELECT condition_name, odate, NULL FROM out_conditions WHERE job_id = ?")?;
+        let mut stmt = conn.prepare("SELECT condition_name, odate, sign FROM out_conditions WHERE job_id = ?")?;
         let conditions = stmt.query_map(params![job_id], |row| {
             Ok(Condition {
                 condition_name: row.get(0)?,
                 odate: row.get(1)?,
-                and_or: row.get(2)?,
+                and_or: None,
+                sign: row.get(2)?,
             })
         })?.collect::<Result<Vec<_>, _>>()?;
         Ok(conditions)
@@ -873,6 +1325,44 @@ impl JobRepository {
     }
 
     pub fn get_dashboard_stats(&self, folder_filter: Option<&str>, datacenter_filter: Option<&str>) -> Result<DashboardStats> {
+        if self.stats_cache_ttl.is_zero() {
+            return self.compute_dashboard_stats(folder_filter, datacenter_filter);
+        }
+
+        let key: StatsCacheKey = (folder_filter.map(String::from), datacenter_filter.map(String::from));
+        let db_modified = self.database_modified();
+
+        if let Some(cached) = self.stats_cache.lock().unwrap().get_mut(&key) {
+            if cached.computed_at.elapsed() < self.stats_cache_ttl && cached.db_modified == db_modified {
+                tracing::debug!("⚡ [CACHE] Serving cached dashboard stats for {:?}", key);
+                cached.last_used = Instant::now();
+                return Ok(cached.stats.clone());
+            }
+        }
+
+        let stats = self.compute_dashboard_stats(folder_filter, datacenter_filter)?;
+        let mut cache = self.stats_cache.lock().unwrap();
+        cache.retain(|_, cached| cached.computed_at.elapsed() < self.stats_cache_ttl);
+        if cache.len() >= MAX_STATS_CACHE_ENTRIES && !cache.contains_key(&key) {
+            let least_recent = cache.iter()
+                .min_by_key(|(_, cached)| cached.last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(least_recent) = least_recent {
+                cache.remove(&least_recent);
+            }
+        }
+        let now = Instant::now();
+        cache.insert(key, CachedStats {
+            stats: stats.clone(),
+            computed_at: now,
+            last_used: now,
+            db_modified,
+        });
+        Ok(stats)
+    }
+
+    fn compute_dashboard_stats(&self, folder_filter: Option<&str>, datacenter_filter: Option<&str>) -> Result<DashboardStats> {
+        self.stats_computations.fetch_add(1, Ordering::Relaxed);
         let conn = self.conn.lock().unwrap();
         
         // Build WHERE clause based on filters
@@ -921,8 +1411,9 @@ impl JobRepository {
             |row| row.get(0)
         )?;
         
+        let file_transfer_condition = self.appl_type_category_condition(&[ApplTypeCategory::FileTransfer, ApplTypeCategory::FileWatch]);
         let file_transfer_jobs: u32 = conn.query_row(
-            &format!("SELECT COUNT(*) FROM jobs WHERE (appl_type = 'FILE_TRANS' OR appl_type = 'FileWatch') AND ({})", where_clause),
+            &format!("SELECT COUNT(*) FROM jobs WHERE ({}) AND ({})", file_transfer_condition, where_clause),
             [],
             |row| row.get(0)
         )?;
@@ -965,6 +1456,13 @@ impl JobRepository {
             })
         })?.collect::<Result<Vec<_>, _>>()?;
         
+        let jobs_by_owner = Self::query_identity_stats(&conn, "owner", &where_clause)?;
+        let jobs_by_run_as = Self::query_identity_stats(&conn, "run_as", &where_clause)?;
+        let jobs_by_notification_target = Self::query_notification_target_stats(&conn, &where_clause)?;
+        let jobs_by_datacenter = Self::query_datacenter_stats(&conn, &where_clause)?;
+        let complexity_distribution = Self::query_complexity_distribution(&conn, &where_clause)?;
+        let most_connected_jobs = Self::query_most_connected_jobs(&conn, &where_clause)?;
+        
         Ok(DashboardStats {
             total_jobs,
             total_folders,
@@ -976,9 +1474,238 @@ impl JobRepository {
             jobs_by_folder,
             jobs_by_task_type,
             jobs_by_appl_type,
+            jobs_by_owner,
+            jobs_by_run_as,
+            most_connected_jobs,
+            jobs_by_notification_target,
+            jobs_by_datacenter,
+            complexity_distribution,
         })
     }
 
+    /// Get the number of analyzed jobs per complexity bucket
+    ///
+    /// Uses the complexity scores stored in `job_analysis` at export, with
+    /// the thresholds of the HTML report's difficulty levels.
+    ///
+    /// # Returns
+    ///
+    /// Result containing the low (0-30), medium (31-60) and high (61+)
+    /// counts; jobs without a stored score are not counted
+    pub fn get_complexity_distribution(&self) -> Result<ComplexityDistribution> {
+        let conn = self.conn.lock().unwrap();
+        Self::query_complexity_distribution(&conn, "1=1")
+    }
+
+    /// Gets the jobs matching the WHERE clause with the most end-to-end dependencies
+    ///
+    /// Databases exported before `job_analysis` existed have none.
+    fn query_most_connected_jobs(conn: &Connection, where_clause: &str) -> Result<Vec<ConnectedJobStat>> {
+        if !Self::table_exists(conn, "job_analysis")? {
+            return Ok(Vec::new());
+        }
+        let mut stmt = conn.prepare(&format!(
+            "SELECT j.id, j.job_name, j.folder_name, ja.total_dependencies_e2e \
+             FROM jobs j JOIN job_analysis ja ON ja.job_id = j.id \
+             WHERE ja.total_dependencies_e2e > 0 AND j.id IN (SELECT id FROM jobs WHERE {}) \
+             ORDER BY ja.total_dependencies_e2e DESC, j.job_name LIMIT 50",
+            where_clause
+        ))?;
+        let most_connected_jobs = stmt.query_map([], |row| {
+            Ok(ConnectedJobStat {
+                job_id: row.get(0)?,
+                job_name: row.get(1)?,
+                folder_name: row.get(2)?,
+                total_dependencies_e2e: row.get(3)?,
+            })
+        })?.collect::<Result<Vec<_>, _>>()?;
+        Ok(most_connected_jobs)
+    }
+
+    /// Counts the jobs matching the WHERE clause per complexity bucket
+    ///
+    /// Databases exported before `job_analysis` existed count no jobs.
+    fn query_complexity_distribution(conn: &Connection, where_clause: &str) -> Result<ComplexityDistribution> {
+        if !Self::table_exists(conn, "job_analysis")? {
+            return Ok(ComplexityDistribution::default());
+        }
+        let distribution = conn.query_row(
+            &format!(
+                "SELECT COALESCE(SUM(CASE WHEN complexity_score <= ?1 THEN 1 ELSE 0 END), 0), \
+                        COALESCE(SUM(CASE WHEN complexity_score > ?1 AND complexity_score <= ?2 THEN 1 ELSE 0 END), 0), \
+                        COALESCE(SUM(CASE WHEN complexity_score > ?2 THEN 1 ELSE 0 END), 0) \
+                 FROM job_analysis WHERE job_id IN (SELECT id FROM jobs WHERE {})",
+                where_clause
+            ),
+            params![EASY_MAX_SCORE, MEDIUM_MAX_SCORE],
+            |row| Ok(ComplexityDistribution {
+                low: row.get(0)?,
+                medium: row.get(1)?,
+                high: row.get(2)?,
+            }),
+        )?;
+        Ok(distribution)
+    }
+
+    /// Counts jobs, critical jobs and cyclic jobs per datacenter, largest first
+    ///
+    /// The datacenter comes from the job's folder; jobs whose folder has no
+    /// datacenter are grouped under "(Unspecified)".
+    fn query_datacenter_stats(conn: &Connection, where_clause: &str) -> Result<Vec<DatacenterStat>> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT COALESCE(f.datacenter, '{}') AS dc, COUNT(*) AS count, \
+                    SUM(CASE WHEN j.critical = 1 THEN 1 ELSE 0 END), \
+                    SUM(CASE WHEN j.cyclic = 1 THEN 1 ELSE 0 END) \
+             FROM jobs j LEFT JOIN folders f ON j.folder_name = f.folder_name AND j.datacenter IS f.datacenter \
+             WHERE j.id IN (SELECT id FROM jobs WHERE {}) \
+             GROUP BY f.datacenter ORDER BY count DESC, dc",
+            UNSPECIFIED_IDENTITY, where_clause
+        ))?;
+        let stats = stmt.query_map([], |row| {
+            Ok(DatacenterStat {
+                datacenter: row.get(0)?,
+                job_count: row.get(1)?,
+                critical_jobs: row.get(2)?,
+                cyclic_jobs: row.get(3)?,
+            })
+        })?.collect::<Result<Vec<_>, _>>()?;
+        Ok(stats)
+    }
+
+    /// Counts jobs per DOMAIL/DOSHOUT recipient, largest first
+    ///
+    /// DEST values listing several recipients are split so each recipient
+    /// is counted on its own; a job counts once per recipient.
+    fn query_notification_target_stats(conn: &Connection, where_clause: &str) -> Result<Vec<NotificationTargetStat>> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT DISTINCT oc.job_id, da.action_type, da.action_value \
+             FROM do_actions da JOIN on_conditions oc ON oc.id = da.on_condition_id \
+             WHERE da.action_type IN ('Mail', 'Shout') AND oc.job_id IN (SELECT id FROM jobs WHERE {})",
+            where_clause
+        ))?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?))
+        })?.collect::<Result<Vec<_>, _>>()?;
+
+        let mut jobs_by_target: HashMap<NotificationTarget, std::collections::HashSet<i64>> = HashMap::new();
+        for (job_id, action_type, dest) in rows {
+            let channel = if action_type == "Mail" { NotificationChannel::Mail } else { NotificationChannel::Shout };
+            for target in NotificationTarget::parse_dest(channel, &dest) {
+                jobs_by_target.entry(target).or_default().insert(job_id);
+            }
+        }
+
+        let mut stats: Vec<NotificationTargetStat> = jobs_by_target.into_iter()
+            .map(|(target, jobs)| NotificationTargetStat {
+                destination: target.destination,
+                channel: target.channel.as_str().to_string(),
+                job_count: jobs.len() as u32,
+            })
+            .collect();
+        stats.sort_by(|a, b| b.job_count.cmp(&a.job_count)
+            .then_with(|| a.destination.cmp(&b.destination))
+            .then_with(|| a.channel.cmp(&b.channel)));
+        stats.truncate(50);
+        Ok(stats)
+    }
+
+    /// Counts jobs per value of an identity column (owner or run_as), largest first
+    fn query_identity_stats(conn: &Connection, column: &str, where_clause: &str) -> Result<Vec<IdentityStat>> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT COALESCE({}, '{}'), COUNT(*) as count FROM jobs WHERE {} GROUP BY {} ORDER BY count DESC LIMIT 50",
+            column, UNSPECIFIED_IDENTITY, where_clause, column
+        ))?;
+        let stats = stmt.query_map([], |row| {
+            Ok(IdentityStat {
+                name: row.get(0)?,
+                count: row.get(1)?,
+            })
+        })?.collect::<Result<Vec<_>, _>>()?;
+        Ok(stats)
+    }
+
+    /// Lists the distinct values of a jobs column with their job counts
+    ///
+    /// Only columns in `DISTINCT_VALUE_COLUMNS` can be listed, since the
+    /// column name is interpolated into the query.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - Column to list
+    /// * `prefix` - Optional case-insensitive prefix the values must start with
+    /// * `limit` - Maximum number of values (capped at `MAX_DISTINCT_VALUES_LIMIT`)
+    ///
+    /// # Returns
+    ///
+    /// Non-empty values, most frequent first
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the column is not in the allowlist or the query fails
+    pub fn get_distinct_values(&self, column: &str, prefix: Option<&str>, limit: Option<u32>) -> Result<Vec<DistinctValue>> {
+        if !DISTINCT_VALUE_COLUMNS.contains(&column) {
+            anyhow::bail!("Column '{}' is not available; expected one of: {}", column, DISTINCT_VALUE_COLUMNS.join(", "));
+        }
+        let limit = limit.unwrap_or(DEFAULT_DISTINCT_VALUES_LIMIT).min(MAX_DISTINCT_VALUES_LIMIT);
+        let pattern = format!(
+            "{}%",
+            prefix.unwrap_or_default().replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+        );
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {column}, COUNT(*) AS count FROM jobs \
+             WHERE {column} IS NOT NULL AND {column} != '' AND {column} LIKE ?1 ESCAPE '\\' \
+             GROUP BY {column} ORDER BY count DESC, {column} LIMIT ?2",
+            column = column
+        ))?;
+        let values = stmt.query_map(params![pattern, limit], |row| {
+            Ok(DistinctValue {
+                value: row.get(0)?,
+                count: row.get(1)?,
+            })
+        })?.collect::<Result<Vec<_>, _>>()?;
+        Ok(values)
+    }
+
+    /// Gets all jobs whose name is in a list, e.g. to cross-reference an
+    /// external inventory
+    ///
+    /// Names are matched exactly, in batches of `JOB_NAMES_BATCH_SIZE`.
+    ///
+    /// # Arguments
+    ///
+    /// * `names` - Job names to look up; duplicates are ignored
+    ///
+    /// # Returns
+    ///
+    /// The matching jobs ordered by name, and the names without a match
+    pub fn get_jobs_by_names(&self, names: &[String]) -> Result<JobsByNamesResponse> {
+        let mut seen: HashSet<&str> = HashSet::new();
+        let unique: Vec<&String> = names.iter().filter(|name| seen.insert(name.as_str())).collect();
+
+        let conn = self.conn.lock().unwrap();
+        let mut jobs = Vec::new();
+        for batch in unique.chunks(JOB_NAMES_BATCH_SIZE) {
+            let where_clause = format!("WHERE j.job_name IN ({})", vec!["?"; batch.len()].join(", "));
+            let params_vec: Vec<Box<dyn rusqlite::ToSql>> = batch.iter()
+                .map(|name| Box::new((*name).clone()) as Box<dyn rusqlite::ToSql>)
+                .collect();
+            jobs.extend(self.execute_search_query(
+                &conn, "", &where_clause, &params_vec,
+                &"j.job_name".to_string(), &"ASC".to_string(), u32::MAX, 0,
+            )?);
+        }
+        jobs.sort_by(|a, b| a.job_name.cmp(&b.job_name).then(a.id.cmp(&b.id)));
+
+        let found: HashSet<&str> = jobs.iter().map(|job| job.job_name.as_str()).collect();
+        let missing = unique.into_iter()
+            .filter(|name| !found.contains(name.as_str()))
+            .cloned()
+            .collect();
+        Ok(JobsByNamesResponse { jobs, missing })
+    }
+
     pub fn get_filter_options(&self, datacenter_filter: Option<&str>) -> Result<FilterOptions> {
         let conn = self.conn.lock().unwrap();
         
@@ -1071,32 +1798,111 @@ impl JobRepository {
         })
     }
     
+    /// Exports the jobs matching a search as a CSV string
+    ///
+    /// Buffers the whole export; prefer `write_search_csv` for large results.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - Search filters
+    ///
+    /// # Returns
+    ///
+    /// The CSV text, header included
     pub fn export_search_to_csv(&self, request: &JobSearchRequest) -> Result<String> {
-        let conn = self.conn.lock().unwrap();
+        let mut buffer = Vec::new();
+        self.write_search_csv(request, &mut buffer)?;
+        Ok(String::from_utf8(buffer)?)
+    }
+
+    /// Entity tag identifying the bytes of a CSV export
+    ///
+    /// Exports are ordered deterministically, so the same filters against an
+    /// unchanged database file produce the same bytes; the tag is derived
+    /// from both and changes when either does (e.g. after a reload).
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - Search filters
+    ///
+    /// # Returns
+    ///
+    /// A quoted strong entity tag, or None if the database file can't be stat'ed
+    pub fn csv_export_etag(&self, request: &JobSearchRequest) -> Option<String> {
+        use std::hash::{Hash, Hasher};
+
+        let modified = self.database_modified()?.duration_since(SystemTime::UNIX_EPOCH).ok()?;
+        let filters = serde_json::to_string(request).ok()?;
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        (modified, filters).hash(&mut hasher);
+        Some(format!("\"{:016x}\"", hasher.finish()))
+    }
+
+    /// Streams the jobs matching a search as CSV into a sink
+    ///
+    /// Rows are written as they are read from the database, so memory use
+    /// does not grow with the result size. csv::Writer applies RFC 4180
+    /// quoting to every field. The export reads through its own read-only
+    /// connection, so a sink that blocks (e.g. a slow download) doesn't hold
+    /// up other requests.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - Search filters
+    /// * `sink` - Destination of the CSV bytes
+    ///
+    /// # Returns
+    ///
+    /// Number of job rows written
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails or the sink rejects a write
+    /// (e.g. the client disconnected)
+    pub fn write_search_csv<W: std::io::Write>(&self, request: &JobSearchRequest, sink: W) -> Result<usize> {
+        let conn = Self::open_connection(&self.database_path, true)?;
         
         tracing::info!("📥 [CSV_EXPORT] Starting CSV export with request: {:?}", request);
         
-        let (where_clause, params_vec) = self.build_csv_where_clause(request);
+        let (text_join, where_clause, params_vec) = self.build_csv_where_clause(&conn, request)?;
         tracing::info!("🔍 [CSV_EXPORT] WHERE clause: '{}'", where_clause);
         tracing::info!("🔍 [CSV_EXPORT] Parameters count: {}", params_vec.len());
         
-        let query = self.build_csv_query(&where_clause);
+        let query = self.build_csv_query(&text_join, &where_clause);
         tracing::info!("📝 [CSV_EXPORT] Final query: {}", query);
         
-        let rows = self.execute_csv_query(&conn, &query, &params_vec)?;
-        tracing::info!("📊 [CSV_EXPORT] Query returned {} rows", rows.len());
+        let mut stmt = conn.prepare(&query)?;
+        let rows = stmt.query_map(
+            rusqlite::params_from_iter(params_vec.iter().map(|p| p.as_ref())),
+            Self::csv_row,
+        )?;
         
-        let csv_output = self.format_csv_output(rows)?;
+        let mut wtr = csv::Writer::from_writer(sink);
+        wtr.write_record(CSV_EXPORT_HEADER)?;
+        let mut written = 0;
+        for row in rows {
+            let row = row.map_err(|e| anyhow::anyhow!("CSV query error: {}", e))?;
+            wtr.write_record(Self::csv_record(row))?;
+            written += 1;
+        }
+        wtr.flush()?;
+        tracing::info!(rows = written, "📊 [CSV_EXPORT] export completed");
         
-        Ok(csv_output)
+        Ok(written)
     }
     
-    fn build_csv_where_clause(&self, request: &JobSearchRequest) -> (String, Vec<Box<dyn rusqlite::ToSql>>) {
-        // Use the same comprehensive filtering logic as search_jobs
-        self.build_where_clause(request)
+    /// Builds the filters of a CSV export, as `search_jobs` does
+    ///
+    /// # Returns
+    ///
+    /// The full-text search join, the WHERE clause and their parameters
+    fn build_csv_where_clause(&self, conn: &Connection, request: &JobSearchRequest) -> Result<SearchFilterSql> {
+        let (where_clause, mut params_vec) = self.build_where_clause(conn, request)?;
+        let (text_join, where_clause) = self.build_text_search(conn, request, where_clause, &mut params_vec)?;
+        Ok((text_join, where_clause, params_vec))
     }
     
-    fn build_csv_query(&self, where_clause: &str) -> String {
+    fn build_csv_query(&self, text_join: &str, where_clause: &str) -> String {
         format!(
             r#"
             SELECT 
@@ -1104,77 +1910,38 @@ impl JobRepository {
                 COALESCE(j.appl_type, '') as appl_type, COALESCE(j.appl_ver, '') as appl_ver,
                 j.task_type, j.critical, j.cyclic, j.owner, j.priority,
                 j.description, j.cmdline
-            FROM jobs j
+            FROM jobs j {}
             LEFT JOIN folders f ON j.folder_name = f.folder_name AND j.datacenter = f.datacenter
             {}
-            ORDER BY j.job_name
+            ORDER BY j.job_name, j.id
             "#,
-            where_clause
+            text_join, where_clause
         )
     }
     
-    fn execute_csv_query(
-        &self,
-        conn: &rusqlite::Connection,
-        query: &str,
-        params_vec: &[Box<dyn rusqlite::ToSql>],
-    ) -> Result<Vec<(String, Option<String>, Option<String>, Option<String>, String, String, Option<String>, i32, i32, Option<String>, Option<String>, Option<String>, Option<String>)>> {
-        tracing::info!("🔍 [CSV_QUERY] Executing query: {}", query);
-        tracing::info!("🔍 [CSV_QUERY] With {} parameters", params_vec.len());
-        
-        let mut stmt = conn.prepare(query)?;
-        let rows = stmt.query_map(
-            rusqlite::params_from_iter(params_vec.iter().map(|p| p.as_ref())),
-            |row| {
-                Ok((
-                    row.get::<_, String>(0)?,
-                    row.get::<_, Option<String>>(1)?,
-                    row.get::<_, Option<String>>(2)?,
-                    row.get::<_, Option<String>>(3)?,
-                    row.get::<_, String>(4)?,
-                    row.get::<_, String>(5)?,
-                    row.get::<_, Option<String>>(6)?,
-                    row.get::<_, i32>(7)?,
-                    row.get::<_, i32>(8)?,
-                    row.get::<_, Option<String>>(9)?,
-                    row.get::<_, Option<String>>(10)?,
-                    row.get::<_, Option<String>>(11)?,
-                    row.get::<_, Option<String>>(12)?,
-                ))
-            },
-        )?;
-        
-        let result = rows.collect::<Result<Vec<_>, _>>().map_err(|e| anyhow::anyhow!("CSV query error: {}", e))?;
-        tracing::info!("📊 [CSV_QUERY] Query executed successfully, returned {} rows", result.len());
-        
-        Ok(result)
+    fn csv_row(row: &rusqlite::Row) -> rusqlite::Result<CsvRow> {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, Option<String>>(1)?,
+            row.get::<_, Option<String>>(2)?,
+            row.get::<_, Option<String>>(3)?,
+            row.get::<_, String>(4)?,
+            row.get::<_, String>(5)?,
+            row.get::<_, Option<String>>(6)?,
+            row.get::<_, i32>(7)?,
+            row.get::<_, i32>(8)?,
+            row.get::<_, Option<String>>(9)?,
+            row.get::<_, Option<String>>(10)?,
+            row.get::<_, Option<String>>(11)?,
+            row.get::<_, Option<String>>(12)?,
+        ))
     }
     
-    fn format_csv_output(
-        &self,
-        rows: Vec<(String, Option<String>, Option<String>, Option<String>, String, String, Option<String>, i32, i32, Option<String>, Option<String>, Option<String>, Option<String>)>,
-    ) -> Result<String> {
-        let mut csv_output = self.get_csv_header();
-        
-        for row in rows {
-            csv_output.push_str(&self.format_csv_row(row));
-        }
+    fn csv_record(row: CsvRow) -> [String; 13] {
+        let (job_name, folder, app, sub_app, appl_type, appl_ver, task_type, critical, cyclic, owner, priority, desc, cmdline) = row;
+        let yes_no = |flag: i32| if flag == 1 { "Yes" } else { "No" }.to_string();
         
-        Ok(csv_output)
-    }
-    
-    fn get_csv_header(&self) -> String {
-        String::from("Job Name,Folder,Application,Sub Application,APPL_TYPE,APPL_VER,Task Type,Critical,Cyclic,Owner,Priority,Description,Command Line\n")
-    }
-    
-    fn format_csv_row(
-        &self,
-        row: (String, Option<String>, Option<String>, Option<String>, String, String, Option<String>, i32, i32, Option<String>, Option<String>, Option<String>, Option<String>),
-    ) -> String {
-        let (job_name, folder, app, sub_app, appl_type, appl_ver, task_type, critical, cyclic, owner, priority, desc, cmdline) = row;
-        
-        format!(
-            "\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\"\n",
+        [
             job_name,
             folder.unwrap_or_default(),
             app.unwrap_or_default(),
@@ -1182,21 +1949,17 @@ impl JobRepository {
             appl_type,
             appl_ver,
             task_type.unwrap_or_default(),
-            if critical == 1 { "Yes" } else { "No" },
-            if cyclic == 1 { "Yes" } else { "No" },
+            yes_no(critical),
+            yes_no(cyclic),
             owner.unwrap_or_default(),
             priority.unwrap_or_default(),
-            self.escape_csv_field(&desc.unwrap_or_default()),
-            self.escape_csv_field(&cmdline.unwrap_or_default())
-        )
-    }
-    
-    fn escape_csv_field(&self, field: &str) -> String {
-        field.replace("\"", "\"\"")
+            desc.unwrap_or_default(),
+            cmdline.unwrap_or_default(),
+        ]
     }
 
     pub fn get_job_graph(&self, job_id: i64) -> Result<super::models::JobGraphData> {
-        tracing::info!("📊 [GRAPH] Fetching dependency graph for job_id={}", job_id);
+        tracing::info!(job_id, "📊 [GRAPH] Fetching dependency graph");
         let conn = self.conn.lock().unwrap();
         
         // Get the main job info
@@ -1237,126 +2000,70 @@ impl JobRepository {
         visited_jobs.insert(job.0);
         
         // Get incoming dependencies (jobs that this job depends on)
-        // Query in_conditions to find what this job depends on
         tracing::debug!("[GRAPH] Querying incoming dependencies for job_id={}", job_id);
-        let in_query = "SELECT DISTINCT condition_name FROM in_conditions WHERE job_id = ?";
-        let mut in_stmt = conn.prepare(in_query)?;
-        let condition_names: Vec<String> = in_stmt
-            .query_map([job_id], |row| row.get(0))?
-            .filter_map(|r| r.ok())
-            .collect();
-        
-        tracing::info!("🔍 [GRAPH] Found {} condition names for incoming dependencies", condition_names.len());
+        let upstream_jobs = Self::dependency_neighbors(&conn, job_id, true).map_err(|e| {
+            tracing::error!("❌ [GRAPH] Failed to query incoming deps: {}", e);
+            e
+        })?;
         
-        // Find jobs matching those condition names
-        for cond_name in condition_names {
-            tracing::debug!("[GRAPH] Looking for job matching condition_name='{}'", cond_name);
-            
-            // Try exact match first, then try stripping common suffixes
-            let base_name = cond_name
-                .trim_end_matches("-ENDED-OK")
-                .trim_end_matches("-ENDED-NOTOK")
-                .trim_end_matches("-ENDED")
-                .trim_end_matches("-OK")
-                .trim_end_matches("-NOTOK");
-            
-            let dep_job_result = conn.query_row(
-                "SELECT id, job_name, folder_name, application, description FROM jobs WHERE job_name = ? OR job_name = ? LIMIT 1",
-                [&cond_name, base_name],
-                |row| {
-                    Ok((
-                        row.get::<_, i64>(0)?,
-                        row.get::<_, String>(1)?,
-                        row.get::<_, String>(2)?,
-                        row.get::<_, Option<String>>(3)?,
-                        row.get::<_, Option<String>>(4)?
-                    ))
-                }
-            );
-            
-            if let Ok(dep_job) = dep_job_result {
-                tracing::debug!("✅ [GRAPH] Matched incoming dep: id={}, name='{}' (from condition '{}')", 
-                               dep_job.0, dep_job.1, cond_name);
-                if !visited_jobs.contains(&dep_job.0) {
-                    nodes.push(super::models::GraphNode {
-                        id: dep_job.0,
-                        label: dep_job.1.clone(),
-                        folder: dep_job.2.clone(),
-                        application: dep_job.3.clone(),
-                        description: dep_job.4.clone(),
-                        color: "#2196F3".to_string(),
-                        is_current: false,
-                    });
-                    visited_jobs.insert(dep_job.0);
-                    tracing::debug!("[GRAPH] Added incoming dep node: {}", dep_job.1);
-                }
-                edges.push(super::models::GraphEdge {
-                    from: dep_job.0,
-                    to: job.0,
-                    edge_type: "in".to_string(),
+        tracing::info!("🔍 [GRAPH] Found {} jobs '{}' depends on", upstream_jobs.len(), job.1);
+        
+        for dep_job in upstream_jobs {
+            tracing::debug!("✅ [GRAPH] Matched incoming dep: id={}, name='{}'", dep_job.0, dep_job.1);
+            if !visited_jobs.contains(&dep_job.0) {
+                nodes.push(super::models::GraphNode {
+                    id: dep_job.0,
+                    label: dep_job.1.clone(),
+                    folder: dep_job.2.clone(),
+                    application: dep_job.3.clone(),
+                    description: dep_job.4.clone(),
+                    color: "#2196F3".to_string(),
+                    is_current: false,
                 });
-                tracing::debug!("[GRAPH] Added edge: {} -> {}", dep_job.1, job.1);
-            } else {
-                tracing::warn!("⚠️  [GRAPH] No job found matching condition_name='{}' or base_name='{}'", cond_name, base_name);
+                visited_jobs.insert(dep_job.0);
+                tracing::debug!("[GRAPH] Added incoming dep node: {}", dep_job.1);
             }
+            edges.push(super::models::GraphEdge {
+                from: dep_job.0,
+                to: job.0,
+                edge_type: "in".to_string(),
+            });
+            tracing::debug!("[GRAPH] Added edge: {} -> {}", dep_job.1, job.1);
         }
         
         // Get outgoing dependencies (jobs that depend on this job)
-        // Find jobs that have this job's name in their in_conditions
         tracing::debug!("[GRAPH] Querying outgoing dependencies for job_name='{}'", job.1);
-        let out_query = "SELECT DISTINCT job_id FROM in_conditions WHERE condition_name = ?";
-        let mut out_stmt = conn.prepare(out_query).map_err(|e| {
-            tracing::error!("❌ [GRAPH] Failed to prepare outgoing deps query: {}", e);
+        let downstream_jobs = Self::dependency_neighbors(&conn, job_id, false).map_err(|e| {
+            tracing::error!("❌ [GRAPH] Failed to query outgoing deps: {}", e);
             e
         })?;
-        let dependent_job_ids: Vec<i64> = out_stmt
-            .query_map([&job.1], |row| row.get(0))?
-            .filter_map(|r| r.ok())
-            .collect();
         
-        tracing::info!("🔍 [GRAPH] Found {} jobs depending on '{}'", dependent_job_ids.len(), job.1);
-        
-        for dep_job_id in dependent_job_ids {
-            tracing::debug!("[GRAPH] Processing outgoing dep job_id={}", dep_job_id);
-            if let Ok(dep_job) = conn.query_row(
-                "SELECT id, job_name, folder_name, application, description FROM jobs WHERE id = ?",
-                [dep_job_id],
-                |row| {
-                    Ok((
-                        row.get::<_, i64>(0)?,
-                        row.get::<_, String>(1)?,
-                        row.get::<_, String>(2)?,
-                        row.get::<_, Option<String>>(3)?,
-                        row.get::<_, Option<String>>(4)?
-                    ))
-                }
-            ) {
-                tracing::debug!("✅ [GRAPH] Matched outgoing dep: id={}, name='{}'", dep_job.0, dep_job.1);
-                if !visited_jobs.contains(&dep_job.0) {
-                    nodes.push(super::models::GraphNode {
-                        id: dep_job.0,
-                        label: dep_job.1.clone(),
-                        folder: dep_job.2.clone(),
-                        application: dep_job.3.clone(),
-                        description: dep_job.4.clone(),
-                        color: "#FF9800".to_string(),
-                        is_current: false,
-                    });
-                    visited_jobs.insert(dep_job.0);
-                    tracing::debug!("[GRAPH] Added outgoing dep node: {}", dep_job.1);
-                }
-                edges.push(super::models::GraphEdge {
-                    from: job.0,
-                    to: dep_job.0,
-                    edge_type: "out".to_string(),
+        tracing::info!("🔍 [GRAPH] Found {} jobs depending on '{}'", downstream_jobs.len(), job.1);
+        
+        for dep_job in downstream_jobs {
+            tracing::debug!("✅ [GRAPH] Matched outgoing dep: id={}, name='{}'", dep_job.0, dep_job.1);
+            if !visited_jobs.contains(&dep_job.0) {
+                nodes.push(super::models::GraphNode {
+                    id: dep_job.0,
+                    label: dep_job.1.clone(),
+                    folder: dep_job.2.clone(),
+                    application: dep_job.3.clone(),
+                    description: dep_job.4.clone(),
+                    color: "#FF9800".to_string(),
+                    is_current: false,
                 });
-                tracing::debug!("[GRAPH] Added edge: {} -> {}", job.1, dep_job.1);
-            } else {
-                tracing::warn!("⚠️  [GRAPH] Failed to fetch job details for job_id={}", dep_job_id);
+                visited_jobs.insert(dep_job.0);
+                tracing::debug!("[GRAPH] Added outgoing dep node: {}", dep_job.1);
             }
+            edges.push(super::models::GraphEdge {
+                from: job.0,
+                to: dep_job.0,
+                edge_type: "out".to_string(),
+            });
+            tracing::debug!("[GRAPH] Added edge: {} -> {}", job.1, dep_job.1);
         }
         
-        tracing::info!("✅ [GRAPH] Graph complete: {} nodes, {} edges", nodes.len(), edges.len());
+        tracing::info!(job_id, nodes = nodes.len(), edges = edges.len(), "✅ [GRAPH] Graph complete");
         tracing::debug!("[GRAPH] Nodes: {:?}", nodes.iter().map(|n| &n.label).collect::<Vec<_>>());
         
         Ok(super::models::JobGraphData {
@@ -1370,7 +2077,7 @@ impl JobRepository {
 
     pub fn get_end_to_end_graph(&self, job_id: i64, max_depth: Option<i32>) -> Result<super::models::JobGraphData> {
         let depth_limit = max_depth.unwrap_or(5).min(10); // Default 5, max 10
-        tracing::info!("📊 [E2E-GRAPH] Fetching end-to-end dependency graph for job_id={}, depth={}", job_id, depth_limit);
+        tracing::info!(job_id, depth = depth_limit, "📊 [E2E-GRAPH] Fetching end-to-end dependency graph");
         
         let conn = self.conn.lock().unwrap();
         
@@ -1417,8 +2124,13 @@ impl JobRepository {
         tracing::info!("🔽 [E2E-GRAPH] Traversing downstream dependencies...");
         self.traverse_downstream(&conn, job.0, &job.1, 1, depth_limit, &mut nodes, &mut edges, &mut visited_jobs, &mut job_levels)?;
         
-        tracing::info!("✅ [E2E-GRAPH] Graph complete: {} nodes, {} edges, max depth reached: {}", 
-                      nodes.len(), edges.len(), job_levels.values().max().unwrap_or(&0));
+        tracing::info!(
+            job_id,
+            nodes = nodes.len(),
+            edges = edges.len(),
+            max_depth = *job_levels.values().max().unwrap_or(&0),
+            "✅ [E2E-GRAPH] Graph complete"
+        );
         
         Ok(super::models::JobGraphData {
             job_id: job.0,
@@ -1429,6 +2141,647 @@ impl JobRepository {
         })
     }
     
+    /// Get the combined dependency graph of every job in a set of applications
+    ///
+    /// Edges are the resolved job dependencies the other graphs read (see
+    /// `dependency_edges`). Both in-scope jobs and their direct neighbors in
+    /// other applications are returned; the graph is cut off at
+    /// `MAX_APPLICATION_GRAPH_NODES` nodes and `MAX_APPLICATION_GRAPH_EDGES`
+    /// edges.
+    pub fn get_applications_graph(&self, applications: &[String]) -> Result<super::models::ApplicationGraphData> {
+        tracing::info!("🗺️  [APP-GRAPH] Fetching dependency graph for {} application(s)", applications.len());
+        let conn = self.conn.lock().unwrap();
+        let placeholders = vec!["?"; applications.len()].join(", ");
+        let mut truncated = false;
+
+        let mut nodes = Vec::new();
+        let mut in_scope = std::collections::HashSet::new();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT id, job_name, folder_name, application, description FROM jobs WHERE application IN ({}) ORDER BY id LIMIT ?",
+            placeholders
+        ))?;
+        let mut query_params: Vec<&dyn rusqlite::ToSql> = applications.iter().map(|a| a as &dyn rusqlite::ToSql).collect();
+        let node_limit = (MAX_APPLICATION_GRAPH_NODES + 1) as i64;
+        query_params.push(&node_limit);
+        let mut rows = stmt.query(rusqlite::params_from_iter(query_params.iter()))?;
+        while let Some(row) = rows.next()? {
+            if nodes.len() == MAX_APPLICATION_GRAPH_NODES {
+                truncated = true;
+                break;
+            }
+            let id: i64 = row.get(0)?;
+            in_scope.insert(id);
+            nodes.push(super::models::GraphNode {
+                id,
+                label: row.get(1)?,
+                folder: row.get(2)?,
+                application: row.get(3)?,
+                description: row.get(4)?,
+                color: "#4CAF50".to_string(),
+                is_current: true,
+            });
+        }
+        drop(rows);
+
+        let mut stmt = conn.prepare(&format!(
+            r#"
+            SELECT DISTINCT d.from_job_id, d.to_job_id
+            FROM {0} d
+            JOIN jobs p ON p.id = d.from_job_id
+            JOIN jobs c ON c.id = d.to_job_id
+            WHERE p.application IN ({1}) OR c.application IN ({1})
+            ORDER BY d.from_job_id, d.to_job_id
+            LIMIT ?
+            "#,
+            Self::dependency_edges(&conn)?, placeholders
+        ))?;
+        let mut query_params: Vec<&dyn rusqlite::ToSql> = applications.iter()
+            .chain(applications.iter())
+            .map(|a| a as &dyn rusqlite::ToSql)
+            .collect();
+        let edge_limit = (MAX_APPLICATION_GRAPH_EDGES + 1) as i64;
+        query_params.push(&edge_limit);
+        let mut links: Vec<(i64, i64)> = stmt
+            .query_map(rusqlite::params_from_iter(query_params.iter()), |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+        if links.len() > MAX_APPLICATION_GRAPH_EDGES {
+            links.truncate(MAX_APPLICATION_GRAPH_EDGES);
+            truncated = true;
+        }
+
+        // Jobs dropped by the node limit are left out with their edges
+        let links_before = links.len();
+        links.retain(|(from, to)| in_scope.contains(from) || in_scope.contains(to));
+        truncated |= links.len() < links_before;
+
+        // Direct neighbors in other applications, up to the node limit
+        let mut included = in_scope.clone();
+        let mut external_ids = Vec::new();
+        for external in links.iter().flat_map(|&(from, to)| [from, to]) {
+            if included.contains(&external) {
+                continue;
+            }
+            if nodes.len() + external_ids.len() == MAX_APPLICATION_GRAPH_NODES {
+                truncated = true;
+                break;
+            }
+            included.insert(external);
+            external_ids.push(external);
+        }
+        for batch in external_ids.chunks(JOB_IDS_BATCH_SIZE) {
+            let mut stmt = conn.prepare(&format!(
+                "SELECT id, job_name, folder_name, application, description FROM jobs WHERE id IN ({}) ORDER BY id",
+                vec!["?"; batch.len()].join(", ")
+            ))?;
+            let batch_nodes = stmt
+                .query_map(rusqlite::params_from_iter(batch.iter()), |row| Ok(super::models::GraphNode {
+                    id: row.get(0)?,
+                    label: row.get(1)?,
+                    folder: row.get(2)?,
+                    application: row.get(3)?,
+                    description: row.get(4)?,
+                    color: "#9E9E9E".to_string(),
+                    is_current: false,
+                }))?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            nodes.extend(batch_nodes);
+        }
+
+        let edges: Vec<_> = links.into_iter()
+            .filter(|(from, to)| included.contains(from) && included.contains(to))
+            .map(|(from, to)| {
+                let edge_type = if in_scope.contains(&from) && in_scope.contains(&to) { "internal" } else { "external" };
+                super::models::GraphEdge {
+                    from,
+                    to,
+                    edge_type: edge_type.to_string(),
+                }
+            })
+            .collect();
+
+        tracing::info!("✅ [APP-GRAPH] Graph complete: {} nodes, {} edges, truncated={}", nodes.len(), edges.len(), truncated);
+        Ok(super::models::ApplicationGraphData {
+            applications: applications.to_vec(),
+            nodes,
+            edges,
+            truncated,
+        })
+    }
+
+    /// Get the files transferred or watched by current job versions
+    ///
+    /// # Arguments
+    ///
+    /// * `direction` - Only return entries of this direction
+    ///
+    /// # Returns
+    ///
+    /// Result containing the entries ordered by folder and job name, empty
+    /// when the database predates file transfer detection
+    pub fn get_file_transfers(&self, direction: Option<TransferDirection>) -> Result<Vec<FileTransferJob>> {
+        tracing::info!(?direction, "📁 [FILE-TRANSFERS] Fetching file transfer jobs");
+        let conn = self.conn.lock().unwrap();
+        let has_table: bool = conn.query_row(
+            "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'job_file_transfers')",
+            [],
+            |row| row.get(0),
+        )?;
+        if !has_table {
+            return Ok(Vec::new());
+        }
+
+        let mut stmt = conn.prepare(&format!(
+            r#"
+            SELECT j.id, j.job_name, j.folder_name, t.direction, t.path, t.remote_path, t.host
+            FROM job_file_transfers t
+            JOIN jobs j ON j.id = t.job_id
+            WHERE (?1 IS NULL OR t.direction = ?1)
+              AND {}
+            ORDER BY j.folder_name, j.job_name, t.id
+            "#,
+            CURRENT_VERSION_PREDICATE
+        ))?;
+        let transfers = stmt
+            .query_map(params![direction.map(|d| d.to_string())], |row| Ok(FileTransferJob {
+                job_id: row.get(0)?,
+                job_name: row.get(1)?,
+                folder_name: row.get(2)?,
+                direction: row.get(3)?,
+                path: row.get(4)?,
+                remote_path: row.get(5)?,
+                host: row.get(6)?,
+            }))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        tracing::info!(total = transfers.len(), "✅ [FILE-TRANSFERS] Query complete");
+        Ok(transfers)
+    }
+
+    /// List every folder with the counts of its current job versions
+    ///
+    /// Folders without jobs are listed with zero counts. Jobs are matched
+    /// to their folder by folder name and datacenter.
+    ///
+    /// # Returns
+    ///
+    /// Result containing the folders ordered by name and datacenter
+    pub fn list_folders(&self) -> Result<Vec<FolderSummary>> {
+        tracing::info!("📂 [FOLDERS] Listing folders");
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(&format!(
+            r#"
+            SELECT f.folder_name, f.datacenter, f.folder_order_method,
+                   COUNT(j.id),
+                   COALESCE(SUM(CASE WHEN j.critical = 1 THEN 1 ELSE 0 END), 0),
+                   COALESCE(SUM(CASE WHEN j.cyclic = 1 THEN 1 ELSE 0 END), 0)
+            FROM folders f
+            LEFT JOIN jobs j ON j.folder_name = f.folder_name AND j.datacenter IS f.datacenter
+                AND {}
+            GROUP BY f.id
+            ORDER BY f.folder_name, f.datacenter
+            "#,
+            CURRENT_VERSION_PREDICATE
+        ))?;
+        let folders = stmt
+            .query_map([], |row| Ok(FolderSummary {
+                folder_name: row.get(0)?,
+                datacenter: row.get(1)?,
+                folder_order_method: row.get(2)?,
+                job_count: row.get(3)?,
+                critical_count: row.get(4)?,
+                cyclic_count: row.get(5)?,
+            }))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        tracing::info!(total = folders.len(), "✅ [FOLDERS] Query complete");
+        Ok(folders)
+    }
+
+    /// Rebuild the folders needed to generate Airflow DAGs from the database
+    ///
+    /// Reads back what `AirflowDagGenerator` uses (command line, cyclic
+    /// interval and conditions) for current job versions, so DAGs can be
+    /// generated without re-parsing the XML. Folder nesting is not stored,
+    /// so every folder name becomes one top-level folder.
+    ///
+    /// # Returns
+    ///
+    /// Result containing the folders ordered by name, jobs in export order
+    pub fn load_dag_folders(&self) -> Result<Vec<crate::domain::entities::Folder>> {
+        use crate::domain::entities::{Condition, Folder, FolderType, Job};
+
+        tracing::info!("🐍 [DAG-EXPORT] Loading jobs for Airflow DAG generation");
+        let conn = self.conn.lock().unwrap();
+
+        let mut in_conditions: HashMap<i64, Vec<Condition>> = HashMap::new();
+        let mut stmt = conn.prepare("SELECT job_id, condition_name, odate FROM in_conditions ORDER BY id")?;
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?, row.get(2)?)))?;
+        for row in rows {
+            let (job_id, name, odate) = row?;
+            let mut condition = Condition::new_in(name);
+            condition.odate = odate;
+            in_conditions.entry(job_id).or_default().push(condition);
+        }
+
+        let mut out_conditions: HashMap<i64, Vec<Condition>> = HashMap::new();
+        let mut stmt = conn.prepare("SELECT job_id, condition_name, odate, sign FROM out_conditions ORDER BY id")?;
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?, row.get(2)?, row.get(3)?)))?;
+        for row in rows {
+            let (job_id, name, odate, sign) = row?;
+            let mut condition = Condition::new_out(name);
+            condition.odate = odate;
+            condition.sign = sign;
+            out_conditions.entry(job_id).or_default().push(condition);
+        }
+
+        let mut stmt = conn.prepare(&format!(
+            r#"
+            SELECT j.id, j.job_name, j.folder_name, j.cmdline, j.cyclic,
+                   (SELECT s.cyclic_interval FROM job_scheduling s WHERE s.job_id = j.id LIMIT 1)
+            FROM jobs j
+            WHERE {}
+            ORDER BY j.folder_name, j.id
+            "#,
+            CURRENT_VERSION_PREDICATE
+        ))?;
+        let rows = stmt.query_map([], |row| Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, Option<String>>(3)?,
+            row.get::<_, Option<i64>>(4)?,
+            row.get::<_, Option<String>>(5)?,
+        )))?;
+
+        let mut folders: Vec<Folder> = Vec::new();
+        for row in rows {
+            let (job_id, job_name, folder_name, cmdline, cyclic, cyclic_interval) = row?;
+            let mut job = Job::new(job_name, folder_name.clone());
+            job.cmdline = cmdline;
+            job.cyclic = cyclic.unwrap_or(0) != 0;
+            job.scheduling.cyclic_interval = cyclic_interval;
+            job.in_conditions = in_conditions.remove(&job_id).unwrap_or_default();
+            job.out_conditions = out_conditions.remove(&job_id).unwrap_or_default();
+
+            if folders.last().is_none_or(|folder| folder.folder_name != folder_name) {
+                folders.push(Folder::new(folder_name, FolderType::Simple));
+            }
+            if let Some(folder) = folders.last_mut() {
+                folder.add_job(job);
+            }
+        }
+
+        tracing::info!("✅ [DAG-EXPORT] Loaded {} folders", folders.len());
+        Ok(folders)
+    }
+
+    /// Rebuild the folders and jobs stored in the database, e.g. to diff two exports
+    ///
+    /// Restores what the database keeps of each job: the job and folder
+    /// columns, scheduling, in- and out-conditions, control and quantitative
+    /// resources, variables, auto edits and metadata. ON conditions, shouts
+    /// and file transfers are not read back. Every job version is loaded and,
+    /// since folder nesting is not stored, each folder name becomes one
+    /// top-level folder.
+    ///
+    /// # Returns
+    ///
+    /// Result containing the folders ordered by name, jobs in export order
+    pub fn load_folders(&self) -> Result<Vec<crate::domain::entities::Folder>> {
+        use crate::domain::entities::{Condition, ControlResource, Folder, FolderType, Job, QuantitativeResource, SchedulingInfo};
+
+        let conn = self.conn.lock().unwrap();
+
+        let mut folders: Vec<Folder> = Vec::new();
+        let mut stmt = conn.prepare("SELECT * FROM folders ORDER BY folder_name, id")?;
+        let columns: Vec<String> = stmt.column_names().into_iter().map(String::from).collect();
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let folder = Folder::new(row.get("folder_name")?, FolderType::Simple);
+            let folder: Folder = overlay_columns(&folder, row, &columns)?;
+            match folders.last_mut() {
+                // Folders of the same name in several datacenters share their jobs
+                Some(last) if last.folder_name == folder.folder_name => *last = folder,
+                _ => folders.push(folder),
+            }
+        }
+
+        let mut scheduling: HashMap<i64, SchedulingInfo> = HashMap::new();
+        let mut stmt = conn.prepare("SELECT * FROM job_scheduling ORDER BY id")?;
+        let columns: Vec<String> = stmt.column_names().into_iter().map(String::from).collect();
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            scheduling.insert(row.get("job_id")?, overlay_columns(&SchedulingInfo::new(), row, &columns)?);
+        }
+
+        let mut in_conditions: HashMap<i64, Vec<Condition>> = HashMap::new();
+        let mut stmt = conn.prepare("SELECT job_id, condition_name, odate, and_or FROM in_conditions ORDER BY id")?;
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?, row.get(2)?, row.get(3)?)))?;
+        for row in rows {
+            let (job_id, name, odate, and_or) = row?;
+            let mut condition = Condition::new_in(name);
+            condition.odate = odate;
+            condition.and_or = and_or;
+            in_conditions.entry(job_id).or_default().push(condition);
+        }
+
+        let mut out_conditions: HashMap<i64, Vec<Condition>> = HashMap::new();
+        let mut stmt = conn.prepare("SELECT job_id, condition_name, odate, sign FROM out_conditions ORDER BY id")?;
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?, row.get(2)?, row.get(3)?)))?;
+        for row in rows {
+            let (job_id, name, odate, sign) = row?;
+            let mut condition = Condition::new_out(name);
+            condition.odate = odate;
+            condition.sign = sign;
+            out_conditions.entry(job_id).or_default().push(condition);
+        }
+
+        let mut control_resources: HashMap<i64, Vec<ControlResource>> = HashMap::new();
+        let mut stmt = conn.prepare("SELECT job_id, resource_name, resource_type, on_fail FROM control_resources ORDER BY id")?;
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?, row.get(2)?, row.get(3)?)))?;
+        for row in rows {
+            let (job_id, name, resource_type, on_fail) = row?;
+            let mut resource = ControlResource::new(name).with_on_fail(on_fail);
+            resource.resource_type = resource_type;
+            control_resources.entry(job_id).or_default().push(resource);
+        }
+
+        let mut quantitative_resources: HashMap<i64, Vec<QuantitativeResource>> = HashMap::new();
+        let mut stmt = conn.prepare("SELECT job_id, resource_name, quantity, on_fail, on_ok FROM quantitative_resources ORDER BY id")?;
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, QuantitativeResource {
+            name: row.get(1)?,
+            quantity: row.get(2)?,
+            on_fail: row.get(3)?,
+            on_ok: row.get(4)?,
+        })))?;
+        for row in rows {
+            let (job_id, resource) = row?;
+            quantitative_resources.entry(job_id).or_default().push(resource);
+        }
+
+        let key_values = |table: &str, key: &str, value: &str| -> Result<HashMap<i64, HashMap<String, String>>> {
+            let mut values: HashMap<i64, HashMap<String, String>> = HashMap::new();
+            let mut stmt = conn.prepare(&format!("SELECT job_id, {}, {} FROM {} ORDER BY id", key, value, table))?;
+            let rows = stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?, row.get::<_, Option<String>>(2)?)))?;
+            for row in rows {
+                let (job_id, key, value) = row?;
+                values.entry(job_id).or_default().insert(key, value.unwrap_or_default());
+            }
+            Ok(values)
+        };
+        let mut variables = key_values("job_variables", "variable_name", "variable_value")?;
+        let mut auto_edits = key_values("job_auto_edits", "edit_name", "edit_value")?;
+        let mut metadata = key_values("job_metadata", "meta_key", "meta_value")?;
+
+        let mut stmt = conn.prepare("SELECT * FROM jobs ORDER BY id")?;
+        let columns: Vec<String> = stmt.column_names().into_iter().map(String::from).collect();
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let job_id: i64 = row.get("id")?;
+            let job = Job::new(row.get("job_name")?, row.get("folder_name")?);
+            let mut job: Job = overlay_columns(&job, row, &columns)?;
+            job.scheduling = scheduling.remove(&job_id).unwrap_or_default();
+            job.in_conditions = in_conditions.remove(&job_id).unwrap_or_default();
+            job.out_conditions = out_conditions.remove(&job_id).unwrap_or_default();
+            job.control_resources = control_resources.remove(&job_id).unwrap_or_default();
+            job.quantitative_resources = quantitative_resources.remove(&job_id).unwrap_or_default();
+            job.variables = variables.remove(&job_id).unwrap_or_default();
+            job.auto_edits = auto_edits.remove(&job_id).unwrap_or_default();
+            job.metadata = metadata.remove(&job_id).unwrap_or_default();
+
+            let position = match folders.iter().position(|folder| folder.folder_name == job.folder_name) {
+                Some(position) => position,
+                None => {
+                    folders.push(Folder::new(job.folder_name.clone(), FolderType::Simple));
+                    folders.len() - 1
+                }
+            };
+            folders[position].add_job(job);
+        }
+        folders.sort_by(|a, b| a.folder_name.cmp(&b.folder_name));
+
+        Ok(folders)
+    }
+
+    /// Get the dependency graph of every job in a folder
+    ///
+    /// All jobs of the folder are returned as nodes, with an "internal" edge
+    /// for each resolved dependency between two jobs of the folder. Jobs with a dependency on a job outside the folder
+    /// are colored as boundary nodes. The graph has no current job, so
+    /// `job_id` is 0 and `job_name` is empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `folder_name` - Name of the folder
+    ///
+    /// # Returns
+    ///
+    /// Result containing the graph, with no nodes if the folder has no jobs
+    pub fn get_folder_graph(&self, folder_name: &str) -> Result<super::models::JobGraphData> {
+        tracing::info!("🗂️  [FOLDER-GRAPH] Fetching dependency graph for folder '{}'", folder_name);
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, job_name, folder_name, application, description FROM jobs WHERE folder_name = ? ORDER BY job_name"
+        )?;
+        let mut nodes = stmt
+            .query_map([folder_name], |row| Ok(super::models::GraphNode {
+                id: row.get(0)?,
+                label: row.get(1)?,
+                folder: row.get(2)?,
+                application: row.get(3)?,
+                description: row.get(4)?,
+                color: "#4CAF50".to_string(),
+                is_current: true,
+            }))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        // Every dependency touching the folder, with whether each side is in it
+        let mut stmt = conn.prepare(&format!(
+            r#"
+            SELECT DISTINCT d.from_job_id, d.to_job_id, p.folder_name = ?1, c.folder_name = ?1
+            FROM {} d
+            JOIN jobs p ON p.id = d.from_job_id
+            JOIN jobs c ON c.id = d.to_job_id
+            WHERE p.folder_name = ?1 OR c.folder_name = ?1
+            ORDER BY d.from_job_id, d.to_job_id
+            "#,
+            Self::dependency_edges(&conn)?
+        ))?;
+        let links: Vec<(i64, i64, bool, bool)> = stmt
+            .query_map([folder_name], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let mut edges = Vec::new();
+        let mut boundary = std::collections::HashSet::new();
+        for (from, to, from_inside, to_inside) in links {
+            match (from_inside, to_inside) {
+                (true, true) => edges.push(super::models::GraphEdge {
+                    from,
+                    to,
+                    edge_type: "internal".to_string(),
+                }),
+                (true, false) => {
+                    boundary.insert(from);
+                }
+                _ => {
+                    boundary.insert(to);
+                }
+            }
+        }
+        for node in nodes.iter_mut().filter(|n| boundary.contains(&n.id)) {
+            node.color = "#FF5722".to_string();
+        }
+
+        tracing::info!("✅ [FOLDER-GRAPH] Graph complete: {} nodes ({} on the boundary), {} edges",
+            nodes.len(), boundary.len(), edges.len());
+        Ok(super::models::JobGraphData {
+            job_id: 0,
+            job_name: String::new(),
+            folder_name: folder_name.to_string(),
+            nodes,
+            edges,
+        })
+    }
+
+    /// Get the full lineage (ancestors and descendants) of a job
+    ///
+    /// Expands both directions from the root job up to `max_depth` levels and
+    /// tags every node as root, ancestor or descendant.
+    pub fn get_job_lineage(&self, job_id: i64, max_depth: Option<i32>) -> Result<super::models::LineageGraph> {
+        let depth_limit = max_depth.unwrap_or(5).clamp(1, 10); // Default 5, max 10
+        tracing::info!(job_id, depth = depth_limit, "🧬 [LINEAGE] Fetching lineage");
+        
+        let conn = self.conn.lock().unwrap();
+        
+        let job = conn.query_row(
+            "SELECT id, job_name, folder_name, application, description FROM jobs WHERE id = ?",
+            [job_id],
+            |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                    row.get::<_, Option<String>>(4)?
+                ))
+            }
+        )?;
+        
+        let mut nodes = Vec::new();
+        let mut edges = Vec::new();
+        let mut visited_jobs = std::collections::HashSet::new();
+        let mut job_levels = std::collections::HashMap::new();
+        
+        nodes.push(super::models::GraphNode {
+            id: job.0,
+            label: job.1.clone(),
+            folder: job.2.clone(),
+            application: job.3.clone(),
+            description: job.4.clone(),
+            color: "#4CAF50".to_string(),
+            is_current: true,
+        });
+        visited_jobs.insert(job.0);
+        job_levels.insert(job.0, 0);
+        
+        self.traverse_upstream(&conn, job.0, &job.1, 1, depth_limit, &mut nodes, &mut edges, &mut visited_jobs, &mut job_levels)?;
+        self.traverse_downstream(&conn, job.0, &job.1, 1, depth_limit, &mut nodes, &mut edges, &mut visited_jobs, &mut job_levels)?;
+        
+        // Levels are negative for ancestors and positive for descendants
+        let nodes: Vec<super::models::LineageNode> = nodes
+            .into_iter()
+            .map(|node| {
+                let level = job_levels.get(&node.id).copied().unwrap_or(0);
+                let role = match level {
+                    0 => "root",
+                    l if l < 0 => "ancestor",
+                    _ => "descendant",
+                };
+                super::models::LineageNode {
+                    id: node.id,
+                    label: node.label,
+                    folder: node.folder,
+                    application: node.application,
+                    description: node.description,
+                    color: node.color,
+                    role: role.to_string(),
+                    depth: level.abs(),
+                }
+            })
+            .collect();
+        
+        tracing::info!(job_id, nodes = nodes.len(), edges = edges.len(), "✅ [LINEAGE] Lineage complete");
+        
+        Ok(super::models::LineageGraph {
+            job_id: job.0,
+            job_name: job.1,
+            folder_name: job.2,
+            max_depth: depth_limit,
+            nodes,
+            edges,
+        })
+    }
+    
+    /// Find critical jobs that depend on non-critical ancestors
+    ///
+    /// For each critical job, walks its upstream chain (up to `max_depth` levels)
+    /// and reports every non-critical ancestor, i.e. unprotected links on the critical path.
+    pub fn get_critical_path_conflicts(&self, max_depth: Option<i32>) -> Result<Vec<super::models::CriticalPathConflict>> {
+        let depth_limit = max_depth.unwrap_or(5).clamp(1, 10);
+        tracing::info!("🚨 [CRITICAL] Checking critical path conflicts, depth={}", depth_limit);
+        
+        let conn = self.conn.lock().unwrap();
+        
+        let mut stmt = conn.prepare(
+            "SELECT id, job_name, folder_name FROM jobs WHERE critical = 1 ORDER BY job_name"
+        )?;
+        let critical_jobs: Vec<(i64, String, String)> = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .filter_map(|r| r.ok())
+            .collect();
+        
+        let mut conflicts = Vec::new();
+        for (job_id, job_name, folder_name) in critical_jobs {
+            let mut nodes = Vec::new();
+            let mut edges = Vec::new();
+            let mut visited = std::collections::HashSet::from([job_id]);
+            let mut levels = std::collections::HashMap::from([(job_id, 0)]);
+            
+            self.traverse_upstream(&conn, job_id, &job_name, 1, depth_limit, &mut nodes, &mut edges, &mut visited, &mut levels)?;
+            
+            let mut non_critical_ancestors = Vec::new();
+            for node in nodes {
+                let is_critical: bool = conn.query_row(
+                    "SELECT critical = 1 FROM jobs WHERE id = ?",
+                    [node.id],
+                    |row| row.get(0),
+                )?;
+                if !is_critical {
+                    non_critical_ancestors.push(super::models::CriticalPathAncestor {
+                        job_id: node.id,
+                        job_name: node.label,
+                        folder_name: node.folder,
+                        depth: levels.get(&node.id).copied().unwrap_or(0).abs(),
+                    });
+                }
+            }
+            
+            if !non_critical_ancestors.is_empty() {
+                non_critical_ancestors.sort_by_key(|a| a.depth);
+                conflicts.push(super::models::CriticalPathConflict {
+                    job_id,
+                    job_name,
+                    folder_name,
+                    non_critical_ancestors,
+                });
+            }
+        }
+        
+        tracing::info!("✅ [CRITICAL] Found {} critical jobs with non-critical ancestors", conflicts.len());
+        Ok(conflicts)
+    }
+    
     fn traverse_upstream(
         &self,
         conn: &rusqlite::Connection,
@@ -1448,70 +2801,42 @@ impl JobRepository {
         
         tracing::debug!("[E2E-GRAPH] Upstream level {}: Processing job_id={}", current_level, current_job_id);
         
-        // Get in_conditions for current job
-        let in_query = "SELECT DISTINCT condition_name FROM in_conditions WHERE job_id = ?";
-        let mut in_stmt = conn.prepare(in_query)?;
-        let condition_names: Vec<String> = in_stmt
-            .query_map([current_job_id], |row| row.get(0))?
-            .filter_map(|r| r.ok())
-            .collect();
-        
-        tracing::debug!("[E2E-GRAPH] Found {} conditions for job_id={}", condition_names.len(), current_job_id);
-        
-        for cond_name in condition_names {
-            let base_name = cond_name
-                .trim_end_matches("-ENDED-OK")
-                .trim_end_matches("-ENDED-NOTOK")
-                .trim_end_matches("-ENDED")
-                .trim_end_matches("-OK")
-                .trim_end_matches("-NOTOK");
-            
-            if let Ok(dep_job) = conn.query_row(
-                "SELECT id, job_name, folder_name, application, description FROM jobs WHERE job_name = ? OR job_name = ? LIMIT 1",
-                [&cond_name, base_name],
-                |row| {
-                    Ok((
-                        row.get::<_, i64>(0)?,
-                        row.get::<_, String>(1)?,
-                        row.get::<_, String>(2)?,
-                        row.get::<_, Option<String>>(3)?,
-                        row.get::<_, Option<String>>(4)?
-                    ))
-                }
-            ) {
-                if !visited.contains(&dep_job.0) {
-                    // Color based on level (upstream = blue shades)
-                    let color = match current_level {
-                        1 => "#2196F3".to_string(), // Blue
-                        2 => "#1976D2".to_string(), // Darker blue
-                        3 => "#1565C0".to_string(), // Even darker
-                        _ => "#0D47A1".to_string(), // Darkest blue
-                    };
-                    
-                    nodes.push(super::models::GraphNode {
-                        id: dep_job.0,
-                        label: dep_job.1.clone(),
-                        folder: dep_job.2.clone(),
-                        application: dep_job.3.clone(),
-                        description: dep_job.4.clone(),
-                        color,
-                        is_current: false,
-                    });
-                    visited.insert(dep_job.0);
-                    levels.insert(dep_job.0, -current_level); // Negative for upstream
-                    
-                    tracing::debug!("[E2E-GRAPH] Added upstream node at level {}: {}", current_level, dep_job.1);
-                    
-                    // Recursively traverse this job's dependencies
-                    self.traverse_upstream(conn, dep_job.0, &dep_job.1, current_level + 1, max_depth, nodes, edges, visited, levels)?;
-                }
+        let upstream_jobs = Self::dependency_neighbors(conn, current_job_id, true)?;
+        tracing::debug!("[E2E-GRAPH] Found {} upstream jobs for job_id={}", upstream_jobs.len(), current_job_id);
+        
+        for dep_job in upstream_jobs {
+            if !visited.contains(&dep_job.0) {
+                // Color based on level (upstream = blue shades)
+                let color = match current_level {
+                    1 => "#2196F3".to_string(), // Blue
+                    2 => "#1976D2".to_string(), // Darker blue
+                    3 => "#1565C0".to_string(), // Even darker
+                    _ => "#0D47A1".to_string(), // Darkest blue
+                };
                 
-                edges.push(super::models::GraphEdge {
-                    from: dep_job.0,
-                    to: current_job_id,
-                    edge_type: "in".to_string(),
+                nodes.push(super::models::GraphNode {
+                    id: dep_job.0,
+                    label: dep_job.1.clone(),
+                    folder: dep_job.2.clone(),
+                    application: dep_job.3.clone(),
+                    description: dep_job.4.clone(),
+                    color,
+                    is_current: false,
                 });
+                visited.insert(dep_job.0);
+                levels.insert(dep_job.0, -current_level); // Negative for upstream
+                
+                tracing::debug!("[E2E-GRAPH] Added upstream node at level {}: {}", current_level, dep_job.1);
+                
+                // Recursively traverse this job's dependencies
+                self.traverse_upstream(conn, dep_job.0, &dep_job.1, current_level + 1, max_depth, nodes, edges, visited, levels)?;
             }
+            
+            edges.push(super::models::GraphEdge {
+                from: dep_job.0,
+                to: current_job_id,
+                edge_type: "in".to_string(),
+            });
         }
         
         Ok(())
@@ -1521,7 +2846,7 @@ impl JobRepository {
         &self,
         conn: &rusqlite::Connection,
         current_job_id: i64,
-        current_job_name: &str,
+        _current_job_name: &str,
         current_level: i32,
         max_depth: i32,
         nodes: &mut Vec<super::models::GraphNode>,
@@ -1536,144 +2861,128 @@ impl JobRepository {
         
         tracing::debug!("[E2E-GRAPH] Downstream level {}: Processing job_id={}", current_level, current_job_id);
         
-        // First, get all out_conditions for current job
-        let out_cond_query = "SELECT DISTINCT condition_name FROM out_conditions WHERE job_id = ?";
-        let mut out_cond_stmt = conn.prepare(out_cond_query)?;
-        let condition_names: Vec<String> = out_cond_stmt
-            .query_map([current_job_id], |row| row.get(0))?
-            .filter_map(|r| r.ok())
-            .collect();
-        
-        tracing::debug!("[E2E-GRAPH] Found {} out_conditions for job_id={}", condition_names.len(), current_job_id);
-        
-        // For each condition, find jobs that wait for it
-        let mut dependent_job_ids = Vec::new();
-        for cond_name in condition_names {
-            let dep_query = "SELECT DISTINCT job_id FROM in_conditions WHERE condition_name = ?";
-            let mut dep_stmt = conn.prepare(dep_query)?;
-            let job_ids: Vec<i64> = dep_stmt
-                .query_map([&cond_name], |row| row.get(0))?
-                .filter_map(|r| r.ok())
-                .collect();
-            
-            tracing::debug!("[E2E-GRAPH] Condition '{}' is waited by {} jobs", cond_name, job_ids.len());
-            dependent_job_ids.extend(job_ids);
-        }
-        
-        // Also check for jobs waiting for job_name directly (fallback)
-        let direct_query = "SELECT DISTINCT job_id FROM in_conditions WHERE condition_name = ? OR condition_name LIKE ?";
-        let mut direct_stmt = conn.prepare(direct_query)?;
-        let pattern = format!("{}%", current_job_name);
-        let direct_job_ids: Vec<i64> = direct_stmt
-            .query_map([current_job_name, &pattern], |row| row.get(0))?
-            .filter_map(|r| r.ok())
-            .collect();
-        dependent_job_ids.extend(direct_job_ids);
-        
-        // Remove duplicates
-        dependent_job_ids.sort();
-        dependent_job_ids.dedup();
-        
-        tracing::debug!("[E2E-GRAPH] Total {} dependent jobs for job_id={}", dependent_job_ids.len(), current_job_id);
-        
-        for dep_job_id in dependent_job_ids {
-            if let Ok(dep_job) = conn.query_row(
-                "SELECT id, job_name, folder_name, application, description FROM jobs WHERE id = ?",
-                [dep_job_id],
-                |row| {
-                    Ok((
-                        row.get::<_, i64>(0)?,
-                        row.get::<_, String>(1)?,
-                        row.get::<_, String>(2)?,
-                        row.get::<_, Option<String>>(3)?,
-                        row.get::<_, Option<String>>(4)?
-                    ))
-                }
-            ) {
-                if !visited.contains(&dep_job.0) {
-                    // Color based on level (downstream = orange shades)
-                    let color = match current_level {
-                        1 => "#FF9800".to_string(), // Orange
-                        2 => "#F57C00".to_string(), // Darker orange
-                        3 => "#E65100".to_string(), // Even darker
-                        _ => "#BF360C".to_string(), // Darkest orange
-                    };
-                    
-                    nodes.push(super::models::GraphNode {
-                        id: dep_job.0,
-                        label: dep_job.1.clone(),
-                        folder: dep_job.2.clone(),
-                        application: dep_job.3.clone(),
-                        description: dep_job.4.clone(),
-                        color,
-                        is_current: false,
-                    });
-                    visited.insert(dep_job.0);
-                    levels.insert(dep_job.0, current_level); // Positive for downstream
-                    
-                    tracing::debug!("[E2E-GRAPH] Added downstream node at level {}: {}", current_level, dep_job.1);
-                    
-                    // Recursively traverse jobs that depend on this job
-                    self.traverse_downstream(conn, dep_job.0, &dep_job.1, current_level + 1, max_depth, nodes, edges, visited, levels)?;
-                }
+        let downstream_jobs = Self::dependency_neighbors(conn, current_job_id, false)?;
+        tracing::debug!("[E2E-GRAPH] Total {} dependent jobs for job_id={}", downstream_jobs.len(), current_job_id);
+        
+        for dep_job in downstream_jobs {
+            if !visited.contains(&dep_job.0) {
+                // Color based on level (downstream = orange shades)
+                let color = match current_level {
+                    1 => "#FF9800".to_string(), // Orange
+                    2 => "#F57C00".to_string(), // Darker orange
+                    3 => "#E65100".to_string(), // Even darker
+                    _ => "#BF360C".to_string(), // Darkest orange
+                };
                 
-                edges.push(super::models::GraphEdge {
-                    from: current_job_id,
-                    to: dep_job.0,
-                    edge_type: "out".to_string(),
+                nodes.push(super::models::GraphNode {
+                    id: dep_job.0,
+                    label: dep_job.1.clone(),
+                    folder: dep_job.2.clone(),
+                    application: dep_job.3.clone(),
+                    description: dep_job.4.clone(),
+                    color,
+                    is_current: false,
                 });
+                visited.insert(dep_job.0);
+                levels.insert(dep_job.0, current_level); // Positive for downstream
+                
+                tracing::debug!("[E2E-GRAPH] Added downstream node at level {}: {}", current_level, dep_job.1);
+                
+                // Recursively traverse jobs that depend on this job
+                self.traverse_downstream(conn, dep_job.0, &dep_job.1, current_level + 1, max_depth, nodes, edges, visited, levels)?;
             }
+            
+            edges.push(super::models::GraphEdge {
+                from: current_job_id,
+                to: dep_job.0,
+                edge_type: "out".to_string(),
+            });
         }
         
         Ok(())
     }
 
+    /// Gets the SQL source of job-to-job dependency edges
+    ///
+    /// Reads the `job_dependencies` table resolved at export time. Databases
+    /// exported before it existed match out- and in-conditions by name
+    /// instead, as graphs were built before.
+    fn dependency_edges(conn: &Connection) -> Result<&'static str> {
+        Ok(if Self::table_exists(conn, "job_dependencies")? {
+            "job_dependencies"
+        } else {
+            "(SELECT DISTINCT o.job_id AS from_job_id, i.job_id AS to_job_id \
+              FROM out_conditions o JOIN in_conditions i ON i.condition_name = o.condition_name \
+              WHERE o.job_id <> i.job_id)"
+        })
+    }
+
+    /// Gets the jobs directly linked to a job
+    ///
+    /// Dependencies are resolved once at export time, so graph and impact
+    /// queries read edges instead of matching condition names per request.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - Database connection
+    /// * `job_id` - Job whose neighbors to fetch
+    /// * `upstream` - true for the jobs this job waits for, false for the jobs waiting for it
+    ///
+    /// # Returns
+    ///
+    /// The neighboring jobs, ordered by name
+    fn dependency_neighbors(conn: &Connection, job_id: i64, upstream: bool) -> Result<Vec<GraphJobRow>> {
+        let (match_column, neighbor_column) = if upstream {
+            ("to_job_id", "from_job_id")
+        } else {
+            ("from_job_id", "to_job_id")
+        };
+        let query = format!(
+            "SELECT DISTINCT j.id, j.job_name, j.folder_name, j.application, j.description
+             FROM {} d
+             JOIN jobs j ON j.id = d.{}
+             WHERE d.{} = ?
+             ORDER BY j.job_name",
+            Self::dependency_edges(conn)?, neighbor_column, match_column
+        );
+        let mut stmt = conn.prepare(&query)?;
+        let jobs = stmt
+            .query_map([job_id], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                    row.get::<_, Option<String>>(4)?
+                ))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(jobs)
+    }
+
     /// Calculate end-to-end dependencies count for a specific job
     pub fn get_e2e_dependencies_count(&self, job_id: i64) -> Result<u32> {
         let conn = self.conn.lock().unwrap();
         
+        let edges = Self::dependency_edges(&conn)?;
         let count: u32 = conn.query_row(
-            r#"
+            &format!(r#"
             WITH RECURSIVE dep_tree AS (
                 -- Base case: direct dependencies (jobs that this job depends on)
-                SELECT DISTINCT j2.id as dep_job_id, 1 as depth
-                FROM in_conditions ic
-                JOIN jobs j2 ON (
-                    j2.job_name = ic.condition_name 
-                    OR j2.job_name = REPLACE(REPLACE(REPLACE(REPLACE(REPLACE(REPLACE(
-                        ic.condition_name,
-                        '-ENDED-OK', ''),
-                        '-ENDED-NOTOK', ''),
-                        '_ENDED_OK', ''),
-                        '_ENDED_NOTOK', ''),
-                        '-ENDED', ''),
-                        '-OK', '')
-                    OR j2.job_name = REPLACE(ic.condition_name, '-NOTOK', '')
-                )
-                WHERE ic.job_id = ?
+                SELECT from_job_id as dep_job_id, 1 as depth
+                FROM {edges}
+                WHERE to_job_id = ?
                 
                 UNION
                 
                 -- Recursive case: transitive dependencies
-                SELECT DISTINCT j3.id as dep_job_id, dt.depth + 1
+                SELECT d.from_job_id as dep_job_id, dt.depth + 1
                 FROM dep_tree dt
-                JOIN in_conditions ic2 ON ic2.job_id = dt.dep_job_id
-                JOIN jobs j3 ON (
-                    j3.job_name = ic2.condition_name 
-                    OR j3.job_name = REPLACE(REPLACE(REPLACE(REPLACE(REPLACE(REPLACE(
-                        ic2.condition_name,
-                        '-ENDED-OK', ''),
-                        '-ENDED-NOTOK', ''),
-                        '_ENDED_OK', ''),
-                        '_ENDED_NOTOK', ''),
-                        '-ENDED', ''),
-                        '-OK', '')
-                    OR j3.job_name = REPLACE(ic2.condition_name, '-NOTOK', '')
-                )
+                JOIN {edges} d ON d.to_job_id = dt.dep_job_id
                 WHERE dt.depth < 10
             )
             SELECT COUNT(DISTINCT dep_job_id) FROM dep_tree
-            "#,
+            "#),
             [job_id],
             |row| row.get(0)
         )?;
@@ -1981,3 +3290,866 @@ impl JobRepository {
         })
     }
 }
+
+/// Turns free text into an FTS5 query matching jobs containing every word
+///
+/// Each word is quoted, so FTS5 operators and punctuation in user input are
+/// matched literally, and prefix-matched, so `extr` finds `EXTRACT`.
+///
+/// # Returns
+///
+/// The MATCH expression, or None if the text has no words
+fn fts_match_query(text: &str) -> Option<String> {
+    let terms: Vec<String> = text
+        .split_whitespace()
+        .map(|word| format!("\"{}\"*", word.replace('"', "\"\"")))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+/// Columns stored under a different name than the entity field
+const RENAMED_COLUMNS: &[(&str, &str)] = &[("option_field", "option"), ("from_field", "from")];
+
+/// Columns stored as 0/1 integers for boolean entity fields
+const BOOLEAN_COLUMNS: &[&str] = &["critical", "cyclic", "modified"];
+
+/// Copies the columns of a row onto the same-named fields of an entity
+///
+/// Columns without a matching field (ids, derived columns) are ignored.
+///
+/// # Arguments
+///
+/// * `entity` - Entity providing the fields not stored in the row
+/// * `row` - Database row
+/// * `columns` - Column names of the row, in order
+///
+/// # Returns
+///
+/// The entity with every stored field replaced by its column value
+fn overlay_columns<T: serde::Serialize + serde::de::DeserializeOwned>(
+    entity: &T,
+    row: &rusqlite::Row,
+    columns: &[String],
+) -> Result<T> {
+    use rusqlite::types::ValueRef;
+    use serde_json::Value;
+
+    let mut fields = match serde_json::to_value(entity)? {
+        Value::Object(fields) => fields,
+        _ => anyhow::bail!("Entity does not serialize to an object"),
+    };
+    for (index, column) in columns.iter().enumerate() {
+        let field = RENAMED_COLUMNS.iter()
+            .find(|(stored, _)| stored == column)
+            .map_or(column.as_str(), |(_, field)| *field);
+        let Some(slot) = fields.get_mut(field) else {
+            continue;
+        };
+        *slot = match row.get_ref(index)? {
+            ValueRef::Null => Value::Null,
+            ValueRef::Integer(value) if BOOLEAN_COLUMNS.contains(&field) => Value::Bool(value != 0),
+            ValueRef::Integer(value) => Value::from(value),
+            ValueRef::Real(value) => Value::from(value),
+            ValueRef::Text(text) => Value::String(String::from_utf8_lossy(text).into_owned()),
+            ValueRef::Blob(_) => continue,
+        };
+    }
+    serde_json::from_value(Value::Object(fields))
+        .map_err(|e| anyhow::anyhow!("Failed to read back a stored row: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{Condition, DoAction, Folder, FolderType, Job, OnCondition};
+    use crate::infrastructure::output::SqliteExporter;
+    use crate::infrastructure::parsers::ControlMXmlParser;
+
+    /// Exports the given folders and their analysis into a fresh database file
+    /// and opens a repository on it
+    ///
+    /// The database is removed when the returned directory handle is dropped.
+    fn create_test_repository(folders: &[Folder]) -> (tempfile::TempDir, JobRepository) {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("jobs.db");
+        let analysis = crate::application::use_cases::AnalyzeJobs::new().execute(folders).unwrap();
+        SqliteExporter::new(&db_path).unwrap()
+            .with_job_analysis(analysis.complexity_results)
+            .export_folders(folders)
+            .unwrap();
+        (dir, JobRepository::new(db_path.to_str().unwrap()).unwrap())
+    }
+
+    fn chain_job(name: &str, waits_for: Option<&str>) -> Job {
+        let mut job = Job::new(name.to_string(), "CHAIN".to_string());
+        if let Some(upstream) = waits_for {
+            job.in_conditions.push(Condition::new_in(format!("{}-ENDED-OK", upstream)));
+        }
+        job.out_conditions.push(Condition::new_out(format!("{}-ENDED-OK", name)));
+        job
+    }
+
+    fn job_id(repo: &JobRepository, name: &str) -> i64 {
+        let conn = repo.conn.lock().unwrap();
+        conn.query_row("SELECT id FROM jobs WHERE job_name = ?", [name], |row| row.get(0)).unwrap()
+    }
+
+    #[test]
+    fn test_search_returns_current_versions_by_default() {
+        let mut folder = Folder::new("CHAIN".to_string(), FolderType::Simple);
+        for (name, is_current) in [("LOAD_V1", Some("N")), ("LOAD_V2", Some("Y")), ("UNVERSIONED", None)] {
+            let mut job = chain_job(name, None);
+            job.is_current_version = is_current.map(String::from);
+            folder.add_job(job);
+        }
+        let (_dir, repo) = create_test_repository(&[folder]);
+
+        let names = |is_current_version| -> Vec<String> {
+            let mut names: Vec<_> = repo.search_jobs(&JobSearchRequest { is_current_version, ..Default::default() })
+                .unwrap().jobs.into_iter().map(|j| j.job_name).collect();
+            names.sort();
+            names
+        };
+        assert_eq!(names(None), vec!["LOAD_V2", "UNVERSIONED"]);
+        assert_eq!(names(Some(true)), vec!["LOAD_V2", "UNVERSIONED"]);
+        assert_eq!(names(Some(false)), vec!["LOAD_V1", "LOAD_V2", "UNVERSIONED"]);
+    }
+
+    #[test]
+    fn test_csv_export_escapes_every_field() {
+        let folder_name = "ETL, \"daily\"";
+        let mut folder = Folder::new(folder_name.to_string(), FolderType::Simple);
+        let mut job = Job::new("LOAD".to_string(), folder_name.to_string());
+        job.owner = Some("batch\nops".to_string());
+        job.description = Some("Loads \"sales\"\r\nnightly".to_string());
+        folder.add_job(job);
+        let (_dir, repo) = create_test_repository(&[folder]);
+
+        let csv_text = repo.export_search_to_csv(&JobSearchRequest::default()).unwrap();
+        let mut reader = csv::Reader::from_reader(csv_text.as_bytes());
+        let records: Vec<csv::StringRecord> = reader.records().collect::<Result<_, _>>().unwrap();
+
+        assert_eq!(reader.headers().unwrap().len(), 13);
+        assert_eq!(records.len(), 1);
+        assert_eq!(&records[0][1], folder_name);
+        assert_eq!(&records[0][9], "batch\nops");
+        assert_eq!(&records[0][11], "Loads \"sales\"\r\nnightly");
+        assert!(csv_text.starts_with("Job Name,Folder,"));
+    }
+
+    #[test]
+    fn test_csv_export_applies_search_text() {
+        let mut folder = Folder::new("CHAIN".to_string(), FolderType::Simple);
+        folder.add_job(chain_job("LOAD_LEDGER", None));
+        folder.add_job(chain_job("SEND_REPORT", None));
+        let (_dir, repo) = create_test_repository(&[folder]);
+
+        let request = JobSearchRequest { search_text: Some("ledger".to_string()), ..Default::default() };
+        let csv_text = repo.export_search_to_csv(&request).unwrap();
+        let mut reader = csv::Reader::from_reader(csv_text.as_bytes());
+        let records: Vec<csv::StringRecord> = reader.records().collect::<Result<_, _>>().unwrap();
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(&records[0][0], "LOAD_LEDGER");
+    }
+
+    #[test]
+    fn test_sort_by_priority_is_numeric() {
+        let mut folder = Folder::new("CHAIN".to_string(), FolderType::Simple);
+        for (name, priority) in [("HIGH", "10"), ("LOW", "2")] {
+            let mut job = chain_job(name, None);
+            job.priority = Some(priority.to_string());
+            folder.add_job(job);
+        }
+        let (_dir, repo) = create_test_repository(&[folder]);
+        let sorted_names = |repo: &JobRepository| -> Vec<String> {
+            repo.search_jobs(&JobSearchRequest {
+                sort_by: Some("priority".to_string()),
+                sort_order: Some(SortOrder::Asc),
+                ..Default::default()
+            }).unwrap().jobs.into_iter().map(|j| j.job_name).collect()
+        };
+
+        assert_eq!(sorted_names(&repo), vec!["LOW", "HIGH"]);
+
+        // Databases exported before priority_num existed parse the text
+        repo.conn.lock().unwrap().execute_batch(
+            "DROP INDEX idx_jobs_priority_num; ALTER TABLE jobs DROP COLUMN priority_num;"
+        ).unwrap();
+        assert_eq!(sorted_names(&repo), vec!["LOW", "HIGH"]);
+    }
+
+    #[test]
+    fn test_sort_by_e2e_dependencies() {
+        let mut folder = Folder::new("CHAIN".to_string(), FolderType::Simple);
+        folder.add_job(chain_job("SRC_A", None));
+        folder.add_job(chain_job("SRC_B", None));
+        let mut hub = chain_job("HUB", Some("SRC_A"));
+        hub.in_conditions.push(Condition::new_in("SRC_B-ENDED-OK".to_string()));
+        folder.add_job(hub);
+        folder.add_job(chain_job("LEAF_A", Some("HUB")));
+        folder.add_job(chain_job("LEAF_B", Some("HUB")));
+        folder.add_job(chain_job("ALONE", None));
+        let (_dir, repo) = create_test_repository(&[folder]);
+
+        let response = repo.search_jobs(&JobSearchRequest {
+            sort_by: Some("total_dependencies_e2e".to_string()),
+            sort_order: Some(SortOrder::Desc),
+            ..Default::default()
+        }).unwrap();
+        let sorted: Vec<_> = response.jobs.iter()
+            .map(|j| (j.job_name.as_str(), j.total_dependencies_e2e))
+            .collect();
+        assert_eq!(sorted[0], ("HUB", 4));
+        assert_eq!(sorted[5], ("ALONE", 0));
+        assert!(sorted.windows(2).all(|w| w[0].1 >= w[1].1));
+
+        let stats = repo.get_dashboard_stats(None, None).unwrap();
+        let connected: Vec<_> = stats.most_connected_jobs.iter().map(|s| s.job_name.as_str()).collect();
+        assert_eq!(connected[0], "HUB");
+        assert!(!connected.contains(&"ALONE"));
+    }
+
+    #[test]
+    fn test_database_without_job_analysis_reads_zero_dependencies() {
+        let mut folder = Folder::new("CHAIN".to_string(), FolderType::Simple);
+        folder.add_job(chain_job("SRC", None));
+        folder.add_job(chain_job("SINK", Some("SRC")));
+        let (_dir, repo) = create_test_repository(&[folder]);
+        repo.conn.lock().unwrap().execute_batch("DROP TABLE job_analysis;").unwrap();
+
+        let response = repo.search_jobs(&JobSearchRequest {
+            sort_by: Some("complexity_score".to_string()),
+            ..Default::default()
+        }).unwrap();
+        assert_eq!(response.total, 2);
+        assert!(response.jobs.iter().all(|j| j.total_dependencies_e2e == 0));
+
+        let detail = repo.get_job_detail(job_id(&repo, "SINK")).unwrap().unwrap();
+        assert_eq!(detail.job.total_dependencies_e2e, 0);
+
+        let stats = repo.get_dashboard_stats(None, None).unwrap();
+        assert_eq!(stats.total_jobs, 2);
+        assert!(stats.most_connected_jobs.is_empty());
+        assert_eq!(stats.complexity_distribution.low, 0);
+    }
+
+    #[test]
+    fn test_applications_graph_includes_direct_external_neighbors() {
+        let mut folder = Folder::new("CHAIN".to_string(), FolderType::Simple);
+        for (name, waits_for, application) in [
+            ("UPSTREAM", None, "OTHER"),
+            ("LOAD", Some("UPSTREAM"), "SALES"),
+            ("REPORT", Some("LOAD"), "SALES"),
+            ("EXPORT", Some("REPORT"), "OTHER"),
+            ("ARCHIVE", Some("EXPORT"), "OTHER"),
+            ("UNRELATED", None, "HR"),
+        ] {
+            let mut job = chain_job(name, waits_for);
+            job.application = Some(application.to_string());
+            folder.add_job(job);
+        }
+        let (_dir, repo) = create_test_repository(&[folder]);
+
+        let graph = repo.get_applications_graph(&["SALES".to_string()]).unwrap();
+
+        let mut names: Vec<_> = graph.nodes.iter().map(|n| n.label.as_str()).collect();
+        names.sort();
+        assert_eq!(names, vec!["EXPORT", "LOAD", "REPORT", "UPSTREAM"]);
+        let in_scope: Vec<_> = graph.nodes.iter().filter(|n| n.is_current).map(|n| n.label.as_str()).collect();
+        assert_eq!(in_scope.len(), 2);
+        assert!(in_scope.contains(&"LOAD") && in_scope.contains(&"REPORT"));
+
+        let edge = |from: &str, to: &str| graph.edges.iter()
+            .find(|e| e.from == job_id(&repo, from) && e.to == job_id(&repo, to))
+            .map(|e| e.edge_type.clone());
+        assert_eq!(edge("LOAD", "REPORT").as_deref(), Some("internal"));
+        assert_eq!(edge("UPSTREAM", "LOAD").as_deref(), Some("external"));
+        assert_eq!(edge("REPORT", "EXPORT").as_deref(), Some("external"));
+        assert_eq!(graph.edges.len(), 3);
+        assert!(!graph.truncated);
+    }
+
+    #[test]
+    fn test_dependency_depth_is_stored_and_sortable() {
+        let mut folder = Folder::new("CHAIN".to_string(), FolderType::Simple);
+        folder.add_job(chain_job("FIRST", None));
+        folder.add_job(chain_job("SECOND", Some("FIRST")));
+        let mut third = chain_job("THIRD", Some("SECOND"));
+        third.in_conditions.push(Condition::new_in("FIRST-ENDED-OK".to_string()));
+        folder.add_job(third);
+        folder.add_job(chain_job("ALONE", None));
+        let (_dir, repo) = create_test_repository(&[folder]);
+
+        let response = repo.search_jobs(&JobSearchRequest {
+            sort_by: Some("dependency_depth".to_string()),
+            sort_order: Some(SortOrder::Desc),
+            ..Default::default()
+        }).unwrap();
+        let depths: Vec<_> = response.jobs.iter()
+            .map(|j| (j.job_name.as_str(), j.dependency_depth))
+            .collect();
+        assert_eq!(&depths[..2], &[("THIRD", 2), ("SECOND", 1)]);
+        assert!(depths[2..].iter().all(|(_, depth)| *depth == 0));
+
+        let detail = repo.get_job_detail(job_id(&repo, "THIRD")).unwrap().unwrap();
+        assert_eq!(detail.job.dependency_depth, 2);
+    }
+
+    #[test]
+    fn test_folder_graph_has_internal_edges_and_marks_boundary_jobs() {
+        let mut etl = Folder::new("ETL".to_string(), FolderType::Simple);
+        let mut report = Folder::new("REPORTS".to_string(), FolderType::Simple);
+        for (name, waits_for) in [("EXTRACT", None), ("LOAD", Some("EXTRACT")), ("PUBLISH", Some("LOAD"))] {
+            let mut job = chain_job(name, waits_for);
+            job.folder_name = "ETL".to_string();
+            etl.add_job(job);
+        }
+        let mut daily = chain_job("DAILY", Some("PUBLISH"));
+        daily.folder_name = "REPORTS".to_string();
+        report.add_job(daily);
+        let (_dir, repo) = create_test_repository(&[etl, report]);
+
+        let graph = repo.get_folder_graph("ETL").unwrap();
+
+        let names: Vec<_> = graph.nodes.iter().map(|n| n.label.as_str()).collect();
+        assert_eq!(names, vec!["EXTRACT", "LOAD", "PUBLISH"]);
+        assert_eq!(graph.edges.len(), 2);
+        assert!(graph.edges.iter().all(|e| e.edge_type == "internal"));
+        let color = |name: &str| graph.nodes.iter().find(|n| n.label == name).unwrap().color.clone();
+        assert_eq!(color("PUBLISH"), "#FF5722");
+        assert_eq!(color("EXTRACT"), "#4CAF50");
+        assert!(repo.get_folder_graph("MISSING").unwrap().nodes.is_empty());
+    }
+
+    #[test]
+    fn test_dashboard_counts_jobs_per_notification_target() {
+        let mut folder = Folder::new("CHAIN".to_string(), FolderType::Simple);
+        for (name, dests) in [("A", vec!["ops@example.com;dba@example.com", "ops@example.com"]), ("B", vec!["ops@example.com"]), ("C", vec![])] {
+            let mut job = chain_job(name, None);
+            let mut on_cond = OnCondition::new();
+            for dest in dests {
+                on_cond.actions.push(DoAction::Mail { dest: dest.to_string(), message: "failed".to_string() });
+            }
+            on_cond.actions.push(DoAction::Shout { dest: "EM".to_string(), message: "failed".to_string() });
+            job.on_conditions.push(on_cond);
+            folder.add_job(job);
+        }
+        assert_eq!(folder.jobs[0].notification_targets().len(), 3);
+        let (_dir, repo) = create_test_repository(&[folder]);
+
+        let stats = repo.get_dashboard_stats(None, None).unwrap();
+        let targets: Vec<_> = stats.jobs_by_notification_target.iter()
+            .map(|s| (s.destination.as_str(), s.channel.as_str(), s.job_count))
+            .collect();
+        assert_eq!(targets, vec![
+            ("EM", "Shout", 3),
+            ("ops@example.com", "Mail", 2),
+            ("dba@example.com", "Mail", 1),
+        ]);
+    }
+
+    #[test]
+    fn test_owner_and_run_as_groupings_and_filters() {
+        let mut folder = Folder::new("CHAIN".to_string(), FolderType::Simple);
+        for (name, owner, run_as) in [("A", Some("alice"), Some("svc_batch")), ("B", Some("alice"), None), ("C", None, Some("svc_batch"))] {
+            let mut job = chain_job(name, None);
+            job.owner = owner.map(String::from);
+            job.run_as = run_as.map(String::from);
+            folder.add_job(job);
+        }
+        let (_dir, repo) = create_test_repository(&[folder]);
+
+        let stats = repo.get_dashboard_stats(None, None).unwrap();
+        let owners: Vec<_> = stats.jobs_by_owner.iter().map(|s| (s.name.as_str(), s.count)).collect();
+        assert_eq!(owners, vec![("alice", 2), (UNSPECIFIED_IDENTITY, 1)]);
+        let run_as: Vec<_> = stats.jobs_by_run_as.iter().map(|s| (s.name.as_str(), s.count)).collect();
+        assert_eq!(run_as, vec![("svc_batch", 2), (UNSPECIFIED_IDENTITY, 1)]);
+
+        let response = repo.search_jobs(&JobSearchRequest {
+            owner: Some("alice".to_string()),
+            run_as: Some(UNSPECIFIED_IDENTITY.to_string()),
+            ..Default::default()
+        }).unwrap();
+        let names: Vec<_> = response.jobs.iter().map(|j| j.job_name.as_str()).collect();
+        assert_eq!(names, vec!["B"]);
+    }
+
+    #[test]
+    fn test_appl_type_aliases_count_as_file_transfer() {
+        let mut folder = Folder::new("CHAIN".to_string(), FolderType::Simple);
+        for (name, appl_type) in [("A", "FILE_TRANS"), ("B", "ft_v2"), ("C", "OS")] {
+            let mut job = chain_job(name, None);
+            job.appl_type = Some(appl_type.to_string());
+            folder.add_job(job);
+        }
+        let aliases: ApplTypeAliases = "FT_V2=file-transfer".parse().unwrap();
+        let (_dir, repo) = create_test_repository(&[folder]);
+        let repo = repo.with_appl_type_aliases(aliases);
+
+        let stats = repo.get_dashboard_stats(None, None).unwrap();
+        assert_eq!(stats.file_transfer_jobs, 2);
+    }
+
+    #[test]
+    fn test_wave_filter_returns_only_that_wave_paginated() {
+        let mut folder = Folder::new("CHAIN".to_string(), FolderType::Simple);
+        for name in ["A", "B", "C", "D", "E"] {
+            folder.add_job(chain_job(name, None));
+        }
+        let mut busy = chain_job("BUSY", None);
+        for upstream in ["A", "B", "C"] {
+            busy.in_conditions.push(Condition::new_in(format!("{}-ENDED-OK", upstream)));
+        }
+        folder.add_job(busy);
+        let (_dir, repo) = create_test_repository(&[folder]);
+
+        let request = |page| JobSearchRequest {
+            migration_wave: Some(1),
+            sort_by: Some("complexity_score".to_string()),
+            page: Some(page),
+            per_page: Some(2),
+            ..Default::default()
+        };
+        let first = repo.search_jobs(&request(1)).unwrap();
+        assert_eq!(first.total, 5);
+        assert_eq!(first.total_pages, 3);
+        assert_eq!(first.jobs.len(), 2);
+
+        let mut names: Vec<String> = (1..=3)
+            .flat_map(|page| repo.search_jobs(&request(page)).unwrap().jobs)
+            .map(|j| j.job_name)
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["A", "B", "C", "D", "E"]);
+    }
+
+    #[test]
+    fn test_job_detail_includes_cmdline_tokens() {
+        let mut folder = Folder::new("CHAIN".to_string(), FolderType::Simple);
+        folder.platform = Some("UNIX".to_string());
+        let mut job = chain_job("SCRIPT", None);
+        job.cmdline = Some(r#"/opt/run.sh "daily load" --force"#.to_string());
+        folder.add_job(job);
+
+        let tmp = tempfile::tempdir().unwrap();
+        let db_path = tmp.path().join("cmdline_tokens.db");
+        SqliteExporter::new(&db_path).unwrap()
+            .with_cmdline_tokens(true)
+            .export_folders(&[folder])
+            .unwrap();
+        let repo = JobRepository::new(db_path.to_str().unwrap()).unwrap();
+
+        let detail = repo.get_job_detail(job_id(&repo, "SCRIPT")).unwrap().unwrap();
+        let tokens = detail.cmdline_tokens.unwrap();
+        assert_eq!(tokens.program, "/opt/run.sh");
+        assert_eq!(tokens.args, vec!["daily load", "--force"]);
+    }
+
+    #[test]
+    fn test_job_detail_reads_back_out_condition_sign() {
+        let mut folder = Folder::new("CHAIN".to_string(), FolderType::Simple);
+        let mut job = chain_job("CLEANUP", Some("LOAD"));
+        let mut deleted = Condition::new_out("LOAD-ENDED-OK".to_string());
+        deleted.sign = Some("-".to_string());
+        job.out_conditions.push(deleted);
+        folder.add_job(job);
+        let (_dir, repo) = create_test_repository(&[folder]);
+
+        let detail = repo.get_job_detail(job_id(&repo, "CLEANUP")).unwrap().unwrap();
+        let signs: Vec<_> = detail.out_conditions.iter()
+            .map(|c| (c.condition_name.as_str(), c.sign.as_deref()))
+            .collect();
+        assert_eq!(signs, vec![("CLEANUP-ENDED-OK", None), ("LOAD-ENDED-OK", Some("-"))]);
+        assert!(detail.out_conditions.iter().all(|c| c.and_or.is_none()));
+    }
+
+    /// Counts events at INFO level or more severe
+    struct InfoEventCounter(Arc<AtomicUsize>);
+
+    impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for InfoEventCounter {
+        fn on_event(&self, event: &tracing::Event<'_>, _ctx: tracing_subscriber::layer::Context<'_, S>) {
+            if *event.metadata().level() <= tracing::Level::INFO {
+                self.0.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    #[test]
+    fn test_search_logs_single_summary_at_info() {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let mut folder = Folder::new("CHAIN".to_string(), FolderType::Simple);
+        folder.add_job(chain_job("A", None));
+        folder.add_job(chain_job("B", Some("A")));
+        let (_dir, repo) = create_test_repository(&[folder]);
+
+        let count = Arc::new(AtomicUsize::new(0));
+        let subscriber = tracing_subscriber::registry().with(InfoEventCounter(count.clone()));
+        tracing::subscriber::with_default(subscriber, || {
+            repo.search_jobs(&JobSearchRequest {
+                job_name: Some("A".to_string()),
+                folder_name: Some("CHAIN".to_string()),
+                min_dependencies: Some(0),
+                critical: Some(false),
+                ..Default::default()
+            }).unwrap();
+        });
+
+        assert!(count.load(Ordering::Relaxed) <= 2, "search logged {} info records", count.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn test_dashboard_stats_are_cached_until_invalidated() {
+        let mut folder = Folder::new("CHAIN".to_string(), FolderType::Simple);
+        folder.add_job(chain_job("A", None));
+        folder.add_job(chain_job("B", Some("A")));
+        let (_dir, repo) = create_test_repository(&[folder]);
+
+        let first = repo.get_dashboard_stats(None, None).unwrap();
+        let second = repo.get_dashboard_stats(None, None).unwrap();
+        assert_eq!(first.total_jobs, 2);
+        assert_eq!(second.total_jobs, 2);
+        assert_eq!(repo.stats_computations.load(Ordering::Relaxed), 1);
+
+        repo.invalidate_stats_cache();
+        repo.get_dashboard_stats(None, None).unwrap();
+        assert_eq!(repo.stats_computations.load(Ordering::Relaxed), 2);
+
+        repo.reload().unwrap();
+        repo.get_dashboard_stats(None, None).unwrap();
+        assert_eq!(repo.stats_computations.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn test_stats_cache_is_bounded() {
+        let mut folder = Folder::new("CHAIN".to_string(), FolderType::Simple);
+        folder.add_job(chain_job("A", None));
+        let (_dir, repo) = create_test_repository(&[folder]);
+
+        repo.get_dashboard_stats(None, None).unwrap();
+        for i in 0..MAX_STATS_CACHE_ENTRIES + 10 {
+            repo.get_dashboard_stats(None, Some(&format!("DC{}", i))).unwrap();
+        }
+        assert_eq!(repo.stats_cache.lock().unwrap().len(), MAX_STATS_CACHE_ENTRIES);
+
+        // The unfiltered stats were used least recently and have been evicted
+        let computations = repo.stats_computations.load(Ordering::Relaxed);
+        repo.get_dashboard_stats(None, None).unwrap();
+        assert_eq!(repo.stats_computations.load(Ordering::Relaxed), computations + 1);
+    }
+
+    #[test]
+    fn test_missing_and_empty_attributes_match_empty_filter() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<DEFTABLE>
+    <FOLDER FOLDER_NAME="MISSING" DATACENTER="DC1">
+        <JOB JOBNAME="JOB1"/>
+    </FOLDER>
+    <FOLDER FOLDER_NAME="EMPTY" DATACENTER="DC1" FOLDER_ORDER_METHOD="">
+        <JOB JOBNAME="JOB2"/>
+    </FOLDER>
+    <FOLDER FOLDER_NAME="SET" DATACENTER="DC1" FOLDER_ORDER_METHOD="SYSTEM">
+        <JOB JOBNAME="JOB3"/>
+    </FOLDER>
+</DEFTABLE>"#;
+        let folders = ControlMXmlParser::new().parse_xml(xml).unwrap();
+        assert_eq!(folders[0].folder_order_method, None);
+        assert_eq!(folders[1].folder_order_method, None);
+
+        let (_dir, repo) = create_test_repository(&folders);
+        // Databases exported before the normalization hold empty strings
+        repo.conn.lock().unwrap()
+            .execute("UPDATE folders SET folder_order_method = '' WHERE folder_name = 'EMPTY'", [])
+            .unwrap();
+        let request = JobSearchRequest {
+            folder_order_method: Some("(Empty)".to_string()),
+            ..Default::default()
+        };
+        let response = repo.search_jobs(&request).unwrap();
+
+        let mut names: Vec<_> = response.jobs.iter().map(|j| j.job_name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, vec!["JOB1", "JOB2"]);
+    }
+
+    #[test]
+    fn test_min_maxrerun_filters_auto_rerun_jobs() {
+        let mut folder = Folder::new("RERUN".to_string(), FolderType::Simple);
+        for (name, maxrerun) in [("NEVER", None), ("ZERO", Some(0)), ("ONCE", Some(1)), ("OFTEN", Some(5))] {
+            let mut job = chain_job(name, None);
+            job.maxrerun = maxrerun;
+            folder.add_job(job);
+        }
+        let (_dir, repo) = create_test_repository(&[folder]);
+
+        let names = |min_maxrerun| {
+            let mut names: Vec<_> = repo.search_jobs(&JobSearchRequest { min_maxrerun, ..Default::default() })
+                .unwrap().jobs.into_iter().map(|j| j.job_name).collect();
+            names.sort();
+            names
+        };
+
+        assert_eq!(names(Some(1)), vec!["OFTEN", "ONCE"]);
+        assert_eq!(names(Some(3)), vec!["OFTEN"]);
+        assert_eq!(names(None).len(), 4);
+    }
+
+    #[test]
+    fn test_critical_job_with_non_critical_ancestor_is_reported() {
+        // A (non-critical) -> B (critical)
+        let mut folder = Folder::new("CHAIN".to_string(), FolderType::Simple);
+        folder.add_job(chain_job("A", None));
+        let mut critical = chain_job("B", Some("A"));
+        critical.critical = true;
+        folder.add_job(critical);
+        let (_dir, repo) = create_test_repository(&[folder]);
+
+        let conflicts = repo.get_critical_path_conflicts(None).unwrap();
+
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].job_name, "B");
+        assert_eq!(conflicts[0].non_critical_ancestors.len(), 1);
+        assert_eq!(conflicts[0].non_critical_ancestors[0].job_name, "A");
+        assert_eq!(conflicts[0].non_critical_ancestors[0].depth, 1);
+    }
+
+    #[test]
+    fn test_lineage_includes_ancestors_and_descendants() {
+        // A -> B -> C -> D, lineage rooted at B
+        let mut folder = Folder::new("CHAIN".to_string(), FolderType::Simple);
+        folder.add_job(chain_job("A", None));
+        folder.add_job(chain_job("B", Some("A")));
+        folder.add_job(chain_job("C", Some("B")));
+        folder.add_job(chain_job("D", Some("C")));
+        let (_dir, repo) = create_test_repository(&[folder]);
+
+        let lineage = repo.get_job_lineage(job_id(&repo, "B"), None).unwrap();
+        let role_of = |name: &str| lineage.nodes.iter()
+            .find(|n| n.label == name)
+            .map(|n| n.role.clone());
+
+        assert_eq!(lineage.nodes.len(), 4);
+        assert_eq!(role_of("A").as_deref(), Some("ancestor"));
+        assert_eq!(role_of("B").as_deref(), Some("root"));
+        assert_eq!(role_of("C").as_deref(), Some("descendant"));
+        assert_eq!(role_of("D").as_deref(), Some("descendant"));
+    }
+
+    #[test]
+    fn test_database_without_job_dependencies_matches_conditions() {
+        let mut folder = Folder::new("CHAIN".to_string(), FolderType::Simple);
+        folder.add_job(chain_job("A", None));
+        folder.add_job(chain_job("B", Some("A")));
+        folder.add_job(chain_job("C", Some("B")));
+        let (_dir, repo) = create_test_repository(&[folder]);
+        repo.conn.lock().unwrap().execute_batch("DROP TABLE job_dependencies;").unwrap();
+
+        assert_eq!(repo.get_e2e_dependencies_count(job_id(&repo, "C")).unwrap(), 2);
+        let lineage = repo.get_job_lineage(job_id(&repo, "B"), None).unwrap();
+        assert_eq!(lineage.nodes.len(), 3);
+    }
+
+    #[test]
+    fn test_jobs_by_names_reports_missing_names() {
+        let mut folder = Folder::new("CHAIN".to_string(), FolderType::Simple);
+        folder.add_job(chain_job("EXTRACT", None));
+        folder.add_job(chain_job("LOAD", Some("EXTRACT")));
+        folder.add_job(chain_job("REPORT", Some("LOAD")));
+        let (_dir, repo) = create_test_repository(&[folder]);
+
+        let names = vec!["LOAD".to_string(), "RETIRED_JOB".to_string(), "EXTRACT".to_string()];
+        let result = repo.get_jobs_by_names(&names).unwrap();
+
+        let found: Vec<&str> = result.jobs.iter().map(|j| j.job_name.as_str()).collect();
+        assert_eq!(found, vec!["EXTRACT", "LOAD"]);
+        assert_eq!(result.missing, vec!["RETIRED_JOB".to_string()]);
+    }
+
+    #[test]
+    fn test_job_detail_returns_every_scheduling_row() {
+        let mut folder = Folder::new("CHAIN".to_string(), FolderType::Smart);
+        folder.add_job(chain_job("VARIANT", None));
+        folder.add_job(chain_job("EVENT_ONLY", None));
+        let (_dir, repo) = create_test_repository(&[folder]);
+        let variant_id = job_id(&repo, "VARIANT");
+        let event_only_id = job_id(&repo, "EVENT_ONLY");
+        {
+            let conn = repo.conn.lock().unwrap();
+            conn.execute("UPDATE job_scheduling SET time_from = '0600' WHERE job_id = ?", [variant_id]).unwrap();
+            conn.execute(
+                "INSERT INTO job_scheduling (job_id, time_from, days_calendar) VALUES (?, '1800', 'MONTH_END')",
+                [variant_id],
+            ).unwrap();
+            conn.execute("DELETE FROM job_scheduling WHERE job_id = ?", [event_only_id]).unwrap();
+        }
+
+        let detail = repo.get_job_detail(variant_id).unwrap().unwrap();
+        let times: Vec<_> = detail.scheduling.iter().map(|s| s.time_from.as_deref()).collect();
+        assert_eq!(times, vec![Some("0600"), Some("1800")]);
+        assert_eq!(detail.scheduling[1].days_calendar.as_deref(), Some("MONTH_END"));
+
+        let detail = repo.get_job_detail(event_only_id).unwrap().unwrap();
+        assert!(detail.scheduling.is_empty());
+    }
+
+    #[test]
+    fn test_search_text_ranks_matches_and_composes_with_filters() {
+        let mut folder = Folder::new("CHAIN".to_string(), FolderType::Simple);
+        let mut extract = chain_job("EXTRACT", None);
+        extract.application = Some("FIN".to_string());
+        extract.description = Some("Nightly ledger extract, ledger totals".to_string());
+        folder.add_job(extract);
+        let mut report = chain_job("REPORT", None);
+        report.application = Some("FIN".to_string());
+        report.cmdline = Some("/opt/bin/report.sh --ledger".to_string());
+        folder.add_job(report);
+        let mut other = chain_job("OTHER", None);
+        other.application = Some("HR".to_string());
+        other.description = Some("ledger archive".to_string());
+        folder.add_job(other);
+        let (_dir, repo) = create_test_repository(&[folder]);
+
+        let names = |request: JobSearchRequest| -> Vec<String> {
+            repo.search_jobs(&request).unwrap().jobs.into_iter().map(|j| j.job_name).collect()
+        };
+
+        let ranked = names(JobSearchRequest { search_text: Some("ledger".to_string()), ..Default::default() });
+        assert_eq!(ranked.len(), 3);
+        assert_eq!(ranked[0], "EXTRACT", "two mentions rank first");
+
+        let filtered = names(JobSearchRequest {
+            search_text: Some("ledg".to_string()),
+            application: Some("FIN".to_string()),
+            sort_by: Some("job_name".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(filtered, vec!["EXTRACT", "REPORT"]);
+
+        assert!(names(JobSearchRequest { search_text: Some("\"ledger OR".to_string()), ..Default::default() }).is_empty());
+    }
+
+    #[test]
+    fn test_dashboard_breaks_jobs_down_per_datacenter() {
+        let mut folders = Vec::new();
+        for (folder_name, datacenter, jobs) in [("EAST", Some("DC1"), 2), ("WEST", Some("DC2"), 1), ("LOCAL", None, 1)] {
+            let mut folder = Folder::new(folder_name.to_string(), FolderType::Simple);
+            folder.datacenter = datacenter.map(String::from);
+            for i in 0..jobs {
+                let mut job = Job::new(format!("{}_{}", folder_name, i), folder_name.to_string());
+                job.critical = i == 0;
+                folder.add_job(job);
+            }
+            folders.push(folder);
+        }
+        let (_dir, repo) = create_test_repository(&folders);
+
+        let stats = repo.get_dashboard_stats(None, None).unwrap();
+        let per_dc: Vec<_> = stats.jobs_by_datacenter.iter()
+            .map(|s| (s.datacenter.as_str(), s.job_count, s.critical_jobs, s.cyclic_jobs))
+            .collect();
+        assert_eq!(per_dc, vec![("DC1", 2, 1, 0), (UNSPECIFIED_IDENTITY, 1, 1, 0), ("DC2", 1, 1, 0)]);
+    }
+
+    #[test]
+    fn test_complexity_distribution_uses_report_thresholds() {
+        let mut folder = Folder::new("CHAIN".to_string(), FolderType::Simple);
+        folder.add_job(chain_job("SIMPLE", None));
+        let mut cyclic = chain_job("CYCLIC", Some("SIMPLE"));
+        cyclic.cyclic = true;
+        folder.add_job(cyclic);
+        let (_dir, repo) = create_test_repository(&[folder]);
+        {
+            let conn = repo.conn.lock().unwrap();
+            conn.execute("UPDATE job_analysis SET complexity_score = 61 WHERE job_id = (SELECT id FROM jobs WHERE job_name = 'CYCLIC')", []).unwrap();
+        }
+
+        let distribution = repo.get_complexity_distribution().unwrap();
+
+        assert_eq!(distribution, ComplexityDistribution { low: 1, medium: 0, high: 1 });
+        assert_eq!(repo.get_dashboard_stats(None, None).unwrap().complexity_distribution, distribution);
+    }
+
+    #[test]
+    fn test_job_name_match_modes() {
+        let mut folder = Folder::new("CHAIN".to_string(), FolderType::Simple);
+        for name in ["LOAD", "RELOAD_DATA", "LOAD_X", "LOADX", "load_lower"] {
+            folder.add_job(chain_job(name, None));
+        }
+        let (_dir, repo) = create_test_repository(&[folder]);
+        let names = |job_name: &str, match_mode: Option<MatchMode>, case_sensitive: Option<bool>| {
+            let mut names: Vec<_> = repo.search_jobs(&JobSearchRequest {
+                job_name: Some(job_name.to_string()),
+                match_mode,
+                case_sensitive,
+                ..Default::default()
+            }).unwrap().jobs.into_iter().map(|j| j.job_name).collect();
+            names.sort();
+            names
+        };
+
+        assert_eq!(names("LOAD", None, None).len(), 5);
+        assert_eq!(names("LOAD", None, Some(true)), vec!["LOAD", "LOADX", "LOAD_X", "RELOAD_DATA"]);
+        assert_eq!(names("LOAD", Some(MatchMode::Exact), None), vec!["LOAD"]);
+        assert_eq!(names("load", Some(MatchMode::Exact), Some(false)), vec!["LOAD"]);
+        assert_eq!(names("LOAD_", Some(MatchMode::StartsWith), None), vec!["LOAD_X", "load_lower"]);
+        assert_eq!(names("LOAD_", Some(MatchMode::StartsWith), Some(true)), vec!["LOAD_X"]);
+    }
+
+    #[test]
+    fn test_list_folders_counts_current_jobs() {
+        let mut etl = Folder::new("ETL".to_string(), FolderType::Simple);
+        etl.folder_order_method = Some("SYSTEM".to_string());
+        let etl_job = |name: &str| Job::new(name.to_string(), "ETL".to_string());
+        let mut critical = etl_job("EXTRACT");
+        critical.critical = true;
+        critical.cyclic = true;
+        etl.add_job(critical);
+        etl.add_job(etl_job("LOAD"));
+        let mut old = etl_job("LOAD_V1");
+        old.is_current_version = Some("N".to_string());
+        etl.add_job(old);
+        let empty = Folder::new("EMPTY".to_string(), FolderType::Simple);
+        let (_dir, repo) = create_test_repository(&[etl, empty]);
+
+        let folders = repo.list_folders().unwrap();
+
+        let counts: Vec<_> = folders.iter()
+            .map(|f| (f.folder_name.as_str(), f.job_count, f.critical_count, f.cyclic_count))
+            .collect();
+        assert_eq!(counts, vec![("EMPTY", 0, 0, 0), ("ETL", 2, 1, 1)]);
+        assert_eq!(folders[1].folder_order_method.as_deref(), Some("SYSTEM"));
+    }
+
+    #[test]
+    fn test_file_transfers_filtered_by_direction() {
+        use crate::domain::value_objects::FileTransferSpec;
+
+        let mut folder = Folder::new("ETL".to_string(), FolderType::Simple);
+        let mut send = Job::new("SEND".to_string(), "ETL".to_string());
+        send.file_transfers = vec![FileTransferSpec {
+            direction: TransferDirection::Upload,
+            path: "/data/out/daily.csv".to_string(),
+            remote_path: Some("/in/daily.csv".to_string()),
+            host: Some("partner".to_string()),
+        }];
+        let mut wait = Job::new("WAIT".to_string(), "ETL".to_string());
+        wait.file_transfers = vec![FileTransferSpec {
+            direction: TransferDirection::Watch,
+            path: "/landing/trigger.ok".to_string(),
+            remote_path: None,
+            host: None,
+        }];
+        folder.add_job(send);
+        folder.add_job(wait);
+        folder.add_job(Job::new("PLAIN".to_string(), "ETL".to_string()));
+        let (_dir, repo) = create_test_repository(&[folder]);
+
+        let all = repo.get_file_transfers(None).unwrap();
+        assert_eq!(all.iter().map(|t| t.job_name.as_str()).collect::<Vec<_>>(), vec!["SEND", "WAIT"]);
+
+        let uploads = repo.get_file_transfers(Some(TransferDirection::Upload)).unwrap();
+        assert_eq!(uploads.len(), 1);
+        assert_eq!((uploads[0].direction.as_str(), uploads[0].host.as_deref()), ("upload", Some("partner")));
+    }
+}
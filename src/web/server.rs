@@ -8,6 +8,7 @@ use actix_cors::Cors;
 use actix_files as fs;
 use actix_web_httpauth::middleware::HttpAuthentication;
 use std::sync::Arc;
+use std::time::Duration;
 use tracing::info;
 
 use crate::web::{handlers, auth, config::WebConfig, repository::JobRepository};
@@ -18,7 +19,7 @@ use crate::web::{handlers, auth, config::WebConfig, repository::JobRepository};
 /// - CORS support for cross-origin requests
 /// - JWT authentication middleware
 /// - Login attempt tracking and rate limiting
-/// - Environment-based authentication (.env credentials)
+/// - Local authentication from a users file or .env credentials
 /// - API routes for job management and authentication
 /// - Static file serving for the web UI
 ///
@@ -30,39 +31,53 @@ use crate::web::{handlers, auth, config::WebConfig, repository::JobRepository};
 ///
 /// Result indicating success or IO error
 pub async fn start_web_server(config: WebConfig) -> std::io::Result<()> {
-    info!("Starting web server on {}:{}", config.host, config.port);
+    info!("Starting web server on {}:{} with {} workers", config.host, config.port, config.workers);
     info!("Database: {}", config.database_path);
     
     // Initialize shared application state
     
     // Database repository for job data
-    let repository = Arc::new(
+    let repository = if config.read_only {
+        JobRepository::open_read_only(&config.database_path)
+    } else {
         JobRepository::new(&config.database_path)
+    };
+    let repository = Arc::new(
+        repository
             .expect("Failed to open database")
+            .with_stats_cache_ttl(Duration::from_secs(config.dashboard_cache_ttl_secs))
+            .with_appl_type_aliases(config.appl_type_aliases.clone())
     );
     
-    // User store with credentials from .env configuration
-    // Validates login attempts against AUTH_USERNAME and AUTH_PASSWORD
+    // User store with credentials from USERS_FILE, or the single
+    // AUTH_USERNAME/AUTH_PASSWORD pair from .env when it is not set
     let user_store = Arc::new(
-        auth::UserStore::new(
-            config.auth_username.clone(),
-            config.auth_password.clone()
-        )
+        auth::UserStore::from_config(&config)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, format!("{:#}", e)))?
     );
+    info!("Local login enabled for {} user(s)", user_store.len());
     
     // Login attempt tracker for rate limiting and account lockout
     // Configured with MAX_LOGIN_ATTEMPTS and LOCKOUT_DURATION_MINUTES from .env
-    let login_tracker = Arc::new(
-        auth::LoginAttemptTracker::new(
-            config.max_login_attempts,
-            config.lockout_duration_minutes
-        )
+    let mut login_tracker = auth::LoginAttemptTracker::new(
+        config.max_login_attempts,
+        config.lockout_duration_minutes
     );
+    if config.persist_login_attempts {
+        let path = config.login_attempts_path();
+        login_tracker = login_tracker.with_persistence(&path)
+            .map_err(|e| std::io::Error::other(format!("{:#}", e)))?;
+        info!("Persisting login attempts to {}", path.display());
+    }
+    let login_tracker = Arc::new(login_tracker);
     
     let config_data = web::Data::new(config.clone());
     let repository_data = web::Data::new(repository);
     let user_store_data = web::Data::new(user_store);
     let login_tracker_data = web::Data::new(login_tracker);
+    if config.analysis_only {
+        info!("Analysis-only mode: raw job data is restricted to admins");
+    }
     
     let server = HttpServer::new(move || {
         // Configure CORS to allow requests from any origin
@@ -90,28 +105,20 @@ pub async fn start_web_server(config: WebConfig) -> std::io::Result<()> {
                 web::scope("/api")
                     // Public routes (no authentication required)
                     .route("/health", web::get().to(handlers::health_check))
+                    .route("/openapi.json", web::get().to(handlers::openapi_spec))
                     .route("/auth/login", web::post().to(handlers::login))
                     .route("/auth/entra-callback", web::post().to(handlers::entra_id_callback))
                     // Protected routes (authentication required)
                     .service(
                         web::scope("")
                             .wrap(auth_middleware)
-                            .route("/auth/me", web::get().to(handlers::get_current_user))
-                            .route("/jobs/search", web::post().to(handlers::search_jobs))
-                            .route("/jobs/{id}", web::get().to(handlers::get_job_detail))
-                            .route("/jobs/{id}/dependencies", web::get().to(handlers::get_dependency_graph))
-                            .route("/jobs/{id}/graph", web::get().to(handlers::get_job_graph))
-                            .route("/jobs/{id}/graph/end-to-end", web::get().to(handlers::get_job_graph_end_to_end))
-                            .route("/jobs/export", web::post().to(handlers::export_jobs_csv))
-                            .route("/dashboard/stats", web::get().to(handlers::get_dashboard_stats))
-                            .route("/dashboard/root-jobs", web::get().to(handlers::get_top_root_jobs))
-                            .route("/wave-migration/analysis", web::get().to(handlers::get_wave_migration_analysis))
-                            .route("/filters", web::get().to(handlers::get_filter_options))
+                            .configure(configure_protected_routes)
                     )
             )
             // Serve static files (web UI)
             .service(fs::Files::new("/", "./static").index_file("index.html"))
     })
+    .workers(config.workers)
     .bind((config.host.as_str(), config.port))?;
     
     info!("Web server started successfully");
@@ -119,3 +126,53 @@ pub async fn start_web_server(config: WebConfig) -> std::io::Result<()> {
     
     server.run().await
 }
+
+/// Registers the routes that require authentication
+///
+/// All endpoints are always registered. In analysis-only mode (read from
+/// the `WebConfig` app data) the endpoints returning raw job data (search,
+/// job detail, name lookup, CSV export, file transfers and Airflow export)
+/// answer non-admin users with HTTP 403.
+///
+/// # Arguments
+///
+/// * `cfg` - Service config of the authenticated scope
+pub fn configure_protected_routes(cfg: &mut web::ServiceConfig) {
+    cfg
+        .route("/auth/me", web::get().to(handlers::get_current_user))
+        .route("/auth/refresh", web::post().to(handlers::refresh_token))
+        .route("/folders", web::get().to(handlers::list_folders))
+        .route("/dashboard/stats", web::get().to(handlers::get_dashboard_stats))
+        .route("/stats/datacenter", web::get().to(handlers::get_datacenter_stats))
+        .route("/dashboard/root-jobs", web::get().to(handlers::get_top_root_jobs))
+        .route("/wave-migration/analysis", web::get().to(handlers::get_wave_migration_analysis))
+        .route("/analysis/critical-conflicts", web::get().to(handlers::get_critical_path_conflicts))
+        .route("/analysis/profile", web::get().to(handlers::get_analysis_profile))
+        .route("/filters", web::get().to(handlers::get_filter_options))
+        .route("/meta/distinct", web::get().to(handlers::get_distinct_values))
+        .route("/admin/reload", web::post().to(handlers::reload_database));
+    raw_job_data_routes(cfg);
+}
+
+/// Registers the endpoints that expose raw job data such as command lines,
+/// run-as accounts and job-level dependency graphs
+///
+/// # Arguments
+///
+/// * `cfg` - Service config to register the routes on
+fn raw_job_data_routes(cfg: &mut web::ServiceConfig) {
+    cfg
+        .route("/jobs/search", web::post().to(handlers::search_jobs))
+        .route("/jobs/by-names", web::post().to(handlers::get_jobs_by_names))
+        // Registered before /jobs/{id}, which would otherwise match them
+        .route("/jobs/file-transfers", web::get().to(handlers::get_file_transfers))
+        .route("/jobs/export", web::post().to(handlers::export_jobs_csv))
+        .route("/jobs/{id}", web::get().to(handlers::get_job_detail))
+        .route("/jobs/{id}/dependencies", web::get().to(handlers::get_dependency_graph))
+        .route("/jobs/{id}/graph", web::get().to(handlers::get_job_graph))
+        .route("/jobs/{id}/graph/end-to-end", web::get().to(handlers::get_job_graph_end_to_end))
+        .route("/jobs/{id}/lineage", web::get().to(handlers::get_job_lineage))
+        .route("/graph/applications", web::post().to(handlers::get_applications_graph))
+        .route("/folders/{name}/graph", web::get().to(handlers::get_folder_graph))
+        .route("/export/airflow", web::get().to(handlers::export_airflow_dags));
+}
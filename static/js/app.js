@@ -578,7 +578,7 @@ async function loadDashboard(folderFilter = '', datacenterFilter = '') {
     console.log('📊 [DASHBOARD] Loading dashboard statistics...');
     
     // Show loading state for charts
-    const chartContainers = ['chart-appl-types', 'chart-task-types', 'chart-applications', 'chart-folders'];
+    const chartContainers = ['chart-appl-types', 'chart-task-types', 'chart-applications', 'chart-folders', 'chart-connected-jobs'];
     chartContainers.forEach(id => {
         const container = document.getElementById(id);
         if (container) {
@@ -641,6 +641,8 @@ async function loadDashboard(folderFilter = '', datacenterFilter = '') {
             renderBarChart('chart-task-types', stats.jobs_by_task_type, 'task_type', 'count');
             renderBarChart('chart-applications', stats.jobs_by_application, 'application', 'count');
             renderBarChart('chart-folders', stats.jobs_by_folder, 'folder_name', 'job_count');
+            renderBarChart('chart-connected-jobs', stats.most_connected_jobs, 'job_name', 'total_dependencies_e2e');
+            renderBarChart('chart-notification-targets', stats.jobs_by_notification_target || [], 'destination', 'job_count');
             
             // Make charts clickable for detailed view
             if (typeof makeChartsClickable === 'function') {
@@ -134,22 +134,29 @@ function populateBasicTab(data) {
 // Populate Scheduling Tab
 function populateSchedulingTab(data) {
     const job = data.job;
-    const scheduling = data.scheduling || {};
-    
-    const timeHtml = `
+    // Event-only jobs have no scheduling rows; scheduling variants have several
+    const schedulings = data.scheduling && data.scheduling.length ? data.scheduling : [{}];
+    const setLabel = (index) => schedulings.length > 1
+        ? createDetailItem('Scheduling Set', `${index + 1} of ${schedulings.length}`)
+        : '';
+    
+    const timeHtml = schedulings.map((scheduling, index) => `
+        ${setLabel(index)}
         ${createDetailItem('Time From', scheduling.time_from)}
         ${createDetailItem('Time To', scheduling.time_to)}
-    `;
+    `).join('');
     
     const daysHtml = `
         ${createDetailItem('Days', job.days)}
         ${createDetailItem('Weekdays', job.weekdays)}
         ${createDetailItem('Date', job.date)}
         ${createDetailItem('Days AND/OR', job.days_and_or)}
+    ` + schedulings.map((scheduling, index) => `
+        ${setLabel(index)}
         ${createDetailItem('Days Calendar', scheduling.days_calendar)}
         ${createDetailItem('Weeks Calendar', scheduling.weeks_calendar)}
         ${createDetailItem('Conf Calendar', scheduling.conf_calendar)}
-    `;
+    `).join('');
     
     const monthsHtml = `
         ${createDetailItem('January', job.jan === '1' ? 'Yes' : 'No')}
@@ -286,7 +293,7 @@ function populateDependenciesTab(data) {
                         <tr>
                             <td><strong>${escapeHtml(c.condition_name)}</strong></td>
                             <td>${escapeHtml(c.odate || '-')}</td>
-                            <td>${escapeHtml(c.and_or || '-')}</td>
+                            <td>${escapeHtml(c.sign || '-')}</td>
                         </tr>
                     `).join('')}
                 </tbody>